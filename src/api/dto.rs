@@ -0,0 +1,383 @@
+//! Request and response bodies of the HTTP API.
+//!
+//! The handlers in [`crate::api`] and the typed `client::RustyMemClient` (feature `http-client`)
+//! share these types, so both sides of the wire agree on field names and optionality.
+
+use crate::metrics::DedupeSnapshot;
+use crate::processing::{IngestProfile, SearchHit, SummarizeOutcome};
+use crate::qdrant::{Citation, MemoryLink};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Request body for the `POST /index` endpoint.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IndexRequest {
+    /// Raw document contents to chunk and index.
+    pub text: String,
+    /// Optional collection override (defaults to `QDRANT_COLLECTION_NAME`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collection: Option<String>,
+    /// Optional project identifier persisted with each chunk (defaults to `"default"`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    /// Optional memory classification (`episodic` | `semantic` | `procedural`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_type: Option<String>,
+    /// Optional tag list applied to each chunk.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    /// Optional source URI (file path or URL) for traceability.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_uri: Option<String>,
+    /// Optional RFC3339 timestamp recorded instead of the ingest time (for backdated notes).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<String>,
+    /// Accept a `timestamp` further in the future than the allowed clock skew.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub allow_future: bool,
+}
+
+/// Success response for the `POST /index` endpoint.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexResponse {
+    /// Number of chunks produced for the provided document.
+    pub chunks_indexed: usize,
+    /// Effective chunk size used for this ingestion.
+    pub chunk_size: usize,
+    /// Number of new vectors inserted into the collection.
+    pub inserted: usize,
+    /// Number of existing vectors updated in place (typically 0 with UUID ids).
+    pub updated: usize,
+    /// Number of duplicate chunks skipped within this request.
+    pub skipped_duplicates: usize,
+    /// Number of chunks skipped for scoring below `CHUNK_QUALITY_MIN`.
+    pub skipped_low_quality: usize,
+    /// True when the source was unchanged since its last sync and nothing was indexed.
+    #[serde(default)]
+    pub skipped_unchanged: bool,
+    /// Share of the document's chunks skipped as duplicates or because the source was unchanged.
+    #[serde(default)]
+    pub duplicate_ratio: f64,
+    /// True when chunks were embedded with the deterministic fallback encoder.
+    pub degraded: bool,
+    /// Chunking and embedding settings applied, for reproducing the ingestion elsewhere.
+    pub ingest_profile: IngestProfileResponse,
+    /// Identifier shared by the document's chunks; absent when nothing was written.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc_id: Option<String>,
+}
+
+/// Ingest settings plus the `profile_hash` stored on each point.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IngestProfileResponse {
+    /// Embedding backend (`ollama` or `openai`).
+    pub embedding_provider: String,
+    /// Embedding model identifier.
+    pub embedding_model: String,
+    /// Vector dimension.
+    pub embedding_dimension: usize,
+    /// Token budget per chunk.
+    pub chunk_size: usize,
+    /// Tokens shared between adjacent chunks.
+    pub chunk_overlap: usize,
+    /// Splitting strategy.
+    pub strategy: String,
+    /// Encoding used to count tokens.
+    pub tokenizer: String,
+    /// How duplicate chunks are detected.
+    pub dedupe: String,
+    /// Version of the server that performed the ingestion.
+    pub version: String,
+    /// Fingerprint of the settings above.
+    pub profile_hash: String,
+}
+
+impl From<IngestProfile> for IngestProfileResponse {
+    fn from(profile: IngestProfile) -> Self {
+        Self {
+            profile_hash: profile.hash(),
+            embedding_provider: profile.embedding_provider,
+            embedding_model: profile.embedding_model,
+            embedding_dimension: profile.embedding_dimension,
+            chunk_size: profile.chunk_size,
+            chunk_overlap: profile.chunk_overlap,
+            strategy: profile.strategy.into(),
+            tokenizer: profile.tokenizer.into(),
+            dedupe: profile.dedupe.into(),
+            version: profile.version.into(),
+        }
+    }
+}
+
+/// Response body for `GET /collections`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CollectionsResponse {
+    /// Collection names.
+    pub collections: Vec<String>,
+}
+
+/// Request body for the `POST /search` endpoint.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SearchBody {
+    /// Natural language query text to embed.
+    pub query_text: String,
+    /// Optional collection override (defaults to `QDRANT_COLLECTION_NAME`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collection: Option<String>,
+    /// Optional `project_id` filter.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    /// Optional memory type filter.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_type: Option<String>,
+    /// Optional contains-any tag filter.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    /// Optional result limit (clamped to `SEARCH_MAX_LIMIT`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+    /// Optional minimum similarity score.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score_threshold: Option<f32>,
+}
+
+/// Response body for `POST /search`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchResponse {
+    /// Collection that was searched.
+    pub collection: String,
+    /// Hits ordered by descending score.
+    pub results: Vec<SearchResult>,
+    /// Whether `query_text` was cut to fit `MAX_QUERY_TOKENS` before embedding.
+    #[serde(default)]
+    pub query_truncated: bool,
+    /// Query actually embedded, present when it differs from `query_text`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effective_query: Option<String>,
+    /// Whether trailing results were dropped to fit `MAX_RESPONSE_BYTES`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
+    /// Number of results dropped to fit `MAX_RESPONSE_BYTES`, present when `truncated`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dropped_count: Option<usize>,
+    /// Monthly partitions read alongside `collection` under `EPISODIC_PARTITIONING`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partitions_searched: Option<Vec<String>>,
+}
+
+/// Single search hit with its stored payload fields.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchResult {
+    /// Point identifier.
+    pub id: String,
+    /// Similarity score.
+    pub score: f32,
+    /// Stored chunk text.
+    pub text: Option<String>,
+    /// Project the memory belongs to.
+    pub project_id: Option<String>,
+    /// Memory classification.
+    pub memory_type: Option<String>,
+    /// Stored tags.
+    pub tags: Option<Vec<String>>,
+    /// RFC3339 timestamp of the memory.
+    pub timestamp: Option<String>,
+    /// Source URI recorded at ingest.
+    pub source_uri: Option<String>,
+    /// Document the chunk was pushed with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc_id: Option<String>,
+    /// Position of the chunk within its document.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunk_index: Option<u64>,
+    /// Resolved `[n]` markers when the hit is a stored summary.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub citations: Option<Vec<Citation>>,
+    /// Outbound links recorded with `link-memories`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub links: Option<Vec<MemoryLink>>,
+}
+
+impl From<SearchHit> for SearchResult {
+    fn from(hit: SearchHit) -> Self {
+        Self {
+            id: hit.id,
+            score: hit.score,
+            text: hit.text,
+            project_id: hit.project_id,
+            memory_type: hit.memory_type,
+            tags: hit.tags,
+            timestamp: hit.timestamp,
+            source_uri: hit.source_uri,
+            doc_id: hit.doc_id,
+            chunk_index: hit.chunk_index,
+            citations: hit.citations,
+            links: hit.links,
+        }
+    }
+}
+
+/// Response body for `GET /metrics`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricsResponse {
+    /// Documents indexed since startup.
+    pub documents_indexed: u64,
+    /// Chunks indexed since startup.
+    pub chunks_indexed: u64,
+    /// Chunk size of the most recent ingestion.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_chunk_size: Option<u64>,
+    /// Chunks skipped as exact duplicates or unchanged sources, and the embeddings saved.
+    #[serde(default)]
+    pub dedupe: DedupeSnapshot,
+    /// Embedding responses rejected for NaN, infinite, or all-zero vectors.
+    #[serde(default)]
+    pub invalid_embeddings: u64,
+}
+
+/// Request body for the `POST /summarize` endpoint.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SummarizeBody {
+    /// Window to summarize.
+    pub time_range: SummarizeTimeRange,
+    /// Project whose memories are summarized (defaults to `"default"`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    /// Memory type to summarize (defaults to `episodic`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_type: Option<String>,
+    /// Optional contains-any tag filter.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    /// Maximum number of source memories.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+    /// `auto`, `abstractive`, or `extractive` (defaults to `auto`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strategy: Option<String>,
+    /// Summarization provider override.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    /// Summarization model override.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Word budget for the summary.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_words: Option<usize>,
+    /// Optional collection override (defaults to `QDRANT_COLLECTION_NAME`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collection: Option<String>,
+    /// Label stored on the summary.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Topic stored on the summary.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub topic: Option<String>,
+}
+
+/// Inclusive summarization window; RFC3339 timestamps or `YYYY-MM-DD` dates.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SummarizeTimeRange {
+    /// Inclusive start.
+    pub start: String,
+    /// Inclusive end.
+    pub end: String,
+}
+
+/// Response body for `POST /summarize`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SummarizeResponse {
+    /// Summary text, with `[n]` markers when abstractive.
+    pub summary: String,
+    /// Memories the summary was built from.
+    pub source_memory_ids: Vec<String>,
+    /// Resolved `[n]` markers.
+    pub citations: Vec<Citation>,
+    /// Identifier of the stored summary memory.
+    pub upserted_memory_id: String,
+    /// Strategy that produced the summary.
+    pub strategy_used: String,
+    /// Provider used for an abstractive summary.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    /// Model used for an abstractive summary.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Source memories the summary actually covers.
+    pub items_summarized: usize,
+    /// Oldest memories dropped to fit the prompt token budget.
+    #[serde(default)]
+    pub items_trimmed: usize,
+    /// Label stored on the summary.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Topic stored on the summary.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub topic: Option<String>,
+}
+
+impl From<SummarizeOutcome> for SummarizeResponse {
+    fn from(outcome: SummarizeOutcome) -> Self {
+        Self {
+            summary: outcome.summary,
+            source_memory_ids: outcome.source_memory_ids,
+            citations: outcome.citations,
+            upserted_memory_id: outcome.upserted_memory_id,
+            strategy_used: outcome.strategy_used,
+            provider: outcome.provider,
+            model: outcome.model,
+            items_summarized: outcome.items_summarized,
+            items_trimmed: outcome.items_trimmed,
+            label: outcome.label,
+            topic: outcome.topic,
+        }
+    }
+}
+
+/// Body returned by every failing endpoint.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ErrorResponse {
+    /// The failure.
+    pub error: ErrorBody,
+}
+
+/// Code, message, and optional structured context of a failure.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ErrorBody {
+    /// Stable machine-readable code.
+    pub code: ErrorCode,
+    /// Human-readable description.
+    pub message: String,
+    /// Extra context; classified failures carry `kind` and `retryable` like MCP error data.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<Value>,
+}
+
+/// Stable error codes of the HTTP API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// The request was malformed or failed validation (`400`).
+    InvalidParams,
+    /// Qdrant reported the collection missing (`404`).
+    CollectionNotFound,
+    /// The embedding provider or Qdrant was unreachable or overloaded (`503`); retry later.
+    ProviderUnavailable,
+    /// The server refused the request because it is saturated (`503`); retry later.
+    ServerBusy,
+    /// Configuration or internal failure (`500`).
+    Internal,
+}
+
+impl ErrorCode {
+    /// Wire form of the code, as serialized in the envelope.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::InvalidParams => "invalid_params",
+            Self::CollectionNotFound => "collection_not_found",
+            Self::ProviderUnavailable => "provider_unavailable",
+            Self::ServerBusy => "server_busy",
+            Self::Internal => "internal",
+        }
+    }
+}
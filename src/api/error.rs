@@ -0,0 +1,186 @@
+//! Shared error mapping for the HTTP API.
+//!
+//! Every failing handler returns an [`AppError`], rendered as
+//! `{ "error": { "code", "message", "details"? } }` with a stable [`ErrorCode`]. Classified
+//! pipeline failures mirror the MCP error data: `details` carries the same `kind` and `retryable`
+//! fields. Requests the MCP tools reject up front, such as a missing `project_id` under
+//! `REQUIRE_PROJECT_FILTER`, carry the MCP error data as `details` unchanged.
+
+use super::dto::{ErrorBody, ErrorCode, ErrorResponse};
+use crate::processing::{
+    ErrorKind, ProcessingError, SearchError, SearchValidationError, SummarizeError,
+};
+use crate::qdrant::QdrantError;
+use axum::{
+    Json,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use rmcp::{ErrorData as McpError, model::ErrorCode as McpErrorCode};
+use serde_json::{Value, json};
+
+/// Failure returned by an HTTP handler.
+pub(super) enum AppError {
+    Processing(ProcessingError),
+    Search(SearchError),
+    Summarize(SummarizeError),
+    BadRequest(String),
+    Mcp(McpError),
+}
+
+impl AppError {
+    /// Status code and body for this failure.
+    fn envelope(&self) -> (StatusCode, ErrorBody) {
+        let (kind, qdrant, details) = match self {
+            Self::BadRequest(message) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    ErrorBody {
+                        code: ErrorCode::InvalidParams,
+                        message: message.clone(),
+                        details: None,
+                    },
+                );
+            }
+            Self::Mcp(inner) => {
+                let (status, code) = if inner.code == McpErrorCode::INVALID_PARAMS {
+                    (StatusCode::BAD_REQUEST, ErrorCode::InvalidParams)
+                } else {
+                    (StatusCode::INTERNAL_SERVER_ERROR, ErrorCode::Internal)
+                };
+                return (
+                    status,
+                    ErrorBody {
+                        code,
+                        message: inner.message.to_string(),
+                        details: inner.data.clone(),
+                    },
+                );
+            }
+            Self::Processing(inner) => (
+                inner.kind(),
+                match inner {
+                    ProcessingError::Qdrant(source) => Some(source),
+                    _ => None,
+                },
+                None,
+            ),
+            Self::Search(inner) => (
+                inner.kind(),
+                match inner {
+                    SearchError::Qdrant(source) => Some(source),
+                    _ => None,
+                },
+                match inner {
+                    SearchError::DimensionMismatch { expected, actual } => {
+                        Some(json!({ "expected": expected, "actual": actual }))
+                    }
+                    _ => None,
+                },
+            ),
+            Self::Summarize(inner) => (
+                inner.kind(),
+                match inner {
+                    SummarizeError::Qdrant(source) => Some(source),
+                    _ => None,
+                },
+                None,
+            ),
+        };
+
+        let (status, code) = if qdrant.and_then(qdrant_status) == Some(StatusCode::NOT_FOUND) {
+            (StatusCode::NOT_FOUND, ErrorCode::CollectionNotFound)
+        } else {
+            (status_for(kind), code_for(kind))
+        };
+        let mut details = details.unwrap_or_else(|| json!({}));
+        details["kind"] = Value::String(kind.as_str().into());
+        details["retryable"] = Value::Bool(kind.is_retryable());
+        (
+            status,
+            ErrorBody {
+                code,
+                message: self.to_string(),
+                details: Some(details),
+            },
+        )
+    }
+}
+
+impl std::fmt::Display for AppError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Processing(inner) => inner.fmt(f),
+            Self::Search(inner) => inner.fmt(f),
+            Self::Summarize(inner) => inner.fmt(f),
+            Self::BadRequest(message) => f.write_str(message),
+            Self::Mcp(inner) => f.write_str(&inner.message),
+        }
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        let (status, error) = self.envelope();
+        tracing::Span::current().record("error.code", error.code.as_str());
+        (status, Json(ErrorResponse { error })).into_response()
+    }
+}
+
+/// HTTP status for a classified pipeline failure; transient failures become `503` so clients retry.
+fn status_for(kind: ErrorKind) -> StatusCode {
+    match kind {
+        ErrorKind::Transient | ErrorKind::ServerBusy => StatusCode::SERVICE_UNAVAILABLE,
+        ErrorKind::InvalidRequest => StatusCode::BAD_REQUEST,
+        ErrorKind::Configuration | ErrorKind::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+/// Envelope code for a classified pipeline failure.
+fn code_for(kind: ErrorKind) -> ErrorCode {
+    match kind {
+        ErrorKind::Transient => ErrorCode::ProviderUnavailable,
+        ErrorKind::ServerBusy => ErrorCode::ServerBusy,
+        ErrorKind::InvalidRequest => ErrorCode::InvalidParams,
+        ErrorKind::Configuration | ErrorKind::Internal => ErrorCode::Internal,
+    }
+}
+
+/// Status Qdrant answered with, when the failure carries one.
+fn qdrant_status(error: &QdrantError) -> Option<StatusCode> {
+    match error {
+        QdrantError::UnexpectedStatus { status, .. } => Some(*status),
+        QdrantError::Http(source) => source.status(),
+        QdrantError::InvalidUrl(_) => None,
+    }
+}
+
+impl From<ProcessingError> for AppError {
+    fn from(inner: ProcessingError) -> Self {
+        Self::Processing(inner)
+    }
+}
+
+impl From<SearchError> for AppError {
+    fn from(inner: SearchError) -> Self {
+        Self::Search(inner)
+    }
+}
+
+impl From<SearchValidationError> for AppError {
+    fn from(inner: SearchValidationError) -> Self {
+        Self::BadRequest(inner.to_string())
+    }
+}
+
+impl From<McpError> for AppError {
+    fn from(inner: McpError) -> Self {
+        Self::Mcp(inner)
+    }
+}
+
+impl From<SummarizeError> for AppError {
+    fn from(inner: SummarizeError) -> Self {
+        Self::Summarize(inner)
+    }
+}
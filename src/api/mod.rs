@@ -0,0 +1,2158 @@
+//! HTTP surface for Rusty Memory.
+//!
+//! This module exposes a compact Axum router with a handful of endpoints:
+//!
+//! - `POST /index` – Chunk a raw document, generate embeddings, and persist them in Qdrant.
+//!   Accepts optional metadata (`collection`, `project_id`, `memory_type`, `tags`, `source_uri`) and
+//!   returns indexing counters (`chunks_indexed`, `chunk_size`, `inserted`, `updated`, `skipped_duplicates`, `skipped_low_quality`, `skipped_unchanged`, `duplicate_ratio`)
+//!   plus `degraded` when fallback vectors were written.
+//! - `GET /collections` – List Qdrant collections managed by this server.
+//! - `POST /collections` – Create or resize a collection (idempotent).
+//! - `POST /collections/:name/import` – Stream newline-delimited memory records into a collection,
+//!   reusing supplied vectors and re-embedding the rest; returns counts and per-line errors. With
+//!   `?format=chroma` or `?format=mem0` the body is another server's JSON export instead, and the
+//!   response reports an outcome per record.
+//! - `GET /metrics` – Observe ingestion counters and the last chunk size used.
+//! - `GET /commands` – Machine-readable command catalog for quick discovery by tools/hosts.
+//! - `GET /projects` – List distinct project identifiers stored in a collection.
+//! - `GET /tags` – List distinct tags stored in a collection, optionally scoped by project.
+//! - `GET /storage-stats` – Approximate disk usage per collection (all of them unless `collection`
+//!   is given), optionally restricted to one `project_id`; the body matches the `storage-stats` tool.
+//! - `POST /search` – Semantic search over stored memories with optional payload filters.
+//! - `POST /summarize` – Summarize memories within a time range and store the summary.
+//! - `GET /ui` – Embedded single-page browser for searching and inspecting memories.
+//!
+//! Responses are gzip/brotli compressed when the client's `Accept-Encoding` allows it. The
+//! read-only listings (`GET /collections`, `/metrics`, `/projects`) carry an `ETag` derived from
+//! the response body and answer a matching `If-None-Match` with `304 Not Modified`, so pollers
+//! skip unchanged payloads.
+//!
+//! Failures use one JSON envelope, `{ "error": { "code", "message", "details"? } }`, with the stable
+//! codes `invalid_params` (400), `collection_not_found` (404), `provider_unavailable` (503), and
+//! `internal` (500).
+//!
+//! The HTTP surface shares the same processing pipeline with the MCP server, so behavior is
+//! identical across interfaces. Request and response bodies live in [`dto`].
+
+pub mod dto;
+mod error;
+
+use crate::config::get_config;
+use crate::mcp::handlers::require_project;
+use crate::processing::{
+    ImportSummary, IngestMetadata, NdjsonImport, ProcessingApi, SearchLimits, SearchOutcome,
+    SearchRequest, SearchRequestBuilder, SearchTimeRange, StorageStatsReport, SummarizeRequest,
+    SummarizeStrategy,
+    import::{
+        chroma,
+        foreign::{
+            FieldMapping, ForeignFormat, ForeignImportSummary, MAX_FOREIGN_IMPORT_BYTES,
+            import_foreign,
+        },
+        mem0,
+    },
+    sanitize::{
+        TimeBoundary, format_utc_rfc3339, parse_time_boundary, sanitize_string,
+        validate_ingest_timestamp,
+    },
+};
+use crate::qdrant::{CollectionTopology, validate_collection_name};
+use crate::response_limit::fit_response;
+use axum::{
+    Json, Router,
+    body::{Body, to_bytes},
+    extract::{MatchedPath, Path, Query, Request, State},
+    http::{HeaderValue, Method, StatusCode, header},
+    middleware::{self, Next},
+    response::{Html, IntoResponse, Response},
+    routing::{get, post},
+};
+use dto::{
+    CollectionsResponse, IndexRequest, IndexResponse, MetricsResponse, SearchBody, SearchResponse,
+    SearchResult, SummarizeBody, SummarizeResponse,
+};
+use error::AppError;
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use std::time::Instant;
+use time::OffsetDateTime;
+use tower_http::compression::{
+    CompressionLayer,
+    predicate::{DefaultPredicate, NotForContentType, Predicate},
+};
+use tracing::{Instrument, field};
+
+/// Static HTML page served at `GET /ui`; vanilla JS backed by the JSON endpoints below.
+const UI_HTML: &str = include_str!("../../static/ui.html");
+
+/// Build the HTTP router exposing the ingestion API surface.
+pub fn create_router<S>(service: Arc<S>) -> Router
+where
+    S: ProcessingApi + 'static,
+{
+    let etag = || middleware::from_fn(conditional_get);
+    // Streamed ndjson bodies are flushed line by line; buffering them in an encoder would stall
+    // consumers until the stream ends.
+    let compression = CompressionLayer::new()
+        .gzip(true)
+        .br(true)
+        .compress_when(DefaultPredicate::new().and(NotForContentType::const_new(NDJSON)));
+    Router::new()
+        .route("/index", post(index_document::<S>))
+        .route(
+            "/collections",
+            get(list_collections::<S>)
+                .layer(etag())
+                .post(create_collection::<S>),
+        )
+        .route("/collections/:name/import", post(import_collection::<S>))
+        .route("/metrics", get(get_metrics::<S>).layer(etag()))
+        .route("/commands", get(get_commands))
+        .route("/projects", get(list_projects::<S>).layer(etag()))
+        .route("/tags", get(list_tags::<S>))
+        .route("/storage-stats", get(storage_stats::<S>))
+        .route("/search", post(search_memories::<S>))
+        .route("/summarize", post(summarize_memories::<S>))
+        .route("/ui", get(get_ui))
+        .layer(middleware::from_fn(same_origin_cors))
+        .layer(middleware::from_fn(trace_request))
+        .layer(compression)
+        .with_state(service)
+}
+
+/// Content type of newline-delimited JSON streams, which are never compressed.
+const NDJSON: &str = "application/x-ndjson";
+
+/// Tag successful `GET` responses with an `ETag` hashed from the body, and answer a request whose
+/// `If-None-Match` already names that tag with an empty `304 Not Modified`.
+///
+/// The tag is weak because the compression layer may re-encode the body after it is computed.
+async fn conditional_get(request: Request, next: Next) -> Response {
+    if request.method() != Method::GET {
+        return next.run(request).await;
+    }
+    let if_none_match = request.headers().get(header::IF_NONE_MATCH).cloned();
+    let response = next.run(request).await;
+    if response.status() != StatusCode::OK {
+        return response;
+    }
+    let (mut parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(error) => {
+            tracing::warn!(error = %error, "Failed to buffer response for ETag");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+    let digest = Sha256::digest(&bytes);
+    let etag = format!("W/\"{}\"", hex::encode(&digest[..16]));
+    let etag_header = HeaderValue::from_str(&etag).expect("hex ETag is a valid header value");
+    if if_none_match.is_some_and(|value| etag_matches(&value, &etag)) {
+        let mut not_modified = StatusCode::NOT_MODIFIED.into_response();
+        not_modified.headers_mut().insert(header::ETAG, etag_header);
+        return not_modified;
+    }
+    parts.headers.insert(header::ETAG, etag_header);
+    parts.headers.remove(header::CONTENT_LENGTH);
+    Response::from_parts(parts, Body::from(bytes))
+}
+
+/// Weak comparison of an `If-None-Match` list (or `*`) against `etag`.
+fn etag_matches(if_none_match: &HeaderValue, etag: &str) -> bool {
+    let Ok(candidates) = if_none_match.to_str() else {
+        return false;
+    };
+    let opaque = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    candidates
+        .split(',')
+        .any(|candidate| candidate.trim() == "*" || opaque(candidate) == opaque(etag))
+}
+
+/// Run the request inside an `http.request` span recording route, status, and duration.
+///
+/// Failing handlers add the envelope `error.code` to the same span (see [`AppError`]).
+async fn trace_request(request: Request, next: Next) -> Response {
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| request.uri().path().to_string());
+    let span = tracing::info_span!(
+        "http.request",
+        http.method = %request.method(),
+        http.route = %route,
+        http.status_code = field::Empty,
+        error.code = field::Empty,
+        duration_ms = field::Empty,
+    );
+    let began = Instant::now();
+    let response = next.run(request).instrument(span.clone()).await;
+    span.record("http.status_code", response.status().as_u16());
+    span.record("duration_ms", began.elapsed().as_millis() as u64);
+    response
+}
+
+/// Echo CORS headers only when the request `Origin` matches the `Host` the server was reached on.
+///
+/// Requests issued by the embedded `/ui` page receive matching headers; other origins receive
+/// none, so browsers keep blocking cross-site reads of local memories.
+async fn same_origin_cors(request: Request, next: Next) -> Response {
+    let origin = request
+        .headers()
+        .get(header::ORIGIN)
+        .filter(|origin| is_same_origin(origin, request.headers().get(header::HOST)))
+        .cloned();
+    let mut response = next.run(request).await;
+    if let Some(origin) = origin {
+        let headers = response.headers_mut();
+        headers.insert(header::ACCESS_CONTROL_ALLOW_ORIGIN, origin);
+        headers.insert(header::VARY, HeaderValue::from_static("Origin"));
+    }
+    response
+}
+
+fn is_same_origin(origin: &HeaderValue, host: Option<&HeaderValue>) -> bool {
+    let (Ok(origin), Some(Ok(host))) = (origin.to_str(), host.map(HeaderValue::to_str)) else {
+        return false;
+    };
+    origin
+        .strip_prefix("http://")
+        .or_else(|| origin.strip_prefix("https://"))
+        .is_some_and(|authority| authority.eq_ignore_ascii_case(host))
+}
+
+/// Serve the embedded memory browser.
+async fn get_ui() -> Html<&'static str> {
+    Html(UI_HTML)
+}
+
+/// Index a document into the target collection.
+///
+/// This handler accepts raw text and optional metadata, derives a chunk size (unless
+/// `TEXT_SPLITTER_CHUNK_SIZE` is set), performs semantic chunking and embedding, and upserts
+/// the resulting vectors to Qdrant.
+async fn index_document<S>(
+    State(service): State<Arc<S>>,
+    Json(request): Json<IndexRequest>,
+) -> Result<Json<IndexResponse>, AppError>
+where
+    S: ProcessingApi,
+{
+    let IndexRequest {
+        text,
+        collection,
+        project_id,
+        memory_type,
+        tags,
+        source_uri,
+        timestamp,
+        allow_future,
+    } = request;
+    let pinned = collection.is_some();
+    let collection_name = resolve_project_collection(collection, project_id.as_deref())?;
+    let timestamp = timestamp
+        .map(|value| {
+            validate_ingest_timestamp(&value, allow_future, OffsetDateTime::now_utc())
+                .map_err(AppError::BadRequest)
+        })
+        .transpose()?;
+    let metadata = IngestMetadata {
+        project_id,
+        memory_type,
+        tags,
+        source_uri,
+        timestamp,
+        auto_classify: None,
+    };
+    let collection_name = if pinned {
+        collection_name
+    } else {
+        let partition = service.ingest_collection(&collection_name, &metadata);
+        validate_collection_name(&partition).map_err(AppError::BadRequest)?;
+        partition
+    };
+    let outcome = service
+        .process_and_index(&collection_name, text, metadata)
+        .await?;
+    tracing::info!(
+        collection = collection_name,
+        chunks = outcome.chunk_count,
+        chunk_size = outcome.chunk_size,
+        inserted = outcome.inserted,
+        updated = outcome.updated,
+        skipped_duplicates = outcome.skipped_duplicates,
+        skipped_low_quality = outcome.skipped_low_quality,
+        skipped_unchanged = outcome.skipped_unchanged,
+        degraded = outcome.degraded,
+        "Index request completed"
+    );
+    Ok(Json(IndexResponse {
+        chunks_indexed: outcome.chunk_count,
+        chunk_size: outcome.chunk_size,
+        inserted: outcome.inserted,
+        updated: outcome.updated,
+        skipped_duplicates: outcome.skipped_duplicates,
+        skipped_low_quality: outcome.skipped_low_quality,
+        skipped_unchanged: outcome.skipped_unchanged,
+        duplicate_ratio: outcome.duplicate_ratio(),
+        degraded: outcome.degraded,
+        ingest_profile: outcome.ingest_profile.into(),
+        doc_id: outcome.doc_id,
+    }))
+}
+
+/// List Qdrant collections available to this server.
+async fn list_collections<S>(
+    State(service): State<Arc<S>>,
+) -> Result<Json<CollectionsResponse>, AppError>
+where
+    S: ProcessingApi,
+{
+    let collections = service.list_collections().await?;
+    Ok(Json(CollectionsResponse { collections }))
+}
+
+/// Response body for `POST /collections/:name/import`.
+#[derive(Serialize)]
+struct ImportResponse {
+    collection: String,
+    #[serde(flatten)]
+    summary: ImportSummary,
+}
+
+/// Response body for `POST /collections/:name/import?format=chroma|mem0`.
+#[derive(Serialize)]
+struct ForeignImportResponse {
+    collection: String,
+    format: ForeignFormat,
+    #[serde(flatten)]
+    summary: ForeignImportSummary,
+}
+
+/// Query parameters of `POST /collections/:name/import`.
+#[derive(Deserialize)]
+struct ImportQuery {
+    /// `ndjson` (default), `chroma`, or `mem0`.
+    #[serde(default)]
+    format: Option<String>,
+    /// Metadata field read as `project_id` in foreign exports.
+    #[serde(default)]
+    project_id_field: Option<String>,
+    /// Metadata field read as `memory_type` in foreign exports.
+    #[serde(default)]
+    memory_type_field: Option<String>,
+    /// Metadata field read as `tags` in foreign exports.
+    #[serde(default)]
+    tags_field: Option<String>,
+    /// Metadata field read as `timestamp` in foreign exports.
+    #[serde(default)]
+    timestamp_field: Option<String>,
+    /// Metadata field read as `source_uri` in foreign exports.
+    #[serde(default)]
+    source_uri_field: Option<String>,
+}
+
+impl ImportQuery {
+    /// Foreign export format requested, or `None` for NDJSON.
+    fn foreign_format(&self) -> Result<Option<ForeignFormat>, AppError> {
+        match self.format.as_deref().map(str::trim) {
+            None | Some("ndjson") => Ok(None),
+            Some("chroma") => Ok(Some(ForeignFormat::Chroma)),
+            Some("mem0") => Ok(Some(ForeignFormat::Mem0)),
+            Some(other) => Err(AppError::BadRequest(format!(
+                "`format` must be \"ndjson\", \"chroma\", or \"mem0\" (got \"{other}\")"
+            ))),
+        }
+    }
+
+    fn has_field_overrides(&self) -> bool {
+        [
+            &self.project_id_field,
+            &self.memory_type_field,
+            &self.tags_field,
+            &self.timestamp_field,
+            &self.source_uri_field,
+        ]
+        .iter()
+        .any(|field| field.is_some())
+    }
+
+    /// Default mapping for `format` with any `*_field` overrides applied.
+    fn mapping(self, format: ForeignFormat) -> FieldMapping {
+        let defaults = FieldMapping::defaults(format);
+        FieldMapping {
+            project_id: self.project_id_field.unwrap_or(defaults.project_id),
+            memory_type: self.memory_type_field.unwrap_or(defaults.memory_type),
+            tags: self.tags_field.unwrap_or(defaults.tags),
+            timestamp: self.timestamp_field.unwrap_or(defaults.timestamp),
+            source_uri: self.source_uri_field.unwrap_or(defaults.source_uri),
+        }
+    }
+}
+
+/// Import an NDJSON body into the collection, one memory per line, or a Chroma or mem0 export
+/// when `format` names one.
+///
+/// NDJSON is read chunk by chunk and each completed line is stored before the next chunk is
+/// pulled, so a large import holds at most one line and one vector batch in memory and slow
+/// embedding naturally throttles the upload. Foreign exports are single JSON documents and are
+/// buffered whole, up to [`MAX_FOREIGN_IMPORT_BYTES`].
+async fn import_collection<S>(
+    State(service): State<Arc<S>>,
+    Path(collection): Path<String>,
+    Query(query): Query<ImportQuery>,
+    body: Body,
+) -> Result<Response, AppError>
+where
+    S: ProcessingApi,
+{
+    validate_collection_name(&collection).map_err(AppError::BadRequest)?;
+    let dimension = service.vector_dimension(&collection).await?;
+    if let Some(format) = query.foreign_format()? {
+        let document = to_bytes(body, MAX_FOREIGN_IMPORT_BYTES)
+            .await
+            .map_err(|error| {
+                AppError::BadRequest(format!("Failed to read import body: {error}"))
+            })?;
+        let records = match format {
+            ForeignFormat::Chroma => chroma::parse(&document),
+            ForeignFormat::Mem0 => mem0::parse(&document),
+        }
+        .map_err(AppError::BadRequest)?;
+        let summary = import_foreign(
+            service.as_ref(),
+            &collection,
+            dimension,
+            records,
+            &query.mapping(format),
+        )
+        .await;
+        return Ok(Json(ForeignImportResponse {
+            collection,
+            format,
+            summary,
+        })
+        .into_response());
+    }
+    if query.has_field_overrides() {
+        return Err(AppError::BadRequest(
+            "`*_field` mappings only apply to `format=chroma` and `format=mem0` imports".into(),
+        ));
+    }
+    let mut import = NdjsonImport::new(service.as_ref(), &collection, dimension);
+    let mut stream = body.into_data_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|error| {
+            AppError::BadRequest(format!("Failed to read import body: {error}"))
+        })?;
+        import.feed(&chunk).await;
+    }
+    let summary = import.finish().await;
+    Ok(Json(ImportResponse {
+        collection,
+        summary,
+    })
+    .into_response())
+}
+
+/// Query parameters shared by the metadata listing endpoints.
+#[derive(Deserialize)]
+struct ListingQuery {
+    /// Optional collection override (defaults to `QDRANT_COLLECTION_NAME`).
+    #[serde(default)]
+    collection: Option<String>,
+    /// Optional project scope (used by `GET /tags` and `GET /storage-stats`).
+    #[serde(default)]
+    project_id: Option<String>,
+}
+
+/// Response body for `GET /projects`.
+#[derive(Serialize)]
+struct ProjectsResponse {
+    collection: String,
+    projects: Vec<String>,
+}
+
+/// List distinct project identifiers stored in the collection.
+async fn list_projects<S>(
+    State(service): State<Arc<S>>,
+    Query(query): Query<ListingQuery>,
+) -> Result<Json<ProjectsResponse>, AppError>
+where
+    S: ProcessingApi,
+{
+    let collection = resolve_collection(query.collection)?;
+    let projects = service.list_projects(&collection).await?;
+    Ok(Json(ProjectsResponse {
+        collection,
+        projects: projects.into_iter().collect(),
+    }))
+}
+
+/// Response body for `GET /tags`.
+#[derive(Serialize)]
+struct TagsResponse {
+    collection: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    project_id: Option<String>,
+    tags: Vec<String>,
+}
+
+/// List distinct tags stored in the collection, optionally scoped by `project_id`.
+async fn list_tags<S>(
+    State(service): State<Arc<S>>,
+    Query(query): Query<ListingQuery>,
+) -> Result<Json<TagsResponse>, AppError>
+where
+    S: ProcessingApi,
+{
+    let collection = resolve_collection(query.collection)?;
+    let project_id = query.project_id.filter(|value| !value.trim().is_empty());
+    let tags = service
+        .list_tags(&collection, project_id.as_deref())
+        .await?;
+    Ok(Json(TagsResponse {
+        collection,
+        project_id,
+        tags: tags.into_iter().collect(),
+    }))
+}
+
+/// Report approximate disk usage for one collection, or for every collection when `collection`
+/// is omitted.
+async fn storage_stats<S>(
+    State(service): State<Arc<S>>,
+    Query(query): Query<ListingQuery>,
+) -> Result<Json<StorageStatsReport>, AppError>
+where
+    S: ProcessingApi,
+{
+    let collection = sanitize_string(query.collection);
+    if let Some(name) = &collection {
+        validate_collection_name(name).map_err(AppError::BadRequest)?;
+    }
+    let project_id = sanitize_string(query.project_id);
+    let report = service
+        .storage_stats(collection.as_deref(), project_id.as_deref())
+        .await?;
+    Ok(Json(report))
+}
+
+/// Resolve an optional collection override, rejecting names unsafe for Qdrant URL paths.
+fn resolve_collection(collection: Option<String>) -> Result<String, AppError> {
+    resolve_project_collection(collection, None)
+}
+
+/// Resolve an optional collection override, defaulting to the project's collection when
+/// `COLLECTION_PER_PROJECT` is set.
+fn resolve_project_collection(
+    collection: Option<String>,
+    project_id: Option<&str>,
+) -> Result<String, AppError> {
+    let name = collection.unwrap_or_else(|| get_config().default_collection(project_id));
+    validate_collection_name(&name).map_err(AppError::BadRequest)?;
+    Ok(name)
+}
+
+/// Run a semantic search against stored memories.
+///
+/// The body is validated like the MCP `search` tool, except that an oversized `limit` is always
+/// clamped to `SEARCH_MAX_LIMIT`. Without a `collection`, the monthly partitions kept under
+/// `EPISODIC_PARTITIONING` are searched too and their hits merged by score.
+async fn search_memories<S>(
+    State(service): State<Arc<S>>,
+    Json(body): Json<SearchBody>,
+) -> Result<Json<SearchResponse>, AppError>
+where
+    S: ProcessingApi,
+{
+    require_project(
+        get_config().require_project_filter,
+        "search",
+        body.project_id.as_deref(),
+    )?;
+    let derived = body.collection.is_none();
+    let collection = resolve_project_collection(body.collection, body.project_id.as_deref())?;
+    // Per-project collections are created on first use, like on push.
+    if derived && collection != get_config().qdrant_collection_name {
+        service.ensure_collection(&collection).await?;
+    }
+    let limits = SearchLimits {
+        clamp_limit: true,
+        ..SearchLimits::from_config(get_config())
+    };
+    let request = SearchRequestBuilder::from(SearchRequest {
+        query_text: body.query_text,
+        collection: Some(collection.clone()),
+        project_id: body.project_id,
+        memory_type: body.memory_type,
+        tags: body.tags,
+        limit: body.limit,
+        score_threshold: body.score_threshold,
+        ..SearchRequest::default()
+    })
+    .build_with_limits(&limits)?;
+    let (targets, partitions_searched) = if derived {
+        service
+            .read_targets(&collection, request.request().memory_type.as_deref(), None)
+            .await?
+    } else {
+        (vec![collection.clone()], None)
+    };
+    let mut outcome = None;
+    for target in &targets {
+        let targeted = SearchRequestBuilder::from(SearchRequest {
+            collection: Some(target.clone()),
+            ..request.request().clone()
+        })
+        .build_with_limits(&limits)?;
+        let found = service.search_memories(targeted).await?;
+        match outcome.as_mut() {
+            Some(SearchOutcome { hits, .. }) => hits.extend(found.hits),
+            None => outcome = Some(found),
+        }
+    }
+    let mut outcome = outcome.unwrap_or_else(|| SearchOutcome {
+        hits: Vec::new(),
+        effective_query: request.request().query_text.clone(),
+        query_truncated: false,
+        query_compressed: false,
+    });
+    if targets.len() > 1 {
+        outcome.hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+        outcome
+            .hits
+            .truncate(request.request().limit.unwrap_or(limits.default_limit));
+    }
+    let results: Vec<SearchResult> = outcome.hits.into_iter().map(SearchResult::from).collect();
+    let effective_query = outcome.query_truncated.then_some(outcome.effective_query);
+    Ok(Json(fit_response(
+        &results,
+        get_config().max_response_bytes,
+        |kept, dropped| SearchResponse {
+            collection: collection.clone(),
+            results: kept.to_vec(),
+            query_truncated: outcome.query_truncated,
+            effective_query: effective_query.clone(),
+            truncated: dropped > 0,
+            dropped_count: (dropped > 0).then_some(dropped),
+            partitions_searched: partitions_searched.clone(),
+        },
+    )))
+}
+
+/// Summarize memories within the requested window and store the summary as a semantic memory.
+async fn summarize_memories<S>(
+    State(service): State<Arc<S>>,
+    Json(body): Json<SummarizeBody>,
+) -> Result<Json<SummarizeResponse>, AppError>
+where
+    S: ProcessingApi,
+{
+    require_project(
+        get_config().require_project_filter,
+        "summarize",
+        body.project_id.as_deref(),
+    )?;
+    let start = parse_boundary(
+        "time_range.start",
+        &body.time_range.start,
+        TimeBoundary::Start,
+    )?;
+    let end = parse_boundary("time_range.end", &body.time_range.end, TimeBoundary::End)?;
+    if start > end {
+        return Err(AppError::BadRequest(
+            "`time_range.start` must be earlier than or equal to `time_range.end`".into(),
+        ));
+    }
+    let strategy = match body.strategy.as_deref().map(str::trim) {
+        None | Some("auto") => SummarizeStrategy::Auto,
+        Some("abstractive") => SummarizeStrategy::Abstractive,
+        Some("extractive") => SummarizeStrategy::Extractive,
+        Some(other) => {
+            return Err(AppError::BadRequest(format!(
+                "`strategy` must be one of auto|abstractive|extractive (got '{other}')"
+            )));
+        }
+    };
+    // Without an explicit collection, sources are also read from the monthly partitions.
+    let explicit = body.collection.is_some();
+    let collection = resolve_collection(body.collection)?;
+    let outcome = service
+        .summarize_memories(SummarizeRequest {
+            project_id: body.project_id,
+            memory_type: body.memory_type,
+            tags: body.tags,
+            time_range: SearchTimeRange {
+                start: Some(format_utc_rfc3339(start)),
+                end: Some(format_utc_rfc3339(end)),
+            },
+            limit: body.limit,
+            strategy: Some(strategy),
+            provider: body.provider,
+            model: body.model,
+            max_words: body.max_words,
+            collection: explicit.then_some(collection),
+            consolidate: None,
+            label: sanitize_string(body.label),
+            topic: sanitize_string(body.topic),
+        })
+        .await?;
+    Ok(Json(outcome.into()))
+}
+
+/// Parse an RFC3339 timestamp or `YYYY-MM-DD` date bounding a summarization window.
+fn parse_boundary(
+    label: &str,
+    value: &str,
+    boundary: TimeBoundary,
+) -> Result<OffsetDateTime, AppError> {
+    parse_time_boundary(value.trim(), boundary).ok_or_else(|| {
+        AppError::BadRequest(format!(
+            "`{label}` must be a valid RFC3339 timestamp or YYYY-MM-DD date (got '{value}')"
+        ))
+    })
+}
+
+/// Request body for `POST /collections` to create/resize a collection.
+#[derive(Deserialize)]
+struct CreateCollectionRequest {
+    /// Name of the collection to create or resize.
+    name: String,
+    /// Optional vector size override (defaults to `EMBEDDING_DIMENSION`).
+    #[serde(default)]
+    vector_size: Option<u64>,
+    /// Optional shard count (defaults to `QDRANT_SHARD_NUMBER`).
+    #[serde(default)]
+    shard_number: Option<u32>,
+    /// Optional replication factor (defaults to `QDRANT_REPLICATION_FACTOR`).
+    #[serde(default)]
+    replication_factor: Option<u32>,
+}
+
+/// Create or resize a collection.
+async fn create_collection<S>(
+    State(service): State<Arc<S>>,
+    Json(request): Json<CreateCollectionRequest>,
+) -> Result<(), AppError>
+where
+    S: ProcessingApi,
+{
+    validate_collection_name(&request.name).map_err(AppError::BadRequest)?;
+    if request.shard_number == Some(0) || request.replication_factor == Some(0) {
+        return Err(AppError::BadRequest(
+            "shard_number and replication_factor must be at least 1".into(),
+        ));
+    }
+    let topology = CollectionTopology {
+        shard_number: request.shard_number,
+        replication_factor: request.replication_factor,
+    };
+    service
+        .create_collection(&request.name, request.vector_size, topology)
+        .await?;
+    Ok(())
+}
+
+/// Return a concise metrics snapshot with document/chunk counters, dedupe savings, and the last
+/// chunk size.
+async fn get_metrics<S>(State(service): State<Arc<S>>) -> Result<Json<MetricsResponse>, AppError>
+where
+    S: ProcessingApi,
+{
+    let snapshot = service.metrics_snapshot();
+    Ok(Json(MetricsResponse {
+        documents_indexed: snapshot.documents_indexed,
+        chunks_indexed: snapshot.chunks_indexed,
+        last_chunk_size: snapshot.last_chunk_size,
+        dedupe: snapshot.dedupe,
+        invalid_embeddings: snapshot.invalid_embeddings,
+    }))
+}
+
+/// Descriptor for a single command in the discovery catalog.
+#[derive(Serialize)]
+struct CommandDescriptor {
+    name: &'static str,
+    method: &'static str,
+    path: &'static str,
+    description: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_example: Option<serde_json::Value>,
+}
+
+/// Response body for `GET /commands`.
+#[derive(Serialize)]
+struct CommandsResponse {
+    commands: Vec<CommandDescriptor>,
+}
+
+/// Enumerate supported HTTP commands for discovery/UX in hosts and tools.
+async fn get_commands() -> Json<CommandsResponse> {
+    Json(CommandsResponse {
+        commands: vec![
+            CommandDescriptor {
+                name: "index",
+                method: "POST",
+                path: "/index",
+                description: "Store source text as retrievable memory instead of pasting it into chats.",
+                request_example: Some(json!({
+                    "text": "Document contents",
+                    "collection": "optional-collection",
+                    "project_id": "project-123",
+                    "memory_type": "episodic",
+                    "tags": ["alpha", "beta"],
+                    "source_uri": "https://example.org/origin"
+                })),
+            },
+            CommandDescriptor {
+                name: "list_collections",
+                method: "GET",
+                path: "/collections",
+                description: "See which memory collections exist before you index or search.",
+                request_example: None,
+            },
+            CommandDescriptor {
+                name: "create_collection",
+                method: "POST",
+                path: "/collections",
+                description: "Create or resize a collection when starting a project or switching embedding dimensions.",
+                request_example: Some(json!({
+                    "name": "my-collection",
+                    "vector_size": 1536
+                })),
+            },
+            CommandDescriptor {
+                name: "import",
+                method: "POST",
+                path: "/collections/:name/import",
+                description: "Bulk-load memories as newline-delimited JSON; lines with a `vector` skip re-embedding. `?format=chroma` or `?format=mem0` accepts those servers' JSON exports.",
+                request_example: Some(json!({
+                    "text": "One memory per line",
+                    "vector": [0.12, -0.03],
+                    "project_id": "project-123",
+                    "tags": ["imported"]
+                })),
+            },
+            CommandDescriptor {
+                name: "metrics",
+                method: "GET",
+                path: "/metrics",
+                description: "Check ingestion volume, dedupe savings, and last chunk size at a glance.",
+                request_example: None,
+            },
+            CommandDescriptor {
+                name: "list_projects",
+                method: "GET",
+                path: "/projects",
+                description: "Discover which projects have stored memories.",
+                request_example: None,
+            },
+            CommandDescriptor {
+                name: "list_tags",
+                method: "GET",
+                path: "/tags",
+                description: "Discover tags in use, optionally scoped with `?project_id=`.",
+                request_example: None,
+            },
+            CommandDescriptor {
+                name: "storage_stats",
+                method: "GET",
+                path: "/storage-stats",
+                description: "Approximate disk usage per collection, optionally for one `?project_id=`.",
+                request_example: None,
+            },
+            CommandDescriptor {
+                name: "search",
+                method: "POST",
+                path: "/search",
+                description: "Find stored memories by meaning instead of re-reading documents.",
+                request_example: Some(json!({
+                    "query_text": "How do we rotate API keys?",
+                    "project_id": "project-123",
+                    "tags": ["security"],
+                    "limit": 5
+                })),
+            },
+            CommandDescriptor {
+                name: "summarize",
+                method: "POST",
+                path: "/summarize",
+                description: "Condense a period of episodic memories into one stored semantic summary.",
+                request_example: Some(json!({
+                    "project_id": "project-123",
+                    "time_range": {
+                        "start": "2025-01-01T00:00:00Z",
+                        "end": "2025-01-07T23:59:59Z"
+                    },
+                    "strategy": "extractive",
+                    "max_words": 200
+                })),
+            },
+            CommandDescriptor {
+                name: "ui",
+                method: "GET",
+                path: "/ui",
+                description: "Open a browser page to search and inspect memories by hand.",
+                request_example: None,
+            },
+        ],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AppError, create_router, get_commands, require_project};
+    use crate::config::ensure_test_config;
+    use crate::embedding::{AiLibClient, EmbeddingClientError};
+    use crate::metrics::MetricsSnapshot;
+    use crate::processing::{
+        ImportedVector, IngestMetadata, IngestProfile, ProcessingApi, ProcessingError,
+        ProcessingOutcome, ProcessingService, QdrantHealthSnapshot, SearchError, SearchHit,
+        SearchOutcome, SearchRequest, StorageEstimate, StorageStats, StorageStatsReport,
+        SummarizeError, SummarizeOutcome, SummarizeRequest, ValidatedSearchRequest, WarmupOutcome,
+    };
+    use crate::qdrant::{CollectionInfo, QdrantError, QdrantService};
+    use async_trait::async_trait;
+    use axum::{
+        Router,
+        body::{Body, to_bytes},
+        http::{Method, Request, StatusCode, header},
+        response::IntoResponse,
+    };
+    use httpmock::prelude::*;
+    use serde_json::json;
+    use std::collections::BTreeSet;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::sync::Mutex;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn commands_catalog_exposes_index_endpoint() {
+        let response = get_commands().await;
+        let commands = response.0.commands;
+        let index = commands
+            .iter()
+            .find(|cmd| cmd.name == "index")
+            .expect("index command present");
+
+        assert_eq!(index.method, "POST");
+        assert_eq!(index.path, "/index");
+        assert!(!index.description.is_empty());
+
+        // ensure catalog exposes multiple commands for host discovery
+        assert!(commands.len() >= 3);
+    }
+
+    #[tokio::test]
+    async fn index_route_stores_episodic_memories_in_their_monthly_partition() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        let partition = format!(
+            "/collections/{}-2025-02",
+            crate::config::get_config().qdrant_collection_name
+        );
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path(partition.as_str());
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method(PUT).path(format!("{partition}/index"));
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+        let upsert = server
+            .mock_async(|when, then| {
+                when.method(PUT)
+                    .path(format!("{partition}/points"))
+                    .body_contains(r#""memory_type":"episodic""#);
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+        let service = ProcessingService::from_parts(
+            Box::new(AiLibClient::new()),
+            QdrantService {
+                client: reqwest::Client::new(),
+                base_url: server.base_url(),
+                read_base_url: None,
+                api_key: None,
+                api_keys: Default::default(),
+                capabilities: Default::default(),
+                #[cfg(feature = "fault-injection")]
+                faults: None,
+            },
+        )
+        .with_monthly_partitions();
+        let app = create_router(Arc::new(service));
+
+        let payload = json!({
+            "text": "Standup: shipped the importer.",
+            "memory_type": "episodic",
+            "timestamp": "2025-02-10T12:00:00Z"
+        });
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/index")
+                    .header("content-type", "application/json")
+                    .body(Body::from(payload.to_string()))
+                    .expect("request"),
+            )
+            .await
+            .expect("router response");
+
+        assert_eq!(response.status(), StatusCode::OK);
+        upsert.assert_hits_async(1).await;
+    }
+
+    #[tokio::test]
+    async fn index_route_accepts_metadata_payload() {
+        ensure_test_config();
+        let outcome = ProcessingOutcome {
+            chunk_count: 2,
+            chunk_size: 512,
+            overlap: 0,
+            auto_sized: false,
+            embedding_context_window: 8192,
+            chunk_size_clamped_from: None,
+            inserted: 2,
+            updated: 0,
+            skipped_duplicates: 0,
+            skipped_low_quality: 0,
+            skipped_unchanged: false,
+            skipped_unchanged_chunks: 0,
+            degraded: false,
+            classification: None,
+            ingest_profile: stub_profile(),
+            doc_id: Some("doc-1".into()),
+            transcript: None,
+        };
+        let service = Arc::new(StubProcessingService::new(outcome));
+        let app = create_router(service.clone());
+
+        let payload = json!({
+            "text": "Document body",
+            "collection": "custom-collection",
+            "project_id": "proj-42",
+            "memory_type": "semantic",
+            "tags": ["alpha", "beta"],
+            "source_uri": "https://example.org/doc"
+        });
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/index")
+                    .header("content-type", "application/json")
+                    .body(Body::from(payload.to_string()))
+                    .expect("request"),
+            )
+            .await
+            .expect("router response");
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("body bytes");
+        let json: serde_json::Value = serde_json::from_slice(&body).expect("json body");
+        assert_eq!(json["chunks_indexed"], 2);
+        assert_eq!(json["chunk_size"], 512);
+        assert_eq!(json["doc_id"], "doc-1");
+        assert_eq!(json["ingest_profile"]["embedding_model"], "test-model");
+        assert_eq!(
+            json["ingest_profile"]["profile_hash"],
+            stub_profile().hash().as_str()
+        );
+
+        let calls = service.recorded_calls().await;
+        assert_eq!(calls.len(), 1);
+        let call = &calls[0];
+        assert_eq!(call.collection, "custom-collection");
+        assert_eq!(call.text, "Document body");
+        assert_eq!(call.metadata.project_id.as_deref(), Some("proj-42"));
+        assert_eq!(call.metadata.memory_type.as_deref(), Some("semantic"));
+        assert_eq!(
+            call.metadata.tags.as_ref(),
+            Some(&vec!["alpha".into(), "beta".into()])
+        );
+        assert_eq!(
+            call.metadata.source_uri.as_deref(),
+            Some("https://example.org/doc")
+        );
+    }
+
+    #[tokio::test]
+    async fn index_route_normalizes_backdated_timestamps_and_rejects_future_ones() {
+        let (service, app) = stub_app();
+        let request = |body: serde_json::Value| {
+            Request::builder()
+                .method(Method::POST)
+                .uri("/index")
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .expect("request")
+        };
+
+        let response = app
+            .clone()
+            .oneshot(request(
+                json!({ "text": "Old note", "timestamp": "2019-03-04T10:00:00+02:00" }),
+            ))
+            .await
+            .expect("router response");
+        assert_eq!(response.status(), StatusCode::OK);
+        let calls = service.recorded_calls().await;
+        assert_eq!(
+            calls[0].metadata.timestamp.as_deref(),
+            Some("2019-03-04T08:00:00Z")
+        );
+
+        let response = app
+            .clone()
+            .oneshot(request(
+                json!({ "text": "Plan", "timestamp": "2999-01-01T00:00:00Z" }),
+            ))
+            .await
+            .expect("router response");
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = app
+            .oneshot(request(json!({
+                "text": "Plan",
+                "timestamp": "2999-01-01T00:00:00Z",
+                "allow_future": true
+            })))
+            .await
+            .expect("router response");
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    fn stub_profile() -> IngestProfile {
+        IngestProfile {
+            embedding_provider: "ollama".into(),
+            embedding_model: "test-model".into(),
+            embedding_dimension: 768,
+            chunk_size: 512,
+            chunk_overlap: 0,
+            strategy: "semchunk",
+            tokenizer: "cl100k_base",
+            dedupe: "chunk_hash",
+            version: "0.0.0",
+        }
+    }
+
+    fn stub_app() -> (Arc<StubProcessingService>, Router) {
+        stub_app_with_partitions(Vec::new())
+    }
+
+    fn stub_app_with_partitions(partitions: Vec<String>) -> (Arc<StubProcessingService>, Router) {
+        ensure_test_config();
+        let mut service = StubProcessingService::new(ProcessingOutcome {
+            chunk_count: 0,
+            chunk_size: 0,
+            overlap: 0,
+            auto_sized: false,
+            embedding_context_window: 0,
+            chunk_size_clamped_from: None,
+            inserted: 0,
+            updated: 0,
+            skipped_duplicates: 0,
+            skipped_low_quality: 0,
+            skipped_unchanged: false,
+            skipped_unchanged_chunks: 0,
+            degraded: false,
+            classification: None,
+            ingest_profile: stub_profile(),
+            doc_id: None,
+            transcript: None,
+        });
+        service.partitions = partitions;
+        let service = Arc::new(service);
+        let app = create_router(service.clone());
+        (service, app)
+    }
+
+    async fn get_json(app: Router, uri: &str) -> serde_json::Value {
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri(uri)
+                    .body(Body::empty())
+                    .expect("request"),
+            )
+            .await
+            .expect("router response");
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("body bytes");
+        serde_json::from_slice(&body).expect("json body")
+    }
+
+    #[tokio::test]
+    async fn projects_route_lists_default_collection_projects() {
+        let (_, app) = stub_app();
+        let json = get_json(app, "/projects").await;
+        assert_eq!(
+            json["collection"],
+            crate::config::get_config().qdrant_collection_name
+        );
+        assert_eq!(json["projects"], json!(["alpha", "beta"]));
+    }
+
+    #[tokio::test]
+    async fn responses_are_gzip_compressed_when_accepted() {
+        let (_, app) = stub_app();
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/commands")
+                    .header(header::ACCEPT_ENCODING, "gzip")
+                    .body(Body::empty())
+                    .expect("request"),
+            )
+            .await
+            .expect("router response");
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_ENCODING], "gzip");
+
+        let compressed = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("body bytes");
+        let mut decoded = String::new();
+        std::io::Read::read_to_string(
+            &mut flate2::read::GzDecoder::new(&compressed[..]),
+            &mut decoded,
+        )
+        .expect("gzip body");
+        let json: serde_json::Value = serde_json::from_str(&decoded).expect("json body");
+        assert!(
+            json["commands"]
+                .as_array()
+                .is_some_and(|commands| !commands.is_empty())
+        );
+    }
+
+    #[tokio::test]
+    async fn unchanged_listings_answer_if_none_match_with_304() {
+        let (_, app) = stub_app();
+        let get = |if_none_match: Option<&str>| {
+            let mut request = Request::builder().uri("/projects");
+            if let Some(tag) = if_none_match {
+                request = request.header(header::IF_NONE_MATCH, tag);
+            }
+            request.body(Body::empty()).expect("request")
+        };
+
+        let first = app.clone().oneshot(get(None)).await.expect("response");
+        assert_eq!(first.status(), StatusCode::OK);
+        let etag = first.headers()[header::ETAG]
+            .to_str()
+            .expect("etag")
+            .to_string();
+        assert!(etag.starts_with("W/\""));
+
+        let cached = app
+            .clone()
+            .oneshot(get(Some(&etag)))
+            .await
+            .expect("response");
+        assert_eq!(cached.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(cached.headers()[header::ETAG], etag.as_str());
+        let body = to_bytes(cached.into_body(), usize::MAX)
+            .await
+            .expect("body bytes");
+        assert!(body.is_empty());
+
+        let stale = app
+            .oneshot(get(Some("W/\"0000\", \"1111\"")))
+            .await
+            .expect("response");
+        assert_eq!(stale.status(), StatusCode::OK);
+        assert_eq!(stale.headers()[header::ETAG], etag.as_str());
+    }
+
+    #[tokio::test]
+    async fn tags_route_scopes_by_project() {
+        let (service, app) = stub_app();
+        let json = get_json(app, "/tags?project_id=alpha&collection=notes").await;
+        assert_eq!(json["collection"], "notes");
+        assert_eq!(json["project_id"], "alpha");
+        assert_eq!(json["tags"], json!(["api", "docs"]));
+        assert_eq!(
+            service.tag_scopes.lock().await.as_slice(),
+            [("notes".to_string(), Some("alpha".to_string()))]
+        );
+    }
+
+    #[tokio::test]
+    async fn storage_stats_route_reports_every_collection_or_one_project() {
+        let (_service, app) = stub_app();
+        let json = get_json(app.clone(), "/storage-stats").await;
+        assert_eq!(json["approximate"], true);
+        assert_eq!(json["collections"].as_array().map(Vec::len), Some(2));
+        assert_eq!(json["total_points"], 20);
+        // 10 points × (4 × float32 vector bytes + 100 payload bytes).
+        assert_eq!(json["total_estimated_bytes"], 2_320);
+
+        let json = get_json(
+            app.clone(),
+            "/storage-stats?collection=notes&project_id=alpha",
+        )
+        .await;
+        assert_eq!(json["project_id"], "alpha");
+        assert_eq!(json["collections"][0]["collection"], "notes");
+        assert_eq!(json["collections"][0]["payload_bytes"], 1_000);
+        assert_eq!(json["collections"][0]["vector_bytes"], 160);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/storage-stats?collection=..%2Fpoints")
+                    .body(Body::empty())
+                    .expect("request"),
+            )
+            .await
+            .expect("router response");
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn search_route_echoes_partitions_and_caps_fan_out_at_the_default_limit() {
+        let (service, app) = stub_app_with_partitions(
+            (1..=8)
+                .map(|month| format!("memories-2025-{month:02}"))
+                .collect(),
+        );
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/search")
+                    .header("content-type", "application/json")
+                    .body(Body::from(json!({ "query_text": "keys" }).to_string()))
+                    .expect("request"),
+            )
+            .await
+            .expect("router response");
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("body bytes");
+        let json: serde_json::Value = serde_json::from_slice(&body).expect("json body");
+        assert_eq!(service.searches.lock().await.len(), 9);
+        assert_eq!(json["partitions_searched"][0], "memories-2025-01");
+        assert_eq!(
+            json["partitions_searched"].as_array().map(Vec::len),
+            Some(8)
+        );
+        assert_eq!(
+            json["results"].as_array().expect("results").len(),
+            crate::config::get_config().search_default_limit
+        );
+    }
+
+    #[tokio::test]
+    async fn search_route_returns_hits_and_rejects_empty_queries() {
+        let (service, app) = stub_app();
+        let request = |body: serde_json::Value| {
+            Request::builder()
+                .method(Method::POST)
+                .uri("/search")
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .expect("request")
+        };
+
+        let response = app
+            .clone()
+            .oneshot(request(json!({ "query_text": "keys", "tags": ["docs"] })))
+            .await
+            .expect("router response");
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("body bytes");
+        let json: serde_json::Value = serde_json::from_slice(&body).expect("json body");
+        assert_eq!(
+            json["collection"],
+            crate::config::get_config().qdrant_collection_name
+        );
+        assert_eq!(json["results"][0]["id"], "memory-1");
+        assert_eq!(json["results"][0]["tags"], json!(["docs"]));
+        assert!(json.get("partitions_searched").is_none());
+        let searches = service.searches.lock().await;
+        assert_eq!(searches[0].tags.as_deref(), Some(&["docs".to_string()][..]));
+        drop(searches);
+
+        let response = app
+            .clone()
+            .oneshot(request(json!({ "query_text": "  " })))
+            .await
+            .expect("router response");
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        for invalid in [
+            json!({ "query_text": "keys", "memory_type": "diary" }),
+            json!({ "query_text": "keys", "score_threshold": 1.5 }),
+            json!({ "query_text": "keys", "tags": [" "] }),
+        ] {
+            let response = app
+                .clone()
+                .oneshot(request(invalid.clone()))
+                .await
+                .expect("router response");
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{invalid}");
+        }
+
+        let response = app
+            .oneshot(request(json!({ "query_text": "keys", "limit": 10_000 })))
+            .await
+            .expect("router response");
+        assert_eq!(response.status(), StatusCode::OK);
+        let searches = service.searches.lock().await;
+        assert_eq!(searches.len(), 2);
+        assert_eq!(
+            searches[1].limit,
+            Some(crate::config::get_config().search_max_limit)
+        );
+    }
+
+    #[tokio::test]
+    async fn summarize_route_normalizes_the_window_and_rejects_unknown_strategies() {
+        let (service, app) = stub_app();
+        let request = |body: serde_json::Value| {
+            Request::builder()
+                .method(Method::POST)
+                .uri("/summarize")
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .expect("request")
+        };
+
+        let response = app
+            .clone()
+            .oneshot(request(json!({
+                "project_id": "alpha",
+                "time_range": { "start": "2025-01-01", "end": "2025-01-07" },
+                "strategy": "extractive"
+            })))
+            .await
+            .expect("router response");
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("body bytes");
+        let json: serde_json::Value = serde_json::from_slice(&body).expect("json body");
+        assert_eq!(json["upserted_memory_id"], "summary-1");
+        assert_eq!(json["source_memory_ids"], json!(["memory-1"]));
+        let summaries = service.summaries.lock().await;
+        assert_eq!(
+            summaries[0].time_range.start.as_deref(),
+            Some("2025-01-01T00:00:00Z")
+        );
+        assert_eq!(
+            summaries[0].time_range.end.as_deref(),
+            Some("2025-01-07T23:59:59.999999999Z")
+        );
+        // No explicit collection, so the service may also read monthly partitions.
+        assert_eq!(summaries[0].collection, None);
+        drop(summaries);
+
+        let response = app
+            .oneshot(request(json!({
+                "time_range": { "start": "2025-01-01", "end": "2025-01-07" },
+                "strategy": "poetic"
+            })))
+            .await
+            .expect("router response");
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn search_route_forwards_filters_to_processing_api() {
+        let (service, app) = stub_app();
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/search")
+                    .header("content-type", "application/json")
+                    .body(Body::from(
+                        json!({
+                            "query_text": "rotation",
+                            "collection": "notes",
+                            "project_id": "alpha",
+                            "memory_type": "semantic",
+                            "limit": 3,
+                            "score_threshold": 0.5
+                        })
+                        .to_string(),
+                    ))
+                    .expect("request"),
+            )
+            .await
+            .expect("router response");
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let searches = service.searches.lock().await;
+        assert_eq!(searches.len(), 1);
+        let search = &searches[0];
+        assert_eq!(search.query_text, "rotation");
+        assert_eq!(search.collection.as_deref(), Some("notes"));
+        assert_eq!(search.project_id.as_deref(), Some("alpha"));
+        assert_eq!(search.memory_type.as_deref(), Some("semantic"));
+        assert_eq!(search.limit, Some(3));
+        assert_eq!(search.score_threshold, Some(0.5));
+        assert!(search.tags.is_none());
+    }
+
+    #[tokio::test]
+    async fn routes_reject_unsafe_collection_names() {
+        let (service, app) = stub_app();
+        let post = |uri: &str, body: serde_json::Value| {
+            Request::builder()
+                .method(Method::POST)
+                .uri(uri)
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .expect("request")
+        };
+        let requests = [
+            post(
+                "/index",
+                json!({ "text": "doc", "collection": "../collections" }),
+            ),
+            post("/search", json!({ "query_text": "q", "collection": "a/b" })),
+            post("/collections", json!({ "name": "notes/points" })),
+            post("/collections", json!({ "name": "mémoire" })),
+            Request::builder()
+                .uri("/projects?collection=notes%3Fwait%3Dfalse")
+                .body(Body::empty())
+                .expect("request"),
+            Request::builder()
+                .uri("/tags?collection=%E8%A8%98%E6%86%B6")
+                .body(Body::empty())
+                .expect("request"),
+        ];
+
+        for request in requests {
+            let uri = request.uri().clone();
+            let response = app.clone().oneshot(request).await.expect("router response");
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{uri}");
+            let body = to_bytes(response.into_body(), usize::MAX)
+                .await
+                .expect("body bytes");
+            let message = String::from_utf8_lossy(&body);
+            assert!(message.contains("invalid character"), "{uri}: {message}");
+        }
+
+        assert!(service.recorded_calls().await.is_empty());
+        assert!(service.searches.lock().await.is_empty());
+        assert!(service.tag_scopes.lock().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn import_route_streams_lines_and_reports_malformed_ones() {
+        let (service, app) = stub_app();
+        let vector = vec![0.5; crate::config::get_config().embedding_dimension];
+        let ndjson = [
+            json!({ "text": "Rotate keys quarterly", "project_id": "ops" }).to_string(),
+            json!({ "text": "Pre-embedded note", "vector": vector, "tags": ["imported"] })
+                .to_string(),
+            String::new(),
+            "{\"text\": \"unterminated".to_string(),
+            json!({ "text": "Wrong size", "vector": [1.0] }).to_string(),
+            json!({ "text": "Trailing line without newline" }).to_string(),
+        ]
+        .join("\n");
+        // Split mid-line so records straddle chunk boundaries, as a real upload would.
+        let chunks: Vec<Result<axum::body::Bytes, std::io::Error>> = ndjson
+            .as_bytes()
+            .chunks(7)
+            .map(|chunk| Ok(axum::body::Bytes::copy_from_slice(chunk)))
+            .collect();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/collections/imports/import")
+                    .header(header::CONTENT_TYPE, "application/x-ndjson")
+                    .body(Body::from_stream(futures_util::stream::iter(chunks)))
+                    .expect("request"),
+            )
+            .await
+            .expect("router response");
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("body bytes");
+        let json: serde_json::Value = serde_json::from_slice(&body).expect("json body");
+        assert_eq!(json["collection"], "imports");
+        assert_eq!(json["lines"], 5);
+        assert_eq!(json["imported"], 3);
+        assert_eq!(json["reembedded"], 2);
+        assert_eq!(json["reused_vectors"], 1);
+        assert_eq!(json["failed"], 2);
+        let failed_lines: Vec<u64> = json["errors"]
+            .as_array()
+            .expect("errors")
+            .iter()
+            .map(|error| error["line"].as_u64().expect("line"))
+            .collect();
+        assert_eq!(failed_lines, [4, 5]);
+
+        let texts: Vec<String> = service
+            .recorded_calls()
+            .await
+            .into_iter()
+            .map(|call| call.text)
+            .collect();
+        assert_eq!(
+            texts,
+            ["Rotate keys quarterly", "Trailing line without newline"]
+        );
+        let imports = service.imports.lock().await;
+        assert_eq!(imports.len(), 1);
+        assert_eq!(imports[0].text, "Pre-embedded note");
+        assert_eq!(
+            imports[0].metadata.tags.as_deref(),
+            Some(&["imported".to_string()][..])
+        );
+    }
+
+    #[tokio::test]
+    async fn import_route_maps_chroma_exports_and_reports_each_record() {
+        let (service, app) = stub_app();
+        let vector = vec![0.25; crate::config::get_config().embedding_dimension];
+        let export = json!({
+            "ids": ["kept", "resized", "blank", "bad-tags"],
+            "documents": ["Reuse my vector", "Embed me again", null, "Tagged oddly"],
+            "metadatas": [
+                { "team": "ops", "labels": "alpha,beta" },
+                { "team": "ops", "created": "2024-05-01" },
+                null,
+                { "labels": 7 }
+            ],
+            "embeddings": [vector, [0.1, 0.2], null, null]
+        });
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/collections/imports/import?format=chroma&project_id_field=team&tags_field=labels&timestamp_field=created")
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(export.to_string()))
+                    .expect("request"),
+            )
+            .await
+            .expect("router response");
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("body bytes");
+        let json: serde_json::Value = serde_json::from_slice(&body).expect("json body");
+        assert_eq!(json["format"], "chroma");
+        assert_eq!(json["records"], 4);
+        assert_eq!(json["imported"], 2);
+        assert_eq!(json["reused_vectors"], 1);
+        assert_eq!(json["reembedded"], 1);
+        assert_eq!(json["skipped"], 2);
+        let statuses: Vec<&str> = json["outcomes"]
+            .as_array()
+            .expect("outcomes")
+            .iter()
+            .map(|outcome| outcome["status"].as_str().expect("status"))
+            .collect();
+        assert_eq!(statuses, ["imported", "reembedded", "skipped", "skipped"]);
+        assert!(
+            json["outcomes"][1]["reason"]
+                .as_str()
+                .expect("mismatch reason")
+                .contains("re-embedded")
+        );
+        assert_eq!(json["outcomes"][2]["reason"], "record has no text");
+        assert_eq!(json["outcomes"][3]["id"], "bad-tags");
+
+        let imports = service.imports.lock().await;
+        assert_eq!(imports[0].metadata.project_id.as_deref(), Some("ops"));
+        assert_eq!(
+            imports[0].metadata.tags,
+            Some(vec!["alpha".to_string(), "beta".to_string()])
+        );
+        let calls = service.recorded_calls().await;
+        assert_eq!(calls[0].text, "Embed me again");
+        assert_eq!(
+            calls[0].metadata.timestamp.as_deref(),
+            Some("2024-05-01T00:00:00Z")
+        );
+    }
+
+    #[tokio::test]
+    async fn import_route_rejects_unknown_formats_and_stray_mappings() {
+        let (_, app) = stub_app();
+        for uri in [
+            "/collections/imports/import?format=pinecone",
+            "/collections/imports/import?tags_field=labels",
+            "/collections/imports/import?format=mem0",
+        ] {
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .method(Method::POST)
+                        .uri(uri)
+                        .body(Body::from("{}"))
+                        .expect("request"),
+                )
+                .await
+                .expect("router response");
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{uri}");
+        }
+    }
+
+    #[tokio::test]
+    async fn ui_route_serves_html() {
+        let (_, app) = stub_app();
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/ui")
+                    .body(Body::empty())
+                    .expect("request"),
+            )
+            .await
+            .expect("router response");
+        assert_eq!(response.status(), StatusCode::OK);
+        let content_type = response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .expect("content type");
+        assert!(content_type.starts_with("text/html"));
+        let body = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("body bytes");
+        assert!(String::from_utf8_lossy(&body).contains("/search"));
+    }
+
+    #[tokio::test]
+    async fn cors_headers_echo_only_same_origin() {
+        let (_, app) = stub_app();
+        let request = |origin: &str| {
+            Request::builder()
+                .uri("/projects")
+                .header(header::HOST, "127.0.0.1:4100")
+                .header(header::ORIGIN, origin)
+                .body(Body::empty())
+                .expect("request")
+        };
+
+        let response = app
+            .clone()
+            .oneshot(request("http://127.0.0.1:4100"))
+            .await
+            .expect("router response");
+        assert_eq!(
+            response.headers().get(header::ACCESS_CONTROL_ALLOW_ORIGIN),
+            Some(&"http://127.0.0.1:4100".parse().unwrap())
+        );
+
+        let response = app
+            .oneshot(request("https://evil.example"))
+            .await
+            .expect("router response");
+        assert!(
+            response
+                .headers()
+                .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+                .is_none()
+        );
+    }
+
+    #[derive(Clone, Debug)]
+    struct IngestCall {
+        collection: String,
+        text: String,
+        metadata: IngestMetadata,
+    }
+
+    /// Collection and optional project recorded for each `list_tags` call.
+    type TagScope = (String, Option<String>);
+
+    #[derive(Clone)]
+    struct StubProcessingService {
+        calls: Arc<Mutex<Vec<IngestCall>>>,
+        tag_scopes: Arc<Mutex<Vec<TagScope>>>,
+        searches: Arc<Mutex<Vec<SearchRequest>>>,
+        imports: Arc<Mutex<Vec<ImportedVector>>>,
+        summaries: Arc<Mutex<Vec<SummarizeRequest>>>,
+        /// Collections `read_targets` fans out to; empty reads only the requested collection.
+        partitions: Vec<String>,
+        outcome: ProcessingOutcome,
+    }
+
+    impl StubProcessingService {
+        fn new(outcome: ProcessingOutcome) -> Self {
+            Self {
+                calls: Arc::new(Mutex::new(Vec::new())),
+                tag_scopes: Arc::new(Mutex::new(Vec::new())),
+                searches: Arc::new(Mutex::new(Vec::new())),
+                imports: Arc::new(Mutex::new(Vec::new())),
+                summaries: Arc::new(Mutex::new(Vec::new())),
+                partitions: Vec::new(),
+                outcome,
+            }
+        }
+
+        async fn recorded_calls(&self) -> Vec<IngestCall> {
+            self.calls.lock().await.clone()
+        }
+    }
+
+    #[async_trait]
+    impl ProcessingApi for StubProcessingService {
+        async fn process_and_index(
+            &self,
+            collection_name: &str,
+            text: String,
+            metadata: IngestMetadata,
+        ) -> Result<ProcessingOutcome, crate::processing::ProcessingError> {
+            let mut guard = self.calls.lock().await;
+            guard.push(IngestCall {
+                collection: collection_name.to_string(),
+                text,
+                metadata,
+            });
+            Ok(self.outcome.clone())
+        }
+
+        async fn import_vectors(
+            &self,
+            _collection_name: &str,
+            records: Vec<ImportedVector>,
+        ) -> Result<usize, crate::processing::ProcessingError> {
+            let count = records.len();
+            self.imports.lock().await.extend(records);
+            Ok(count)
+        }
+
+        async fn create_collection(
+            &self,
+            _collection_name: &str,
+            _vector_size: Option<u64>,
+            _topology: crate::qdrant::CollectionTopology,
+        ) -> Result<(), crate::processing::ProcessingError> {
+            Ok(())
+        }
+
+        async fn ensure_collection(
+            &self,
+            _collection_name: &str,
+        ) -> Result<(), crate::processing::ProcessingError> {
+            Ok(())
+        }
+
+        async fn vector_dimension(
+            &self,
+            _collection_name: &str,
+        ) -> Result<usize, crate::processing::ProcessingError> {
+            Ok(crate::config::get_config().embedding_dimension)
+        }
+
+        async fn list_collections(
+            &self,
+        ) -> Result<Vec<String>, crate::processing::ProcessingError> {
+            Ok(vec![])
+        }
+
+        async fn list_projects(
+            &self,
+            _collection_name: &str,
+        ) -> Result<BTreeSet<String>, crate::processing::ProcessingError> {
+            Ok(["beta".to_string(), "alpha".to_string()].into())
+        }
+
+        async fn list_tags(
+            &self,
+            collection_name: &str,
+            project_id: Option<&str>,
+        ) -> Result<BTreeSet<String>, crate::processing::ProcessingError> {
+            self.tag_scopes
+                .lock()
+                .await
+                .push((collection_name.to_string(), project_id.map(str::to_string)));
+            Ok(["docs".to_string(), "api".to_string()].into())
+        }
+
+        async fn search_memories(
+            &self,
+            request: ValidatedSearchRequest,
+        ) -> Result<SearchOutcome, SearchError> {
+            let request = request.into_inner();
+            let effective_query = request.query_text.clone();
+            self.searches.lock().await.push(request);
+            let hits = vec![SearchHit {
+                id: "memory-1".into(),
+                score: 0.9,
+                text: Some("Rotate keys quarterly".into()),
+                project_id: Some("alpha".into()),
+                memory_type: Some("semantic".into()),
+                tags: Some(vec!["docs".into()]),
+                timestamp: Some("2025-01-01T00:00:00Z".into()),
+                source_uri: None,
+                doc_id: None,
+                chunk_index: None,
+                ingested_at: None,
+                embedding_model: None,
+                citations: None,
+                links: None,
+                collection: None,
+            }];
+            Ok(SearchOutcome {
+                hits,
+                effective_query,
+                query_truncated: false,
+                query_compressed: false,
+            })
+        }
+
+        async fn summarize_memories(
+            &self,
+            request: SummarizeRequest,
+        ) -> Result<SummarizeOutcome, SummarizeError> {
+            self.summaries.lock().await.push(request);
+            Ok(SummarizeOutcome {
+                summary: "- Rotated keys".into(),
+                source_memory_ids: vec!["memory-1".into()],
+                citations: Vec::new(),
+                upserted_memory_id: "summary-1".into(),
+                strategy_used: "extractive".into(),
+                provider: None,
+                model: None,
+                consolidated: None,
+                abstractive_attempts: 0,
+                items_summarized: 1,
+                items_trimmed: 0,
+                label: None,
+                topic: None,
+            })
+        }
+
+        fn ingest_collection(&self, collection_name: &str, _metadata: &IngestMetadata) -> String {
+            collection_name.to_string()
+        }
+
+        async fn read_targets(
+            &self,
+            collection_name: &str,
+            _memory_type: Option<&str>,
+            _time_range: Option<&crate::processing::SearchTimeRange>,
+        ) -> Result<(Vec<String>, Option<Vec<String>>), crate::processing::ProcessingError>
+        {
+            if self.partitions.is_empty() {
+                return Ok((vec![collection_name.to_string()], None));
+            }
+            let mut targets = vec![collection_name.to_string()];
+            targets.extend(self.partitions.iter().cloned());
+            Ok((targets, Some(self.partitions.clone())))
+        }
+
+        async fn collection_info(
+            &self,
+            _collection_name: &str,
+        ) -> Result<CollectionInfo, crate::processing::ProcessingError> {
+            Ok(CollectionInfo::default())
+        }
+
+        async fn storage_stats(
+            &self,
+            collection: Option<&str>,
+            project_id: Option<&str>,
+        ) -> Result<StorageStatsReport, crate::processing::ProcessingError> {
+            let collections = match collection {
+                Some(name) => vec![name.to_string()],
+                None => vec!["alpha".to_string(), "beta".to_string()],
+            };
+            let stats = collections
+                .into_iter()
+                .map(|name| {
+                    StorageStats::from_estimate(
+                        name,
+                        project_id.map(str::to_string),
+                        &StorageEstimate::compute(10, 4, None, &[100]),
+                        &CollectionInfo::default(),
+                    )
+                })
+                .collect();
+            Ok(StorageStatsReport::new(
+                project_id.map(str::to_string),
+                stats,
+            ))
+        }
+
+        async fn warm_collection(
+            &self,
+            _collection_name: &str,
+            _timeout: Duration,
+            warmup_queries: usize,
+        ) -> Result<WarmupOutcome, crate::processing::ProcessingError> {
+            Ok(WarmupOutcome {
+                before: CollectionInfo::default(),
+                after: CollectionInfo::default(),
+                warmup_queries,
+                elapsed: Duration::ZERO,
+            })
+        }
+
+        async fn qdrant_health(&self) -> QdrantHealthSnapshot {
+            QdrantHealthSnapshot {
+                reachable: true,
+                default_collection_present: true,
+                error: None,
+                version: None,
+                read_replica: None,
+                probe: "healthz",
+            }
+        }
+
+        fn metrics_snapshot(&self) -> MetricsSnapshot {
+            MetricsSnapshot {
+                documents_indexed: 0,
+                chunks_indexed: 0,
+                last_chunk_size: None,
+                dedupe: Default::default(),
+                invalid_embeddings: 0,
+            }
+        }
+    }
+
+    async fn error_body(response: axum::response::Response) -> serde_json::Value {
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+        let body = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("body bytes");
+        serde_json::from_slice(&body).expect("json error body")
+    }
+
+    #[tokio::test]
+    async fn validation_errors_use_the_error_envelope() {
+        let (_, app) = stub_app();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/search")
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(json!({ "query_text": "  " }).to_string()))
+                    .expect("request"),
+            )
+            .await
+            .expect("router response");
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = error_body(response).await;
+        assert_eq!(body["error"]["code"], "invalid_params");
+        assert!(
+            body["error"]["message"]
+                .as_str()
+                .is_some_and(|message| message.contains("query_text"))
+        );
+        assert!(body["error"].get("details").is_none());
+    }
+
+    #[tokio::test]
+    async fn provider_and_missing_collection_failures_use_the_error_envelope() {
+        let unavailable = AppError::from(SearchError::Embedding(
+            EmbeddingClientError::ProviderUnavailable("connection refused".into()),
+        ))
+        .into_response();
+        assert_eq!(unavailable.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(
+            error_body(unavailable).await,
+            json!({
+                "error": {
+                    "code": "provider_unavailable",
+                    "message": "Failed to generate embeddings: Embedding provider unavailable: connection refused",
+                    "details": { "kind": "transient", "retryable": true }
+                }
+            })
+        );
+
+        let missing = AppError::from(ProcessingError::Qdrant(QdrantError::UnexpectedStatus {
+            status: StatusCode::NOT_FOUND,
+            body: "Collection `nope` doesn't exist!".into(),
+        }))
+        .into_response();
+        assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            error_body(missing).await["error"]["code"],
+            "collection_not_found"
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_projects_use_the_mcp_project_required_data() {
+        let rejection = require_project(true, "search", None).expect_err("project required");
+        let response = AppError::from(rejection.clone()).into_response();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = error_body(response).await;
+        assert_eq!(body["error"]["code"], "invalid_params");
+        assert_eq!(body["error"]["message"], rejection.message.as_ref());
+        assert_eq!(body["error"]["details"], rejection.data.expect("data"));
+        assert_eq!(body["error"]["details"]["code"], "PROJECT_REQUIRED");
+    }
+
+    #[test]
+    fn app_errors_map_status_from_error_kind() {
+        let status_of = |error: AppError| error.into_response().status();
+        let qdrant = |code: StatusCode| {
+            ProcessingError::Qdrant(QdrantError::UnexpectedStatus {
+                status: code,
+                body: String::new(),
+            })
+        };
+
+        assert_eq!(
+            status_of(qdrant(StatusCode::BAD_GATEWAY).into()),
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+        assert_eq!(
+            status_of(qdrant(StatusCode::UNPROCESSABLE_ENTITY).into()),
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            status_of(qdrant(StatusCode::UNAUTHORIZED).into()),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+        assert_eq!(
+            status_of(
+                SearchError::DimensionMismatch {
+                    expected: 256,
+                    actual: 3
+                }
+                .into()
+            ),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+}
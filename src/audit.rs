@@ -0,0 +1,193 @@
+//! Append-only audit trail of mutating operations.
+//!
+//! Every successful write (`push`, `new-collection`, `move-to-collection`, hash fixes from
+//! `verify-collection`, `reembed-fallback`, `summarize`, pre-embedded lines of an HTTP `import`)
+//! appends one JSON line describing what changed; a move writes one line for each collection. The trail is independent of `tracing` so operators can ship
+//! it to a different sink and keep it regardless of `RUST_LOG`. `AUDIT_LOG` selects the target:
+//! unset or `off` disables it, `stdout` prints the lines, and any other value is a file path
+//! opened in append mode.
+//!
+//! The MCP binary speaks JSON-RPC over stdout, so point `AUDIT_LOG` at a file when running it.
+
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use time::{OffsetDateTime, format_description::well_known::Rfc3339};
+
+/// Destination configured through `AUDIT_LOG`.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq)]
+pub enum AuditTarget {
+    /// No audit records are written.
+    #[default]
+    Disabled,
+    /// Records are printed to stdout.
+    Stdout,
+    /// Records are appended to the file at this path.
+    File(PathBuf),
+}
+
+impl AuditTarget {
+    /// Interpret an `AUDIT_LOG` value.
+    pub fn parse(raw: &str) -> Self {
+        match raw.trim() {
+            "" => Self::Disabled,
+            value if ["off", "none", "false", "0"].contains(&value.to_lowercase().as_str()) => {
+                Self::Disabled
+            }
+            value if value.eq_ignore_ascii_case("stdout") => Self::Stdout,
+            path => Self::File(PathBuf::from(path)),
+        }
+    }
+}
+
+/// Rows affected by a mutating operation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct AuditCounts {
+    /// Points created.
+    pub inserted: usize,
+    /// Points whose vector or payload was rewritten.
+    pub updated: usize,
+    /// Points removed.
+    pub deleted: usize,
+}
+
+/// One line of the audit trail.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct AuditRecord {
+    /// RFC3339 UTC time the operation completed.
+    pub timestamp: String,
+    /// Operation name, matching the MCP tool that performs it.
+    pub tool: &'static str,
+    /// Collection the operation wrote to.
+    pub collection: String,
+    /// Project the written memories belong to, when the operation is project-scoped.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    /// Rows affected.
+    #[serde(flatten)]
+    pub counts: AuditCounts,
+}
+
+impl AuditRecord {
+    /// Describe an operation that just completed.
+    pub fn new(
+        tool: &'static str,
+        collection: &str,
+        project_id: Option<String>,
+        counts: AuditCounts,
+    ) -> Self {
+        Self {
+            timestamp: OffsetDateTime::now_utc()
+                .format(&Rfc3339)
+                .unwrap_or_default(),
+            tool,
+            collection: collection.to_string(),
+            project_id,
+            counts,
+        }
+    }
+}
+
+/// Writes audit records as JSON lines to the configured sink.
+///
+/// Each record is written and flushed under a lock so concurrent operations never interleave
+/// lines. A failed write is reported through `tracing` but never fails the operation itself.
+pub struct AuditLogger {
+    sink: Option<Mutex<Box<dyn Write + Send>>>,
+}
+
+impl AuditLogger {
+    /// Logger that drops every record.
+    pub fn disabled() -> Self {
+        Self { sink: None }
+    }
+
+    /// Open the sink described by `target`.
+    ///
+    /// Fails when the audit file cannot be opened for appending.
+    pub fn open(target: &AuditTarget) -> io::Result<Self> {
+        let sink: Box<dyn Write + Send> = match target {
+            AuditTarget::Disabled => return Ok(Self::disabled()),
+            AuditTarget::Stdout => Box::new(io::stdout()),
+            AuditTarget::File(path) => Box::new(
+                std::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)?,
+            ),
+        };
+        Ok(Self::to_writer(sink))
+    }
+
+    /// Logger writing to an arbitrary sink.
+    pub fn to_writer(writer: impl Write + Send + 'static) -> Self {
+        Self {
+            sink: Some(Mutex::new(Box::new(writer))),
+        }
+    }
+
+    /// Append `record` to the trail.
+    pub fn record(&self, record: AuditRecord) {
+        let Some(sink) = &self.sink else {
+            return;
+        };
+        let mut line = match serde_json::to_vec(&record) {
+            Ok(line) => line,
+            Err(error) => {
+                tracing::warn!(error = %error, tool = record.tool, "Failed to encode audit record");
+                return;
+            }
+        };
+        line.push(b'\n');
+        let mut sink = sink.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Err(error) = sink.write_all(&line).and_then(|()| sink.flush()) {
+            tracing::warn!(error = %error, tool = record.tool, "Failed to write audit record");
+        }
+    }
+}
+
+impl Default for AuditLogger {
+    fn default() -> Self {
+        Self::disabled()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_audit_targets() {
+        assert_eq!(AuditTarget::parse(""), AuditTarget::Disabled);
+        assert_eq!(AuditTarget::parse(" OFF "), AuditTarget::Disabled);
+        assert_eq!(AuditTarget::parse("stdout"), AuditTarget::Stdout);
+        assert_eq!(
+            AuditTarget::parse("/var/log/rusty-mem/audit.jsonl"),
+            AuditTarget::File(PathBuf::from("/var/log/rusty-mem/audit.jsonl"))
+        );
+    }
+
+    #[test]
+    fn file_sink_appends_one_line_per_record() {
+        let path =
+            std::env::temp_dir().join(format!("rusty-mem-audit-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let logger = AuditLogger::open(&AuditTarget::File(path.clone())).expect("open");
+        for tool in ["push", "new-collection"] {
+            logger.record(AuditRecord::new(tool, "demo", None, AuditCounts::default()));
+        }
+
+        let contents = std::fs::read_to_string(&path).expect("audit file");
+        let _ = std::fs::remove_file(&path);
+        let tools: Vec<String> = contents
+            .lines()
+            .map(|line| {
+                let value: serde_json::Value = serde_json::from_str(line).expect("json line");
+                value["tool"].as_str().unwrap_or_default().to_string()
+            })
+            .collect();
+        assert_eq!(tools, ["push", "new-collection"]);
+    }
+}
@@ -522,7 +522,7 @@ fn process_churn(input: &Path, json_output: &Path, md_output: &Path, since: &str
     }
 
     let mut top_files: Vec<_> = file_counter.into_iter().collect();
-    top_files.sort_by(|a, b| b.1.cmp(&a.1));
+    top_files.sort_by_key(|entry| std::cmp::Reverse(entry.1));
     top_files.truncate(5);
 
     let summary = serde_json::json!({
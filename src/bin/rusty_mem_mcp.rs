@@ -12,6 +12,7 @@ use std::sync::Arc;
 async fn main() -> Result<()> {
     config::init_config();
     logging::init_tracing();
+    config::log_config();
 
     let processing = Arc::new(processing::ProcessingService::new().await);
     let server = RustyMemMcpServer::new(processing);
@@ -21,10 +22,9 @@ async fn main() -> Result<()> {
         .await
         .context("failed to start MCP server over stdio")?;
 
-    service
-        .waiting()
-        .await
-        .context("MCP server terminated unexpectedly")?;
+    let outcome = service.waiting().await;
+    logging::shutdown_tracing();
+    outcome.context("MCP server terminated unexpectedly")?;
 
     Ok(())
 }
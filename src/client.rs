@@ -0,0 +1,192 @@
+//! Typed async client for the HTTP API (feature `http-client`).
+//!
+//! [`RustyMemClient`] wraps `reqwest` and speaks the same [`dto`](crate::api::dto) types as the
+//! server handlers, so Rust services can push, search, and summarize without hand-written
+//! requests:
+//!
+//! ```no_run
+//! # async fn demo() -> Result<(), rustymcp::client::ClientError> {
+//! use rustymcp::{api::dto::SearchBody, client::RustyMemClient};
+//!
+//! let client = RustyMemClient::new("http://127.0.0.1:4100").with_auth_token("secret");
+//! let response = client
+//!     .search(&SearchBody {
+//!         query_text: "How do we rotate API keys?".into(),
+//!         limit: Some(5),
+//!         ..Default::default()
+//!     })
+//!     .await?;
+//! println!("{} hits", response.results.len());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Failed requests map onto [`ClientError`], whose variants follow the server's status mapping:
+//! `400` for invalid requests, `503` for transient or busy upstreams, and `500` for
+//! configuration and internal failures.
+
+use crate::api::dto::{
+    CollectionsResponse, ErrorResponse, IndexRequest, IndexResponse, MetricsResponse, SearchBody,
+    SearchResponse, SummarizeBody, SummarizeResponse,
+};
+use crate::logging::TracedSend;
+use crate::processing::ErrorKind;
+use reqwest::{Method, RequestBuilder, StatusCode, header};
+use serde::de::DeserializeOwned;
+
+/// Failure returned by [`RustyMemClient`] calls.
+#[derive(Debug, thiserror::Error)]
+pub enum ClientError {
+    /// The server rejected the request as invalid (`400`).
+    #[error("invalid request: {0}")]
+    InvalidRequest(String),
+    /// An upstream dependency was unavailable or the server was busy (`503`); retry later.
+    #[error("service unavailable: {0}")]
+    Unavailable(String),
+    /// The server failed because of its configuration or an internal error (`500`).
+    #[error("server error: {0}")]
+    Internal(String),
+    /// Any other non-success status, such as `401` from an authenticating proxy.
+    #[error("unexpected status {status}: {message}")]
+    Status {
+        /// HTTP status code.
+        status: u16,
+        /// Error message from the envelope, or the raw body when there is none.
+        message: String,
+    },
+    /// The request could not be sent or the response could not be read.
+    #[error("transport error: {0}")]
+    Transport(#[from] reqwest::Error),
+    /// The response body did not match the expected shape.
+    #[error("failed to decode response: {0}")]
+    Decode(#[from] serde_json::Error),
+}
+
+impl ClientError {
+    /// Server-side classification implied by the status code, when it maps onto one.
+    pub fn kind(&self) -> Option<ErrorKind> {
+        match self {
+            Self::InvalidRequest(_) => Some(ErrorKind::InvalidRequest),
+            Self::Unavailable(_) => Some(ErrorKind::Transient),
+            Self::Internal(_) => Some(ErrorKind::Internal),
+            Self::Status { .. } | Self::Transport(_) | Self::Decode(_) => None,
+        }
+    }
+
+    /// Whether the same request may succeed if retried unchanged.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Transport(source) => source.is_connect() || source.is_timeout(),
+            other => other.kind().is_some_and(ErrorKind::is_retryable),
+        }
+    }
+
+    fn from_status(status: StatusCode, body: String) -> Self {
+        // Prefer the message from the server's error envelope; proxies may answer with plain text.
+        let message = serde_json::from_str::<ErrorResponse>(&body)
+            .map(|envelope| envelope.error.message)
+            .unwrap_or(body);
+        match status {
+            StatusCode::BAD_REQUEST => Self::InvalidRequest(message),
+            StatusCode::SERVICE_UNAVAILABLE => Self::Unavailable(message),
+            StatusCode::INTERNAL_SERVER_ERROR => Self::Internal(message),
+            other => Self::Status {
+                status: other.as_u16(),
+                message,
+            },
+        }
+    }
+}
+
+/// Async client for a Rusty Memory HTTP server.
+#[derive(Debug, Clone)]
+pub struct RustyMemClient {
+    http: reqwest::Client,
+    base_url: String,
+    auth_token: Option<String>,
+}
+
+impl RustyMemClient {
+    /// Client for the server at `base_url`, for example `http://127.0.0.1:4100`.
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            auth_token: None,
+        }
+    }
+
+    /// Send `Authorization: Bearer <token>` with every request, for servers behind an
+    /// authenticating proxy.
+    pub fn with_auth_token(mut self, token: impl Into<String>) -> Self {
+        self.auth_token = Some(token.into());
+        self
+    }
+
+    /// Reuse a preconfigured `reqwest` client (timeouts, TLS roots, proxies).
+    pub fn with_http_client(mut self, http: reqwest::Client) -> Self {
+        self.http = http;
+        self
+    }
+
+    /// Base URL requests are sent to, without a trailing slash.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Chunk, embed, and store a document (`POST /index`).
+    pub async fn push(&self, request: &IndexRequest) -> Result<IndexResponse, ClientError> {
+        self.send(self.request(Method::POST, "index").json(request))
+            .await
+    }
+
+    /// Semantic search over stored memories (`POST /search`).
+    pub async fn search(&self, request: &SearchBody) -> Result<SearchResponse, ClientError> {
+        self.send(self.request(Method::POST, "search").json(request))
+            .await
+    }
+
+    /// Names of the collections on the server (`GET /collections`).
+    pub async fn collections(&self) -> Result<Vec<String>, ClientError> {
+        let response: CollectionsResponse =
+            self.send(self.request(Method::GET, "collections")).await?;
+        Ok(response.collections)
+    }
+
+    /// Ingestion counters (`GET /metrics`).
+    pub async fn metrics(&self) -> Result<MetricsResponse, ClientError> {
+        self.send(self.request(Method::GET, "metrics")).await
+    }
+
+    /// Summarize a time window and store the summary (`POST /summarize`).
+    pub async fn summarize(
+        &self,
+        request: &SummarizeBody,
+    ) -> Result<SummarizeResponse, ClientError> {
+        self.send(self.request(Method::POST, "summarize").json(request))
+            .await
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let builder = self
+            .http
+            .request(method, format!("{}/{path}", self.base_url));
+        match &self.auth_token {
+            Some(token) => builder.bearer_auth(token),
+            None => builder,
+        }
+    }
+
+    async fn send<T: DeserializeOwned>(&self, builder: RequestBuilder) -> Result<T, ClientError> {
+        let response = builder
+            .header(header::ACCEPT, "application/json")
+            .send_traced("rusty-mem")
+            .await?;
+        let status = response.status();
+        let body = response.text().await?;
+        if !status.is_success() {
+            return Err(ClientError::from_status(status, body));
+        }
+        Ok(serde_json::from_str(&body)?)
+    }
+}
@@ -4,21 +4,46 @@
 //! a global, read‑only view through `get_config`. The configuration powers both the HTTP API and
 //! the MCP server and includes:
 //!
-//! - Qdrant connectivity (`QDRANT_URL`, `QDRANT_COLLECTION_NAME`, `QDRANT_API_KEY?`).
+//! - Qdrant connectivity (`QDRANT_URL`, `QDRANT_READ_URL?`, `QDRANT_COLLECTION_NAME`,
+//!   `QDRANT_API_KEY?`, `QDRANT_API_KEYS?`, `QDRANT_HEALTH_PROBE?`, `STRICT_PAYLOAD_INDEXES?`,
+//!   `COLLECTION_PER_PROJECT?`, `EPISODIC_PARTITIONING?`).
 //! - Embedding provider/model (`EMBEDDING_PROVIDER`, `EMBEDDING_MODEL`, `EMBEDDING_DIMENSION`,
-//!   `OLLAMA_URL?`).
-//! - Chunking overrides (`TEXT_SPLITTER_CHUNK_SIZE?`, `TEXT_SPLITTER_CHUNK_OVERLAP?`,
-//!   `TEXT_SPLITTER_USE_SAFE_DEFAULTS?`).
-//! - Search ergonomics (`SEARCH_DEFAULT_LIMIT?`, `SEARCH_MAX_LIMIT?`,
-//!   `SEARCH_DEFAULT_SCORE_THRESHOLD?`).
+//!   `OLLAMA_URL?`, `INGEST_FALLBACK?`, `EMBEDDING_EXTRA_HEADERS?`,
+//!   `EMBEDDING_ALLOW_TRUNCATION?`, `EMBEDDING_DISK_CACHE_PATH?`, `EMBEDDING_TIMEOUT_MS?`,
+//!   `EMBEDDING_DIMENSION_AUTO?`).
+//! - Provider migration window (`EMBEDDING_PROVIDER_NEXT?`, `EMBEDDING_MODEL_NEXT?`,
+//!   `EMBEDDING_DIMENSION_NEXT?`).
+//! - Push screening (`TRANSCRIPT_POLICY?`).
+//! - Chunking overrides (`TEXT_SPLITTER_CHUNK_SIZE?`, `TEXT_SPLITTER_MIN_CHUNK_SIZE?`,
+//!   `TEXT_SPLITTER_STRICT?`, `TEXT_SPLITTER_CHUNK_OVERLAP?`, `TEXT_SPLITTER_USE_SAFE_DEFAULTS?`,
+//!   `TEXT_SPLITTER_STRATEGY?`, `TOKENIZER_FALLBACK?`).
+//! - Search ergonomics (`SEARCH_DEFAULT_LIMIT?`, `SEARCH_MAX_LIMIT?`, `SEARCH_CLAMP_LIMIT?`,
+//!   `SEARCH_DEFAULT_SCORE_THRESHOLD?`, `SEARCH_NORMALIZE_SCORES?`, `MAX_QUERY_TOKENS?`,
+//!   `MAX_RESPONSE_BYTES?`, `ANSWER_CONTEXT_MAX_TOKENS?`).
 //! - Summarization (`SUMMARIZATION_PROVIDER?`, `SUMMARIZATION_MODEL?`,
-//!   `SUMMARIZATION_MAX_WORDS?`).
+//!   `SUMMARIZATION_MAX_WORDS?`, `SUMMARIZATION_MAX_PROMPT_TOKENS?`, `SUMMARIZATION_MAX_RETRIES?`,
+//!   `SUMMARIZATION_TIMEOUT_MS?`).
+//! - Project scoping for shared deployments (`REQUIRE_PROJECT_FILTER?`).
+//! - Project and tag listing cache (`LISTING_CACHE_TTL_MS?`).
 //! - HTTP server port (`SERVER_PORT?`).
+//! - Audit trail destination (`AUDIT_LOG?`).
+//! - Ingest cursor directory (`STATE_DIR?`).
+//! - Collection bootstrap manifest (`COLLECTION_MANIFEST?`).
+//! - Push defaults (`CLIENT_PROJECT_MAP?`, `MEMORY_TYPE_RULES?`, `PROJECT_DEFAULT_MEMORY_TYPE?`).
+//! - Background maintenance (`MAINTENANCE_INTERVAL_MINUTES?`, `MAINTENANCE_COLLECTIONS?`,
+//!   `MAINTENANCE_EPISODIC_TTL_DAYS?`).
 //!
 //! Most fields are optional with sensible defaults; invalid combinations are flagged early with
 //! descriptive errors so misconfiguration is easy to diagnose.
+use crate::audit::AuditTarget;
+use crate::embedding::openai_model_dimension;
+use crate::processing::chunking::{DEFAULT_MIN_CHUNK_SIZE_OVERRIDE, override_below_floor};
+use crate::processing::classify::MemoryTypeRule;
+use crate::processing::sanitize::sanitize_memory_type;
 use serde::Deserialize;
+use std::collections::BTreeMap;
 use std::env;
+use std::path::PathBuf;
 use std::sync::OnceLock;
 use thiserror::Error;
 
@@ -38,42 +63,135 @@ pub enum ConfigError {
 pub struct Config {
     /// Base URL of the Qdrant instance that stores embeddings.
     pub qdrant_url: String,
+    /// Optional read replica URL for searches, scrolls, and counts.
+    pub qdrant_read_url: Option<String>,
+    /// How the health resource checks Qdrant (`QDRANT_HEALTH_PROBE`).
+    pub qdrant_health_probe: QdrantHealthProbe,
     /// Name of the Qdrant collection used for document storage.
     pub qdrant_collection_name: String,
     /// Optional API key required to access Qdrant.
     pub qdrant_api_key: Option<String>,
+    /// Labelled API keys (`read=key,write=key`); each overrides `qdrant_api_key` for its operations.
+    pub qdrant_api_keys: BTreeMap<String, String>,
+    /// Shard count sent when creating collections; unset leaves Qdrant's default.
+    pub qdrant_shard_number: Option<u32>,
+    /// Replication factor sent when creating collections; unset leaves Qdrant's default.
+    pub qdrant_replication_factor: Option<u32>,
+    /// Fail collection setup when Qdrant refuses a payload index (`STRICT_PAYLOAD_INDEXES`).
+    pub strict_payload_indexes: bool,
+    /// Route requests without a `collection` to `<QDRANT_COLLECTION_NAME>-<project_id>`
+    /// (`COLLECTION_PER_PROJECT`).
+    pub collection_per_project: bool,
+    /// Split episodic memories into time-partitioned collections (`EPISODIC_PARTITIONING`).
+    pub episodic_partitioning: EpisodicPartitioning,
     /// Embedding provider used to generate vector representations.
     pub embedding_provider: EmbeddingProvider,
     /// Optional override for the automatic chunk size selection.
     pub text_splitter_chunk_size: Option<usize>,
+    /// Floor applied to `text_splitter_chunk_size`; smaller overrides are raised with a warning.
+    pub text_splitter_min_chunk_size: usize,
     /// Optional overlap between sequential chunks produced by the splitter.
     pub text_splitter_chunk_overlap: Option<usize>,
     /// Opt-in flag enabling safer chunk-size defaults tuned for retrieval quality.
     pub text_splitter_use_safe_defaults: bool,
+    /// How chunk boundaries are chosen (`TEXT_SPLITTER_STRATEGY`).
+    pub text_splitter_strategy: ChunkStrategy,
+    /// Minimum composite quality score a chunk needs to be indexed; `None` indexes every chunk.
+    pub chunk_quality_min: Option<f32>,
+    /// Token counter used when tiktoken encoding data cannot be loaded.
+    pub tokenizer_fallback: TokenizerFallback,
     /// Embedding model identifier passed to the provider.
     pub embedding_model: String,
     /// Dimensionality of the produced vectors.
     pub embedding_dimension: usize,
+    /// `EMBEDDING_DIMENSION` as configured, when `EMBEDDING_DIMENSION_AUTO` replaced it with the
+    /// model's known output size.
+    pub configured_embedding_dimension: Option<usize>,
+    /// Text prepended to search queries before embedding (e.g. `query: ` for e5 models).
+    pub embedding_query_prefix: String,
+    /// Text prepended to stored documents before embedding (e.g. `passage: ` for e5 models).
+    pub embedding_doc_prefix: String,
     /// Base URL of the Ollama runtime providing embeddings (when enabled).
     pub ollama_url: Option<String>,
+    /// Headers added to every embedding request, keyed by lowercase header name.
+    pub embedding_extra_headers: BTreeMap<String, String>,
+    /// Truncate over-long embeddings to `embedding_dimension` (Matryoshka models) instead of
+    /// rejecting them.
+    pub embedding_allow_truncation: bool,
+    /// File persisting computed embeddings across restarts; unset disables the disk cache.
+    pub embedding_disk_cache_path: Option<PathBuf>,
+    /// Per-request timeout for the embedding provider in milliseconds; `0` waits indefinitely.
+    pub embedding_timeout_ms: u64,
+    /// Provider being migrated to (`EMBEDDING_PROVIDER_NEXT`); ingest dual-writes while set.
+    pub embedding_next: Option<NextEmbedding>,
+    /// Degraded-ingest policy applied when the embedding provider fails.
+    pub ingest_fallback: IngestFallback,
+    /// What `push` does with text that reads as a pasted chat transcript (`TRANSCRIPT_POLICY`).
+    pub transcript_policy: TranscriptPolicy,
     /// Optional override for the HTTP server port.
     pub server_port: Option<u16>,
     /// Default number of results returned by search when callers omit `limit`.
     pub search_default_limit: usize,
     /// Maximum number of results allowed per search request.
     pub search_max_limit: usize,
+    /// Clamp oversized `limit` values to `search_max_limit` instead of rejecting the request.
+    pub search_clamp_limit: bool,
     /// Default similarity threshold applied when callers omit `score_threshold`.
     pub search_default_score_threshold: f32,
+    /// Map search scores into `0..1` according to the collection's distance metric.
+    pub search_normalize_scores: bool,
+    /// Reject MCP `search`, `find-summaries`, and `summarize` calls that omit `project_id`.
+    pub require_project_filter: bool,
+    /// Lifetime of cached MCP search responses in milliseconds; `0` disables the cache.
+    pub search_cache_ttl_ms: u64,
+    /// Lifetime of cached project and tag listings in milliseconds; `0` disables the cache.
+    pub listing_cache_ttl_ms: u64,
+    /// Token budget for `query_text`; longer queries are shortened before embedding.
+    pub max_query_tokens: usize,
+    /// Cap on serialized search and listing responses; trailing results are dropped to fit it.
+    pub max_response_bytes: Option<usize>,
+    /// Default token budget of `search` in `answer_context` mode when `max_context_tokens` is
+    /// omitted.
+    pub answer_context_max_tokens: usize,
     /// Summarization provider selection.
     pub summarization_provider: SummarizationProvider,
     /// Optional model identifier for abstractive summarization.
     pub summarization_model: Option<String>,
     /// Default word budget for summaries.
     pub summarization_max_words: usize,
+    /// Token ceiling for abstractive prompts; the oldest memories are dropped to fit it.
+    pub summarization_max_prompt_tokens: Option<usize>,
+    /// Extra attempts after a transient summarization failure (connection error, 5xx, 429).
+    pub summarization_max_retries: usize,
+    /// Per-attempt timeout for the summarization provider in milliseconds; `0` waits
+    /// indefinitely.
+    pub summarization_timeout_ms: u64,
+    /// Destination of the audit trail of mutating operations.
+    pub audit_log: AuditTarget,
+    /// Directory holding persistent ingest cursors; unset disables unchanged-source skipping.
+    pub state_dir: Option<PathBuf>,
+    /// TOML manifest of collections applied at startup and by the `apply-manifest` tool.
+    pub collection_manifest: Option<PathBuf>,
+    /// Default `project_id` for `push`, keyed by lowercase MCP client name (`cursor=frontend`).
+    pub client_project_map: BTreeMap<String, String>,
+    /// Ordered rules inferring `memory_type` for pushes that omit it.
+    pub memory_type_rules: Vec<MemoryTypeRule>,
+    /// `memory_type` applied to pushes into a project when neither the call nor a rule sets one.
+    pub project_default_memory_type: BTreeMap<String, String>,
+    /// Maximum number of MCP tool calls executing at once.
+    pub mcp_max_concurrent_tools: usize,
+    /// Maximum number of MCP tool calls waiting for a slot before new calls are rejected.
+    pub mcp_max_queued_tools: usize,
+    /// Minutes between background maintenance passes; unset disables them.
+    pub maintenance_interval_minutes: Option<u32>,
+    /// Collections maintained by each pass; empty means `qdrant_collection_name`.
+    pub maintenance_collections: Vec<String>,
+    /// Age in days after which maintenance prunes episodic memories; unset keeps them.
+    pub maintenance_episodic_ttl_days: Option<u32>,
 }
 
 /// Supported embedding backends for the processing pipeline.
-#[derive(Clone, Copy, Debug, Deserialize)]
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum EmbeddingProvider {
     /// Local Ollama runtime.
@@ -82,6 +200,17 @@ pub enum EmbeddingProvider {
     OpenAI,
 }
 
+/// Embedding provider, model, and dimension a provider migration writes candidate vectors with.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+pub struct NextEmbedding {
+    /// `EMBEDDING_PROVIDER_NEXT`.
+    pub provider: EmbeddingProvider,
+    /// `EMBEDDING_MODEL_NEXT`, defaulting to `EMBEDDING_MODEL`.
+    pub model: String,
+    /// `EMBEDDING_DIMENSION_NEXT`, defaulting to `EMBEDDING_DIMENSION`.
+    pub dimension: usize,
+}
+
 /// Supported summarization backends for abstractive summaries.
 #[derive(Clone, Copy, Debug, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -92,6 +221,89 @@ pub enum SummarizationProvider {
     Ollama,
 }
 
+/// Behaviour of `push` when the embedding provider cannot produce vectors.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum IngestFallback {
+    /// Fail the ingestion and surface the embedding error.
+    #[default]
+    None,
+    /// Embed with the deterministic encoder and tag the points for later repair.
+    Deterministic,
+}
+
+/// Layout of episodic memories across collections.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum EpisodicPartitioning {
+    /// Store episodic memories alongside every other memory type.
+    #[default]
+    Off,
+    /// Store episodic memories in `<collection>-YYYY-MM` by memory timestamp.
+    Monthly,
+}
+
+/// Handling of pushed text that looks like a pasted chat transcript.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TranscriptPolicy {
+    /// Index the text unchanged and add a warning to the push response.
+    #[default]
+    Warn,
+    /// Refuse the push as an invalid request.
+    Reject,
+    /// Remove assistant boilerplate sentences before chunking.
+    Strip,
+}
+
+/// Endpoint the health resource uses to decide whether Qdrant is reachable.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum QdrantHealthProbe {
+    /// `GET /healthz`, falling back to a collection listing on servers without it.
+    #[default]
+    Healthz,
+    /// `GET /readyz`, falling back to a collection listing on servers without it.
+    Readyz,
+    /// List collections, which needs a key allowed to see every collection.
+    Collections,
+}
+
+impl QdrantHealthProbe {
+    /// Dedicated health endpoint path, or `None` for the collection listing.
+    pub fn endpoint(self) -> Option<&'static str> {
+        match self {
+            Self::Healthz => Some("healthz"),
+            Self::Readyz => Some("readyz"),
+            Self::Collections => None,
+        }
+    }
+}
+
+/// Boundary selection used when splitting pushed text into chunks.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChunkStrategy {
+    /// Token-budgeted semantic splitting (sentences, then words).
+    #[default]
+    Token,
+    /// Split source code between top-level units, keeping functions whole when they fit.
+    Code,
+}
+
+/// Token counter substituted when tiktoken encoding data fails to load.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenizerFallback {
+    /// Require the `cl100k_base` encoding; chunking fails when it cannot load.
+    #[default]
+    Cl100k,
+    /// Count whitespace-separated words.
+    Whitespace,
+    /// Count characters, over-estimating tokens so chunks stay within budget.
+    Char,
+}
+
 impl Config {
     /// Load configuration from environment variables, performing validation along the way.
     pub fn from_env() -> Result<Self, ConfigError> {
@@ -120,21 +332,82 @@ impl Config {
                 "SEARCH_DEFAULT_SCORE_THRESHOLD must be between 0.0 and 1.0".into(),
             ));
         }
+        let chunk_quality_min = load_env_optional("CHUNK_QUALITY_MIN")
+            .map(|value| {
+                value
+                    .parse::<f32>()
+                    .map_err(|_| ConfigError::InvalidValue("CHUNK_QUALITY_MIN".to_string()))
+            })
+            .transpose()?;
+        if chunk_quality_min.is_some_and(|min| !(0.0..=1.0).contains(&min)) {
+            return Err(ConfigError::InvalidValue(
+                "CHUNK_QUALITY_MIN must be between 0.0 and 1.0".into(),
+            ));
+        }
+        let mcp_max_concurrent_tools = load_usize_with_default("MCP_MAX_CONCURRENT_TOOLS", 8)?;
+        if mcp_max_concurrent_tools == 0 {
+            return Err(ConfigError::InvalidValue(
+                "MCP_MAX_CONCURRENT_TOOLS must be at least 1".into(),
+            ));
+        }
 
-        Ok(Self {
+        let text_splitter_chunk_size = load_env_optional("TEXT_SPLITTER_CHUNK_SIZE")
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| ConfigError::InvalidValue("TEXT_SPLITTER_CHUNK_SIZE".to_string()))
+            })
+            .transpose()?;
+        let text_splitter_min_chunk_size = load_usize_with_default(
+            "TEXT_SPLITTER_MIN_CHUNK_SIZE",
+            DEFAULT_MIN_CHUNK_SIZE_OVERRIDE,
+        )?;
+        check_chunk_size_override(
+            text_splitter_chunk_size,
+            text_splitter_min_chunk_size,
+            load_bool_with_default("TEXT_SPLITTER_STRICT", false)?,
+        )?;
+
+        let mut config = Self {
             qdrant_url: load_env("QDRANT_URL")?,
-            qdrant_collection_name: load_env("QDRANT_COLLECTION_NAME")?,
+            qdrant_read_url: load_env_optional("QDRANT_READ_URL"),
+            qdrant_health_probe: match load_env_optional("QDRANT_HEALTH_PROBE")
+                .map(|value| value.trim().to_lowercase())
+                .as_deref()
+            {
+                None | Some("" | "healthz") => QdrantHealthProbe::Healthz,
+                Some("readyz") => QdrantHealthProbe::Readyz,
+                Some("collections") => QdrantHealthProbe::Collections,
+                Some(_) => {
+                    return Err(ConfigError::InvalidValue(
+                        "QDRANT_HEALTH_PROBE must be `healthz`, `readyz`, or `collections`".into(),
+                    ));
+                }
+            },
+            qdrant_collection_name: load_collection_name("QDRANT_COLLECTION_NAME")?,
             qdrant_api_key: load_env_optional("QDRANT_API_KEY"),
+            qdrant_api_keys: load_qdrant_api_keys("QDRANT_API_KEYS")?,
+            qdrant_shard_number: load_positive_u32_optional("QDRANT_SHARD_NUMBER")?,
+            qdrant_replication_factor: load_positive_u32_optional("QDRANT_REPLICATION_FACTOR")?,
+            strict_payload_indexes: load_bool_with_default("STRICT_PAYLOAD_INDEXES", false)?,
+            collection_per_project: load_bool_with_default("COLLECTION_PER_PROJECT", false)?,
+            episodic_partitioning: match load_env_optional("EPISODIC_PARTITIONING")
+                .map(|value| value.trim().to_lowercase())
+                .as_deref()
+            {
+                None | Some("" | "off") => EpisodicPartitioning::Off,
+                Some("monthly") => EpisodicPartitioning::Monthly,
+                Some(_) => {
+                    return Err(ConfigError::InvalidValue(
+                        "EPISODIC_PARTITIONING must be `off` or `monthly`".into(),
+                    ));
+                }
+            },
             embedding_provider: load_env("EMBEDDING_PROVIDER")?.parse().map_err(|()| {
                 ConfigError::MissingVariable("Invalid EMBEDDING_PROVIDER".to_string())
             })?,
-            text_splitter_chunk_size: load_env_optional("TEXT_SPLITTER_CHUNK_SIZE")
-                .map(|value| {
-                    value.parse().map_err(|_| {
-                        ConfigError::InvalidValue("TEXT_SPLITTER_CHUNK_SIZE".to_string())
-                    })
-                })
-                .transpose()?,
+            text_splitter_chunk_size,
+            text_splitter_min_chunk_size,
             text_splitter_chunk_overlap: load_env_optional("TEXT_SPLITTER_CHUNK_OVERLAP")
                 .map(|value| {
                     value.parse().map_err(|_| {
@@ -146,11 +419,74 @@ impl Config {
                 "TEXT_SPLITTER_USE_SAFE_DEFAULTS",
                 false,
             )?,
+            text_splitter_strategy: match load_env_optional("TEXT_SPLITTER_STRATEGY")
+                .map(|value| value.trim().to_lowercase())
+                .as_deref()
+            {
+                None | Some("" | "token") => ChunkStrategy::Token,
+                Some("code") => ChunkStrategy::Code,
+                Some(_) => {
+                    return Err(ConfigError::InvalidValue(
+                        "TEXT_SPLITTER_STRATEGY must be `token` or `code`".into(),
+                    ));
+                }
+            },
+            chunk_quality_min,
+            tokenizer_fallback: match load_env_optional("TOKENIZER_FALLBACK")
+                .map(|value| value.trim().to_lowercase())
+                .as_deref()
+            {
+                None | Some("" | "cl100k") => TokenizerFallback::Cl100k,
+                Some("whitespace") => TokenizerFallback::Whitespace,
+                Some("char") => TokenizerFallback::Char,
+                Some(_) => {
+                    return Err(ConfigError::InvalidValue(
+                        "TOKENIZER_FALLBACK must be `cl100k`, `whitespace`, or `char`".into(),
+                    ));
+                }
+            },
             embedding_model: load_env("EMBEDDING_MODEL")?,
             embedding_dimension: load_env("EMBEDDING_DIMENSION")?.parse().map_err(|_| {
                 ConfigError::MissingVariable("Invalid EMBEDDING_DIMENSION".to_string())
             })?,
+            configured_embedding_dimension: None,
+            embedding_query_prefix: load_env_optional("EMBEDDING_QUERY_PREFIX").unwrap_or_default(),
+            embedding_doc_prefix: load_env_optional("EMBEDDING_DOC_PREFIX").unwrap_or_default(),
             ollama_url: load_env_optional("OLLAMA_URL"),
+            embedding_extra_headers: load_header_map("EMBEDDING_EXTRA_HEADERS")?,
+            embedding_allow_truncation: load_bool_with_default(
+                "EMBEDDING_ALLOW_TRUNCATION",
+                false,
+            )?,
+            embedding_disk_cache_path: load_env_optional("EMBEDDING_DISK_CACHE_PATH")
+                .map(|value| PathBuf::from(value.trim())),
+            embedding_timeout_ms: load_usize_with_default("EMBEDDING_TIMEOUT_MS", 30_000)? as u64,
+            embedding_next: None,
+            ingest_fallback: match load_env_optional("INGEST_FALLBACK")
+                .map(|value| value.trim().to_lowercase())
+                .as_deref()
+            {
+                None | Some("" | "none") => IngestFallback::None,
+                Some("deterministic") => IngestFallback::Deterministic,
+                Some(_) => {
+                    return Err(ConfigError::InvalidValue(
+                        "INGEST_FALLBACK must be `none` or `deterministic`".into(),
+                    ));
+                }
+            },
+            transcript_policy: match load_env_optional("TRANSCRIPT_POLICY")
+                .map(|value| value.trim().to_lowercase())
+                .as_deref()
+            {
+                None | Some("" | "warn") => TranscriptPolicy::Warn,
+                Some("reject") => TranscriptPolicy::Reject,
+                Some("strip") => TranscriptPolicy::Strip,
+                Some(_) => {
+                    return Err(ConfigError::InvalidValue(
+                        "TRANSCRIPT_POLICY must be `warn`, `reject`, or `strip`".into(),
+                    ));
+                }
+            },
             server_port: load_env_optional("SERVER_PORT")
                 .map(|value| {
                     value
@@ -160,7 +496,24 @@ impl Config {
                 .transpose()?,
             search_default_limit,
             search_max_limit,
+            search_clamp_limit: load_bool_with_default("SEARCH_CLAMP_LIMIT", false)?,
             search_default_score_threshold,
+            search_normalize_scores: load_bool_with_default("SEARCH_NORMALIZE_SCORES", false)?,
+            require_project_filter: load_bool_with_default("REQUIRE_PROJECT_FILTER", false)?,
+            search_cache_ttl_ms: load_usize_with_default("SEARCH_CACHE_TTL_MS", 0)? as u64,
+            listing_cache_ttl_ms: load_usize_with_default("LISTING_CACHE_TTL_MS", 5_000)? as u64,
+            max_query_tokens: match load_usize_with_default("MAX_QUERY_TOKENS", 256)? {
+                0 => {
+                    return Err(ConfigError::InvalidValue(
+                        "MAX_QUERY_TOKENS must be at least 1".into(),
+                    ));
+                }
+                tokens => tokens,
+            },
+            max_response_bytes: load_positive_u32_optional("MAX_RESPONSE_BYTES")?
+                .map(|bytes| bytes as usize),
+            answer_context_max_tokens: load_positive_u32_optional("ANSWER_CONTEXT_MAX_TOKENS")?
+                .map_or(2000, |tokens| tokens as usize),
             summarization_provider: load_env_optional("SUMMARIZATION_PROVIDER")
                 .as_deref()
                 .map(|s| match s.to_lowercase().as_str() {
@@ -170,10 +523,158 @@ impl Config {
                 .unwrap_or(SummarizationProvider::None),
             summarization_model: load_env_optional("SUMMARIZATION_MODEL"),
             summarization_max_words: load_usize_with_default("SUMMARIZATION_MAX_WORDS", 250)?,
-        })
+            summarization_max_prompt_tokens: load_positive_u32_optional(
+                "SUMMARIZATION_MAX_PROMPT_TOKENS",
+            )?
+            .map(|tokens| tokens as usize),
+            summarization_max_retries: load_usize_with_default("SUMMARIZATION_MAX_RETRIES", 2)?,
+            summarization_timeout_ms: load_usize_with_default("SUMMARIZATION_TIMEOUT_MS", 120_000)?
+                as u64,
+            audit_log: load_env_optional("AUDIT_LOG")
+                .map(|value| AuditTarget::parse(&value))
+                .unwrap_or_default(),
+            state_dir: load_env_optional("STATE_DIR").map(|value| PathBuf::from(value.trim())),
+            collection_manifest: load_env_optional("COLLECTION_MANIFEST")
+                .map(|value| PathBuf::from(value.trim())),
+            client_project_map: load_labeled_keys("CLIENT_PROJECT_MAP")?,
+            memory_type_rules: match load_env_optional("MEMORY_TYPE_RULES") {
+                Some(raw) => MemoryTypeRule::parse_list(&raw)
+                    .ok_or_else(|| ConfigError::InvalidValue("MEMORY_TYPE_RULES".to_string()))?,
+                None => Vec::new(),
+            },
+            project_default_memory_type: match load_env_optional("PROJECT_DEFAULT_MEMORY_TYPE") {
+                Some(raw) => parse_project_memory_types(&raw).ok_or_else(|| {
+                    ConfigError::InvalidValue(
+                        "PROJECT_DEFAULT_MEMORY_TYPE must list `project=episodic|semantic|procedural` pairs"
+                            .to_string(),
+                    )
+                })?,
+                None => BTreeMap::new(),
+            },
+            mcp_max_concurrent_tools,
+            mcp_max_queued_tools: load_usize_with_default("MCP_MAX_QUEUED_TOOLS", 16)?,
+            maintenance_interval_minutes: load_positive_u32_optional(
+                "MAINTENANCE_INTERVAL_MINUTES",
+            )?,
+            maintenance_collections: load_collection_list("MAINTENANCE_COLLECTIONS")?,
+            maintenance_episodic_ttl_days: load_positive_u32_optional(
+                "MAINTENANCE_EPISODIC_TTL_DAYS",
+            )?,
+        };
+        if load_bool_with_default("EMBEDDING_DIMENSION_AUTO", false)? {
+            config.apply_known_embedding_dimension();
+        }
+        config.embedding_next = load_next_embedding(&config)?;
+        Ok(config)
+    }
+
+    /// Replace `embedding_dimension` with the known output size of an OpenAI `embedding_model`.
+    ///
+    /// Only runs with `EMBEDDING_DIMENSION_AUTO`; unknown models and other providers keep the
+    /// configured value. A replaced value is kept in `configured_embedding_dimension` and
+    /// reported by [`log_config`], since tracing is not initialised yet while loading.
+    fn apply_known_embedding_dimension(&mut self) {
+        if !matches!(self.embedding_provider, EmbeddingProvider::OpenAI) {
+            return;
+        }
+        let Some(known) = openai_model_dimension(&self.embedding_model) else {
+            return;
+        };
+        if known != self.embedding_dimension {
+            self.configured_embedding_dimension = Some(self.embedding_dimension);
+            self.embedding_dimension = known;
+        }
+    }
+
+    /// Collection used when a request names none.
+    ///
+    /// This is `qdrant_collection_name`, or the project's own collection when
+    /// `COLLECTION_PER_PROJECT` is set and the request carries a `project_id`.
+    pub fn default_collection(&self, project_id: Option<&str>) -> String {
+        collection_for_project(
+            &self.qdrant_collection_name,
+            self.collection_per_project,
+            project_id,
+        )
+    }
+}
+
+/// Read the `EMBEDDING_*_NEXT` variables; model and dimension default to the current ones.
+fn load_next_embedding(config: &Config) -> Result<Option<NextEmbedding>, ConfigError> {
+    let Some(provider) = load_env_optional("EMBEDDING_PROVIDER_NEXT") else {
+        return Ok(None);
+    };
+    let next = NextEmbedding {
+        provider: provider
+            .trim()
+            .parse()
+            .map_err(|()| ConfigError::InvalidValue("EMBEDDING_PROVIDER_NEXT".to_string()))?,
+        model: load_env_optional("EMBEDDING_MODEL_NEXT")
+            .unwrap_or_else(|| config.embedding_model.clone()),
+        dimension: load_usize_with_default("EMBEDDING_DIMENSION_NEXT", config.embedding_dimension)?,
+    };
+    if next.dimension == 0 {
+        return Err(ConfigError::InvalidValue(
+            "EMBEDDING_DIMENSION_NEXT must be at least 1".to_string(),
+        ));
+    }
+    if next.provider == config.embedding_provider
+        && next.model == config.embedding_model
+        && next.dimension == config.embedding_dimension
+    {
+        return Err(ConfigError::InvalidValue(
+            "EMBEDDING_PROVIDER_NEXT, EMBEDDING_MODEL_NEXT and EMBEDDING_DIMENSION_NEXT match the current embedding settings".to_string(),
+        ));
+    }
+    Ok(Some(next))
+}
+
+fn collection_for_project(base: &str, per_project: bool, project_id: Option<&str>) -> String {
+    match project_id
+        .map(str::trim)
+        .filter(|project| !project.is_empty())
+    {
+        Some(project) if per_project => crate::qdrant::project_collection_name(base, project),
+        _ => base.to_string(),
     }
 }
 
+/// Reject overrides below the floor in strict mode; otherwise they are raised at chunking time.
+fn check_chunk_size_override(
+    override_size: Option<usize>,
+    floor: usize,
+    strict: bool,
+) -> Result<(), ConfigError> {
+    match override_below_floor(override_size, floor) {
+        Some(size) if strict => Err(ConfigError::InvalidValue(format!(
+            "TEXT_SPLITTER_CHUNK_SIZE={size} is below TEXT_SPLITTER_MIN_CHUNK_SIZE={floor} (TEXT_SPLITTER_STRICT is set)"
+        ))),
+        _ => Ok(()),
+    }
+}
+
+fn load_collection_name(key: &str) -> Result<String, ConfigError> {
+    parse_collection_name(key, load_env(key)?)
+}
+
+fn parse_collection_name(key: &str, name: String) -> Result<String, ConfigError> {
+    crate::qdrant::validate_collection_name(&name)
+        .map_err(|message| ConfigError::InvalidValue(format!("{key}: {message}")))?;
+    Ok(name)
+}
+
+/// Parse a comma-separated list of collection names, skipping blank entries.
+fn load_collection_list(key: &str) -> Result<Vec<String>, ConfigError> {
+    let Some(raw) = load_env_optional(key) else {
+        return Ok(Vec::new());
+    };
+    raw.split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| parse_collection_name(key, name.to_string()))
+        .collect()
+}
+
 fn load_usize_with_default(key: &str, default: usize) -> Result<usize, ConfigError> {
     match load_env_optional(key) {
         Some(value) => value
@@ -183,6 +684,17 @@ fn load_usize_with_default(key: &str, default: usize) -> Result<usize, ConfigErr
     }
 }
 
+fn load_positive_u32_optional(key: &str) -> Result<Option<u32>, ConfigError> {
+    match load_env_optional(key).map(|value| value.parse::<u32>()) {
+        Some(Ok(0)) => Err(ConfigError::InvalidValue(format!(
+            "{key} must be at least 1"
+        ))),
+        Some(Ok(value)) => Ok(Some(value)),
+        Some(Err(_)) => Err(ConfigError::InvalidValue(key.to_string())),
+        None => Ok(None),
+    }
+}
+
 fn load_f32_with_default(key: &str, default: f32) -> Result<f32, ConfigError> {
     match load_env_optional(key) {
         Some(value) => value
@@ -203,6 +715,94 @@ fn load_bool_with_default(key: &str, default: bool) -> Result<bool, ConfigError>
     }
 }
 
+/// Parse a comma-separated `label=value` list into a map keyed by lowercase label.
+fn load_labeled_keys(key: &str) -> Result<BTreeMap<String, String>, ConfigError> {
+    let Some(raw) = load_env_optional(key) else {
+        return Ok(BTreeMap::new());
+    };
+    parse_labeled_keys(&raw).ok_or_else(|| ConfigError::InvalidValue(key.to_string()))
+}
+
+/// Parse `QDRANT_API_KEYS`, rejecting labels other than `read` and `write` so a typo does not
+/// silently fall back to `QDRANT_API_KEY`.
+fn load_qdrant_api_keys(key: &str) -> Result<BTreeMap<String, String>, ConfigError> {
+    let keys = load_labeled_keys(key)?;
+    check_qdrant_key_labels(&keys)
+        .map_err(|message| ConfigError::InvalidValue(format!("{key}: {message}")))?;
+    Ok(keys)
+}
+
+fn check_qdrant_key_labels(keys: &BTreeMap<String, String>) -> Result<(), String> {
+    match keys
+        .keys()
+        .find(|label| !matches!(label.as_str(), "read" | "write"))
+    {
+        Some(label) => Err(format!(
+            "unknown label `{label}` (expected `read` or `write`)"
+        )),
+        None => Ok(()),
+    }
+}
+
+fn parse_labeled_keys(raw: &str) -> Option<BTreeMap<String, String>> {
+    let mut keys = BTreeMap::new();
+    for entry in raw
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+    {
+        let (label, value) = entry.split_once('=')?;
+        let (label, value) = (label.trim(), value.trim());
+        if label.is_empty() || value.is_empty() {
+            return None;
+        }
+        keys.insert(label.to_lowercase(), value.to_string());
+    }
+    Some(keys)
+}
+
+/// Parse comma-separated `project=type` pairs.
+///
+/// Project ids are kept verbatim (they are matched exactly); the memory type is normalized and
+/// must be `episodic`, `semantic`, or `procedural`.
+fn parse_project_memory_types(raw: &str) -> Option<BTreeMap<String, String>> {
+    let mut defaults = BTreeMap::new();
+    for entry in raw
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+    {
+        let (project, memory_type) = entry.split_once('=')?;
+        let project = project.trim();
+        let memory_type = sanitize_memory_type(Some(memory_type.to_string()))?;
+        if project.is_empty() {
+            return None;
+        }
+        defaults.insert(project.to_string(), memory_type);
+    }
+    Some(defaults)
+}
+
+/// Load `name=value` header pairs, rejecting names or values HTTP does not allow.
+fn load_header_map(key: &str) -> Result<BTreeMap<String, String>, ConfigError> {
+    let Some(raw) = load_env_optional(key) else {
+        return Ok(BTreeMap::new());
+    };
+    parse_header_map(&raw).map_err(|message| ConfigError::InvalidValue(format!("{key}: {message}")))
+}
+
+fn parse_header_map(raw: &str) -> Result<BTreeMap<String, String>, String> {
+    let headers =
+        parse_labeled_keys(raw).ok_or_else(|| "expected `name=value` pairs".to_string())?;
+    for (name, value) in &headers {
+        reqwest::header::HeaderName::from_bytes(name.as_bytes())
+            .map_err(|_| format!("`{name}` is not a valid header name"))?;
+        reqwest::header::HeaderValue::from_str(value)
+            .map_err(|_| format!("the value of `{name}` is not a valid header value"))?;
+    }
+    Ok(headers)
+}
+
 fn load_env(key: &str) -> Result<String, ConfigError> {
     env::var(key).map_err(|_| ConfigError::MissingVariable(key.to_string()))
 }
@@ -232,22 +832,260 @@ pub fn get_config() -> &'static Config {
 }
 
 /// Load configuration from the environment and install it in the global cache.
+///
+/// Runs before tracing is initialised (the `.env` file may set `RUST_LOG`), so the summary is
+/// logged separately by [`log_config`].
 pub fn init_config() {
     dotenvy::dotenv().ok();
     let config = Config::from_env().expect("Failed to load config from environment");
+    CONFIG.set(config).expect("Failed to set config");
+}
+
+/// Log the loaded configuration; call once tracing is initialised.
+pub fn log_config() {
+    let config = get_config();
+    if let Some(configured) = config.configured_embedding_dimension {
+        tracing::info!(
+            model = %config.embedding_model,
+            configured,
+            dimension = config.embedding_dimension,
+            "Setting EMBEDDING_DIMENSION from the model's known output (EMBEDDING_DIMENSION_AUTO)"
+        );
+    }
     tracing::debug!(
         qdrant_url = %config.qdrant_url,
+        qdrant_read_url = ?config.qdrant_read_url,
+        qdrant_health_probe = ?config.qdrant_health_probe,
         collection = %config.qdrant_collection_name,
+        qdrant_shard_number = ?config.qdrant_shard_number,
+        qdrant_replication_factor = ?config.qdrant_replication_factor,
+        strict_payload_indexes = config.strict_payload_indexes,
+        collection_per_project = config.collection_per_project,
+        episodic_partitioning = ?config.episodic_partitioning,
         server_port = ?config.server_port,
+        text_splitter_chunk_size = ?config.text_splitter_chunk_size,
+        text_splitter_min_chunk_size = config.text_splitter_min_chunk_size,
         embedding_provider = ?config.embedding_provider,
+        embedding_dimension = config.embedding_dimension,
+        configured_embedding_dimension = ?config.configured_embedding_dimension,
         ollama_url = ?config.ollama_url,
+        embedding_extra_headers = ?config.embedding_extra_headers.keys().collect::<Vec<_>>(),
+        embedding_allow_truncation = config.embedding_allow_truncation,
+        embedding_disk_cache_path = ?config.embedding_disk_cache_path,
+        embedding_timeout_ms = config.embedding_timeout_ms,
+        embedding_next = ?config.embedding_next,
+        ingest_fallback = ?config.ingest_fallback,
+        transcript_policy = ?config.transcript_policy,
+        text_splitter_strategy = ?config.text_splitter_strategy,
+        tokenizer_fallback = ?config.tokenizer_fallback,
+        chunk_quality_min = ?config.chunk_quality_min,
+        embedding_query_prefix = ?config.embedding_query_prefix,
+        embedding_doc_prefix = ?config.embedding_doc_prefix,
         search_default_limit = config.search_default_limit,
         search_max_limit = config.search_max_limit,
+        search_clamp_limit = config.search_clamp_limit,
         search_default_score_threshold = config.search_default_score_threshold,
+        search_normalize_scores = config.search_normalize_scores,
+        require_project_filter = config.require_project_filter,
+        search_cache_ttl_ms = config.search_cache_ttl_ms,
+        listing_cache_ttl_ms = config.listing_cache_ttl_ms,
+        max_query_tokens = config.max_query_tokens,
+        max_response_bytes = ?config.max_response_bytes,
+        answer_context_max_tokens = config.answer_context_max_tokens,
         summarization_provider = ?config.summarization_provider,
         summarization_model = ?config.summarization_model,
         summarization_max_words = config.summarization_max_words,
+        summarization_max_prompt_tokens = ?config.summarization_max_prompt_tokens,
+        summarization_max_retries = config.summarization_max_retries,
+        summarization_timeout_ms = config.summarization_timeout_ms,
+        audit_log = ?config.audit_log,
+        state_dir = ?config.state_dir,
+        collection_manifest = ?config.collection_manifest,
+        client_project_map = ?config.client_project_map,
+        memory_type_rules = ?config.memory_type_rules,
+        project_default_memory_type = ?config.project_default_memory_type,
+        mcp_max_concurrent_tools = config.mcp_max_concurrent_tools,
+        mcp_max_queued_tools = config.mcp_max_queued_tools,
+        maintenance_interval_minutes = ?config.maintenance_interval_minutes,
+        maintenance_collections = ?config.maintenance_collections,
+        maintenance_episodic_ttl_days = ?config.maintenance_episodic_ttl_days,
         "Loaded configuration"
     );
-    CONFIG.set(config).expect("Failed to set config");
+}
+
+#[cfg(test)]
+impl Config {
+    /// Configuration shared by unit tests; override fields on the returned value as needed.
+    pub(crate) fn for_tests() -> Self {
+        Self {
+            qdrant_url: "http://127.0.0.1:6333".into(),
+            qdrant_read_url: None,
+            qdrant_health_probe: Default::default(),
+            qdrant_collection_name: "rusty-mem".into(),
+            qdrant_api_key: None,
+            qdrant_api_keys: Default::default(),
+            qdrant_shard_number: None,
+            qdrant_replication_factor: None,
+            strict_payload_indexes: false,
+            collection_per_project: false,
+            episodic_partitioning: EpisodicPartitioning::Off,
+            transcript_policy: TranscriptPolicy::Warn,
+            embedding_provider: EmbeddingProvider::Ollama,
+            text_splitter_chunk_size: None,
+            text_splitter_min_chunk_size: 16,
+            text_splitter_chunk_overlap: None,
+            text_splitter_use_safe_defaults: false,
+            text_splitter_strategy: ChunkStrategy::Token,
+            chunk_quality_min: None,
+            tokenizer_fallback: TokenizerFallback::Cl100k,
+            embedding_model: "test-model".into(),
+            embedding_dimension: 768,
+            configured_embedding_dimension: None,
+            ollama_url: None,
+            embedding_extra_headers: Default::default(),
+            embedding_allow_truncation: false,
+            embedding_disk_cache_path: None,
+            embedding_timeout_ms: 0,
+            embedding_next: None,
+            ingest_fallback: IngestFallback::None,
+            server_port: None,
+            search_default_limit: 5,
+            search_max_limit: 50,
+            search_clamp_limit: false,
+            search_default_score_threshold: 0.25,
+            search_normalize_scores: false,
+            require_project_filter: false,
+            search_cache_ttl_ms: 0,
+            listing_cache_ttl_ms: 0,
+            max_query_tokens: 256,
+            max_response_bytes: Some(1 << 20),
+            answer_context_max_tokens: 2000,
+            summarization_provider: SummarizationProvider::Ollama,
+            summarization_model: Some("llama".into()),
+            summarization_max_words: 200,
+            summarization_max_prompt_tokens: None,
+            summarization_max_retries: 2,
+            summarization_timeout_ms: 0,
+            project_default_memory_type: Default::default(),
+            audit_log: Default::default(),
+            state_dir: None,
+            collection_manifest: None,
+            client_project_map: Default::default(),
+            memory_type_rules: Vec::new(),
+            mcp_max_concurrent_tools: 8,
+            mcp_max_queued_tools: 16,
+            maintenance_interval_minutes: None,
+            maintenance_collections: Vec::new(),
+            maintenance_episodic_ttl_days: None,
+            embedding_query_prefix: String::new(),
+            embedding_doc_prefix: String::new(),
+        }
+    }
+}
+
+/// Install [`Config::for_tests`] as the global configuration.
+///
+/// Every test module goes through this one initialiser, so the values tests observe through
+/// [`get_config`] do not depend on which module happens to run first.
+#[cfg(test)]
+pub(crate) fn ensure_test_config() {
+    static INIT: std::sync::Once = std::sync::Once::new();
+    INIT.call_once(|| {
+        let _ = CONFIG.set(Config::for_tests());
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_collection_name_is_validated() {
+        assert_eq!(
+            parse_collection_name("QDRANT_COLLECTION_NAME", "rusty-mem".into()).expect("valid"),
+            "rusty-mem"
+        );
+        for name in ["../admin", "notes/points", "mémoire", ""] {
+            let error = parse_collection_name("QDRANT_COLLECTION_NAME", name.into()).unwrap_err();
+            assert!(
+                matches!(&error, ConfigError::InvalidValue(message) if message.starts_with("QDRANT_COLLECTION_NAME:")),
+                "{name:?}: {error}"
+            );
+        }
+    }
+
+    #[test]
+    fn collections_follow_the_project_only_when_enabled() {
+        assert_eq!(
+            collection_for_project("rusty-mem", true, Some("repo-a")),
+            "rusty-mem-repo-a"
+        );
+        assert_eq!(
+            collection_for_project("rusty-mem", true, Some(" repo-a ")),
+            "rusty-mem-repo-a"
+        );
+        assert_eq!(
+            collection_for_project("rusty-mem", false, Some("repo-a")),
+            "rusty-mem"
+        );
+        for project in [None, Some(""), Some("  ")] {
+            assert_eq!(
+                collection_for_project("rusty-mem", true, project),
+                "rusty-mem"
+            );
+        }
+    }
+
+    #[test]
+    fn strict_mode_rejects_tiny_chunk_size_overrides() {
+        let error = check_chunk_size_override(Some(1), 16, true).unwrap_err();
+        assert!(
+            matches!(&error, ConfigError::InvalidValue(message) if message.contains("TEXT_SPLITTER_CHUNK_SIZE=1")),
+            "{error}"
+        );
+        assert!(check_chunk_size_override(Some(1), 16, false).is_ok());
+        assert!(check_chunk_size_override(Some(16), 16, true).is_ok());
+        assert!(check_chunk_size_override(Some(8192), 16, true).is_ok());
+        assert!(check_chunk_size_override(None, 16, true).is_ok());
+    }
+
+    #[test]
+    fn qdrant_api_key_labels_are_limited_to_read_and_write() {
+        let keys = parse_labeled_keys("read=ro-key, WRITE=rw-key").expect("valid");
+        assert!(check_qdrant_key_labels(&keys).is_ok());
+        let keys = parse_labeled_keys("read=ro-key,wirte=rw-key").expect("valid");
+        let message = check_qdrant_key_labels(&keys).unwrap_err();
+        assert!(message.contains("`wirte`"), "{message}");
+    }
+
+    #[test]
+    fn embedding_headers_are_validated() {
+        let headers = parse_header_map("X-Org-Id=acme, x-route = gpu-pool").expect("valid");
+        assert_eq!(
+            headers,
+            BTreeMap::from([
+                ("x-org-id".to_string(), "acme".to_string()),
+                ("x-route".to_string(), "gpu-pool".to_string()),
+            ])
+        );
+        for raw in ["x-org-id", "bad header=acme", "x-org-id=line\nbreak"] {
+            assert!(parse_header_map(raw).is_err(), "{raw:?}");
+        }
+    }
+
+    #[test]
+    fn project_memory_types_are_validated() {
+        let defaults =
+            parse_project_memory_types("runbooks=Procedural, Journal = episodic,").expect("valid");
+        assert_eq!(
+            defaults,
+            BTreeMap::from([
+                ("Journal".to_string(), "episodic".to_string()),
+                ("runbooks".to_string(), "procedural".to_string()),
+            ])
+        );
+        for raw in ["runbooks=howto", "runbooks", "=semantic", "runbooks="] {
+            assert!(parse_project_memory_types(raw).is_none(), "{raw:?}");
+        }
+    }
 }
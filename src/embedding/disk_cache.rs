@@ -0,0 +1,335 @@
+//! Embeddings persisted to disk across restarts.
+//!
+//! When `EMBEDDING_DISK_CACHE_PATH` is set, the configured client is wrapped so that every vector
+//! it returns is appended to a JSON-lines file keyed by embedding model and the SHA-256 of the
+//! exact input text (including any `EMBEDDING_DOC_PREFIX`). The file is loaded into memory on
+//! startup; later requests for the same model and text are answered from memory and only misses
+//! reach the provider.
+//!
+//! The file is a cache: unreadable lines, a partially written last line, entries for other
+//! models, and vectors whose length differs from `EMBEDDING_DIMENSION` are skipped (with a
+//! warning) and recomputed on demand. Entries are never evicted, so delete the file to reclaim
+//! space or after changing models.
+
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::{EmbeddingClient, EmbeddingClientError};
+
+/// One cached vector, stored as a single line of the cache file.
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheRecord {
+    model: String,
+    key: String,
+    vector: Vec<f32>,
+}
+
+/// Persistent map from `(model, input text)` to embedding vectors.
+pub struct EmbeddingDiskCache {
+    model: String,
+    path: PathBuf,
+    entries: Mutex<HashMap<String, Vec<f32>>>,
+    file: Mutex<File>,
+}
+
+impl EmbeddingDiskCache {
+    /// Load the cache at `path` for `model`, creating the file and its parent directories when
+    /// missing.
+    ///
+    /// Only entries recorded for `model` with exactly `dimension` values are kept.
+    pub fn open(path: &Path, model: &str, dimension: usize) -> io::Result<Self> {
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(path)?;
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes)?;
+        let contents = String::from_utf8_lossy(&bytes);
+
+        let mut entries = HashMap::new();
+        let (mut malformed, mut wrong_dimension) = (0usize, 0usize);
+        for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+            match serde_json::from_str::<CacheRecord>(line) {
+                Ok(record) if record.model != model => {}
+                Ok(record) if record.vector.len() != dimension => wrong_dimension += 1,
+                Ok(record) => {
+                    entries.insert(record.key, record.vector);
+                }
+                Err(_) => malformed += 1,
+            }
+        }
+        if malformed > 0 || wrong_dimension > 0 {
+            tracing::warn!(
+                path = %path.display(),
+                malformed,
+                wrong_dimension,
+                dimension,
+                "Skipped unusable embedding cache entries"
+            );
+        }
+        // A crash mid-append leaves a partial last line; start the next record on a fresh one.
+        if !bytes.is_empty() && !bytes.ends_with(b"\n") {
+            file.write_all(b"\n")?;
+        }
+        tracing::info!(
+            path = %path.display(),
+            model,
+            entries = entries.len(),
+            "Loaded embedding disk cache"
+        );
+
+        Ok(Self {
+            model: model.to_string(),
+            path: path.to_path_buf(),
+            entries: Mutex::new(entries),
+            file: Mutex::new(file),
+        })
+    }
+
+    /// Number of vectors cached for the configured model.
+    pub fn len(&self) -> usize {
+        lock(&self.entries).len()
+    }
+
+    /// Whether no vectors are cached for the configured model.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Cached vector for each text, in order; `None` marks a miss.
+    fn lookup(&self, texts: &[String]) -> Vec<Option<Vec<f32>>> {
+        let entries = lock(&self.entries);
+        texts
+            .iter()
+            .map(|text| entries.get(&cache_key(text)).cloned())
+            .collect()
+    }
+
+    /// Remember `vectors` for `texts` and append them to the file.
+    ///
+    /// Write failures are logged; the vectors stay cached in memory for this process.
+    fn store(&self, texts: &[String], vectors: &[Vec<f32>]) {
+        let records: Vec<CacheRecord> = texts
+            .iter()
+            .zip(vectors)
+            .map(|(text, vector)| CacheRecord {
+                model: self.model.clone(),
+                key: cache_key(text),
+                vector: vector.clone(),
+            })
+            .collect();
+        let mut buffer = Vec::new();
+        for record in &records {
+            if serde_json::to_writer(&mut buffer, record).is_ok() {
+                buffer.push(b'\n');
+            }
+        }
+        {
+            let mut entries = lock(&self.entries);
+            for record in records {
+                entries.insert(record.key, record.vector);
+            }
+        }
+        let result = {
+            let mut file = lock(&self.file);
+            file.write_all(&buffer).and_then(|()| file.flush())
+        };
+        if let Err(error) = result {
+            tracing::warn!(
+                path = %self.path.display(),
+                error = %error,
+                "Failed to persist embeddings to the disk cache"
+            );
+        }
+    }
+}
+
+/// Embedding client answering repeated inputs from an [`EmbeddingDiskCache`] before calling
+/// `inner`.
+pub struct DiskCachedEmbeddingClient {
+    inner: Box<dyn EmbeddingClient + Send + Sync>,
+    cache: EmbeddingDiskCache,
+}
+
+impl DiskCachedEmbeddingClient {
+    /// Wrap `inner` so that its results are read from and written to `cache`.
+    pub fn new(inner: Box<dyn EmbeddingClient + Send + Sync>, cache: EmbeddingDiskCache) -> Self {
+        Self { inner, cache }
+    }
+}
+
+#[async_trait]
+impl EmbeddingClient for DiskCachedEmbeddingClient {
+    async fn generate_embeddings(
+        &self,
+        texts: Vec<String>,
+    ) -> Result<Vec<Vec<f32>>, EmbeddingClientError> {
+        if texts.is_empty() {
+            return self.inner.generate_embeddings(texts).await;
+        }
+        let mut embeddings = self.cache.lookup(&texts);
+        let (miss_indices, miss_texts): (Vec<usize>, Vec<String>) = texts
+            .into_iter()
+            .enumerate()
+            .filter(|(index, _)| embeddings[*index].is_none())
+            .unzip();
+        tracing::debug!(
+            hits = embeddings.len() - miss_indices.len(),
+            misses = miss_indices.len(),
+            "Embedding disk cache lookup"
+        );
+
+        if !miss_texts.is_empty() {
+            let computed = self.inner.generate_embeddings(miss_texts.clone()).await?;
+            if computed.len() != miss_texts.len() {
+                return Err(EmbeddingClientError::GenerationFailed(format!(
+                    "provider returned {} embeddings for {} texts",
+                    computed.len(),
+                    miss_texts.len()
+                )));
+            }
+            // Never persist a broken vector; it would be served on every later lookup.
+            super::validate_embeddings(&computed)?;
+            self.cache.store(&miss_texts, &computed);
+            for (index, vector) in miss_indices.into_iter().zip(computed) {
+                embeddings[index] = Some(vector);
+            }
+        }
+
+        Ok(embeddings.into_iter().flatten().collect())
+    }
+}
+
+fn cache_key(text: &str) -> String {
+    hex::encode(Sha256::digest(text.as_bytes()))
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Returns `[len, 1.0]` per text and counts the texts it was asked to embed.
+    struct CountingEmbedding(Arc<AtomicUsize>);
+
+    #[async_trait]
+    impl EmbeddingClient for CountingEmbedding {
+        async fn generate_embeddings(
+            &self,
+            texts: Vec<String>,
+        ) -> Result<Vec<Vec<f32>>, EmbeddingClientError> {
+            self.0.fetch_add(texts.len(), Ordering::SeqCst);
+            Ok(texts
+                .iter()
+                .map(|text| vec![text.len() as f32, 1.0])
+                .collect())
+        }
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "rusty-mem-embedding-cache-{name}-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir.join("embeddings.jsonl")
+    }
+
+    fn open(path: &Path, calls: &Arc<AtomicUsize>) -> DiskCachedEmbeddingClient {
+        DiskCachedEmbeddingClient::new(
+            Box::new(CountingEmbedding(calls.clone())),
+            EmbeddingDiskCache::open(path, "test-model", 2).expect("open cache"),
+        )
+    }
+
+    fn texts(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[tokio::test]
+    async fn cached_embeddings_survive_reopen() {
+        let path = temp_path("reopen");
+        let calls = Arc::new(AtomicUsize::new(0));
+        let cache = open(&path, &calls);
+        let first = cache
+            .generate_embeddings(texts(&["alpha", "beta"]))
+            .await
+            .expect("embed");
+        assert_eq!(first, vec![vec![5.0, 1.0], vec![4.0, 1.0]]);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        drop(cache);
+
+        let reopened = open(&path, &calls);
+        assert_eq!(reopened.cache.len(), 2);
+        let again = reopened
+            .generate_embeddings(texts(&["beta", "gamma", "alpha"]))
+            .await
+            .expect("embed");
+        assert_eq!(again, vec![vec![4.0, 1.0], vec![5.0, 1.0], vec![5.0, 1.0]]);
+        assert_eq!(calls.load(Ordering::SeqCst), 3, "only `gamma` is computed");
+
+        let other_model = EmbeddingDiskCache::open(&path, "other-model", 2).expect("open cache");
+        let _ = std::fs::remove_dir_all(path.parent().expect("dir"));
+        assert!(other_model.is_empty());
+    }
+
+    #[tokio::test]
+    async fn corrupt_lines_and_wrong_dimensions_are_skipped() {
+        let path = temp_path("corrupt");
+        std::fs::create_dir_all(path.parent().expect("dir")).expect("dir");
+        let good = serde_json::to_string(&CacheRecord {
+            model: "test-model".into(),
+            key: cache_key("alpha"),
+            vector: vec![0.25, 0.75],
+        })
+        .expect("json");
+        let resized = serde_json::to_string(&CacheRecord {
+            model: "test-model".into(),
+            key: cache_key("beta"),
+            vector: vec![0.1, 0.2, 0.3],
+        })
+        .expect("json");
+        let partial = r#"{"model":"test-model","key":"abc","vec"#;
+        std::fs::write(&path, format!("{good}\nnot json\n{resized}\n{partial}")).expect("write");
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let cache = open(&path, &calls);
+        assert_eq!(cache.cache.len(), 1);
+        let embeddings = cache
+            .generate_embeddings(texts(&["alpha", "beta"]))
+            .await
+            .expect("embed");
+        assert_eq!(embeddings, vec![vec![0.25, 0.75], vec![4.0, 1.0]]);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        drop(cache);
+
+        let reopened = open(&path, &calls);
+        let _ = std::fs::remove_dir_all(path.parent().expect("dir"));
+        assert_eq!(
+            reopened.cache.len(),
+            2,
+            "the record after the partial line is readable"
+        );
+    }
+}
@@ -9,14 +9,68 @@
 //!   end‑to‑end pipeline testable without external credentials.
 //!
 //! The concrete provider is selected at runtime by `get_embedding_client()` based on configuration.
+//! `EMBEDDING_EXTRA_HEADERS` (for gateways that route or authorize on custom headers) is sent with
+//! every Ollama request; the deterministic encoder makes no requests. With
+//! `EMBEDDING_ALLOW_TRUNCATION`, Ollama vectors longer than `EMBEDDING_DIMENSION` are cut down to
+//! it (valid for Matryoshka models); shorter vectors are always rejected. With
+//! `EMBEDDING_DISK_CACHE_PATH`, vectors are persisted per model and input text and reused across
+//! restarts (see the `disk_cache` module). Ollama requests give up after `EMBEDDING_TIMEOUT_MS`
+//! and surface as [`EmbeddingClientError::ProviderUnavailable`], as do refused connections, 5xx,
+//! and 429 answers; a missing model is a configuration error and other rejections fail the
+//! request outright. For OpenAI models with a known
+//! output size (see [`OPENAI_EMBEDDING_DIMENSIONS`]) a differing `EMBEDDING_DIMENSION` is logged
+//! as a warning, or replaced at startup when `EMBEDDING_DIMENSION_AUTO` is set.
+//! `get_next_embedding_client()` builds the same adapters for `EMBEDDING_PROVIDER_NEXT` during a
+//! provider migration.
+use std::collections::BTreeMap;
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+use std::time::Duration;
+
 use crate::config::{EmbeddingProvider, get_config};
 use async_trait::async_trait;
-use ollama_rs::Ollama;
+use ollama_rs::generation::embeddings::GenerateEmbeddingsResponse;
 use ollama_rs::generation::embeddings::request::GenerateEmbeddingsRequest;
+use reqwest::StatusCode;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use thiserror::Error;
 
+pub mod disk_cache;
+
+pub use disk_cache::{DiskCachedEmbeddingClient, EmbeddingDiskCache};
+
 const DEFAULT_OLLAMA_URL: &str = "http://127.0.0.1:11434";
 
+/// Native output dimension of the OpenAI embedding models.
+pub const OPENAI_EMBEDDING_DIMENSIONS: [(&str, usize); 3] = [
+    ("text-embedding-3-small", 1536),
+    ("text-embedding-3-large", 3072),
+    ("text-embedding-ada-002", 1536),
+];
+
+/// Known output dimension of an OpenAI embedding `model`, ignoring an `openai/` prefix and case.
+pub fn openai_model_dimension(model: &str) -> Option<usize> {
+    let model = model.trim().to_ascii_lowercase();
+    let model = model.strip_prefix("openai/").unwrap_or(&model);
+    OPENAI_EMBEDDING_DIMENSIONS
+        .iter()
+        .find(|(name, _)| *name == model)
+        .map(|(_, dimension)| *dimension)
+}
+
+/// Explain how `configured` differs from the known output of OpenAI `model`, if it does.
+fn openai_dimension_mismatch(model: &str, configured: usize) -> Option<String> {
+    let known = openai_model_dimension(model)?;
+    (known != configured).then(|| {
+        format!(
+            "EMBEDDING_DIMENSION is {configured} but OpenAI model '{}' produces {known}-dimensional vectors. Set EMBEDDING_DIMENSION={known}, or EMBEDDING_DIMENSION_AUTO=1 to apply it at startup.",
+            model.trim()
+        )
+    })
+}
+
 /// Errors raised by embedding providers.
 #[derive(Debug, Error)]
 pub enum EmbeddingClientError {
@@ -29,6 +83,14 @@ pub enum EmbeddingClientError {
     /// Configuration is invalid or insufficient to request embeddings.
     #[error("Invalid embedding configuration: {0}")]
     Configuration(String),
+    /// Provider returned a vector that cannot be stored or searched meaningfully.
+    #[error("Embedding provider returned an invalid vector at index {index}: {reason}")]
+    InvalidVector {
+        /// Position of the offending vector in the response.
+        index: usize,
+        /// Which check the vector failed.
+        reason: &'static str,
+    },
 }
 
 /// Interface implemented by embedding backends.
@@ -42,21 +104,27 @@ pub trait EmbeddingClient {
 }
 
 /// Deterministic fallback embedding client backed by ai-lib settings.
-pub struct AiLibClient;
+pub struct AiLibClient {
+    /// Output length; `None` follows `EMBEDDING_DIMENSION`.
+    dimension: Option<usize>,
+}
 
 impl AiLibClient {
     /// Construct a new deterministic embedding client instance.
     pub const fn new() -> Self {
-        Self
+        Self { dimension: None }
+    }
+
+    /// Construct a deterministic client producing vectors of `dimension` entries.
+    pub const fn with_dimension(dimension: usize) -> Self {
+        Self {
+            dimension: Some(dimension),
+        }
     }
 
     fn encode(text: &str, dimension: usize) -> Vec<f32> {
         let mut embedding = vec![0.0_f32; dimension];
 
-        if text.is_empty() {
-            return embedding;
-        }
-
         for (idx, byte) in text.bytes().enumerate() {
             let position = idx % dimension;
             // Basic hashing of content into the vector slot
@@ -73,12 +141,40 @@ impl AiLibClient {
             for value in &mut embedding {
                 *value /= norm;
             }
+        } else {
+            // Empty (or all-NUL) text has no signal; a fixed unit vector keeps it valid.
+            embedding[0] = 1.0;
         }
 
         embedding
     }
 }
 
+/// Reject responses containing a vector with NaN or infinite components, or only zeros.
+///
+/// Qdrant accepts such vectors but scores them as nonsense (and an all-zero vector has no cosine
+/// direction), so they are caught before they are stored or searched with.
+pub fn validate_embeddings(vectors: &[Vec<f32>]) -> Result<(), EmbeddingClientError> {
+    for (index, vector) in vectors.iter().enumerate() {
+        if let Some(reason) = invalid_vector_reason(vector) {
+            return Err(EmbeddingClientError::InvalidVector { index, reason });
+        }
+    }
+    Ok(())
+}
+
+fn invalid_vector_reason(vector: &[f32]) -> Option<&'static str> {
+    if vector.iter().any(|value| value.is_nan()) {
+        Some("contains NaN components")
+    } else if vector.iter().any(|value| value.is_infinite()) {
+        Some("contains infinite components")
+    } else if vector.iter().all(|value| *value == 0.0) {
+        Some("is all zeros")
+    } else {
+        None
+    }
+}
+
 impl Default for AiLibClient {
     fn default() -> Self {
         Self::new()
@@ -87,12 +183,17 @@ impl Default for AiLibClient {
 
 #[async_trait]
 impl EmbeddingClient for AiLibClient {
+    #[tracing::instrument(
+        name = "embedding.generate",
+        skip_all,
+        fields(provider = "deterministic", texts = texts.len())
+    )]
     async fn generate_embeddings(
         &self,
         texts: Vec<String>,
     ) -> Result<Vec<Vec<f32>>, EmbeddingClientError> {
         let config = get_config();
-        let dimension = config.embedding_dimension;
+        let dimension = self.dimension.unwrap_or(config.embedding_dimension);
 
         tracing::debug!(
             provider = ?config.embedding_provider,
@@ -124,10 +225,14 @@ impl EmbeddingClient for AiLibClient {
 
 #[derive(Clone)]
 struct OllamaClient {
-    inner: Ollama,
+    http: reqwest::Client,
+    embed_url: reqwest::Url,
     model: String,
     dimension: usize,
     base_url: String,
+    allow_truncation: bool,
+    truncation_logged: Arc<AtomicBool>,
+    timeout: Option<Duration>,
 }
 
 impl OllamaClient {
@@ -135,6 +240,9 @@ impl OllamaClient {
         base_url: String,
         model: String,
         dimension: usize,
+        extra_headers: &BTreeMap<String, String>,
+        allow_truncation: bool,
+        timeout: Option<Duration>,
     ) -> Result<Self, EmbeddingClientError> {
         if dimension == 0 {
             return Err(EmbeddingClientError::Configuration(
@@ -142,21 +250,122 @@ impl OllamaClient {
             ));
         }
 
-        let inner = Ollama::try_new(base_url.as_str()).map_err(|error| {
-            EmbeddingClientError::Configuration(format!("invalid OLLAMA_URL '{base_url}': {error}"))
+        let embed_url = reqwest::Url::parse(&format!("{}/", base_url.trim_end_matches('/')))
+            .and_then(|url| url.join("api/embed"))
+            .map_err(|error| {
+                EmbeddingClientError::Configuration(format!(
+                    "invalid OLLAMA_URL '{base_url}': {error}"
+                ))
+            })?;
+        let mut http = reqwest::Client::builder().default_headers(header_map(extra_headers)?);
+        if let Some(timeout) = timeout {
+            http = http.timeout(timeout);
+        }
+        let http = http.build().map_err(|error| {
+            EmbeddingClientError::Configuration(format!(
+                "failed to build the embedding HTTP client: {error}"
+            ))
         })?;
 
         Ok(Self {
-            inner,
+            http,
+            embed_url,
             model,
             dimension,
             base_url,
+            allow_truncation,
+            truncation_logged: Arc::new(AtomicBool::new(false)),
+            timeout,
         })
     }
+
+    /// Classify a failed request, calling out an elapsed `EMBEDDING_TIMEOUT_MS`.
+    ///
+    /// Only connection failures and timeouts mean the runtime is unavailable; anything else
+    /// (an unreadable body, a malformed request) fails the same way on retry.
+    fn request_failed(&self, error: reqwest::Error) -> EmbeddingClientError {
+        match self.timeout {
+            Some(timeout) if error.is_timeout() => {
+                EmbeddingClientError::ProviderUnavailable(format!(
+                    "Ollama at {} did not respond within {} ms (EMBEDDING_TIMEOUT_MS)",
+                    self.base_url,
+                    timeout.as_millis()
+                ))
+            }
+            _ if error.is_connect() || error.is_timeout() => {
+                EmbeddingClientError::ProviderUnavailable(format!(
+                    "failed to reach Ollama at {}: {}. Set OLLAMA_URL and ensure the runtime is running.",
+                    self.base_url, error
+                ))
+            }
+            _ => EmbeddingClientError::GenerationFailed(format!(
+                "Ollama request to {} failed: {error}",
+                self.base_url
+            )),
+        }
+    }
+
+    /// Classify an error response from `/api/embed`.
+    ///
+    /// Server errors and rate limiting are retryable; a missing model is a configuration
+    /// problem, and other client errors reject the input itself.
+    fn status_failed(&self, status: StatusCode, body: &str) -> EmbeddingClientError {
+        let body = body.trim();
+        if status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS {
+            EmbeddingClientError::ProviderUnavailable(format!(
+                "Ollama at {} answered {status}: {body}",
+                self.base_url
+            ))
+        } else if status == StatusCode::NOT_FOUND {
+            EmbeddingClientError::Configuration(format!(
+                "Ollama at {} does not serve model '{}' ({status}: {body}). Pull it with `ollama pull {}` or set EMBEDDING_MODEL.",
+                self.base_url, self.model, self.model
+            ))
+        } else {
+            EmbeddingClientError::GenerationFailed(format!(
+                "Ollama at {} rejected the embedding request with {status}: {body}",
+                self.base_url
+            ))
+        }
+    }
+
+    /// Bring a returned vector to `dimension`, truncating over-long ones when allowed.
+    fn fit_dimension(&self, vector: &mut Vec<f32>) -> Result<(), EmbeddingClientError> {
+        let returned = vector.len();
+        if returned == self.dimension {
+            return Ok(());
+        }
+        if returned > self.dimension && self.allow_truncation {
+            if !self.truncation_logged.swap(true, Ordering::Relaxed) {
+                tracing::warn!(
+                    model = %self.model,
+                    returned,
+                    dimension = self.dimension,
+                    "Truncating embeddings to EMBEDDING_DIMENSION (EMBEDDING_ALLOW_TRUNCATION)"
+                );
+            }
+            vector.truncate(self.dimension);
+            return Ok(());
+        }
+        let hint = if returned > self.dimension {
+            "Update EMBEDDING_DIMENSION, use a compatible model, or set EMBEDDING_ALLOW_TRUNCATION=1 for Matryoshka models."
+        } else {
+            "Update EMBEDDING_DIMENSION or use a compatible model."
+        };
+        Err(EmbeddingClientError::Configuration(format!(
+            "Ollama model '{}' at {} produced vectors of dimension {} but EMBEDDING_DIMENSION is {}. {hint}",
+            self.model, self.base_url, returned, self.dimension
+        )))
+    }
 }
 
 #[async_trait]
 impl EmbeddingClient for OllamaClient {
+    #[tracing::instrument(
+        name = "embedding.generate",
+        skip_all,
+        fields(provider = "ollama", model = %self.model, texts = texts.len())
+    )]
     async fn generate_embeddings(
         &self,
         texts: Vec<String>,
@@ -178,17 +387,23 @@ impl EmbeddingClient for OllamaClient {
 
         let request = GenerateEmbeddingsRequest::new(self.model.clone(), texts.into());
         let response = self
-            .inner
-            .generate_embeddings(request)
+            .http
+            .post(self.embed_url.clone())
+            .json(&request)
+            .send()
             .await
-            .map_err(|error| {
-                EmbeddingClientError::ProviderUnavailable(format!(
-                    "failed to reach Ollama at {}: {}. Set OLLAMA_URL and ensure the runtime is running.",
-                    self.base_url, error
-                ))
-            })?;
+            .map_err(|error| self.request_failed(error))?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(self.status_failed(status, &body));
+        }
+        let response: GenerateEmbeddingsResponse = response
+            .json()
+            .await
+            .map_err(|error| self.request_failed(error))?;
 
-        let embeddings = response.embeddings;
+        let mut embeddings = response.embeddings;
 
         if embeddings.len() != text_count {
             return Err(EmbeddingClientError::GenerationFailed(format!(
@@ -199,22 +414,32 @@ impl EmbeddingClient for OllamaClient {
             )));
         }
 
-        for vector in &embeddings {
-            if vector.len() != self.dimension {
-                return Err(EmbeddingClientError::GenerationFailed(format!(
-                    "Ollama model '{}' at {} produced vectors of dimension {} but EMBEDDING_DIMENSION is {}. Update EMBEDDING_DIMENSION or use a compatible model.",
-                    self.model,
-                    self.base_url,
-                    vector.len(),
-                    self.dimension
-                )));
-            }
+        for vector in &mut embeddings {
+            self.fit_dimension(vector)?;
         }
 
         Ok(embeddings)
     }
 }
 
+/// Convert configured extra headers into a `HeaderMap`.
+fn header_map(headers: &BTreeMap<String, String>) -> Result<HeaderMap, EmbeddingClientError> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            match (
+                HeaderName::from_bytes(name.as_bytes()),
+                HeaderValue::from_str(value),
+            ) {
+                (Ok(name), Ok(value)) => Ok((name, value)),
+                _ => Err(EmbeddingClientError::Configuration(format!(
+                    "invalid EMBEDDING_EXTRA_HEADERS entry `{name}`"
+                ))),
+            }
+        })
+        .collect()
+}
+
 /// Build an embedding client suitable for the current configuration.
 ///
 /// - When `EMBEDDING_PROVIDER=ollama`, constructs an `OllamaClient` pointed at `OLLAMA_URL`
@@ -222,10 +447,50 @@ impl EmbeddingClient for OllamaClient {
 /// - Otherwise returns the deterministic `AiLibClient`.
 ///
 /// Errors during Ollama client initialization are surfaced as a process panic because the binary
-/// cannot function without a working embedding backend in that mode.
+/// cannot function without a working embedding backend in that mode. When
+/// `EMBEDDING_DISK_CACHE_PATH` is set the client is wrapped in a [`DiskCachedEmbeddingClient`];
+/// a cache file that cannot be opened is logged and the client runs uncached.
 pub fn get_embedding_client() -> Box<dyn EmbeddingClient + Send + Sync> {
     let config = get_config();
-    match config.embedding_provider {
+    let client = provider_client(
+        config.embedding_provider,
+        &config.embedding_model,
+        config.embedding_dimension,
+    );
+    #[cfg(feature = "fault-injection")]
+    let client = crate::fault::embedding_client_from_env(client);
+    let Some(path) = &config.embedding_disk_cache_path else {
+        return client;
+    };
+    match EmbeddingDiskCache::open(path, &config.embedding_model, config.embedding_dimension) {
+        Ok(cache) => Box::new(DiskCachedEmbeddingClient::new(client, cache)),
+        Err(error) => {
+            tracing::warn!(
+                path = %path.display(),
+                error = %error,
+                "Failed to open EMBEDDING_DISK_CACHE_PATH; embeddings will not be cached"
+            );
+            client
+        }
+    }
+}
+
+/// Build the client for `EMBEDDING_PROVIDER_NEXT` while a provider migration is configured.
+///
+/// The candidate client is never disk cached, so cached vectors of the current model cannot be
+/// mixed into the candidate vectors.
+pub fn get_next_embedding_client() -> Option<Box<dyn EmbeddingClient + Send + Sync>> {
+    let next = get_config().embedding_next.as_ref()?;
+    Some(provider_client(next.provider, &next.model, next.dimension))
+}
+
+fn provider_client(
+    provider: EmbeddingProvider,
+    model: &str,
+    dimension: usize,
+) -> Box<dyn EmbeddingClient + Send + Sync> {
+    let config = get_config();
+    match provider {
         EmbeddingProvider::Ollama => {
             let base_url = config
                 .ollama_url
@@ -234,13 +499,17 @@ pub fn get_embedding_client() -> Box<dyn EmbeddingClient + Send + Sync> {
             tracing::info!(
                 provider = "ollama",
                 url = %base_url,
-                model = %config.embedding_model,
+                model = %model,
                 "Using Ollama embedding provider"
             );
             let client = OllamaClient::try_new(
                 base_url,
-                config.embedding_model.clone(),
-                config.embedding_dimension,
+                model.to_string(),
+                dimension,
+                &config.embedding_extra_headers,
+                config.embedding_allow_truncation,
+                (config.embedding_timeout_ms > 0)
+                    .then(|| Duration::from_millis(config.embedding_timeout_ms)),
             )
             .unwrap_or_else(|error| {
                 panic!("Failed to initialize Ollama embedding client: {error}");
@@ -248,19 +517,79 @@ pub fn get_embedding_client() -> Box<dyn EmbeddingClient + Send + Sync> {
             Box::new(client)
         }
         EmbeddingProvider::OpenAI => {
+            if let Some(warning) = openai_dimension_mismatch(model, dimension) {
+                tracing::warn!(model = %model, "{warning}");
+            }
             tracing::info!(
                 provider = "deterministic-fallback",
-                configured_provider = ?config.embedding_provider,
+                configured_provider = ?provider,
                 "Using deterministic embeddings for compatibility"
             );
-            Box::new(AiLibClient::new())
+            Box::new(AiLibClient::with_dimension(dimension))
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{EmbeddingClientError, OllamaClient};
+    use super::{
+        AiLibClient, EmbeddingClient, EmbeddingClientError, OllamaClient,
+        openai_dimension_mismatch, openai_model_dimension, validate_embeddings,
+    };
+    use httpmock::prelude::*;
+    use serde_json::json;
+    use std::collections::BTreeMap;
+    use std::time::Duration;
+
+    #[test]
+    fn openai_model_dimensions_are_looked_up_by_normalized_name() {
+        assert_eq!(openai_model_dimension("text-embedding-3-small"), Some(1536));
+        assert_eq!(
+            openai_model_dimension(" OpenAI/text-embedding-3-large "),
+            Some(3072)
+        );
+        assert_eq!(openai_model_dimension("text-embedding-ada-002"), Some(1536));
+        assert_eq!(openai_model_dimension("nomic-embed-text"), None);
+    }
+
+    #[test]
+    fn openai_dimension_mismatch_names_the_expected_dimension() {
+        let warning =
+            openai_dimension_mismatch("text-embedding-3-large", 1536).expect("mismatch warning");
+        assert!(warning.contains("EMBEDDING_DIMENSION is 1536"), "{warning}");
+        assert!(warning.contains("EMBEDDING_DIMENSION=3072"), "{warning}");
+        assert!(warning.contains("EMBEDDING_DIMENSION_AUTO"), "{warning}");
+
+        assert!(openai_dimension_mismatch("text-embedding-3-large", 3072).is_none());
+        assert!(openai_dimension_mismatch("custom-model", 768).is_none());
+    }
+
+    #[test]
+    fn validate_embeddings_rejects_nan_infinite_and_zero_vectors() {
+        let valid = vec![0.6, 0.8];
+        assert!(validate_embeddings(&[valid.clone(), vec![0.0, -1.0]]).is_ok());
+
+        for (bad, expected) in [
+            (vec![0.5, f32::NAN], "contains NaN components"),
+            (vec![f32::INFINITY, 0.5], "contains infinite components"),
+            (vec![f32::NEG_INFINITY, 0.5], "contains infinite components"),
+            (vec![0.0, -0.0], "is all zeros"),
+        ] {
+            let error = validate_embeddings(&[valid.clone(), bad]).expect_err("invalid vector");
+            assert!(
+                matches!(error, EmbeddingClientError::InvalidVector { index: 1, reason } if reason == expected),
+                "unexpected error: {error}"
+            );
+        }
+    }
+
+    #[test]
+    fn deterministic_encoder_never_returns_zero_vectors() {
+        for text in ["", "\0\0", "hello"] {
+            let vector = AiLibClient::encode(text, 8);
+            assert!(validate_embeddings(&[vector]).is_ok(), "{text:?}");
+        }
+    }
 
     #[test]
     fn ollama_client_rejects_zero_dimension() {
@@ -268,6 +597,9 @@ mod tests {
             "http://localhost:11434".to_string(),
             "test-model".to_string(),
             0,
+            &BTreeMap::new(),
+            false,
+            None,
         );
 
         assert!(matches!(
@@ -279,10 +611,200 @@ mod tests {
 
     #[test]
     fn ollama_client_requires_valid_url() {
-        let result = OllamaClient::try_new("not a url".to_string(), "test-model".to_string(), 128);
+        let result = OllamaClient::try_new(
+            "not a url".to_string(),
+            "test-model".to_string(),
+            128,
+            &BTreeMap::new(),
+            false,
+            None,
+        );
 
         assert!(
             matches!(result, Err(EmbeddingClientError::Configuration(message)) if message.contains("invalid OLLAMA_URL"))
         );
     }
+
+    #[tokio::test]
+    async fn ollama_requests_carry_extra_headers() {
+        let server = MockServer::start_async().await;
+        let embed = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/api/embed")
+                    .header("x-org-id", "acme")
+                    .header("x-route", "gpu-pool");
+                then.status(200)
+                    .json_body(json!({ "embeddings": [[0.6, 0.8]] }));
+            })
+            .await;
+        let headers = BTreeMap::from([
+            ("x-org-id".to_string(), "acme".to_string()),
+            ("x-route".to_string(), "gpu-pool".to_string()),
+        ]);
+        let client = OllamaClient::try_new(
+            server.base_url(),
+            "test-model".to_string(),
+            2,
+            &headers,
+            false,
+            None,
+        )
+        .expect("client");
+
+        let embeddings = client
+            .generate_embeddings(vec!["hello".to_string()])
+            .await
+            .expect("embeddings");
+
+        embed.assert_async().await;
+        assert_eq!(embeddings, vec![vec![0.6, 0.8]]);
+    }
+
+    #[tokio::test]
+    async fn stalled_ollama_requests_time_out_as_provider_unavailable() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(POST).path("/api/embed");
+                then.status(200)
+                    .delay(Duration::from_millis(500))
+                    .json_body(json!({ "embeddings": [[0.6, 0.8]] }));
+            })
+            .await;
+        let client = OllamaClient::try_new(
+            server.base_url(),
+            "test-model".to_string(),
+            2,
+            &BTreeMap::new(),
+            false,
+            Some(Duration::from_millis(50)),
+        )
+        .expect("client");
+
+        let error = client
+            .generate_embeddings(vec!["hello".to_string()])
+            .await
+            .expect_err("timed out");
+
+        assert!(
+            matches!(&error, EmbeddingClientError::ProviderUnavailable(message) if message.contains("did not respond within 50 ms")),
+            "unexpected error: {error}"
+        );
+    }
+
+    #[tokio::test]
+    async fn only_unreachable_or_failing_runtimes_are_provider_unavailable() {
+        let server = MockServer::start_async().await;
+        let cases = [
+            (503, json!({ "error": "server busy" }), "unavailable"),
+            (429, json!({ "error": "slow down" }), "unavailable"),
+            (
+                404,
+                json!({ "error": "model \"test-model\" not found" }),
+                "configuration",
+            ),
+            (
+                400,
+                json!({ "error": "input exceeds context length" }),
+                "failed",
+            ),
+            (200, json!({ "vectors": [] }), "failed"),
+        ];
+        for (status, body, expected) in cases {
+            let mock = server
+                .mock_async(|when, then| {
+                    when.method(POST).path("/api/embed");
+                    then.status(status).json_body(body.clone());
+                })
+                .await;
+            let error = embed_with(&server, false).await.unwrap_err();
+            let kind = match &error {
+                EmbeddingClientError::ProviderUnavailable(_) => "unavailable",
+                EmbeddingClientError::Configuration(message) => {
+                    assert!(message.contains("ollama pull test-model"), "{message}");
+                    "configuration"
+                }
+                EmbeddingClientError::GenerationFailed(_) => "failed",
+                EmbeddingClientError::InvalidVector { .. } => "invalid",
+            };
+            assert_eq!(kind, expected, "{status}: {error}");
+            mock.delete_async().await;
+        }
+
+        let unreachable = OllamaClient::try_new(
+            "http://127.0.0.1:9".to_string(),
+            "test-model".to_string(),
+            2,
+            &BTreeMap::new(),
+            false,
+            None,
+        )
+        .expect("client");
+        let error = unreachable
+            .generate_embeddings(vec!["hello".to_string()])
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&error, EmbeddingClientError::ProviderUnavailable(message) if message.contains("Set OLLAMA_URL")),
+            "{error}"
+        );
+    }
+
+    async fn embed_with(
+        server: &MockServer,
+        allow_truncation: bool,
+    ) -> Result<Vec<Vec<f32>>, EmbeddingClientError> {
+        let client = OllamaClient::try_new(
+            server.base_url(),
+            "test-model".to_string(),
+            2,
+            &BTreeMap::new(),
+            allow_truncation,
+            None,
+        )
+        .expect("client");
+        client.generate_embeddings(vec!["hello".to_string()]).await
+    }
+
+    #[tokio::test]
+    async fn over_long_vectors_are_truncated_only_when_allowed() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(POST).path("/api/embed");
+                then.status(200)
+                    .json_body(json!({ "embeddings": [[0.6, 0.8, 0.1, 0.2]] }));
+            })
+            .await;
+
+        let embeddings = embed_with(&server, true).await.expect("truncated");
+        assert_eq!(embeddings, vec![vec![0.6, 0.8]]);
+
+        let error = embed_with(&server, false).await.unwrap_err();
+        assert!(matches!(
+            error,
+            EmbeddingClientError::Configuration(message)
+                if message.contains("dimension 4") && message.contains("EMBEDDING_ALLOW_TRUNCATION")
+        ));
+    }
+
+    #[tokio::test]
+    async fn short_vectors_are_rejected_even_when_truncation_is_allowed() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(POST).path("/api/embed");
+                then.status(200).json_body(json!({ "embeddings": [[0.6]] }));
+            })
+            .await;
+
+        let error = embed_with(&server, true).await.unwrap_err();
+        assert!(matches!(
+            error,
+            EmbeddingClientError::Configuration(message)
+                if message.contains("dimension 1 but EMBEDDING_DIMENSION is 2")
+                    && !message.contains("EMBEDDING_ALLOW_TRUNCATION")
+        ));
+    }
 }
@@ -0,0 +1,300 @@
+//! Deterministic fault injection for the embedding client and the Qdrant transport.
+//!
+//! Only compiled with the `fault-injection` feature. Each layer reads its own settings from the
+//! environment (`FAULT_EMBEDDING_*`, `FAULT_QDRANT_*`):
+//!
+//! * `FAULT_<LAYER>_ERROR_RATE` – share of calls that fail, in `[0, 1]`. Failures are spread
+//!   evenly rather than drawn at random, so `0.25` fails calls 4, 8, 12, … on every run.
+//! * `FAULT_<LAYER>_LATENCY_MS` – delay added before every call, failing or not.
+//! * `FAULT_<LAYER>_FAIL_ON_CALL` – 1-based call number that always fails.
+//!
+//! Injected embedding failures surface as `ProviderUnavailable`; injected Qdrant failures as a
+//! `503` status, so both are classified as transient like a real outage.
+
+use std::{
+    fmt,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+use async_trait::async_trait;
+
+use crate::embedding::{EmbeddingClient, EmbeddingClientError};
+
+/// Failures and delays injected into one layer.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FaultConfig {
+    /// Share of calls that fail, in `[0, 1]`.
+    pub error_rate: f64,
+    /// Delay added before every call.
+    pub latency: Duration,
+    /// 1-based call number that always fails.
+    pub fail_on_call: Option<u64>,
+}
+
+impl FaultConfig {
+    /// Read `FAULT_<layer>_*` from the environment; `None` when no variable is set.
+    pub fn from_env(layer: &str) -> Result<Option<Self>, String> {
+        Self::from_lookup(layer, |key| std::env::var(key).ok())
+    }
+
+    /// Parse `FAULT_<layer>_*` values supplied by `lookup`.
+    pub fn from_lookup(
+        layer: &str,
+        lookup: impl Fn(&str) -> Option<String>,
+    ) -> Result<Option<Self>, String> {
+        let read = |suffix: &str| {
+            let key = format!("FAULT_{layer}_{suffix}");
+            lookup(&key)
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+                .map(|value| (key, value))
+        };
+        let error_rate = read("ERROR_RATE")
+            .map(|(key, value)| match value.parse::<f64>() {
+                Ok(rate) if (0.0..=1.0).contains(&rate) => Ok(rate),
+                _ => Err(format!(
+                    "{key} must be a number between 0 and 1 (got '{value}')"
+                )),
+            })
+            .transpose()?;
+        let latency = read("LATENCY_MS")
+            .map(|(key, value)| {
+                value
+                    .parse::<u64>()
+                    .map(Duration::from_millis)
+                    .map_err(|_| {
+                        format!("{key} must be a whole number of milliseconds (got '{value}')")
+                    })
+            })
+            .transpose()?;
+        let fail_on_call = read("FAIL_ON_CALL")
+            .map(|(key, value)| match value.parse::<u64>() {
+                Ok(call) if call > 0 => Ok(call),
+                _ => Err(format!(
+                    "{key} must be a positive call number (got '{value}')"
+                )),
+            })
+            .transpose()?;
+
+        if error_rate.is_none() && latency.is_none() && fail_on_call.is_none() {
+            return Ok(None);
+        }
+        Ok(Some(Self {
+            error_rate: error_rate.unwrap_or(0.0),
+            latency: latency.unwrap_or_default(),
+            fail_on_call,
+        }))
+    }
+
+    /// Whether the 1-based `call` is scheduled to fail.
+    pub fn fails_on(&self, call: u64) -> bool {
+        let spread = |n: u64| (n as f64 * self.error_rate).floor();
+        self.fail_on_call == Some(call) || (call > 0 && spread(call) > spread(call - 1))
+    }
+}
+
+/// Failure injected on a numbered call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InjectedFault {
+    /// 1-based call that was failed.
+    pub call: u64,
+}
+
+impl fmt::Display for InjectedFault {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "injected fault on call {}", self.call)
+    }
+}
+
+/// Call counter applying a [`FaultConfig`].
+#[derive(Debug, Default)]
+pub struct FaultInjector {
+    config: FaultConfig,
+    calls: AtomicU64,
+}
+
+impl FaultInjector {
+    /// Start counting calls from zero.
+    pub fn new(config: FaultConfig) -> Self {
+        Self {
+            config,
+            calls: AtomicU64::new(0),
+        }
+    }
+
+    /// Calls seen so far, including failed ones.
+    pub fn calls(&self) -> u64 {
+        self.calls.load(Ordering::SeqCst)
+    }
+
+    /// Count a call, wait out the configured latency, and fail it if scheduled.
+    pub async fn inject(&self) -> Result<(), InjectedFault> {
+        let call = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+        if !self.config.latency.is_zero() {
+            tokio::time::sleep(self.config.latency).await;
+        }
+        if self.config.fails_on(call) {
+            tracing::debug!(call, "Injecting fault");
+            return Err(InjectedFault { call });
+        }
+        Ok(())
+    }
+}
+
+/// Embedding client that runs a [`FaultInjector`] before delegating each batch.
+pub struct FaultyEmbeddingClient {
+    inner: Box<dyn EmbeddingClient + Send + Sync>,
+    injector: FaultInjector,
+}
+
+impl FaultyEmbeddingClient {
+    /// Wrap `inner` with the given faults.
+    pub fn new(inner: Box<dyn EmbeddingClient + Send + Sync>, config: FaultConfig) -> Self {
+        Self {
+            inner,
+            injector: FaultInjector::new(config),
+        }
+    }
+
+    /// Batches requested so far, including failed ones.
+    pub fn calls(&self) -> u64 {
+        self.injector.calls()
+    }
+}
+
+#[async_trait]
+impl EmbeddingClient for FaultyEmbeddingClient {
+    async fn generate_embeddings(
+        &self,
+        texts: Vec<String>,
+    ) -> Result<Vec<Vec<f32>>, EmbeddingClientError> {
+        self.injector
+            .inject()
+            .await
+            .map_err(|fault| EmbeddingClientError::ProviderUnavailable(fault.to_string()))?;
+        self.inner.generate_embeddings(texts).await
+    }
+}
+
+/// Wrap `client` with the `FAULT_EMBEDDING_*` faults, or return it unchanged when none are set.
+///
+/// Invalid settings panic, like other embedding client initialization failures.
+pub fn embedding_client_from_env(
+    client: Box<dyn EmbeddingClient + Send + Sync>,
+) -> Box<dyn EmbeddingClient + Send + Sync> {
+    match layer_from_env("EMBEDDING") {
+        Some(config) => Box::new(FaultyEmbeddingClient::new(client, config)),
+        None => client,
+    }
+}
+
+/// Injector for the Qdrant transport built from `FAULT_QDRANT_*`, if any are set.
+pub fn qdrant_injector_from_env() -> Option<Arc<FaultInjector>> {
+    layer_from_env("QDRANT").map(|config| Arc::new(FaultInjector::new(config)))
+}
+
+fn layer_from_env(layer: &str) -> Option<FaultConfig> {
+    let config = FaultConfig::from_env(layer)
+        .unwrap_or_else(|error| panic!("Invalid fault-injection settings: {error}"))?;
+    tracing::warn!(layer, ?config, "Fault injection enabled");
+    Some(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    struct UnitEmbedding;
+
+    #[async_trait]
+    impl EmbeddingClient for UnitEmbedding {
+        async fn generate_embeddings(
+            &self,
+            texts: Vec<String>,
+        ) -> Result<Vec<Vec<f32>>, EmbeddingClientError> {
+            Ok(texts.iter().map(|_| vec![1.0]).collect())
+        }
+    }
+
+    fn config(pairs: &[(&str, &str)]) -> Result<Option<FaultConfig>, String> {
+        let values: HashMap<String, String> = pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        FaultConfig::from_lookup("QDRANT", |key| values.get(key).cloned())
+    }
+
+    #[test]
+    fn env_values_are_parsed_per_layer() {
+        assert_eq!(config(&[]), Ok(None));
+        assert_eq!(config(&[("FAULT_EMBEDDING_ERROR_RATE", "1")]), Ok(None));
+        assert_eq!(
+            config(&[
+                ("FAULT_QDRANT_ERROR_RATE", "0.5"),
+                ("FAULT_QDRANT_LATENCY_MS", "20"),
+            ]),
+            Ok(Some(FaultConfig {
+                error_rate: 0.5,
+                latency: Duration::from_millis(20),
+                fail_on_call: None,
+            }))
+        );
+        for invalid in [
+            ("FAULT_QDRANT_ERROR_RATE", "1.5"),
+            ("FAULT_QDRANT_LATENCY_MS", "-1"),
+            ("FAULT_QDRANT_FAIL_ON_CALL", "0"),
+        ] {
+            let error = config(&[invalid]).unwrap_err();
+            assert!(error.contains(invalid.0), "{error}");
+        }
+    }
+
+    #[test]
+    fn error_rate_spreads_failures_evenly() {
+        let quarter = FaultConfig {
+            error_rate: 0.25,
+            ..FaultConfig::default()
+        };
+        let failing: Vec<u64> = (1..=12).filter(|&call| quarter.fails_on(call)).collect();
+        assert_eq!(failing, [4, 8, 12]);
+
+        let always = FaultConfig {
+            error_rate: 1.0,
+            ..FaultConfig::default()
+        };
+        assert!((1..=5).all(|call| always.fails_on(call)));
+
+        let third_call = FaultConfig {
+            fail_on_call: Some(3),
+            ..FaultConfig::default()
+        };
+        let failing: Vec<u64> = (1..=6).filter(|&call| third_call.fails_on(call)).collect();
+        assert_eq!(failing, [3]);
+    }
+
+    #[tokio::test]
+    async fn embedding_faults_follow_the_call_sequence() {
+        let client = FaultyEmbeddingClient::new(
+            Box::new(UnitEmbedding),
+            FaultConfig {
+                fail_on_call: Some(2),
+                ..FaultConfig::default()
+            },
+        );
+        let embed = || client.generate_embeddings(vec!["hello".into()]);
+
+        assert!(embed().await.is_ok());
+        let error = embed().await.unwrap_err();
+        assert!(
+            matches!(&error, EmbeddingClientError::ProviderUnavailable(message) if message == "injected fault on call 2"),
+            "{error}"
+        );
+        assert!(embed().await.is_ok());
+        assert_eq!(client.calls(), 3);
+    }
+}
@@ -12,19 +12,29 @@
 //!   embeddings, and writes vectors to Qdrant while recording metrics.
 //! * `api` and `mcp` surface the same processing primitives through REST and MCP
 //!   tooling respectively.
-//! * `metrics`, `logging`, and `qdrant` provide the supporting infrastructure.
+//! * `metrics`, `logging`, `audit`, and `qdrant` provide the supporting infrastructure.
 //!
 //! The library can be embedded in other projects or used as documentation for
 //! students exploring how modern agent memory stacks are built in Rust.
 
 /// HTTP routing and REST handlers.
 pub mod api;
+/// Append-only audit trail of mutating operations.
+pub mod audit;
+/// Typed async client for the HTTP API.
+#[cfg(feature = "http-client")]
+pub mod client;
 /// Environment-driven configuration management.
 pub mod config;
 /// Embedding client abstraction and adapters.
 pub mod embedding;
+/// Deterministic fault injection for reliability tests.
+#[cfg(feature = "fault-injection")]
+pub mod fault;
 /// Structured logging and tracing setup.
 pub mod logging;
+/// Scheduled background maintenance (duplicate purge, episodic TTL eviction).
+pub mod maintenance;
 /// Model Context Protocol server implementation.
 pub mod mcp;
 /// Ingestion metrics helpers.
@@ -33,5 +43,9 @@ pub mod metrics;
 pub mod processing;
 /// Qdrant vector store integration.
 pub mod qdrant;
+/// Size cap for list-shaped HTTP and MCP responses.
+pub mod response_limit;
+/// Persistent ingest cursors for incremental source syncing.
+pub mod state;
 /// Optional abstractive summarization client(s).
 pub mod summarization;
@@ -4,36 +4,59 @@
 //! `RUSTY_MEM_LOG_FILE` is set, logs are appended to that path; otherwise a file logger is
 //! created under `logs/rusty-mem.log`. A non‑blocking writer is used to minimize contention
 //! on hot paths.
+//!
+//! Built with the `otel` feature and with `OTEL_EXPORTER_OTLP_ENDPOINT` set, spans are also
+//! exported over OTLP/HTTP and outbound requests carry a W3C `traceparent` header. Otherwise the
+//! same spans stay local to the log layers.
+use std::future::Future;
 use std::sync::OnceLock;
+use std::time::Instant;
 
+use reqwest::header::HeaderMap;
+use tracing::{Instrument, Span, field};
 use tracing_appender::non_blocking::{NonBlocking, WorkerGuard};
 use tracing_subscriber::{EnvFilter, fmt, prelude::*};
 
 static LOG_GUARD: OnceLock<WorkerGuard> = OnceLock::new();
 
+#[cfg(feature = "otel")]
+static TRACER_PROVIDER: OnceLock<opentelemetry_sdk::trace::SdkTracerProvider> = OnceLock::new();
+
 /// Configure tracing subscribers for stdout and optional file logging.
 ///
 /// - Respects `RUST_LOG` for filtering (defaults to `info`).
 /// - Installs a compact stdout layer and, when available, a file layer.
+/// - Adds the OTLP export layer when built with `otel` and `OTEL_EXPORTER_OTLP_ENDPOINT` is set.
 /// - Uses a global guard to keep the non‑blocking writer alive for the process lifetime.
 pub fn init_tracing() {
     let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
     let stdout_layer = fmt::layer().with_target(false).compact();
+    let file_layer = configure_file_writer().map(|writer| {
+        fmt::layer()
+            .with_writer(writer)
+            .with_target(true)
+            .with_ansi(false)
+            .compact()
+    });
 
     let registry = tracing_subscriber::registry()
         .with(env_filter)
-        .with(stdout_layer);
+        .with(stdout_layer)
+        .with(file_layer);
 
-    if let Some(writer) = configure_file_writer() {
-        let file_layer = fmt::layer()
-            .with_writer(writer)
-            .with_target(true)
-            .with_ansi(false)
-            .compact();
+    #[cfg(feature = "otel")]
+    registry.with(otel::layer()).init();
+    #[cfg(not(feature = "otel"))]
+    registry.init();
+}
 
-        registry.with(file_layer).init();
-    } else {
-        registry.init();
+/// Flush and stop the OTLP exporter, if one was started; call once before the process exits.
+pub fn shutdown_tracing() {
+    #[cfg(feature = "otel")]
+    if let Some(provider) = TRACER_PROVIDER.get()
+        && let Err(error) = provider.shutdown()
+    {
+        eprintln!("Failed to flush OpenTelemetry spans: {error}");
     }
 }
 
@@ -68,3 +91,218 @@ fn configure_file_writer() -> Option<NonBlocking> {
         Some(non_blocking)
     }
 }
+
+/// Send an outbound request inside an `outbound.request` span.
+///
+/// The span records the peer, method, path, target collection (for `collections/{name}` paths),
+/// status, and duration, and its trace context is injected into the request headers.
+pub(crate) trait TracedSend {
+    /// Build and execute the request on behalf of `peer` (for example `qdrant`).
+    fn send_traced(
+        self,
+        peer: &'static str,
+    ) -> impl Future<Output = reqwest::Result<reqwest::Response>> + Send;
+}
+
+impl TracedSend for reqwest::RequestBuilder {
+    async fn send_traced(self, peer: &'static str) -> reqwest::Result<reqwest::Response> {
+        let (client, request) = self.build_split();
+        let mut request = request?;
+        let path = request.url().path().to_string();
+        let span = tracing::info_span!(
+            "outbound.request",
+            peer.service = peer,
+            http.method = %request.method(),
+            url.path = %path,
+            collection = collection_from_path(&path),
+            http.status_code = field::Empty,
+            duration_ms = field::Empty,
+            error = field::Empty,
+        );
+        inject_trace_context(&span, request.headers_mut());
+
+        let started = Instant::now();
+        let result = client.execute(request).instrument(span.clone()).await;
+        span.record("duration_ms", started.elapsed().as_millis() as u64);
+        match &result {
+            Ok(response) => span.record("http.status_code", response.status().as_u16()),
+            Err(error) => span.record("error", field::display(error)),
+        };
+        result
+    }
+}
+
+/// Collection named by a `/collections/{name}/...` request path.
+fn collection_from_path(path: &str) -> Option<&str> {
+    path.strip_prefix("/collections/")?
+        .split('/')
+        .next()
+        .filter(|name| !name.is_empty())
+}
+
+/// Write the span's W3C trace context into outbound request headers.
+#[cfg(feature = "otel")]
+fn inject_trace_context(span: &Span, headers: &mut HeaderMap) {
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+    let context = span.context();
+    opentelemetry::global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(&context, &mut otel::HeaderInjector(headers));
+    });
+}
+
+#[cfg(not(feature = "otel"))]
+fn inject_trace_context(_span: &Span, _headers: &mut HeaderMap) {}
+
+#[cfg(feature = "otel")]
+mod otel {
+    use opentelemetry::{global, propagation::Injector, trace::TracerProvider as _};
+    use opentelemetry_sdk::{
+        Resource, propagation::TraceContextPropagator, trace::SdkTracerProvider,
+    };
+    use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+    use tracing::Subscriber;
+    use tracing_subscriber::{Layer, registry::LookupSpan};
+
+    use super::TRACER_PROVIDER;
+
+    /// Service name reported on exported spans.
+    const SERVICE_NAME: &str = "rusty-mem";
+
+    /// OTLP export layer, or `None` when `OTEL_EXPORTER_OTLP_ENDPOINT` is unset or invalid.
+    pub(super) fn layer<S>() -> Option<impl Layer<S>>
+    where
+        S: Subscriber + for<'span> LookupSpan<'span>,
+    {
+        let endpoint = std::env::var(opentelemetry_otlp::OTEL_EXPORTER_OTLP_ENDPOINT)
+            .ok()
+            .filter(|value| !value.trim().is_empty())?;
+        // The exporter reads the endpoint itself and appends `/v1/traces`.
+        let exporter = match opentelemetry_otlp::SpanExporter::builder()
+            .with_http()
+            .build()
+        {
+            Ok(exporter) => exporter,
+            Err(error) => {
+                eprintln!("Failed to configure OTLP exporter for {endpoint}: {error}");
+                return None;
+            }
+        };
+        let provider = SdkTracerProvider::builder()
+            .with_batch_exporter(exporter)
+            .with_resource(Resource::builder().with_service_name(SERVICE_NAME).build())
+            .build();
+        Some(install(provider))
+    }
+
+    /// Register `provider` globally and return the layer feeding it.
+    pub(super) fn install<S>(provider: SdkTracerProvider) -> impl Layer<S>
+    where
+        S: Subscriber + for<'span> LookupSpan<'span>,
+    {
+        let tracer = provider.tracer(SERVICE_NAME);
+        global::set_text_map_propagator(TraceContextPropagator::new());
+        global::set_tracer_provider(provider.clone());
+        let _ = TRACER_PROVIDER.set(provider);
+        tracing_opentelemetry::layer().with_tracer(tracer)
+    }
+
+    /// Adapter writing propagation fields into reqwest headers.
+    pub(super) struct HeaderInjector<'a>(pub(super) &'a mut HeaderMap);
+
+    impl Injector for HeaderInjector<'_> {
+        fn set(&mut self, key: &str, value: String) {
+            if let (Ok(name), Ok(value)) = (
+                HeaderName::from_bytes(key.as_bytes()),
+                HeaderValue::from_str(&value),
+            ) {
+                self.0.insert(name, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collection_is_read_from_collection_paths() {
+        assert_eq!(
+            collection_from_path("/collections/demo/points/query"),
+            Some("demo")
+        );
+        assert_eq!(collection_from_path("/collections/demo"), Some("demo"));
+        assert_eq!(collection_from_path("/collections"), None);
+        assert_eq!(collection_from_path("/"), None);
+    }
+
+    #[cfg(feature = "otel")]
+    mod otel_export {
+        use super::super::otel;
+        use crate::logging::TracedSend;
+        use httpmock::prelude::*;
+        use opentelemetry_sdk::{
+            error::OTelSdkResult,
+            trace::{SdkTracerProvider, SpanData, SpanExporter},
+        };
+        use std::sync::{Arc, Mutex};
+        use tracing_subscriber::prelude::*;
+
+        /// Exporter keeping finished spans in memory.
+        #[derive(Clone, Debug, Default)]
+        struct CapturingExporter(Arc<Mutex<Vec<SpanData>>>);
+
+        impl SpanExporter for CapturingExporter {
+            async fn export(&self, batch: Vec<SpanData>) -> OTelSdkResult {
+                self.0.lock().expect("captured spans").extend(batch);
+                Ok(())
+            }
+        }
+
+        #[tokio::test]
+        async fn outbound_requests_export_spans_and_propagate_context() {
+            let server = MockServer::start_async().await;
+            let mock = server
+                .mock_async(|when, then| {
+                    when.method(GET)
+                        .path("/collections/demo")
+                        .header_exists("traceparent");
+                    then.status(200).body("{}");
+                })
+                .await;
+
+            let exporter = CapturingExporter::default();
+            let provider = SdkTracerProvider::builder()
+                .with_simple_exporter(exporter.clone())
+                .build();
+            let subscriber = tracing_subscriber::registry().with(otel::install(provider.clone()));
+            let _guard = tracing::subscriber::set_default(subscriber);
+
+            let response = reqwest::Client::new()
+                .get(format!("{}/collections/demo", server.base_url()))
+                .send_traced("qdrant")
+                .await
+                .expect("response");
+            assert_eq!(response.status(), 200);
+            provider.force_flush().expect("flush spans");
+
+            mock.assert_async().await;
+            let spans = exporter.0.lock().expect("captured spans");
+            let span = spans
+                .iter()
+                .find(|span| span.name == "outbound.request")
+                .expect("outbound span exported");
+            let attribute = |key: &str| {
+                span.attributes
+                    .iter()
+                    .find(|attribute| attribute.key.as_str() == key)
+                    .map(|attribute| attribute.value.to_string())
+            };
+            assert_eq!(attribute("peer.service").as_deref(), Some("qdrant"));
+            assert_eq!(attribute("collection").as_deref(), Some("demo"));
+            assert_eq!(attribute("http.status_code").as_deref(), Some("200"));
+            assert!(attribute("duration_ms").is_some());
+        }
+    }
+}
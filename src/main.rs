@@ -1,8 +1,10 @@
 //! HTTP server entrypoint for Rusty Memory.
 //!
 //! This binary initializes configuration and logging, constructs the Axum router from the shared
-//! processing pipeline, and binds to `SERVER_PORT` or the first free port in `4100–4199`.
-use rustymcp::{api, config, logging, processing};
+//! processing pipeline, and binds to `SERVER_PORT` or the first free port in `4100–4199`. With
+//! `MAINTENANCE_INTERVAL_MINUTES` set, background maintenance shares the same pipeline and is
+//! stopped after the server drains on Ctrl-C or SIGTERM.
+use rustymcp::{api, config, logging, maintenance, processing};
 use std::sync::Arc;
 use tokio::net::TcpListener;
 
@@ -10,11 +12,52 @@ use tokio::net::TcpListener;
 async fn main() {
     config::init_config();
     logging::init_tracing();
-    let app = api::create_router(Arc::new(processing::ProcessingService::new().await));
+    config::log_config();
+    let service = Arc::new(processing::ProcessingService::new().await);
+    let maintenance = maintenance::MaintenancePlan::from_config(config::get_config())
+        .map(|plan| maintenance::spawn(Arc::clone(&service), plan));
+    let app = api::create_router(service);
 
     let (listener, port) = bind_listener().await.expect("Failed to bind listener");
     tracing::info!("Listening on http://0.0.0.0:{}", port);
-    axum::serve(listener, app).await.unwrap();
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown_signal())
+        .await
+        .unwrap();
+    if let Some(maintenance) = maintenance {
+        maintenance.shutdown().await;
+    }
+    logging::shutdown_tracing();
+}
+
+/// Resolve on Ctrl-C or, on Unix, SIGTERM.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(error) = tokio::signal::ctrl_c().await {
+            tracing::warn!(error = %error, "Failed to listen for Ctrl-C");
+            std::future::pending::<()>().await;
+        }
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(error) => {
+                tracing::warn!(error = %error, "Failed to listen for SIGTERM");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+    tracing::info!("Shutdown signal received");
 }
 
 async fn bind_listener() -> Result<(TcpListener, u16), std::io::Error> {
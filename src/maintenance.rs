@@ -0,0 +1,210 @@
+//! Periodic background maintenance for long-running servers.
+//!
+//! When `MAINTENANCE_INTERVAL_MINUTES` is set, the HTTP binary spawns [`spawn`] next to the
+//! router. Every interval it walks `MAINTENANCE_COLLECTIONS` (the primary collection by default),
+//! deletes redundant copies of duplicated chunks, and, with `MAINTENANCE_EPISODIC_TTL_DAYS`,
+//! prunes episodic memories older than that many days. With `EPISODIC_PARTITIONING=monthly`, the
+//! monthly partitions of each collection are visited too. Results and failures are logged; a
+//! failed collection never stops the schedule.
+//!
+//! The first pass runs one interval after startup. [`MaintenanceHandle::shutdown`] lets an
+//! in-flight pass finish and then stops the task.
+
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
+use crate::config::Config;
+use crate::processing::ProcessingService;
+
+/// Settings for one maintenance pass, resolved from [`Config`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MaintenancePlan {
+    /// Time between passes.
+    pub interval: Duration,
+    /// Collections visited by every pass.
+    pub collections: Vec<String>,
+    /// Episodic memories older than this are pruned; `None` keeps them.
+    pub episodic_ttl: Option<time::Duration>,
+}
+
+impl MaintenancePlan {
+    /// Build the plan from configuration, or `None` when maintenance is disabled.
+    pub fn from_config(config: &Config) -> Option<Self> {
+        let minutes = config.maintenance_interval_minutes?;
+        let collections = if config.maintenance_collections.is_empty() {
+            vec![config.qdrant_collection_name.clone()]
+        } else {
+            config.maintenance_collections.clone()
+        };
+        Some(Self {
+            interval: Duration::from_secs(u64::from(minutes) * 60),
+            collections,
+            episodic_ttl: config
+                .maintenance_episodic_ttl_days
+                .map(|days| time::Duration::days(i64::from(days))),
+        })
+    }
+}
+
+/// Running maintenance task.
+pub struct MaintenanceHandle {
+    stop: oneshot::Sender<()>,
+    task: JoinHandle<()>,
+}
+
+impl MaintenanceHandle {
+    /// Stop the schedule, waiting for an in-flight pass to finish.
+    pub async fn shutdown(self) {
+        let _ = self.stop.send(());
+        if let Err(error) = self.task.await {
+            tracing::warn!(error = %error, "Maintenance task ended abnormally");
+        }
+    }
+}
+
+/// Spawn the maintenance schedule described by `plan` against the shared `service`.
+pub fn spawn(service: Arc<ProcessingService>, plan: MaintenancePlan) -> MaintenanceHandle {
+    let (stop, stopped) = oneshot::channel();
+    tracing::info!(
+        interval_secs = plan.interval.as_secs(),
+        collections = ?plan.collections,
+        episodic_ttl_days = plan.episodic_ttl.map(|ttl| ttl.whole_days()),
+        "Background maintenance enabled"
+    );
+    let interval = plan.interval;
+    let task = tokio::spawn(run_every(
+        interval,
+        async {
+            let _ = stopped.await;
+        },
+        move || {
+            let service = Arc::clone(&service);
+            let plan = plan.clone();
+            async move { run_pass(&service, &plan).await }
+        },
+    ));
+    MaintenanceHandle { stop, task }
+}
+
+/// Call `job` every `period` (first call one period from now) until `shutdown` resolves.
+///
+/// A pass that overruns the period delays the next one instead of triggering a burst, and a
+/// shutdown arriving mid-pass takes effect once that pass returns.
+pub async fn run_every<F, Fut>(period: Duration, shutdown: impl Future<Output = ()>, mut job: F)
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = ()>,
+{
+    let mut ticks = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+    ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    tokio::pin!(shutdown);
+    loop {
+        tokio::select! {
+            _ = &mut shutdown => break,
+            _ = ticks.tick() => job().await,
+        }
+    }
+}
+
+/// Run one maintenance pass over every planned collection, logging each result.
+async fn run_pass(service: &ProcessingService, plan: &MaintenancePlan) {
+    for collection in &plan.collections {
+        let partitions = service
+            .partitions_of(collection)
+            .await
+            .unwrap_or_else(|error| {
+                tracing::warn!(
+                    collection = %collection,
+                    error = %error,
+                    "Maintenance: listing episodic partitions failed"
+                );
+                Vec::new()
+            });
+        for target in std::iter::once(collection).chain(&partitions) {
+            match service.purge_duplicates(target, false).await {
+                Ok(outcome) => tracing::info!(
+                    collection = %target,
+                    scanned = outcome.scanned,
+                    duplicate_groups = outcome.duplicate_groups,
+                    deleted = outcome.deleted,
+                    "Maintenance: duplicate purge finished"
+                ),
+                Err(error) => tracing::warn!(
+                    collection = %target,
+                    error = %error,
+                    "Maintenance: duplicate purge failed"
+                ),
+            }
+        }
+
+        // Episodic pruning covers the partitions itself.
+        let Some(ttl) = plan.episodic_ttl else {
+            continue;
+        };
+        let cutoff = time::OffsetDateTime::now_utc() - ttl;
+        match service
+            .prune_memories(collection, None, "episodic", cutoff, false)
+            .await
+        {
+            Ok(outcome) => tracing::info!(
+                collection = %collection,
+                cutoff = %outcome.cutoff,
+                deleted = outcome.deleted,
+                "Maintenance: episodic TTL eviction finished"
+            ),
+            Err(error) => tracing::warn!(
+                collection = %collection,
+                error = %error,
+                "Maintenance: episodic TTL eviction failed"
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test(start_paused = true)]
+    async fn scheduler_runs_the_job_once_per_interval_until_shutdown() {
+        let runs = Arc::new(AtomicUsize::new(0));
+        let (stop, stopped) = oneshot::channel::<()>();
+        let counter = Arc::clone(&runs);
+        let task = tokio::spawn(run_every(
+            Duration::from_secs(60),
+            async {
+                let _ = stopped.await;
+            },
+            move || {
+                let counter = Arc::clone(&counter);
+                async move {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                }
+            },
+        ));
+
+        tokio::time::sleep(Duration::from_secs(59)).await;
+        assert_eq!(
+            runs.load(Ordering::SeqCst),
+            0,
+            "no pass before the first interval"
+        );
+
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+
+        tokio::time::sleep(Duration::from_secs(120)).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+
+        stop.send(()).expect("scheduler running");
+        task.await.expect("scheduler stops");
+        tokio::time::sleep(Duration::from_secs(600)).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 3, "no pass after shutdown");
+    }
+}
@@ -0,0 +1,148 @@
+//! Short-lived cache of formatted `search` responses.
+//!
+//! Entries expire by TTL: pushes do not invalidate them, so a cached response can miss
+//! memories indexed within the last `SEARCH_CACHE_TTL_MS`. Promoting an embedding provider
+//! clears the cache, since its responses were ranked with the previous provider's vectors.
+
+use std::{
+    collections::HashMap,
+    sync::{Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+use serde_json::Value;
+
+use crate::config::get_config;
+
+/// Maximum number of responses retained at once.
+pub(crate) const SEARCH_CACHE_CAPACITY: usize = 256;
+
+/// TTL-bounded map from normalized search requests to formatted responses.
+pub(crate) struct SearchCache {
+    ttl: Duration,
+    capacity: usize,
+    entries: Mutex<HashMap<String, CachedResponse>>,
+}
+
+struct CachedResponse {
+    stored_at: Instant,
+    payload: Value,
+}
+
+impl SearchCache {
+    /// Create a cache holding at most `capacity` responses for `ttl` each.
+    pub(crate) fn new(ttl: Duration, capacity: usize) -> Self {
+        Self {
+            ttl,
+            capacity,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Build the cache described by `SEARCH_CACHE_TTL_MS`; a zero TTL disables it.
+    pub(crate) fn from_config() -> Self {
+        Self::new(
+            Duration::from_millis(get_config().search_cache_ttl_ms),
+            SEARCH_CACHE_CAPACITY,
+        )
+    }
+
+    fn is_enabled(&self) -> bool {
+        !self.ttl.is_zero() && self.capacity > 0
+    }
+
+    /// Return the cached response for `key` if it has not expired.
+    pub(crate) fn get(&self, key: &str) -> Option<Value> {
+        if !self.is_enabled() {
+            return None;
+        }
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        match entries.get(key) {
+            Some(entry) if entry.stored_at.elapsed() < self.ttl => Some(entry.payload.clone()),
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Drop every cached response.
+    pub(crate) fn clear(&self) {
+        self.entries
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+
+    /// Store `payload` under `key`, evicting expired entries and then the oldest when full.
+    pub(crate) fn insert(&self, key: String, payload: Value) {
+        if !self.is_enabled() {
+            return;
+        }
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        if entries.len() >= self.capacity && !entries.contains_key(&key) {
+            entries.retain(|_, entry| entry.stored_at.elapsed() < self.ttl);
+            if entries.len() >= self.capacity {
+                let oldest = entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.stored_at)
+                    .map(|(key, _)| key.clone());
+                if let Some(oldest) = oldest {
+                    entries.remove(&oldest);
+                }
+            }
+        }
+        entries.insert(
+            key,
+            CachedResponse {
+                stored_at: Instant::now(),
+                payload,
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn zero_ttl_disables_the_cache() {
+        let cache = SearchCache::new(Duration::ZERO, 4);
+        cache.insert("a".into(), json!(1));
+        assert!(cache.get("a").is_none());
+    }
+
+    #[test]
+    fn entries_expire_after_ttl() {
+        let cache = SearchCache::new(Duration::from_millis(20), 4);
+        cache.insert("a".into(), json!(1));
+        assert_eq!(cache.get("a"), Some(json!(1)));
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(cache.get("a").is_none());
+    }
+
+    #[test]
+    fn clear_drops_every_entry() {
+        let cache = SearchCache::new(Duration::from_secs(60), 4);
+        cache.insert("a".into(), json!(1));
+        cache.insert("b".into(), json!(2));
+        cache.clear();
+        assert!(cache.get("a").is_none());
+        assert!(cache.get("b").is_none());
+    }
+
+    #[test]
+    fn full_cache_evicts_the_oldest_entry() {
+        let cache = SearchCache::new(Duration::from_secs(60), 2);
+        cache.insert("a".into(), json!(1));
+        std::thread::sleep(Duration::from_millis(2));
+        cache.insert("b".into(), json!(2));
+        cache.insert("c".into(), json!(3));
+        assert!(cache.get("a").is_none());
+        assert_eq!(cache.get("b"), Some(json!(2)));
+        assert_eq!(cache.get("c"), Some(json!(3)));
+    }
+}
@@ -2,12 +2,19 @@
 
 use crate::{
     config::EmbeddingProvider,
+    mcp::{
+        highlight::{highlight_spans, query_terms},
+        limiter::LimiterSnapshot,
+        schemas::OUTPUT_SCHEMA_VERSION,
+    },
     processing::{QdrantHealthSnapshot, SearchHit, SummarizeOutcome},
+    qdrant::TagCounts,
 };
 use rmcp::model::ResourceContents;
 use schemars::JsonSchema;
 use serde::Serialize;
 use serde_json::{Map, Value, json};
+use std::collections::BTreeMap;
 
 pub(crate) const APPLICATION_JSON: &str = "application/json";
 
@@ -23,7 +30,7 @@ pub(crate) fn memory_types_payload() -> String {
     })
 }
 
-/// Build the health payload summarizing embedding and Qdrant status.
+/// Build the health payload summarizing embedding and Qdrant status and tool-call load.
 pub(crate) fn health_payload(
     provider: EmbeddingProvider,
     model: &str,
@@ -31,10 +38,12 @@ pub(crate) fn health_payload(
     qdrant_url: &str,
     default_collection: &str,
     snapshot: &QdrantHealthSnapshot,
+    tools: LimiterSnapshot,
 ) -> String {
     let mut qdrant = Map::new();
     qdrant.insert("url".into(), Value::String(qdrant_url.to_string()));
     qdrant.insert("reachable".into(), Value::Bool(snapshot.reachable));
+    qdrant.insert("probe".into(), Value::String(snapshot.probe.into()));
     qdrant.insert(
         "defaultCollection".into(),
         Value::String(default_collection.to_string()),
@@ -46,6 +55,18 @@ pub(crate) fn health_payload(
     if let Some(error) = snapshot.error.as_ref() {
         qdrant.insert("error".into(), Value::String(error.clone()));
     }
+    if let Some(version) = snapshot.version.as_ref() {
+        qdrant.insert("version".into(), Value::String(version.clone()));
+    }
+    if let Some(replica) = snapshot.read_replica.as_ref() {
+        let mut read = Map::new();
+        read.insert("url".into(), Value::String(replica.url.clone()));
+        read.insert("reachable".into(), Value::Bool(replica.reachable));
+        if let Some(error) = replica.error.as_ref() {
+            read.insert("error".into(), Value::String(error.clone()));
+        }
+        qdrant.insert("readReplica".into(), Value::Object(read));
+    }
 
     let payload = json!({
         "embedding": {
@@ -54,6 +75,7 @@ pub(crate) fn health_payload(
             "dimension": dimension,
         },
         "qdrant": Value::Object(qdrant),
+        "tools": tools,
     });
 
     serde_json::to_string_pretty(&payload).unwrap_or_else(|_| payload.to_string())
@@ -89,6 +111,9 @@ pub(crate) fn json_resource_contents(uri: &str, text: String) -> ResourceContent
 pub(crate) struct ProjectsSnapshot {
     /// Ordered list of project identifiers.
     pub(crate) projects: Vec<String>,
+    /// `PROJECT_DEFAULT_MEMORY_TYPE` entries, keyed by project identifier.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) default_memory_types: BTreeMap<String, String>,
 }
 
 /// Project tags snapshot returned by the templated resource.
@@ -100,6 +125,140 @@ pub(crate) struct ProjectTagsSnapshot {
     pub(crate) tags: Vec<String>,
 }
 
+/// Project tags snapshot including per-tag occurrence counts.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct ProjectTagCountsSnapshot {
+    /// Project identifier used to scope the tags.
+    pub(crate) project_id: String,
+    /// Memory type the counts were scoped to, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) memory_type: Option<String>,
+    /// Minimum count a tag needed to be listed, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) min_count: Option<usize>,
+    /// Tags ordered by descending popularity.
+    pub(crate) tags: Vec<TagCountSnapshot>,
+    /// Occurrences of tags beyond the tracking cap, when it was reached.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) overflow: Option<TagOverflowSnapshot>,
+}
+
+/// Single tag entry with the number of memories carrying it.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct TagCountSnapshot {
+    /// Tag value.
+    pub(crate) tag: String,
+    /// Number of memories tagged with the value.
+    pub(crate) count: usize,
+    /// `count` broken down by memory type.
+    pub(crate) memory_types: BTreeMap<String, usize>,
+}
+
+/// Tag occurrences folded together once the distinct-tag cap was reached.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct TagOverflowSnapshot {
+    /// Occurrences not attributed to a listed tag.
+    pub(crate) count: usize,
+    /// `count` broken down by memory type.
+    pub(crate) memory_types: BTreeMap<String, usize>,
+}
+
+impl ProjectTagCountsSnapshot {
+    /// Snapshot `counts`, dropping tags seen fewer than `min_count` times.
+    pub(crate) fn new(
+        project_id: String,
+        counts: TagCounts,
+        memory_type: Option<String>,
+        min_count: Option<usize>,
+    ) -> Self {
+        let floor = min_count.unwrap_or(0);
+        Self {
+            project_id,
+            memory_type,
+            min_count,
+            tags: counts
+                .tags
+                .into_iter()
+                .filter(|entry| entry.count >= floor)
+                .map(|entry| TagCountSnapshot {
+                    tag: entry.tag,
+                    count: entry.count,
+                    memory_types: entry.memory_types,
+                })
+                .collect(),
+            overflow: counts.overflow.map(|overflow| TagOverflowSnapshot {
+                count: overflow.count,
+                memory_types: overflow.memory_types,
+            }),
+        }
+    }
+}
+
+/// Characters of memory text kept in a `recent` resource preview.
+pub(crate) const RECENT_PREVIEW_CHARS: usize = 160;
+
+/// Latest memories of a project returned by the `recent` resource template.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct RecentMemoriesSnapshot {
+    /// Project identifier used to scope the memories.
+    pub(crate) project_id: String,
+    /// Maximum number of memories requested.
+    pub(crate) limit: usize,
+    /// Memories ordered by descending timestamp.
+    pub(crate) memories: Vec<RecentMemorySnapshot>,
+}
+
+/// Single recently stored memory.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct RecentMemorySnapshot {
+    /// Point identifier.
+    pub(crate) id: String,
+    /// RFC3339 timestamp of the memory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) timestamp: Option<String>,
+    /// Whitespace-collapsed start of the memory text.
+    pub(crate) preview: String,
+    /// Memory classification.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) memory_type: Option<String>,
+    /// Stored tags.
+    pub(crate) tags: Vec<String>,
+    /// Source URI recorded at ingest.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) source_uri: Option<String>,
+}
+
+impl RecentMemoriesSnapshot {
+    /// Build the snapshot from hits already ordered newest first.
+    pub(crate) fn new(project_id: String, limit: usize, hits: Vec<SearchHit>) -> Self {
+        let memories = hits
+            .into_iter()
+            .map(|hit| RecentMemorySnapshot {
+                id: hit.id,
+                timestamp: hit.timestamp,
+                preview: preview_text(hit.text.as_deref().unwrap_or_default()),
+                memory_type: hit.memory_type,
+                tags: hit.tags.unwrap_or_default(),
+                source_uri: hit.source_uri,
+            })
+            .collect();
+        Self {
+            project_id,
+            limit,
+            memories,
+        }
+    }
+}
+
+/// Collapse whitespace and cut `text` to [`RECENT_PREVIEW_CHARS`], marking truncation with `…`.
+fn preview_text(text: &str) -> String {
+    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    match collapsed.char_indices().nth(RECENT_PREVIEW_CHARS) {
+        Some((cut, _)) => format!("{}…", collapsed[..cut].trim_end()),
+        None => collapsed,
+    }
+}
+
 /// Top-level settings snapshot describing search defaults.
 #[derive(Debug, Serialize, JsonSchema)]
 pub(crate) struct SettingsSnapshot {
@@ -118,21 +277,104 @@ pub(crate) struct SearchSettingsSnapshot {
     pub(crate) default_score_threshold: f32,
 }
 
+/// Search inputs each `explain_hits` explanation is assembled against.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ExplainInputs {
+    /// Effective score threshold of the search.
+    pub(crate) score_threshold: f32,
+    /// `project_id` filter, if any.
+    pub(crate) project_id: Option<String>,
+    /// `memory_type` filter, if any.
+    pub(crate) memory_type: Option<String>,
+    /// Tags filter, if any.
+    pub(crate) tags: Option<Vec<String>>,
+}
+
+/// Why a hit ranked where it did, returned as `explanation` when `explain_hits` is set.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct HitExplanation {
+    /// Similarity score reported by Qdrant.
+    pub(crate) raw_score: f32,
+    /// Score threshold the hit cleared.
+    pub(crate) threshold: f32,
+    /// 1-based position in the returned results.
+    pub(crate) rank: usize,
+    /// Filters the hit satisfied.
+    pub(crate) filter_matches: FilterMatches,
+}
+
+/// Payload values that satisfied each active filter; inactive filters are omitted.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub(crate) struct FilterMatches {
+    /// Stored `project_id`, when the search filtered by project.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) project: Option<String>,
+    /// Stored `memory_type`, when the search filtered by type.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub(crate) memory_type: Option<String>,
+    /// Requested tags present on the hit, in request order.
+    pub(crate) tags_matched: Vec<String>,
+}
+
+impl HitExplanation {
+    fn new(hit: &SearchHit, rank: usize, inputs: &ExplainInputs) -> Self {
+        let hit_tags = hit.tags.as_deref().unwrap_or_default();
+        Self {
+            raw_score: hit.score,
+            threshold: inputs.score_threshold,
+            rank,
+            filter_matches: FilterMatches {
+                project: inputs.project_id.as_ref().and(hit.project_id.clone()),
+                memory_type: inputs.memory_type.as_ref().and(hit.memory_type.clone()),
+                tags_matched: inputs
+                    .tags
+                    .iter()
+                    .flatten()
+                    .filter(|tag| hit_tags.contains(tag))
+                    .cloned()
+                    .collect(),
+            },
+        }
+    }
+}
+
 /// Format search hits into MCP response payloads and a prompt-ready context string.
-pub(crate) fn format_search_hits(hits: Vec<SearchHit>) -> (Vec<Value>, Option<String>) {
+///
+/// With `highlight_query`, each hit with text gains `highlights: [{start, end}]` character
+/// offsets of the query terms found in that text. With `explain`, each hit gains an
+/// `explanation` describing its score, rank, and matched filters. With `provenance`, each hit
+/// gains `provenance: {collection?, created_at?, embedding_model?}`, naming the collection that
+/// stores it.
+pub(crate) fn format_search_hits(
+    hits: Vec<SearchHit>,
+    highlight_query: Option<&str>,
+    explain: Option<&ExplainInputs>,
+    provenance: bool,
+) -> (Vec<Value>, Option<String>) {
+    let highlight_terms = highlight_query.map(query_terms);
     let mut results = Vec::with_capacity(hits.len());
     let mut context_segments = Vec::new();
 
-    for hit in hits {
+    for (index, hit) in hits.into_iter().enumerate() {
         let mut item = Map::new();
+        if let Some(inputs) = explain {
+            item.insert(
+                "explanation".into(),
+                json!(HitExplanation::new(&hit, index + 1, inputs)),
+            );
+        }
         let id = hit.id;
         item.insert("id".into(), Value::String(id.clone()));
         item.insert("score".into(), json!(hit.score));
 
         if let Some(text) = hit.text {
-            let trimmed = text.trim();
-            if !trimmed.is_empty() {
-                context_segments.push(format!("{trimmed} [{id}]"));
+            context_segments.extend(context_segment(&id, &text));
+            if let Some(terms) = highlight_terms.as_deref() {
+                let spans = highlight_spans(&text, terms)
+                    .into_iter()
+                    .map(|span| json!({ "start": span.start, "end": span.end }))
+                    .collect();
+                item.insert("highlights".into(), Value::Array(spans));
             }
             item.insert("text".into(), Value::String(text));
         }
@@ -151,6 +393,31 @@ pub(crate) fn format_search_hits(hits: Vec<SearchHit>) -> (Vec<Value>, Option<St
         if let Some(source_uri) = hit.source_uri {
             item.insert("source_uri".into(), Value::String(source_uri));
         }
+        if let Some(doc_id) = hit.doc_id {
+            item.insert("doc_id".into(), Value::String(doc_id));
+        }
+        if let Some(chunk_index) = hit.chunk_index {
+            item.insert("chunk_index".into(), json!(chunk_index));
+        }
+        if let Some(citations) = hit.citations {
+            item.insert("citations".into(), json!(citations));
+        }
+        if let Some(links) = hit.links {
+            item.insert("links".into(), json!(links));
+        }
+        if provenance {
+            let mut origin = Map::new();
+            if let Some(collection) = hit.collection {
+                origin.insert("collection".into(), Value::String(collection));
+            }
+            if let Some(ingested_at) = hit.ingested_at {
+                origin.insert("created_at".into(), Value::String(ingested_at));
+            }
+            if let Some(model) = hit.embedding_model {
+                origin.insert("embedding_model".into(), Value::String(model));
+            }
+            item.insert("provenance".into(), Value::Object(origin));
+        }
 
         results.push(Value::Object(item));
     }
@@ -164,9 +431,77 @@ pub(crate) fn format_search_hits(hits: Vec<SearchHit>) -> (Vec<Value>, Option<St
     (results, context)
 }
 
+/// Context line for a hit: its trimmed text followed by an `[id]` citation.
+fn context_segment(id: &str, text: &str) -> Option<String> {
+    let trimmed = text.trim();
+    (!trimmed.is_empty()).then(|| format!("{trimmed} [{id}]"))
+}
+
+/// Prompt-ready context packed into a token budget by [`pack_context`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PackedContext {
+    /// Joined context lines that fit the budget, if any did.
+    pub(crate) context: Option<String>,
+    /// Tokens `context` occupies under the counter it was packed with.
+    pub(crate) tokens_used: usize,
+    /// Whether hits with text were left out because the budget ran out.
+    pub(crate) truncated: bool,
+    /// Ids of the hits whose lines made it into `context`, in context order.
+    pub(crate) cited: Vec<String>,
+}
+
+/// Pack hit context lines, in result order, into at most `max_tokens` tokens.
+///
+/// Each candidate is measured by counting the whole joined context, so the total is exact for
+/// the chosen encoding. Packing stops at the first line that would overflow the budget; later
+/// (lower-ranked) lines are not used to fill the remaining space.
+pub(crate) fn pack_context(
+    hits: &[SearchHit],
+    max_tokens: usize,
+    count_tokens: &dyn Fn(&str) -> usize,
+) -> PackedContext {
+    let mut packed = PackedContext {
+        context: None,
+        tokens_used: 0,
+        truncated: false,
+        cited: Vec::new(),
+    };
+    let segments = hits.iter().filter_map(|hit| {
+        context_segment(&hit.id, hit.text.as_deref()?).map(|segment| (&hit.id, segment))
+    });
+    for (id, segment) in segments {
+        let candidate = match packed.context.as_deref() {
+            Some(context) => format!("{context}\n{segment}"),
+            None => segment,
+        };
+        let tokens = count_tokens(&candidate);
+        if tokens > max_tokens {
+            packed.truncated = true;
+            break;
+        }
+        packed.context = Some(candidate);
+        packed.tokens_used = tokens;
+        packed.cited.push(id.clone());
+    }
+    packed
+}
+
+/// Pack the `answer_context` of a search: like [`pack_context`], but always in descending score
+/// order so the most relevant memories are cited first whatever `sort` was requested.
+pub(crate) fn pack_answer_context(
+    hits: &[SearchHit],
+    max_tokens: usize,
+    count_tokens: &dyn Fn(&str) -> usize,
+) -> PackedContext {
+    let mut ranked = hits.to_vec();
+    ranked.sort_by(|a, b| b.score.total_cmp(&a.score));
+    pack_context(&ranked, max_tokens, count_tokens)
+}
+
 /// Assemble the full structured search response.
 pub(crate) fn build_search_response(
     collection_name: String,
+    resolved_collection: String,
     limit: usize,
     score_threshold: f32,
     results: Vec<Value>,
@@ -174,8 +509,13 @@ pub(crate) fn build_search_response(
     used_filters: Map<String, Value>,
 ) -> Value {
     let mut payload = Map::new();
+    payload.insert("schema_version".into(), json!(OUTPUT_SCHEMA_VERSION));
     payload.insert("results".into(), Value::Array(results));
     payload.insert("collection".into(), Value::String(collection_name));
+    payload.insert(
+        "resolved_collection".into(),
+        Value::String(resolved_collection),
+    );
     payload.insert("limit".into(), Value::from(limit as u64));
     payload.insert("score_threshold".into(), json!(score_threshold));
     payload.insert("scoreThreshold".into(), json!(score_threshold));
@@ -193,6 +533,7 @@ pub(crate) fn build_summarize_response(
     used_filters: Map<String, Value>,
 ) -> Value {
     let mut payload = Map::new();
+    payload.insert("schema_version".into(), json!(OUTPUT_SCHEMA_VERSION));
     payload.insert("summary".into(), Value::String(outcome.summary));
     payload.insert(
         "source_memory_ids".into(),
@@ -204,6 +545,7 @@ pub(crate) fn build_summarize_response(
                 .collect(),
         ),
     );
+    payload.insert("citations".into(), json!(outcome.citations));
     payload.insert(
         "upserted_memory_id".into(),
         Value::String(outcome.upserted_memory_id),
@@ -215,6 +557,21 @@ pub(crate) fn build_summarize_response(
     if let Some(model) = outcome.model {
         payload.insert("model".into(), Value::String(model));
     }
+    if let Some(mode) = outcome.consolidated {
+        payload.insert("consolidated".into(), Value::String(mode.as_str().into()));
+    }
+    payload.insert(
+        "abstractive_attempts".into(),
+        json!(outcome.abstractive_attempts),
+    );
+    payload.insert("items_summarized".into(), json!(outcome.items_summarized));
+    payload.insert("items_trimmed".into(), json!(outcome.items_trimmed));
+    if let Some(label) = outcome.label {
+        payload.insert("label".into(), Value::String(label));
+    }
+    if let Some(topic) = outcome.topic {
+        payload.insert("topic".into(), Value::String(topic));
+    }
     payload.insert("used_filters".into(), Value::Object(used_filters));
 
     Value::Object(payload)
@@ -223,35 +580,10 @@ pub(crate) fn build_summarize_response(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::{CONFIG, Config, EmbeddingProvider, SummarizationProvider};
-    use crate::processing::QdrantHealthSnapshot;
+    use crate::config::{EmbeddingProvider, ensure_test_config};
+    use crate::processing::{QdrantHealthSnapshot, ReadReplicaHealth};
+    use crate::qdrant::{TagCount, TagOverflow};
     use serde_json::Value;
-    use std::sync::Once;
-
-    fn ensure_test_config() {
-        static INIT: Once = Once::new();
-        INIT.call_once(|| {
-            let _ = CONFIG.set(Config {
-                qdrant_url: "http://127.0.0.1:6333".into(),
-                qdrant_collection_name: "rusty-mem".into(),
-                qdrant_api_key: None,
-                embedding_provider: EmbeddingProvider::Ollama,
-                text_splitter_chunk_size: None,
-                text_splitter_chunk_overlap: None,
-                text_splitter_use_safe_defaults: false,
-                embedding_model: "test-model".into(),
-                embedding_dimension: 768,
-                ollama_url: None,
-                server_port: None,
-                search_default_limit: 5,
-                search_max_limit: 50,
-                search_default_score_threshold: 0.25,
-                summarization_provider: SummarizationProvider::Ollama,
-                summarization_model: Some("llama".into()),
-                summarization_max_words: 200,
-            });
-        });
-    }
 
     #[test]
     fn memory_types_payload_is_valid_json() {
@@ -265,6 +597,39 @@ mod tests {
         assert_eq!(memory_types.len(), 3);
     }
 
+    #[test]
+    fn tag_counts_snapshot_applies_min_count_and_reports_overflow() {
+        let entry = |tag: &str, count: usize| TagCount {
+            tag: tag.into(),
+            count,
+            memory_types: [("semantic".to_string(), count)].into(),
+        };
+        let counts = TagCounts {
+            tags: vec![entry("deploy", 4), entry("ops", 2), entry("misc", 1)],
+            overflow: Some(TagOverflow {
+                count: 7,
+                memory_types: [("episodic".to_string(), 7)].into(),
+            }),
+        };
+
+        let snapshot =
+            ProjectTagCountsSnapshot::new("alpha".into(), counts, Some("semantic".into()), Some(2));
+        let value = serde_json::to_value(&snapshot).expect("snapshot json");
+        assert_eq!(
+            value,
+            json!({
+                "project_id": "alpha",
+                "memory_type": "semantic",
+                "min_count": 2,
+                "tags": [
+                    { "tag": "deploy", "count": 4, "memory_types": { "semantic": 4 } },
+                    { "tag": "ops", "count": 2, "memory_types": { "semantic": 2 } }
+                ],
+                "overflow": { "count": 7, "memory_types": { "episodic": 7 } }
+            })
+        );
+    }
+
     #[test]
     fn health_payload_captures_qdrant_status() {
         ensure_test_config();
@@ -272,6 +637,13 @@ mod tests {
             reachable: false,
             default_collection_present: false,
             error: Some("connection refused".into()),
+            version: None,
+            read_replica: Some(ReadReplicaHealth {
+                url: "http://replica:6333/".into(),
+                reachable: true,
+                error: None,
+            }),
+            probe: "healthz",
         };
 
         let body = health_payload(
@@ -281,6 +653,12 @@ mod tests {
             "http://127.0.0.1:6333",
             "rusty-mem",
             &snapshot,
+            LimiterSnapshot {
+                in_flight: 3,
+                queued: 1,
+                max_concurrent: 8,
+                max_queued: 16,
+            },
         );
 
         let value: Value = serde_json::from_str(&body).expect("health payload must be valid JSON");
@@ -288,5 +666,48 @@ mod tests {
         assert_eq!(value["embedding"]["dimension"], 768);
         assert_eq!(value["qdrant"]["reachable"], false);
         assert_eq!(value["qdrant"]["error"], "connection refused");
+        assert_eq!(value["qdrant"]["probe"], "healthz");
+        assert_eq!(
+            value["qdrant"]["readReplica"],
+            json!({ "url": "http://replica:6333/", "reachable": true })
+        );
+        assert_eq!(value["tools"]["inFlight"], 3);
+        assert_eq!(value["tools"]["queued"], 1);
+    }
+
+    #[test]
+    fn recent_snapshot_collapses_and_truncates_previews() {
+        let hit = |id: &str, text: &str| SearchHit {
+            id: id.into(),
+            score: 0.0,
+            text: Some(text.into()),
+            project_id: Some("repo-a".into()),
+            memory_type: Some("episodic".into()),
+            tags: None,
+            timestamp: Some("2025-03-01T00:00:00Z".into()),
+            source_uri: None,
+            doc_id: None,
+            chunk_index: None,
+            ingested_at: None,
+            embedding_model: None,
+            citations: None,
+            links: None,
+            collection: None,
+        };
+        let long = "word ".repeat(100);
+
+        let snapshot = RecentMemoriesSnapshot::new(
+            "repo-a".into(),
+            20,
+            vec![hit("a", "  Deployed\n\nthe   fix  "), hit("b", &long)],
+        );
+        let value = serde_json::to_value(&snapshot).expect("serialize");
+
+        assert_eq!(value["memories"][0]["preview"], "Deployed the fix");
+        assert_eq!(value["memories"][0]["tags"], json!([]));
+        assert!(value["memories"][0].get("source_uri").is_none());
+        let preview = value["memories"][1]["preview"].as_str().expect("preview");
+        assert!(preview.ends_with('…'));
+        assert!(preview.chars().count() <= RECENT_PREVIEW_CHARS + 1);
     }
 }
@@ -1,16 +1,34 @@
 //! Handlers for collection discovery and management tools.
 
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
-use crate::{config::get_config, processing::ProcessingService};
+use crate::{
+    config::get_config,
+    mcp::schemas::{
+        OUTPUT_SCHEMA_VERSION, apply_manifest_input_schema, create_collection_input_schema,
+        describe_collection_input_schema, estimate_storage_input_schema,
+        move_to_collection_input_schema, prune_input_schema, rename_collection_input_schema,
+        storage_stats_input_schema, verify_collection_input_schema, warm_collection_input_schema,
+    },
+    processing::{
+        AnomalyReport, CollectionManifest, ErrorKind, ManifestAction, ProcessingService,
+        sanitize::{resolve_older_than, sanitize_memory_type, sanitize_string, sanitize_tags},
+        types::validate_memory_type,
+    },
+    qdrant::{CollectionInfo, CollectionTopology, SearchFilterArgs},
+};
 use rmcp::{
     ErrorData as McpError,
     model::{CallToolResult, JsonObject},
 };
 use serde::Deserialize;
-use serde_json::json;
+use serde_json::{Value, json};
+use time::OffsetDateTime;
 
-use super::parse_arguments;
+use super::{
+    check_collection_name, classified_error, map_processing_error, parse_arguments,
+    resolve_collection,
+};
 
 /// Request payload for the `new-collection` tool.
 #[derive(Debug, Deserialize)]
@@ -20,6 +38,41 @@ pub(crate) struct CreateCollectionRequest {
     /// Optional vector dimension override.
     #[serde(default)]
     pub(crate) vector_size: Option<u64>,
+    /// Optional shard count override.
+    #[serde(default)]
+    pub(crate) shard_number: Option<u32>,
+    /// Optional replication factor override.
+    #[serde(default)]
+    pub(crate) replication_factor: Option<u32>,
+}
+
+/// Request payload for the `apply-manifest` tool.
+#[derive(Debug, Deserialize)]
+pub(crate) struct ApplyManifestRequest {
+    /// Inline TOML manifest; defaults to the `COLLECTION_MANIFEST` file.
+    #[serde(default)]
+    pub(crate) manifest: Option<String>,
+}
+
+/// Default time budget for `warm-collection` to wait on indexing.
+pub(crate) const WARM_DEFAULT_TIMEOUT_MS: u64 = 30_000;
+/// Upper bound accepted for `timeout_ms`.
+pub(crate) const WARM_MAX_TIMEOUT_MS: u64 = 300_000;
+/// Upper bound accepted for `warmup_queries`.
+pub(crate) const WARM_MAX_QUERIES: usize = 10;
+
+/// Request payload for the `warm-collection` tool.
+#[derive(Debug, Deserialize)]
+pub(crate) struct WarmCollectionRequest {
+    /// Optional Qdrant collection override.
+    #[serde(default)]
+    pub(crate) collection: Option<String>,
+    /// Maximum time to wait for indexing, in milliseconds.
+    #[serde(default)]
+    pub(crate) timeout_ms: Option<u64>,
+    /// Number of throwaway searches to run once indexing settles.
+    #[serde(default)]
+    pub(crate) warmup_queries: Option<usize>,
 }
 
 /// Handle the `get-collections` tool, returning known Qdrant collections.
@@ -29,9 +82,9 @@ pub(crate) async fn handle_list_collections(
     let collections = processing
         .list_collections()
         .await
-        .map_err(|err| McpError::internal_error(err.to_string(), None))?;
+        .map_err(map_processing_error)?;
     Ok(CallToolResult::structured(
-        json!({ "collections": collections }),
+        json!({ "schema_version": OUTPUT_SCHEMA_VERSION, "collections": collections }),
     ))
 }
 
@@ -40,23 +93,556 @@ pub(crate) async fn handle_create_collection(
     processing: &Arc<ProcessingService>,
     arguments: Option<JsonObject>,
 ) -> Result<CallToolResult, McpError> {
-    let args: CreateCollectionRequest = parse_arguments(arguments)?;
+    let args: CreateCollectionRequest =
+        parse_arguments(arguments, &create_collection_input_schema())?;
     if args.name.trim().is_empty() {
         return Err(McpError::invalid_params("`name` must not be empty", None));
     }
+    check_collection_name("name", &args.name)?;
+
+    for (key, value) in [
+        ("shard_number", args.shard_number),
+        ("replication_factor", args.replication_factor),
+    ] {
+        if value == Some(0) {
+            return Err(McpError::invalid_params(
+                format!("`{key}` must be at least 1"),
+                None,
+            ));
+        }
+    }
 
     let target_size = args.vector_size.unwrap_or_else(|| {
         let cfg = get_config();
         cfg.embedding_dimension as u64
     });
+    let topology = CollectionTopology {
+        shard_number: args.shard_number,
+        replication_factor: args.replication_factor,
+    }
+    .or(CollectionTopology::from_config());
 
     processing
-        .create_collection(&args.name, Some(target_size))
+        .create_collection(&args.name, Some(target_size), topology)
         .await
-        .map_err(|err| McpError::internal_error(err.to_string(), None))?;
+        .map_err(map_processing_error)?;
 
-    Ok(CallToolResult::structured(json!({
+    let mut body = json!({
         "status": "ok",
         "vectorSize": target_size,
+    });
+    if let Some(shard_number) = topology.shard_number {
+        body["shardNumber"] = json!(shard_number);
+    }
+    if let Some(replication_factor) = topology.replication_factor {
+        body["replicationFactor"] = json!(replication_factor);
+    }
+    Ok(CallToolResult::structured(body))
+}
+
+/// Handle the `apply-manifest` tool, reporting what happened to each declared collection.
+pub(crate) async fn handle_apply_manifest(
+    processing: &Arc<ProcessingService>,
+    arguments: Option<JsonObject>,
+) -> Result<CallToolResult, McpError> {
+    let args: ApplyManifestRequest = parse_arguments(arguments, &apply_manifest_input_schema())?;
+    let manifest = match args.manifest {
+        Some(text) => CollectionManifest::parse(&text)
+            .map_err(|error| classified_error(ErrorKind::InvalidRequest, error.to_string()))?,
+        None => {
+            let path = get_config().collection_manifest.as_deref().ok_or_else(|| {
+                McpError::invalid_params(
+                    "Pass `manifest` or set COLLECTION_MANIFEST on the server",
+                    None,
+                )
+            })?;
+            CollectionManifest::load(path)
+                .map_err(|error| classified_error(ErrorKind::Configuration, error.to_string()))?
+        }
+    };
+
+    let results = processing
+        .apply_manifest(&manifest)
+        .await
+        .map_err(map_processing_error)?;
+
+    let mut counts = json!({ "created": 0, "updated": 0, "unchanged": 0, "conflict": 0 });
+    let collections: Vec<Value> = results
+        .iter()
+        .map(|result| {
+            let label = result.action.label();
+            counts[label] = json!(counts[label].as_u64().unwrap_or(0) + 1);
+            let mut entry = json!({ "collection": result.collection, "action": label });
+            match &result.action {
+                ManifestAction::Updated { indexes_added } => {
+                    entry["indexesAdded"] = json!(indexes_added);
+                }
+                ManifestAction::Conflict { reasons } => entry["reasons"] = json!(reasons),
+                ManifestAction::Created | ManifestAction::Unchanged => {}
+            }
+            entry
+        })
+        .collect();
+
+    Ok(CallToolResult::structured(json!({
+        "status": "ok",
+        "collections": collections,
+        "counts": counts,
     })))
 }
+
+/// Handle the `warm-collection` tool by waiting for indexing and optionally warming caches.
+pub(crate) async fn handle_warm_collection(
+    processing: &Arc<ProcessingService>,
+    arguments: Option<JsonObject>,
+) -> Result<CallToolResult, McpError> {
+    let args: WarmCollectionRequest = parse_arguments(arguments, &warm_collection_input_schema())?;
+    let timeout_ms = args.timeout_ms.unwrap_or(WARM_DEFAULT_TIMEOUT_MS);
+    if timeout_ms > WARM_MAX_TIMEOUT_MS {
+        return Err(McpError::invalid_params(
+            format!("`timeout_ms` must be at most {WARM_MAX_TIMEOUT_MS}"),
+            None,
+        ));
+    }
+    let warmup_queries = args.warmup_queries.unwrap_or(0);
+    if warmup_queries > WARM_MAX_QUERIES {
+        return Err(McpError::invalid_params(
+            format!("`warmup_queries` must be at most {WARM_MAX_QUERIES}"),
+            None,
+        ));
+    }
+    let collection = resolve_collection(args.collection)?;
+
+    let outcome = processing
+        .warm_collection(
+            &collection,
+            Duration::from_millis(timeout_ms),
+            warmup_queries,
+        )
+        .await
+        .map_err(map_processing_error)?;
+
+    Ok(CallToolResult::structured(json!({
+        "collection": collection,
+        "ready": outcome.after.is_ready(),
+        "timedOut": !outcome.after.is_ready(),
+        "before": collection_status(&outcome.before),
+        "after": collection_status(&outcome.after),
+        "warmupQueries": outcome.warmup_queries,
+        "elapsedMs": outcome.elapsed.as_millis() as u64,
+    })))
+}
+
+/// Render collection indexing counters for tool responses.
+pub(crate) fn collection_status(info: &CollectionInfo) -> Value {
+    json!({
+        "status": info.status,
+        "pointsCount": info.points_count,
+        "indexedVectorsCount": info.indexed_vectors_count,
+        "unindexedBacklog": info.unindexed_backlog(),
+    })
+}
+
+/// Request payload for the `describe-collection` tool.
+#[derive(Debug, Deserialize)]
+pub(crate) struct DescribeCollectionRequest {
+    /// Optional Qdrant collection override.
+    #[serde(default)]
+    pub(crate) collection: Option<String>,
+}
+
+/// Handle the `describe-collection` tool, reporting vectors, indexes, and sampled payload keys.
+pub(crate) async fn handle_describe_collection(
+    processing: &Arc<ProcessingService>,
+    arguments: Option<JsonObject>,
+) -> Result<CallToolResult, McpError> {
+    let args: DescribeCollectionRequest =
+        parse_arguments(arguments, &describe_collection_input_schema())?;
+    let collection = resolve_collection(args.collection)?;
+
+    let profile = processing
+        .describe_collection(&collection)
+        .await
+        .map_err(map_processing_error)?;
+
+    let indexes: Vec<Value> = profile
+        .indexes
+        .iter()
+        .map(|(field, kind)| json!({ "field": field, "type": kind }))
+        .collect();
+    let payload_keys: Vec<Value> = profile
+        .payload_keys
+        .iter()
+        .map(|(key, occurrences)| {
+            json!({
+                "key": key,
+                "indexed": profile.indexes.contains_key(key),
+                "occurrences": occurrences,
+            })
+        })
+        .collect();
+
+    Ok(CallToolResult::structured(json!({
+        "collection": collection,
+        "vectorSize": profile.vector_size,
+        "distance": profile.distance,
+        "collectionStatus": collection_status(&profile.info),
+        "indexes": indexes,
+        "sampledPoints": profile.sampled_points,
+        "payloadKeys": payload_keys,
+        "dedupeStats": {
+            "sampledPoints": profile.dedupe_stats.sampled_points,
+            "distinctHashes": profile.dedupe_stats.distinct_hashes,
+            "duplicatePoints": profile.dedupe_stats.duplicate_points,
+            "duplicateRatio": profile.dedupe_stats.duplicate_ratio,
+        },
+    })))
+}
+
+/// Request payload for the `estimate-storage` tool.
+#[derive(Debug, Deserialize)]
+pub(crate) struct EstimateStorageRequest {
+    /// Optional Qdrant collection override.
+    #[serde(default)]
+    pub(crate) collection: Option<String>,
+}
+
+/// Handle the `estimate-storage` tool, extrapolating bytes from a payload sample.
+pub(crate) async fn handle_estimate_storage(
+    processing: &Arc<ProcessingService>,
+    arguments: Option<JsonObject>,
+) -> Result<CallToolResult, McpError> {
+    let args: EstimateStorageRequest =
+        parse_arguments(arguments, &estimate_storage_input_schema())?;
+    let collection = resolve_collection(args.collection)?;
+
+    let estimate = processing
+        .estimate_storage(&collection)
+        .await
+        .map_err(map_processing_error)?;
+
+    Ok(CallToolResult::structured(json!({
+        "collection": collection,
+        "approximate": true,
+        "pointsCount": estimate.points_count,
+        "vectorSize": estimate.vector_size,
+        "vectorDatatype": estimate.vector_datatype,
+        "vectorBytes": estimate.vector_bytes,
+        "sampledPoints": estimate.sampled_points,
+        "averagePayloadBytes": estimate.average_payload_bytes,
+        "estimatedBytes": estimate.estimated_bytes,
+        "note": "Raw vectors plus payloads only; HNSW graphs, payload indexes, and segment overhead add to this.",
+    })))
+}
+
+/// Request payload for the `storage-stats` tool.
+#[derive(Debug, Deserialize)]
+pub(crate) struct StorageStatsRequest {
+    /// Collection to report on; every collection when omitted.
+    #[serde(default)]
+    pub(crate) collection: Option<String>,
+    /// Restrict the counts and payload sample to one project.
+    #[serde(default)]
+    pub(crate) project_id: Option<String>,
+}
+
+/// Handle the `storage-stats` tool, reporting approximate disk usage per collection.
+pub(crate) async fn handle_storage_stats(
+    processing: &Arc<ProcessingService>,
+    arguments: Option<JsonObject>,
+) -> Result<CallToolResult, McpError> {
+    let args: StorageStatsRequest = parse_arguments(arguments, &storage_stats_input_schema())?;
+    let collection = sanitize_string(args.collection);
+    if let Some(name) = &collection {
+        check_collection_name("collection", name)?;
+    }
+    let project_id = sanitize_string(args.project_id);
+
+    let report = processing
+        .storage_stats(collection.as_deref(), project_id.as_deref())
+        .await
+        .map_err(map_processing_error)?;
+
+    Ok(CallToolResult::structured(json!(report)))
+}
+
+/// Request payload for the `move-to-collection` tool.
+#[derive(Debug, Deserialize)]
+pub(crate) struct MoveToCollectionRequest {
+    /// Source collection override.
+    #[serde(default)]
+    pub(crate) collection: Option<String>,
+    /// Collection receiving the memories.
+    pub(crate) target_collection: String,
+    /// Restrict the move to one project.
+    #[serde(default)]
+    pub(crate) project_id: Option<String>,
+    /// Restrict the move to one memory type.
+    #[serde(default)]
+    pub(crate) memory_type: Option<String>,
+    /// Restrict the move to memories carrying any of these tags.
+    #[serde(default)]
+    pub(crate) tags: Option<Vec<String>>,
+}
+
+/// Handle the `move-to-collection` tool, relocating matching memories with their ids intact.
+pub(crate) async fn handle_move_to_collection(
+    processing: &Arc<ProcessingService>,
+    arguments: Option<JsonObject>,
+) -> Result<CallToolResult, McpError> {
+    let args: MoveToCollectionRequest =
+        parse_arguments(arguments, &move_to_collection_input_schema())?;
+    let source = resolve_collection(args.collection)?;
+    check_collection_name("target_collection", &args.target_collection)?;
+    let filter = SearchFilterArgs {
+        project_id: sanitize_string(args.project_id),
+        memory_type: sanitize_memory_type(args.memory_type),
+        tags: sanitize_tags(args.tags),
+        ..Default::default()
+    };
+
+    let moved = processing
+        .move_to_collection(&source, &args.target_collection, filter)
+        .await
+        .map_err(|error| classified_error(error.kind(), error.to_string()))?;
+
+    Ok(CallToolResult::structured(json!({
+        "status": "ok",
+        "collection": source,
+        "targetCollection": args.target_collection,
+        "moved": moved,
+    })))
+}
+
+/// Request payload for the `rename-collection` tool.
+#[derive(Debug, Deserialize)]
+pub(crate) struct RenameCollectionRequest {
+    /// Collection to rename; the configured collection when omitted.
+    #[serde(default)]
+    pub(crate) collection: Option<String>,
+    /// Name the collection is copied to.
+    pub(crate) new_name: String,
+    /// Explicit acknowledgement required because the original is deleted.
+    #[serde(default)]
+    pub(crate) confirm: Option<bool>,
+}
+
+/// Handle the `rename-collection` tool, copying a collection under a new name.
+pub(crate) async fn handle_rename_collection(
+    processing: &Arc<ProcessingService>,
+    arguments: Option<JsonObject>,
+) -> Result<CallToolResult, McpError> {
+    let args: RenameCollectionRequest =
+        parse_arguments(arguments, &rename_collection_input_schema())?;
+    if args.confirm != Some(true) {
+        return Err(McpError::invalid_params(
+            "`rename-collection` deletes the original collection once it is copied; pass `confirm: true`",
+            None,
+        ));
+    }
+    let source = resolve_collection(args.collection)?;
+    check_collection_name("new_name", &args.new_name)?;
+
+    let outcome = processing
+        .rename_collection(&source, &args.new_name)
+        .await
+        .map_err(|error| classified_error(error.kind(), error.to_string()))?;
+
+    Ok(CallToolResult::structured(json!({
+        "status": "ok",
+        "collection": source,
+        "newName": args.new_name,
+        "copied": outcome.copied,
+        "batches": outcome.batches,
+        "aliases": outcome.aliases,
+    })))
+}
+
+/// Request payload for the `prune` tool.
+#[derive(Debug, Deserialize)]
+pub(crate) struct PruneRequest {
+    /// Optional Qdrant collection override.
+    #[serde(default)]
+    pub(crate) collection: Option<String>,
+    /// Restrict the prune to one project.
+    #[serde(default)]
+    pub(crate) project_id: Option<String>,
+    /// Memory type to prune; `episodic` when omitted.
+    #[serde(default)]
+    pub(crate) memory_type: Option<String>,
+    /// RFC3339 timestamp, date, or `h`/`d`/`w` duration shorthand.
+    pub(crate) older_than: String,
+    /// Count matching memories without deleting them.
+    #[serde(default)]
+    pub(crate) dry_run: Option<bool>,
+    /// Explicit acknowledgement required to delete.
+    #[serde(default)]
+    pub(crate) confirm: Option<bool>,
+}
+
+/// Handle the `prune` tool, bulk-deleting memories stamped before a cutoff.
+pub(crate) async fn handle_prune(
+    processing: &Arc<ProcessingService>,
+    arguments: Option<JsonObject>,
+) -> Result<CallToolResult, McpError> {
+    let args: PruneRequest = parse_arguments(arguments, &prune_input_schema())?;
+    let dry_run = args.dry_run.unwrap_or(false);
+    if !dry_run && args.confirm != Some(true) {
+        return Err(McpError::invalid_params(
+            "`prune` permanently deletes memories; pass `confirm: true`, or `dry_run: true` to preview the count",
+            None,
+        ));
+    }
+    let memory_type = validate_memory_type(args.memory_type)
+        .map_err(|error| McpError::invalid_params(error.to_string(), None))?
+        .unwrap_or_else(|| "episodic".to_string());
+    let now = OffsetDateTime::now_utc();
+    let Some(cutoff) = resolve_older_than(&args.older_than, now) else {
+        return Err(McpError::invalid_params(
+            "`older_than` must be an RFC3339 timestamp, a YYYY-MM-DD date, or a duration like `90d`, `36h`, or `2w`",
+            None,
+        ));
+    };
+    if cutoff > now {
+        return Err(McpError::invalid_params(
+            "`older_than` resolves to a cutoff in the future, which would match every memory",
+            None,
+        ));
+    }
+    let project_id = sanitize_string(args.project_id);
+    let collection = resolve_collection(args.collection)?;
+
+    let outcome = processing
+        .prune_memories(
+            &collection,
+            project_id.clone(),
+            &memory_type,
+            cutoff,
+            dry_run,
+        )
+        .await
+        .map_err(map_processing_error)?;
+
+    let mut payload = json!({
+        "status": "ok",
+        "collection": collection,
+        "projectId": project_id,
+        "memoryType": memory_type,
+        "cutoff": outcome.cutoff,
+        "dryRun": dry_run,
+    });
+    if dry_run {
+        payload["estimated"] = json!(outcome.matched);
+    } else {
+        payload["deleted"] = json!(outcome.deleted);
+    }
+    Ok(CallToolResult::structured(payload))
+}
+
+/// Request payload for the `verify-collection` tool.
+#[derive(Debug, Deserialize)]
+pub(crate) struct VerifyCollectionRequest {
+    /// Optional Qdrant collection override.
+    #[serde(default)]
+    pub(crate) collection: Option<String>,
+    /// Share of points to check, in `(0, 1]`.
+    #[serde(default)]
+    pub(crate) sample_rate: Option<f64>,
+    /// Rewrite wrong or missing `chunk_hash` values.
+    #[serde(default)]
+    pub(crate) fix_hashes: Option<bool>,
+    /// Write `timestamp_unix` from `timestamp` where it is missing or stale.
+    #[serde(default)]
+    pub(crate) backfill_timestamps: Option<bool>,
+}
+
+/// Handle the `verify-collection` tool, reporting payload anomalies and optionally fixing hashes
+/// or backfilling `timestamp_unix`.
+pub(crate) async fn handle_verify_collection(
+    processing: &Arc<ProcessingService>,
+    arguments: Option<JsonObject>,
+) -> Result<CallToolResult, McpError> {
+    let args: VerifyCollectionRequest =
+        parse_arguments(arguments, &verify_collection_input_schema())?;
+    let sample_rate = args.sample_rate.unwrap_or(1.0);
+    if !(sample_rate > 0.0 && sample_rate <= 1.0) {
+        return Err(McpError::invalid_params(
+            "`sample_rate` must be greater than 0 and at most 1",
+            None,
+        ));
+    }
+    let collection = resolve_collection(args.collection)?;
+
+    let outcome = processing
+        .verify_collection(
+            &collection,
+            sample_rate,
+            args.fix_hashes.unwrap_or(false),
+            args.backfill_timestamps.unwrap_or(false),
+        )
+        .await
+        .map_err(map_processing_error)?;
+
+    Ok(CallToolResult::structured(json!({
+        "status": "ok",
+        "collection": collection,
+        "scanned": outcome.scanned,
+        "checked": outcome.checked,
+        "anomalies": {
+            "hashMismatch": anomaly_report(&outcome.hash_mismatch),
+            "missingFields": anomaly_report(&outcome.missing_fields),
+            "invalidTimestamp": anomaly_report(&outcome.invalid_timestamp),
+        },
+        "hashesFixed": outcome.hashes_fixed,
+        "timestampsBackfilled": outcome.timestamps_backfilled,
+    })))
+}
+
+fn anomaly_report(report: &AnomalyReport) -> Value {
+    json!({ "count": report.count, "examples": report.examples })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embedding::AiLibClient;
+    use crate::mcp::schemas::{assert_matches_output_schema, collections_output_schema};
+    use crate::qdrant::QdrantService;
+    use httpmock::{Method::GET, MockServer};
+    use std::collections::BTreeMap;
+
+    #[tokio::test]
+    async fn get_collections_output_matches_declared_schema() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path("/collections");
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": { "collections": [{ "name": "docs" }, { "name": "notes" }] }
+                }));
+            })
+            .await;
+        let processing = Arc::new(ProcessingService::from_parts(
+            Box::new(AiLibClient::new()),
+            QdrantService {
+                client: reqwest::Client::new(),
+                base_url: server.base_url(),
+                read_base_url: None,
+                api_key: None,
+                api_keys: BTreeMap::new(),
+                capabilities: Default::default(),
+                #[cfg(feature = "fault-injection")]
+                faults: None,
+            },
+        ));
+
+        let result = handle_list_collections(&processing)
+            .await
+            .expect("collections");
+
+        let payload = result.structured_content.expect("structured");
+        assert_eq!(payload["collections"], json!(["docs", "notes"]));
+        assert_matches_output_schema(collections_output_schema(), &payload);
+    }
+}
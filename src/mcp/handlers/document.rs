@@ -0,0 +1,94 @@
+//! Handler for the get-document tool.
+
+use std::sync::Arc;
+
+use crate::{
+    mcp::schemas::get_document_input_schema,
+    processing::{DocumentSelector, ErrorKind, ProcessingService, sanitize::sanitize_string},
+};
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, JsonObject},
+};
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use super::{classified_error, map_processing_error, parse_arguments, resolve_collection};
+
+/// Request payload for the `get-document` tool.
+#[derive(Debug, Deserialize)]
+pub(crate) struct GetDocumentRequest {
+    /// Optional Qdrant collection override.
+    #[serde(default)]
+    pub(crate) collection: Option<String>,
+    /// Identifier returned by `push` as `docId`.
+    #[serde(default)]
+    pub(crate) doc_id: Option<String>,
+    /// Source URI whose most recent ingestion should be rebuilt.
+    #[serde(default)]
+    pub(crate) source_uri: Option<String>,
+    /// Restrict a `source_uri` lookup to one project.
+    #[serde(default)]
+    pub(crate) project_id: Option<String>,
+}
+
+/// Handle the `get-document` tool, stitching a pushed document back together from its chunks.
+pub(crate) async fn handle_get_document(
+    processing: &Arc<ProcessingService>,
+    arguments: Option<JsonObject>,
+) -> Result<CallToolResult, McpError> {
+    let args: GetDocumentRequest = parse_arguments(arguments, &get_document_input_schema())?;
+    let collection = resolve_collection(args.collection)?;
+    let selector = match (
+        sanitize_string(args.doc_id),
+        sanitize_string(args.source_uri),
+    ) {
+        (Some(doc_id), None) => DocumentSelector::DocId(doc_id),
+        (None, Some(source_uri)) => DocumentSelector::LatestForSource {
+            source_uri,
+            project_id: sanitize_string(args.project_id),
+        },
+        _ => {
+            return Err(classified_error(
+                ErrorKind::InvalidRequest,
+                "Provide exactly one of 'doc_id' or 'source_uri'".into(),
+            ));
+        }
+    };
+
+    let document = processing
+        .get_document(&collection, selector)
+        .await
+        .map_err(map_processing_error)?
+        .ok_or_else(|| {
+            classified_error(
+                ErrorKind::InvalidRequest,
+                format!(
+                    "No tracked document matched in '{collection}'; documents pushed before chunk ordering was recorded must be re-pushed"
+                ),
+            )
+        })?;
+
+    let chunks: Vec<Value> = document
+        .chunks
+        .iter()
+        .map(|chunk| {
+            json!({
+                "id": chunk.id,
+                "chunkIndex": chunk.chunk_index,
+                "overlapStripped": chunk.overlap_stripped,
+            })
+        })
+        .collect();
+    Ok(CallToolResult::structured(json!({
+        "collection": collection,
+        "docId": document.doc_id,
+        "sourceUri": document.source_uri,
+        "projectId": document.project_id,
+        "ingestedAt": document.ingested_at,
+        "text": document.text,
+        "chunkCount": chunks.len(),
+        "chunks": chunks,
+        "missingChunkIndexes": document.missing_chunk_indexes,
+    })))
+}
@@ -1,19 +1,39 @@
 //! MCP handler for document ingestion tools.
 
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 
 use crate::{
-    config::get_config,
-    processing::{IngestMetadata, ProcessingService},
+    config::{SummarizationProvider, get_config},
+    mcp::{
+        cache::SearchCache,
+        schemas::{
+            OUTPUT_SCHEMA_VERSION, compare_providers_input_schema, index_input_schema,
+            promote_provider_input_schema, reembed_fallback_input_schema,
+        },
+    },
+    processing::{
+        AutoClassify, IngestMetadata, ProcessingOutcome, ProcessingService, TranscriptHandling,
+        classify::{MemoryTypeRule, infer_memory_type},
+        migration::{DEFAULT_COMPARE_K, DEFAULT_COMPARE_SAMPLE},
+        sanitize::{sanitize_memory_type, validate_ingest_timestamp},
+    },
+    qdrant::CollectionInfo,
 };
 use rmcp::{
     ErrorData as McpError,
     model::{CallToolResult, JsonObject},
 };
 use serde::Deserialize;
-use serde_json::json;
+use serde_json::{Value, json};
+use time::OffsetDateTime;
 
-use super::parse_arguments;
+use super::{
+    check_collection_name, classified_error, map_processing_error, parse_arguments,
+    resolve_collection, resolve_project_collection,
+};
+
+/// Un-indexed points above which `push` suggests running `warm-collection`.
+const BACKLOG_HINT_THRESHOLD: u64 = 10_000;
 
 /// Request payload accepted by the `push` tool.
 #[derive(Debug, Deserialize)]
@@ -35,14 +55,97 @@ pub(crate) struct IndexToolRequest {
     /// Optional URI describing the source document.
     #[serde(default)]
     pub(crate) source_uri: Option<String>,
+    /// Optional per-chunk classification: `true`, `"heuristic"`, or `"llm"`.
+    #[serde(default)]
+    pub(crate) auto_classify: Option<Value>,
+    /// Optional RFC3339 timestamp recorded instead of the ingest time.
+    #[serde(default)]
+    pub(crate) timestamp: Option<String>,
+    /// Accept a `timestamp` further in the future than the allowed clock skew.
+    #[serde(default)]
+    pub(crate) allow_future: Option<bool>,
+}
+
+/// Metadata `push` filled in from the calling client's identity and project defaults.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct InferredMetadata {
+    /// Project taken from `CLIENT_PROJECT_MAP`.
+    pub(crate) project_id: Option<String>,
+    /// Tags derived from the client name.
+    pub(crate) tags: Option<Vec<String>>,
+    /// Memory type taken from `PROJECT_DEFAULT_MEMORY_TYPE`.
+    pub(crate) memory_type: Option<String>,
+}
+
+impl InferredMetadata {
+    fn is_empty(&self) -> bool {
+        self.project_id.is_none() && self.tags.is_none() && self.memory_type.is_none()
+    }
+}
+
+/// Fill omitted `tags` with `client:<name>` and an omitted `project_id` from `project_map`.
+///
+/// Values supplied by the caller always win; the returned record lists only what was inferred.
+pub(crate) fn infer_client_metadata(
+    client_name: Option<&str>,
+    project_map: &BTreeMap<String, String>,
+    project_id: &mut Option<String>,
+    tags: &mut Option<Vec<String>>,
+) -> InferredMetadata {
+    let mut inferred = InferredMetadata::default();
+    let Some(name) = client_name.map(str::trim).filter(|name| !name.is_empty()) else {
+        return inferred;
+    };
+    let name = name.to_lowercase();
+    if tags.is_none() {
+        let tag = format!(
+            "client:{}",
+            name.split_whitespace().collect::<Vec<_>>().join("-")
+        );
+        *tags = Some(vec![tag]);
+        inferred.tags = tags.clone();
+    }
+    if project_id.is_none()
+        && let Some(project) = project_map.get(&name)
+    {
+        *project_id = Some(project.clone());
+        inferred.project_id = Some(project.clone());
+    }
+    inferred
+}
+
+/// Fill an omitted `memory_type` from the project's `PROJECT_DEFAULT_MEMORY_TYPE` entry.
+///
+/// Precedence is: an explicit `memory_type`, then the first matching `MEMORY_TYPE_RULES` entry
+/// (applied later by the service), then the project default, then the global `semantic`.
+/// Returns the project default when it was applied.
+pub(crate) fn apply_project_memory_type(
+    defaults: &BTreeMap<String, String>,
+    rules: &[MemoryTypeRule],
+    project_id: Option<&str>,
+    tags: Option<&[String]>,
+    source_uri: Option<&str>,
+    memory_type: &mut Option<String>,
+) -> Option<String> {
+    if sanitize_memory_type(memory_type.clone()).is_some()
+        || infer_memory_type(rules, tags, source_uri).is_some()
+    {
+        return None;
+    }
+    let default = defaults.get(project_id?.trim())?;
+    *memory_type = Some(default.clone());
+    Some(default.clone())
 }
 
 /// Handle the `push` tool by chunking, embedding, and indexing the supplied text.
+///
+/// `client_name` is the MCP client identity used to default omitted tags and project.
 pub(crate) async fn handle_push(
     processing: &Arc<ProcessingService>,
+    client_name: Option<&str>,
     arguments: Option<JsonObject>,
 ) -> Result<CallToolResult, McpError> {
-    let args: IndexToolRequest = parse_arguments(arguments)?;
+    let args: IndexToolRequest = parse_arguments(arguments, &index_input_schema())?;
     if args.text.trim().is_empty() {
         return Err(McpError::invalid_params("`text` must not be empty", None));
     }
@@ -50,32 +153,541 @@ pub(crate) async fn handle_push(
     let IndexToolRequest {
         text,
         collection,
-        project_id,
-        memory_type,
-        tags,
+        mut project_id,
+        mut memory_type,
+        mut tags,
         source_uri,
+        auto_classify,
+        timestamp,
+        allow_future,
     } = args;
 
-    let collection = collection.unwrap_or_else(|| get_config().qdrant_collection_name.clone());
+    let timestamp = timestamp
+        .map(|value| {
+            validate_ingest_timestamp(
+                &value,
+                allow_future.unwrap_or(false),
+                OffsetDateTime::now_utc(),
+            )
+            .map_err(|message| McpError::invalid_params(message, None))
+        })
+        .transpose()?;
+    let config = get_config();
+    let mut inferred = infer_client_metadata(
+        client_name,
+        &config.client_project_map,
+        &mut project_id,
+        &mut tags,
+    );
+    if config.require_project_filter && project_id.is_none() {
+        tracing::warn!(
+            client = client_name.unwrap_or("unknown"),
+            "push without project_id stored under the `default` project while REQUIRE_PROJECT_FILTER is enabled"
+        );
+    }
+    let pinned = collection.is_some();
+    let mut collection = resolve_project_collection(collection, project_id.as_deref())?;
+    inferred.memory_type = apply_project_memory_type(
+        &config.project_default_memory_type,
+        &config.memory_type_rules,
+        project_id.as_deref(),
+        tags.as_deref(),
+        source_uri.as_deref(),
+        &mut memory_type,
+    );
     let metadata = IngestMetadata {
         project_id,
         memory_type,
         tags,
         source_uri,
+        timestamp,
+        auto_classify: parse_auto_classify(auto_classify)?,
     };
+    if !pinned {
+        collection = processing.ingest_collection(&collection, &metadata);
+        check_collection_name("collection", &collection)?;
+    }
 
     let outcome = processing
         .process_and_index(&collection, text, metadata)
         .await
-        .map_err(|err| McpError::internal_error(err.to_string(), None))?;
+        .map_err(map_processing_error)?;
 
-    Ok(CallToolResult::structured(json!({
+    // Best effort: a failed status probe must not fail an ingestion that already succeeded.
+    let hint = match processing.indexing_status(&collection).await {
+        Ok(info) => indexing_hint(&info),
+        Err(error) => {
+            tracing::debug!(collection = %collection, error = %error, "Skipping indexing hint");
+            None
+        }
+    };
+
+    let mut payload = push_response(&collection, &outcome);
+    if let Some(hint) = hint {
+        payload["hint"] = json!(hint);
+    }
+    if !inferred.is_empty() {
+        let mut fields = serde_json::Map::new();
+        if let Some(project_id) = inferred.project_id {
+            fields.insert("projectId".into(), json!(project_id));
+        }
+        if let Some(tags) = inferred.tags {
+            fields.insert("tags".into(), json!(tags));
+        }
+        if let Some(memory_type) = inferred.memory_type {
+            fields.insert("memoryType".into(), json!(memory_type));
+        }
+        payload["inferred"] = Value::Object(fields);
+    }
+    Ok(CallToolResult::structured(payload))
+}
+
+/// Render the `push` response for a completed ingestion.
+fn push_response(collection: &str, outcome: &ProcessingOutcome) -> Value {
+    let profile = &outcome.ingest_profile;
+    let mut payload = json!({
+        "schemaVersion": OUTPUT_SCHEMA_VERSION,
         "status": "ok",
         "collection": collection,
         "chunksIndexed": outcome.chunk_count,
         "chunkSize": outcome.chunk_size,
+        "overlap": outcome.overlap,
+        "autoSized": outcome.auto_sized,
+        "embeddingContextWindow": outcome.embedding_context_window,
         "inserted": outcome.inserted,
         "updated": outcome.updated,
         "skippedDuplicates": outcome.skipped_duplicates,
+        "skippedLowQuality": outcome.skipped_low_quality,
+        "skippedUnchanged": outcome.skipped_unchanged,
+        "skippedUnchangedChunks": outcome.skipped_unchanged_chunks,
+        "duplicateRatio": outcome.duplicate_ratio(),
+        "degraded": outcome.degraded,
+        "docId": outcome.doc_id,
+        "ingestProfile": {
+            "embeddingProvider": profile.embedding_provider,
+            "embeddingModel": profile.embedding_model,
+            "embeddingDimension": profile.embedding_dimension,
+            "chunkSize": profile.chunk_size,
+            "chunkOverlap": profile.chunk_overlap,
+            "strategy": profile.strategy,
+            "tokenizer": profile.tokenizer,
+            "dedupe": profile.dedupe,
+            "version": profile.version,
+            "profileHash": profile.hash(),
+        },
+    });
+    let mut warnings = Vec::new();
+    if let Some(requested) = outcome.chunk_size_clamped_from {
+        warnings.push(if outcome.auto_sized {
+            format!(
+                "Derived chunk size {requested} (context window {}) was clamped to {}",
+                outcome.embedding_context_window, outcome.chunk_size
+            )
+        } else {
+            format!(
+                "TEXT_SPLITTER_CHUNK_SIZE {requested} was raised to the floor of {}",
+                outcome.chunk_size
+            )
+        });
+    }
+    match outcome.transcript {
+        Some(TranscriptHandling::Warned) => warnings.push(
+            "Text looks like a pasted chat transcript; consider pushing a summary instead".into(),
+        ),
+        Some(TranscriptHandling::Stripped { sentences }) => warnings.push(format!(
+            "Text looks like a pasted chat transcript; removed {sentences} assistant filler sentences before chunking"
+        )),
+        None => {}
+    }
+    if !warnings.is_empty() {
+        payload["warnings"] = json!(warnings);
+    }
+    if let Some(summary) = outcome.classification {
+        payload["classification"] = json!({
+            "episodic": summary.episodic,
+            "semantic": summary.semantic,
+            "procedural": summary.procedural,
+        });
+    }
+    payload
+}
+
+/// Request payload accepted by the `reembed-fallback` tool.
+#[derive(Debug, Deserialize)]
+pub(crate) struct ReembedFallbackRequest {
+    /// Optional Qdrant collection override.
+    #[serde(default)]
+    pub(crate) collection: Option<String>,
+}
+
+/// Handle the `reembed-fallback` tool by repairing points written during a degraded ingest.
+pub(crate) async fn handle_reembed_fallback(
+    processing: &Arc<ProcessingService>,
+    arguments: Option<JsonObject>,
+) -> Result<CallToolResult, McpError> {
+    let args: ReembedFallbackRequest =
+        parse_arguments(arguments, &reembed_fallback_input_schema())?;
+    let collection = resolve_collection(args.collection)?;
+
+    let outcome = processing
+        .reembed_fallback(&collection)
+        .await
+        .map_err(map_processing_error)?;
+
+    Ok(CallToolResult::structured(json!({
+        "status": "ok",
+        "collection": collection,
+        "reembedded": outcome.reembedded,
+        "skipped": outcome.skipped,
+    })))
+}
+
+/// Request payload accepted by the `compare-providers` tool.
+#[derive(Debug, Deserialize)]
+pub(crate) struct CompareProvidersRequest {
+    /// Optional Qdrant collection override.
+    #[serde(default)]
+    pub(crate) collection: Option<String>,
+    /// Queries to compare instead of the recent searches.
+    #[serde(default)]
+    pub(crate) queries: Option<Vec<String>>,
+    /// Recent searches to compare when `queries` is omitted.
+    #[serde(default)]
+    pub(crate) sample: Option<usize>,
+    /// Hits compared per query.
+    #[serde(default)]
+    pub(crate) k: Option<usize>,
+}
+
+/// Handle the `compare-providers` tool, scoring the candidate provider against the active one.
+pub(crate) async fn handle_compare_providers(
+    processing: &Arc<ProcessingService>,
+    arguments: Option<JsonObject>,
+) -> Result<CallToolResult, McpError> {
+    let args: CompareProvidersRequest =
+        parse_arguments(arguments, &compare_providers_input_schema())?;
+    let collection = resolve_collection(args.collection)?;
+    let queries: Vec<String> = args
+        .queries
+        .unwrap_or_default()
+        .into_iter()
+        .map(|query| query.trim().to_string())
+        .filter(|query| !query.is_empty())
+        .collect();
+
+    let comparison = processing
+        .compare_providers(
+            &collection,
+            queries,
+            args.sample.unwrap_or(DEFAULT_COMPARE_SAMPLE).max(1),
+            args.k.unwrap_or(DEFAULT_COMPARE_K).max(1),
+        )
+        .await
+        .map_err(|error| classified_error(error.kind(), error.to_string()))?;
+
+    let queries: Vec<Value> = comparison
+        .queries
+        .iter()
+        .map(|query| {
+            json!({
+                "query": query.query,
+                "overlap": query.overlap,
+                "activeIds": query.active_ids,
+                "candidateIds": query.candidate_ids,
+            })
+        })
+        .collect();
+    Ok(CallToolResult::structured(json!({
+        "status": "ok",
+        "collection": comparison.collection,
+        "candidateCollection": comparison.candidate_collection,
+        "k": comparison.k,
+        "activePoints": comparison.active_points,
+        "candidatePoints": comparison.candidate_points,
+        "meanOverlap": comparison.mean_overlap,
+        "scoreCorrelation": comparison.score_correlation,
+        "sharedHits": comparison.shared_hits,
+        "queries": queries,
+    })))
+}
+
+/// Request payload accepted by the `promote-provider` tool.
+#[derive(Debug, Deserialize)]
+pub(crate) struct PromoteProviderRequest {
+    /// Optional Qdrant collection override.
+    #[serde(default)]
+    pub(crate) collection: Option<String>,
+    /// Promote even when the candidate collection is missing points.
+    #[serde(default)]
+    pub(crate) force: Option<bool>,
+    /// Allow deleting a concrete active collection so an alias can take its name.
+    #[serde(default)]
+    pub(crate) drop_previous: Option<bool>,
+    /// Explicit acknowledgement required because the embedding provider changes.
+    #[serde(default)]
+    pub(crate) confirm: Option<bool>,
+}
+
+/// Handle the `promote-provider` tool, making the candidate vectors the primary ones.
+///
+/// Clears the search `cache`, whose responses were ranked with the previous provider.
+pub(crate) async fn handle_promote_provider(
+    processing: &Arc<ProcessingService>,
+    cache: &SearchCache,
+    arguments: Option<JsonObject>,
+) -> Result<CallToolResult, McpError> {
+    let args: PromoteProviderRequest =
+        parse_arguments(arguments, &promote_provider_input_schema())?;
+    if args.confirm != Some(true) {
+        return Err(McpError::invalid_params(
+            "`promote-provider` switches queries and writes to the candidate provider; pass `confirm: true`",
+            None,
+        ));
+    }
+    let collection = resolve_collection(args.collection)?;
+
+    let outcome = processing
+        .promote_provider(
+            &collection,
+            args.force.unwrap_or(false),
+            args.drop_previous.unwrap_or(false),
+        )
+        .await
+        .map_err(|error| classified_error(error.kind(), error.to_string()))?;
+    // Cached results were ranked with the previous provider's vectors.
+    cache.clear();
+
+    Ok(CallToolResult::structured(json!({
+        "status": "ok",
+        "collection": outcome.collection,
+        "candidateCollection": outcome.candidate_collection,
+        "previousTarget": outcome.previous_target,
+        "droppedPrevious": outcome.dropped_previous,
+        "restartEnv": {
+            "EMBEDDING_PROVIDER": outcome.provider,
+            "EMBEDDING_MODEL": outcome.model,
+            "EMBEDDING_DIMENSION": outcome.dimension,
+        },
     })))
 }
+
+/// Interpret the `auto_classify` flag; `"llm"` requires a configured summarization provider.
+fn parse_auto_classify(value: Option<Value>) -> Result<Option<AutoClassify>, McpError> {
+    let mode = match value {
+        None | Some(Value::Bool(false)) => return Ok(None),
+        Some(Value::Bool(true)) => AutoClassify::Heuristic,
+        Some(Value::String(mode)) if mode == "heuristic" => AutoClassify::Heuristic,
+        Some(Value::String(mode)) if mode == "llm" => AutoClassify::Llm,
+        Some(other) => {
+            return Err(McpError::invalid_params(
+                format!(
+                    "`auto_classify` must be true, false, \"heuristic\", or \"llm\" (got {other})"
+                ),
+                None,
+            ));
+        }
+    };
+    if mode == AutoClassify::Llm
+        && matches!(
+            get_config().summarization_provider,
+            SummarizationProvider::None
+        )
+    {
+        return Err(McpError::invalid_params(
+            "`auto_classify: \"llm\"` requires SUMMARIZATION_PROVIDER to be configured",
+            None,
+        ));
+    }
+    Ok(Some(mode))
+}
+
+/// Suggest `warm-collection` while Qdrant is still indexing a large backlog.
+fn indexing_hint(info: &CollectionInfo) -> Option<String> {
+    let backlog = info.unindexed_backlog();
+    (!info.is_ready() && backlog > BACKLOG_HINT_THRESHOLD).then(|| {
+        format!(
+            "{backlog} points are still being indexed; call `warm-collection` before latency-sensitive searches."
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::EmbeddingProvider,
+        mcp::schemas::{assert_matches_output_schema, push_output_schema},
+        processing::{ClassificationSummary, IngestProfile, chunking::determine_chunk_size},
+    };
+
+    fn info(status: &str, points: u64, indexed: u64) -> CollectionInfo {
+        CollectionInfo {
+            status: Some(status.into()),
+            points_count: Some(points),
+            indexed_vectors_count: Some(indexed),
+            segments_count: None,
+        }
+    }
+
+    #[test]
+    fn auto_classify_accepts_flags_and_named_modes() {
+        assert_eq!(parse_auto_classify(None).unwrap(), None);
+        assert_eq!(parse_auto_classify(Some(json!(false))).unwrap(), None);
+        assert_eq!(
+            parse_auto_classify(Some(json!(true))).unwrap(),
+            Some(AutoClassify::Heuristic)
+        );
+        assert_eq!(
+            parse_auto_classify(Some(json!("heuristic"))).unwrap(),
+            Some(AutoClassify::Heuristic)
+        );
+        let error = parse_auto_classify(Some(json!("magic"))).unwrap_err();
+        assert!(error.message.contains("`auto_classify` must be"));
+    }
+
+    #[test]
+    fn push_response_echoes_auto_sizing_for_large_context_models() {
+        let sizing = determine_chunk_size(
+            None,
+            16,
+            EmbeddingProvider::Ollama,
+            "nomic-embed-text",
+            false,
+        );
+        let outcome = ProcessingOutcome {
+            chunk_count: 3,
+            chunk_size: sizing.chunk_size,
+            overlap: 32,
+            auto_sized: sizing.auto_sized,
+            embedding_context_window: sizing.context_window,
+            chunk_size_clamped_from: sizing.clamped_from,
+            inserted: 3,
+            updated: 0,
+            skipped_duplicates: 0,
+            skipped_low_quality: 0,
+            skipped_unchanged: false,
+            skipped_unchanged_chunks: 0,
+            degraded: false,
+            classification: None,
+            ingest_profile: IngestProfile {
+                embedding_provider: "ollama".into(),
+                embedding_model: "nomic-embed-text".into(),
+                embedding_dimension: 768,
+                chunk_size: sizing.chunk_size,
+                chunk_overlap: 32,
+                strategy: "semchunk",
+                tokenizer: "cl100k_base",
+                dedupe: "chunk_hash",
+                version: "0.0.0",
+            },
+            doc_id: Some("doc-1".into()),
+            transcript: None,
+        };
+
+        let payload = push_response("demo", &outcome);
+        assert_eq!(payload["chunkSize"], 1024);
+        assert_eq!(payload["overlap"], 32);
+        assert_eq!(payload["autoSized"], true);
+        assert_eq!(payload["embeddingContextWindow"], 8192);
+        assert_eq!(payload["docId"], "doc-1");
+        assert_eq!(payload["schemaVersion"], OUTPUT_SCHEMA_VERSION);
+        assert_eq!(payload["duplicateRatio"], 0.0);
+        assert_eq!(
+            payload["warnings"],
+            json!(["Derived chunk size 2048 (context window 8192) was clamped to 1024"])
+        );
+        assert_matches_output_schema(push_output_schema(), &payload);
+
+        let stripped = ProcessingOutcome {
+            chunk_size_clamped_from: None,
+            chunk_count: 0,
+            skipped_unchanged: true,
+            skipped_unchanged_chunks: 4,
+            classification: Some(ClassificationSummary {
+                episodic: 1,
+                semantic: 2,
+                procedural: 0,
+            }),
+            doc_id: None,
+            transcript: Some(TranscriptHandling::Stripped { sentences: 3 }),
+            ..outcome
+        };
+        let payload = push_response("demo", &stripped);
+        assert_eq!(payload["docId"], Value::Null);
+        assert_eq!(payload["duplicateRatio"], 1.0);
+        assert!(
+            payload["warnings"][0]
+                .as_str()
+                .is_some_and(|warning| warning.contains("removed 3 assistant filler sentences"))
+        );
+        assert_matches_output_schema(push_output_schema(), &payload);
+    }
+
+    #[test]
+    fn client_identity_fills_only_omitted_fields() {
+        let map = BTreeMap::from([("cursor".to_string(), "frontend".to_string())]);
+
+        let (mut project_id, mut tags) = (None, None);
+        let inferred = infer_client_metadata(Some("Cursor"), &map, &mut project_id, &mut tags);
+        assert_eq!(project_id.as_deref(), Some("frontend"));
+        assert_eq!(tags, Some(vec!["client:cursor".to_string()]));
+        assert_eq!(inferred.project_id.as_deref(), Some("frontend"));
+
+        let (mut project_id, mut tags) = (None, Some(vec!["notes".to_string()]));
+        let inferred =
+            infer_client_metadata(Some("Claude Desktop"), &map, &mut project_id, &mut tags);
+        assert_eq!(project_id, None);
+        assert_eq!(tags, Some(vec!["notes".to_string()]));
+        assert!(inferred.is_empty());
+
+        let (mut project_id, mut tags) = (None, None);
+        infer_client_metadata(Some("Zed Editor"), &map, &mut project_id, &mut tags);
+        assert_eq!(tags, Some(vec!["client:zed-editor".to_string()]));
+        assert!(infer_client_metadata(None, &map, &mut None, &mut None).is_empty());
+    }
+
+    #[test]
+    fn memory_type_precedence_is_explicit_then_rules_then_project_default() {
+        let defaults = BTreeMap::from([("runbooks".to_string(), "procedural".to_string())]);
+        let rules = MemoryTypeRule::parse_list("*.log=episodic").expect("rules");
+        let resolve = |project: Option<&str>, source: Option<&str>, explicit: Option<&str>| {
+            let mut memory_type = explicit.map(str::to_string);
+            let applied = apply_project_memory_type(
+                &defaults,
+                &rules,
+                project,
+                None,
+                source,
+                &mut memory_type,
+            );
+            (memory_type, applied)
+        };
+
+        let explicit = resolve(Some("runbooks"), Some("deploy.log"), Some("semantic"));
+        assert_eq!(explicit, (Some("semantic".to_string()), None));
+        let rule = resolve(Some("runbooks"), Some("deploy.log"), None);
+        assert_eq!(rule, (None, None));
+        let project = resolve(Some("runbooks"), Some("deploy.md"), None);
+        assert_eq!(
+            project,
+            (
+                Some("procedural".to_string()),
+                Some("procedural".to_string())
+            )
+        );
+        assert_eq!(
+            resolve(Some("other"), Some("deploy.md"), None),
+            (None, None)
+        );
+        assert_eq!(resolve(None, None, None), (None, None));
+    }
+
+    #[test]
+    fn indexing_hint_fires_only_for_large_pending_backlogs() {
+        let hint = indexing_hint(&info("yellow", 50_000, 10_000)).expect("hint");
+        assert!(hint.contains("40000 points"));
+        assert!(hint.contains("warm-collection"));
+        assert!(indexing_hint(&info("yellow", 5_000, 0)).is_none());
+        assert!(indexing_hint(&info("green", 50_000, 0)).is_none());
+    }
+}
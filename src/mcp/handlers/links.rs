@@ -0,0 +1,128 @@
+//! Handlers for the link-memories and get-links tools.
+
+use std::sync::Arc;
+
+use crate::{
+    mcp::schemas::{get_links_input_schema, link_memories_input_schema},
+    processing::{ErrorKind, LinkOutcome, ProcessingService, sanitize::sanitize_string},
+    qdrant::LinkRelation,
+};
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, JsonObject},
+};
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use super::{classified_error, map_processing_error, parse_arguments, resolve_collection};
+
+/// Request payload for the `link-memories` tool.
+#[derive(Debug, Deserialize)]
+pub(crate) struct LinkMemoriesRequest {
+    /// Optional Qdrant collection override.
+    #[serde(default)]
+    pub(crate) collection: Option<String>,
+    /// Memory recording the link.
+    pub(crate) source_memory_id: String,
+    /// Memory the link points at.
+    pub(crate) target_memory_id: String,
+    /// Relation from the source to the target.
+    pub(crate) relation: LinkRelation,
+}
+
+/// Request payload for the `get-links` tool.
+#[derive(Debug, Deserialize)]
+pub(crate) struct GetLinksRequest {
+    /// Optional Qdrant collection override.
+    #[serde(default)]
+    pub(crate) collection: Option<String>,
+    /// Memory whose links are resolved.
+    pub(crate) memory_id: String,
+}
+
+/// Handle the `link-memories` tool, recording a directed relation between two stored memories.
+pub(crate) async fn handle_link_memories(
+    processing: &Arc<ProcessingService>,
+    arguments: Option<JsonObject>,
+) -> Result<CallToolResult, McpError> {
+    let args: LinkMemoriesRequest = parse_arguments(arguments, &link_memories_input_schema())?;
+    let collection = resolve_collection(args.collection)?;
+    let source = required_id("source_memory_id", args.source_memory_id)?;
+    let target = required_id("target_memory_id", args.target_memory_id)?;
+    if source == target {
+        return Err(classified_error(
+            ErrorKind::InvalidRequest,
+            "A memory cannot be linked to itself".into(),
+        ));
+    }
+
+    let created = match processing
+        .link_memories(&collection, &source, &target, args.relation)
+        .await
+        .map_err(map_processing_error)?
+    {
+        LinkOutcome::Created => true,
+        LinkOutcome::AlreadyLinked => false,
+        LinkOutcome::MissingMemories(missing) => {
+            return Err(classified_error(
+                ErrorKind::InvalidRequest,
+                format!("No memory with id {} in '{collection}'", missing.join(", ")),
+            ));
+        }
+    };
+    Ok(CallToolResult::structured(json!({
+        "status": "ok",
+        "collection": collection,
+        "sourceMemoryId": source,
+        "targetMemoryId": target,
+        "relation": args.relation,
+        "created": created,
+    })))
+}
+
+/// Handle the `get-links` tool, resolving a memory's outbound and inbound links.
+pub(crate) async fn handle_get_links(
+    processing: &Arc<ProcessingService>,
+    arguments: Option<JsonObject>,
+) -> Result<CallToolResult, McpError> {
+    let args: GetLinksRequest = parse_arguments(arguments, &get_links_input_schema())?;
+    let collection = resolve_collection(args.collection)?;
+    let memory_id = required_id("memory_id", args.memory_id)?;
+
+    let links = processing
+        .memory_links(&collection, &memory_id)
+        .await
+        .map_err(map_processing_error)?
+        .ok_or_else(|| {
+            classified_error(
+                ErrorKind::InvalidRequest,
+                format!("No memory with id {memory_id} in '{collection}'"),
+            )
+        })?;
+
+    let outbound: Vec<Value> = links
+        .outbound
+        .iter()
+        .map(|link| json!({ "rel": link.rel, "targetMemoryId": link.target_memory_id }))
+        .collect();
+    let inbound: Vec<Value> = links
+        .inbound
+        .iter()
+        .map(|link| json!({ "rel": link.rel, "sourceMemoryId": link.source_memory_id }))
+        .collect();
+    Ok(CallToolResult::structured(json!({
+        "collection": collection,
+        "memoryId": memory_id,
+        "outbound": outbound,
+        "inbound": inbound,
+    })))
+}
+
+fn required_id(field: &str, value: String) -> Result<String, McpError> {
+    sanitize_string(Some(value)).ok_or_else(|| {
+        classified_error(
+            ErrorKind::InvalidRequest,
+            format!("`{field}` must not be empty"),
+        )
+    })
+}
@@ -2,18 +2,105 @@
 
 use std::sync::Arc;
 
-use crate::processing::ProcessingService;
-use rmcp::{ErrorData as McpError, model::CallToolResult};
+use crate::{
+    mcp::{
+        limiter::LimiterSnapshot,
+        schemas::{OUTPUT_SCHEMA_VERSION, metrics_input_schema},
+    },
+    processing::ProcessingService,
+};
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, JsonObject},
+};
+use serde::Deserialize;
 use serde_json::json;
 
-/// Handle the `metrics` tool, returning the current ingestion counters.
+use super::{check_collection_name, parse_arguments};
+
+/// Request payload for the `metrics` tool.
+#[derive(Debug, Deserialize)]
+pub(crate) struct MetricsRequest {
+    /// Optional collection whose counters should be reported instead of the global totals.
+    #[serde(default)]
+    pub(crate) collection: Option<String>,
+}
+
+/// Handle the `metrics` tool, returning the ingestion counters and tool-call load.
+///
+/// Without a `collection` the counters are global totals; with one they cover only documents
+/// pushed to that collection.
 pub(crate) async fn handle_metrics(
     processing: &Arc<ProcessingService>,
+    tools: LimiterSnapshot,
+    arguments: Option<JsonObject>,
 ) -> Result<CallToolResult, McpError> {
-    let snapshot = processing.metrics_snapshot();
-    Ok(CallToolResult::structured(json!({
+    let args: MetricsRequest = parse_arguments(arguments, &metrics_input_schema())?;
+    let snapshot = match args.collection.as_deref() {
+        Some(collection) => {
+            check_collection_name("collection", collection)?;
+            processing.collection_metrics_snapshot(collection)
+        }
+        None => processing.metrics_snapshot(),
+    };
+    let mut payload = json!({
+        "schemaVersion": OUTPUT_SCHEMA_VERSION,
         "documentsIndexed": snapshot.documents_indexed,
         "chunksIndexed": snapshot.chunks_indexed,
         "lastChunkSize": snapshot.last_chunk_size,
-    })))
+        "dedupe": {
+            "chunksSeen": snapshot.dedupe.chunks_seen,
+            "skippedExact": snapshot.dedupe.skipped_exact,
+            "skippedUnchangedSource": snapshot.dedupe.skipped_unchanged_source,
+            "embeddingsSaved": snapshot.dedupe.embeddings_saved,
+            "duplicateRatio": snapshot.dedupe.duplicate_ratio,
+        },
+        "invalidEmbeddings": snapshot.invalid_embeddings,
+        "tools": tools,
+    });
+    if let Some(collection) = args.collection {
+        payload["collection"] = json!(collection);
+    }
+    Ok(CallToolResult::structured(payload))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embedding::AiLibClient;
+    use crate::mcp::schemas::{assert_matches_output_schema, metrics_output_schema};
+    use crate::qdrant::QdrantService;
+    use std::collections::BTreeMap;
+
+    #[tokio::test]
+    async fn metrics_output_matches_declared_schema() {
+        let processing = Arc::new(ProcessingService::from_parts(
+            Box::new(AiLibClient::new()),
+            QdrantService {
+                client: reqwest::Client::new(),
+                base_url: "http://127.0.0.1:6333".into(),
+                read_base_url: None,
+                api_key: None,
+                api_keys: BTreeMap::new(),
+                capabilities: Default::default(),
+                #[cfg(feature = "fault-injection")]
+                faults: None,
+            },
+        ));
+        let tools = LimiterSnapshot {
+            in_flight: 1,
+            queued: 0,
+            max_concurrent: 8,
+            max_queued: 16,
+        };
+
+        for arguments in [None, json!({ "collection": "docs" }).as_object().cloned()] {
+            let result = handle_metrics(&processing, tools, arguments)
+                .await
+                .expect("metrics");
+            let payload = result.structured_content.expect("structured");
+            assert_eq!(payload["schemaVersion"], OUTPUT_SCHEMA_VERSION);
+            assert_matches_output_schema(metrics_output_schema(), &payload);
+        }
+    }
 }
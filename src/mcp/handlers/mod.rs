@@ -1,27 +1,331 @@
 //! Tool handlers for the MCP server.
 
+use crate::{
+    config::get_config,
+    processing::{ErrorKind, ProcessingError},
+    qdrant::validate_collection_name,
+};
 use rmcp::{ErrorData as McpError, model::JsonObject};
 use serde::de::DeserializeOwned;
-use serde_json::Value;
+use serde_json::{Map, Value, json};
 
 pub mod collections;
+pub mod document;
 pub mod index;
+pub mod links;
 pub mod metrics;
+pub mod ping;
 pub mod search;
 pub mod summarize;
+pub mod sync;
 
 /// Parse structured arguments supplied to a tool invocation.
 pub(crate) fn parse_arguments<T: DeserializeOwned>(
     arguments: Option<JsonObject>,
+    schema: &Map<String, Value>,
 ) -> Result<T, McpError> {
     let value = arguments
         .map(Value::Object)
         .unwrap_or_else(|| Value::Object(JsonObject::new()));
-    parse_arguments_value(value)
+    parse_arguments_value(value, schema)
 }
 
 /// Deserialize arguments represented as a JSON value into the target type.
-pub(crate) fn parse_arguments_value<T: DeserializeOwned>(value: Value) -> Result<T, McpError> {
+///
+/// Keys are first checked against the tool's input `schema` so that typos surface as a
+/// targeted error (with a nearest-key suggestion) rather than a raw serde message or, for
+/// lenient request types, a silently ignored field.
+pub(crate) fn parse_arguments_value<T: DeserializeOwned>(
+    value: Value,
+    schema: &Map<String, Value>,
+) -> Result<T, McpError> {
+    reject_unknown_keys(&value, schema, "")?;
     serde_json::from_value(value)
         .map_err(|err| McpError::invalid_params(format!("Invalid arguments: {err}"), None))
 }
+
+/// Resolve an optional `collection` override, defaulting to `QDRANT_COLLECTION_NAME`.
+pub(crate) fn resolve_collection(collection: Option<String>) -> Result<String, McpError> {
+    resolve_project_collection(collection, None)
+}
+
+/// Resolve an optional `collection` override, defaulting to the project's collection when
+/// `COLLECTION_PER_PROJECT` is set.
+pub(crate) fn resolve_project_collection(
+    collection: Option<String>,
+    project_id: Option<&str>,
+) -> Result<String, McpError> {
+    match collection {
+        Some(name) => {
+            check_collection_name("collection", &name)?;
+            Ok(name)
+        }
+        None => Ok(get_config().default_collection(project_id)),
+    }
+}
+
+/// Reject collection names that are unsafe to place in Qdrant URL paths.
+pub(crate) fn check_collection_name(field: &str, name: &str) -> Result<(), McpError> {
+    validate_collection_name(name)
+        .map_err(|message| McpError::invalid_params(format!("Invalid `{field}`: {message}"), None))
+}
+
+/// Build an MCP error for a classified pipeline failure.
+///
+/// Invalid requests surface as `invalid_params`; everything else is an internal error. Both carry
+/// `{ "kind", "retryable" }` data so clients can decide whether to retry.
+pub(crate) fn classified_error(kind: ErrorKind, message: String) -> McpError {
+    let data = Some(json!({ "kind": kind.as_str(), "retryable": kind.is_retryable() }));
+    match kind {
+        ErrorKind::InvalidRequest => McpError::invalid_params(message, data),
+        _ => McpError::internal_error(message, data),
+    }
+}
+
+/// Stable `code` carried by tool calls rejected because the call queue is full.
+pub(crate) const SERVER_BUSY: &str = "SERVER_BUSY";
+
+/// Reject a tool call that found the `MCP_MAX_QUEUED_TOOLS` queue full.
+///
+/// Carries `{ "code": "SERVER_BUSY", "kind": "server_busy", "retryable": true }` so clients can
+/// back off without matching on the message.
+pub(crate) fn server_busy() -> McpError {
+    let kind = ErrorKind::ServerBusy;
+    McpError::internal_error(
+        "Server busy: tool call queue is full; retry shortly",
+        Some(json!({
+            "code": SERVER_BUSY,
+            "kind": kind.as_str(),
+            "retryable": kind.is_retryable(),
+        })),
+    )
+}
+
+/// Stable `code` carried by errors for calls that omit `project_id` under
+/// `REQUIRE_PROJECT_FILTER`.
+pub(crate) const PROJECT_REQUIRED: &str = "PROJECT_REQUIRED";
+
+/// Reject a missing or blank `project_id` when `required` (`REQUIRE_PROJECT_FILTER`) is set.
+///
+/// The error is `invalid_params` with `{ "code": "PROJECT_REQUIRED", "hint" }` data pointing at
+/// the `mcp://projects` resource, so clients can pick a project and retry.
+pub(crate) fn require_project(
+    required: bool,
+    tool: &str,
+    project_id: Option<&str>,
+) -> Result<(), McpError> {
+    if !required || project_id.is_some_and(|project| !project.trim().is_empty()) {
+        return Ok(());
+    }
+    let kind = ErrorKind::InvalidRequest;
+    Err(McpError::invalid_params(
+        format!("`{tool}` requires `project_id` because REQUIRE_PROJECT_FILTER is enabled"),
+        Some(json!({
+            "code": PROJECT_REQUIRED,
+            "kind": kind.as_str(),
+            "retryable": kind.is_retryable(),
+            "hint": format!(
+                "Read {} for the known project ids and pass one as `project_id`",
+                crate::mcp::server::PROJECTS_URI
+            ),
+        })),
+    ))
+}
+
+/// Map a processing failure onto an MCP error using its [`ErrorKind`].
+pub(crate) fn map_processing_error(error: ProcessingError) -> McpError {
+    classified_error(error.kind(), error.to_string())
+}
+
+/// Reject keys not declared in the schema's `properties`, recursing into nested objects.
+fn reject_unknown_keys(
+    value: &Value,
+    schema: &Map<String, Value>,
+    prefix: &str,
+) -> Result<(), McpError> {
+    let (Some(provided), Some(Value::Object(properties))) =
+        (value.as_object(), schema.get("properties"))
+    else {
+        return Ok(());
+    };
+
+    for (key, nested) in provided {
+        match properties.get(key) {
+            Some(Value::Object(property_schema)) => {
+                reject_unknown_keys(nested, property_schema, &format!("{prefix}{key}."))?;
+            }
+            Some(_) => {}
+            None => {
+                let hint = match suggest_key(key, properties.keys()) {
+                    Some(candidate) => format!("did you mean `{prefix}{candidate}`?"),
+                    None => format!(
+                        "expected one of: {}",
+                        properties
+                            .keys()
+                            .map(|name| format!("`{prefix}{name}`"))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                };
+                return Err(McpError::invalid_params(
+                    format!("Unknown argument `{prefix}{key}`; {hint}"),
+                    None,
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Return the closest candidate within a typo-sized edit distance of `key`.
+fn suggest_key<'a>(key: &str, candidates: impl Iterator<Item = &'a String>) -> Option<&'a str> {
+    let budget = (key.chars().count() / 3).max(2);
+    candidates
+        .map(|candidate| (levenshtein(key, candidate), candidate))
+        .filter(|(distance, _)| *distance <= budget)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate.as_str())
+}
+
+fn levenshtein(left: &str, right: &str) -> usize {
+    let right: Vec<char> = right.chars().collect();
+    let mut previous: Vec<usize> = (0..=right.len()).collect();
+    for (i, left_char) in left.chars().enumerate() {
+        let mut current = vec![i + 1; right.len() + 1];
+        for (j, right_char) in right.iter().enumerate() {
+            let substitution = previous[j] + usize::from(left_char != *right_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[right.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::schemas::{create_collection_input_schema, index_input_schema};
+    use serde_json::json;
+
+    fn error_message(result: Result<Value, McpError>) -> String {
+        result.unwrap_err().message.to_string()
+    }
+
+    #[test]
+    fn levenshtein_counts_single_edits() {
+        assert_eq!(levenshtein("score_treshold", "score_threshold"), 1);
+        assert_eq!(levenshtein("tag", "tags"), 1);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("same", "same"), 0);
+    }
+
+    #[test]
+    fn push_rejects_typo_with_suggestion() {
+        let message = error_message(parse_arguments_value(
+            json!({ "text": "doc", "projet_id": "alpha" }),
+            &index_input_schema(),
+        ));
+        assert!(message.contains("`projet_id`"), "{message}");
+        assert!(message.contains("did you mean `project_id`?"), "{message}");
+    }
+
+    #[test]
+    fn new_collection_rejects_typo_with_suggestion() {
+        let message = error_message(parse_arguments_value(
+            json!({ "name": "notes", "vectorsize": 768 }),
+            &create_collection_input_schema(),
+        ));
+        assert!(message.contains("did you mean `vector_size`?"), "{message}");
+    }
+
+    #[test]
+    fn collection_overrides_reject_unsafe_names() {
+        for name in [
+            "../../collections",
+            "notes/points",
+            "noté",
+            "notes?wait=false",
+        ] {
+            let error = resolve_collection(Some(name.into())).unwrap_err();
+            assert_eq!(error.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+            assert!(
+                error.message.contains("Invalid `collection`"),
+                "{}",
+                error.message
+            );
+        }
+        assert_eq!(
+            resolve_collection(Some("repo-a.notes".into())).expect("valid"),
+            "repo-a.notes"
+        );
+        let error = check_collection_name("name", "a/b").unwrap_err();
+        assert!(
+            error.message.contains("Invalid `name`"),
+            "{}",
+            error.message
+        );
+        assert!(error.message.contains("'/'"), "{}", error.message);
+    }
+
+    #[test]
+    fn unrelated_keys_list_expected_arguments() {
+        let message = error_message(parse_arguments_value(
+            json!({ "name": "notes", "zzz": true }),
+            &create_collection_input_schema(),
+        ));
+        assert!(message.contains("expected one of"), "{message}");
+        assert!(message.contains("`vector_size`"), "{message}");
+    }
+
+    #[test]
+    fn require_project_only_rejects_missing_projects_when_enabled() {
+        assert!(require_project(false, "search", None).is_ok());
+        assert!(require_project(true, "search", Some("alpha")).is_ok());
+
+        for missing in [None, Some("  ")] {
+            let error = require_project(true, "search", missing).unwrap_err();
+            assert_eq!(error.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+            let data = error.data.expect("error data");
+            assert_eq!(data["code"], PROJECT_REQUIRED);
+            assert_eq!(data["retryable"], false);
+            assert!(
+                data["hint"].as_str().unwrap().contains("mcp://projects"),
+                "{data}"
+            );
+        }
+    }
+
+    #[test]
+    fn classified_errors_carry_kind_and_retry_hint() {
+        let transient = classified_error(ErrorKind::Transient, "down".into());
+        assert_eq!(transient.code, rmcp::model::ErrorCode::INTERNAL_ERROR);
+        assert_eq!(
+            transient.data,
+            Some(json!({ "kind": "transient", "retryable": true }))
+        );
+
+        let invalid = classified_error(ErrorKind::InvalidRequest, "bad".into());
+        assert_eq!(invalid.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+        assert_eq!(
+            invalid.data,
+            Some(json!({ "kind": "invalid_request", "retryable": false }))
+        );
+
+        let busy = classified_error(ErrorKind::ServerBusy, "busy".into());
+        assert_eq!(
+            busy.data,
+            Some(json!({ "kind": "server_busy", "retryable": true }))
+        );
+    }
+
+    #[test]
+    fn busy_rejections_carry_a_stable_code() {
+        let error = server_busy();
+        assert_eq!(error.code, rmcp::model::ErrorCode::INTERNAL_ERROR);
+        assert_eq!(
+            error.data,
+            Some(json!({ "code": SERVER_BUSY, "kind": "server_busy", "retryable": true }))
+        );
+    }
+}
@@ -0,0 +1,45 @@
+//! Handler for the liveness `ping` tool.
+
+use std::time::Instant;
+
+use rmcp::{ErrorData as McpError, model::CallToolResult};
+use serde_json::json;
+
+/// Handle the `ping` tool, reporting the server version and time since construction.
+///
+/// Touches neither Qdrant nor the embedding provider, so it stays cheap enough for periodic
+/// liveness checks by hosts that only speak the tools protocol.
+pub(crate) fn handle_ping(started_at: Instant) -> Result<CallToolResult, McpError> {
+    Ok(CallToolResult::structured(json!({
+        "ok": true,
+        "version": env!("CARGO_PKG_VERSION"),
+        "uptime_ms": started_at.elapsed().as_millis() as u64,
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn uptime(result: &CallToolResult) -> u64 {
+        let payload = result.structured_content.as_ref().expect("structured");
+        assert_eq!(payload["ok"], true);
+        assert_eq!(payload["version"], env!("CARGO_PKG_VERSION"));
+        payload["uptime_ms"].as_u64().expect("uptime_ms")
+    }
+
+    #[test]
+    fn ping_reports_ok_and_monotonic_uptime() {
+        let started_at = Instant::now()
+            .checked_sub(Duration::from_millis(25))
+            .expect("instant in range");
+
+        let first = uptime(&handle_ping(started_at).expect("ping"));
+        std::thread::sleep(Duration::from_millis(5));
+        let second = uptime(&handle_ping(started_at).expect("ping"));
+
+        assert!(first >= 25, "first uptime {first}");
+        assert!(second >= first + 5, "{second} < {first} + 5");
+    }
+}
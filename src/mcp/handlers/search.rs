@@ -1,27 +1,51 @@
 //! Handler and helpers for the `search` tool.
 
-use std::{collections::HashSet, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 use crate::{
-    config::get_config,
+    config::{SummarizationProvider, get_config},
     mcp::{
-        MEMORY_TYPES,
-        format::{build_search_response, format_search_hits},
-        handlers::parse_arguments_value,
+        cache::SearchCache,
+        format::{
+            ExplainInputs, PackedContext, build_search_response, format_search_hits,
+            pack_answer_context, pack_context,
+        },
+        handlers::{
+            check_collection_name, classified_error, map_processing_error, parse_arguments_value,
+            require_project,
+        },
+        schemas::search_input_schema,
+    },
+    processing::{
+        ErrorKind, LookupRequest, ProcessingService, QueryCompression, SearchError, SearchHit,
+        SearchOutcome, SearchRequest, SearchRequestBuilder, SearchTimeRange, SearchValidationError,
+        chunking::{
+            ENCODING_NAMES, TokenCounter, build_token_counter, encoding_counter, tokenizer_name,
+        },
+        sanitize::{TimeBoundary, parse_time_boundary},
+        types,
     },
-    processing::{ProcessingService, SearchError, SearchRequest, SearchTimeRange},
+    qdrant::{SearchParams, TimestampOrder},
+    response_limit::{fit_response, mark_truncated},
 };
 use rmcp::{
     ErrorData as McpError,
     model::{CallToolResult, JsonObject},
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value, json};
-use time::{OffsetDateTime, format_description::well_known::Rfc3339};
 
 /// Handle the `search` tool by performing a semantic query against stored memories.
+///
+/// Identical normalized requests are answered from `cache` while its TTL holds. Requests naming
+/// a `memory_id` or `chunk_hash` instead of `query_text` are exact lookups: they skip the
+/// embedding and the cache and return the matching records directly.
 pub(crate) async fn handle_search(
     processing: &Arc<ProcessingService>,
+    cache: &SearchCache,
     arguments: Option<JsonObject>,
 ) -> Result<CallToolResult, McpError> {
     let normalized_arguments = normalize_search_arguments(arguments);
@@ -34,25 +58,58 @@ pub(crate) async fn handle_search(
         .map(|map| map.contains_key("time_range"))
         .unwrap_or(false);
 
-    let args: SearchToolRequest = parse_arguments_value(normalized_arguments)?;
-    let params = validate_search_request(args, tags_present, time_range_present)?;
+    let args: SearchToolRequest =
+        parse_arguments_value(normalized_arguments, &search_input_schema())?;
+    let params = validate_search_request(
+        args,
+        tags_present,
+        time_range_present,
+        get_config().require_project_filter,
+    )?;
     let ValidatedSearchInput {
-        query_text,
+        target,
         project_id,
         memory_type,
         tags,
+        include_ids,
         time_range,
         limit,
+        clamped_limit,
         score_threshold,
+        search_params,
         collection,
+        highlight,
+        explain_hits,
+        include_provenance,
+        context_budget,
+        auto_relax,
+        sort,
+        require_text,
+        query_compression,
+        one_per_document,
+        prefer_exact,
+        fields,
+        mode,
     } = params;
 
     let config = get_config();
-    let collection_name = collection
-        .clone()
-        .unwrap_or_else(|| config.qdrant_collection_name.clone());
+    let pinned = collection.is_some();
+    let collection_name = match collection {
+        Some(name) => name,
+        None => {
+            let name = config.default_collection(project_id.as_deref());
+            // Per-project collections are created on first use, like on push.
+            if name != config.qdrant_collection_name {
+                processing
+                    .ensure_collection(&name)
+                    .await
+                    .map_err(map_processing_error)?;
+            }
+            name
+        }
+    };
 
-    let used_filters = build_used_filters(
+    let mut used_filters = build_used_filters(
         &collection_name,
         limit,
         score_threshold,
@@ -61,42 +118,542 @@ pub(crate) async fn handle_search(
         tags.as_ref(),
         time_range.as_ref(),
     );
+    if clamped_limit {
+        mark_clamped_limit(&mut used_filters);
+    }
+    if sort != SearchSort::Score {
+        used_filters.insert("sort".into(), json!(sort));
+    }
+    if require_text {
+        used_filters.insert("require_text".into(), Value::Bool(true));
+    }
+    if one_per_document {
+        used_filters.insert("one_per_document".into(), Value::Bool(true));
+    }
+    if query_compression == QueryCompression::Llm {
+        used_filters.insert("query_compression".into(), json!("llm"));
+    }
+    if auto_relax {
+        used_filters.insert("auto_relax".into(), Value::Bool(true));
+    }
+    if prefer_exact {
+        used_filters.insert("prefer_exact".into(), Value::Bool(true));
+    }
+    if let Some(ids) = include_ids.as_ref() {
+        used_filters.insert("include_ids".into(), json!(ids));
+    }
+    if let Some(fields) = fields.as_ref() {
+        used_filters.insert("fields".into(), json!(fields));
+    }
+    if mode == SearchMode::AnswerContext {
+        used_filters.insert("mode".into(), json!(mode));
+    }
+    let context_counter = context_budget
+        .as_ref()
+        .map(ContextBudget::counter)
+        .transpose()?;
+
+    let query_text = match target {
+        SearchTarget::Query(query_text) => query_text,
+        SearchTarget::Lookup {
+            memory_id,
+            chunk_hash,
+        } => {
+            if let Some(id) = memory_id.as_ref() {
+                used_filters.insert("memory_id".into(), Value::String(id.clone()));
+            }
+            if let Some(hash) = chunk_hash.as_ref() {
+                used_filters.insert("chunk_hash".into(), Value::String(hash.clone()));
+            }
+            let time_range = time_range.map(SearchTimeRange::from);
+            let (targets, partitions) = search_targets(
+                processing,
+                &collection_name,
+                pinned,
+                memory_type.as_deref(),
+                time_range.as_ref(),
+            )
+            .await?;
+            let request = LookupRequest {
+                memory_id,
+                chunk_hash,
+                collection: None,
+                project_id,
+                memory_type,
+                tags,
+                include_ids,
+                time_range,
+                limit: Some(limit),
+                require_text,
+                text_contains: None,
+            };
+            let mut hits = Vec::new();
+            for target in &targets {
+                let found = processing
+                    .lookup_memories(LookupRequest {
+                        collection: Some(target.clone()),
+                        ..request.clone()
+                    })
+                    .await
+                    .map_err(map_search_error)?;
+                hits.extend(found);
+            }
+            hits.truncate(limit);
+            if one_per_document {
+                keep_best_per_document(&mut hits);
+            }
+            sort_hits(&mut hits, sort);
+            retain_hit_fields(&mut hits, fields.as_deref());
+            let resolved_collection = processing.resolve_collection(&collection_name).await;
+            let result = fit_response(&hits, config.max_response_bytes, |kept, dropped| {
+                let (results, context) =
+                    format_search_hits(kept.to_vec(), None, None, include_provenance);
+                let mut payload = build_search_response(
+                    collection_name.clone(),
+                    resolved_collection.clone(),
+                    limit,
+                    score_threshold,
+                    results,
+                    context,
+                    used_filters.clone(),
+                );
+                if let Some((budget, (tokenizer, counter))) =
+                    context_budget.as_ref().zip(context_counter.as_ref())
+                {
+                    let packed = pack_context(kept, budget.max_tokens, &**counter);
+                    apply_packed_context(&mut payload, packed, tokenizer);
+                }
+                if let Some(partitions) = partitions.as_ref() {
+                    payload["partitions_searched"] = json!(partitions);
+                }
+                mark_truncated(&mut payload, dropped);
+                CallToolResult::structured(payload)
+            });
+            return Ok(result);
+        }
+    };
+
+    let cache_key = search_cache_key(
+        &query_text,
+        &used_filters,
+        search_params.as_ref(),
+        highlight,
+        explain_hits,
+        include_provenance,
+        context_budget.as_ref(),
+    );
+    let highlight_query = highlight.then(|| query_text.clone());
+    let explain = explain_hits.then(|| ExplainInputs {
+        score_threshold,
+        project_id: project_id.clone(),
+        memory_type: memory_type.clone(),
+        tags: tags.clone(),
+    });
+    if let Some(payload) = cache.get(&cache_key) {
+        return Ok(CallToolResult::structured(payload));
+    }
 
-    let search_request = SearchRequest {
+    let time_range = time_range.map(SearchTimeRange::from);
+    let (targets, partitions) = search_targets(
+        processing,
+        &collection_name,
+        pinned,
+        memory_type.as_deref(),
+        time_range.as_ref(),
+    )
+    .await?;
+    let mut search_request = SearchRequest {
         query_text,
         collection: Some(collection_name.clone()),
         project_id,
         memory_type,
         tags,
-        time_range: time_range.clone().map(SearchTimeRange::from),
+        include_ids,
+        time_range,
         limit: Some(limit),
         score_threshold: Some(score_threshold),
+        search_params,
+        order_by_timestamp: sort.timestamp_order(),
+        require_text,
+        query_compression,
+        fields: fields
+            .as_ref()
+            .map(|fields| payload_fields_for(fields, sort != SearchSort::Score, one_per_document)),
+    };
+    let exact_hits = if prefer_exact {
+        exact_text_hits(processing, &search_request, &targets, limit).await?
+    } else {
+        Vec::new()
+    };
+    // Enough exact matches fill the response on their own, so nothing is embedded.
+    let mut outcome = if exact_hits.len() >= limit {
+        SearchOutcome {
+            hits: Vec::new(),
+            effective_query: search_request.query_text.clone(),
+            query_truncated: false,
+            query_compressed: false,
+        }
+    } else {
+        run_targeted_search(processing, &search_request, &targets, sort).await?
     };
+    let mut relaxed_filters = Vec::new();
+    if auto_relax && exact_hits.is_empty() {
+        for (filter, drop_filter) in RELAX_STEPS {
+            if !outcome.hits.is_empty() {
+                break;
+            }
+            if drop_filter(&mut search_request) {
+                relaxed_filters.push(filter);
+                used_filters.remove(filter);
+                outcome = run_targeted_search(processing, &search_request, &targets, sort).await?;
+            }
+        }
+    }
+
+    let SearchOutcome {
+        mut hits,
+        effective_query,
+        query_truncated,
+        query_compressed,
+    } = outcome;
+    let exact_matches = exact_hits.len();
+    if prefer_exact {
+        hits = merge_exact_hits(exact_hits, hits, limit);
+    }
+    if one_per_document {
+        keep_best_per_document(&mut hits);
+    }
+    sort_hits(&mut hits, sort);
+    retain_hit_fields(&mut hits, fields.as_deref());
+
+    let resolved_collection = processing.resolve_collection(&collection_name).await;
+    let result = fit_response(&hits, config.max_response_bytes, |kept, dropped| {
+        let (results, context) = format_search_hits(
+            kept.to_vec(),
+            highlight_query.as_deref(),
+            explain.as_ref(),
+            include_provenance,
+        );
+        let mut payload = build_search_response(
+            collection_name.clone(),
+            resolved_collection.clone(),
+            limit,
+            score_threshold,
+            results,
+            context,
+            used_filters.clone(),
+        );
+
+        payload["query_truncated"] = json!(query_truncated);
+        payload["query_compressed"] = json!(query_compressed);
+        payload["effective_query"] = json!(effective_query);
+        if let Some((budget, (tokenizer, counter))) =
+            context_budget.as_ref().zip(context_counter.as_ref())
+        {
+            let packed = match mode {
+                SearchMode::Results => pack_context(kept, budget.max_tokens, &**counter),
+                SearchMode::AnswerContext => {
+                    let packed = pack_answer_context(kept, budget.max_tokens, &**counter);
+                    apply_answer_citations(&mut payload, kept, &packed);
+                    packed
+                }
+            };
+            apply_packed_context(&mut payload, packed, tokenizer);
+        }
+        if auto_relax {
+            payload["relaxed_filters"] = json!(relaxed_filters);
+        }
+        if prefer_exact {
+            payload["exact_matches"] = json!(exact_matches);
+        }
+        if let Some(partitions) = partitions.as_ref() {
+            payload["partitions_searched"] = json!(partitions);
+        }
+        mark_truncated(&mut payload, dropped);
+        CallToolResult::structured(payload)
+    });
+    if let Some(payload) = result.structured_content.clone() {
+        cache.insert(cache_key, payload);
+    }
+    Ok(result)
+}
+
+/// Fields to fetch for a `fields` projection: the requested ones plus `timestamp` when hits are
+/// sorted by time and `doc_id` when they are collapsed per document.
+fn payload_fields_for(fields: &[String], sorted_by_time: bool, per_document: bool) -> Vec<String> {
+    let mut payload_fields = fields.to_vec();
+    for (needed, field) in [(sorted_by_time, "timestamp"), (per_document, "doc_id")] {
+        if needed && !payload_fields.iter().any(|existing| existing == field) {
+            payload_fields.push(field.to_string());
+        }
+    }
+    payload_fields
+}
+
+/// Clear the fields a `fields` projection left out, including those fetched only for sorting.
+fn retain_hit_fields(hits: &mut [SearchHit], fields: Option<&[String]>) {
+    if let Some(fields) = fields {
+        hits.iter_mut().for_each(|hit| hit.retain_fields(fields));
+    }
+}
+
+/// Removes one filter from a request, reporting whether it was set.
+type DropFilter = fn(&mut SearchRequest) -> bool;
+
+/// Filters `auto_relax` drops, least important first.
+const RELAX_STEPS: [(&str, DropFilter); 2] = [
+    ("time_range", |request| request.time_range.take().is_some()),
+    ("tags", |request| request.tags.take().is_some()),
+];
 
-    let hits = processing
-        .search_memories(search_request)
+/// Validate and run one vector search.
+async fn run_search(
+    processing: &ProcessingService,
+    request: SearchRequest,
+) -> Result<SearchOutcome, McpError> {
+    let request = SearchRequestBuilder::from(request)
+        .build()
+        .map_err(invalid_search)?;
+    processing
+        .search_memories(request)
         .await
-        .map_err(map_search_error)?;
+        .map_err(map_search_error)
+}
 
-    let (results, context) = format_search_hits(hits);
-    let payload = build_search_response(
-        collection_name,
-        limit,
-        score_threshold,
-        results,
-        context,
-        used_filters,
-    );
+/// Collections a search reads, with the partitions among them when `EPISODIC_PARTITIONING`
+/// applies.
+///
+/// A search that names its `collection` reads only that collection; see
+/// [`ProcessingService::read_targets`] for the others.
+async fn search_targets(
+    processing: &ProcessingService,
+    collection_name: &str,
+    pinned: bool,
+    memory_type: Option<&str>,
+    time_range: Option<&SearchTimeRange>,
+) -> Result<(Vec<String>, Option<Vec<String>>), McpError> {
+    if pinned {
+        return Ok((vec![collection_name.to_string()], None));
+    }
+    processing
+        .read_targets(collection_name, memory_type, time_range)
+        .await
+        .map_err(|error| map_processing_error(error.into()))
+}
+
+/// Records in `targets` whose stored `text` contains the trimmed query verbatim, at most
+/// `limit` of them.
+///
+/// The scroll honours the request's filters and scores every match `1.0`.
+async fn exact_text_hits(
+    processing: &ProcessingService,
+    request: &SearchRequest,
+    targets: &[String],
+    limit: usize,
+) -> Result<Vec<SearchHit>, McpError> {
+    let lookup = LookupRequest {
+        project_id: request.project_id.clone(),
+        memory_type: request.memory_type.clone(),
+        tags: request.tags.clone(),
+        include_ids: request.include_ids.clone(),
+        time_range: request.time_range.clone(),
+        limit: Some(limit),
+        require_text: true,
+        text_contains: Some(request.query_text.trim().to_string()),
+        ..LookupRequest::default()
+    };
+    let mut hits = Vec::new();
+    for target in targets {
+        if hits.len() >= limit {
+            break;
+        }
+        let found = processing
+            .lookup_memories(LookupRequest {
+                collection: Some(target.clone()),
+                ..lookup.clone()
+            })
+            .await
+            .map_err(map_search_error)?;
+        hits.extend(found);
+    }
+    hits.truncate(limit);
+    Ok(hits)
+}
+
+/// Put `exact` hits ahead of the vector `hits`, dropping vector duplicates, up to `limit`.
+fn merge_exact_hits(exact: Vec<SearchHit>, hits: Vec<SearchHit>, limit: usize) -> Vec<SearchHit> {
+    let exact_ids: HashSet<String> = exact.iter().map(|hit| hit.id.clone()).collect();
+    let mut merged = exact;
+    merged.extend(hits.into_iter().filter(|hit| !exact_ids.contains(&hit.id)));
+    merged.truncate(limit);
+    merged
+}
+
+/// Run `request` against every collection in `targets`, keeping the best hits overall.
+///
+/// Hits from several collections are merged by score, reordered by `sort`, and cut to the
+/// request limit. No targets yield no hits.
+async fn run_targeted_search(
+    processing: &ProcessingService,
+    request: &SearchRequest,
+    targets: &[String],
+    sort: SearchSort,
+) -> Result<SearchOutcome, McpError> {
+    let mut merged: Option<SearchOutcome> = None;
+    for target in targets {
+        let outcome = run_search(
+            processing,
+            SearchRequest {
+                collection: Some(target.clone()),
+                ..request.clone()
+            },
+        )
+        .await?;
+        match merged.as_mut() {
+            Some(merged) => merged.hits.extend(outcome.hits),
+            None => merged = Some(outcome),
+        }
+    }
+    let mut outcome = merged.unwrap_or_else(|| SearchOutcome {
+        hits: Vec::new(),
+        effective_query: request.query_text.clone(),
+        query_truncated: false,
+        query_compressed: false,
+    });
+    if targets.len() > 1 {
+        outcome.hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+        sort_hits(&mut outcome.hits, sort);
+        if let Some(limit) = request.limit {
+            outcome.hits.truncate(limit);
+        }
+    }
+    Ok(outcome)
+}
+
+/// Key a search by everything that shapes its response: query, filters, tuning, highlights,
+/// explanations, provenance, and the context budget.
+fn search_cache_key(
+    query_text: &str,
+    used_filters: &Map<String, Value>,
+    search_params: Option<&SearchParams>,
+    highlight: bool,
+    explain_hits: bool,
+    include_provenance: bool,
+    context_budget: Option<&ContextBudget>,
+) -> String {
+    json!({
+        "query_text": query_text,
+        "filters": used_filters,
+        "search_params": search_params,
+        "highlight": highlight,
+        "explain_hits": explain_hits,
+        "include_provenance": include_provenance,
+        "max_context_tokens": context_budget.map(|budget| budget.max_tokens),
+        "context_tokenizer": context_budget.and_then(|budget| budget.tokenizer.as_deref()),
+    })
+    .to_string()
+}
+
+/// Replace `context` with the packed one and report the budget it was packed against.
+fn apply_packed_context(payload: &mut Value, packed: PackedContext, tokenizer: &str) {
+    let Some(map) = payload.as_object_mut() else {
+        return;
+    };
+    match packed.context {
+        Some(context) => map.insert("context".into(), Value::String(context)),
+        None => map.remove("context"),
+    };
+    map.insert("context_tokens_used".into(), json!(packed.tokens_used));
+    map.insert("context_tokenizer".into(), json!(tokenizer));
+    map.insert("context_truncated".into(), json!(packed.truncated));
+}
+
+/// Add the `answer_context` fields: the mode, `context_tokens`, and one citation per hit in the
+/// packed context, in context order.
+fn apply_answer_citations(payload: &mut Value, hits: &[SearchHit], packed: &PackedContext) {
+    let Some(map) = payload.as_object_mut() else {
+        return;
+    };
+    let citations: Vec<Value> = packed
+        .cited
+        .iter()
+        .filter_map(|id| hits.iter().find(|hit| &hit.id == id))
+        .map(|hit| {
+            let mut citation = Map::new();
+            citation.insert("marker".into(), json!(format!("[{}]", hit.id)));
+            citation.insert("id".into(), json!(hit.id));
+            citation.insert("score".into(), json!(hit.score));
+            if let Some(source_uri) = hit.source_uri.as_ref() {
+                citation.insert("source_uri".into(), json!(source_uri));
+            }
+            Value::Object(citation)
+        })
+        .collect();
+    map.insert("mode".into(), json!(SearchMode::AnswerContext));
+    map.insert("context_tokens".into(), json!(packed.tokens_used));
+    map.insert("citations".into(), Value::Array(citations));
+}
+
+/// Token budget for the prompt-ready `context`, from `max_context_tokens`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ContextBudget {
+    /// Maximum tokens the joined context may occupy.
+    pub(crate) max_tokens: usize,
+    /// Encoding named by `context_tokenizer`; the embedding model's counter when `None`.
+    pub(crate) tokenizer: Option<String>,
+}
+
+impl ContextBudget {
+    /// Name of the applied tokenizer together with its counter.
+    fn counter(&self) -> Result<(String, TokenCounter), McpError> {
+        let config = get_config();
+        let (name, counter) = match self.tokenizer.as_deref() {
+            Some(name) => (
+                name.to_string(),
+                encoding_counter(name).ok_or_else(|| unsupported_tokenizer(name))?,
+            ),
+            None => (
+                tokenizer_name(&config.embedding_model).to_string(),
+                build_token_counter(
+                    config.embedding_provider,
+                    &config.embedding_model,
+                    config.tokenizer_fallback,
+                ),
+            ),
+        };
+        let counter = counter.map_err(|error| {
+            classified_error(
+                ErrorKind::Configuration,
+                format!("Context tokenizer `{name}` is unavailable: {error}"),
+            )
+        })?;
+        Ok((name, counter))
+    }
+}
 
-    Ok(CallToolResult::structured(payload))
+fn unsupported_tokenizer(name: &str) -> McpError {
+    McpError::invalid_params(
+        format!(
+            "unsupported `context_tokenizer` `{name}`; expected one of: {}",
+            ENCODING_NAMES.join(", ")
+        ),
+        None,
+    )
 }
 
 /// Raw search request payload accepted from MCP clients.
 #[derive(Debug, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub(crate) struct SearchToolRequest {
-    /// Natural language query text to embed.
-    pub(crate) query_text: String,
+    /// Natural language query text to embed; omitted for exact lookups.
+    #[serde(default)]
+    pub(crate) query_text: Option<String>,
+    /// Exact `memory_id` to look up instead of running a vector query.
+    #[serde(default)]
+    pub(crate) memory_id: Option<String>,
+    /// Exact `chunk_hash` to look up instead of running a vector query.
+    #[serde(default)]
+    pub(crate) chunk_hash: Option<String>,
     /// Optional `project_id` filter.
     #[serde(default)]
     pub(crate) project_id: Option<String>,
@@ -106,6 +663,9 @@ pub(crate) struct SearchToolRequest {
     /// Optional tags filter.
     #[serde(default)]
     pub(crate) tags: Option<Vec<String>>,
+    /// Only match these memory ids.
+    #[serde(default)]
+    pub(crate) include_ids: Option<Vec<String>>,
     /// Optional timestamp range filter.
     #[serde(default)]
     pub(crate) time_range: Option<SearchToolTimeRange>,
@@ -115,9 +675,101 @@ pub(crate) struct SearchToolRequest {
     /// Optional score threshold override.
     #[serde(default)]
     pub(crate) score_threshold: Option<f32>,
+    /// Optional HNSW/quantization tuning for this query.
+    #[serde(default)]
+    pub(crate) search_params: Option<SearchParams>,
     /// Optional collection override.
     #[serde(default)]
     pub(crate) collection: Option<String>,
+    /// Return lexical `highlights` offsets for query terms found in each hit's text.
+    #[serde(default)]
+    pub(crate) highlight: Option<bool>,
+    /// Attach an `explanation` of score, rank, and matched filters to each hit.
+    #[serde(default)]
+    pub(crate) explain_hits: Option<bool>,
+    /// Attach `provenance` (collection, write time, embedding model) to each hit.
+    #[serde(default)]
+    pub(crate) include_provenance: Option<bool>,
+    /// Token budget for the assembled `context`.
+    #[serde(default)]
+    pub(crate) max_context_tokens: Option<usize>,
+    /// tiktoken encoding used to count `max_context_tokens`.
+    #[serde(default)]
+    pub(crate) context_tokenizer: Option<String>,
+    /// On zero results, drop `time_range`, then `tags`, and retry.
+    #[serde(default)]
+    pub(crate) auto_relax: Option<bool>,
+    /// Order of the returned hits; relevance by default.
+    #[serde(default)]
+    pub(crate) sort: Option<SearchSort>,
+    /// Only return records that have stored `text`.
+    #[serde(default)]
+    pub(crate) require_text: Option<bool>,
+    /// How an over-long `query_text` is shortened: `truncate` (default) or `llm`.
+    #[serde(default)]
+    pub(crate) query_compression: Option<String>,
+    /// Keep only the best-scoring chunk of each `doc_id`.
+    #[serde(default)]
+    pub(crate) one_per_document: Option<bool>,
+    /// Return records whose text contains `query_text` verbatim ahead of vector hits.
+    #[serde(default)]
+    pub(crate) prefer_exact: Option<bool>,
+    /// Hit fields to return; `id` and `score` are always included.
+    #[serde(default)]
+    pub(crate) fields: Option<Vec<String>>,
+    /// `results` (default) or `answer_context` for budgeted, cited context in score order.
+    #[serde(default)]
+    pub(crate) mode: Option<SearchMode>,
+}
+
+/// Shape of a search response.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum SearchMode {
+    /// Ranked hits, with an unbudgeted `context` unless `max_context_tokens` is given.
+    #[default]
+    Results,
+    /// Hits plus a `context` packed in score order into `max_context_tokens` (or
+    /// `ANSWER_CONTEXT_MAX_TOKENS`), with `citations` and `context_tokens`.
+    AnswerContext,
+}
+
+/// Ordering applied to hits after score-threshold filtering.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum SearchSort {
+    /// Highest similarity first, as returned by Qdrant.
+    #[default]
+    Score,
+    /// Newest `timestamp` first.
+    TimestampDesc,
+    /// Oldest `timestamp` first.
+    TimestampAsc,
+}
+
+impl SearchSort {
+    /// Timestamp ordering to request from Qdrant, if any.
+    fn timestamp_order(self) -> Option<TimestampOrder> {
+        match self {
+            Self::Score => None,
+            Self::TimestampDesc => Some(TimestampOrder::Descending),
+            Self::TimestampAsc => Some(TimestampOrder::Ascending),
+        }
+    }
+}
+
+/// What a validated search matches against.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum SearchTarget {
+    /// Semantic query text ready for embedding.
+    Query(String),
+    /// Exact match on stored identifiers; at least one is set.
+    Lookup {
+        /// Required `memory_id`.
+        memory_id: Option<String>,
+        /// Required `chunk_hash`.
+        chunk_hash: Option<String>,
+    },
 }
 
 /// Timestamp bounds supplied by MCP clients.
@@ -135,22 +787,52 @@ pub(crate) struct SearchToolTimeRange {
 /// Normalized search parameters after validation.
 #[derive(Debug)]
 pub(crate) struct ValidatedSearchInput {
-    /// Query text ready for embedding.
-    pub(crate) query_text: String,
+    /// Query text or exact identifiers to match.
+    pub(crate) target: SearchTarget,
     /// Optional project identifier filter.
     pub(crate) project_id: Option<String>,
     /// Optional memory type filter.
     pub(crate) memory_type: Option<String>,
     /// Optional tag filter.
     pub(crate) tags: Option<Vec<String>>,
-    /// Optional time-range filter retaining the original representation.
+    /// Memory ids the search is restricted to, trimmed and deduplicated.
+    pub(crate) include_ids: Option<Vec<String>>,
+    /// Optional time-range filter with boundaries normalized to UTC RFC3339.
     pub(crate) time_range: Option<SearchToolTimeRange>,
     /// Effective result limit.
     pub(crate) limit: usize,
+    /// Whether the requested limit exceeded `search_max_limit` and was clamped.
+    pub(crate) clamped_limit: bool,
     /// Effective score threshold.
     pub(crate) score_threshold: f32,
+    /// Optional HNSW/quantization tuning for this query.
+    pub(crate) search_params: Option<SearchParams>,
     /// Optional collection override.
     pub(crate) collection: Option<String>,
+    /// Whether to compute lexical highlight spans for each hit.
+    pub(crate) highlight: bool,
+    /// Whether to attach a ranking explanation to each hit.
+    pub(crate) explain_hits: bool,
+    /// Whether to attach write provenance to each hit.
+    pub(crate) include_provenance: bool,
+    /// Token budget the `context` is packed into, if requested.
+    pub(crate) context_budget: Option<ContextBudget>,
+    /// Whether an empty result is retried with fewer filters.
+    pub(crate) auto_relax: bool,
+    /// Order of the returned hits.
+    pub(crate) sort: SearchSort,
+    /// Whether records without stored `text` are excluded.
+    pub(crate) require_text: bool,
+    /// How an over-long query is shortened before embedding.
+    pub(crate) query_compression: QueryCompression,
+    /// Whether hits are collapsed to the best chunk per `doc_id`.
+    pub(crate) one_per_document: bool,
+    /// Whether exact text matches are tried before the vector query.
+    pub(crate) prefer_exact: bool,
+    /// Hit fields to return, when the caller narrowed them.
+    pub(crate) fields: Option<Vec<String>>,
+    /// Response shape.
+    pub(crate) mode: SearchMode,
 }
 
 impl From<SearchToolTimeRange> for SearchTimeRange {
@@ -203,179 +885,292 @@ fn move_alias(map: &mut JsonObject, alias: &str, canonical: &str) {
     }
 }
 
-fn normalize_tags(
-    tags: Option<Vec<String>>,
-    provided: bool,
-) -> Result<Option<Vec<String>>, &'static str> {
-    let Some(mut tags_vec) = tags else {
-        if provided {
-            return Err("`tags` must be an array of non-empty strings");
-        }
-        return Ok(None);
-    };
-
-    let mut normalized = Vec::new();
-    let mut seen = HashSet::new();
-
-    for tag in tags_vec.drain(..) {
-        let trimmed = tag.trim();
-        if trimmed.is_empty() {
-            return Err("`tags` must be an array of non-empty strings");
-        }
-        let prepared = trimmed.to_string();
-        if seen.insert(prepared.clone()) {
-            normalized.push(prepared);
-        }
-    }
-
-    if normalized.is_empty() {
-        return Err("`tags` must be an array of non-empty strings");
-    }
-
-    Ok(Some(normalized))
-}
-
+/// Normalize a client time range to UTC RFC3339 bounds (see [`types::validate_time_range`]).
 fn validate_time_range(
     time_range: Option<SearchToolTimeRange>,
     provided: bool,
 ) -> Result<Option<SearchToolTimeRange>, McpError> {
-    let Some(mut range) = time_range else {
-        return Ok(None);
-    };
-
-    let parse_timestamp = |label: &str, value: &str| -> Result<OffsetDateTime, McpError> {
-        OffsetDateTime::parse(value, &Rfc3339).map_err(|_| {
-            McpError::invalid_params(
-                format!("`{label}` must be a valid RFC3339 timestamp (got '{value}')"),
-                None,
-            )
-        })
-    };
-
-    let mut start_dt = None;
-    if let Some(ref mut start) = range.start {
-        let trimmed = start.trim();
-        if trimmed.is_empty() {
-            return Err(McpError::invalid_params(
-                "`time_range.start` must be a valid RFC3339 timestamp",
-                None,
-            ));
-        }
-        let parsed = parse_timestamp("time_range.start", trimmed)?;
-        *start = trimmed.to_string();
-        start_dt = Some(parsed);
-    }
-
-    let mut end_dt = None;
-    if let Some(ref mut end) = range.end {
-        let trimmed = end.trim();
-        if trimmed.is_empty() {
-            return Err(McpError::invalid_params(
-                "`time_range.end` must be a valid RFC3339 timestamp",
-                None,
-            ));
-        }
-        let parsed = parse_timestamp("time_range.end", trimmed)?;
-        *end = trimmed.to_string();
-        end_dt = Some(parsed);
-    }
-
-    if range.start.is_none() && range.end.is_none() {
-        if provided {
-            return Err(McpError::invalid_params(
-                "`time_range` must include `start`, `end`, or both",
-                None,
-            ));
-        }
-        return Ok(None);
-    }
-
-    if start_dt.zip(end_dt).is_some_and(|(start, end)| start > end) {
-        return Err(McpError::invalid_params(
-            "`time_range.start` must be earlier than or equal to `time_range.end`",
-            None,
-        ));
-    }
-
-    Ok(Some(range))
+    let range = types::validate_time_range(time_range.map(SearchTimeRange::from), provided)
+        .map_err(invalid_search)?;
+    Ok(range.map(|range| SearchToolTimeRange {
+        start: range.start,
+        end: range.end,
+    }))
 }
 
 fn validate_search_request(
     args: SearchToolRequest,
     tags_present: bool,
     time_range_present: bool,
+    require_project_filter: bool,
 ) -> Result<ValidatedSearchInput, McpError> {
     let SearchToolRequest {
         query_text,
+        memory_id,
+        chunk_hash,
         project_id,
         memory_type,
         tags,
+        include_ids,
         time_range,
         limit,
         score_threshold,
+        search_params,
         collection,
+        highlight,
+        explain_hits,
+        include_provenance,
+        max_context_tokens,
+        context_tokenizer,
+        auto_relax,
+        sort,
+        require_text,
+        query_compression,
+        one_per_document,
+        prefer_exact,
+        fields,
+        mode,
     } = args;
 
-    if query_text.trim().is_empty() {
-        return Err(McpError::invalid_params(
-            "`query_text` must not be empty",
-            None,
-        ));
-    }
-
-    let mut memory_type = memory_type;
-    if let Some(ref mut value) = memory_type {
-        let trimmed = value.trim();
-        if trimmed.is_empty() {
+    require_project(require_project_filter, "search", project_id.as_deref())?;
+    let memory_id = validate_lookup_key("memory_id", memory_id)?;
+    let chunk_hash = validate_lookup_key("chunk_hash", chunk_hash)?;
+    let target = if memory_id.is_some() || chunk_hash.is_some() {
+        let vector_params = [
+            ("query_text", query_text.is_some()),
+            ("score_threshold", score_threshold.is_some()),
+            ("search_params", search_params.is_some()),
+            ("highlight", highlight.is_some()),
+            ("explain_hits", explain_hits.is_some()),
+            ("query_compression", query_compression.is_some()),
+            ("auto_relax", auto_relax.is_some()),
+            ("prefer_exact", prefer_exact.is_some()),
+            ("mode", mode == Some(SearchMode::AnswerContext)),
+        ];
+        if let Some((name, _)) = vector_params.iter().find(|(_, present)| *present) {
             return Err(McpError::invalid_params(
-                "`memory_type` must be one of episodic|semantic|procedural",
+                format!(
+                    "`{name}` cannot be combined with `memory_id` or `chunk_hash`; exact lookups do not run a vector query"
+                ),
                 None,
             ));
         }
-        let normalized = trimmed.to_lowercase();
-        if !MEMORY_TYPES.contains(&normalized.as_str()) {
+        SearchTarget::Lookup {
+            memory_id,
+            chunk_hash,
+        }
+    } else {
+        let Some(query_text) = query_text else {
+            return Err(McpError::invalid_params(
+                "`query_text` is required unless `memory_id` or `chunk_hash` is given",
+                None,
+            ));
+        };
+        if query_text.trim().is_empty() {
             return Err(McpError::invalid_params(
-                "`memory_type` must be one of episodic|semantic|procedural",
+                "`query_text` must not be empty",
                 None,
             ));
         }
-        *value = normalized;
-    }
+        SearchTarget::Query(query_text)
+    };
 
-    let tags = normalize_tags(tags, tags_present)
-        .map_err(|message| McpError::invalid_params(message.to_string(), None))?;
+    let memory_type = types::validate_memory_type(memory_type).map_err(invalid_search)?;
+    let tags = types::validate_tags(tags, tags_present).map_err(invalid_search)?;
+    let include_ids = include_ids
+        .map(types::validate_include_ids)
+        .transpose()
+        .map_err(invalid_search)?;
     let time_range = validate_time_range(time_range, time_range_present)?;
 
     let config = get_config();
 
-    if limit.is_some_and(|value| value < 1 || value > config.search_max_limit) {
-        return Err(McpError::invalid_params(
-            format!("`limit` must be between 1 and {}", config.search_max_limit),
-            None,
-        ));
-    }
-    let limit_value = limit.unwrap_or(config.search_default_limit);
+    let (limit_value, clamped_limit) = resolve_limit(
+        limit,
+        config.search_default_limit,
+        config.search_max_limit,
+        config.search_clamp_limit,
+    )?;
 
-    if score_threshold.is_some_and(|threshold| !(0.0..=1.0).contains(&threshold)) {
-        return Err(McpError::invalid_params(
-            "`score_threshold` must be between 0.0 and 1.0",
-            None,
-        ));
-    }
+    types::validate_score_threshold(score_threshold).map_err(invalid_search)?;
     let threshold_value = score_threshold.unwrap_or(config.search_default_score_threshold);
+    if let Some(ref params) = search_params {
+        types::validate_search_params(params).map_err(invalid_search)?;
+    }
+    if let Some(ref name) = collection {
+        check_collection_name("collection", name)?;
+    }
+    let query_compression = parse_query_compression(query_compression.as_deref())?;
+    let mode = mode.unwrap_or_default();
+    let mut context_budget = validate_context_budget(max_context_tokens, context_tokenizer)?;
+    let fields = fields
+        .map(types::validate_search_fields)
+        .transpose()
+        .map_err(invalid_search)?;
+    if mode == SearchMode::AnswerContext {
+        if fields
+            .as_ref()
+            .is_some_and(|fields| !fields.iter().any(|field| field == "text"))
+        {
+            return Err(McpError::invalid_params(
+                "`fields` must include `text` in `answer_context` mode",
+                None,
+            ));
+        }
+        context_budget.get_or_insert(ContextBudget {
+            max_tokens: config.answer_context_max_tokens,
+            tokenizer: None,
+        });
+    }
 
     Ok(ValidatedSearchInput {
-        query_text,
+        target,
         project_id,
         memory_type,
         tags,
+        include_ids,
         time_range,
         limit: limit_value,
+        clamped_limit,
         score_threshold: threshold_value,
+        search_params,
         collection,
+        highlight: highlight.unwrap_or(false),
+        explain_hits: explain_hits.unwrap_or(false),
+        include_provenance: include_provenance.unwrap_or(false),
+        context_budget,
+        auto_relax: auto_relax.unwrap_or(false),
+        sort: sort.unwrap_or_default(),
+        require_text: require_text.unwrap_or(false),
+        query_compression,
+        one_per_document: one_per_document.unwrap_or(false),
+        prefer_exact: prefer_exact.unwrap_or(false),
+        fields,
+        mode,
     })
 }
 
+/// Check `max_context_tokens` and the encoding named by `context_tokenizer`.
+fn validate_context_budget(
+    max_tokens: Option<usize>,
+    tokenizer: Option<String>,
+) -> Result<Option<ContextBudget>, McpError> {
+    let tokenizer = tokenizer.map(|name| name.trim().to_string());
+    if let Some(name) = tokenizer.as_deref()
+        && !ENCODING_NAMES.contains(&name)
+    {
+        return Err(unsupported_tokenizer(name));
+    }
+    match max_tokens {
+        Some(0) => Err(McpError::invalid_params(
+            "`max_context_tokens` must be at least 1",
+            None,
+        )),
+        Some(max_tokens) => Ok(Some(ContextBudget {
+            max_tokens,
+            tokenizer,
+        })),
+        None if tokenizer.is_some() => Err(McpError::invalid_params(
+            "`context_tokenizer` requires `max_context_tokens`",
+            None,
+        )),
+        None => Ok(None),
+    }
+}
+
+/// Interpret `query_compression`; `"llm"` requires a configured summarization model.
+fn parse_query_compression(value: Option<&str>) -> Result<QueryCompression, McpError> {
+    let mode = match value.map(str::trim) {
+        None | Some("truncate") => return Ok(QueryCompression::Truncate),
+        Some("llm") => QueryCompression::Llm,
+        Some(other) => {
+            return Err(McpError::invalid_params(
+                format!("`query_compression` must be \"truncate\" or \"llm\" (got \"{other}\")"),
+                None,
+            ));
+        }
+    };
+    let config = get_config();
+    if matches!(config.summarization_provider, SummarizationProvider::None)
+        || config.summarization_model.is_none()
+    {
+        return Err(McpError::invalid_params(
+            "`query_compression: \"llm\"` requires SUMMARIZATION_PROVIDER and SUMMARIZATION_MODEL to be configured",
+            None,
+        ));
+    }
+    Ok(mode)
+}
+
+/// Re-order hits chronologically when requested; hits without a parseable timestamp go last.
+///
+/// The sort is stable, so hits sharing a timestamp keep their relevance order.
+fn sort_hits(hits: &mut [SearchHit], sort: SearchSort) {
+    let instant = |hit: &SearchHit| {
+        hit.timestamp
+            .as_deref()
+            .and_then(|value| parse_time_boundary(value, TimeBoundary::Start))
+    };
+    match sort {
+        SearchSort::Score => {}
+        SearchSort::TimestampDesc => {
+            hits.sort_by_key(|hit| (instant(hit).is_none(), std::cmp::Reverse(instant(hit))))
+        }
+        SearchSort::TimestampAsc => hits.sort_by_key(|hit| (instant(hit).is_none(), instant(hit))),
+    }
+}
+
+/// Keep only the highest-scoring hit of each `doc_id`; hits without one are all kept.
+///
+/// Retained hits keep their relative order.
+fn keep_best_per_document(hits: &mut Vec<SearchHit>) {
+    let mut best: HashMap<&str, usize> = HashMap::new();
+    for (index, hit) in hits.iter().enumerate() {
+        if let Some(doc_id) = hit.doc_id.as_deref() {
+            best.entry(doc_id)
+                .and_modify(|kept| {
+                    if hit.score > hits[*kept].score {
+                        *kept = index;
+                    }
+                })
+                .or_insert(index);
+        }
+    }
+    let kept: HashSet<usize> = best.into_values().collect();
+    let mut index = 0;
+    hits.retain(|hit| {
+        let keep = hit.doc_id.is_none() || kept.contains(&index);
+        index += 1;
+        keep
+    });
+}
+
+/// Trim an exact-lookup identifier, rejecting blank values.
+fn validate_lookup_key(name: &str, value: Option<String>) -> Result<Option<String>, McpError> {
+    match value {
+        Some(value) if value.trim().is_empty() => Err(McpError::invalid_params(
+            format!("`{name}` must not be empty"),
+            None,
+        )),
+        value => Ok(value.map(|value| value.trim().to_string())),
+    }
+}
+
+/// Resolve the effective limit (see [`types::resolve_limit`]).
+fn resolve_limit(
+    limit: Option<usize>,
+    default: usize,
+    max: usize,
+    clamp: bool,
+) -> Result<(usize, bool), McpError> {
+    types::resolve_limit(limit, default, max, clamp).map_err(invalid_search)
+}
+
+/// Surface a shared validation failure as `invalid_params`.
+fn invalid_search(error: SearchValidationError) -> McpError {
+    McpError::invalid_params(error.to_string(), None)
+}
+
 fn build_used_filters(
     collection: &str,
     limit: usize,
@@ -416,70 +1211,69 @@ fn build_used_filters(
     filters
 }
 
+/// Note in `used_filters` that the requested limit was clamped to `SEARCH_MAX_LIMIT`.
+fn mark_clamped_limit(filters: &mut Map<String, Value>) {
+    filters.insert("clamped_limit".into(), Value::Bool(true));
+}
+
 fn map_search_error(error: SearchError) -> McpError {
-    match error {
-        SearchError::Embedding(source) => {
-            McpError::internal_error(format!("Embedding provider error: {source}"), None)
-        }
-        SearchError::Qdrant(source) => {
-            McpError::internal_error(format!("Qdrant request failed: {source}"), None)
-        }
-        SearchError::DimensionMismatch { expected, actual } => McpError::internal_error(
-            format!(
-                "Embedding dimension mismatch: expected {expected}, got {actual}. Align EMBEDDING_MODEL and EMBEDDING_DIMENSION."
-            ),
-            None,
-        ),
-        SearchError::EmptyEmbedding => McpError::internal_error(
-            "Embedding provider returned no vectors for the query.",
-            None,
+    let kind = error.kind();
+    let message = match error {
+        SearchError::Embedding(source) => format!("Embedding provider error: {source}"),
+        SearchError::Qdrant(source) => format!("Qdrant request failed: {source}"),
+        SearchError::DimensionMismatch { expected, actual } => format!(
+            "Embedding dimension mismatch: expected {expected}, got {actual}. Align EMBEDDING_MODEL and EMBEDDING_DIMENSION."
         ),
-    }
+        SearchError::EmptyEmbedding => {
+            "Embedding provider returned no vectors for the query.".into()
+        }
+    };
+    classified_error(kind, message)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::{CONFIG, Config, EmbeddingProvider, SummarizationProvider};
-    use crate::processing::SearchHit;
+    use crate::config::ensure_test_config;
+    use crate::embedding::{EmbeddingClient, EmbeddingClientError};
+    use crate::mcp::schemas::{
+        OUTPUT_SCHEMA_VERSION, assert_matches_output_schema, search_output_schema,
+    };
+    use crate::qdrant::QdrantService;
+    use async_trait::async_trait;
+    use httpmock::{Method::GET, Method::POST, MockServer};
     use serde_json::Value;
-    use std::sync::Once;
-
-    fn ensure_test_config() {
-        static INIT: Once = Once::new();
-        INIT.call_once(|| {
-            let _ = CONFIG.set(Config {
-                qdrant_url: "http://127.0.0.1:6333".into(),
-                qdrant_collection_name: "rusty-mem".into(),
-                qdrant_api_key: None,
-                embedding_provider: EmbeddingProvider::Ollama,
-                text_splitter_chunk_size: None,
-                text_splitter_chunk_overlap: None,
-                text_splitter_use_safe_defaults: false,
-                embedding_model: "test-model".into(),
-                embedding_dimension: 768,
-                ollama_url: None,
-                server_port: None,
-                search_default_limit: 5,
-                search_max_limit: 50,
-                search_default_score_threshold: 0.25,
-                summarization_provider: SummarizationProvider::Ollama,
-                summarization_model: Some("llama".into()),
-                summarization_max_words: 200,
-            });
-        });
-    }
+    use std::collections::BTreeMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
 
     fn base_search_request() -> SearchToolRequest {
         SearchToolRequest {
-            query_text: "demo".into(),
+            query_text: Some("demo".into()),
+            memory_id: None,
+            chunk_hash: None,
             project_id: None,
             memory_type: None,
             tags: None,
+            include_ids: None,
             time_range: None,
             limit: None,
             score_threshold: None,
+            search_params: None,
             collection: None,
+            highlight: None,
+            explain_hits: None,
+            include_provenance: None,
+            max_context_tokens: None,
+            context_tokenizer: None,
+            auto_relax: None,
+            sort: None,
+            require_text: None,
+            query_compression: None,
+            one_per_document: None,
+            prefer_exact: None,
+            fields: None,
+            mode: None,
         }
     }
 
@@ -496,9 +1290,10 @@ mod tests {
 
         let normalized = normalize_search_arguments(Some(raw));
         let mut request: SearchToolRequest =
-            parse_arguments_value(normalized).expect("normalized arguments parse");
+            parse_arguments_value(normalized, &search_input_schema())
+                .expect("normalized arguments parse");
         request.tags =
-            normalize_tags(request.tags, true).expect("tags normalization should succeed");
+            types::validate_tags(request.tags, true).expect("tags normalization should succeed");
 
         assert_eq!(request.memory_type.as_deref(), Some("episodic"));
         assert_eq!(request.project_id.as_deref(), Some("alpha"));
@@ -506,26 +1301,139 @@ mod tests {
         assert_eq!(request.tags, Some(vec!["docs".into()]));
     }
 
+    #[test]
+    fn search_arguments_reject_typos_with_suggestions() {
+        ensure_test_config();
+        let mut raw = JsonObject::new();
+        raw.insert("query_text".into(), Value::String("demo".into()));
+        raw.insert("score_treshold".into(), Value::from(0.5));
+        let error = parse_arguments_value::<SearchToolRequest>(
+            normalize_search_arguments(Some(raw)),
+            &search_input_schema(),
+        )
+        .unwrap_err();
+        assert_eq!(error.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+        assert!(error.message.contains("`score_treshold`"));
+        assert!(error.message.contains("did you mean `score_threshold`?"));
+    }
+
+    #[test]
+    fn search_arguments_accept_aliases_without_unknown_key_errors() {
+        ensure_test_config();
+        let mut raw = JsonObject::new();
+        raw.insert("query_text".into(), Value::String("demo".into()));
+        raw.insert("type".into(), Value::String("semantic".into()));
+        raw.insert("project".into(), Value::String("alpha".into()));
+        raw.insert("k".into(), Value::from(3));
+        let request: SearchToolRequest = parse_arguments_value(
+            normalize_search_arguments(Some(raw)),
+            &search_input_schema(),
+        )
+        .expect("aliases are accepted");
+        assert_eq!(request.memory_type.as_deref(), Some("semantic"));
+        assert_eq!(request.project_id.as_deref(), Some("alpha"));
+        assert_eq!(request.limit, Some(3));
+    }
+
     #[test]
     fn validate_search_request_rejects_empty_query() {
         ensure_test_config();
         let request = SearchToolRequest {
-            query_text: "   ".into(),
+            query_text: Some("   ".into()),
             ..base_search_request()
         };
-        let error = validate_search_request(request, false, false).unwrap_err();
+        let error = validate_search_request(request, false, false, false).unwrap_err();
         assert_eq!(error.code, rmcp::model::ErrorCode::INVALID_PARAMS);
     }
 
+    #[test]
+    fn validate_search_request_enforces_project_only_when_required() {
+        ensure_test_config();
+        let unscoped = || SearchToolRequest {
+            query_text: Some("deploy steps".into()),
+            ..base_search_request()
+        };
+        assert!(validate_search_request(unscoped(), false, false, false).is_ok());
+
+        let error = validate_search_request(unscoped(), false, false, true).unwrap_err();
+        assert_eq!(error.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+        assert_eq!(error.data.expect("data")["code"], "PROJECT_REQUIRED");
+
+        let scoped = SearchToolRequest {
+            project_id: Some("alpha".into()),
+            ..unscoped()
+        };
+        assert!(validate_search_request(scoped, false, false, true).is_ok());
+    }
+
+    #[test]
+    fn validate_search_request_rejects_lookup_combined_with_vector_params() {
+        ensure_test_config();
+        let request = SearchToolRequest {
+            chunk_hash: Some("abc123".into()),
+            ..base_search_request()
+        };
+        let error = validate_search_request(request, false, false, false).unwrap_err();
+        assert_eq!(error.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+        assert!(error.message.contains("`query_text` cannot be combined"));
+
+        let request = SearchToolRequest {
+            query_text: None,
+            memory_id: Some("memory-1".into()),
+            score_threshold: Some(0.5),
+            ..base_search_request()
+        };
+        let error = validate_search_request(request, false, false, false).unwrap_err();
+        assert!(
+            error
+                .message
+                .contains("`score_threshold` cannot be combined")
+        );
+
+        let request = SearchToolRequest {
+            query_text: None,
+            ..base_search_request()
+        };
+        let error = validate_search_request(request, false, false, false).unwrap_err();
+        assert!(error.message.contains("`query_text` is required"));
+
+        let request = SearchToolRequest {
+            query_text: None,
+            memory_id: Some("memory-1".into()),
+            auto_relax: Some(true),
+            ..base_search_request()
+        };
+        let error = validate_search_request(request, false, false, false).unwrap_err();
+        assert!(error.message.contains("`auto_relax` cannot be combined"));
+    }
+
+    #[test]
+    fn validate_search_request_rejects_unsafe_collection_names() {
+        ensure_test_config();
+        for name in ["../points", "notes/points", "ノート"] {
+            let request = SearchToolRequest {
+                collection: Some(name.into()),
+                ..base_search_request()
+            };
+            let error = validate_search_request(request, false, false, false).unwrap_err();
+            assert_eq!(error.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+            assert!(
+                error.message.contains("Invalid `collection`"),
+                "{}",
+                error.message
+            );
+        }
+    }
+
     #[test]
     fn validate_search_request_rejects_invalid_memory_type() {
         ensure_test_config();
         let request = SearchToolRequest {
-            query_text: "demo".into(),
+            query_text: Some("demo".into()),
             memory_type: Some("invalid".into()),
             ..base_search_request()
         };
-        let error = validate_search_request(request, false, false).unwrap_err();
+        let error = validate_search_request(request, false, false, false).unwrap_err();
         assert_eq!(error.code, rmcp::model::ErrorCode::INVALID_PARAMS);
     }
 
@@ -533,9 +1441,46 @@ mod tests {
     fn validate_search_request_rejects_limit_out_of_bounds() {
         ensure_test_config();
         let mut request = base_search_request();
-        request.query_text = "demo".into();
+        request.query_text = Some("demo".into());
         request.limit = Some(0);
-        let error = validate_search_request(request, false, false).unwrap_err();
+        let error = validate_search_request(request, false, false, false).unwrap_err();
+        assert_eq!(error.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+    }
+
+    #[test]
+    fn resolve_limit_rejects_oversized_values_when_strict() {
+        let error = resolve_limit(Some(1000), 5, 50, false).unwrap_err();
+        assert_eq!(error.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+        assert_eq!(resolve_limit(Some(50), 5, 50, false).unwrap(), (50, false));
+        assert_eq!(resolve_limit(None, 5, 50, false).unwrap(), (5, false));
+    }
+
+    #[test]
+    fn resolve_limit_clamps_oversized_values_when_enabled() {
+        assert_eq!(resolve_limit(Some(1000), 5, 50, true).unwrap(), (50, true));
+        assert_eq!(resolve_limit(Some(20), 5, 50, true).unwrap(), (20, false));
+        let error = resolve_limit(Some(0), 5, 50, true).unwrap_err();
+        assert_eq!(error.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+    }
+
+    #[test]
+    fn used_filters_note_clamped_limit() {
+        let (limit, clamped) = resolve_limit(Some(1000), 5, 50, true).unwrap();
+        let mut filters = build_used_filters("rusty", limit, 0.25, None, None, None, None);
+        assert!(!filters.contains_key("clamped_limit"));
+        if clamped {
+            mark_clamped_limit(&mut filters);
+        }
+        assert_eq!(filters.get("limit").and_then(Value::as_u64), Some(50));
+        assert_eq!(filters.get("clamped_limit"), Some(&Value::Bool(true)));
+    }
+
+    #[test]
+    fn validate_search_request_rejects_oversized_limit_by_default() {
+        ensure_test_config();
+        let mut request = base_search_request();
+        request.limit = Some(1000);
+        let error = validate_search_request(request, false, false, false).unwrap_err();
         assert_eq!(error.code, rmcp::model::ErrorCode::INVALID_PARAMS);
     }
 
@@ -543,19 +1488,137 @@ mod tests {
     fn validate_search_request_rejects_score_threshold_out_of_range() {
         ensure_test_config();
         let mut request = base_search_request();
-        request.query_text = "demo".into();
+        request.query_text = Some("demo".into());
         request.score_threshold = Some(1.5);
-        let error = validate_search_request(request, false, false).unwrap_err();
+        let error = validate_search_request(request, false, false, false).unwrap_err();
         assert_eq!(error.code, rmcp::model::ErrorCode::INVALID_PARAMS);
     }
 
+    #[test]
+    fn search_params_parse_and_reject_invalid_oversampling() {
+        ensure_test_config();
+        let mut raw = JsonObject::new();
+        raw.insert("query_text".into(), Value::String("demo".into()));
+        raw.insert(
+            "search_params".into(),
+            json!({ "hnsw_ef": 128, "quantization": { "rescore": true, "oversampling": 2.0 } }),
+        );
+        let request: SearchToolRequest = parse_arguments_value(
+            normalize_search_arguments(Some(raw.clone())),
+            &search_input_schema(),
+        )
+        .expect("search params parse");
+        let validated =
+            validate_search_request(request, false, false, false).expect("valid params");
+        let params = validated.search_params.expect("params kept");
+        assert_eq!(params.hnsw_ef, Some(128));
+        assert_eq!(
+            params
+                .quantization
+                .and_then(|quantization| quantization.oversampling),
+            Some(2.0)
+        );
+
+        raw.insert(
+            "search_params".into(),
+            json!({ "quantization": { "oversampling": 0.5 } }),
+        );
+        let request: SearchToolRequest = parse_arguments_value(
+            normalize_search_arguments(Some(raw.clone())),
+            &search_input_schema(),
+        )
+        .expect("search params parse");
+        let error = validate_search_request(request, false, false, false).unwrap_err();
+        assert!(error.message.contains("oversampling"), "{}", error.message);
+
+        raw.insert(
+            "search_params".into(),
+            json!({ "quantization": { "oversampl": 2.0 } }),
+        );
+        let error = parse_arguments_value::<SearchToolRequest>(
+            normalize_search_arguments(Some(raw)),
+            &search_input_schema(),
+        )
+        .unwrap_err();
+        assert!(
+            error
+                .message
+                .contains("did you mean `search_params.quantization.oversampling`?"),
+            "{}",
+            error.message
+        );
+    }
+
     #[test]
     fn validate_search_request_rejects_empty_tags() {
         ensure_test_config();
         let mut request = base_search_request();
-        request.query_text = "demo".into();
+        request.query_text = Some("demo".into());
         request.tags = Some(vec![" ".into()]);
-        let error = validate_search_request(request, true, false).unwrap_err();
+        let error = validate_search_request(request, true, false, false).unwrap_err();
+        assert_eq!(error.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+    }
+
+    #[test]
+    fn validate_search_request_checks_context_budget() {
+        ensure_test_config();
+        let mut request = base_search_request();
+        request.query_text = Some("demo".into());
+        request.max_context_tokens = Some(256);
+        request.context_tokenizer = Some("o200k_base".into());
+        let params = validate_search_request(request, false, false, false).unwrap();
+        assert_eq!(
+            params.context_budget,
+            Some(ContextBudget {
+                max_tokens: 256,
+                tokenizer: Some("o200k_base".into()),
+            })
+        );
+
+        let mut request = base_search_request();
+        request.query_text = Some("demo".into());
+        request.max_context_tokens = Some(256);
+        request.context_tokenizer = Some("gpt-4o".into());
+        let error = validate_search_request(request, false, false, false).unwrap_err();
+        assert_eq!(error.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+        assert!(error.message.contains("cl100k_base, o200k_base"));
+
+        let mut request = base_search_request();
+        request.query_text = Some("demo".into());
+        request.context_tokenizer = Some("cl100k_base".into());
+        let error = validate_search_request(request, false, false, false).unwrap_err();
+        assert!(error.message.contains("requires `max_context_tokens`"));
+
+        let mut request = base_search_request();
+        request.query_text = Some("demo".into());
+        request.max_context_tokens = Some(0);
+        assert!(validate_search_request(request, false, false, false).is_err());
+    }
+
+    #[test]
+    fn validate_time_range_normalizes_offsets_and_dates() {
+        let range = SearchToolTimeRange {
+            start: Some("2024-06-01T02:00:00+02:00".into()),
+            end: Some("2024-06-02".into()),
+        };
+        let normalized = validate_time_range(Some(range), true)
+            .expect("valid range")
+            .expect("range present");
+        assert_eq!(normalized.start.as_deref(), Some("2024-06-01T00:00:00Z"));
+        assert_eq!(
+            normalized.end.as_deref(),
+            Some("2024-06-02T23:59:59.999999999Z")
+        );
+    }
+
+    #[test]
+    fn validate_time_range_compares_normalized_instants() {
+        // 01:00+02:00 is 23:00Z the previous day, so it precedes 2024-06-01 start of day.
+        let range = SearchToolTimeRange {
+            start: Some("2024-06-01".into()),
+            end: Some("2024-06-01T01:00:00+02:00".into()),
+        };
+        let error = validate_time_range(Some(range), true).unwrap_err();
         assert_eq!(error.code, rmcp::model::ErrorCode::INVALID_PARAMS);
     }
 
@@ -614,6 +1677,62 @@ mod tests {
         assert!(!time_value.contains_key("end"));
     }
 
+    fn timed_hit(id: &str, score: f32, timestamp: Option<&str>) -> SearchHit {
+        SearchHit {
+            id: id.into(),
+            score,
+            text: None,
+            project_id: None,
+            memory_type: None,
+            tags: None,
+            timestamp: timestamp.map(str::to_string),
+            source_uri: None,
+            doc_id: None,
+            chunk_index: None,
+            ingested_at: None,
+            embedding_model: None,
+            citations: None,
+            links: None,
+            collection: None,
+        }
+    }
+
+    #[test]
+    fn sort_hits_orders_by_timestamp_descending_after_scoring() {
+        let mut hits = vec![
+            timed_hit("oldest", 0.9, Some("2024-01-01T00:00:00Z")),
+            timed_hit("undated", 0.8, None),
+            timed_hit("newest", 0.7, Some("2025-03-01T08:00:00+02:00")),
+            timed_hit("middle", 0.6, Some("2024-06-15T12:00:00Z")),
+        ];
+        sort_hits(&mut hits, SearchSort::TimestampDesc);
+        let ids: Vec<&str> = hits.iter().map(|hit| hit.id.as_str()).collect();
+        assert_eq!(ids, ["newest", "middle", "oldest", "undated"]);
+
+        sort_hits(&mut hits, SearchSort::TimestampAsc);
+        let ids: Vec<&str> = hits.iter().map(|hit| hit.id.as_str()).collect();
+        assert_eq!(ids, ["oldest", "middle", "newest", "undated"]);
+    }
+
+    #[test]
+    fn keep_best_per_document_retains_the_top_chunk_of_each_document() {
+        let chunk = |id: &str, score: f32, doc_id: Option<&str>| SearchHit {
+            doc_id: doc_id.map(str::to_string),
+            ..timed_hit(id, score, None)
+        };
+        let mut hits = vec![
+            chunk("a-1", 0.9, Some("doc-a")),
+            chunk("b-2", 0.8, Some("doc-b")),
+            chunk("a-2", 0.7, Some("doc-a")),
+            chunk("loose", 0.65, None),
+            chunk("b-1", 0.85, Some("doc-b")),
+            chunk("a-3", 0.6, Some("doc-a")),
+        ];
+        keep_best_per_document(&mut hits);
+        let ids: Vec<&str> = hits.iter().map(|hit| hit.id.as_str()).collect();
+        assert_eq!(ids, ["a-1", "loose", "b-1"]);
+    }
+
     #[test]
     fn format_search_hits_builds_context_with_citations() {
         let hit = SearchHit {
@@ -625,10 +1744,264 @@ mod tests {
             tags: None,
             timestamp: None,
             source_uri: None,
+            doc_id: None,
+            chunk_index: None,
+            ingested_at: None,
+            embedding_model: None,
+            citations: None,
+            links: None,
+            collection: None,
         };
-        let (results, context) = format_search_hits(vec![hit]);
+        let (results, context) = format_search_hits(vec![hit], None, None, false);
         assert_eq!(results.len(), 1);
         assert_eq!(context.as_deref(), Some("Example text [chunk-1]"));
+        assert!(results[0].get("highlights").is_none());
+        assert!(results[0].get("explanation").is_none());
+    }
+
+    fn text_hit(id: &str, text: &str) -> SearchHit {
+        SearchHit {
+            text: Some(text.into()),
+            ..timed_hit(id, 0.9, None)
+        }
+    }
+
+    #[test]
+    fn pack_context_counts_tokens_with_the_selected_encoding() {
+        let hits = vec![
+            text_hit("m1", "Rotate the API keys every ninety days"),
+            text_hit(
+                "m2",
+                "La rotation des clés doit être consignée dans le journal d'audit",
+            ),
+            text_hit("m3", "नियमित रूप से कुंजियाँ बदलें"),
+        ];
+        let two = "Rotate the API keys every ninety days [m1]\n\
+                   La rotation des clés doit être consignée dans le journal d'audit [m2]";
+        let three = format!("{two}\nनियमित रूप से कुंजियाँ बदलें [m3]");
+
+        // The Devanagari line is far cheaper under o200k_base, so only it fits all three.
+        let cl100k = encoding_counter("cl100k_base").unwrap().unwrap();
+        let packed = pack_context(&hits, 50, &*cl100k);
+        assert_eq!(packed.context.as_deref(), Some(two));
+        assert_eq!(packed.tokens_used, cl100k(two));
+        assert!(packed.truncated);
+
+        let o200k = encoding_counter("o200k_base").unwrap().unwrap();
+        let packed = pack_context(&hits, 50, &*o200k);
+        assert_eq!(packed.context.as_deref(), Some(three.as_str()));
+        assert_eq!(packed.tokens_used, o200k(&three));
+        assert!(packed.tokens_used <= 50);
+        assert!(!packed.truncated);
+    }
+
+    #[test]
+    fn answer_context_cites_hits_in_score_order_within_the_budget() {
+        // Returned newest first (sort: timestamp_desc), but cited by relevance.
+        let hits = vec![
+            SearchHit {
+                score: 0.41,
+                ..text_hit("recent", "Deploys moved to Fridays")
+            },
+            SearchHit {
+                score: 0.93,
+                ..text_hit("best", "Deploys run through the release pipeline")
+            },
+            SearchHit {
+                score: 0.77,
+                ..text_hit("middle", "Rollback needs the previous image tag")
+            },
+        ];
+        let counter = encoding_counter("cl100k_base").unwrap().unwrap();
+        let two = "Deploys run through the release pipeline [best]\n\
+                   Rollback needs the previous image tag [middle]";
+        let budget = counter(two);
+
+        let packed = pack_answer_context(&hits, budget, &*counter);
+        assert_eq!(packed.context.as_deref(), Some(two));
+        assert!(packed.tokens_used <= budget);
+        assert!(packed.truncated);
+        assert_eq!(packed.cited, vec!["best", "middle"]);
+
+        let mut payload = json!({});
+        apply_answer_citations(&mut payload, &hits, &packed);
+        assert_eq!(payload["context_tokens"], json!(packed.tokens_used));
+        assert_eq!(payload["mode"], "answer_context");
+        let markers: Vec<&str> = payload["citations"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|citation| citation["marker"].as_str().unwrap())
+            .collect();
+        assert_eq!(markers, vec!["[best]", "[middle]"]);
+    }
+
+    #[test]
+    fn answer_context_mode_defaults_the_budget_and_rejects_lookups() {
+        ensure_test_config();
+        let request = SearchToolRequest {
+            mode: Some(SearchMode::AnswerContext),
+            ..base_search_request()
+        };
+        let validated = validate_search_request(request, false, false, false).expect("valid");
+        assert_eq!(validated.mode, SearchMode::AnswerContext);
+        assert_eq!(
+            validated.context_budget.map(|budget| budget.max_tokens),
+            Some(2000)
+        );
+
+        let lookup = SearchToolRequest {
+            query_text: None,
+            memory_id: Some("m-1".into()),
+            mode: Some(SearchMode::AnswerContext),
+            ..base_search_request()
+        };
+        assert!(validate_search_request(lookup, false, false, false).is_err());
+
+        let without_text = SearchToolRequest {
+            mode: Some(SearchMode::AnswerContext),
+            fields: Some(vec!["source_uri".into()]),
+            ..base_search_request()
+        };
+        let error = validate_search_request(without_text, false, false, false).unwrap_err();
+        assert!(
+            error.message.contains("must include `text`"),
+            "{}",
+            error.message
+        );
+    }
+
+    #[test]
+    fn pack_context_stops_at_the_first_hit_that_does_not_fit() {
+        let hits = vec![
+            text_hit("m1", "one two three four five six seven eight nine ten"),
+            text_hit("m2", "short"),
+        ];
+        let counter = encoding_counter("cl100k_base").unwrap().unwrap();
+        let packed = pack_context(&hits, 3, &*counter);
+        assert_eq!(packed.context, None);
+        assert_eq!(packed.tokens_used, 0);
+        assert!(packed.truncated);
+    }
+
+    #[test]
+    fn format_search_hits_adds_provenance_only_when_requested() {
+        let hit = SearchHit {
+            id: "chunk-1".into(),
+            score: 0.42,
+            text: Some("Example text".into()),
+            project_id: None,
+            memory_type: None,
+            tags: None,
+            timestamp: Some("2023-01-01T00:00:00Z".into()),
+            source_uri: None,
+            doc_id: None,
+            chunk_index: None,
+            ingested_at: Some("2025-03-04T05:06:07Z".into()),
+            embedding_model: Some("nomic-embed-text".into()),
+            citations: None,
+            links: None,
+            collection: Some("notes".into()),
+        };
+        let (results, _) = format_search_hits(vec![hit.clone()], None, None, false);
+        assert!(results[0].get("provenance").is_none());
+
+        let (results, _) = format_search_hits(vec![hit.clone()], None, None, true);
+        assert_eq!(
+            results[0]["provenance"],
+            json!({
+                "collection": "notes",
+                "created_at": "2025-03-04T05:06:07Z",
+                "embedding_model": "nomic-embed-text"
+            })
+        );
+
+        let legacy = SearchHit {
+            ingested_at: None,
+            embedding_model: None,
+            ..hit
+        };
+        let (results, _) = format_search_hits(vec![legacy], None, None, true);
+        assert_eq!(results[0]["provenance"], json!({ "collection": "notes" }));
+    }
+
+    #[test]
+    fn format_search_hits_adds_highlight_spans_when_requested() {
+        let hit = SearchHit {
+            id: "chunk-1".into(),
+            score: 0.42,
+            text: Some("Rotate the Qdrant API keys quarterly".into()),
+            project_id: None,
+            memory_type: None,
+            tags: None,
+            timestamp: None,
+            source_uri: None,
+            doc_id: None,
+            chunk_index: None,
+            ingested_at: None,
+            embedding_model: None,
+            citations: None,
+            links: None,
+            collection: None,
+        };
+        let (results, _) =
+            format_search_hits(vec![hit], Some("when to rotate api keys"), None, false);
+        assert_eq!(
+            results[0]["highlights"],
+            json!([
+                { "start": 0, "end": 6 },
+                { "start": 18, "end": 21 },
+                { "start": 22, "end": 26 }
+            ])
+        );
+        assert_eq!(results[0]["text"], "Rotate the Qdrant API keys quarterly");
+    }
+
+    #[test]
+    fn format_search_hits_explains_rank_score_and_matched_filters() {
+        let tagged = |id: &str, score: f32, tags: &[&str]| SearchHit {
+            id: id.into(),
+            score,
+            text: Some(format!("memory {id}")),
+            project_id: Some("alpha".into()),
+            memory_type: Some("procedural".into()),
+            tags: Some(tags.iter().map(|tag| tag.to_string()).collect()),
+            timestamp: None,
+            source_uri: None,
+            doc_id: None,
+            chunk_index: None,
+            ingested_at: None,
+            embedding_model: None,
+            citations: None,
+            links: None,
+            collection: None,
+        };
+        let hits = vec![
+            tagged("first", 0.91, &["deploy", "ops", "qdrant"]),
+            tagged("second", 0.55, &["ops"]),
+        ];
+        let inputs = ExplainInputs {
+            score_threshold: 0.5,
+            project_id: Some("alpha".into()),
+            memory_type: None,
+            tags: Some(vec!["qdrant".into(), "ops".into()]),
+        };
+
+        let (results, _) = format_search_hits(hits, None, Some(&inputs), false);
+        let explanations: Vec<&Value> = results.iter().map(|item| &item["explanation"]).collect();
+        assert_eq!(explanations[0]["raw_score"].as_f64(), Some(0.91f32 as f64));
+        assert_eq!(explanations[0]["threshold"], json!(0.5));
+        assert_eq!(explanations[0]["rank"], 1);
+        assert_eq!(
+            explanations[0]["filter_matches"],
+            json!({ "project": "alpha", "tags_matched": ["qdrant", "ops"] })
+        );
+        assert_eq!(explanations[1]["rank"], 2);
+        assert_eq!(
+            explanations[1]["filter_matches"]["tags_matched"],
+            json!(["ops"])
+        );
+        assert!(explanations[1]["filter_matches"].get("type").is_none());
     }
 
     #[test]
@@ -640,4 +2013,769 @@ mod tests {
         assert_eq!(mapped.code, rmcp::model::ErrorCode::INTERNAL_ERROR);
         assert!(mapped.message.contains("Embedding provider error"));
     }
+
+    #[test]
+    fn map_search_error_flags_retryable_qdrant_failures() {
+        let error = SearchError::Qdrant(crate::qdrant::QdrantError::UnexpectedStatus {
+            status: reqwest::StatusCode::SERVICE_UNAVAILABLE,
+            body: "busy".into(),
+        });
+        let mapped = map_search_error(error);
+        assert!(mapped.message.starts_with("Qdrant request failed"));
+        assert_eq!(mapped.data.expect("data")["retryable"], json!(true));
+    }
+
+    struct CountingEmbedding(Arc<AtomicUsize>);
+
+    #[async_trait]
+    impl EmbeddingClient for CountingEmbedding {
+        async fn generate_embeddings(
+            &self,
+            texts: Vec<String>,
+        ) -> Result<Vec<Vec<f32>>, EmbeddingClientError> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            let dimension = get_config().embedding_dimension;
+            Ok(texts.iter().map(|_| vec![0.1; dimension]).collect())
+        }
+    }
+
+    fn search_args(query: &str) -> Option<JsonObject> {
+        json!({ "query_text": query, "collection": "cache-test" })
+            .as_object()
+            .cloned()
+    }
+
+    #[tokio::test]
+    async fn repeated_search_within_ttl_is_served_from_cache() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        let query = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/cache-test/points/query");
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "time": 0.0,
+                    "result": [{
+                        "id": "memory-1",
+                        "score": 0.9,
+                        "payload": { "text": "cached memory", "project_id": "default" }
+                    }]
+                }));
+            })
+            .await;
+        let embeddings = Arc::new(AtomicUsize::new(0));
+        let processing = Arc::new(ProcessingService::from_parts(
+            Box::new(CountingEmbedding(embeddings.clone())),
+            QdrantService {
+                client: reqwest::Client::new(),
+                base_url: server.base_url(),
+                read_base_url: None,
+                api_key: None,
+                api_keys: BTreeMap::new(),
+                capabilities: Default::default(),
+                #[cfg(feature = "fault-injection")]
+                faults: None,
+            },
+        ));
+        let cache = SearchCache::new(Duration::from_secs(60), 8);
+
+        let first = handle_search(&processing, &cache, search_args("cache me"))
+            .await
+            .expect("first search");
+        let second = handle_search(&processing, &cache, search_args("cache me"))
+            .await
+            .expect("cached search");
+
+        assert_eq!(first.structured_content, second.structured_content);
+        assert_eq!(embeddings.load(Ordering::SeqCst), 1);
+        assert_eq!(query.hits_async().await, 1);
+
+        handle_search(&processing, &cache, search_args("different query"))
+            .await
+            .expect("uncached search");
+        assert_eq!(embeddings.load(Ordering::SeqCst), 2);
+        assert_eq!(query.hits_async().await, 2);
+    }
+
+    fn relax_processing(server: &MockServer) -> Arc<ProcessingService> {
+        Arc::new(ProcessingService::from_parts(
+            Box::new(CountingEmbedding(Arc::new(AtomicUsize::new(0)))),
+            QdrantService {
+                client: reqwest::Client::new(),
+                base_url: server.base_url(),
+                read_base_url: None,
+                api_key: None,
+                api_keys: BTreeMap::new(),
+                capabilities: Default::default(),
+                #[cfg(feature = "fault-injection")]
+                faults: None,
+            },
+        ))
+    }
+
+    fn relax_args(auto_relax: bool) -> Option<JsonObject> {
+        json!({
+            "query_text": "key rotation",
+            "collection": "relax-test",
+            "tags": ["security"],
+            "time_range": { "start": "2025-01-01", "end": "2025-01-02" },
+            "auto_relax": auto_relax
+        })
+        .as_object()
+        .cloned()
+    }
+
+    #[tokio::test]
+    async fn auto_relax_drops_time_range_then_tags_until_something_matches() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        let empty = json!({ "status": "ok", "time": 0.0, "result": [] });
+        let strict = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/relax-test/points/query")
+                    .body_contains(r#""key":"timestamp""#);
+                then.status(200).json_body(empty.clone());
+            })
+            .await;
+        let tags_only = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/relax-test/points/query")
+                    .matches(|request| {
+                        let body =
+                            String::from_utf8_lossy(request.body.as_deref().unwrap_or_default());
+                        body.contains(r#""key":"tags""#) && !body.contains(r#""key":"timestamp""#)
+                    });
+                then.status(200).json_body(empty.clone());
+            })
+            .await;
+        let unfiltered = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/relax-test/points/query")
+                    .matches(|request| {
+                        let body =
+                            String::from_utf8_lossy(request.body.as_deref().unwrap_or_default());
+                        !body.contains(r#""key":"tags""#) && !body.contains(r#""key":"timestamp""#)
+                    });
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "time": 0.0,
+                    "result": [{
+                        "id": "memory-1",
+                        "score": 0.9,
+                        "payload": { "text": "rotate keys quarterly", "project_id": "default" }
+                    }]
+                }));
+            })
+            .await;
+        let processing = relax_processing(&server);
+        let cache = SearchCache::new(Duration::ZERO, 0);
+
+        let result = handle_search(&processing, &cache, relax_args(true))
+            .await
+            .expect("relaxed search");
+        let payload = result.structured_content.expect("payload");
+        assert_eq!(payload["results"][0]["id"], "memory-1");
+        assert_eq!(payload["relaxed_filters"], json!(["time_range", "tags"]));
+        assert_eq!(payload["used_filters"]["auto_relax"], json!(true));
+        assert!(payload["used_filters"].get("tags").is_none());
+        assert!(payload["used_filters"].get("time_range").is_none());
+        assert_eq!(strict.hits_async().await, 1);
+        assert_eq!(tags_only.hits_async().await, 1);
+        assert_eq!(unfiltered.hits_async().await, 1);
+
+        let result = handle_search(&processing, &cache, relax_args(false))
+            .await
+            .expect("strict search");
+        let payload = result.structured_content.expect("payload");
+        assert_eq!(payload["results"], json!([]));
+        assert!(payload.get("relaxed_filters").is_none());
+        assert_eq!(payload["used_filters"]["tags"], json!(["security"]));
+        assert_eq!(strict.hits_async().await, 2);
+        assert_eq!(tags_only.hits_async().await, 1);
+    }
+
+    #[tokio::test]
+    async fn include_ids_restrict_the_query_with_a_has_id_condition() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        let listed = [
+            "6f9619ff-8b86-d011-b42d-00cf4fc964ff",
+            "0b7e7dee-87ac-4e04-a7f4-6d5ef2e1e4a1",
+        ];
+        let hit = |id: &str, score: f64| json!({ "id": id, "score": score, "payload": { "text": format!("note {id}") } });
+        let restricted = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/ids-test/points/query")
+                    .body_contains(format!(r#"{{"has_id":["{}","{}"]}}"#, listed[0], listed[1]));
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "time": 0.0,
+                    "result": [hit(listed[1], 0.8), hit(listed[0], 0.7)]
+                }));
+            })
+            .await;
+        let unrestricted = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/ids-test/points/query")
+                    .matches(|request| {
+                        !String::from_utf8_lossy(request.body.as_deref().unwrap_or_default())
+                            .contains("has_id")
+                    });
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "time": 0.0,
+                    "result": [hit("unlisted", 0.9), hit(listed[1], 0.8)]
+                }));
+            })
+            .await;
+        let processing = relax_processing(&server);
+        let cache = SearchCache::new(Duration::ZERO, 0);
+        let arguments = json!({
+            "query_text": "rollout",
+            "collection": "ids-test",
+            "include_ids": [listed[0].to_uppercase(), listed[1], listed[0]]
+        })
+        .as_object()
+        .cloned();
+
+        let result = handle_search(&processing, &cache, arguments)
+            .await
+            .expect("restricted search");
+
+        let payload = result.structured_content.expect("payload");
+        let ids: Vec<&str> = payload["results"]
+            .as_array()
+            .expect("results")
+            .iter()
+            .filter_map(|hit| hit["id"].as_str())
+            .collect();
+        assert_eq!(ids, [listed[1], listed[0]]);
+        assert_eq!(payload["used_filters"]["include_ids"], json!(listed));
+        assert_eq!(restricted.hits_async().await, 1);
+        assert_eq!(unrestricted.hits_async().await, 0);
+
+        for include_ids in [json!([]), json!(["memory-1"])] {
+            let arguments = json!({ "query_text": "rollout", "include_ids": include_ids })
+                .as_object()
+                .cloned();
+            let error = handle_search(&processing, &cache, arguments)
+                .await
+                .expect_err("invalid include_ids");
+            assert!(error.message.contains("include_ids"), "{}", error.message);
+        }
+    }
+
+    #[tokio::test]
+    async fn auto_relax_stops_at_the_first_step_that_matches() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        let strict = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/relax-test/points/query")
+                    .body_contains(r#""key":"timestamp""#);
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "time": 0.0, "result": [] }));
+            })
+            .await;
+        let tags_only = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/relax-test/points/query")
+                    .matches(|request| {
+                        let body =
+                            String::from_utf8_lossy(request.body.as_deref().unwrap_or_default());
+                        body.contains(r#""key":"tags""#) && !body.contains(r#""key":"timestamp""#)
+                    });
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "time": 0.0,
+                    "result": [{
+                        "id": "memory-2",
+                        "score": 0.8,
+                        "payload": { "text": "older security note", "tags": ["security"] }
+                    }]
+                }));
+            })
+            .await;
+        let processing = relax_processing(&server);
+        let cache = SearchCache::new(Duration::ZERO, 0);
+
+        let result = handle_search(&processing, &cache, relax_args(true))
+            .await
+            .expect("relaxed search");
+        let payload = result.structured_content.expect("payload");
+        assert_eq!(payload["results"][0]["id"], "memory-2");
+        assert_eq!(payload["relaxed_filters"], json!(["time_range"]));
+        assert_eq!(payload["used_filters"]["tags"], json!(["security"]));
+        assert_eq!(strict.hits_async().await, 1);
+        assert_eq!(tags_only.hits_async().await, 1);
+    }
+
+    #[tokio::test]
+    async fn chunk_hash_lookup_scrolls_without_embedding() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        let scroll = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/lookup-test/points/scroll")
+                    .body_contains(r#""key":"chunk_hash","match":{"value":"abc123"}"#);
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "time": 0.0,
+                    "result": {
+                        "points": [{
+                            "id": "memory-7",
+                            "payload": {
+                                "text": "exact record",
+                                "project_id": "default",
+                                "chunk_hash": "abc123"
+                            }
+                        }],
+                        "next_page_offset": null
+                    }
+                }));
+            })
+            .await;
+        let embeddings = Arc::new(AtomicUsize::new(0));
+        let processing = Arc::new(ProcessingService::from_parts(
+            Box::new(CountingEmbedding(embeddings.clone())),
+            QdrantService {
+                client: reqwest::Client::new(),
+                base_url: server.base_url(),
+                read_base_url: None,
+                api_key: None,
+                api_keys: BTreeMap::new(),
+                capabilities: Default::default(),
+                #[cfg(feature = "fault-injection")]
+                faults: None,
+            },
+        ));
+        let cache = SearchCache::new(Duration::from_secs(60), 8);
+        let arguments = json!({ "chunk_hash": " abc123 ", "collection": "lookup-test" })
+            .as_object()
+            .cloned();
+
+        let result = handle_search(&processing, &cache, arguments)
+            .await
+            .expect("lookup");
+
+        let payload = result.structured_content.expect("structured");
+        let results = payload["results"].as_array().expect("results");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["id"], "memory-7");
+        assert_eq!(results[0]["score"], 1.0);
+        assert_eq!(results[0]["text"], "exact record");
+        assert_eq!(payload["used_filters"]["chunk_hash"], "abc123");
+        assert_eq!(embeddings.load(Ordering::SeqCst), 0);
+        assert_eq!(scroll.hits_async().await, 1);
+    }
+
+    #[tokio::test]
+    async fn search_output_matches_declared_schema() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/schema-test/points/query");
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "time": 0.0,
+                    "result": [{
+                        "id": "memory-1",
+                        "score": 0.9,
+                        "payload": {
+                            "text": "Rotate the staging keys quarterly",
+                            "project_id": "default",
+                            "memory_type": "procedural",
+                            "tags": ["security"],
+                            "timestamp": "2025-01-01T00:00:00Z",
+                            "source_uri": "docs/keys.md",
+                            "doc_id": "doc-1",
+                            "chunk_index": 0,
+                            "ingested_at": "2025-01-02T00:00:00Z",
+                            "embedding_model": "test-model",
+                            "citations": [{ "marker": 1, "memory_id": "memory-0" }],
+                            "links": [{ "rel": "refines", "target_memory_id": "memory-0" }]
+                        }
+                    }]
+                }));
+            })
+            .await;
+        let processing = relax_processing(&server);
+        let cache = SearchCache::new(Duration::from_secs(60), 8);
+        let arguments = json!({
+            "query_text": "rotate keys",
+            "collection": "schema-test",
+            "tags": ["security"],
+            "highlight": true,
+            "explain_hits": true,
+            "include_provenance": true,
+            "max_context_tokens": 64,
+            "auto_relax": true
+        })
+        .as_object()
+        .cloned();
+
+        let result = handle_search(&processing, &cache, arguments)
+            .await
+            .expect("search");
+
+        let payload = result.structured_content.expect("structured");
+        assert_eq!(payload["schema_version"], OUTPUT_SCHEMA_VERSION);
+        assert_eq!(payload["results"][0]["links"][0]["rel"], "refines");
+        assert!(payload["context_tokens_used"].as_u64().is_some());
+        assert_matches_output_schema(search_output_schema(), &payload);
+    }
+
+    #[tokio::test]
+    async fn resolved_collection_reports_alias_target() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path("/aliases");
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "time": 0.0,
+                    "result": { "aliases": [
+                        { "alias_name": "live", "collection_name": "docs-v2" },
+                        { "alias_name": "archive", "collection_name": "docs-v1" }
+                    ] }
+                }));
+            })
+            .await;
+        let query = server
+            .mock_async(|when, then| {
+                when.method(POST).path("/collections/live/points/query");
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "time": 0.0,
+                    "result": [{
+                        "id": "memory-1",
+                        "score": 0.9,
+                        "payload": { "text": "Served through the alias" }
+                    }]
+                }));
+            })
+            .await;
+        let processing = relax_processing(&server);
+        let cache = SearchCache::new(Duration::from_secs(60), 8);
+        let arguments = json!({
+            "query_text": "alias",
+            "collection": "live",
+            "include_provenance": true
+        })
+        .as_object()
+        .cloned();
+
+        let result = handle_search(&processing, &cache, arguments)
+            .await
+            .expect("search");
+
+        query.assert_async().await;
+        let payload = result.structured_content.expect("structured");
+        assert_eq!(payload["collection"], "live");
+        assert_eq!(payload["resolved_collection"], "docs-v2");
+        // Provenance names the collection that stores the hit, not the alias that was searched.
+        assert_eq!(payload["results"][0]["provenance"]["collection"], "docs-v2");
+        assert_matches_output_schema(search_output_schema(), &payload);
+    }
+
+    #[tokio::test]
+    async fn partitioned_search_merges_hits_by_score_and_applies_the_limit() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        for (collection, hits) in [
+            ("mem-2026-01", json!([["jan-a", 0.91], ["jan-b", 0.52]])),
+            ("mem-2025-12", json!([["dec-a", 0.87], ["dec-b", 0.74]])),
+        ] {
+            let points: Vec<Value> = hits
+                .as_array()
+                .expect("hits")
+                .iter()
+                .map(|hit| json!({ "id": hit[0], "score": hit[1], "payload": { "text": "note" } }))
+                .collect();
+            server
+                .mock_async(move |when, then| {
+                    when.method(POST)
+                        .path(format!("/collections/{collection}/points/query"));
+                    then.status(200)
+                        .json_body(json!({ "status": "ok", "time": 0.0, "result": points }));
+                })
+                .await;
+        }
+        let processing = relax_processing(&server);
+        let request = SearchRequest {
+            query_text: "deploy".into(),
+            limit: Some(3),
+            ..SearchRequest::default()
+        };
+        let targets = vec!["mem-2026-01".to_string(), "mem-2025-12".to_string()];
+
+        let outcome = run_targeted_search(&processing, &request, &targets, SearchSort::Score)
+            .await
+            .expect("search");
+
+        let ids: Vec<&str> = outcome.hits.iter().map(|hit| hit.id.as_str()).collect();
+        assert_eq!(ids, ["jan-a", "dec-a", "dec-b"]);
+        let (results, _) = format_search_hits(outcome.hits, None, None, true);
+        let stored_in: Vec<&Value> = results
+            .iter()
+            .map(|result| &result["provenance"]["collection"])
+            .collect();
+        assert_eq!(stored_in, ["mem-2026-01", "mem-2025-12", "mem-2025-12"]);
+
+        let none = run_targeted_search(&processing, &request, &[], SearchSort::Score)
+            .await
+            .expect("search");
+        assert!(none.hits.is_empty());
+        assert_eq!(none.effective_query, "deploy");
+    }
+
+    fn exact_scroll(points: Value) -> Value {
+        json!({
+            "status": "ok",
+            "time": 0.0,
+            "result": { "points": points, "next_page_offset": null }
+        })
+    }
+
+    fn exact_args(limit: usize) -> Option<JsonObject> {
+        json!({
+            "query_text": " ERR_QUOTA_42 ",
+            "collection": "exact-test",
+            "limit": limit,
+            "prefer_exact": true
+        })
+        .as_object()
+        .cloned()
+    }
+
+    #[tokio::test]
+    async fn prefer_exact_returns_verbatim_matches_without_a_vector_query() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        let scroll = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/exact-test/points/scroll")
+                    .body_contains(r#""key":"text","match":{"text":"ERR_QUOTA_42"}"#);
+                then.status(200).json_body(exact_scroll(json!([
+                    { "id": "memory-1", "payload": { "text": "Raised ERR_QUOTA_42 on upload" } },
+                    { "id": "memory-2", "payload": { "text": "err quota 42 is unrelated" } },
+                    { "id": "memory-3", "payload": { "text": "ERR_QUOTA_42 means the bucket is full" } }
+                ])));
+            })
+            .await;
+        let query = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/exact-test/points/query");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "time": 0.0, "result": [] }));
+            })
+            .await;
+        let processing = relax_processing(&server);
+        let cache = SearchCache::new(Duration::from_secs(60), 8);
+
+        let result = handle_search(&processing, &cache, exact_args(2))
+            .await
+            .expect("search");
+
+        scroll.assert_async().await;
+        assert_eq!(query.hits_async().await, 0);
+        let payload = result.structured_content.expect("structured");
+        let ids: Vec<&str> = payload["results"]
+            .as_array()
+            .expect("results")
+            .iter()
+            .map(|hit| hit["id"].as_str().expect("id"))
+            .collect();
+        assert_eq!(ids, ["memory-1", "memory-3"]);
+        assert_eq!(payload["results"][0]["score"], json!(1.0));
+        assert_eq!(payload["exact_matches"], json!(2));
+        assert_eq!(payload["used_filters"]["prefer_exact"], json!(true));
+        assert_matches_output_schema(search_output_schema(), &payload);
+    }
+
+    #[tokio::test]
+    async fn prefer_exact_falls_back_to_vector_hits_without_verbatim_matches() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/exact-test/points/scroll");
+                then.status(200).json_body(exact_scroll(json!([
+                    { "id": "memory-2", "payload": { "text": "err quota 42 is unrelated" } }
+                ])));
+            })
+            .await;
+        let query = server
+            .mock_async(|when, then| {
+                when.method(POST).path("/collections/exact-test/points/query");
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "time": 0.0,
+                    "result": [
+                        { "id": "memory-8", "score": 0.82, "payload": { "text": "Quota errors on upload" } },
+                        { "id": "memory-9", "score": 0.64, "payload": { "text": "Bucket limits" } }
+                    ]
+                }));
+            })
+            .await;
+        let processing = relax_processing(&server);
+        let cache = SearchCache::new(Duration::from_secs(60), 8);
+
+        let result = handle_search(&processing, &cache, exact_args(2))
+            .await
+            .expect("search");
+
+        query.assert_async().await;
+        let payload = result.structured_content.expect("structured");
+        let ids: Vec<&str> = payload["results"]
+            .as_array()
+            .expect("results")
+            .iter()
+            .map(|hit| hit["id"].as_str().expect("id"))
+            .collect();
+        assert_eq!(ids, ["memory-8", "memory-9"]);
+        assert_eq!(payload["exact_matches"], json!(0));
+        assert_matches_output_schema(search_output_schema(), &payload);
+    }
+
+    #[tokio::test]
+    async fn fields_projection_fetches_listed_keys_and_hides_sort_helpers() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        let query = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/fields-test/points/query")
+                    .json_body_partial(
+                        r#"{ "with_payload": { "include": ["tags", "timestamp"] } }"#,
+                    );
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "time": 0.0,
+                    "result": [
+                        { "id": "older", "score": 0.75, "payload": { "tags": ["ops"], "timestamp": "2025-01-01T00:00:00Z" } },
+                        { "id": "newer", "score": 0.5, "payload": { "timestamp": "2025-02-01T00:00:00Z" } }
+                    ]
+                }));
+            })
+            .await;
+        let processing = relax_processing(&server);
+        let cache = SearchCache::new(Duration::from_secs(60), 8);
+        let args = json!({
+            "query_text": "deploys",
+            "collection": "fields-test",
+            "sort": "timestamp_desc",
+            "fields": ["id", "score", "tags"]
+        });
+
+        let result = handle_search(&processing, &cache, args.as_object().cloned())
+            .await
+            .expect("search");
+
+        query.assert_async().await;
+        let payload = result.structured_content.expect("structured");
+        assert_eq!(
+            payload["results"],
+            json!([
+                { "id": "newer", "score": 0.5 },
+                { "id": "older", "score": 0.75, "tags": ["ops"] }
+            ])
+        );
+        assert_eq!(
+            payload["used_filters"]["fields"],
+            json!(["id", "score", "tags"])
+        );
+        assert_matches_output_schema(search_output_schema(), &payload);
+    }
+
+    #[test]
+    fn unknown_fields_are_rejected() {
+        ensure_test_config();
+        let mut args = base_search_request();
+        args.fields = Some(vec!["tags".into(), "vector".into()]);
+
+        let error = validate_search_request(args, false, false, false).expect_err("unknown field");
+        assert!(error.message.contains("`fields` entries must be one of"));
+        assert!(error.message.contains("got 'vector'"));
+
+        let mut args = base_search_request();
+        args.fields = Some(vec![" tags ".into(), "tags".into()]);
+        let validated = validate_search_request(args, false, false, false).expect("valid");
+        assert_eq!(validated.fields, Some(vec!["tags".to_string()]));
+    }
+
+    #[test]
+    fn merge_exact_hits_leads_with_exact_matches_and_drops_duplicates() {
+        let exact = vec![timed_hit("exact", 1.0, None), timed_hit("both", 1.0, None)];
+        let vector = vec![
+            timed_hit("both", 0.9, None),
+            timed_hit("near", 0.8, None),
+            timed_hit("far", 0.7, None),
+        ];
+
+        let merged = merge_exact_hits(exact, vector, 3);
+
+        let ids: Vec<&str> = merged.iter().map(|hit| hit.id.as_str()).collect();
+        assert_eq!(ids, ["exact", "both", "near"]);
+        assert_eq!(merged[1].score, 1.0);
+    }
+
+    #[tokio::test]
+    async fn oversized_search_responses_drop_trailing_hits_and_flag_truncation() {
+        ensure_test_config();
+        let max_bytes = get_config().max_response_bytes.expect("test cap");
+        let server = MockServer::start_async().await;
+        let points: Vec<Value> = (0..40)
+            .map(|index| {
+                json!({
+                    "id": format!("memory-{index:02}"),
+                    "score": 0.99 - index as f32 / 100.0,
+                    "payload": { "text": format!("{index:02} {}", "lorem ".repeat(6_000)) }
+                })
+            })
+            .collect();
+        server
+            .mock_async(|when, then| {
+                when.method(POST).path("/collections/big-test/points/query");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "time": 0.0, "result": points }));
+            })
+            .await;
+        let processing = relax_processing(&server);
+        let cache = SearchCache::new(Duration::from_secs(60), 8);
+        let arguments = json!({ "query_text": "lorem", "collection": "big-test", "limit": 40 })
+            .as_object()
+            .cloned();
+
+        let result = handle_search(&processing, &cache, arguments)
+            .await
+            .expect("search");
+
+        assert!(serde_json::to_vec(&result).expect("json").len() <= max_bytes);
+        let payload = result.structured_content.expect("structured");
+        let results = payload["results"].as_array().expect("results");
+        assert!(!results.is_empty() && results.len() < 40);
+        assert_eq!(results[0]["id"], "memory-00");
+        assert_eq!(payload["truncated"], json!(true));
+        assert_eq!(payload["dropped_count"], json!(40 - results.len()));
+        let context = payload["context"].as_str().expect("context");
+        let last_kept = results.last().expect("last")["id"].as_str().expect("id");
+        assert!(context.ends_with(&format!("[{last_kept}]")));
+        assert_matches_output_schema(search_output_schema(), &payload);
+    }
 }
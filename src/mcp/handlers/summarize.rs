@@ -1,11 +1,28 @@
-//! Handler for the `summarize` MCP tool.
+//! Handlers for the `summarize`, `refine-summary`, `find-summaries`, and `check-summaries` MCP
+//! tools.
 
 use std::{collections::HashSet, sync::Arc};
 
 use crate::{
     config::get_config,
-    mcp::{MEMORY_TYPES, format::build_summarize_response, handlers::parse_arguments_value},
-    processing::{ProcessingService, SummarizeError, SummarizeRequest, SummarizeStrategy},
+    mcp::{
+        MEMORY_TYPES,
+        format::build_summarize_response,
+        handlers::{
+            check_collection_name, classified_error, map_processing_error, parse_arguments,
+            parse_arguments_value, require_project, resolve_collection,
+        },
+        schemas::{
+            OUTPUT_SCHEMA_VERSION, check_summaries_input_schema, find_summaries_input_schema,
+            refine_summary_input_schema, summarize_input_schema,
+        },
+    },
+    processing::{
+        ConsolidateMode, ProcessingService, RefineSummaryRequest, SearchTimeRange, SummarizeError,
+        SummarizeRequest, SummarizeStrategy, SummaryQuery,
+        sanitize::{TimeBoundary, format_utc_rfc3339, parse_time_boundary, sanitize_string},
+    },
+    response_limit::{fit_response, mark_truncated},
 };
 use rmcp::{
     ErrorData as McpError,
@@ -13,7 +30,7 @@ use rmcp::{
 };
 use serde::Deserialize;
 use serde_json::{Map, Value, json};
-use time::{OffsetDateTime, format_description::well_known::Rfc3339};
+use time::OffsetDateTime;
 
 /// Handle the `summarize` tool invocation.
 pub(crate) async fn handle_summarize(
@@ -26,8 +43,10 @@ pub(crate) async fn handle_summarize(
         .map(|map| map.contains_key("tags"))
         .unwrap_or(false);
 
-    let args: SummarizeToolRequest = parse_arguments_value(normalized_arguments)?;
-    let params = validate_summarize_request(args, tags_present)?;
+    let args: SummarizeToolRequest =
+        parse_arguments_value(normalized_arguments, &summarize_input_schema())?;
+    let params =
+        validate_summarize_request(args, tags_present, get_config().require_project_filter)?;
     let ValidatedSummarizeInput {
         project_id,
         memory_type,
@@ -39,6 +58,9 @@ pub(crate) async fn handle_summarize(
         model,
         max_words,
         collection,
+        consolidate,
+        label,
+        topic,
     } = params;
 
     let project_id_for_filters = project_id.clone();
@@ -62,6 +84,9 @@ pub(crate) async fn handle_summarize(
         model,
         max_words: Some(max_words),
         collection: collection.clone(),
+        consolidate,
+        label,
+        topic,
     };
 
     let outcome = processing
@@ -80,12 +105,248 @@ pub(crate) async fn handle_summarize(
         strategy,
         provider: provider_for_filters,
         model: model_for_filters,
+        consolidate,
     });
 
     let payload = build_summarize_response(outcome, used_filters);
     Ok(CallToolResult::structured(payload))
 }
 
+/// Handle the `refine-summary` tool, regenerating a stored summary from feedback in place.
+pub(crate) async fn handle_refine_summary(
+    processing: &Arc<ProcessingService>,
+    arguments: Option<JsonObject>,
+) -> Result<CallToolResult, McpError> {
+    let args: RefineSummaryToolRequest =
+        parse_arguments(arguments, &refine_summary_input_schema())?;
+    let collection = resolve_collection(args.collection)?;
+    let memory_id = sanitize_string(Some(args.upserted_memory_id))
+        .ok_or_else(|| McpError::invalid_params("`upserted_memory_id` must not be empty", None))?;
+    let feedback = sanitize_string(Some(args.feedback))
+        .ok_or_else(|| McpError::invalid_params("`feedback` must not be empty", None))?;
+    if args.max_words == Some(0) {
+        return Err(McpError::invalid_params(
+            "`max_words` must be at least 1",
+            None,
+        ));
+    }
+
+    let outcome = processing
+        .refine_summary(RefineSummaryRequest {
+            memory_id,
+            feedback,
+            max_words: args.max_words,
+            model: sanitize_string(args.model),
+            collection: Some(collection.clone()),
+        })
+        .await
+        .map_err(map_summarize_error)?;
+
+    Ok(CallToolResult::structured(json!({
+        "schema_version": OUTPUT_SCHEMA_VERSION,
+        "status": "ok",
+        "collection": collection,
+        "upserted_memory_id": outcome.memory_id,
+        "summary": outcome.summary,
+        "revision": outcome.revision,
+        "citations": outcome.citations,
+        "source_memory_ids": outcome.source_memory_ids,
+        "missing_source_memory_ids": outcome.missing_source_memory_ids,
+        "model": outcome.model,
+        "abstractive_attempts": outcome.abstractive_attempts,
+        "items_summarized": outcome.items_summarized,
+        "items_trimmed": outcome.items_trimmed,
+    })))
+}
+
+/// Request payload for the `refine-summary` tool.
+#[derive(Debug, Deserialize)]
+struct RefineSummaryToolRequest {
+    upserted_memory_id: String,
+    feedback: String,
+    #[serde(default)]
+    max_words: Option<usize>,
+    #[serde(default)]
+    model: Option<String>,
+    #[serde(default)]
+    collection: Option<String>,
+}
+
+/// Handle the `find-summaries` tool, listing stored summaries newest first.
+pub(crate) async fn handle_find_summaries(
+    processing: &Arc<ProcessingService>,
+    arguments: Option<JsonObject>,
+) -> Result<CallToolResult, McpError> {
+    let mut args: FindSummariesRequest =
+        parse_arguments(arguments, &find_summaries_input_schema())?;
+    let collection = resolve_collection(args.collection.take())?;
+    let query = validate_find_summaries_request(args, get_config().require_project_filter)?;
+
+    let summaries = processing
+        .find_summaries(&collection, query)
+        .await
+        .map_err(map_processing_error)?;
+
+    let items: Vec<Value> = summaries
+        .into_iter()
+        .map(|record| {
+            json!({
+                "memoryId": record.memory_id,
+                "summary": record.summary,
+                "projectId": record.project_id,
+                "label": record.label,
+                "topic": record.topic,
+                "timestamp": record.timestamp,
+                "summaryKey": record.summary_key,
+                "sourceMemoryIds": record.source_memory_ids,
+            })
+        })
+        .collect();
+    let max_bytes = get_config().max_response_bytes;
+    Ok(fit_response(&items, max_bytes, |kept, dropped| {
+        let mut payload = json!({
+            "status": "ok",
+            "collection": collection,
+            "count": kept.len(),
+            "summaries": kept,
+        });
+        mark_truncated(&mut payload, dropped);
+        CallToolResult::structured(payload)
+    }))
+}
+
+/// Handle the `check-summaries` tool, reporting summaries whose source memories were deleted.
+pub(crate) async fn handle_check_summaries(
+    processing: &Arc<ProcessingService>,
+    arguments: Option<JsonObject>,
+) -> Result<CallToolResult, McpError> {
+    let args: CheckSummariesRequest = parse_arguments(arguments, &check_summaries_input_schema())?;
+    let collection = resolve_collection(args.collection)?;
+    let project_id = sanitize_string(args.project_id);
+
+    let outcome = processing
+        .check_summaries(
+            &collection,
+            project_id.clone(),
+            args.tag_orphans.unwrap_or(false),
+        )
+        .await
+        .map_err(map_processing_error)?;
+
+    let orphaned: Vec<Value> = outcome
+        .orphaned
+        .iter()
+        .map(|summary| {
+            json!({
+                "memoryId": summary.memory_id,
+                "projectId": summary.project_id,
+                "status": if summary.is_partial() { "partial" } else { "orphaned" },
+                "sourceCount": summary.source_count,
+                "missingSourceMemoryIds": summary.missing_source_memory_ids,
+            })
+        })
+        .collect();
+    Ok(CallToolResult::structured(json!({
+        "status": "ok",
+        "collection": collection,
+        "projectId": project_id,
+        "checked": outcome.checked,
+        "orphanedCount": orphaned.len(),
+        "danglingReferences": outcome.dangling_references,
+        "tagged": outcome.tagged,
+        "orphaned": orphaned,
+    })))
+}
+
+/// Request payload for the `check-summaries` tool.
+#[derive(Debug, Deserialize)]
+struct CheckSummariesRequest {
+    #[serde(default)]
+    collection: Option<String>,
+    #[serde(default)]
+    project_id: Option<String>,
+    #[serde(default)]
+    tag_orphans: Option<bool>,
+}
+
+/// Request payload for the `find-summaries` tool.
+#[derive(Debug, Deserialize)]
+struct FindSummariesRequest {
+    #[serde(default)]
+    collection: Option<String>,
+    #[serde(default)]
+    project_id: Option<String>,
+    #[serde(default)]
+    label: Option<String>,
+    #[serde(default)]
+    topic: Option<String>,
+    #[serde(default)]
+    time_range: Option<SummarizeToolTimeRange>,
+    #[serde(default)]
+    limit: Option<usize>,
+}
+
+fn validate_find_summaries_request(
+    query: FindSummariesRequest,
+    require_project_filter: bool,
+) -> Result<SummaryQuery, McpError> {
+    require_project(
+        require_project_filter,
+        "find-summaries",
+        query.project_id.as_deref(),
+    )?;
+    let config = get_config();
+    let limit = query.limit.unwrap_or(config.search_default_limit);
+    if limit == 0 || limit > config.search_max_limit {
+        return Err(McpError::invalid_params(
+            format!("`limit` must be between 1 and {}", config.search_max_limit),
+            None,
+        ));
+    }
+
+    let time_range = match query.time_range {
+        Some(SummarizeToolTimeRange { start, end }) => {
+            let parse = |label: &str, value: Option<String>, boundary: TimeBoundary| {
+                sanitize_string(value)
+                    .map(|value| {
+                        parse_time_boundary(&value, boundary).ok_or_else(|| {
+                            McpError::invalid_params(
+                                format!(
+                                    "`{label}` must be a valid RFC3339 timestamp or YYYY-MM-DD date (got '{value}')"
+                                ),
+                                None,
+                            )
+                        })
+                    })
+                    .transpose()
+            };
+            let start = parse("time_range.start", start, TimeBoundary::Start)?;
+            let end = parse("time_range.end", end, TimeBoundary::End)?;
+            if let (Some(start), Some(end)) = (start, end)
+                && start > end
+            {
+                return Err(McpError::invalid_params(
+                    "`time_range.start` must be earlier than or equal to `time_range.end`",
+                    None,
+                ));
+            }
+            (start.is_some() || end.is_some()).then(|| SearchTimeRange {
+                start: start.map(format_utc_rfc3339),
+                end: end.map(format_utc_rfc3339),
+            })
+        }
+        None => None,
+    };
+
+    Ok(SummaryQuery {
+        project_id: sanitize_string(query.project_id),
+        label: validate_summary_field("label", query.label)?,
+        topic: validate_summary_field("topic", query.topic)?,
+        time_range,
+        limit,
+    })
+}
+
 /// Raw request payload accepted from MCP clients.
 #[derive(Debug, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -111,6 +372,14 @@ struct SummarizeToolRequest {
     _score_threshold: Option<f32>,
     #[serde(default)]
     collection: Option<String>,
+    #[serde(default)]
+    consolidate: Option<bool>,
+    #[serde(default)]
+    consolidate_mode: Option<String>,
+    #[serde(default)]
+    label: Option<String>,
+    #[serde(default)]
+    topic: Option<String>,
 }
 
 /// Timestamp bounds supplied by the tool request.
@@ -136,6 +405,9 @@ struct ValidatedSummarizeInput {
     model: Option<String>,
     max_words: usize,
     collection: Option<String>,
+    consolidate: Option<ConsolidateMode>,
+    label: Option<String>,
+    topic: Option<String>,
 }
 
 struct SummarizeFilterContext {
@@ -149,6 +421,7 @@ struct SummarizeFilterContext {
     strategy: SummarizeStrategy,
     provider: Option<String>,
     model: Option<String>,
+    consolidate: Option<ConsolidateMode>,
 }
 
 fn normalize_summarize_arguments(arguments: Option<JsonObject>) -> Value {
@@ -176,6 +449,7 @@ fn normalize_summarize_arguments(arguments: Option<JsonObject>) -> Value {
 fn validate_summarize_request(
     args: SummarizeToolRequest,
     tags_present: bool,
+    require_project_filter: bool,
 ) -> Result<ValidatedSummarizeInput, McpError> {
     let SummarizeToolRequest {
         mut project_id,
@@ -189,8 +463,13 @@ fn validate_summarize_request(
         max_words,
         _score_threshold,
         collection,
+        consolidate,
+        consolidate_mode,
+        label,
+        topic,
     } = args;
 
+    require_project(require_project_filter, "summarize", project_id.as_deref())?;
     if let Some(ref mut project) = project_id {
         let trimmed = project.trim();
         if trimmed.is_empty() {
@@ -265,6 +544,27 @@ fn validate_summarize_request(
         }
     }
 
+    if let Some(ref name) = collection {
+        check_collection_name("collection", name)?;
+    }
+
+    let consolidate_mode = match consolidate_mode
+        .map(|value| value.trim().to_lowercase())
+        .as_deref()
+    {
+        None | Some("archive") => ConsolidateMode::Archive,
+        Some("delete") => ConsolidateMode::Delete,
+        Some(other) => {
+            return Err(McpError::invalid_params(
+                format!("`consolidate_mode` must be archive|delete (got '{other}')"),
+                None,
+            ));
+        }
+    };
+    let consolidate = consolidate.unwrap_or(false).then_some(consolidate_mode);
+    let label = validate_summary_field("label", label)?;
+    let topic = validate_summary_field("topic", topic)?;
+
     Ok(ValidatedSummarizeInput {
         project_id,
         memory_type,
@@ -276,9 +576,23 @@ fn validate_summarize_request(
         model,
         max_words,
         collection,
+        consolidate,
+        label,
+        topic,
     })
 }
 
+/// Trim an optional `label`/`topic`, rejecting values that are blank once trimmed.
+fn validate_summary_field(name: &str, value: Option<String>) -> Result<Option<String>, McpError> {
+    match value {
+        Some(value) if value.trim().is_empty() => Err(McpError::invalid_params(
+            format!("`{name}` must not be empty"),
+            None,
+        )),
+        value => Ok(value.map(|value| value.trim().to_string())),
+    }
+}
+
 fn normalize_tags(
     tags: Option<Vec<String>>,
     provided: bool,
@@ -316,39 +630,35 @@ fn normalize_tags(
 fn validate_time_range(range: SummarizeToolTimeRange) -> Result<SummarizeToolTimeRange, McpError> {
     let SummarizeToolTimeRange { mut start, mut end } = range;
 
-    let parse_timestamp = |label: &str, value: &str| -> Result<String, McpError> {
-        OffsetDateTime::parse(value, &Rfc3339).map_err(|_| {
+    let parse_timestamp =
+        |label: &str, value: &str, boundary: TimeBoundary| -> Result<OffsetDateTime, McpError> {
+            parse_time_boundary(value, boundary).ok_or_else(|| {
             McpError::invalid_params(
-                format!("`{label}` must be a valid RFC3339 timestamp (got '{value}')"),
+                format!(
+                    "`{label}` must be a valid RFC3339 timestamp or YYYY-MM-DD date (got '{value}')"
+                ),
                 None,
             )
-        })?;
-        Ok(value.trim().to_string())
-    };
+        })
+        };
 
-    match (start.as_mut(), end.as_mut()) {
-        (Some(start_value), Some(end_value)) => {
-            *start_value = parse_timestamp("time_range.start", start_value.trim())?;
-            *end_value = parse_timestamp("time_range.end", end_value.trim())?;
-        }
-        _ => {
-            return Err(McpError::invalid_params(
-                "`time_range` must include both `start` and `end`",
-                None,
-            ));
-        }
-    }
+    let (Some(start_value), Some(end_value)) = (start.as_mut(), end.as_mut()) else {
+        return Err(McpError::invalid_params(
+            "`time_range` must include both `start` and `end`",
+            None,
+        ));
+    };
 
-    if let (Some(start), Some(end)) = (&start, &end) {
-        let start_dt = OffsetDateTime::parse(start, &Rfc3339).unwrap();
-        let end_dt = OffsetDateTime::parse(end, &Rfc3339).unwrap();
-        if start_dt > end_dt {
-            return Err(McpError::invalid_params(
-                "`time_range.start` must be earlier than or equal to `time_range.end`",
-                None,
-            ));
-        }
+    let start_dt = parse_timestamp("time_range.start", start_value, TimeBoundary::Start)?;
+    let end_dt = parse_timestamp("time_range.end", end_value, TimeBoundary::End)?;
+    if start_dt > end_dt {
+        return Err(McpError::invalid_params(
+            "`time_range.start` must be earlier than or equal to `time_range.end`",
+            None,
+        ));
     }
+    *start_value = format_utc_rfc3339(start_dt);
+    *end_value = format_utc_rfc3339(end_dt);
 
     Ok(SummarizeToolTimeRange { start, end })
 }
@@ -365,6 +675,7 @@ fn build_used_filters(context: SummarizeFilterContext) -> Map<String, Value> {
         strategy,
         provider,
         model,
+        consolidate,
     } = context;
 
     let mut filters = Map::new();
@@ -404,26 +715,30 @@ fn build_used_filters(context: SummarizeFilterContext) -> Map<String, Value> {
     if let Some(model_value) = model {
         filters.insert("model".into(), Value::String(model_value));
     }
+    if let Some(mode) = consolidate {
+        filters.insert("consolidate".into(), Value::String(mode.as_str().into()));
+    }
 
     filters
 }
 
 fn map_summarize_error(error: SummarizeError) -> McpError {
-    match error {
-        SummarizeError::GenerationFailed(message) => McpError::internal_error(message, None),
-        SummarizeError::EmptyResult => {
-            McpError::invalid_params("No episodic memories found for the requested scope", None)
-        }
+    let kind = error.kind();
+    tracing::debug!(kind = kind.as_str(), retryable = error.is_retryable(), error = %error, "Summarization failed");
+    let message = match error {
+        SummarizeError::GenerationFailed(message) => message,
+        SummarizeError::EmptyResult => "No episodic memories found for the requested scope".into(),
         SummarizeError::InvalidTimeRange => {
-            McpError::invalid_params("`time_range` must include both `start` and `end`", None)
-        }
-        SummarizeError::Embedding(source) => {
-            McpError::internal_error(format!("Embedding provider error: {source}"), None)
-        }
-        SummarizeError::Qdrant(source) => {
-            McpError::internal_error(format!("Qdrant request failed: {source}"), None)
+            "`time_range` must include both `start` and `end`".into()
         }
-    }
+        SummarizeError::Embedding(source) => format!("Embedding provider error: {source}"),
+        SummarizeError::Qdrant(source) => format!("Qdrant request failed: {source}"),
+        error @ (SummarizeError::SummaryNotFound(_)
+        | SummarizeError::NotASummary(_)
+        | SummarizeError::ProviderNotConfigured
+        | SummarizeError::ProviderUnavailable) => error.to_string(),
+    };
+    classified_error(kind, message)
 }
 
 fn strategy_to_string(strategy: SummarizeStrategy) -> &'static str {
@@ -446,36 +761,19 @@ impl From<SummarizeToolTimeRange> for crate::processing::SearchTimeRange {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::{CONFIG, Config, EmbeddingProvider, SummarizationProvider};
-    use std::sync::Once;
-
-    fn ensure_test_config() {
-        static INIT: Once = Once::new();
-        INIT.call_once(|| {
-            let _ = CONFIG.set(Config {
-                qdrant_url: "http://127.0.0.1:6333".into(),
-                qdrant_collection_name: "rusty-mem".into(),
-                qdrant_api_key: None,
-                embedding_provider: EmbeddingProvider::Ollama,
-                text_splitter_chunk_size: None,
-                text_splitter_chunk_overlap: None,
-                text_splitter_use_safe_defaults: false,
-                embedding_model: "test-model".into(),
-                embedding_dimension: 768,
-                ollama_url: None,
-                server_port: None,
-                search_default_limit: 5,
-                search_max_limit: 50,
-                search_default_score_threshold: 0.25,
-                summarization_provider: SummarizationProvider::Ollama,
-                summarization_model: Some("llama".into()),
-                summarization_max_words: 200,
-            });
-        });
-    }
+    use crate::config::ensure_test_config;
+    use crate::embedding::AiLibClient;
+    use crate::mcp::schemas::{assert_matches_output_schema, summarize_output_schema};
+    use crate::qdrant::QdrantService;
+    use httpmock::{
+        Method::{GET, POST, PUT},
+        MockServer,
+    };
+    use std::collections::BTreeMap;
 
     #[test]
     fn normalize_arguments_converts_scalar_tags() {
+        ensure_test_config();
         let mut args = JsonObject::new();
         args.insert("tags".into(), Value::String("daily".into()));
         args.insert(
@@ -483,10 +781,30 @@ mod tests {
             json!({ "start": "2025-01-01T00:00:00Z", "end": "2025-01-02T00:00:00Z" }),
         );
         let value = normalize_summarize_arguments(Some(args));
-        let request: SummarizeToolRequest = parse_arguments_value(value).expect("deserialize");
+        let request: SummarizeToolRequest =
+            parse_arguments_value(value, &summarize_input_schema()).expect("deserialize");
         assert_eq!(request.tags, Some(vec!["daily".into()]));
     }
 
+    #[test]
+    fn summarize_arguments_reject_typos_with_suggestions() {
+        ensure_test_config();
+        let value = json!({
+            "time_range": { "start": "2025-01-01T00:00:00Z", "end": "2025-01-02T00:00:00Z" },
+            "max_word": 100
+        });
+        let error = parse_arguments_value::<SummarizeToolRequest>(value, &summarize_input_schema())
+            .unwrap_err();
+        assert!(error.message.contains("did you mean `max_words`?"));
+
+        let value = json!({
+            "time_range": { "start": "2025-01-01T00:00:00Z", "ends": "2025-01-02T00:00:00Z" }
+        });
+        let error = parse_arguments_value::<SummarizeToolRequest>(value, &summarize_input_schema())
+            .unwrap_err();
+        assert!(error.message.contains("did you mean `time_range.end`?"));
+    }
+
     #[test]
     fn validate_summarize_request_honors_defaults() {
         ensure_test_config();
@@ -505,9 +823,13 @@ mod tests {
             max_words: Some(180),
             _score_threshold: None,
             collection: Some("workspace".into()),
+            consolidate: None,
+            consolidate_mode: None,
+            label: None,
+            topic: None,
         };
 
-        let validated = validate_summarize_request(request, true).expect("validated");
+        let validated = validate_summarize_request(request, true, false).expect("validated");
         assert_eq!(validated.project_id.as_deref(), Some("default"));
         assert_eq!(validated.memory_type.as_deref(), Some("episodic"));
         assert_eq!(validated.limit, 20);
@@ -533,10 +855,72 @@ mod tests {
             max_words: None,
             _score_threshold: None,
             collection: None,
+            consolidate: None,
+            consolidate_mode: None,
+            label: None,
+            topic: None,
+        };
+
+        let error = validate_summarize_request(request, false, false).unwrap_err();
+        assert_eq!(error.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+    }
+
+    #[test]
+    fn validate_summarize_request_enforces_project_only_when_required() {
+        ensure_test_config();
+        let request = |value: Value| -> SummarizeToolRequest {
+            parse_arguments_value(value, &summarize_input_schema()).expect("deserialize")
         };
+        let time_range = json!({ "start": "2025-01-01T00:00:00Z", "end": "2025-01-02T00:00:00Z" });
+        let unscoped = || request(json!({ "time_range": time_range }));
+
+        let validated = validate_summarize_request(unscoped(), false, false).expect("optional");
+        assert_eq!(validated.project_id, None);
 
-        let error = validate_summarize_request(request, false).unwrap_err();
+        let error = validate_summarize_request(unscoped(), false, true).unwrap_err();
         assert_eq!(error.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+        assert_eq!(error.data.expect("data")["code"], "PROJECT_REQUIRED");
+
+        let scoped = request(json!({ "time_range": time_range, "project_id": "alpha" }));
+        let validated = validate_summarize_request(scoped, false, true).expect("scoped");
+        assert_eq!(validated.project_id.as_deref(), Some("alpha"));
+    }
+
+    #[test]
+    fn validate_find_summaries_request_enforces_project_only_when_required() {
+        ensure_test_config();
+        let request = |value: Value| {
+            parse_arguments_value::<FindSummariesRequest>(value, &find_summaries_input_schema())
+                .expect("deserialize")
+        };
+
+        assert!(validate_find_summaries_request(request(json!({})), false).is_ok());
+        let error = validate_find_summaries_request(request(json!({ "label": "weekly" })), true)
+            .unwrap_err();
+        assert_eq!(error.data.expect("data")["code"], "PROJECT_REQUIRED");
+        let query =
+            validate_find_summaries_request(request(json!({ "project_id": "alpha" })), true)
+                .expect("scoped");
+        assert_eq!(query.project_id.as_deref(), Some("alpha"));
+    }
+
+    #[test]
+    fn validate_summarize_request_rejects_unsafe_collection_names() {
+        ensure_test_config();
+        for name in ["../../admin", "naïve"] {
+            let value = json!({
+                "time_range": { "start": "2025-01-01T00:00:00Z", "end": "2025-01-02T00:00:00Z" },
+                "collection": name
+            });
+            let request: SummarizeToolRequest =
+                parse_arguments_value(value, &summarize_input_schema()).expect("deserialize");
+            let error = validate_summarize_request(request, false, false).unwrap_err();
+            assert!(
+                error.message.contains("Invalid `collection`"),
+                "{}",
+                error.message
+            );
+        }
     }
 
     #[test]
@@ -557,9 +941,172 @@ mod tests {
             max_words: None,
             _score_threshold: None,
             collection: None,
+            consolidate: None,
+            consolidate_mode: None,
+            label: None,
+            topic: None,
         };
 
-        let error = validate_summarize_request(request, false).unwrap_err();
+        let error = validate_summarize_request(request, false, false).unwrap_err();
         assert_eq!(error.code, rmcp::model::ErrorCode::INVALID_PARAMS);
     }
+
+    #[test]
+    fn validate_summarize_request_resolves_consolidate_mode() {
+        ensure_test_config();
+        let request = |consolidate: Option<bool>, mode: Option<&str>| {
+            let value = json!({
+                "time_range": { "start": "2025-01-01T00:00:00Z", "end": "2025-01-02T00:00:00Z" },
+                "consolidate": consolidate,
+                "consolidate_mode": mode,
+            });
+            parse_arguments_value::<SummarizeToolRequest>(value, &summarize_input_schema())
+                .expect("deserialize")
+        };
+
+        let validated =
+            validate_summarize_request(request(Some(true), None), false, false).unwrap();
+        assert_eq!(validated.consolidate, Some(ConsolidateMode::Archive));
+        let validated =
+            validate_summarize_request(request(Some(true), Some("Delete")), false, false).unwrap();
+        assert_eq!(validated.consolidate, Some(ConsolidateMode::Delete));
+        let validated =
+            validate_summarize_request(request(None, Some("delete")), false, false).unwrap();
+        assert_eq!(validated.consolidate, None);
+        let error = validate_summarize_request(request(Some(true), Some("purge")), false, false)
+            .unwrap_err();
+        assert_eq!(error.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+    }
+
+    #[test]
+    fn validate_time_range_normalizes_to_utc() {
+        let range = SummarizeToolTimeRange {
+            start: Some("2025-01-01".into()),
+            end: Some("2025-01-07T12:00:00-05:00".into()),
+        };
+        let normalized = validate_time_range(range).expect("valid range");
+        assert_eq!(normalized.start.as_deref(), Some("2025-01-01T00:00:00Z"));
+        assert_eq!(normalized.end.as_deref(), Some("2025-01-07T17:00:00Z"));
+    }
+
+    #[test]
+    fn validate_find_summaries_request_normalizes_filters() {
+        ensure_test_config();
+        let request = |value: Value| {
+            parse_arguments_value::<FindSummariesRequest>(value, &find_summaries_input_schema())
+                .expect("deserialize")
+        };
+
+        let query = validate_find_summaries_request(
+            request(json!({
+                "project_id": " demo ",
+                "label": " weekly-review ",
+                "time_range": { "start": "2025-01-01" },
+            })),
+            false,
+        )
+        .expect("valid query");
+        assert_eq!(query.project_id.as_deref(), Some("demo"));
+        assert_eq!(query.label.as_deref(), Some("weekly-review"));
+        assert_eq!(query.topic, None);
+        assert_eq!(query.limit, 5);
+        let range = query.time_range.expect("time range");
+        assert_eq!(range.start.as_deref(), Some("2025-01-01T00:00:00Z"));
+        assert_eq!(range.end, None);
+
+        for invalid in [
+            json!({ "label": "  " }),
+            json!({ "limit": 0 }),
+            json!({ "time_range": { "start": "2025-02-01", "end": "2025-01-01" } }),
+        ] {
+            let error = validate_find_summaries_request(request(invalid), false).unwrap_err();
+            assert_eq!(error.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+        }
+    }
+
+    #[tokio::test]
+    async fn summarize_output_matches_declared_schema() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/scroll")
+                    .body_contains("episodic");
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": {
+                        "points": [
+                            { "id": "ep-1", "payload": {
+                                "text": "Fixed the login bug",
+                                "timestamp": "2025-01-01T09:00:00Z"
+                            } },
+                            { "id": "ep-2", "payload": {
+                                "text": "Shipped the release",
+                                "timestamp": "2025-01-01T17:00:00Z"
+                            } }
+                        ],
+                        "next_page_offset": null
+                    }
+                }));
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/scroll")
+                    .body_contains("summary:");
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": { "points": [], "next_page_offset": null }
+                }));
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path("/collections/demo");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+        for path in ["/collections/demo/index", "/collections/demo/points"] {
+            server
+                .mock_async(|when, then| {
+                    when.method(PUT).path(path);
+                    then.status(200)
+                        .json_body(json!({ "status": "ok", "result": {} }));
+                })
+                .await;
+        }
+        let processing = Arc::new(ProcessingService::from_parts(
+            Box::new(AiLibClient::new()),
+            QdrantService {
+                client: reqwest::Client::new(),
+                base_url: server.base_url(),
+                read_base_url: None,
+                api_key: None,
+                api_keys: BTreeMap::new(),
+                capabilities: Default::default(),
+                #[cfg(feature = "fault-injection")]
+                faults: None,
+            },
+        ));
+        let arguments = json!({
+            "collection": "demo",
+            "memory_type": "episodic",
+            "time_range": { "start": "2025-01-01T00:00:00Z", "end": "2025-01-02T00:00:00Z" },
+            "strategy": "extractive",
+            "label": "release day"
+        })
+        .as_object()
+        .cloned();
+
+        let result = handle_summarize(&processing, arguments)
+            .await
+            .expect("summary");
+
+        let payload = result.structured_content.expect("structured");
+        assert_eq!(payload["source_memory_ids"], json!(["ep-1", "ep-2"]));
+        assert_matches_output_schema(summarize_output_schema(), &payload);
+    }
 }
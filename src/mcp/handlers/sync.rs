@@ -0,0 +1,85 @@
+//! Handler for the sync-state tool.
+
+use std::sync::Arc;
+
+use crate::{
+    mcp::schemas::sync_state_input_schema,
+    processing::{ProcessingService, sanitize::sanitize_string},
+};
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, JsonObject},
+};
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use super::parse_arguments;
+
+/// Operation requested from the `sync-state` tool.
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum SyncStateAction {
+    /// Report the recorded cursors.
+    #[default]
+    List,
+    /// Forget matching cursors so their sources are re-indexed.
+    Reset,
+}
+
+/// Request payload for the `sync-state` tool.
+#[derive(Debug, Deserialize)]
+pub(crate) struct SyncStateRequest {
+    /// Whether to list or reset cursors.
+    #[serde(default)]
+    pub(crate) action: SyncStateAction,
+    /// Limit the operation to one project.
+    #[serde(default)]
+    pub(crate) project_id: Option<String>,
+    /// Limit the operation to one source.
+    #[serde(default)]
+    pub(crate) source_uri: Option<String>,
+}
+
+/// Handle the `sync-state` tool, listing or resetting the ingest cursors kept under `STATE_DIR`.
+///
+/// When `STATE_DIR` is unset the response reports `enabled: false` instead of failing, so clients
+/// can probe whether incremental syncing is available.
+pub(crate) async fn handle_sync_state(
+    processing: &Arc<ProcessingService>,
+    arguments: Option<JsonObject>,
+) -> Result<CallToolResult, McpError> {
+    let args: SyncStateRequest = parse_arguments(arguments, &sync_state_input_schema())?;
+    let project_id = sanitize_string(args.project_id);
+    let source_uri = sanitize_string(args.source_uri);
+
+    let payload = match args.action {
+        SyncStateAction::List => {
+            let entries = processing.sync_entries(project_id.as_deref());
+            let rendered: Vec<Value> = entries
+                .iter()
+                .flatten()
+                .filter(|entry| {
+                    source_uri
+                        .as_deref()
+                        .is_none_or(|source| entry.source_uri == source)
+                })
+                .map(|entry| {
+                    json!({
+                        "projectId": entry.project_id,
+                        "sourceUri": entry.source_uri,
+                        "collection": entry.collection,
+                        "contentHash": entry.content_hash,
+                        "indexedAt": entry.indexed_at,
+                    })
+                })
+                .collect();
+            json!({ "enabled": entries.is_some(), "entries": rendered })
+        }
+        SyncStateAction::Reset => {
+            let removed =
+                processing.reset_sync_entries(project_id.as_deref(), source_uri.as_deref());
+            json!({ "enabled": removed.is_some(), "reset": removed.unwrap_or(0) })
+        }
+    };
+    Ok(CallToolResult::structured(payload))
+}
@@ -0,0 +1,115 @@
+//! Lexical match highlighting for `search` results.
+//!
+//! Highlights approximate why a hit matched by locating query terms in the returned text. They
+//! are computed locally over each hit's `text`, so semantic matches without shared words simply
+//! carry no spans.
+
+use std::collections::HashSet;
+
+/// Shortest query term that also matches longer words it prefixes (`deploy` → `deployed`).
+const MIN_PREFIX_TERM_CHARS: usize = 4;
+
+/// Query words too common to be worth highlighting.
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "for", "from", "how", "in", "is", "it", "of",
+    "on", "or", "that", "the", "to", "was", "what", "when", "where", "which", "with",
+];
+
+/// Half-open span `[start, end)` of a highlighted token, in characters of the hit text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct HighlightSpan {
+    /// Character offset of the first highlighted character.
+    pub(crate) start: usize,
+    /// Character offset just past the last highlighted character.
+    pub(crate) end: usize,
+}
+
+/// Lowercased, deduplicated query terms used for matching.
+pub(crate) fn query_terms(query: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+    tokens(query)
+        .map(|(_, _, token)| token.to_lowercase())
+        .filter(|term| !STOP_WORDS.contains(&term.as_str()))
+        .filter(|term| seen.insert(term.clone()))
+        .collect()
+}
+
+/// Locate every token in `text` that equals a query term, or starts with a long enough one.
+pub(crate) fn highlight_spans(text: &str, terms: &[String]) -> Vec<HighlightSpan> {
+    if terms.is_empty() {
+        return Vec::new();
+    }
+    tokens(text)
+        .filter(|(_, _, token)| {
+            let token = token.to_lowercase();
+            terms.iter().any(|term| {
+                token == *term
+                    || (term.chars().count() >= MIN_PREFIX_TERM_CHARS && token.starts_with(term))
+            })
+        })
+        .map(|(start, end, _)| HighlightSpan { start, end })
+        .collect()
+}
+
+/// Alphanumeric runs of `text` with their character offsets.
+fn tokens(text: &str) -> impl Iterator<Item = (usize, usize, &str)> {
+    let mut chars = text.char_indices().enumerate().peekable();
+    std::iter::from_fn(move || {
+        while chars
+            .peek()
+            .is_some_and(|(_, (_, ch))| !ch.is_alphanumeric())
+        {
+            chars.next();
+        }
+        let (start, (start_byte, _)) = chars.next()?;
+        let mut end = start + 1;
+        let mut end_byte = text.len();
+        while let Some(&(index, (byte, ch))) = chars.peek() {
+            if !ch.is_alphanumeric() {
+                end_byte = byte;
+                break;
+            }
+            end = index + 1;
+            chars.next();
+        }
+        Some((start, end, &text[start_byte..end_byte]))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spans(text: &str, query: &str) -> Vec<(usize, usize)> {
+        highlight_spans(text, &query_terms(query))
+            .into_iter()
+            .map(|span| (span.start, span.end))
+            .collect()
+    }
+
+    #[test]
+    fn marks_each_query_term_in_the_hit_text() {
+        let text = "Deployed the Qdrant cluster; qdrant restarts are slow.";
+        assert_eq!(
+            spans(text, "how is Qdrant deploy done"),
+            vec![(0, 8), (13, 19), (29, 35)]
+        );
+        assert_eq!(&text[13..19], "Qdrant");
+    }
+
+    #[test]
+    fn short_terms_only_match_whole_tokens() {
+        assert_eq!(spans("api apiary rapid", "API"), vec![(0, 3)]);
+    }
+
+    #[test]
+    fn stop_words_and_empty_queries_produce_no_spans() {
+        assert!(spans("the cat and the hat", "the and").is_empty());
+        assert!(spans("anything", "").is_empty());
+    }
+
+    #[test]
+    fn offsets_count_characters_not_bytes() {
+        assert_eq!(spans("café résumé", "résumé"), vec![(5, 11)]);
+    }
+}
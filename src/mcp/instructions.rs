@@ -0,0 +1,174 @@
+//! `initialize` instructions generated from the registered tools and resources.
+//!
+//! The text is assembled once at startup from [`Tool`] and [`Resource`] metadata, so a tool that
+//! is registered always appears in it. Tools follow the curated [`TOOL_FLOW`] order; each line
+//! carries the first sentence of the tool's description and, for the core tools, a short call
+//! example. When the text would exceed [`MAX_INSTRUCTIONS_CHARS`], the examples are dropped
+//! first and then every description, so tool names are always kept.
+
+use rmcp::model::{Resource, Tool};
+
+/// Upper bound on the generated instructions, in characters.
+pub(crate) const MAX_INSTRUCTIONS_CHARS: usize = 4000;
+
+const INTRO: &str = "Use this server to index, search, and summarize project memories for agents. Push source text once, then retrieve concise context with filtered semantic search instead of pasting documents into prompts.";
+
+/// Order in which tools are presented, from the everyday flow to maintenance.
+pub(crate) const TOOL_FLOW: &[&str] = &[
+    "get-collections",
+    "push",
+    "search",
+    "get-document",
+    "summarize",
+    "refine-summary",
+    "find-summaries",
+    "link-memories",
+    "get-links",
+    "new-collection",
+    "describe-collection",
+    "apply-manifest",
+    "move-to-collection",
+    "rename-collection",
+    "prune",
+    "check-summaries",
+    "verify-collection",
+    "reembed-fallback",
+    "compare-providers",
+    "promote-provider",
+    "warm-collection",
+    "estimate-storage",
+    "storage-stats",
+    "sync-state",
+    "metrics",
+    "ping",
+];
+
+/// Short call examples shown next to the core tools.
+const TOOL_EXAMPLES: &[(&str, &str)] = &[
+    (
+        "push",
+        "push({ text, project_id, memory_type: \"semantic\", tags })",
+    ),
+    ("search", "search({ query_text, project_id, time_range })"),
+    (
+        "summarize",
+        "summarize({ project_id, time_range, max_words })",
+    ),
+];
+
+/// Level of detail kept while fitting the instructions under the cap.
+#[derive(Clone, Copy)]
+enum Detail {
+    Full,
+    NoExamples,
+    Compact,
+}
+
+/// Assemble the instructions for `tools` and `resources`.
+pub(crate) fn build_instructions(tools: &[Tool], resources: &[Resource]) -> String {
+    [Detail::Full, Detail::NoExamples, Detail::Compact]
+        .into_iter()
+        .map(|detail| render(tools, resources, detail))
+        .find(|text| text.chars().count() <= MAX_INSTRUCTIONS_CHARS)
+        .unwrap_or_else(|| render(tools, resources, Detail::Compact))
+}
+
+fn render(tools: &[Tool], resources: &[Resource], detail: Detail) -> String {
+    let mut ordered: Vec<&Tool> = tools.iter().collect();
+    ordered.sort_by_key(|tool| {
+        TOOL_FLOW
+            .iter()
+            .position(|name| *name == tool.name)
+            .unwrap_or(TOOL_FLOW.len())
+    });
+
+    let mut text = format!("{INTRO}\n\nTools, in the usual order:");
+    for (index, tool) in ordered.iter().enumerate() {
+        text.push_str(&format!("\n{}. {}", index + 1, tool.name));
+        if matches!(detail, Detail::Compact) {
+            continue;
+        }
+        if let Some(summary) = tool.description.as_deref().map(first_sentence) {
+            text.push_str(&format!(" - {summary}"));
+        }
+        if matches!(detail, Detail::Full)
+            && let Some((_, example)) = TOOL_EXAMPLES.iter().find(|(name, _)| *name == tool.name)
+        {
+            text.push_str(&format!(" e.g. {example}"));
+        }
+    }
+
+    text.push_str("\n\nResources:");
+    for resource in resources {
+        text.push_str(&format!("\n- {}", resource.uri));
+        if let Some(description) = resource
+            .description
+            .as_deref()
+            .filter(|_| !matches!(detail, Detail::Compact))
+        {
+            text.push_str(&format!(" - {description}"));
+        }
+    }
+    text
+}
+
+/// Text up to and including the first sentence-ending period.
+fn first_sentence(description: &str) -> &str {
+    description
+        .match_indices(". ")
+        .next()
+        .map_or(description, |(end, _)| &description[..=end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rmcp::model::{AnnotateAble, RawResource};
+    use serde_json::Map;
+    use std::{borrow::Cow, sync::Arc};
+
+    fn tool(name: &'static str, description: impl Into<Cow<'static, str>>) -> Tool {
+        Tool::new(name, description, Arc::new(Map::new()))
+    }
+
+    #[test]
+    fn tools_follow_the_curated_flow_with_unknown_tools_last() {
+        let tools = [
+            tool("ping", "Cheap liveness check."),
+            tool("brand-new", "Something added later. With more detail."),
+            tool("search", "Retrieve memories."),
+            tool("push", "Store source text."),
+        ];
+        let mut resource = RawResource::new("mcp://usage", "usage");
+        resource.description = Some("Recommended tool flow".into());
+
+        let text = build_instructions(&tools, &[resource.no_annotation()]);
+
+        let push = text
+            .find("1. push - Store source text. e.g. push(")
+            .expect("push");
+        let search = text.find("2. search").expect("search");
+        let ping = text.find("3. ping").expect("ping");
+        let added = text
+            .find("4. brand-new - Something added later.\n")
+            .expect("unlisted tools keep their first sentence");
+        assert!(push < search && search < ping && ping < added);
+        assert!(text.ends_with("- mcp://usage - Recommended tool flow"));
+    }
+
+    #[test]
+    fn oversized_instructions_drop_details_but_keep_every_tool() {
+        let tools: Vec<Tool> = TOOL_FLOW
+            .iter()
+            .map(|name| tool(name, "word ".repeat(200)))
+            .collect();
+
+        let text = build_instructions(&tools, &[]);
+
+        assert!(text.chars().count() <= MAX_INSTRUCTIONS_CHARS);
+        assert!(!text.contains("word"));
+        for name in TOOL_FLOW {
+            assert!(text.contains(&format!(". {name}\n")), "{name} missing");
+        }
+    }
+}
@@ -0,0 +1,193 @@
+//! Global limit on concurrently executing MCP tool calls.
+//!
+//! At most `MCP_MAX_CONCURRENT_TOOLS` calls run at once and up to `MCP_MAX_QUEUED_TOOLS` more
+//! wait for a slot in arrival order. Calls beyond that fail immediately with a retryable
+//! `server_busy` error, so a burst from one agent cannot stack unbounded work on the embedding
+//! provider and Qdrant.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use serde::Serialize;
+use tokio::sync::{Semaphore, SemaphorePermit, TryAcquireError};
+
+use crate::config::get_config;
+
+/// Semaphore-backed gate with counters for running and waiting calls.
+pub(crate) struct ToolLimiter {
+    permits: Semaphore,
+    max_concurrent: usize,
+    max_queued: usize,
+    in_flight: AtomicUsize,
+    queued: AtomicUsize,
+}
+
+/// Point-in-time view of the gate, reported by the `metrics` tool and `mcp://health`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LimiterSnapshot {
+    /// Calls currently executing.
+    pub(crate) in_flight: usize,
+    /// Calls waiting for a slot.
+    pub(crate) queued: usize,
+    /// Configured concurrency limit.
+    pub(crate) max_concurrent: usize,
+    /// Configured queue depth.
+    pub(crate) max_queued: usize,
+}
+
+/// Slot held for the duration of one tool call; dropping it admits the next queued call.
+pub(crate) struct ToolPermit<'a> {
+    _permit: SemaphorePermit<'a>,
+    in_flight: &'a AtomicUsize,
+}
+
+impl Drop for ToolPermit<'_> {
+    fn drop(&mut self) {
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Queue position released when a waiting call is admitted or cancelled.
+struct QueueSlot<'a>(&'a AtomicUsize);
+
+impl Drop for QueueSlot<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl ToolLimiter {
+    /// Allow `max_concurrent` running calls (at least one) and `max_queued` waiting ones.
+    pub(crate) fn new(max_concurrent: usize, max_queued: usize) -> Self {
+        let max_concurrent = max_concurrent.max(1);
+        Self {
+            permits: Semaphore::new(max_concurrent),
+            max_concurrent,
+            max_queued,
+            in_flight: AtomicUsize::new(0),
+            queued: AtomicUsize::new(0),
+        }
+    }
+
+    /// Build the gate described by `MCP_MAX_CONCURRENT_TOOLS` and `MCP_MAX_QUEUED_TOOLS`.
+    pub(crate) fn from_config() -> Self {
+        let config = get_config();
+        Self::new(config.mcp_max_concurrent_tools, config.mcp_max_queued_tools)
+    }
+
+    /// Wait for a slot, or return `None` at once when the queue is already full.
+    pub(crate) async fn acquire(&self) -> Option<ToolPermit<'_>> {
+        let permit = match self.permits.try_acquire() {
+            Ok(permit) => permit,
+            Err(TryAcquireError::NoPermits) => {
+                self.queued
+                    .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |queued| {
+                        (queued < self.max_queued).then_some(queued + 1)
+                    })
+                    .ok()?;
+                let _slot = QueueSlot(&self.queued);
+                self.permits.acquire().await.ok()?
+            }
+            Err(TryAcquireError::Closed) => return None,
+        };
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        Some(ToolPermit {
+            _permit: permit,
+            in_flight: &self.in_flight,
+        })
+    }
+
+    /// Current running and waiting counts alongside the configured limits.
+    pub(crate) fn snapshot(&self) -> LimiterSnapshot {
+        LimiterSnapshot {
+            in_flight: self.in_flight.load(Ordering::SeqCst),
+            queued: self.queued.load(Ordering::SeqCst),
+            max_concurrent: self.max_concurrent,
+            max_queued: self.max_queued,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{sync::Arc, time::Duration};
+    use tokio::sync::oneshot;
+
+    /// Tool call that holds its slot until `release` fires; reports `false` when rejected.
+    fn spawn_tool(
+        limiter: &Arc<ToolLimiter>,
+        release: oneshot::Receiver<()>,
+    ) -> tokio::task::JoinHandle<bool> {
+        let limiter = limiter.clone();
+        tokio::spawn(async move {
+            let Some(_permit) = limiter.acquire().await else {
+                return false;
+            };
+            let _ = release.await;
+            true
+        })
+    }
+
+    async fn wait_for(limiter: &ToolLimiter, in_flight: usize, queued: usize) {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                let snapshot = limiter.snapshot();
+                if snapshot.in_flight == in_flight && snapshot.queued == queued {
+                    return;
+                }
+                tokio::task::yield_now().await;
+            }
+        })
+        .await
+        .unwrap_or_else(|_| panic!("expected {in_flight} in flight and {queued} queued"));
+    }
+
+    #[tokio::test]
+    async fn ninth_call_queues_and_calls_past_the_queue_are_rejected() {
+        let limiter = Arc::new(ToolLimiter::new(8, 2));
+        let mut releases = Vec::new();
+        let mut calls = Vec::new();
+        for _ in 0..10 {
+            let (release, wait) = oneshot::channel();
+            releases.push(release);
+            calls.push(spawn_tool(&limiter, wait));
+            tokio::task::yield_now().await;
+        }
+        wait_for(&limiter, 8, 2).await;
+
+        let (_release, wait) = oneshot::channel();
+        let rejected = spawn_tool(&limiter, wait);
+        assert!(!rejected.await.expect("rejected call"));
+        wait_for(&limiter, 8, 2).await;
+
+        releases.remove(0).send(()).expect("first call running");
+        wait_for(&limiter, 8, 1).await;
+
+        for release in releases {
+            let _ = release.send(());
+        }
+        for call in calls {
+            assert!(call.await.expect("admitted call"));
+        }
+        assert_eq!(
+            limiter.snapshot(),
+            LimiterSnapshot {
+                in_flight: 0,
+                queued: 0,
+                max_concurrent: 8,
+                max_queued: 2,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn cancelled_waiters_release_their_queue_position() {
+        let limiter = ToolLimiter::new(1, 1);
+        let _running = limiter.acquire().await.expect("slot");
+        let waiting = tokio::time::timeout(Duration::from_millis(10), limiter.acquire()).await;
+        assert!(waiting.is_err());
+        assert_eq!(limiter.snapshot().queued, 0);
+        assert_eq!(limiter.snapshot().in_flight, 1);
+    }
+}
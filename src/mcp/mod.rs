@@ -10,8 +10,12 @@
 //! Handlers, schemas, and formatting helpers are kept in focused submodules to make tests and
 //! reviews small and targeted.
 
+mod cache;
 mod format;
 pub mod handlers;
+mod highlight;
+mod instructions;
+mod limiter;
 mod schemas;
 mod server;
 
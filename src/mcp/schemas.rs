@@ -1,6 +1,13 @@
 //! JSON schema builders for MCP tools.
 
-use crate::config::get_config;
+use crate::{
+    config::get_config,
+    processing::{
+        MAX_INCLUDE_IDS, SEARCH_HIT_FIELDS,
+        chunking::ENCODING_NAMES,
+        migration::{DEFAULT_COMPARE_K, DEFAULT_COMPARE_SAMPLE, RECENT_QUERY_CAPACITY},
+    },
+};
 use serde_json::{Map, Value, json};
 
 /// Build the schema describing the `push` tool input.
@@ -62,6 +69,31 @@ pub(crate) fn index_input_schema() -> Map<String, Value> {
     );
     properties.insert("source_uri".into(), Value::Object(source_schema));
 
+    properties.insert(
+        "auto_classify".into(),
+        json!({
+            "description": "Classify each chunk as episodic/semantic/procedural, overriding `memory_type` per chunk. `true` or \"heuristic\" uses built-in rules; \"llm\" asks the summarization model.",
+            "oneOf": [
+                { "type": "boolean" },
+                { "type": "string", "enum": ["heuristic", "llm"] }
+            ]
+        }),
+    );
+    properties.insert(
+        "timestamp".into(),
+        string_schema(
+            "Optional RFC3339 timestamp (or YYYY-MM-DD) to record instead of now, for backdated notes; time_range filters use it",
+        ),
+    );
+    properties.insert(
+        "allow_future".into(),
+        json!({
+            "type": "boolean",
+            "description": "Accept a `timestamp` more than a few minutes in the future",
+            "default": false
+        }),
+    );
+
     finalize_object_schema(properties, &["text"])
 }
 
@@ -76,10 +108,391 @@ pub(crate) fn create_collection_input_schema() -> Map<String, Value> {
         Value::String("Vector dimension (defaults to EMBEDDING_DIMENSION)".into()),
     );
     properties.insert("vector_size".into(), Value::Object(vector_schema));
+    for (key, description) in [
+        (
+            "shard_number",
+            "Shard count for clustered Qdrant (defaults to QDRANT_SHARD_NUMBER)",
+        ),
+        (
+            "replication_factor",
+            "Copies of each shard (defaults to QDRANT_REPLICATION_FACTOR)",
+        ),
+    ] {
+        let mut topology_schema = Map::new();
+        topology_schema.insert("type".into(), Value::String("integer".into()));
+        topology_schema.insert("minimum".into(), Value::Number(1.into()));
+        topology_schema.insert("description".into(), Value::String(description.into()));
+        properties.insert(key.into(), Value::Object(topology_schema));
+    }
 
     finalize_object_schema(properties, &["name"])
 }
 
+/// Build the schema describing the `apply-manifest` tool input.
+pub(crate) fn apply_manifest_input_schema() -> Map<String, Value> {
+    let mut properties = Map::new();
+    properties.insert(
+        "manifest".into(),
+        string_schema(
+            "TOML manifest with [[collections]] entries (defaults to the COLLECTION_MANIFEST file)",
+        ),
+    );
+    finalize_object_schema(properties, &[])
+}
+
+/// Build the schema describing the `reembed-fallback` tool input.
+pub(crate) fn reembed_fallback_input_schema() -> Map<String, Value> {
+    let mut properties = Map::new();
+    properties.insert(
+        "collection".into(),
+        string_schema("Optional collection override"),
+    );
+    finalize_object_schema(properties, &[])
+}
+
+/// Build the schema describing the `compare-providers` tool input.
+pub(crate) fn compare_providers_input_schema() -> Map<String, Value> {
+    let mut properties = Map::new();
+    properties.insert(
+        "collection".into(),
+        string_schema("Collection to compare (defaults to the configured collection)"),
+    );
+    properties.insert(
+        "queries".into(),
+        json!({
+            "type": "array",
+            "description": "Queries to compare; defaults to the most recent searches since the migration window opened",
+            "items": { "type": "string", "minLength": 1 },
+            "minItems": 1,
+            "maxItems": RECENT_QUERY_CAPACITY
+        }),
+    );
+    properties.insert(
+        "sample".into(),
+        json!({
+            "type": "integer",
+            "description": "Recent searches to compare when `queries` is omitted",
+            "minimum": 1,
+            "maximum": RECENT_QUERY_CAPACITY,
+            "default": DEFAULT_COMPARE_SAMPLE
+        }),
+    );
+    properties.insert(
+        "k".into(),
+        json!({
+            "type": "integer",
+            "description": "Hits compared per query for overlap@k",
+            "minimum": 1,
+            "maximum": 100,
+            "default": DEFAULT_COMPARE_K
+        }),
+    );
+    finalize_object_schema(properties, &[])
+}
+
+/// Build the schema describing the `promote-provider` tool input.
+pub(crate) fn promote_provider_input_schema() -> Map<String, Value> {
+    let mut properties = Map::new();
+    properties.insert(
+        "collection".into(),
+        string_schema("Collection to promote (defaults to the configured collection)"),
+    );
+    properties.insert(
+        "force".into(),
+        json!({
+            "type": "boolean",
+            "description": "Promote even when the candidate collection holds fewer points than the active one",
+            "default": false
+        }),
+    );
+    properties.insert(
+        "drop_previous".into(),
+        json!({
+            "type": "boolean",
+            "description": "Allow deleting the active collection when it is not an alias, so an alias can take its name",
+            "default": false
+        }),
+    );
+    properties.insert(
+        "confirm".into(),
+        json!({
+            "type": "boolean",
+            "description": "Must be true; queries and writes switch to the candidate provider",
+            "default": false
+        }),
+    );
+    finalize_object_schema(properties, &[])
+}
+
+/// Build the schema describing the `move-to-collection` tool input.
+pub(crate) fn move_to_collection_input_schema() -> Map<String, Value> {
+    let mut properties = Map::new();
+    properties.insert(
+        "collection".into(),
+        string_schema("Source collection (defaults to the configured collection)"),
+    );
+    properties.insert(
+        "target_collection".into(),
+        string_schema("Existing collection to move the memories into; must share the vector size"),
+    );
+    properties.insert(
+        "project_id".into(),
+        string_schema("Move only memories with this project_id"),
+    );
+    properties.insert(
+        "memory_type".into(),
+        json!({
+            "type": "string",
+            "description": "Move only memories of this memory_type",
+            "enum": ["episodic", "semantic", "procedural"]
+        }),
+    );
+    properties.insert(
+        "tags".into(),
+        json!({
+            "type": "array",
+            "description": "Move only memories carrying any of these tags",
+            "items": { "type": "string" }
+        }),
+    );
+    finalize_object_schema(properties, &["target_collection"])
+}
+
+/// Build the schema describing the `rename-collection` tool input.
+pub(crate) fn rename_collection_input_schema() -> Map<String, Value> {
+    let mut properties = Map::new();
+    properties.insert(
+        "collection".into(),
+        string_schema("Collection to rename (defaults to the configured collection)"),
+    );
+    properties.insert(
+        "new_name".into(),
+        string_schema("Name of the collection to create; must not exist yet"),
+    );
+    properties.insert(
+        "confirm".into(),
+        json!({
+            "type": "boolean",
+            "description": "Must be true; the original collection is deleted once copied",
+            "default": false
+        }),
+    );
+    finalize_object_schema(properties, &["new_name"])
+}
+
+/// Build the schema describing the `prune` tool input.
+pub(crate) fn prune_input_schema() -> Map<String, Value> {
+    let mut properties = Map::new();
+    properties.insert(
+        "collection".into(),
+        string_schema("Optional collection override"),
+    );
+    properties.insert(
+        "project_id".into(),
+        string_schema("Prune only memories with this project_id; all projects when omitted"),
+    );
+    properties.insert(
+        "memory_type".into(),
+        json!({
+            "type": "string",
+            "description": "Memory type to prune",
+            "enum": ["episodic", "semantic", "procedural"],
+            "default": "episodic"
+        }),
+    );
+    properties.insert(
+        "older_than".into(),
+        string_schema(
+            "Delete memories whose timestamp is before this RFC3339 timestamp or date, or older than a duration like `90d`, `36h`, or `2w`",
+        ),
+    );
+    properties.insert(
+        "dry_run".into(),
+        json!({
+            "type": "boolean",
+            "description": "Only count the memories that would be deleted",
+            "default": false
+        }),
+    );
+    properties.insert(
+        "confirm".into(),
+        json!({
+            "type": "boolean",
+            "description": "Must be true to delete; not needed for a dry run",
+            "default": false
+        }),
+    );
+    finalize_object_schema(properties, &["older_than"])
+}
+
+/// Build the schema describing the `verify-collection` tool input.
+pub(crate) fn verify_collection_input_schema() -> Map<String, Value> {
+    let mut properties = Map::new();
+    properties.insert(
+        "collection".into(),
+        string_schema("Optional collection override"),
+    );
+    properties.insert(
+        "sample_rate".into(),
+        json!({
+            "type": "number",
+            "description": "Share of points to check, evenly spaced; 1 checks every point",
+            "exclusiveMinimum": 0,
+            "maximum": 1,
+            "default": 1
+        }),
+    );
+    properties.insert(
+        "fix_hashes".into(),
+        json!({
+            "type": "boolean",
+            "description": "Rewrite wrong or missing chunk_hash values from the stored text",
+            "default": false
+        }),
+    );
+    properties.insert(
+        "backfill_timestamps".into(),
+        json!({
+            "type": "boolean",
+            "description": "Write the integer timestamp_unix field parsed from each RFC3339 timestamp where it is missing or stale",
+            "default": false
+        }),
+    );
+    finalize_object_schema(properties, &[])
+}
+
+/// Build the schema describing the `estimate-storage` tool input.
+pub(crate) fn estimate_storage_input_schema() -> Map<String, Value> {
+    let mut properties = Map::new();
+    properties.insert(
+        "collection".into(),
+        string_schema("Optional collection override"),
+    );
+    finalize_object_schema(properties, &[])
+}
+
+/// Build the schema describing the `storage-stats` tool input.
+pub(crate) fn storage_stats_input_schema() -> Map<String, Value> {
+    let mut properties = Map::new();
+    properties.insert(
+        "collection".into(),
+        string_schema("Collection to report on; every collection when omitted"),
+    );
+    properties.insert(
+        "project_id".into(),
+        string_schema("Only count and sample points of this project"),
+    );
+    finalize_object_schema(properties, &[])
+}
+
+/// Build the schema describing the `get-document` tool input.
+pub(crate) fn get_document_input_schema() -> Map<String, Value> {
+    let mut properties = Map::new();
+    properties.insert(
+        "collection".into(),
+        string_schema("Optional collection override"),
+    );
+    properties.insert(
+        "doc_id".into(),
+        string_schema("Document identifier returned by push as docId"),
+    );
+    properties.insert(
+        "source_uri".into(),
+        string_schema("Rebuild the most recent ingestion of this source instead of a doc_id"),
+    );
+    properties.insert(
+        "project_id".into(),
+        string_schema("Limit a source_uri lookup to one project"),
+    );
+    finalize_object_schema(properties, &[])
+}
+
+/// Build the schema describing the `link-memories` tool input.
+pub(crate) fn link_memories_input_schema() -> Map<String, Value> {
+    let mut properties = Map::new();
+    properties.insert(
+        "collection".into(),
+        string_schema("Optional collection override"),
+    );
+    properties.insert(
+        "source_memory_id".into(),
+        string_schema("Memory recording the link"),
+    );
+    properties.insert(
+        "target_memory_id".into(),
+        string_schema("Memory the link points at; must exist in the same collection"),
+    );
+    properties.insert(
+        "relation".into(),
+        json!({
+            "type": "string",
+            "description": "Relation from the source to the target",
+            "enum": ["supersedes", "refines", "relates_to"]
+        }),
+    );
+    finalize_object_schema(
+        properties,
+        &["source_memory_id", "target_memory_id", "relation"],
+    )
+}
+
+/// Build the schema describing the `get-links` tool input.
+pub(crate) fn get_links_input_schema() -> Map<String, Value> {
+    let mut properties = Map::new();
+    properties.insert(
+        "collection".into(),
+        string_schema("Optional collection override"),
+    );
+    properties.insert(
+        "memory_id".into(),
+        string_schema("Memory whose outbound and inbound links are resolved"),
+    );
+    finalize_object_schema(properties, &["memory_id"])
+}
+
+/// Build the schema describing the `describe-collection` tool input.
+pub(crate) fn describe_collection_input_schema() -> Map<String, Value> {
+    let mut properties = Map::new();
+    properties.insert(
+        "collection".into(),
+        string_schema("Optional collection override"),
+    );
+    finalize_object_schema(properties, &[])
+}
+
+/// Build the schema describing the `warm-collection` tool input.
+pub(crate) fn warm_collection_input_schema() -> Map<String, Value> {
+    let mut properties = Map::new();
+    properties.insert(
+        "collection".into(),
+        string_schema("Optional collection override"),
+    );
+
+    let mut timeout_schema = Map::new();
+    timeout_schema.insert("type".into(), Value::String("integer".into()));
+    timeout_schema.insert(
+        "description".into(),
+        Value::String("Maximum time to wait for indexing to finish, in milliseconds".into()),
+    );
+    timeout_schema.insert("minimum".into(), Value::Number(0.into()));
+    timeout_schema.insert("maximum".into(), Value::Number(300_000.into()));
+    timeout_schema.insert("default".into(), Value::Number(30_000.into()));
+    properties.insert("timeout_ms".into(), Value::Object(timeout_schema));
+
+    let mut queries_schema = Map::new();
+    queries_schema.insert("type".into(), Value::String("integer".into()));
+    queries_schema.insert(
+        "description".into(),
+        Value::String("Dummy searches to run after indexing to warm caches".into()),
+    );
+    queries_schema.insert("minimum".into(), Value::Number(0.into()));
+    queries_schema.insert("maximum".into(), Value::Number(10.into()));
+    queries_schema.insert("default".into(), Value::Number(0.into()));
+    properties.insert("warmup_queries".into(), Value::Object(queries_schema));
+
+    finalize_object_schema(properties, &[])
+}
+
 /// Build the schema describing the `search` tool input.
 pub(crate) fn search_input_schema() -> Map<String, Value> {
     let config = get_config();
@@ -90,17 +503,27 @@ pub(crate) fn search_input_schema() -> Map<String, Value> {
     let mut properties = Map::new();
     properties.insert(
         "query_text".into(),
-        string_schema("Natural language query text to embed and search with"),
+        string_schema(
+            "Natural language query text to embed and search with; required unless `memory_id` or `chunk_hash` is given",
+        ),
+    );
+    properties.insert(
+        "memory_id".into(),
+        string_schema(
+            "Exact lookup: return the record with this memory_id instead of running a vector query (excludes query_text, score_threshold, search_params, highlight, explain_hits)",
+        ),
+    );
+    properties.insert(
+        "chunk_hash".into(),
+        string_schema(
+            "Exact lookup: return records whose chunk_hash matches instead of running a vector query (excludes the same vector options as memory_id)",
+        ),
     );
 
-    let mut project_schema = Map::new();
-    project_schema.insert("type".into(), Value::String("string".into()));
-    project_schema.insert(
-        "description".into(),
-        Value::String("Filter results to a specific project_id".into()),
+    properties.insert(
+        "project_id".into(),
+        project_filter_schema("Filter results to a specific project_id", Some("default")),
     );
-    project_schema.insert("default".into(), Value::String("default".into()));
-    properties.insert("project_id".into(), Value::Object(project_schema));
 
     let mut memory_schema = Map::new();
     memory_schema.insert("type".into(), Value::String("string".into()));
@@ -129,6 +552,16 @@ pub(crate) fn search_input_schema() -> Map<String, Value> {
     );
     tags_schema.insert("items".into(), Value::Object(tag_item_schema));
     properties.insert("tags".into(), Value::Object(tags_schema));
+    properties.insert(
+        "include_ids".into(),
+        json!({
+            "type": "array",
+            "description": "Only search these memory ids (UUIDs or unsigned integers), e.g. a set tracked from earlier results",
+            "items": { "type": "string" },
+            "minItems": 1,
+            "maxItems": MAX_INCLUDE_IDS
+        }),
+    );
 
     let mut time_range_properties = Map::new();
     time_range_properties.insert(
@@ -156,10 +589,13 @@ pub(crate) fn search_input_schema() -> Map<String, Value> {
         "default".into(),
         Value::Number(serde_json::Number::from(default_limit as u64)),
     );
-    limit_schema.insert(
-        "maximum".into(),
-        Value::Number(serde_json::Number::from(max_limit as u64)),
-    );
+    // Oversized values are accepted (and clamped) when clamping is enabled.
+    if !config.search_clamp_limit {
+        limit_schema.insert(
+            "maximum".into(),
+            Value::Number(serde_json::Number::from(max_limit as u64)),
+        );
+    }
     properties.insert("limit".into(), Value::Object(limit_schema));
 
     let mut threshold_schema = Map::new();
@@ -183,6 +619,7 @@ pub(crate) fn search_input_schema() -> Map<String, Value> {
         ),
     );
     properties.insert("score_threshold".into(), Value::Object(threshold_schema));
+    properties.insert("search_params".into(), search_params_schema());
 
     let mut collection_schema = Map::new();
     collection_schema.insert("type".into(), Value::String("string".into()));
@@ -191,8 +628,115 @@ pub(crate) fn search_input_schema() -> Map<String, Value> {
         Value::String("Optional collection override".into()),
     );
     properties.insert("collection".into(), Value::Object(collection_schema));
+    properties.insert(
+        "highlight".into(),
+        json!({
+            "type": "boolean",
+            "description": "Return `highlights: [{start, end}]` character offsets of query terms found in each hit's text",
+            "default": false
+        }),
+    );
+    properties.insert(
+        "explain_hits".into(),
+        json!({
+            "type": "boolean",
+            "description": "Attach `explanation: {raw_score, threshold, rank, filter_matches}` to each hit showing why it ranked where it did",
+            "default": false
+        }),
+    );
+    properties.insert(
+        "include_provenance".into(),
+        json!({
+            "type": "boolean",
+            "description": "Attach `provenance: {collection, created_at, embedding_model}` to each hit showing where and when it was written",
+            "default": false
+        }),
+    );
+    properties.insert(
+        "max_context_tokens".into(),
+        json!({
+            "type": "integer",
+            "minimum": 1,
+            "description": "Pack the prompt-ready `context` into at most this many tokens, keeping the highest-ranked hits that fit"
+        }),
+    );
+    properties.insert(
+        "context_tokenizer".into(),
+        json!({
+            "type": "string",
+            "description": "tiktoken encoding used to count `max_context_tokens`; defaults to the embedding model's tokenizer",
+            "enum": ENCODING_NAMES
+        }),
+    );
+    properties.insert(
+        "mode".into(),
+        json!({
+            "type": "string",
+            "enum": ["results", "answer_context"],
+            "description": "`answer_context` packs hit texts in score order into `max_context_tokens` (default ANSWER_CONTEXT_MAX_TOKENS) with `[id]` markers and returns `citations` and `context_tokens`",
+            "default": "results"
+        }),
+    );
+    properties.insert(
+        "auto_relax".into(),
+        json!({
+            "type": "boolean",
+            "description": "When nothing matches, retry without `time_range`, then without `tags`; dropped filters are listed in `relaxed_filters`",
+            "default": false
+        }),
+    );
+    properties.insert(
+        "sort".into(),
+        json!({
+            "type": "string",
+            "description": "Order of the returned hits: by relevance, or the newest/oldest of the nearest candidates",
+            "enum": ["score", "timestamp_desc", "timestamp_asc"],
+            "default": "score"
+        }),
+    );
+    properties.insert(
+        "require_text".into(),
+        json!({
+            "type": "boolean",
+            "description": "Only return records that have stored `text` (skips text-suppressed records)",
+            "default": false
+        }),
+    );
+    properties.insert(
+        "one_per_document".into(),
+        json!({
+            "type": "boolean",
+            "description": "Collapse hits sharing a `doc_id` to the highest-scoring chunk of each document",
+            "default": false
+        }),
+    );
+    properties.insert(
+        "prefer_exact".into(),
+        json!({
+            "type": "boolean",
+            "description": "First return records whose text contains `query_text` verbatim (score 1.0), then fill the rest with vector hits; the vector query is skipped when exact matches reach `limit`",
+            "default": false
+        }),
+    );
+    properties.insert(
+        "fields".into(),
+        json!({
+            "type": "array",
+            "description": "Return only these hit fields; `id` and `score` are always included and `text` is left out unless listed. Only the listed payload keys are fetched from Qdrant, which keeps large texts off the wire when only ids, scores, or metadata are needed",
+            "items": { "type": "string", "enum": SEARCH_HIT_FIELDS }
+        }),
+    );
+    properties.insert(
+        "query_compression".into(),
+        json!({
+            "type": "string",
+            "description": "How a query_text longer than MAX_QUERY_TOKENS is shortened: keep the leading sentences, or ask the summarization model for a one-sentence restatement",
+            "enum": ["truncate", "llm"],
+            "default": "truncate"
+        }),
+    );
 
-    let mut schema = finalize_object_schema(properties, &["query_text"]);
+    let mut schema = finalize_object_schema(properties, &[]);
 
     let example_canonical = json!({
         "query_text": "current architecture plan",
@@ -210,14 +754,51 @@ pub(crate) fn search_input_schema() -> Map<String, Value> {
         "k": 3
     });
 
+    let example_lookup = json!({
+        "chunk_hash": "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+    });
+
     schema.insert(
         "examples".into(),
-        Value::Array(vec![example_canonical, example_aliases]),
+        Value::Array(vec![example_canonical, example_aliases, example_lookup]),
     );
 
     schema
 }
 
+/// Build the schema describing the `metrics` tool input.
+pub(crate) fn metrics_input_schema() -> Map<String, Value> {
+    let mut properties = Map::new();
+    properties.insert(
+        "collection".into(),
+        string_schema("Optional collection to report; omit for global totals"),
+    );
+    finalize_object_schema(properties, &[])
+}
+
+/// Build the schema describing the `sync-state` tool input.
+pub(crate) fn sync_state_input_schema() -> Map<String, Value> {
+    let mut properties = Map::new();
+    properties.insert(
+        "action".into(),
+        json!({
+            "type": "string",
+            "description": "List recorded cursors or reset them so sources are re-indexed",
+            "enum": ["list", "reset"],
+            "default": "list"
+        }),
+    );
+    properties.insert(
+        "project_id".into(),
+        string_schema("Limit the operation to one project"),
+    );
+    properties.insert(
+        "source_uri".into(),
+        string_schema("Limit the operation to one source"),
+    );
+    finalize_object_schema(properties, &[])
+}
+
 /// Schema representing an empty object (used for parameterless tools).
 pub(crate) fn empty_object_schema() -> Map<String, Value> {
     finalize_object_schema(Map::new(), &[])
@@ -230,14 +811,13 @@ pub(crate) fn summarize_input_schema() -> Map<String, Value> {
 
     let mut properties = Map::new();
 
-    let mut project_schema = Map::new();
-    project_schema.insert("type".into(), Value::String("string".into()));
-    project_schema.insert(
-        "description".into(),
-        Value::String("Optional project filter; defaults to 'default' when omitted".into()),
+    properties.insert(
+        "project_id".into(),
+        project_filter_schema(
+            "Optional project filter; defaults to 'default' when omitted",
+            Some("default"),
+        ),
     );
-    project_schema.insert("default".into(), Value::String("default".into()));
-    properties.insert("project_id".into(), Value::Object(project_schema));
 
     let mut memory_schema = Map::new();
     memory_schema.insert("type".into(), Value::String("string".into()));
@@ -341,9 +921,802 @@ pub(crate) fn summarize_input_schema() -> Map<String, Value> {
         string_schema("Optional collection override"),
     );
 
+    let mut consolidate_schema = Map::new();
+    consolidate_schema.insert("type".into(), Value::String("boolean".into()));
+    consolidate_schema.insert(
+        "description".into(),
+        Value::String("Archive or delete the source memories once the summary is written".into()),
+    );
+    consolidate_schema.insert("default".into(), Value::Bool(false));
+    properties.insert("consolidate".into(), Value::Object(consolidate_schema));
+
+    let mut consolidate_mode_schema = Map::new();
+    consolidate_mode_schema.insert("type".into(), Value::String("string".into()));
+    consolidate_mode_schema.insert(
+        "description".into(),
+        Value::String(
+            "How sources are consolidated: 'archive' re-tags them, 'delete' removes them".into(),
+        ),
+    );
+    consolidate_mode_schema.insert(
+        "enum".into(),
+        Value::Array(
+            ["archive", "delete"]
+                .into_iter()
+                .map(|v| Value::String(v.into()))
+                .collect(),
+        ),
+    );
+    consolidate_mode_schema.insert("default".into(), Value::String("archive".into()));
+    properties.insert(
+        "consolidate_mode".into(),
+        Value::Object(consolidate_mode_schema),
+    );
+
+    properties.insert(
+        "label".into(),
+        string_schema(
+            "Human-friendly label stored on the summary; re-running over the same sources re-tags it",
+        ),
+    );
+    properties.insert(
+        "topic".into(),
+        string_schema("Topic stored on the summary; findable with `find-summaries`"),
+    );
+
     finalize_object_schema(properties, &["time_range"])
 }
 
+/// Schema of a filtering `project_id` argument.
+///
+/// With `REQUIRE_PROJECT_FILTER` the description states that the argument is mandatory and the
+/// `default` is dropped, since omitting it is rejected rather than defaulted.
+fn project_filter_schema(optional: &str, default: Option<&str>) -> Value {
+    if get_config().require_project_filter {
+        return string_schema(&format!(
+            "Project to scope to; required because REQUIRE_PROJECT_FILTER is enabled (list ids via {})",
+            super::server::PROJECTS_URI
+        ));
+    }
+    let mut schema = string_schema(optional);
+    if let (Some(default), Value::Object(map)) = (default, &mut schema) {
+        map.insert("default".into(), Value::String(default.into()));
+    }
+    schema
+}
+
+/// Build the schema describing the `find-summaries` tool input.
+pub(crate) fn find_summaries_input_schema() -> Map<String, Value> {
+    let config = get_config();
+    let mut properties = Map::new();
+    properties.insert(
+        "project_id".into(),
+        project_filter_schema(
+            "Only summaries of this project; all projects when omitted",
+            None,
+        ),
+    );
+    properties.insert(
+        "label".into(),
+        string_schema("Exact label the summary was stored with"),
+    );
+    properties.insert(
+        "topic".into(),
+        string_schema("Exact topic the summary was stored with"),
+    );
+    properties.insert(
+        "time_range".into(),
+        json!({
+            "type": "object",
+            "description": "Bounds on when the summary was written",
+            "properties": {
+                "start": { "type": "string", "description": "Inclusive RFC3339 timestamp or YYYY-MM-DD date" },
+                "end": { "type": "string", "description": "Inclusive RFC3339 timestamp or YYYY-MM-DD date" }
+            },
+            "additionalProperties": false
+        }),
+    );
+    properties.insert(
+        "limit".into(),
+        json!({
+            "type": "integer",
+            "description": "Maximum summaries returned, newest first",
+            "minimum": 1,
+            "maximum": config.search_max_limit,
+            "default": config.search_default_limit
+        }),
+    );
+    properties.insert(
+        "collection".into(),
+        string_schema("Optional collection override"),
+    );
+    finalize_object_schema(properties, &[])
+}
+
+/// Build the schema describing the `check-summaries` tool input.
+pub(crate) fn check_summaries_input_schema() -> Map<String, Value> {
+    let mut properties = Map::new();
+    properties.insert(
+        "project_id".into(),
+        string_schema("Only check summaries of this project; all projects when omitted"),
+    );
+    properties.insert(
+        "tag_orphans".into(),
+        json!({
+            "type": "boolean",
+            "description": "Tag summaries with missing sources as summary:orphaned",
+            "default": false
+        }),
+    );
+    properties.insert(
+        "collection".into(),
+        string_schema("Optional collection override"),
+    );
+    finalize_object_schema(properties, &[])
+}
+
+/// Build the schema describing the `refine-summary` tool input.
+pub(crate) fn refine_summary_input_schema() -> Map<String, Value> {
+    let config = get_config();
+    let mut properties = Map::new();
+    properties.insert(
+        "upserted_memory_id".into(),
+        string_schema("Point id of the summary, as returned by `summarize` or `find-summaries`"),
+    );
+    properties.insert(
+        "feedback".into(),
+        string_schema("What the revised summary should change, add, or drop"),
+    );
+    properties.insert(
+        "max_words".into(),
+        json!({
+            "type": "integer",
+            "description": "Word budget for the revision",
+            "minimum": 1,
+            "default": config.summarization_max_words
+        }),
+    );
+    properties.insert(
+        "model".into(),
+        string_schema("Summarization model override"),
+    );
+    properties.insert(
+        "collection".into(),
+        string_schema("Optional collection override"),
+    );
+    finalize_object_schema(properties, &["upserted_memory_id", "feedback"])
+}
+
+/// Version of the structured tool outputs described by the `*_output_schema` builders.
+///
+/// Every described payload echoes it as `schema_version`, or `schemaVersion` in the camelCase
+/// `push` and `metrics` payloads. Bump it when a field is removed,
+/// renamed, or changes type; adding an optional field does not require a bump.
+pub(crate) const OUTPUT_SCHEMA_VERSION: u64 = 1;
+
+/// Build the schema describing the `metrics` tool output.
+pub(crate) fn metrics_output_schema() -> Map<String, Value> {
+    let mut properties = Map::new();
+    properties.insert("schemaVersion".into(), schema_version_schema());
+    properties.insert(
+        "documentsIndexed".into(),
+        count_schema("Documents indexed since startup"),
+    );
+    properties.insert(
+        "chunksIndexed".into(),
+        count_schema("Chunks indexed since startup"),
+    );
+    properties.insert(
+        "lastChunkSize".into(),
+        json!({
+            "type": ["integer", "null"],
+            "minimum": 0,
+            "description": "Chunk size of the most recent push; null before the first"
+        }),
+    );
+    properties.insert(
+        "dedupe".into(),
+        json!({
+            "type": "object",
+            "description": "Chunks skipped as exact duplicates or unchanged sources instead of embedded",
+            "properties": {
+                "chunksSeen": { "type": "integer", "minimum": 0 },
+                "skippedExact": { "type": "integer", "minimum": 0 },
+                "skippedUnchangedSource": { "type": "integer", "minimum": 0 },
+                "embeddingsSaved": { "type": "integer", "minimum": 0 },
+                "duplicateRatio": { "type": "number", "minimum": 0, "maximum": 1 }
+            },
+            "required": [
+                "chunksSeen", "skippedExact", "skippedUnchangedSource", "embeddingsSaved",
+                "duplicateRatio"
+            ],
+            "additionalProperties": false
+        }),
+    );
+    properties.insert(
+        "invalidEmbeddings".into(),
+        count_schema("Embedding responses rejected for NaN, infinite, or all-zero vectors"),
+    );
+    properties.insert(
+        "tools".into(),
+        json!({
+            "type": "object",
+            "description": "Tool-call concurrency gate",
+            "properties": {
+                "inFlight": { "type": "integer", "minimum": 0 },
+                "queued": { "type": "integer", "minimum": 0 },
+                "maxConcurrent": { "type": "integer", "minimum": 0 },
+                "maxQueued": { "type": "integer", "minimum": 0 }
+            },
+            "required": ["inFlight", "queued", "maxConcurrent", "maxQueued"],
+            "additionalProperties": false
+        }),
+    );
+    properties.insert(
+        "collection".into(),
+        string_schema("Collection the counters cover; absent for global totals"),
+    );
+    finalize_object_schema(
+        properties,
+        &[
+            "schemaVersion",
+            "documentsIndexed",
+            "chunksIndexed",
+            "lastChunkSize",
+            "dedupe",
+            "invalidEmbeddings",
+            "tools",
+        ],
+    )
+}
+
+/// Build the schema describing the `push` tool output.
+pub(crate) fn push_output_schema() -> Map<String, Value> {
+    let mut properties = Map::new();
+    properties.insert("schemaVersion".into(), schema_version_schema());
+    properties.insert("status".into(), json!({ "type": "string", "const": "ok" }));
+    properties.insert(
+        "collection".into(),
+        string_schema("Collection the chunks were written to"),
+    );
+    properties.insert(
+        "docId".into(),
+        json!({
+            "type": ["string", "null"],
+            "description": "Identifier shared by the document's chunks; null when nothing was written"
+        }),
+    );
+    for (key, description) in [
+        ("chunksIndexed", "Chunks produced for the document"),
+        ("chunkSize", "Chunk size applied"),
+        ("overlap", "Tokens shared between adjacent chunks"),
+        (
+            "embeddingContextWindow",
+            "Embedding context window of the configured model",
+        ),
+        ("inserted", "New points written"),
+        ("updated", "Existing points updated in place"),
+        (
+            "skippedDuplicates",
+            "Chunks skipped as duplicates within the request",
+        ),
+        (
+            "skippedLowQuality",
+            "Chunks dropped for scoring below CHUNK_QUALITY_MIN",
+        ),
+        (
+            "skippedUnchangedChunks",
+            "Chunks the unchanged source was stored as at its last sync",
+        ),
+    ] {
+        properties.insert(key.into(), count_schema(description));
+    }
+    for (key, description) in [
+        (
+            "autoSized",
+            "Chunk size was derived from the model's context window",
+        ),
+        (
+            "skippedUnchanged",
+            "The source matched its STATE_DIR cursor and nothing was written",
+        ),
+        (
+            "degraded",
+            "Chunks were embedded with the deterministic fallback encoder",
+        ),
+    ] {
+        properties.insert(key.into(), boolean_schema(description));
+    }
+    properties.insert(
+        "duplicateRatio".into(),
+        json!({
+            "type": "number",
+            "minimum": 0,
+            "maximum": 1,
+            "description": "Share of the document's chunks skipped as duplicates or unchanged"
+        }),
+    );
+    properties.insert(
+        "ingestProfile".into(),
+        json!({
+            "type": "object",
+            "description": "Chunking and embedding settings applied to the document",
+            "properties": {
+                "embeddingProvider": { "type": "string" },
+                "embeddingModel": { "type": "string" },
+                "embeddingDimension": { "type": "integer", "minimum": 0 },
+                "chunkSize": { "type": "integer", "minimum": 0 },
+                "chunkOverlap": { "type": "integer", "minimum": 0 },
+                "strategy": { "type": "string" },
+                "tokenizer": { "type": "string" },
+                "dedupe": { "type": "string" },
+                "version": { "type": "string" },
+                "profileHash": { "type": "string" }
+            },
+            "required": [
+                "embeddingProvider", "embeddingModel", "embeddingDimension", "chunkSize",
+                "chunkOverlap", "strategy", "tokenizer", "dedupe", "version", "profileHash"
+            ],
+            "additionalProperties": false
+        }),
+    );
+    properties.insert(
+        "warnings".into(),
+        json!({
+            "type": "array",
+            "items": { "type": "string" },
+            "description": "Chunk size adjustments and transcript notices"
+        }),
+    );
+    properties.insert(
+        "hint".into(),
+        string_schema("Suggestion to warm a collection that is still indexing"),
+    );
+    properties.insert(
+        "classification".into(),
+        json!({
+            "type": "object",
+            "description": "Per-type chunk counts when auto_classify was requested",
+            "properties": {
+                "episodic": { "type": "integer", "minimum": 0 },
+                "semantic": { "type": "integer", "minimum": 0 },
+                "procedural": { "type": "integer", "minimum": 0 }
+            },
+            "required": ["episodic", "semantic", "procedural"],
+            "additionalProperties": false
+        }),
+    );
+    properties.insert(
+        "inferred".into(),
+        json!({
+            "type": "object",
+            "description": "Metadata filled in from the client identity and push defaults",
+            "properties": {
+                "projectId": { "type": "string" },
+                "tags": { "type": "array", "items": { "type": "string" } },
+                "memoryType": { "type": "string" }
+            },
+            "additionalProperties": false
+        }),
+    );
+    finalize_object_schema(
+        properties,
+        &[
+            "schemaVersion",
+            "status",
+            "collection",
+            "docId",
+            "chunksIndexed",
+            "chunkSize",
+            "overlap",
+            "autoSized",
+            "embeddingContextWindow",
+            "inserted",
+            "updated",
+            "skippedDuplicates",
+            "skippedLowQuality",
+            "skippedUnchanged",
+            "duplicateRatio",
+            "degraded",
+            "ingestProfile",
+        ],
+    )
+}
+
+/// Build the schema describing the `search` tool output.
+pub(crate) fn search_output_schema() -> Map<String, Value> {
+    let mut hit = Map::new();
+    hit.insert("id".into(), string_schema("Point identifier"));
+    hit.insert("score".into(), json!({ "type": "number" }));
+    hit.insert("text".into(), string_schema("Stored chunk text"));
+    for key in [
+        "project_id",
+        "memory_type",
+        "timestamp",
+        "source_uri",
+        "doc_id",
+    ] {
+        hit.insert(key.into(), json!({ "type": "string" }));
+    }
+    hit.insert(
+        "tags".into(),
+        json!({ "type": "array", "items": { "type": "string" } }),
+    );
+    hit.insert(
+        "chunk_index".into(),
+        json!({ "type": "integer", "minimum": 0 }),
+    );
+    hit.insert(
+        "highlights".into(),
+        json!({
+            "type": "array",
+            "description": "Byte ranges of query terms in `text`",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "start": { "type": "integer", "minimum": 0 },
+                    "end": { "type": "integer", "minimum": 0 }
+                },
+                "required": ["start", "end"],
+                "additionalProperties": false
+            }
+        }),
+    );
+    hit.insert("citations".into(), citations_schema());
+    hit.insert(
+        "links".into(),
+        json!({
+            "type": "array",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "rel": { "type": "string", "enum": ["supersedes", "refines", "relates_to"] },
+                    "target_memory_id": { "type": "string" }
+                },
+                "required": ["rel", "target_memory_id"],
+                "additionalProperties": false
+            }
+        }),
+    );
+    hit.insert(
+        "explanation".into(),
+        json!({
+            "type": "object",
+            "description": "Why the hit was returned, when explain_hits is set",
+            "properties": {
+                "raw_score": { "type": "number" },
+                "threshold": { "type": "number" },
+                "rank": { "type": "integer", "minimum": 1 },
+                "filter_matches": {
+                    "type": "object",
+                    "properties": {
+                        "project": { "type": "string" },
+                        "type": { "type": "string" },
+                        "tags_matched": { "type": "array", "items": { "type": "string" } }
+                    },
+                    "required": ["tags_matched"],
+                    "additionalProperties": false
+                }
+            },
+            "required": ["raw_score", "threshold", "rank", "filter_matches"],
+            "additionalProperties": false
+        }),
+    );
+    hit.insert(
+        "provenance".into(),
+        json!({
+            "type": "object",
+            "description": "Origin of the hit, when include_provenance is set",
+            "properties": {
+                "collection": { "type": "string" },
+                "created_at": { "type": "string" },
+                "embedding_model": { "type": "string" }
+            },
+            "required": ["collection"],
+            "additionalProperties": false
+        }),
+    );
+
+    let mut properties = Map::new();
+    properties.insert("schema_version".into(), schema_version_schema());
+    properties.insert(
+        "results".into(),
+        json!({
+            "type": "array",
+            "items": Value::Object(finalize_object_schema(hit, &["id", "score"]))
+        }),
+    );
+    properties.insert("collection".into(), string_schema("Collection searched"));
+    properties.insert(
+        "resolved_collection".into(),
+        string_schema("Concrete collection that served the search after alias resolution"),
+    );
+    properties.insert("limit".into(), count_schema("Maximum number of results"));
+    properties.insert("score_threshold".into(), json!({ "type": "number" }));
+    properties.insert(
+        "scoreThreshold".into(),
+        json!({ "type": "number", "description": "Alias of score_threshold" }),
+    );
+    properties.insert(
+        "used_filters".into(),
+        json!({
+            "type": "object",
+            "description": "Effective filters and options after defaults were applied"
+        }),
+    );
+    properties.insert(
+        "context".into(),
+        string_schema("Hit texts joined into prompt-ready lines with [id] citations"),
+    );
+    properties.insert(
+        "query_truncated".into(),
+        boolean_schema("The query was cut to MAX_QUERY_TOKENS before embedding"),
+    );
+    properties.insert(
+        "query_compressed".into(),
+        boolean_schema("The query was rewritten before embedding"),
+    );
+    properties.insert(
+        "effective_query".into(),
+        string_schema("Text that was embedded for the search"),
+    );
+    properties.insert(
+        "context_tokens_used".into(),
+        count_schema("Tokens occupied by `context` under max_context_tokens"),
+    );
+    properties.insert(
+        "context_tokenizer".into(),
+        string_schema("Encoding used to pack `context`"),
+    );
+    properties.insert(
+        "context_truncated".into(),
+        boolean_schema("Hits were left out of `context` because the budget ran out"),
+    );
+    properties.insert(
+        "mode".into(),
+        json!({ "type": "string", "enum": ["answer_context"], "description": "Present in answer_context mode" }),
+    );
+    properties.insert(
+        "context_tokens".into(),
+        count_schema("Tokens occupied by the answer `context`"),
+    );
+    let mut citation = Map::new();
+    citation.insert(
+        "marker".into(),
+        string_schema("Marker used in `context`, `[id]`"),
+    );
+    citation.insert("id".into(), string_schema("Cited memory id"));
+    citation.insert("score".into(), json!({ "type": "number" }));
+    citation.insert(
+        "source_uri".into(),
+        string_schema("Source of the cited memory"),
+    );
+    properties.insert(
+        "citations".into(),
+        json!({
+            "type": "array",
+            "description": "Memories cited in the answer `context`, in score order",
+            "items": Value::Object(finalize_object_schema(citation, &["marker", "id", "score"]))
+        }),
+    );
+    properties.insert(
+        "relaxed_filters".into(),
+        json!({
+            "type": "array",
+            "items": { "type": "string", "enum": ["time_range", "tags"] },
+            "description": "Filters auto_relax dropped, in order"
+        }),
+    );
+    properties.insert(
+        "partitions_searched".into(),
+        json!({
+            "type": "array",
+            "items": { "type": "string" },
+            "description": "Monthly episodic partitions read, newest first (EPISODIC_PARTITIONING)"
+        }),
+    );
+    properties.insert(
+        "truncated".into(),
+        boolean_schema(
+            "Trailing results were dropped to keep the response under MAX_RESPONSE_BYTES",
+        ),
+    );
+    properties.insert(
+        "dropped_count".into(),
+        count_schema("Results dropped to fit MAX_RESPONSE_BYTES"),
+    );
+    properties.insert(
+        "exact_matches".into(),
+        count_schema(
+            "Records containing `query_text` verbatim, placed ahead of vector hits (prefer_exact)",
+        ),
+    );
+    finalize_object_schema(
+        properties,
+        &[
+            "schema_version",
+            "results",
+            "collection",
+            "resolved_collection",
+            "limit",
+            "score_threshold",
+            "scoreThreshold",
+            "used_filters",
+        ],
+    )
+}
+
+/// Build the schema describing the `get-collections` tool output.
+pub(crate) fn collections_output_schema() -> Map<String, Value> {
+    let mut properties = Map::new();
+    properties.insert("schema_version".into(), schema_version_schema());
+    properties.insert(
+        "collections".into(),
+        json!({
+            "type": "array",
+            "items": { "type": "string" },
+            "description": "Collection names known to Qdrant"
+        }),
+    );
+    finalize_object_schema(properties, &["schema_version", "collections"])
+}
+
+/// Build the schema describing the `summarize` tool output.
+pub(crate) fn summarize_output_schema() -> Map<String, Value> {
+    let mut properties = Map::new();
+    properties.insert("schema_version".into(), schema_version_schema());
+    properties.insert(
+        "summary".into(),
+        string_schema("Summary text with [n] citations"),
+    );
+    properties.insert(
+        "source_memory_ids".into(),
+        json!({
+            "type": "array",
+            "items": { "type": "string" },
+            "description": "Memories the summary was built from"
+        }),
+    );
+    properties.insert("citations".into(), citations_schema());
+    properties.insert(
+        "upserted_memory_id".into(),
+        string_schema("Identifier of the stored summary memory"),
+    );
+    properties.insert(
+        "strategy".into(),
+        string_schema("Strategy that produced the summary"),
+    );
+    properties.insert(
+        "provider".into(),
+        string_schema("Summarization provider used"),
+    );
+    properties.insert("model".into(), string_schema("Summarization model used"));
+    properties.insert(
+        "consolidated".into(),
+        json!({
+            "type": "string",
+            "enum": ["archive", "delete"],
+            "description": "What happened to the source memories"
+        }),
+    );
+    properties.insert(
+        "abstractive_attempts".into(),
+        count_schema("Abstractive generations attempted"),
+    );
+    properties.insert(
+        "items_summarized".into(),
+        count_schema("Memories included in the summary"),
+    );
+    properties.insert(
+        "items_trimmed".into(),
+        count_schema("Memories dropped to fit the prompt budget"),
+    );
+    properties.insert(
+        "label".into(),
+        string_schema("Label stored with the summary"),
+    );
+    properties.insert(
+        "topic".into(),
+        string_schema("Topic stored with the summary"),
+    );
+    properties.insert(
+        "used_filters".into(),
+        json!({
+            "type": "object",
+            "description": "Effective filters and options after defaults were applied"
+        }),
+    );
+    finalize_object_schema(
+        properties,
+        &[
+            "schema_version",
+            "summary",
+            "source_memory_ids",
+            "citations",
+            "upserted_memory_id",
+            "strategy",
+            "abstractive_attempts",
+            "items_summarized",
+            "items_trimmed",
+            "used_filters",
+        ],
+    )
+}
+
+fn schema_version_schema() -> Value {
+    json!({
+        "type": "integer",
+        "const": OUTPUT_SCHEMA_VERSION,
+        "description": "Version of this output schema"
+    })
+}
+
+fn count_schema(description: &str) -> Value {
+    json!({ "type": "integer", "minimum": 0, "description": description })
+}
+
+fn boolean_schema(description: &str) -> Value {
+    json!({ "type": "boolean", "description": description })
+}
+
+fn citations_schema() -> Value {
+    json!({
+        "type": "array",
+        "description": "Inline [n] markers resolved to the memories they cite",
+        "items": {
+            "type": "object",
+            "properties": {
+                "marker": { "type": "integer", "minimum": 1 },
+                "memory_id": { "type": "string" }
+            },
+            "required": ["marker", "memory_id"],
+            "additionalProperties": false
+        }
+    })
+}
+
+/// Assert that a tool's structured output satisfies its declared output schema.
+#[cfg(test)]
+pub(crate) fn assert_matches_output_schema(schema: Map<String, Value>, output: &Value) {
+    let validator = jsonschema::validator_for(&Value::Object(schema)).expect("valid schema");
+    let errors: Vec<String> = validator
+        .iter_errors(output)
+        .map(|error| format!("{}: {error}", error.instance_path()))
+        .collect();
+    assert!(errors.is_empty(), "{errors:#?}\n{output:#}");
+}
+
+/// Schema for the optional per-query `search_params` object (HNSW/quantization tuning).
+fn search_params_schema() -> Value {
+    json!({
+        "type": "object",
+        "description": "Advanced Qdrant tuning for quantized collections; trades latency for recall",
+        "properties": {
+            "hnsw_ef": {
+                "type": "integer",
+                "minimum": 1,
+                "description": "HNSW beam width used during search"
+            },
+            "quantization": {
+                "type": "object",
+                "properties": {
+                    "rescore": {
+                        "type": "boolean",
+                        "description": "Re-score candidates with the original vectors"
+                    },
+                    "oversampling": {
+                        "type": "number",
+                        "minimum": 1.0,
+                        "description": "Multiplier on candidates fetched before rescoring"
+                    }
+                },
+                "additionalProperties": false
+            }
+        },
+        "additionalProperties": false
+    })
+}
+
 fn string_schema(description: &str) -> Value {
     let mut schema = Map::new();
     schema.insert("type".into(), Value::String("string".into()));
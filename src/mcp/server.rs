@@ -1,20 +1,43 @@
 //! MCP server bootstrap and request dispatch.
 
-use std::{borrow::Cow, sync::Arc};
+use std::{
+    borrow::Cow,
+    sync::{Arc, OnceLock},
+    time::Instant,
+};
 
 use crate::{
     config::get_config,
     mcp::{
+        MEMORY_TYPES,
+        cache::SearchCache,
         format::{
-            ProjectTagsSnapshot, ProjectsSnapshot, SearchSettingsSnapshot, SettingsSnapshot,
-            health_payload, json_resource_contents, memory_types_payload, serialize_json,
+            ProjectTagCountsSnapshot, ProjectTagsSnapshot, ProjectsSnapshot,
+            RecentMemoriesSnapshot, SearchSettingsSnapshot, SettingsSnapshot, health_payload,
+            json_resource_contents, memory_types_payload, serialize_json,
         },
         handlers::{
-            collections::{handle_create_collection, handle_list_collections},
-            index::handle_push,
+            collections::{
+                handle_apply_manifest, handle_create_collection, handle_describe_collection,
+                handle_estimate_storage, handle_list_collections, handle_move_to_collection,
+                handle_prune, handle_rename_collection, handle_storage_stats,
+                handle_verify_collection, handle_warm_collection,
+            },
+            document::handle_get_document,
+            index::{
+                handle_compare_providers, handle_promote_provider, handle_push,
+                handle_reembed_fallback,
+            },
+            links::{handle_get_links, handle_link_memories},
+            map_processing_error,
             metrics::handle_metrics,
+            ping::handle_ping,
             search::handle_search,
+            server_busy,
+            sync::handle_sync_state,
         },
+        instructions::build_instructions,
+        limiter::ToolLimiter,
         schemas,
     },
     processing::ProcessingService,
@@ -23,34 +46,82 @@ use rmcp::{
     ErrorData as McpError,
     handler::server::ServerHandler,
     model::{
-        AnnotateAble, CallToolRequestParam, CallToolResult, ListResourceTemplatesResult,
-        ListResourcesResult, ListToolsResult, RawResource, RawResourceTemplate,
-        ReadResourceRequestParam, ReadResourceResult, Resource, ResourceTemplate,
-        ServerCapabilities, ServerInfo, Tool, ToolAnnotations,
+        AnnotateAble, CallToolRequestParam, CallToolResult, InitializeRequestParam,
+        InitializeResult, ListResourceTemplatesResult, ListResourcesResult, ListToolsResult,
+        RawResource, RawResourceTemplate, ReadResourceRequestParam, ReadResourceResult, Resource,
+        ResourceTemplate, ServerCapabilities, ServerInfo, Tool, ToolAnnotations,
     },
+    service::{Peer, RequestContext, RoleServer},
 };
+use serde_json::Value;
+use tracing::{Instrument, field};
 const MEMORY_TYPES_URI: &str = "mcp://memory-types";
 const HEALTH_URI: &str = "mcp://health";
-const PROJECTS_URI: &str = "mcp://projects";
+pub(crate) const PROJECTS_URI: &str = "mcp://projects";
 const SETTINGS_URI: &str = "mcp://settings";
 const USAGE_URI: &str = "mcp://usage";
 const PROJECT_TAGS_TEMPLATE_URI: &str = "mcp://{project_id}/tags";
 const PROJECT_TAGS_PREFIX: &str = "mcp://";
 const PROJECT_TAGS_SUFFIX: &str = "/tags";
+const WITH_COUNTS_PARAM: &str = "with_counts";
+const MEMORY_TYPE_PARAM: &str = "memory_type";
+const MIN_COUNT_PARAM: &str = "min_count";
+const PROJECT_RECENT_TEMPLATE_URI: &str = "mcp://{project_id}/recent";
+const PROJECT_RECENT_SUFFIX: &str = "/recent";
+const LIMIT_PARAM: &str = "limit";
+/// Memories returned by the `recent` resource when `?limit=` is omitted.
+const RECENT_DEFAULT_LIMIT: usize = 20;
+/// Largest `?limit=` accepted by the `recent` resource.
+const RECENT_MAX_LIMIT: usize = 100;
 
 /// MCP server implementation exposing Rusty Memory operations.
 #[derive(Clone)]
 pub struct RustyMemMcpServer {
     processing: Arc<ProcessingService>,
+    search_cache: Arc<SearchCache>,
+    /// Gate bounding concurrent tool calls across every connection served by this process.
+    limiter: Arc<ToolLimiter>,
+    started_at: Instant,
+    /// Client implementation name reported by this connection's `initialize` handshake.
+    client_name: Arc<OnceLock<String>>,
+    /// `initialize` instructions generated from the registered tools and resources.
+    instructions: Arc<str>,
 }
 
 impl RustyMemMcpServer {
     /// Create a new MCP server using the supplied processing pipeline.
     pub fn new(processing: Arc<ProcessingService>) -> Self {
-        Self { processing }
+        Self {
+            processing,
+            search_cache: Arc::new(SearchCache::from_config()),
+            limiter: Arc::new(ToolLimiter::from_config()),
+            started_at: Instant::now(),
+            client_name: Arc::new(OnceLock::new()),
+            instructions: Arc::from(build_instructions(
+                &Self::describe_tools(),
+                &Self::describe_resources(),
+            )),
+        }
+    }
+
+    /// Remember the connected client's name; the first non-empty name wins.
+    fn record_client_name(&self, name: &str) -> Option<String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return None;
+        }
+        Some(self.client_name.get_or_init(|| name.to_string()).clone())
+    }
+
+    /// Name of the connected client, read from the peer info when no handshake was observed.
+    fn client_name(&self, peer: &Peer<RoleServer>) -> Option<String> {
+        match self.client_name.get() {
+            Some(name) => Some(name.clone()),
+            None => self.record_client_name(&peer.peer_info()?.client_info.name),
+        }
     }
 
-    fn describe_tools(&self) -> Vec<Tool> {
+    fn describe_tools() -> Vec<Tool> {
         let push_schema = Arc::new(schemas::index_input_schema());
         let search_schema = Arc::new(schemas::search_input_schema());
         let summarize_schema = Arc::new(schemas::summarize_input_schema());
@@ -62,7 +133,7 @@ impl RustyMemMcpServer {
                     "Retrieve the most relevant memories to ground your next step; add filters for project/type/tags/time.",
                 )),
                 input_schema: search_schema.clone(),
-                output_schema: None,
+                output_schema: Some(Arc::new(schemas::search_output_schema())),
                 annotations: Some(
                     ToolAnnotations::with_title("Search Memories")
                         .read_only(true)
@@ -78,7 +149,7 @@ impl RustyMemMcpServer {
                     "Store source text as retrievable memory instead of pasting it into chats.",
                 )),
                 input_schema: push_schema.clone(),
-                output_schema: None,
+                output_schema: Some(Arc::new(schemas::push_output_schema())),
                 annotations: Some(
                     ToolAnnotations::with_title("Index Document")
                         .destructive(true)
@@ -94,7 +165,7 @@ impl RustyMemMcpServer {
                     "See which memory collections exist before you index or search.",
                 )),
                 input_schema: Arc::new(schemas::empty_object_schema()),
-                output_schema: None,
+                output_schema: Some(Arc::new(schemas::collections_output_schema())),
                 annotations: Some(
                     ToolAnnotations::with_title("List Collections")
                         .read_only(true)
@@ -119,22 +190,295 @@ impl RustyMemMcpServer {
                 ),
                 icons: None,
             },
+            Tool {
+                name: Cow::Borrowed("describe-collection"),
+                title: Some("Describe Collection".to_string()),
+                description: Some(Cow::Borrowed(
+                    "Inspect a collection's vector size, payload indexes, and the payload keys found in a small sample, to help build filters.",
+                )),
+                input_schema: Arc::new(schemas::describe_collection_input_schema()),
+                output_schema: None,
+                annotations: Some(
+                    ToolAnnotations::with_title("Describe Collection")
+                        .read_only(true)
+                        .idempotent(true)
+                        .open_world(false),
+                ),
+                icons: None,
+            },
+            Tool {
+                name: Cow::Borrowed("apply-manifest"),
+                title: Some("Apply Collection Manifest".to_string()),
+                description: Some(Cow::Borrowed(
+                    "Bootstrap collections from a declarative TOML manifest; reports each collection as created, updated, unchanged, or conflicting (drifted settings are never changed).",
+                )),
+                input_schema: Arc::new(schemas::apply_manifest_input_schema()),
+                output_schema: None,
+                annotations: Some(
+                    ToolAnnotations::with_title("Apply Collection Manifest")
+                        .destructive(false)
+                        .idempotent(true)
+                        .open_world(false),
+                ),
+                icons: None,
+            },
+            Tool {
+                name: Cow::Borrowed("estimate-storage"),
+                title: Some("Estimate Storage".to_string()),
+                description: Some(Cow::Borrowed(
+                    "Capacity planning: approximate bytes used by a collection's vectors and payloads, extrapolated from a small payload sample.",
+                )),
+                input_schema: Arc::new(schemas::estimate_storage_input_schema()),
+                output_schema: None,
+                annotations: Some(
+                    ToolAnnotations::with_title("Estimate Storage")
+                        .read_only(true)
+                        .idempotent(true)
+                        .open_world(false),
+                ),
+                icons: None,
+            },
+            Tool {
+                name: Cow::Borrowed("storage-stats"),
+                title: Some("Storage Stats".to_string()),
+                description: Some(Cow::Borrowed(
+                    "Approximate disk usage per collection (or for one project): points, payload bytes from a sampled mean, raw vector bytes, and Qdrant's segment count.",
+                )),
+                input_schema: Arc::new(schemas::storage_stats_input_schema()),
+                output_schema: None,
+                annotations: Some(
+                    ToolAnnotations::with_title("Storage Stats")
+                        .read_only(true)
+                        .idempotent(true)
+                        .open_world(false),
+                ),
+                icons: None,
+            },
+            Tool {
+                name: Cow::Borrowed("get-document"),
+                title: Some("Get Document".to_string()),
+                description: Some(Cow::Borrowed(
+                    "Reassemble a pushed document from its chunks by doc_id, or the latest ingestion of a source_uri, stripping chunk overlap.",
+                )),
+                input_schema: Arc::new(schemas::get_document_input_schema()),
+                output_schema: None,
+                annotations: Some(
+                    ToolAnnotations::with_title("Get Document")
+                        .read_only(true)
+                        .idempotent(true)
+                        .open_world(false),
+                ),
+                icons: None,
+            },
+            Tool {
+                name: Cow::Borrowed("link-memories"),
+                title: Some("Link Memories".to_string()),
+                description: Some(Cow::Borrowed(
+                    "Record that one memory supersedes, refines, or relates to another existing memory; repeating a link is a no-op.",
+                )),
+                input_schema: Arc::new(schemas::link_memories_input_schema()),
+                output_schema: None,
+                annotations: Some(
+                    ToolAnnotations::with_title("Link Memories")
+                        .destructive(false)
+                        .idempotent(true)
+                        .open_world(false),
+                ),
+                icons: None,
+            },
+            Tool {
+                name: Cow::Borrowed("get-links"),
+                title: Some("Get Links".to_string()),
+                description: Some(Cow::Borrowed(
+                    "List a memory's outbound links and the memories linking to it.",
+                )),
+                input_schema: Arc::new(schemas::get_links_input_schema()),
+                output_schema: None,
+                annotations: Some(
+                    ToolAnnotations::with_title("Get Links")
+                        .read_only(true)
+                        .idempotent(true)
+                        .open_world(false),
+                ),
+                icons: None,
+            },
+            Tool {
+                name: Cow::Borrowed("warm-collection"),
+                title: Some("Warm Collection".to_string()),
+                description: Some(Cow::Borrowed(
+                    "After a large backfill, wait for indexing to finish (and optionally warm caches) before latency-sensitive searches.",
+                )),
+                input_schema: Arc::new(schemas::warm_collection_input_schema()),
+                output_schema: None,
+                annotations: Some(
+                    ToolAnnotations::with_title("Warm Collection")
+                        .read_only(true)
+                        .idempotent(true)
+                        .open_world(false),
+                ),
+                icons: None,
+            },
+            Tool {
+                name: Cow::Borrowed("rename-collection"),
+                title: Some("Rename Collection".to_string()),
+                description: Some(Cow::Borrowed(
+                    "Rename a collection by copying its points, vectors, and payloads into a new collection with the same vector settings, repointing its aliases, and deleting the original. Requires confirm: true.",
+                )),
+                input_schema: Arc::new(schemas::rename_collection_input_schema()),
+                output_schema: None,
+                annotations: Some(
+                    ToolAnnotations::with_title("Rename Collection")
+                        .destructive(true)
+                        .idempotent(false)
+                        .open_world(false),
+                ),
+                icons: None,
+            },
+            Tool {
+                name: Cow::Borrowed("move-to-collection"),
+                title: Some("Move To Collection".to_string()),
+                description: Some(Cow::Borrowed(
+                    "Reorganize memories by moving those matching a project, memory type, or tags into another existing collection, keeping their ids and metadata.",
+                )),
+                input_schema: Arc::new(schemas::move_to_collection_input_schema()),
+                output_schema: None,
+                annotations: Some(
+                    ToolAnnotations::with_title("Move To Collection")
+                        .destructive(true)
+                        .idempotent(true)
+                        .open_world(false),
+                ),
+                icons: None,
+            },
+            Tool {
+                name: Cow::Borrowed("prune"),
+                title: Some("Prune Memories".to_string()),
+                description: Some(Cow::Borrowed(
+                    "Bulk-delete memories of one type (episodic by default), optionally in one project, older than a timestamp or a duration like 90d. Requires confirm: true; dry_run reports the count.",
+                )),
+                input_schema: Arc::new(schemas::prune_input_schema()),
+                output_schema: None,
+                annotations: Some(
+                    ToolAnnotations::with_title("Prune Memories")
+                        .destructive(true)
+                        .idempotent(false)
+                        .open_world(false),
+                ),
+                icons: None,
+            },
+            Tool {
+                name: Cow::Borrowed("verify-collection"),
+                title: Some("Verify Collection".to_string()),
+                description: Some(Cow::Borrowed(
+                    "Check a collection for corrupted memories (hash mismatches, missing fields, bad timestamps) after a crash; optionally repair hashes or backfill timestamp_unix.",
+                )),
+                input_schema: Arc::new(schemas::verify_collection_input_schema()),
+                output_schema: None,
+                annotations: Some(
+                    ToolAnnotations::with_title("Verify Collection")
+                        .destructive(false)
+                        .idempotent(true)
+                        .open_world(false),
+                ),
+                icons: None,
+            },
+            Tool {
+                name: Cow::Borrowed("reembed-fallback"),
+                title: Some("Re-embed Fallback Points".to_string()),
+                description: Some(Cow::Borrowed(
+                    "Once the embedding provider is back, re-embed memories captured with degraded fallback vectors.",
+                )),
+                input_schema: Arc::new(schemas::reembed_fallback_input_schema()),
+                output_schema: None,
+                annotations: Some(
+                    ToolAnnotations::with_title("Re-embed Fallback Points")
+                        .destructive(false)
+                        .idempotent(true)
+                        .open_world(false),
+                ),
+                icons: None,
+            },
+            Tool {
+                name: Cow::Borrowed("compare-providers"),
+                title: Some("Compare Embedding Providers".to_string()),
+                description: Some(Cow::Borrowed(
+                    "Compare candidate and active embedding vectors. During an EMBEDDING_PROVIDER_NEXT migration, runs recent or given queries against both and reports overlap@k and score correlation.",
+                )),
+                input_schema: Arc::new(schemas::compare_providers_input_schema()),
+                output_schema: None,
+                annotations: Some(
+                    ToolAnnotations::with_title("Compare Embedding Providers")
+                        .read_only(true)
+                        .destructive(false)
+                        .idempotent(true)
+                        .open_world(false),
+                ),
+                icons: None,
+            },
+            Tool {
+                name: Cow::Borrowed("promote-provider"),
+                title: Some("Promote Embedding Provider".to_string()),
+                description: Some(Cow::Borrowed(
+                    "Switch to the candidate embedding provider. Serves the collection from its EMBEDDING_PROVIDER_NEXT candidate vectors and embeds with that provider from then on. Requires confirm: true.",
+                )),
+                input_schema: Arc::new(schemas::promote_provider_input_schema()),
+                output_schema: None,
+                annotations: Some(
+                    ToolAnnotations::with_title("Promote Embedding Provider")
+                        .destructive(true)
+                        .idempotent(false)
+                        .open_world(false),
+                ),
+                icons: None,
+            },
             Tool {
                 name: Cow::Borrowed("metrics"),
                 title: Some("Metrics Snapshot".to_string()),
                 description: Some(Cow::Borrowed(
-                    "Check ingestion volume and last chunk size at a glance.",
+                    "Check ingestion volume and last chunk size at a glance, globally or for one collection.",
+                )),
+                input_schema: Arc::new(schemas::metrics_input_schema()),
+                output_schema: Some(Arc::new(schemas::metrics_output_schema())),
+                annotations: Some(
+                    ToolAnnotations::with_title("Metrics Snapshot")
+                        .read_only(true)
+                        .idempotent(true)
+                        .open_world(false),
+                ),
+                icons: None,
+            },
+            Tool {
+                name: Cow::Borrowed("ping"),
+                title: Some("Ping".to_string()),
+                description: Some(Cow::Borrowed(
+                    "Cheap liveness check: returns ok, server version, and uptime without touching storage.",
                 )),
                 input_schema: Arc::new(schemas::empty_object_schema()),
                 output_schema: None,
                 annotations: Some(
-                    ToolAnnotations::with_title("Metrics Snapshot")
+                    ToolAnnotations::with_title("Ping")
                         .read_only(true)
                         .idempotent(true)
                         .open_world(false),
                 ),
                 icons: None,
             },
+            Tool {
+                name: Cow::Borrowed("sync-state"),
+                title: Some("Sync State".to_string()),
+                description: Some(Cow::Borrowed(
+                    "List or reset the per-project ingest cursors that let re-pushes of unchanged sources be skipped (requires STATE_DIR).",
+                )),
+                input_schema: Arc::new(schemas::sync_state_input_schema()),
+                output_schema: None,
+                annotations: Some(
+                    ToolAnnotations::with_title("Sync State")
+                        .destructive(false)
+                        .idempotent(true)
+                        .open_world(false),
+                ),
+                icons: None,
+            },
             Tool {
                 name: Cow::Borrowed("summarize"),
                 title: Some("Summarize Memories".to_string()),
@@ -142,7 +486,7 @@ impl RustyMemMcpServer {
                     "Turn episodic logs within a time window into a concise, reusable summary with provenance.",
                 )),
                 input_schema: summarize_schema.clone(),
-                output_schema: None,
+                output_schema: Some(Arc::new(schemas::summarize_output_schema())),
                 annotations: Some(
                     ToolAnnotations::with_title("Summarize Memories")
                         .destructive(false)
@@ -151,10 +495,58 @@ impl RustyMemMcpServer {
                 ),
                 icons: None,
             },
+            Tool {
+                name: Cow::Borrowed("refine-summary"),
+                title: Some("Refine Summary".to_string()),
+                description: Some(Cow::Borrowed(
+                    "Regenerate a stored summary from its source memories, steered by your feedback, and update it in place with a bumped revision.",
+                )),
+                input_schema: Arc::new(schemas::refine_summary_input_schema()),
+                output_schema: None,
+                annotations: Some(
+                    ToolAnnotations::with_title("Refine Summary")
+                        .destructive(false)
+                        .idempotent(false)
+                        .open_world(false),
+                ),
+                icons: None,
+            },
+            Tool {
+                name: Cow::Borrowed("find-summaries"),
+                title: Some("Find Summaries".to_string()),
+                description: Some(Cow::Borrowed(
+                    "List stored summaries for a project, newest first, filtered by label, topic, or when they were written.",
+                )),
+                input_schema: Arc::new(schemas::find_summaries_input_schema()),
+                output_schema: None,
+                annotations: Some(
+                    ToolAnnotations::with_title("Find Summaries")
+                        .read_only(true)
+                        .idempotent(true)
+                        .open_world(false),
+                ),
+                icons: None,
+            },
+            Tool {
+                name: Cow::Borrowed("check-summaries"),
+                title: Some("Check Summaries".to_string()),
+                description: Some(Cow::Borrowed(
+                    "Find summaries whose source memories were deleted, counting dangling source references; optionally tag them summary:orphaned.",
+                )),
+                input_schema: Arc::new(schemas::check_summaries_input_schema()),
+                output_schema: None,
+                annotations: Some(
+                    ToolAnnotations::with_title("Check Summaries")
+                        .destructive(false)
+                        .idempotent(true)
+                        .open_world(false),
+                ),
+                icons: None,
+            },
         ]
     }
 
-    fn describe_resources(&self) -> Vec<Resource> {
+    fn describe_resources() -> Vec<Resource> {
         let mut memory_types = RawResource::new(MEMORY_TYPES_URI, "memory-types");
         memory_types.description =
             Some("Supported memory_type values and default selection".into());
@@ -189,13 +581,27 @@ impl RustyMemMcpServer {
             name: "project-tags".into(),
             title: Some("Project Tags".into()),
             description: Some(
-                "Enumerate distinct tags for a project: replace {project_id} and call readResource"
+                "Enumerate distinct tags for a project: replace {project_id} and call readResource; append ?with_counts=true for [{tag, count, memory_types}] sorted by popularity, optionally scoped with &memory_type= and &min_count="
                     .into(),
             ),
             mime_type: Some(super::format::APPLICATION_JSON.into()),
         };
 
-        vec![tags_template.no_annotation()]
+        let recent_template = RawResourceTemplate {
+            uri_template: PROJECT_RECENT_TEMPLATE_URI.into(),
+            name: "project-recent".into(),
+            title: Some("Recent Project Memories".into()),
+            description: Some(
+                "Latest memories stored for a project, newest first, as [{id, timestamp, preview, memory_type, tags, source_uri}]: replace {project_id}; append ?limit= (default 20, max 100)"
+                    .into(),
+            ),
+            mime_type: Some(super::format::APPLICATION_JSON.into()),
+        };
+
+        vec![
+            tags_template.no_annotation(),
+            recent_template.no_annotation(),
+        ]
     }
 }
 
@@ -212,19 +618,29 @@ impl ServerHandler for RustyMemMcpServer {
                 .enable_tools()
                 .build(),
             server_info: implementation,
-            instructions: Some(
-                "Use this server to index, search, and summarize project memories for agents. Index source text, then retrieve concise context via semantic search with project/type/tag/time filters; summarize time‑bounded entries when needed.".into(),
-            ),
+            instructions: Some(self.instructions.to_string()),
             ..ServerInfo::default()
         }
     }
 
+    fn initialize(
+        &self,
+        request: InitializeRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> impl std::future::Future<Output = Result<InitializeResult, McpError>> + Send + '_ {
+        self.record_client_name(&request.client_info.name);
+        if context.peer.peer_info().is_none() {
+            context.peer.set_peer_info(request);
+        }
+        std::future::ready(Ok(self.get_info()))
+    }
+
     fn list_resources(
         &self,
         _request: Option<rmcp::model::PaginatedRequestParam>,
         _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
     ) -> impl std::future::Future<Output = Result<ListResourcesResult, McpError>> + Send + '_ {
-        let resources = self.describe_resources();
+        let resources = Self::describe_resources();
         std::future::ready(Ok(ListResourcesResult::with_all_items(resources)))
     }
 
@@ -243,7 +659,7 @@ impl ServerHandler for RustyMemMcpServer {
         _request: Option<rmcp::model::PaginatedRequestParam>,
         _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
     ) -> impl std::future::Future<Output = Result<ListToolsResult, McpError>> + Send + '_ {
-        let tools = self.describe_tools();
+        let tools = Self::describe_tools();
         std::future::ready(Ok(ListToolsResult::with_all_items(tools)))
     }
 
@@ -253,6 +669,7 @@ impl ServerHandler for RustyMemMcpServer {
         _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
     ) -> impl std::future::Future<Output = Result<ReadResourceResult, McpError>> + Send + '_ {
         let processing = self.processing.clone();
+        let limiter = self.limiter.clone();
         async move {
             match request.uri.as_str() {
                 MEMORY_TYPES_URI => Ok(ReadResourceResult {
@@ -274,6 +691,7 @@ impl ServerHandler for RustyMemMcpServer {
                                 &config.qdrant_url,
                                 &config.qdrant_collection_name,
                                 &snapshot,
+                                limiter.snapshot(),
                             ),
                         )],
                     })
@@ -283,9 +701,10 @@ impl ServerHandler for RustyMemMcpServer {
                     let projects = processing
                         .list_projects(&config.qdrant_collection_name)
                         .await
-                        .map_err(|err| McpError::internal_error(err.to_string(), None))?;
+                        .map_err(map_processing_error)?;
                     let payload = ProjectsSnapshot {
                         projects: projects.into_iter().collect(),
+                        default_memory_types: config.project_default_memory_type.clone(),
                     };
                     Ok(ReadResourceResult {
                         contents: vec![json_resource_contents(
@@ -346,10 +765,11 @@ impl ServerHandler for RustyMemMcpServer {
                 }
                 other
                     if other.starts_with(PROJECT_TAGS_PREFIX)
-                        && other.ends_with(PROJECT_TAGS_SUFFIX) =>
+                        && split_resource_query(other).0.ends_with(PROJECT_TAGS_SUFFIX) =>
                 {
+                    let (path, query) = split_resource_query(other);
                     let project_segment =
-                        &other[PROJECT_TAGS_PREFIX.len()..other.len() - PROJECT_TAGS_SUFFIX.len()];
+                        &path[PROJECT_TAGS_PREFIX.len()..path.len() - PROJECT_TAGS_SUFFIX.len()];
                     if project_segment.is_empty() {
                         return Err(McpError::invalid_params(
                             "Project identifier missing in resource URI",
@@ -357,14 +777,76 @@ impl ServerHandler for RustyMemMcpServer {
                         ));
                     }
                     let config = get_config();
-                    let tags = processing
-                        .list_tags(&config.qdrant_collection_name, Some(project_segment))
-                        .await
-                        .map_err(|err| McpError::internal_error(err.to_string(), None))?;
-                    let payload = ProjectTagsSnapshot {
-                        project_id: project_segment.to_string(),
-                        tags: tags.into_iter().collect(),
+                    let memory_type = query_value(query, MEMORY_TYPE_PARAM)
+                        .map(|value| {
+                            let normalized = value.trim().to_lowercase();
+                            if MEMORY_TYPES.contains(&normalized.as_str()) {
+                                Ok(normalized)
+                            } else {
+                                Err(McpError::invalid_params(
+                                    "`memory_type` must be one of episodic|semantic|procedural",
+                                    None,
+                                ))
+                            }
+                        })
+                        .transpose()?;
+                    let min_count = query_value(query, MIN_COUNT_PARAM)
+                        .map(|value| {
+                            value.trim().parse::<usize>().map_err(|_| {
+                                McpError::invalid_params(
+                                    "`min_count` must be a non-negative integer",
+                                    None,
+                                )
+                            })
+                        })
+                        .transpose()?;
+                    let body = if query_flag(query, WITH_COUNTS_PARAM)
+                        || memory_type.is_some()
+                        || min_count.is_some()
+                    {
+                        let counts = processing
+                            .list_tag_counts(
+                                &config.qdrant_collection_name,
+                                Some(project_segment),
+                                memory_type.as_deref(),
+                            )
+                            .await
+                            .map_err(map_processing_error)?;
+                        let payload = ProjectTagCountsSnapshot::new(
+                            project_segment.to_string(),
+                            counts,
+                            memory_type,
+                            min_count,
+                        );
+                        serialize_json(&payload, other)
+                    } else {
+                        let tags = processing
+                            .list_tags(&config.qdrant_collection_name, Some(project_segment))
+                            .await
+                            .map_err(map_processing_error)?;
+                        let payload = ProjectTagsSnapshot {
+                            project_id: project_segment.to_string(),
+                            tags: tags.into_iter().collect(),
+                        };
+                        serialize_json(&payload, other)
                     };
+                    Ok(ReadResourceResult {
+                        contents: vec![json_resource_contents(other, body)],
+                    })
+                }
+                other
+                    if other.starts_with(PROJECT_TAGS_PREFIX)
+                        && split_resource_query(other)
+                            .0
+                            .ends_with(PROJECT_RECENT_SUFFIX) =>
+                {
+                    let (project_id, limit) = parse_recent_uri(other)?;
+                    let config = get_config();
+                    let hits = processing
+                        .recent_memories(&config.qdrant_collection_name, project_id, limit)
+                        .await
+                        .map_err(map_processing_error)?;
+                    let payload = RecentMemoriesSnapshot::new(project_id.to_string(), limit, hits);
                     Ok(ReadResourceResult {
                         contents: vec![json_resource_contents(
                             other,
@@ -383,16 +865,68 @@ impl ServerHandler for RustyMemMcpServer {
     fn call_tool(
         &self,
         request: CallToolRequestParam,
-        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+        context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
     ) -> impl std::future::Future<Output = Result<CallToolResult, McpError>> + Send + '_ {
+        let client_name = self.client_name(&context.peer);
         let processing = self.processing.clone();
-        async move {
+        let search_cache = self.search_cache.clone();
+        let limiter = self.limiter.clone();
+        let started_at = self.started_at;
+        let span = tracing::info_span!(
+            "mcp.tool_call",
+            tool = %request.name,
+            collection = request
+                .arguments
+                .as_ref()
+                .and_then(|arguments| arguments.get("collection"))
+                .and_then(serde_json::Value::as_str),
+            error.code = field::Empty,
+            error.kind = field::Empty,
+            duration_ms = field::Empty,
+        );
+        let dispatch = async move {
+            // Diagnostics stay answerable while the gate is saturated.
+            let _permit = match request.name.as_ref() {
+                "metrics" | "ping" => None,
+                _ => Some(limiter.acquire().await.ok_or_else(server_busy)?),
+            };
             match request.name.as_ref() {
-                "push" => handle_push(&processing, request.arguments).await,
-                "search" => handle_search(&processing, request.arguments).await,
+                "push" => handle_push(&processing, client_name.as_deref(), request.arguments).await,
+                "search" => handle_search(&processing, &search_cache, request.arguments).await,
                 "get-collections" => handle_list_collections(&processing).await,
                 "new-collection" => handle_create_collection(&processing, request.arguments).await,
-                "metrics" => handle_metrics(&processing).await,
+                "describe-collection" => {
+                    handle_describe_collection(&processing, request.arguments).await
+                }
+                "apply-manifest" => handle_apply_manifest(&processing, request.arguments).await,
+                "estimate-storage" => handle_estimate_storage(&processing, request.arguments).await,
+                "storage-stats" => handle_storage_stats(&processing, request.arguments).await,
+                "get-document" => handle_get_document(&processing, request.arguments).await,
+                "link-memories" => handle_link_memories(&processing, request.arguments).await,
+                "get-links" => handle_get_links(&processing, request.arguments).await,
+                "warm-collection" => handle_warm_collection(&processing, request.arguments).await,
+                "move-to-collection" => {
+                    handle_move_to_collection(&processing, request.arguments).await
+                }
+                "rename-collection" => {
+                    handle_rename_collection(&processing, request.arguments).await
+                }
+                "prune" => handle_prune(&processing, request.arguments).await,
+                "verify-collection" => {
+                    handle_verify_collection(&processing, request.arguments).await
+                }
+                "reembed-fallback" => handle_reembed_fallback(&processing, request.arguments).await,
+                "compare-providers" => {
+                    handle_compare_providers(&processing, request.arguments).await
+                }
+                "promote-provider" => {
+                    handle_promote_provider(&processing, &search_cache, request.arguments).await
+                }
+                "metrics" => {
+                    handle_metrics(&processing, limiter.snapshot(), request.arguments).await
+                }
+                "ping" => handle_ping(started_at),
+                "sync-state" => handle_sync_state(&processing, request.arguments).await,
                 "summarize" => {
                     crate::mcp::handlers::summarize::handle_summarize(
                         &processing,
@@ -400,11 +934,168 @@ impl ServerHandler for RustyMemMcpServer {
                     )
                     .await
                 }
+                "refine-summary" => {
+                    crate::mcp::handlers::summarize::handle_refine_summary(
+                        &processing,
+                        request.arguments,
+                    )
+                    .await
+                }
+                "find-summaries" => {
+                    crate::mcp::handlers::summarize::handle_find_summaries(
+                        &processing,
+                        request.arguments,
+                    )
+                    .await
+                }
+                "check-summaries" => {
+                    crate::mcp::handlers::summarize::handle_check_summaries(
+                        &processing,
+                        request.arguments,
+                    )
+                    .await
+                }
                 other => Err(McpError::invalid_params(
                     format!("Unknown tool: {other}"),
                     None,
                 )),
             }
+        };
+        let recorder = span.clone();
+        async move {
+            let began = Instant::now();
+            let result = dispatch.await;
+            recorder.record("duration_ms", began.elapsed().as_millis() as u64);
+            if let Err(error) = &result {
+                recorder.record("error.code", error.code.0);
+                if let Some(kind) = error
+                    .data
+                    .as_ref()
+                    .and_then(|data| data.get("kind"))
+                    .and_then(Value::as_str)
+                {
+                    recorder.record("error.kind", kind);
+                }
+            }
+            result
+        }
+        .instrument(span)
+    }
+}
+
+/// Split a resource URI into its path and optional query string.
+fn split_resource_query(uri: &str) -> (&str, Option<&str>) {
+    match uri.split_once('?') {
+        Some((path, query)) => (path, Some(query)),
+        None => (uri, None),
+    }
+}
+
+/// Project and limit addressed by a `mcp://{project_id}/recent[?limit=N]` URI.
+fn parse_recent_uri(uri: &str) -> Result<(&str, usize), McpError> {
+    let (path, query) = split_resource_query(uri);
+    let project_id = path
+        .strip_prefix(PROJECT_TAGS_PREFIX)
+        .and_then(|rest| rest.strip_suffix(PROJECT_RECENT_SUFFIX))
+        .filter(|project_id| !project_id.is_empty())
+        .ok_or_else(|| {
+            McpError::invalid_params("Project identifier missing in resource URI", None)
+        })?;
+    let limit = match query_value(query, LIMIT_PARAM) {
+        Some(value) => value
+            .trim()
+            .parse::<usize>()
+            .ok()
+            .filter(|limit| (1..=RECENT_MAX_LIMIT).contains(limit))
+            .ok_or_else(|| {
+                McpError::invalid_params(
+                    format!("`limit` must be an integer between 1 and {RECENT_MAX_LIMIT}"),
+                    None,
+                )
+            })?,
+        None => RECENT_DEFAULT_LIMIT,
+    };
+    Ok((project_id, limit))
+}
+
+/// First value of `key` on a resource URI query string, if present.
+fn query_value<'a>(query: Option<&'a str>, key: &str) -> Option<&'a str> {
+    query
+        .into_iter()
+        .flat_map(|query| query.split('&'))
+        .find_map(|pair| match pair.split_once('=') {
+            Some((name, value)) if name == key => Some(value),
+            _ => None,
+        })
+}
+
+/// Interpret a boolean query parameter (`key`, `key=true`, `key=1`) on a resource URI.
+fn query_flag(query: Option<&str>, key: &str) -> bool {
+    query
+        .into_iter()
+        .flat_map(|query| query.split('&'))
+        .any(|pair| match pair.split_once('=') {
+            Some((name, value)) => {
+                name == key && matches!(value.to_ascii_lowercase().as_str(), "1" | "true" | "yes")
+            }
+            None => pair == key,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ensure_test_config;
+    use crate::mcp::instructions::{MAX_INSTRUCTIONS_CHARS, TOOL_FLOW};
+
+    #[test]
+    fn instructions_cover_every_registered_tool_and_resource() {
+        ensure_test_config();
+        let tools = RustyMemMcpServer::describe_tools();
+        let resources = RustyMemMcpServer::describe_resources();
+        let instructions = build_instructions(&tools, &resources);
+
+        assert!(instructions.chars().count() <= MAX_INSTRUCTIONS_CHARS);
+        for tool in &tools {
+            assert!(
+                instructions.contains(&format!(". {} ", tool.name)),
+                "`{}` is missing from the instructions",
+                tool.name
+            );
+            assert!(
+                TOOL_FLOW.contains(&tool.name.as_ref()),
+                "`{}` needs a place in TOOL_FLOW",
+                tool.name
+            );
+        }
+        for name in TOOL_FLOW {
+            assert!(
+                tools.iter().any(|tool| tool.name == *name),
+                "TOOL_FLOW lists unregistered tool `{name}`"
+            );
+        }
+        for resource in &resources {
+            assert!(instructions.contains(resource.uri.as_str()));
+        }
+    }
+
+    #[test]
+    fn recent_uri_routes_project_and_limit() {
+        assert_eq!(
+            parse_recent_uri("mcp://repo-a/recent").expect("default limit"),
+            ("repo-a", RECENT_DEFAULT_LIMIT)
+        );
+        assert_eq!(
+            parse_recent_uri("mcp://repo-a/recent?limit=100").expect("max limit"),
+            ("repo-a", 100)
+        );
+        for uri in [
+            "mcp:///recent",
+            "mcp://repo-a/recent?limit=0",
+            "mcp://repo-a/recent?limit=101",
+            "mcp://repo-a/recent?limit=ten",
+        ] {
+            assert!(parse_recent_uri(uri).is_err(), "{uri}");
         }
     }
 }
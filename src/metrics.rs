@@ -4,22 +4,67 @@
 //! - Documents indexed
 //! - Chunks indexed (cumulative)
 //! - The effective chunk size used for the last ingestion
+//! - Chunks skipped as exact duplicates or because their source was unchanged, which is the
+//!   number of embedding calls saved
+//! - Embedding vectors rejected as invalid (NaN, infinite, or all-zero components)
+//!
+//! The same counters are also kept per collection so multi-collection servers can break
+//! activity down by target.
 //!
 //! The snapshot is surfaced via HTTP (`GET /metrics`) and MCP (`metrics` tool) to help validate
 //! chunking heuristics and overall ingestion activity during development.
 
+use std::collections::HashMap;
+use std::sync::Mutex;
 use std::sync::atomic::{AtomicU64, Ordering};
 
 /// Thread-safe counters describing ingestion activity.
 ///
 /// The struct intentionally stays minimal—just atomic counters—so it can be cloned freely and
 /// queried without holding locks.  The metrics surface already exposes the most recent chunk size
-/// so front-ends can teach how the automatic sizing behaves over time.
+/// so front-ends can teach how the automatic sizing behaves over time.  Per-collection breakdowns
+/// live behind a short-lived mutex that is only touched once per ingested document.
 #[derive(Default)]
 pub struct CodeMetrics {
     documents_indexed: AtomicU64,
     chunks_indexed: AtomicU64,
     last_chunk_size: AtomicU64,
+    dedupe_chunks_seen: AtomicU64,
+    dedupe_skipped_exact: AtomicU64,
+    dedupe_skipped_unchanged: AtomicU64,
+    invalid_embeddings: AtomicU64,
+    collections: Mutex<HashMap<String, CollectionCounters>>,
+}
+
+/// Plain counters tracked for a single collection.
+#[derive(Debug, Default, Clone, Copy)]
+struct CollectionCounters {
+    documents_indexed: u64,
+    chunks_indexed: u64,
+    last_chunk_size: u64,
+    dedupe: DedupeCounters,
+    invalid_embeddings: u64,
+}
+
+/// Chunk dedupe counters shared by the global and per-collection views.
+#[derive(Debug, Default, Clone, Copy)]
+struct DedupeCounters {
+    chunks_seen: u64,
+    skipped_exact: u64,
+    skipped_unchanged: u64,
+}
+
+impl DedupeCounters {
+    fn snapshot(self) -> DedupeSnapshot {
+        let embeddings_saved = self.skipped_exact + self.skipped_unchanged;
+        DedupeSnapshot {
+            chunks_seen: self.chunks_seen,
+            skipped_exact: self.skipped_exact,
+            skipped_unchanged_source: self.skipped_unchanged,
+            embeddings_saved,
+            duplicate_ratio: duplicate_ratio(embeddings_saved, self.chunks_seen),
+        }
+    }
 }
 
 impl CodeMetrics {
@@ -30,16 +75,71 @@ impl CodeMetrics {
 
     /// Record a processed document and the number of chunks produced for it.
     ///
-    /// The caller supplies the number of chunks and the chunk size used for the ingestion.  We
-    /// capture the chunk size so diagnostics can show how the automatic heuristics evolve when
-    /// different embedding models are configured.
-    pub fn record_document(&self, chunk_count: u64, chunk_size: u64) {
+    /// The caller supplies the target collection, the number of chunks, and the chunk size used
+    /// for the ingestion.  We capture the chunk size so diagnostics can show how the automatic
+    /// heuristics evolve when different embedding models are configured.
+    pub fn record_document(&self, collection: &str, chunk_count: u64, chunk_size: u64) {
         self.documents_indexed.fetch_add(1, Ordering::Relaxed);
         self.chunks_indexed
             .fetch_add(chunk_count, Ordering::Relaxed);
         // Persist the effective chunk size so the dashboard endpoints can explain
         // how the automatic sizing behaved for the last ingestion.
         self.last_chunk_size.store(chunk_size, Ordering::Relaxed);
+
+        let mut collections = self
+            .collections
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let counters = collections.entry(collection.to_string()).or_default();
+        counters.documents_indexed += 1;
+        counters.chunks_indexed += chunk_count;
+        counters.last_chunk_size = chunk_size;
+    }
+
+    /// Record how many of a push's chunks were skipped instead of embedded.
+    ///
+    /// `chunks_seen` counts every chunk the push produced, including those skipped: exact
+    /// in-request duplicates (`skipped_exact`) and the stored chunks of a source that was
+    /// unchanged since its last sync (`skipped_unchanged`).
+    pub fn record_dedupe(
+        &self,
+        collection: &str,
+        chunks_seen: u64,
+        skipped_exact: u64,
+        skipped_unchanged: u64,
+    ) {
+        self.dedupe_chunks_seen
+            .fetch_add(chunks_seen, Ordering::Relaxed);
+        self.dedupe_skipped_exact
+            .fetch_add(skipped_exact, Ordering::Relaxed);
+        self.dedupe_skipped_unchanged
+            .fetch_add(skipped_unchanged, Ordering::Relaxed);
+
+        let mut collections = self
+            .collections
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let dedupe = &mut collections
+            .entry(collection.to_string())
+            .or_default()
+            .dedupe;
+        dedupe.chunks_seen += chunks_seen;
+        dedupe.skipped_exact += skipped_exact;
+        dedupe.skipped_unchanged += skipped_unchanged;
+    }
+
+    /// Record an embedding response rejected because a vector was NaN, infinite, or all-zero.
+    pub fn record_invalid_embedding(&self, collection: &str) {
+        self.invalid_embeddings.fetch_add(1, Ordering::Relaxed);
+
+        let mut collections = self
+            .collections
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        collections
+            .entry(collection.to_string())
+            .or_default()
+            .invalid_embeddings += 1;
     }
 
     /// Return a snapshot of the current counters.
@@ -58,6 +158,34 @@ impl CodeMetrics {
                     Some(last)
                 }
             },
+            dedupe: DedupeCounters {
+                chunks_seen: self.dedupe_chunks_seen.load(Ordering::Relaxed),
+                skipped_exact: self.dedupe_skipped_exact.load(Ordering::Relaxed),
+                skipped_unchanged: self.dedupe_skipped_unchanged.load(Ordering::Relaxed),
+            }
+            .snapshot(),
+            invalid_embeddings: self.invalid_embeddings.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Return the counters recorded for a single collection.
+    ///
+    /// Collections that have not received any documents yield an empty snapshot rather than an
+    /// error so callers can poll a collection before its first ingestion.
+    pub fn collection_snapshot(&self, collection: &str) -> MetricsSnapshot {
+        let counters = self
+            .collections
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(collection)
+            .copied()
+            .unwrap_or_default();
+        MetricsSnapshot {
+            documents_indexed: counters.documents_indexed,
+            chunks_indexed: counters.chunks_indexed,
+            last_chunk_size: (counters.last_chunk_size > 0).then_some(counters.last_chunk_size),
+            dedupe: counters.dedupe.snapshot(),
+            invalid_embeddings: counters.invalid_embeddings,
         }
     }
 }
@@ -75,6 +203,34 @@ pub struct MetricsSnapshot {
     /// Chunk size used for the most recently ingested document, if any.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_chunk_size: Option<u64>,
+    /// Chunks skipped instead of embedded.
+    pub dedupe: DedupeSnapshot,
+    /// Embedding responses rejected for NaN, infinite, or all-zero vectors.
+    pub invalid_embeddings: u64,
+}
+
+/// Cumulative chunk dedupe counters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DedupeSnapshot {
+    /// Chunks produced by pushes, skipped ones included.
+    pub chunks_seen: u64,
+    /// Chunks skipped as exact duplicates within their push.
+    pub skipped_exact: u64,
+    /// Stored chunks of sources skipped as unchanged since their last sync.
+    pub skipped_unchanged_source: u64,
+    /// Embedding calls avoided: `skipped_exact + skipped_unchanged_source`.
+    pub embeddings_saved: u64,
+    /// `embeddings_saved / chunks_seen`, or 0 before any chunk was seen.
+    pub duplicate_ratio: f64,
+}
+
+/// `skipped / total`, or 0 when `total` is 0.
+pub fn duplicate_ratio(skipped: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        skipped as f64 / total as f64
+    }
 }
 
 #[cfg(test)]
@@ -84,8 +240,8 @@ mod tests {
     #[test]
     fn records_documents_and_chunks() {
         let metrics = CodeMetrics::new();
-        metrics.record_document(2, 128);
-        metrics.record_document(3, 256);
+        metrics.record_document("docs", 2, 128);
+        metrics.record_document("docs", 3, 256);
 
         let snapshot = metrics.snapshot();
         assert_eq!(snapshot.documents_indexed, 2);
@@ -100,4 +256,61 @@ mod tests {
         assert_eq!(metrics.snapshot().chunks_indexed, 0);
         assert_eq!(metrics.snapshot().last_chunk_size, None);
     }
+
+    #[test]
+    fn tracks_counters_per_collection() {
+        let metrics = CodeMetrics::new();
+        metrics.record_document("alpha", 2, 128);
+        metrics.record_document("beta", 4, 512);
+        metrics.record_document("alpha", 1, 256);
+
+        let alpha = metrics.collection_snapshot("alpha");
+        assert_eq!(alpha.documents_indexed, 2);
+        assert_eq!(alpha.chunks_indexed, 3);
+        assert_eq!(alpha.last_chunk_size, Some(256));
+
+        let beta = metrics.collection_snapshot("beta");
+        assert_eq!(beta.documents_indexed, 1);
+        assert_eq!(beta.chunks_indexed, 4);
+        assert_eq!(beta.last_chunk_size, Some(512));
+
+        let unknown = metrics.collection_snapshot("gamma");
+        assert_eq!(unknown.documents_indexed, 0);
+        assert_eq!(unknown.last_chunk_size, None);
+
+        assert_eq!(metrics.snapshot().documents_indexed, 3);
+        assert_eq!(metrics.snapshot().chunks_indexed, 7);
+    }
+
+    #[test]
+    fn dedupe_ratios_accumulate_globally_and_per_collection() {
+        let metrics = CodeMetrics::new();
+        // 10 chunks, 2 exact duplicates skipped.
+        metrics.record_dedupe("alpha", 10, 2, 0);
+        // An unchanged source whose 6 stored chunks were not re-embedded.
+        metrics.record_dedupe("alpha", 6, 0, 6);
+        // 4 fresh chunks in another collection.
+        metrics.record_dedupe("beta", 4, 0, 0);
+
+        let alpha = metrics.collection_snapshot("alpha").dedupe;
+        assert_eq!(alpha.chunks_seen, 16);
+        assert_eq!(alpha.skipped_exact, 2);
+        assert_eq!(alpha.skipped_unchanged_source, 6);
+        assert_eq!(alpha.embeddings_saved, 8);
+        assert_eq!(alpha.duplicate_ratio, 0.5);
+
+        let global = metrics.snapshot().dedupe;
+        assert_eq!(global.chunks_seen, 20);
+        assert_eq!(global.embeddings_saved, 8);
+        assert_eq!(global.duplicate_ratio, 0.4);
+
+        assert_eq!(
+            metrics.collection_snapshot("beta").dedupe.duplicate_ratio,
+            0.0
+        );
+        assert_eq!(
+            metrics.collection_snapshot("gamma").dedupe,
+            DedupeSnapshot::default()
+        );
+    }
 }
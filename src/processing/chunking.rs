@@ -11,46 +11,126 @@
 //!   spans around boundaries remain visible to retrieval and downstream prompts.
 //! - Token counting: prefer `tiktoken-rs` for OpenAI/known encodings; fall back to a whitespace
 //!   counter when the model’s tokenizer is unavailable (common for some Ollama models).
+//! - Code blocks: fenced ```` ``` ```` blocks are swapped for placeholders before splitting and
+//!   restored afterwards, so a block is never cut in half. Blocks larger than the budget become
+//!   standalone chunks tagged [`CODE_BLOCK_CONTENT_KIND`].
+//! - Source files: `TEXT_SPLITTER_STRATEGY=code` routes pushes through the function-aligned
+//!   splitter in `processing::code`, which falls back to this chunker for oversized units.
 
-use crate::config::EmbeddingProvider;
+use crate::config::{EmbeddingProvider, TokenizerFallback};
 use anyhow::Error as TokenizerError;
 use semchunk_rs::Chunker;
-use std::sync::Arc;
+use std::{ops::Range, sync::Arc};
 use tiktoken_rs::{
-    CoreBPE, cl100k_base, get_bpe_from_model, model::get_context_size, o200k_base, p50k_base,
-    p50k_edit, r50k_base,
+    CoreBPE, cl100k_base, get_bpe_from_model,
+    model::get_context_size,
+    o200k_base, p50k_base, p50k_edit, r50k_base,
+    tokenizer::{Tokenizer, get_tokenizer},
 };
 
 use super::types::ChunkingError;
 
-type TokenCounter = Arc<dyn Fn(&str) -> usize + Send + Sync>;
+/// Shared token-counting closure.
+pub(crate) type TokenCounter = Arc<dyn Fn(&str) -> usize + Send + Sync>;
+
+/// `content_kind` payload value for a fenced code block stored as its own chunk.
+pub(crate) const CODE_BLOCK_CONTENT_KIND: &str = "code_block";
+
+/// Private-use characters delimiting a code block placeholder (`\u{E000}<index>\u{E001}`).
+const PLACEHOLDER_OPEN: char = '\u{E000}';
+const PLACEHOLDER_CLOSE: char = '\u{E001}';
+
+/// Chunk produced by [`chunk_text`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TextChunk {
+    /// Chunk text, with any fenced code blocks restored.
+    pub(crate) text: String,
+    /// `content_kind` payload value, set for oversized code blocks stored on their own.
+    pub(crate) content_kind: Option<&'static str>,
+    /// `language` payload value detected by the code splitter.
+    pub(crate) language: Option<&'static str>,
+    /// `symbol` payload value: the first definition in a code chunk.
+    pub(crate) symbol: Option<String>,
+}
+
+impl From<String> for TextChunk {
+    fn from(text: String) -> Self {
+        Self {
+            text,
+            content_kind: None,
+            language: None,
+            symbol: None,
+        }
+    }
+}
+
+/// tiktoken encodings that can be selected by name (`gpt2` is also accepted as `r50k_base`).
+pub(crate) const ENCODING_NAMES: &[&str] = &[
+    "cl100k_base",
+    "o200k_base",
+    "p50k_base",
+    "p50k_edit",
+    "r50k_base",
+];
 
 const MIN_AUTOMATIC_CHUNK_SIZE: usize = 256;
 const MAX_AUTOMATIC_CHUNK_SIZE: usize = 1024;
 
+/// Default floor for `TEXT_SPLITTER_CHUNK_SIZE` overrides (`TEXT_SPLITTER_MIN_CHUNK_SIZE`).
+pub(crate) const DEFAULT_MIN_CHUNK_SIZE_OVERRIDE: usize = 16;
+
+/// How the chunk size for a request was chosen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ChunkSizing {
+    /// Token budget per chunk.
+    pub(crate) chunk_size: usize,
+    /// The size was derived from the model's context window rather than an override.
+    pub(crate) auto_sized: bool,
+    /// Embedding context window of the configured model.
+    pub(crate) context_window: usize,
+    /// Size before it was clamped into the automatic bounds or raised to the override floor.
+    pub(crate) clamped_from: Option<usize>,
+}
+
 /// Determine the chunk size for a request, respecting overrides and safe defaults.
 ///
 /// Precedence:
-/// 1) Explicit override (e.g., `TEXT_SPLITTER_CHUNK_SIZE`) wins and is clamped at `>= 1`.
+/// 1) Explicit override (e.g., `TEXT_SPLITTER_CHUNK_SIZE`) wins and is raised to
+///    `min_override` (and at least `1`); large overrides are honored as-is.
 /// 2) Otherwise, derive from the provider/model context window and divide by `4` (or `8` when
 ///    `use_safe_defaults` is true). The result is clamped into `[256, 1024]`.
 ///
 /// The derived size is logged by the processing service and exposed via metrics (`lastChunkSize`).
 pub(crate) fn determine_chunk_size(
     override_size: Option<usize>,
+    min_override: usize,
     provider: EmbeddingProvider,
     model: &str,
     use_safe_defaults: bool,
-) -> usize {
-    if let Some(explicit) = override_size {
-        return explicit.max(1);
+) -> ChunkSizing {
+    let context_window = embedding_context_window(provider, model);
+    let (requested, chunk_size) = match override_size {
+        Some(explicit) => (explicit, explicit.max(min_override).max(1)),
+        None => {
+            let divisor = if use_safe_defaults { 8 } else { 4 };
+            let base = (context_window / divisor).max(1);
+            (
+                base,
+                base.clamp(MIN_AUTOMATIC_CHUNK_SIZE, MAX_AUTOMATIC_CHUNK_SIZE),
+            )
+        }
+    };
+    ChunkSizing {
+        chunk_size,
+        auto_sized: override_size.is_none(),
+        context_window,
+        clamped_from: (requested != chunk_size).then_some(requested),
     }
+}
 
-    let window = embedding_context_window(provider, model);
-    let divisor = if use_safe_defaults { 8 } else { 4 };
-    let base = (window / divisor).max(1);
-    let candidate = base.max(MIN_AUTOMATIC_CHUNK_SIZE);
-    candidate.clamp(MIN_AUTOMATIC_CHUNK_SIZE, MAX_AUTOMATIC_CHUNK_SIZE)
+/// Return the override when it falls below `floor` and would produce degenerate chunks.
+pub(crate) fn override_below_floor(override_size: Option<usize>, floor: usize) -> Option<usize> {
+    override_size.filter(|size| *size < floor)
 }
 
 /// Look up the embedding context window for a given provider/model combination.
@@ -91,10 +171,13 @@ fn ollama_embedding_context_window(model: &str) -> usize {
 
 /// Chunk text into semantic segments using the configured token counter.
 ///
-/// - `chunk_size` is a hard upper bound on the token count per segment.
+/// - `chunk_size` is a hard upper bound on the token count per segment, except for fenced code
+///   blocks that alone exceed it: those are emitted whole as standalone chunks.
 /// - `overlap` requests a sliding-window overlap (tokens) between adjacent chunks after semantic
-///   splitting; the function guarantees the final strings respect the token budget.
-/// - Tokenization uses `tiktoken` when possible and falls back to whitespace counting.
+///   splitting; the overlap only fills the budget a chunk leaves free and never starts inside a
+///   code block.
+/// - Tokenization uses `tiktoken` when possible and falls back to whitespace counting, or to
+///   `tokenizer_fallback` when the encoding data itself cannot be loaded.
 ///
 /// Returns an empty vector when the input text is all whitespace.
 pub(crate) fn chunk_text(
@@ -103,7 +186,8 @@ pub(crate) fn chunk_text(
     overlap: usize,
     provider: EmbeddingProvider,
     model: &str,
-) -> Result<Vec<String>, ChunkingError> {
+    tokenizer_fallback: TokenizerFallback,
+) -> Result<Vec<TextChunk>, ChunkingError> {
     if chunk_size == 0 {
         return Err(ChunkingError::InvalidChunkSize);
     }
@@ -111,7 +195,7 @@ pub(crate) fn chunk_text(
         return Ok(Vec::new());
     }
 
-    let token_counter = build_token_counter(provider, model)?;
+    let token_counter = build_token_counter(provider, model, tokenizer_fallback)?;
     Ok(chunk_text_with_counter(
         text,
         chunk_size,
@@ -123,15 +207,18 @@ pub(crate) fn chunk_text(
 /// Build a token counter for the given provider/model.
 ///
 /// Uses OpenAI encodings when possible and gracefully falls back to whitespace tokenization for
-/// unknown or locally aliased models (typical with Ollama). The fallback is logged at `warn` level
-/// to aid diagnosis while keeping ingestion flowing.
+/// unknown or locally aliased models (typical with Ollama). When the encoding data cannot be
+/// loaded at all, a non-`cl100k` `tokenizer_fallback` takes over for either provider. Fallbacks
+/// are logged at `warn` level to aid diagnosis while keeping ingestion flowing.
 pub(crate) fn build_token_counter(
     provider: EmbeddingProvider,
     model: &str,
+    tokenizer_fallback: TokenizerFallback,
 ) -> Result<TokenCounter, ChunkingError> {
+    let counter = with_tokenizer_fallback(build_tiktoken_counter(model), tokenizer_fallback);
     match provider {
-        EmbeddingProvider::OpenAI => build_tiktoken_counter(model),
-        EmbeddingProvider::Ollama => match build_tiktoken_counter(model) {
+        EmbeddingProvider::OpenAI => counter,
+        EmbeddingProvider::Ollama => match counter {
             Ok(counter) => Ok(counter),
             Err(error) => {
                 tracing::warn!(
@@ -145,6 +232,68 @@ pub(crate) fn build_token_counter(
     }
 }
 
+/// Replace a tokenizer load failure with the configured fallback counter, if any.
+fn with_tokenizer_fallback(
+    counter: Result<TokenCounter, ChunkingError>,
+    fallback: TokenizerFallback,
+) -> Result<TokenCounter, ChunkingError> {
+    let error = match counter {
+        Ok(counter) => return Ok(counter),
+        Err(error) => error,
+    };
+    let replacement = match fallback {
+        TokenizerFallback::Cl100k => return Err(error),
+        TokenizerFallback::Whitespace => default_token_counter(),
+        TokenizerFallback::Char => char_token_counter(),
+    };
+    tracing::warn!(
+        error = %error,
+        fallback = ?fallback,
+        "Tokenizer data unavailable; downgrading to TOKENIZER_FALLBACK counter"
+    );
+    Ok(replacement)
+}
+
+/// Name of the tiktoken encoding [`build_token_counter`] resolves for `model`.
+///
+/// Unknown models resolve to `cl100k_base`, matching the counter's own fallback.
+pub(crate) fn tokenizer_name(model: &str) -> &'static str {
+    let target = model.trim();
+    match get_tokenizer(target) {
+        Some(Tokenizer::O200kBase) => "o200k_base",
+        Some(Tokenizer::Cl100kBase) => "cl100k_base",
+        Some(Tokenizer::P50kBase) => "p50k_base",
+        Some(Tokenizer::P50kEdit) => "p50k_edit",
+        Some(Tokenizer::R50kBase | Tokenizer::Gpt2) => "r50k_base",
+        None => match target {
+            "o200k_base" => "o200k_base",
+            "p50k_base" => "p50k_base",
+            "p50k_edit" => "p50k_edit",
+            "r50k_base" | "gpt2" => "r50k_base",
+            _ => "cl100k_base",
+        },
+    }
+}
+
+/// Token counter for a tiktoken encoding named directly, independent of any model.
+///
+/// Returns `None` when `name` is not a known encoding (see [`ENCODING_NAMES`]).
+pub(crate) fn encoding_counter(name: &str) -> Option<Result<TokenCounter, ChunkingError>> {
+    let encoding = encoding_from_name(name)?;
+    Some(
+        encoding
+            .map(|encoding| {
+                let encoding = Arc::new(encoding);
+                Arc::new(move |segment: &str| encoding.encode_ordinary(segment).len())
+                    as TokenCounter
+            })
+            .map_err(|source| ChunkingError::Tokenizer {
+                model: name.to_string(),
+                source,
+            }),
+    )
+}
+
 fn build_tiktoken_counter(model: &str) -> Result<TokenCounter, ChunkingError> {
     let normalized = model.trim();
     let target = if normalized.is_empty() {
@@ -207,23 +356,181 @@ fn default_token_counter() -> TokenCounter {
     })
 }
 
+fn char_token_counter() -> TokenCounter {
+    Arc::new(|segment: &str| segment.chars().count())
+}
+
 /// Lower-level chunker that accepts an explicit token counter.
 ///
 /// You likely want [`chunk_text`]; this helper exists for tests and for callers that need to
 /// plug in a custom token counter.
-fn chunk_text_with_counter(
+pub(super) fn chunk_text_with_counter(
     text: &str,
     chunk_size: usize,
     overlap: usize,
     token_counter: TokenCounter,
-) -> Vec<String> {
-    let counter_for_chunker = token_counter.clone();
+) -> Vec<TextChunk> {
+    // Text that already uses the placeholder characters is split as-is rather than risk
+    // confusing its content with a placeholder.
+    if text.contains([PLACEHOLDER_OPEN, PLACEHOLDER_CLOSE]) {
+        return chunk_prose(text, &[], chunk_size, overlap, &token_counter);
+    }
+
+    let mut chunks = Vec::new();
+    let mut prose = String::with_capacity(text.len());
+    let mut blocks: Vec<(&str, usize)> = Vec::new();
+    let mut cursor = 0;
+    for range in fenced_block_ranges(text) {
+        prose.push_str(&text[cursor..range.start]);
+        cursor = range.end;
+        let block = &text[range];
+        let tokens = token_counter.as_ref()(block);
+        if tokens > chunk_size {
+            // The line breaks around a standalone block belong to neither neighbour.
+            chunks.extend(chunk_prose(
+                prose.trim_end(),
+                &blocks,
+                chunk_size,
+                overlap,
+                &token_counter,
+            ));
+            prose.clear();
+            blocks.clear();
+            cursor += text[cursor..].len() - text[cursor..].trim_start().len();
+            chunks.push(TextChunk {
+                content_kind: Some(CODE_BLOCK_CONTENT_KIND),
+                ..TextChunk::from(block.to_string())
+            });
+        } else {
+            prose.push(PLACEHOLDER_OPEN);
+            prose.push_str(&blocks.len().to_string());
+            prose.push(PLACEHOLDER_CLOSE);
+            blocks.push((block, tokens));
+        }
+    }
+    prose.push_str(&text[cursor..]);
+    chunks.extend(chunk_prose(
+        &prose,
+        &blocks,
+        chunk_size,
+        overlap,
+        &token_counter,
+    ));
+    chunks
+}
+
+/// Split text whose code blocks were replaced by placeholders, then restore the blocks.
+///
+/// Each placeholder counts as its block's tokens, so the chunker budgets for the restored text
+/// and never splits a placeholder (every inlined block fits the budget on its own).
+fn chunk_prose(
+    text: &str,
+    blocks: &[(&str, usize)],
+    chunk_size: usize,
+    overlap: usize,
+    token_counter: &TokenCounter,
+) -> Vec<TextChunk> {
+    if text.trim().is_empty() {
+        return Vec::new();
+    }
+
+    let counter: TokenCounter = if blocks.is_empty() {
+        token_counter.clone()
+    } else {
+        let base = token_counter.clone();
+        let block_tokens: Vec<usize> = blocks.iter().map(|(_, tokens)| *tokens).collect();
+        Arc::new(move |segment: &str| {
+            let parts = placeholder_parts(segment, block_tokens.len());
+            let plain: String = parts.iter().map(|(text, _)| *text).collect();
+            let inlined: usize = parts
+                .iter()
+                .filter_map(|(_, block)| block.map(|index| block_tokens[index]))
+                .sum();
+            base.as_ref()(&plain) + inlined
+        })
+    };
+    let counter_for_chunker = counter.clone();
     let chunker = Chunker::new(
         chunk_size,
         Box::new(move |segment: &str| counter_for_chunker.as_ref()(segment)),
     );
     let base_chunks = chunker.chunk(text);
-    apply_overlap(base_chunks, chunk_size, overlap, &token_counter)
+    apply_overlap(base_chunks, chunk_size, overlap, &counter)
+        .into_iter()
+        .map(|chunk| {
+            let text = if blocks.is_empty() {
+                chunk
+            } else {
+                placeholder_parts(&chunk, blocks.len())
+                    .into_iter()
+                    .flat_map(|(text, block)| [text, block.map_or("", |index| blocks[index].0)])
+                    .collect()
+            };
+            TextChunk::from(text)
+        })
+        .collect()
+}
+
+/// Byte ranges of closed fenced code blocks, each spanning its opening to its closing fence.
+///
+/// Fences follow CommonMark: at most three spaces of indentation, three or more backticks, and
+/// a closing fence at least as long as the opening one. An unclosed fence is left as plain text.
+fn fenced_block_ranges(text: &str) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut open: Option<(usize, usize)> = None;
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+        let content = line.trim_end_matches(['\n', '\r']);
+        let unindented = content.trim_start_matches(' ');
+        if content.len() - unindented.len() > 3 {
+            continue;
+        }
+        let info = unindented.trim_start_matches('`');
+        let fence = unindented.len() - info.len();
+        if fence < 3 {
+            continue;
+        }
+        match open {
+            None if !info.contains('`') => open = Some((line_start, fence)),
+            Some((start, opening)) if fence >= opening && info.trim().is_empty() => {
+                ranges.push(start..line_start + content.len());
+                open = None;
+            }
+            _ => {}
+        }
+    }
+    ranges
+}
+
+/// Split `segment` into text runs, each followed by the index of the placeholder after it.
+///
+/// Malformed placeholders or indexes outside `block_count` stay part of the text.
+fn placeholder_parts(segment: &str, block_count: usize) -> Vec<(&str, Option<usize>)> {
+    let mut parts = Vec::new();
+    let mut run_start = 0;
+    let mut search_from = 0;
+    while let Some(found) = segment[search_from..].find(PLACEHOLDER_OPEN) {
+        let open = search_from + found;
+        let digits_start = open + PLACEHOLDER_OPEN.len_utf8();
+        search_from = digits_start;
+        let Some(length) = segment[digits_start..].find(PLACEHOLDER_CLOSE) else {
+            break;
+        };
+        let Some(index) = segment[digits_start..digits_start + length]
+            .parse::<usize>()
+            .ok()
+            .filter(|index| *index < block_count)
+        else {
+            continue;
+        };
+        parts.push((&segment[run_start..open], Some(index)));
+        run_start = digits_start + length + PLACEHOLDER_CLOSE.len_utf8();
+        search_from = run_start;
+    }
+    parts.push((&segment[run_start..], None));
+    parts
 }
 
 /// Apply a token-limited overlap between the tail of the previous chunk and the current one.
@@ -278,16 +585,23 @@ fn build_overlapped_chunk(
         return current.to_string();
     }
 
-    let tail = tail_with_token_limit(previous, overlap, token_counter);
-    let mut combined = String::with_capacity(tail.len() + current.len() + 1);
-
-    if !tail.is_empty() {
-        combined.push_str(tail);
-        if !ends_with_whitespace(tail) && !starts_with_whitespace(current) {
-            combined.push(' ');
-        }
+    // Only text after the previous chunk's last code block is repeated, so the overlap never
+    // begins inside a block, and it only fills the room `current` leaves in the budget, so the
+    // trim below never cuts into `current` (or a placeholder within it).
+    let after_code = previous.rfind(PLACEHOLDER_CLOSE).map_or(previous, |end| {
+        &previous[end + PLACEHOLDER_CLOSE.len_utf8()..]
+    });
+    let room = chunk_size.saturating_sub(token_counter.as_ref()(current));
+    let tail = tail_with_token_limit(after_code, overlap.min(room), token_counter);
+    if tail.is_empty() {
+        return current.to_string();
     }
 
+    let mut combined = String::with_capacity(tail.len() + current.len() + 1);
+    combined.push_str(tail);
+    if !ends_with_whitespace(tail) && !starts_with_whitespace(current) {
+        combined.push(' ');
+    }
     combined.push_str(current);
     trim_to_token_budget(&combined, chunk_size, token_counter)
 }
@@ -373,11 +687,34 @@ fn ends_with_whitespace(text: &str) -> bool {
 mod tests {
     use super::*;
 
+    fn texts(chunks: &[TextChunk]) -> Vec<&str> {
+        chunks.iter().map(|chunk| chunk.text.as_str()).collect()
+    }
+
+    /// Assert no chunk leaks a placeholder or holds an unbalanced fence.
+    fn assert_fences_intact(chunks: &[TextChunk]) {
+        for chunk in chunks {
+            assert!(
+                !chunk.text.contains([PLACEHOLDER_OPEN, PLACEHOLDER_CLOSE]),
+                "placeholder leaked: {:?}",
+                chunk.text
+            );
+            let fences = chunk
+                .text
+                .lines()
+                .filter(|line| line.trim_start().starts_with("```"))
+                .count();
+            assert_eq!(fences % 2, 0, "split fence: {:?}", chunk.text);
+        }
+    }
+
+    const BLOCK: &str = "```rust\nfn main() {\n    let answer = 42;\n}\n```";
+
     #[test]
     fn chunk_text_respects_chunk_size_whitespace_counter() {
         let text = "one two three four five";
         let chunks = chunk_text_with_counter(text, 2, 0, default_token_counter());
-        assert_eq!(chunks, vec!["one two", "three four", "five"]);
+        assert_eq!(texts(&chunks), vec!["one two", "three four", "five"]);
     }
 
     #[test]
@@ -391,12 +728,75 @@ mod tests {
         let text = "one two three four five";
         let counter = default_token_counter();
         let chunks = chunk_text_with_counter(text, 3, 1, counter.clone());
-        assert_eq!(chunks, vec!["one two three", "three four five"]);
+        assert_eq!(texts(&chunks), vec!["one two three", "three four five"]);
         for chunk in &chunks {
-            assert!(counter.as_ref()(chunk) <= 3);
+            assert!(counter.as_ref()(&chunk.text) <= 3);
+        }
+    }
+
+    #[test]
+    fn code_blocks_stay_whole_wherever_they_appear() {
+        let prose = "Intro words about the service.\n\nMore detail on the deploy flow.";
+        for text in [
+            format!("{BLOCK}\n\n{prose}"),
+            format!("{prose}\n\n{BLOCK}\n\n{prose}"),
+            format!("{prose}\n\n{BLOCK}"),
+        ] {
+            for overlap in [0, 3] {
+                let chunks = chunk_text_with_counter(&text, 12, overlap, default_token_counter());
+                assert_fences_intact(&chunks);
+                let holding: Vec<_> = chunks
+                    .iter()
+                    .filter(|chunk| chunk.text.contains("let answer = 42;"))
+                    .collect();
+                assert!(!holding.is_empty(), "{chunks:?}");
+                for chunk in holding {
+                    assert!(chunk.text.contains(BLOCK), "{chunk:?}");
+                    assert_eq!(chunk.content_kind, None);
+                }
+            }
         }
     }
 
+    #[test]
+    fn oversized_code_blocks_become_standalone_chunks() {
+        let body: String = (0..20).map(|line| format!("step_{line}();\n")).collect();
+        let block = format!("```python\n{body}```");
+        let text = format!("Before the script.\n\n{block}\n\nAfter the script.");
+
+        let chunks = chunk_text_with_counter(&text, 8, 2, default_token_counter());
+        assert_fences_intact(&chunks);
+        assert_eq!(
+            texts(&chunks),
+            vec!["Before the script.", block.as_str(), "After the script."]
+        );
+        assert_eq!(
+            chunks
+                .iter()
+                .map(|chunk| chunk.content_kind)
+                .collect::<Vec<_>>(),
+            vec![None, Some(CODE_BLOCK_CONTENT_KIND), None]
+        );
+    }
+
+    #[test]
+    fn unclosed_and_indented_fences_are_plain_text() {
+        assert!(fenced_block_ranges("```rust\nfn open() {}").is_empty());
+        assert!(fenced_block_ranges("    ```\n    code\n    ```").is_empty());
+
+        let text = "~~~\nnot a fence\n~~~\n````\ninner ```\n````\n";
+        let ranges = fenced_block_ranges(text);
+        assert_eq!(ranges.len(), 1);
+        assert_eq!(&text[ranges[0].clone()], "````\ninner ```\n````");
+    }
+
+    #[test]
+    fn text_containing_placeholder_characters_is_chunked_verbatim() {
+        let text = format!("odd {PLACEHOLDER_OPEN}0{PLACEHOLDER_CLOSE} marker\n{BLOCK}");
+        let chunks = chunk_text_with_counter(&text, 64, 0, default_token_counter());
+        assert_eq!(texts(&chunks), vec![text.as_str()]);
+    }
+
     #[test]
     fn chunk_text_rejects_zero_chunk_size() {
         let error = chunk_text(
@@ -405,6 +805,7 @@ mod tests {
             0,
             EmbeddingProvider::OpenAI,
             "text-embedding-3-small",
+            TokenizerFallback::Cl100k,
         )
         .unwrap_err();
         assert!(matches!(error, ChunkingError::InvalidChunkSize));
@@ -419,15 +820,16 @@ mod tests {
             0,
             EmbeddingProvider::OpenAI,
             "text-embedding-3-small",
+            TokenizerFallback::Cl100k,
         )
         .expect("chunking succeeded");
         let token_counter = build_tiktoken_counter("text-embedding-3-small").unwrap();
         for chunk in &chunks {
-            assert!(token_counter.as_ref()(chunk) <= 5);
+            assert!(token_counter.as_ref()(&chunk.text) <= 5);
         }
         let chunk_words: Vec<String> = chunks
             .iter()
-            .flat_map(|chunk| chunk.split_whitespace().map(|word| word.to_string()))
+            .flat_map(|chunk| chunk.text.split_whitespace().map(|word| word.to_string()))
             .collect();
         let original_words: Vec<String> = text
             .split_whitespace()
@@ -436,14 +838,36 @@ mod tests {
         assert_eq!(chunk_words, original_words);
     }
 
+    #[test]
+    fn tokenizer_name_follows_model_and_encoding_names() {
+        assert_eq!(tokenizer_name("text-embedding-3-small"), "cl100k_base");
+        assert_eq!(tokenizer_name("gpt-4o"), "o200k_base");
+        assert_eq!(tokenizer_name("p50k_base"), "p50k_base");
+        assert_eq!(tokenizer_name("nomic-embed-text:latest"), "cl100k_base");
+    }
+
+    #[test]
+    fn encoding_counter_accepts_encoding_names_only() {
+        for name in ENCODING_NAMES {
+            let counter = encoding_counter(name)
+                .expect("listed encoding")
+                .expect("encoding loads");
+            assert!(counter("hello world") > 0, "{name}");
+        }
+        assert!(encoding_counter("gpt-4o").is_none());
+        assert!(encoding_counter("whitespace").is_none());
+    }
+
     #[test]
     fn determine_chunk_size_prefers_override() {
         let chunk_size = determine_chunk_size(
             Some(42),
+            16,
             EmbeddingProvider::OpenAI,
             "text-embedding-3-small",
             false,
-        );
+        )
+        .chunk_size;
         assert_eq!(chunk_size, 42);
     }
 
@@ -451,32 +875,146 @@ mod tests {
     fn determine_chunk_size_infers_openai_embedding_window() {
         let chunk_size = determine_chunk_size(
             None,
+            16,
             EmbeddingProvider::OpenAI,
             "text-embedding-3-small",
             false,
-        );
+        )
+        .chunk_size;
         assert_eq!(chunk_size, 1024);
     }
 
     #[test]
     fn determine_chunk_size_handles_common_ollama_models() {
-        let chunk_size =
-            determine_chunk_size(None, EmbeddingProvider::Ollama, "nomic-embed-text", false);
+        let chunk_size = determine_chunk_size(
+            None,
+            16,
+            EmbeddingProvider::Ollama,
+            "nomic-embed-text",
+            false,
+        )
+        .chunk_size;
         assert_eq!(chunk_size, 1024);
 
-        let mini_chunk =
-            determine_chunk_size(None, EmbeddingProvider::Ollama, "all-minilm-l6-v2", false);
+        let mini_chunk = determine_chunk_size(
+            None,
+            16,
+            EmbeddingProvider::Ollama,
+            "all-minilm-l6-v2",
+            false,
+        )
+        .chunk_size;
         assert_eq!(mini_chunk, 256);
     }
 
+    #[test]
+    fn determine_chunk_size_reports_auto_sizing_and_clamping() {
+        let sizing = determine_chunk_size(
+            None,
+            16,
+            EmbeddingProvider::Ollama,
+            "nomic-embed-text",
+            false,
+        );
+        assert_eq!(
+            sizing,
+            ChunkSizing {
+                chunk_size: 1024,
+                auto_sized: true,
+                context_window: 8192,
+                clamped_from: Some(2048),
+            }
+        );
+
+        let explicit = determine_chunk_size(
+            Some(300),
+            16,
+            EmbeddingProvider::Ollama,
+            "nomic-embed-text",
+            false,
+        );
+        assert!(!explicit.auto_sized);
+        assert_eq!(explicit.clamped_from, None);
+    }
+
     #[test]
     fn determine_chunk_size_safe_defaults_reduce_window_proportion() {
         let conservative =
-            determine_chunk_size(None, EmbeddingProvider::Ollama, "custom-model", true);
+            determine_chunk_size(None, 16, EmbeddingProvider::Ollama, "custom-model", true)
+                .chunk_size;
         let aggressive =
-            determine_chunk_size(None, EmbeddingProvider::Ollama, "custom-model", false);
+            determine_chunk_size(None, 16, EmbeddingProvider::Ollama, "custom-model", false)
+                .chunk_size;
 
         assert_eq!(aggressive, 1024);
         assert_eq!(conservative, 512);
     }
+
+    #[test]
+    fn determine_chunk_size_raises_tiny_overrides_to_floor() {
+        let floored = determine_chunk_size(
+            Some(1),
+            DEFAULT_MIN_CHUNK_SIZE_OVERRIDE,
+            EmbeddingProvider::Ollama,
+            "nomic-embed-text",
+            false,
+        )
+        .chunk_size;
+        assert_eq!(floored, DEFAULT_MIN_CHUNK_SIZE_OVERRIDE);
+
+        let large = determine_chunk_size(
+            Some(8192),
+            DEFAULT_MIN_CHUNK_SIZE_OVERRIDE,
+            EmbeddingProvider::Ollama,
+            "nomic-embed-text",
+            false,
+        )
+        .chunk_size;
+        assert_eq!(large, 8192);
+
+        let unfloored =
+            determine_chunk_size(Some(0), 0, EmbeddingProvider::Ollama, "custom-model", false)
+                .chunk_size;
+        assert_eq!(unfloored, 1);
+    }
+
+    #[test]
+    fn override_below_floor_flags_only_tiny_overrides() {
+        assert_eq!(override_below_floor(Some(1), 16), Some(1));
+        assert_eq!(override_below_floor(Some(16), 16), None);
+        assert_eq!(override_below_floor(Some(4096), 16), None);
+        assert_eq!(override_below_floor(None, 16), None);
+    }
+
+    #[test]
+    fn encoding_load_failure_uses_configured_fallback_counter() {
+        let failed = || -> Result<TokenCounter, ChunkingError> {
+            Err(ChunkingError::Tokenizer {
+                model: "cl100k_base".into(),
+                source: TokenizerError::msg("encoding data unavailable"),
+            })
+        };
+        let text = "two words";
+
+        let chars = with_tokenizer_fallback(failed(), TokenizerFallback::Char).expect("char");
+        assert_eq!(chars.as_ref()(text), 9);
+
+        let words =
+            with_tokenizer_fallback(failed(), TokenizerFallback::Whitespace).expect("whitespace");
+        assert_eq!(words.as_ref()(text), 2);
+
+        let strict = with_tokenizer_fallback(failed(), TokenizerFallback::Cl100k);
+        assert!(matches!(strict, Err(ChunkingError::Tokenizer { .. })));
+    }
+
+    #[test]
+    fn loaded_encodings_ignore_the_fallback() {
+        let counter = build_token_counter(
+            EmbeddingProvider::OpenAI,
+            "text-embedding-3-small",
+            TokenizerFallback::Char,
+        )
+        .expect("tiktoken counter");
+        assert!(counter.as_ref()("hello world") < "hello world".len());
+    }
 }
@@ -0,0 +1,485 @@
+//! Cheap per-chunk memory type classification used by `push` when `auto_classify` is set.
+//!
+//! Each line of a chunk is labelled independently and the chunk takes the most common label:
+//!
+//! - **procedural**: starts with an imperative verb, or addresses the reader (`you should ...`).
+//! - **episodic**: carries a timestamp, date, or log level, or narrates a past event.
+//! - **semantic**: everything else.
+//!
+//! Pushes without a `memory_type` can instead take one from the first matching
+//! `MEMORY_TYPE_RULES` entry, keyed on a tag or the source URI.
+
+use serde::Deserialize;
+
+/// Strategy used to assign memory types to individual chunks during ingestion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutoClassify {
+    /// Rule-based classifier implemented in this module.
+    Heuristic,
+    /// Ask the configured summarization model, falling back to the heuristic on bad replies.
+    Llm,
+}
+
+/// Number of chunks assigned to each memory type by auto-classification.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ClassificationSummary {
+    /// Chunks labelled `episodic`.
+    pub episodic: usize,
+    /// Chunks labelled `semantic`.
+    pub semantic: usize,
+    /// Chunks labelled `procedural`.
+    pub procedural: usize,
+}
+
+impl ClassificationSummary {
+    /// Count one chunk under `memory_type`.
+    pub(crate) fn record(&mut self, memory_type: &str) {
+        match memory_type {
+            EPISODIC => self.episodic += 1,
+            PROCEDURAL => self.procedural += 1,
+            _ => self.semantic += 1,
+        }
+    }
+}
+
+const EPISODIC: &str = "episodic";
+const SEMANTIC: &str = "semantic";
+const PROCEDURAL: &str = "procedural";
+
+/// Verbs that, in first position, mark a line as an instruction.
+const IMPERATIVE_VERBS: &[&str] = &[
+    "add",
+    "always",
+    "avoid",
+    "build",
+    "bump",
+    "call",
+    "check",
+    "clean",
+    "commit",
+    "configure",
+    "copy",
+    "create",
+    "delete",
+    "deploy",
+    "disable",
+    "do",
+    "don't",
+    "enable",
+    "ensure",
+    "export",
+    "follow",
+    "install",
+    "keep",
+    "make",
+    "never",
+    "open",
+    "prefer",
+    "pull",
+    "push",
+    "rebase",
+    "remember",
+    "remove",
+    "restart",
+    "run",
+    "set",
+    "start",
+    "stop",
+    "test",
+    "try",
+    "update",
+    "use",
+    "verify",
+    "wait",
+    "write",
+];
+
+/// Words that, right after a leading verb, turn it into a statement (`Test coverage is ...`).
+const STATEMENT_VERBS: &[&str] = &["is", "are", "was", "were", "of", "has", "have"];
+
+/// Modal phrases that make a second-person line a directive.
+const DIRECTIVE_WORDS: &[&str] = &["should", "must", "need", "needs", "have", "always", "never"];
+
+/// Words that narrate a past event regardless of position.
+const PAST_MARKERS: &[&str] = &[
+    "yesterday",
+    "ago",
+    "deployed",
+    "fixed",
+    "merged",
+    "released",
+    "shipped",
+    "decided",
+    "discussed",
+    "met",
+    "found",
+    "noticed",
+    "broke",
+    "failed",
+    "happened",
+    "resolved",
+    "completed",
+    "finished",
+    "rolled",
+];
+
+/// Subjects that, followed by an `-ed` verb, narrate an event (`we upgraded ...`).
+const NARRATIVE_SUBJECTS: &[&str] = &["i", "we", "they", "he", "she", "team"];
+
+/// Log levels recognised as a standalone uppercase token.
+const LOG_LEVELS: &[&str] = &[
+    "TRACE", "DEBUG", "INFO", "WARN", "WARNING", "ERROR", "FATAL",
+];
+
+/// Classify a chunk as `episodic`, `semantic`, or `procedural`.
+pub fn classify_chunk(text: &str) -> &'static str {
+    let mut summary = ClassificationSummary::default();
+    for line in text.lines().filter(|line| !line.trim().is_empty()) {
+        summary.record(classify_line(line));
+    }
+    // Ties favour the more specific labels.
+    if summary.procedural > 0
+        && summary.procedural >= summary.episodic
+        && summary.procedural >= summary.semantic
+    {
+        PROCEDURAL
+    } else if summary.episodic > 0 && summary.episodic >= summary.semantic {
+        EPISODIC
+    } else {
+        SEMANTIC
+    }
+}
+
+/// Prompt asking a model to label `text` with a single memory type.
+pub(crate) fn llm_prompt(text: &str) -> String {
+    format!(
+        "Classify the memory below as exactly one of: episodic (a specific past event), \
+         semantic (a general fact), procedural (an instruction or how-to). \
+         Reply with the single word only.\n\nMemory:\n{text}"
+    )
+}
+
+/// Extract the memory type named in a model reply, if exactly one is present.
+pub(crate) fn parse_llm_label(reply: &str) -> Option<&'static str> {
+    let reply = reply.to_lowercase();
+    let mut found = [EPISODIC, SEMANTIC, PROCEDURAL]
+        .into_iter()
+        .filter(|label| reply.contains(label));
+    match (found.next(), found.next()) {
+        (Some(label), None) => Some(label),
+        _ => None,
+    }
+}
+
+fn classify_line(line: &str) -> &'static str {
+    let words = words(strip_list_marker(line.trim()));
+    if is_procedural(&words) {
+        PROCEDURAL
+    } else if has_log_marker(line) || is_narrative(&words) {
+        EPISODIC
+    } else {
+        SEMANTIC
+    }
+}
+
+fn is_procedural(words: &[String]) -> bool {
+    let Some(first) = words.first() else {
+        return false;
+    };
+    if IMPERATIVE_VERBS.contains(&first.as_str()) {
+        return !words
+            .iter()
+            .skip(1)
+            .take(2)
+            .any(|word| STATEMENT_VERBS.contains(&word.as_str()));
+    }
+    let second_person = words.iter().any(|word| word == "you");
+    second_person
+        && words
+            .iter()
+            .any(|word| DIRECTIVE_WORDS.contains(&word.as_str()))
+}
+
+fn is_narrative(words: &[String]) -> bool {
+    if words
+        .iter()
+        .any(|word| PAST_MARKERS.contains(&word.as_str()))
+    {
+        return true;
+    }
+    if words.first().is_some_and(|word| is_past_tense(word)) {
+        return true;
+    }
+    words
+        .windows(2)
+        .any(|pair| NARRATIVE_SUBJECTS.contains(&pair[0].as_str()) && is_past_tense(&pair[1]))
+}
+
+fn is_past_tense(word: &str) -> bool {
+    word.len() > 4 && word.ends_with("ed") && !word.ends_with("eed")
+}
+
+/// Detect `HH:MM` times, `YYYY-MM-DD` dates, or an uppercase log level token.
+fn has_log_marker(line: &str) -> bool {
+    let bytes = line.as_bytes();
+    let digits_at = |start: usize, count: usize| {
+        bytes
+            .get(start..start + count)
+            .is_some_and(|slice| slice.iter().all(u8::is_ascii_digit))
+    };
+    let has_time_or_date = (0..bytes.len()).any(|index| {
+        let boundary = index == 0 || !bytes[index - 1].is_ascii_digit();
+        boundary
+            && ((digits_at(index, 2)
+                && bytes.get(index + 2) == Some(&b':')
+                && digits_at(index + 3, 2))
+                || (digits_at(index, 1)
+                    && bytes.get(index + 1) == Some(&b':')
+                    && digits_at(index + 2, 2))
+                || (digits_at(index, 4)
+                    && bytes.get(index + 4) == Some(&b'-')
+                    && digits_at(index + 5, 2)
+                    && bytes.get(index + 7) == Some(&b'-')
+                    && digits_at(index + 8, 2)))
+    });
+    has_time_or_date
+        || line
+            .split(|c: char| !c.is_ascii_alphabetic())
+            .any(|token| LOG_LEVELS.contains(&token))
+}
+
+fn strip_list_marker(line: &str) -> &str {
+    let trimmed = line.trim_start_matches(['-', '*', '•', '>', ' ']);
+    let digits = trimmed.len()
+        - trimmed
+            .trim_start_matches(|c: char| c.is_ascii_digit())
+            .len();
+    match trimmed[digits..].strip_prefix(['.', ')']) {
+        Some(rest) if digits > 0 => rest.trim_start(),
+        _ => trimmed,
+    }
+}
+
+fn words(line: &str) -> Vec<String> {
+    line.split(|c: char| !(c.is_alphanumeric() || c == '\''))
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// `MEMORY_TYPE_RULES` entry assigning a memory type to pushes that omit one.
+///
+/// `tag:<name>` matches a push carrying that tag. Any other pattern is a case-insensitive glob
+/// (`*`, `?`) tested against the file name of `source_uri`, or the whole URI when the pattern
+/// contains `/`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct MemoryTypeRule {
+    /// Pattern as configured.
+    pub pattern: String,
+    /// Memory type assigned on a match.
+    pub memory_type: String,
+}
+
+impl MemoryTypeRule {
+    /// Parse a comma-separated `pattern=type` list, keeping rule order.
+    ///
+    /// Returns `None` when an entry is malformed or names an unknown memory type.
+    pub fn parse_list(raw: &str) -> Option<Vec<Self>> {
+        raw.split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| {
+                let (pattern, memory_type) = entry.rsplit_once('=')?;
+                let (pattern, memory_type) = (pattern.trim(), memory_type.trim().to_lowercase());
+                let known = [EPISODIC, SEMANTIC, PROCEDURAL].contains(&memory_type.as_str());
+                (!pattern.is_empty() && known).then(|| Self {
+                    pattern: pattern.to_string(),
+                    memory_type,
+                })
+            })
+            .collect()
+    }
+
+    fn matches(&self, tags: &[String], source_uri: Option<&str>) -> bool {
+        if let Some(tag) = self.pattern.strip_prefix("tag:") {
+            return tags
+                .iter()
+                .any(|candidate| candidate.trim().eq_ignore_ascii_case(tag));
+        }
+        let Some(uri) = source_uri else {
+            return false;
+        };
+        let uri = uri.split(['?', '#']).next().unwrap_or(uri);
+        let subject = if self.pattern.contains('/') {
+            uri
+        } else {
+            uri.trim_end_matches('/').rsplit('/').next().unwrap_or(uri)
+        };
+        glob_matches(&self.pattern, subject)
+    }
+}
+
+/// First rule matching the push's tags or source URI.
+pub(crate) fn infer_memory_type<'a>(
+    rules: &'a [MemoryTypeRule],
+    tags: Option<&[String]>,
+    source_uri: Option<&str>,
+) -> Option<&'a MemoryTypeRule> {
+    let tags = tags.unwrap_or_default();
+    rules.iter().find(|rule| rule.matches(tags, source_uri))
+}
+
+/// Case-insensitive glob match supporting `*` (any run) and `?` (one character).
+fn glob_matches(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&ch| ch == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn imperative_lines_are_procedural() {
+        assert_eq!(classify_chunk("run cargo test before pushing"), PROCEDURAL);
+        assert_eq!(classify_chunk("Always rebase onto main first."), PROCEDURAL);
+        assert_eq!(
+            classify_chunk("1. Install Qdrant\n2. Set QDRANT_URL"),
+            PROCEDURAL
+        );
+        assert_eq!(classify_chunk("- don't commit .env files"), PROCEDURAL);
+    }
+
+    #[test]
+    fn second_person_directives_are_procedural() {
+        assert_eq!(
+            classify_chunk("Before a release you should bump the version."),
+            PROCEDURAL
+        );
+        assert_eq!(classify_chunk("Your config lives in ~/.config."), SEMANTIC);
+    }
+
+    #[test]
+    fn statements_starting_with_verbs_stay_semantic() {
+        assert_eq!(
+            classify_chunk("Test coverage is measured with tarpaulin."),
+            SEMANTIC
+        );
+        assert_eq!(
+            classify_chunk("Use of unwrap has been discouraged."),
+            SEMANTIC
+        );
+    }
+
+    #[test]
+    fn timestamps_and_log_lines_are_episodic() {
+        assert_eq!(classify_chunk("deployed v1.2 at 14:00"), EPISODIC);
+        assert_eq!(classify_chunk("2025-03-02 outage in eu-west"), EPISODIC);
+        assert_eq!(
+            classify_chunk("[ERROR] connection refused by qdrant"),
+            EPISODIC
+        );
+    }
+
+    #[test]
+    fn past_tense_narration_is_episodic() {
+        assert_eq!(
+            classify_chunk("Upgraded tokio to 1.38 across services"),
+            EPISODIC
+        );
+        assert_eq!(
+            classify_chunk("Yesterday the team discussed caching"),
+            EPISODIC
+        );
+        assert_eq!(classify_chunk("We migrated the index last night"), EPISODIC);
+    }
+
+    #[test]
+    fn plain_facts_are_semantic() {
+        assert_eq!(
+            classify_chunk("Qdrant stores vectors alongside JSON payloads."),
+            SEMANTIC
+        );
+        assert_eq!(
+            classify_chunk("The embedding speed depends on batch size"),
+            SEMANTIC
+        );
+        assert_eq!(classify_chunk("   \n  "), SEMANTIC);
+    }
+
+    #[test]
+    fn mixed_chunks_take_the_majority_label() {
+        let chunk = "Deployed v1.2 at 14:00\nRolled back at 14:20\nrun smoke tests first";
+        assert_eq!(classify_chunk(chunk), EPISODIC);
+        let chunk = "Deployed v1.2 at 14:00\nrun smoke tests first";
+        assert_eq!(classify_chunk(chunk), PROCEDURAL);
+    }
+
+    #[test]
+    fn summary_counts_each_label() {
+        let mut summary = ClassificationSummary::default();
+        for label in [EPISODIC, PROCEDURAL, PROCEDURAL, SEMANTIC] {
+            summary.record(label);
+        }
+        assert_eq!(
+            summary,
+            ClassificationSummary {
+                episodic: 1,
+                semantic: 1,
+                procedural: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn llm_replies_must_name_exactly_one_label() {
+        assert_eq!(parse_llm_label("Procedural"), Some(PROCEDURAL));
+        assert_eq!(parse_llm_label(" episodic.\n"), Some(EPISODIC));
+        assert_eq!(parse_llm_label("semantic or episodic"), None);
+        assert_eq!(parse_llm_label("no idea"), None);
+        assert!(llm_prompt("note").ends_with("Memory:\nnote"));
+    }
+
+    #[test]
+    fn memory_type_rules_parse_in_order_and_match_uris_and_tags() {
+        let rules =
+            MemoryTypeRule::parse_list("*.log=episodic, README*=Semantic, tag:runbook=procedural")
+                .expect("rules");
+        assert_eq!(rules.len(), 3);
+        assert_eq!(rules[1].memory_type, "semantic");
+
+        let uri =
+            |uri| infer_memory_type(&rules, None, Some(uri)).map(|rule| rule.pattern.as_str());
+        assert_eq!(uri("file:///var/log/app.LOG"), Some("*.log"));
+        assert_eq!(
+            uri("https://github.com/org/repo/README.md?plain=1"),
+            Some("README*")
+        );
+        assert_eq!(uri("docs/guide.md"), None);
+
+        let tags = vec!["Runbook".to_string()];
+        let rule = infer_memory_type(&rules, Some(&tags), None).expect("tag rule");
+        assert_eq!(rule.memory_type, "procedural");
+
+        assert!(MemoryTypeRule::parse_list("*.log=diary").is_none());
+        assert!(MemoryTypeRule::parse_list("=episodic").is_none());
+    }
+}
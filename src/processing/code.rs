@@ -0,0 +1,424 @@
+//! Function-aligned chunking for source code (`TEXT_SPLITTER_STRATEGY=code`).
+//!
+//! Source text is first cut into top-level units: runs of lines separated by a blank line at
+//! brace depth zero whose next line starts in column zero. Doc comments, attributes, and
+//! decorators directly above an item therefore stay with it, and indented members (methods,
+//! nested blocks) stay with their parent. Units are packed greedily up to the token budget, so
+//! chunk boundaries fall between functions. A unit larger than the budget is handed to the token
+//! splitter on its own. No overlap is added between unit-aligned chunks.
+//!
+//! Chunks carry the language implied by the `source_uri` extension and the first top-level
+//! symbol they define, when either is detectable.
+
+use std::ops::Range;
+
+use crate::config::{EmbeddingProvider, TokenizerFallback};
+
+use super::chunking::{TextChunk, TokenCounter, build_token_counter, chunk_text_with_counter};
+use super::types::ChunkingError;
+
+/// Source file extensions and the `language` payload value they imply.
+const LANGUAGES: &[(&[&str], &str)] = &[
+    (&["rs"], "rust"),
+    (&["py", "pyi"], "python"),
+    (&["js", "mjs", "cjs", "jsx"], "javascript"),
+    (&["ts", "mts", "cts", "tsx"], "typescript"),
+    (&["go"], "go"),
+    (&["java"], "java"),
+    (&["kt", "kts"], "kotlin"),
+    (&["c", "h"], "c"),
+    (&["cc", "cpp", "cxx", "hh", "hpp", "hxx"], "cpp"),
+    (&["cs"], "csharp"),
+    (&["rb"], "ruby"),
+    (&["php"], "php"),
+    (&["swift"], "swift"),
+    (&["scala"], "scala"),
+    (&["sh", "bash", "zsh"], "shell"),
+];
+
+/// Keywords introducing a named definition; the following word is taken as its symbol.
+const DEFINITION_KEYWORDS: &[&str] = &[
+    "fn",
+    "def",
+    "class",
+    "struct",
+    "enum",
+    "trait",
+    "impl",
+    "interface",
+    "function",
+    "func",
+    "type",
+    "mod",
+    "module",
+    "union",
+    "macro_rules!",
+];
+
+/// Words that may precede a definition keyword without changing what is defined.
+const DEFINITION_MODIFIERS: &[&str] = &[
+    "pub",
+    "async",
+    "unsafe",
+    "const",
+    "extern",
+    "export",
+    "default",
+    "static",
+    "public",
+    "private",
+    "protected",
+    "internal",
+    "abstract",
+    "final",
+    "override",
+    "sealed",
+    "open",
+    "inline",
+    "virtual",
+];
+
+/// Split source code at top-level unit boundaries.
+///
+/// Mirrors [`super::chunking::chunk_text`]; `source_uri` only feeds the `language` hint.
+/// Returns an empty vector when the input text is all whitespace.
+pub(crate) fn chunk_code(
+    text: &str,
+    chunk_size: usize,
+    overlap: usize,
+    provider: EmbeddingProvider,
+    model: &str,
+    tokenizer_fallback: TokenizerFallback,
+    source_uri: Option<&str>,
+) -> Result<Vec<TextChunk>, ChunkingError> {
+    if chunk_size == 0 {
+        return Err(ChunkingError::InvalidChunkSize);
+    }
+    if text.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let token_counter = build_token_counter(provider, model, tokenizer_fallback)?;
+    Ok(chunk_code_with_counter(
+        text,
+        chunk_size,
+        overlap,
+        token_counter,
+        source_uri.and_then(detect_language),
+    ))
+}
+
+fn chunk_code_with_counter(
+    text: &str,
+    chunk_size: usize,
+    overlap: usize,
+    token_counter: TokenCounter,
+    language: Option<&'static str>,
+) -> Vec<TextChunk> {
+    let count = |range: &Range<usize>| token_counter.as_ref()(&text[range.clone()]);
+    let hinted = |text: String, symbol: Option<String>| TextChunk {
+        text,
+        content_kind: None,
+        language,
+        symbol,
+    };
+
+    let mut chunks = Vec::new();
+    let mut pending: Option<(Range<usize>, Option<String>)> = None;
+    for unit in top_level_units(text) {
+        let symbol = unit_symbol(&text[unit.clone()]);
+        if count(&unit) > chunk_size {
+            if let Some((range, symbol)) = pending.take() {
+                chunks.push(hinted(text[range].to_string(), symbol));
+            }
+            for piece in
+                chunk_text_with_counter(&text[unit], chunk_size, overlap, token_counter.clone())
+            {
+                chunks.push(TextChunk {
+                    symbol: symbol.clone(),
+                    language,
+                    ..piece
+                });
+            }
+            continue;
+        }
+        pending = match pending.take() {
+            Some((range, pending_symbol)) if count(&(range.start..unit.end)) <= chunk_size => {
+                Some((range.start..unit.end, pending_symbol.or(symbol)))
+            }
+            Some((range, pending_symbol)) => {
+                chunks.push(hinted(text[range].to_string(), pending_symbol));
+                Some((unit, symbol))
+            }
+            None => Some((unit, symbol)),
+        };
+    }
+    if let Some((range, symbol)) = pending {
+        chunks.push(hinted(text[range].to_string(), symbol));
+    }
+    chunks
+}
+
+/// Byte ranges of top-level units, without the blank lines between them.
+fn top_level_units(text: &str) -> Vec<Range<usize>> {
+    let mut units = Vec::new();
+    let mut depth = 0usize;
+    let mut unit_start: Option<usize> = None;
+    let mut unit_end = 0;
+    let mut after_blank = false;
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+        let content = line.trim_end();
+        if content.trim().is_empty() {
+            after_blank = true;
+            continue;
+        }
+        let starts_unit = after_blank
+            && depth == 0
+            && !line.starts_with(char::is_whitespace)
+            && !content.starts_with(['}', ')', ']']);
+        match unit_start {
+            Some(begin) if starts_unit => {
+                units.push(begin..unit_end);
+                unit_start = Some(start);
+            }
+            Some(_) => {}
+            None => unit_start = Some(start),
+        }
+        unit_end = start + content.len();
+        depth = track_depth(content, depth);
+        after_blank = false;
+    }
+    if let Some(begin) = unit_start {
+        units.push(begin..unit_end);
+    }
+    units
+}
+
+/// Brace depth after `line`, ignoring braces in `//` comments, string literals, and character
+/// literals. The depth never drops below zero, so stray closers cannot derail later lines.
+fn track_depth(line: &str, mut depth: usize) -> usize {
+    let chars: Vec<char> = line.chars().collect();
+    let mut index = 0;
+    let mut in_string = false;
+    while index < chars.len() {
+        let ch = chars[index];
+        if in_string {
+            match ch {
+                '\\' => index += 1,
+                '"' => in_string = false,
+                _ => {}
+            }
+        } else {
+            match ch {
+                '/' if chars.get(index + 1) == Some(&'/') => break,
+                '"' => in_string = true,
+                // Only `'x'` and `'\x'` are character literals; lifetimes such as `'a` are not.
+                '\'' if chars.get(index + 2) == Some(&'\'') => index += 2,
+                '\'' if chars.get(index + 1) == Some(&'\\')
+                    && chars.get(index + 3) == Some(&'\'') =>
+                {
+                    index += 3
+                }
+                '{' => depth += 1,
+                '}' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+        }
+        index += 1;
+    }
+    depth
+}
+
+/// Name of the first definition on a column-zero line of `unit`.
+fn unit_symbol(unit: &str) -> Option<String> {
+    unit.lines()
+        .filter(|line| !line.starts_with(char::is_whitespace))
+        .find_map(definition_symbol)
+}
+
+fn definition_symbol(line: &str) -> Option<String> {
+    let mut words = line.split_whitespace();
+    while let Some(word) = words.next() {
+        if DEFINITION_MODIFIERS.contains(&word) || word.starts_with("pub(") || word.starts_with('"')
+        {
+            continue;
+        }
+        // `impl<T>` and `fn<T>`-style generics are glued to the keyword.
+        let keyword_len = word
+            .find(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '_' || ch == '!'))
+            .unwrap_or(word.len());
+        if !DEFINITION_KEYWORDS.contains(&&word[..keyword_len]) {
+            return None;
+        }
+        let name: String = words
+            .next()?
+            .chars()
+            .take_while(|ch| ch.is_alphanumeric() || *ch == '_' || *ch == '$')
+            .collect();
+        return (!name.is_empty()).then_some(name);
+    }
+    None
+}
+
+/// Language implied by the file extension of `source_uri`.
+fn detect_language(source_uri: &str) -> Option<&'static str> {
+    let path = source_uri.split(['?', '#']).next().unwrap_or(source_uri);
+    let file = path.rsplit(['/', '\\']).next().unwrap_or(path);
+    let (_, extension) = file.rsplit_once('.')?;
+    let extension = extension.to_ascii_lowercase();
+    LANGUAGES
+        .iter()
+        .find(|(extensions, _)| extensions.contains(&extension.as_str()))
+        .map(|(_, language)| *language)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    const SOURCE: &str = r#"//! Key rotation helpers.
+
+use std::time::Duration;
+
+/// Interval between rotations.
+const ROTATION: Duration = Duration::from_secs(86_400);
+
+/// Rotate every key that is older than the interval.
+pub fn rotate_keys(keys: &mut Vec<Key>) {
+    for key in keys.iter_mut() {
+        if key.age() > ROTATION {
+
+            key.rotate();
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Key<'a> {
+    name: &'a str,
+    created: u64,
+}
+
+impl<'a> Key<'a> {
+    fn age(&self) -> Duration {
+        let brace = '{';
+        Duration::from_secs(self.created)
+    }
+
+    fn rotate(&mut self) {
+        self.created = 0;
+    }
+}
+"#;
+
+    fn word_counter() -> TokenCounter {
+        Arc::new(|text: &str| text.split_whitespace().count())
+    }
+
+    fn split(text: &str, chunk_size: usize) -> Vec<TextChunk> {
+        chunk_code_with_counter(text, chunk_size, 0, word_counter(), Some("rust"))
+    }
+
+    #[test]
+    fn units_follow_top_level_items_and_keep_bodies_whole() {
+        let units: Vec<&str> = top_level_units(SOURCE)
+            .into_iter()
+            .map(|unit| &SOURCE[unit])
+            .collect();
+        assert_eq!(units.len(), 6, "{units:#?}");
+        assert_eq!(units[0], "//! Key rotation helpers.");
+        assert!(units[3].starts_with("/// Rotate every key"));
+        assert!(
+            units[3].ends_with("}\n}"),
+            "blank line inside the body: {}",
+            units[3]
+        );
+        assert!(units[4].starts_with("#[derive(Debug)]"));
+        assert!(units[5].starts_with("impl<'a> Key<'a> {"));
+        assert!(units[5].ends_with("self.created = 0;\n    }\n}"));
+    }
+
+    #[test]
+    fn chunks_end_on_function_boundaries_with_symbol_hints() {
+        let chunks = split(SOURCE, 30);
+        let texts: Vec<&str> = chunks.iter().map(|chunk| chunk.text.as_str()).collect();
+        assert_eq!(texts.len(), 4, "{texts:#?}");
+        assert!(texts[0].starts_with("//! Key rotation helpers."));
+        assert!(texts[0].ends_with("Duration::from_secs(86_400);"));
+        assert!(texts[1].starts_with("/// Rotate every key"));
+        assert!(texts[1].ends_with("key.rotate();\n        }\n    }\n}"));
+        assert!(texts[2].starts_with("#[derive(Debug)]"));
+        assert!(texts[3].starts_with("impl<'a> Key<'a> {"));
+        assert!(texts[3].ends_with("self.created = 0;\n    }\n}"));
+
+        let symbols: Vec<Option<&str>> =
+            chunks.iter().map(|chunk| chunk.symbol.as_deref()).collect();
+        assert_eq!(
+            symbols,
+            [None, Some("rotate_keys"), Some("Key"), Some("Key")]
+        );
+        assert!(chunks.iter().all(|chunk| chunk.language == Some("rust")));
+        assert!(chunks.iter().all(|chunk| word_counter()(&chunk.text) <= 30));
+
+        let packed = split(SOURCE, 45);
+        assert_eq!(packed.len(), 2);
+        assert!(
+            packed[0]
+                .text
+                .ends_with("key.rotate();\n        }\n    }\n}")
+        );
+        assert_eq!(packed[0].symbol.as_deref(), Some("rotate_keys"));
+    }
+
+    #[test]
+    fn oversized_units_fall_back_to_token_splitting() {
+        let body: Vec<String> = (0..30).map(|line| format!("    step_{line}();")).collect();
+        let source = format!(
+            "def small():\n    return 1\n\n\ndef huge():\n{}\n\nclass Tail:\n    pass\n",
+            body.join("\n")
+        );
+        let chunks = split(&source, 12);
+
+        assert_eq!(chunks[0].text, "def small():\n    return 1");
+        assert_eq!(chunks[0].symbol.as_deref(), Some("small"));
+        let huge: Vec<&TextChunk> = chunks
+            .iter()
+            .filter(|chunk| chunk.symbol.as_deref() == Some("huge"))
+            .collect();
+        assert!(huge.len() > 1, "{chunks:#?}");
+        assert!(huge.iter().all(|chunk| word_counter()(&chunk.text) <= 12));
+        let last = chunks.last().expect("chunks");
+        assert_eq!(last.text, "class Tail:\n    pass");
+        assert_eq!(last.symbol.as_deref(), Some("Tail"));
+    }
+
+    #[test]
+    fn symbols_and_languages_are_detected_when_present() {
+        assert_eq!(
+            definition_symbol("pub(crate) async fn load_config() -> Config {").as_deref(),
+            Some("load_config")
+        );
+        assert_eq!(
+            definition_symbol("impl<T> Store<T> {").as_deref(),
+            Some("Store")
+        );
+        assert_eq!(
+            definition_symbol("export default function render(props) {").as_deref(),
+            Some("render")
+        );
+        assert_eq!(definition_symbol("const LIMIT: usize = 4;"), None);
+        assert_eq!(definition_symbol("@app.route('/')"), None);
+
+        assert_eq!(detect_language("file:///repo/src/main.RS"), Some("rust"));
+        assert_eq!(
+            detect_language("https://host/lib/app.tsx?raw=1"),
+            Some("typescript")
+        );
+        assert_eq!(detect_language("notes/readme.md"), None);
+        assert_eq!(detect_language("Makefile"), None);
+    }
+}
@@ -0,0 +1,131 @@
+//! Reassembly of pushed documents from their stored chunks.
+//!
+//! Every push stamps its chunks with a shared `doc_id` and their `chunk_index`. Sorting a
+//! document's points by index gives the text back in order. When the push used
+//! `TEXT_SPLITTER_CHUNK_OVERLAP`, each chunk starts with the tail of its predecessor; that
+//! repeated prefix is detected against the previous chunk and stripped before joining.
+
+/// Overlap in tokens recorded in a compact ingest profile (`... chunk=512+64 ...`).
+pub(crate) fn profile_overlap(profile: &str) -> Option<usize> {
+    let sizes = profile
+        .split_whitespace()
+        .find_map(|part| part.strip_prefix("chunk="))?;
+    sizes.split_once('+')?.1.parse().ok()
+}
+
+/// Length in bytes of the longest prefix of `next` that `previous` ends with.
+///
+/// The prefix must stop at a word boundary in `next` (whitespace or its end), so a chunk that
+/// merely starts with the same letters the previous one ended with is left intact.
+pub(crate) fn overlap_len(previous: &str, next: &str) -> usize {
+    let mut ends: Vec<usize> = next
+        .char_indices()
+        .map(|(index, _)| index)
+        .skip(1)
+        .chain(std::iter::once(next.len()))
+        .collect();
+    ends.reverse();
+    ends.into_iter()
+        .filter(|&end| end <= previous.len())
+        .find(|&end| {
+            next[end..].chars().next().is_none_or(char::is_whitespace)
+                && previous.ends_with(&next[..end])
+        })
+        .unwrap_or(0)
+}
+
+/// Join ordered chunk texts, stripping repeated prefixes when `overlapped`.
+///
+/// Returns the document text and, per chunk, how many leading bytes were dropped. Chunks are
+/// separated by a single space unless the boundary already has whitespace.
+pub(crate) fn stitch_chunks(chunks: &[&str], overlapped: bool) -> (String, Vec<usize>) {
+    let mut text = String::new();
+    let mut stripped = Vec::with_capacity(chunks.len());
+    let mut previous: Option<&str> = None;
+    for chunk in chunks {
+        let skip = match previous {
+            Some(previous) if overlapped => overlap_len(previous, chunk),
+            _ => 0,
+        };
+        let rest = &chunk[skip..];
+        if !text.is_empty()
+            && !rest.is_empty()
+            && !text.ends_with(char::is_whitespace)
+            && !rest.starts_with(char::is_whitespace)
+        {
+            text.push(' ');
+        }
+        text.push_str(rest);
+        stripped.push(skip);
+        previous = Some(chunk);
+    }
+    (text, stripped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overlap_is_read_from_the_compact_profile() {
+        let profile = "ollama:nomic@768 chunk=512+64 semchunk cl100k_base dedupe=chunk_hash v0.2.0";
+        assert_eq!(profile_overlap(profile), Some(64));
+        assert_eq!(profile_overlap("chunk=512+0"), Some(0));
+        assert_eq!(profile_overlap("no sizes here"), None);
+    }
+
+    #[test]
+    fn chunks_without_overlap_are_joined_with_a_space() {
+        let (text, stripped) = stitch_chunks(&["one two three", "four five"], false);
+        assert_eq!(text, "one two three four five");
+        assert_eq!(stripped, [0, 0]);
+
+        let (text, _) = stitch_chunks(&["first paragraph\n", "second"], false);
+        assert_eq!(text, "first paragraph\nsecond");
+    }
+
+    #[test]
+    fn overlapping_prefixes_are_stripped() {
+        let (text, stripped) = stitch_chunks(
+            &[
+                "alpha beta gamma",
+                "beta gamma delta epsilon",
+                "epsilon zeta",
+            ],
+            true,
+        );
+        assert_eq!(text, "alpha beta gamma delta epsilon zeta");
+        assert_eq!(stripped, [0, 10, 7]);
+    }
+
+    #[test]
+    fn overlap_must_end_on_a_word_boundary() {
+        assert_eq!(overlap_len("the cat", "category theory"), 0);
+        assert_eq!(overlap_len("ends with a", "a new sentence"), 1);
+
+        let (text, stripped) = stitch_chunks(&["the cat", "category theory"], true);
+        assert_eq!(text, "the cat category theory");
+        assert_eq!(stripped, [0, 0]);
+    }
+
+    #[test]
+    fn overlap_detection_is_disabled_without_overlap() {
+        let (text, stripped) = stitch_chunks(&["ends with a", "a new sentence"], false);
+        assert_eq!(text, "ends with a a new sentence");
+        assert_eq!(stripped, [0, 0]);
+    }
+
+    #[test]
+    fn stitching_respects_multibyte_boundaries() {
+        let (text, stripped) = stitch_chunks(&["un café crème", "crème brûlée"], true);
+        assert_eq!(text, "un café crème brûlée");
+        assert_eq!(stripped, [0, "crème".len()]);
+    }
+
+    #[test]
+    fn fully_repeated_chunks_add_nothing() {
+        let (text, stripped) = stitch_chunks(&["one two three", "two three"], true);
+        assert_eq!(text, "one two three");
+        assert_eq!(stripped, [0, 9]);
+    }
+}
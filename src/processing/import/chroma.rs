@@ -0,0 +1,135 @@
+//! Parser for Chroma exports.
+//!
+//! Chroma's `collection.get(include=["documents", "metadatas", "embeddings"])` returns parallel
+//! arrays; entry `i` of `documents`, `metadatas`, and `embeddings` belongs to `ids[i]`. Omitted
+//! arrays and `null` entries are allowed. Keys Chroma adds for other content (`uris`, `data`,
+//! `included`) are ignored.
+
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use super::foreign::{ForeignRecord, ParsedRecord};
+
+#[derive(Deserialize)]
+struct ChromaExport {
+    ids: Vec<Value>,
+    #[serde(default)]
+    documents: Option<Vec<Value>>,
+    #[serde(default)]
+    metadatas: Option<Vec<Value>>,
+    #[serde(default)]
+    embeddings: Option<Vec<Value>>,
+}
+
+/// Parse a Chroma export into one entry per id.
+///
+/// The document is rejected when it is not a `get()` result or a parallel array's length differs
+/// from `ids`. An embedding that is not a number array is treated as absent, so the text is
+/// re-embedded.
+pub fn parse(document: &[u8]) -> Result<Vec<ParsedRecord>, String> {
+    let export: ChromaExport = serde_json::from_slice(document)
+        .map_err(|error| format!("invalid Chroma export: {error}"))?;
+    let count = export.ids.len();
+    for (name, values) in [
+        ("documents", &export.documents),
+        ("metadatas", &export.metadatas),
+        ("embeddings", &export.embeddings),
+    ] {
+        if let Some(values) = values
+            && values.len() != count
+        {
+            return Err(format!(
+                "invalid Chroma export: `{name}` has {} entries but `ids` has {count}",
+                values.len()
+            ));
+        }
+    }
+
+    let entry = |values: &Option<Vec<Value>>, index: usize| {
+        values
+            .as_ref()
+            .and_then(|values| values.get(index))
+            .cloned()
+            .unwrap_or(Value::Null)
+    };
+    Ok((0..count)
+        .map(|index| {
+            let id = match &export.ids[index] {
+                Value::String(id) => Some(id.clone()),
+                Value::Number(id) => Some(id.to_string()),
+                _ => None,
+            };
+            let text = match entry(&export.documents, index) {
+                Value::Null => None,
+                Value::String(text) => Some(text),
+                _ => return Err(format!("`documents[{index}]` must be a string")),
+            };
+            let fields = match entry(&export.metadatas, index) {
+                Value::Null => Map::new(),
+                Value::Object(fields) => fields,
+                _ => return Err(format!("`metadatas[{index}]` must be an object")),
+            };
+            let vector = serde_json::from_value(entry(&export.embeddings, index)).ok();
+            Ok(ForeignRecord {
+                id,
+                text,
+                vector,
+                fields,
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const FIXTURE: &str = include_str!("fixtures/chroma_export.json");
+
+    #[test]
+    fn parses_parallel_arrays_into_records() {
+        let records = parse(FIXTURE.as_bytes()).expect("parse");
+
+        assert_eq!(records.len(), 4);
+        let first = records[0].as_ref().expect("first record");
+        assert_eq!(first.id.as_deref(), Some("note-1"));
+        assert_eq!(
+            first.text.as_deref(),
+            Some("Rotate the API signing keys every quarter.")
+        );
+        assert_eq!(first.vector, Some(vec![0.12, -0.03, 0.4]));
+        assert_eq!(first.fields["tags"], json!("security, keys"));
+        assert_eq!(first.fields["timestamp"], json!(1735689600));
+
+        let second = records[1].as_ref().expect("second record");
+        assert_eq!(second.vector, None);
+        assert_eq!(second.fields["memory_type"], json!("episodic"));
+        assert_eq!(records[2].as_ref().expect("third record").text, None);
+        assert!(
+            records[3]
+                .as_ref()
+                .expect("fourth record")
+                .fields
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn rejects_mismatched_arrays_and_reports_malformed_entries() {
+        let error = parse(br#"{ "ids": ["a", "b"], "documents": ["only one"] }"#)
+            .expect_err("length mismatch");
+        assert!(error.contains("`documents` has 1 entries but `ids` has 2"));
+        assert!(parse(br#"[{ "id": "a" }]"#).is_err());
+
+        let records = parse(
+            br#"{ "ids": ["a", "b"], "documents": ["fine", 7], "embeddings": [["x"], null] }"#,
+        )
+        .expect("parse");
+        assert_eq!(records[0].as_ref().expect("first").vector, None);
+        assert_eq!(
+            records[1].as_ref().expect_err("second"),
+            "`documents[1]` must be a string"
+        );
+    }
+}
@@ -0,0 +1,421 @@
+//! Shared import path for exports produced by other memory servers.
+//!
+//! Format parsers turn a document into [`ForeignRecord`]s: an optional source id, the text, an
+//! optional embedding, and a flat map of metadata fields. [`import_foreign`] reads
+//! `project_id`, `memory_type`, `tags`, `timestamp`, and `source_uri` from those fields through a
+//! [`FieldMapping`], stores records whose vector matches `EMBEDDING_DIMENSION` as-is, and
+//! re-embeds the rest. Every record gets a [`RecordOutcome`], so skipped records carry a reason.
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+use time::OffsetDateTime;
+
+use super::{IMPORT_BATCH_SIZE, ImportedVector};
+use crate::processing::{
+    ProcessingApi,
+    sanitize::{format_utc_rfc3339, validate_ingest_timestamp},
+    types::IngestMetadata,
+};
+
+/// Largest export document accepted; the whole document is parsed in memory.
+pub const MAX_FOREIGN_IMPORT_BYTES: usize = 64 * 1024 * 1024;
+
+/// Export format of another memory server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ForeignFormat {
+    /// Chroma `collection.get()` output: parallel `ids`/`documents`/`metadatas`/`embeddings`.
+    Chroma,
+    /// mem0 `get_all()` output: objects carrying `memory` text and `metadata`.
+    Mem0,
+}
+
+/// One record read from an export, before metadata mapping.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ForeignRecord {
+    /// Identifier the record had in the source system.
+    pub id: Option<String>,
+    /// Memory text; records without it are skipped.
+    pub text: Option<String>,
+    /// Embedding from the source system, reused when its dimension matches.
+    pub vector: Option<Vec<f32>>,
+    /// Metadata fields the [`FieldMapping`] reads from.
+    pub fields: Map<String, Value>,
+}
+
+/// Parsed record, or why the entry could not be read as one.
+pub type ParsedRecord = Result<ForeignRecord, String>;
+
+/// Metadata field names holding each memory attribute.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldMapping {
+    /// Field mapped to `project_id`.
+    pub project_id: String,
+    /// Field mapped to `memory_type`.
+    pub memory_type: String,
+    /// Field mapped to `tags`: a string array or a comma-separated string.
+    pub tags: String,
+    /// Field mapped to `timestamp`: RFC3339, `YYYY-MM-DD`, or Unix seconds.
+    pub timestamp: String,
+    /// Field mapped to `source_uri`.
+    pub source_uri: String,
+}
+
+impl FieldMapping {
+    /// Conventional field names for `format`.
+    ///
+    /// Chroma metadata is free-form, so the memory attribute names are assumed, with LangChain's
+    /// `source` for the URI. mem0 scopes memories by `user_id`, labels them with `categories`,
+    /// and stamps them with `created_at`.
+    pub fn defaults(format: ForeignFormat) -> Self {
+        let (project_id, tags, timestamp) = match format {
+            ForeignFormat::Chroma => ("project_id", "tags", "timestamp"),
+            ForeignFormat::Mem0 => ("user_id", "categories", "created_at"),
+        };
+        Self {
+            project_id: project_id.into(),
+            memory_type: "memory_type".into(),
+            tags: tags.into(),
+            timestamp: timestamp.into(),
+            source_uri: "source".into(),
+        }
+    }
+}
+
+/// How one record ended up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordStatus {
+    /// Stored with the embedding from the export.
+    Imported,
+    /// Stored after chunking and embedding the text.
+    Reembedded,
+    /// Not stored; see the outcome's `reason`.
+    Skipped,
+}
+
+/// Result for the record at `index` in the export.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RecordOutcome {
+    /// 0-based position of the record in the export.
+    pub index: usize,
+    /// Identifier the record had in the source system.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Whether and how the record was stored.
+    pub status: RecordStatus,
+    /// Why the record was skipped, or why its vector was not reused.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Aggregate result of a foreign import.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ForeignImportSummary {
+    /// Records found in the export.
+    pub records: usize,
+    /// Records stored as memories.
+    pub imported: usize,
+    /// Stored records that were chunked and embedded.
+    pub reembedded: usize,
+    /// Stored records whose exported vector was kept.
+    pub reused_vectors: usize,
+    /// Records that were not stored.
+    pub skipped: usize,
+    /// One outcome per record, in export order.
+    pub outcomes: Vec<RecordOutcome>,
+}
+
+impl ForeignImportSummary {
+    fn record(
+        &mut self,
+        index: usize,
+        id: Option<String>,
+        status: RecordStatus,
+        reason: Option<String>,
+    ) {
+        match status {
+            RecordStatus::Imported => {
+                self.imported += 1;
+                self.reused_vectors += 1;
+            }
+            RecordStatus::Reembedded => {
+                self.imported += 1;
+                self.reembedded += 1;
+            }
+            RecordStatus::Skipped => self.skipped += 1,
+        }
+        self.outcomes.push(RecordOutcome {
+            index,
+            id,
+            status,
+            reason,
+        });
+    }
+}
+
+/// Store parsed `records` in `collection`, reading metadata through `mapping`.
+///
+/// Records with a vector of `dimension` components (the collection's vector size) are written
+/// in batches of [`IMPORT_BATCH_SIZE`]; the others go through the regular `push` pipeline. A
+/// failed batch skips each of its records with the storage error as the reason.
+pub async fn import_foreign<S: ProcessingApi + ?Sized>(
+    service: &S,
+    collection: &str,
+    dimension: usize,
+    records: Vec<ParsedRecord>,
+    mapping: &FieldMapping,
+) -> ForeignImportSummary {
+    let now = OffsetDateTime::now_utc();
+    let mut summary = ForeignImportSummary {
+        records: records.len(),
+        ..ForeignImportSummary::default()
+    };
+    let mut batch: Vec<(usize, Option<String>, ImportedVector)> = Vec::new();
+
+    for (index, record) in records.into_iter().enumerate() {
+        let record = match record {
+            Ok(record) => record,
+            Err(reason) => {
+                summary.record(index, None, RecordStatus::Skipped, Some(reason));
+                continue;
+            }
+        };
+        let (text, metadata) = match map_record(&record, mapping, now) {
+            Ok(mapped) => mapped,
+            Err(reason) => {
+                summary.record(index, record.id, RecordStatus::Skipped, Some(reason));
+                continue;
+            }
+        };
+        let mismatch = match record.vector {
+            Some(vector) if vector.len() == dimension => {
+                batch.push((
+                    index,
+                    record.id,
+                    ImportedVector {
+                        text,
+                        vector,
+                        metadata,
+                    },
+                ));
+                if batch.len() >= IMPORT_BATCH_SIZE {
+                    flush(service, collection, &mut batch, &mut summary).await;
+                }
+                continue;
+            }
+            Some(vector) => Some(format!(
+                "vector has {} dimensions; expected {dimension}, re-embedded",
+                vector.len()
+            )),
+            None => None,
+        };
+        match service.process_and_index(collection, text, metadata).await {
+            Ok(_) => summary.record(index, record.id, RecordStatus::Reembedded, mismatch),
+            Err(error) => summary.record(
+                index,
+                record.id,
+                RecordStatus::Skipped,
+                Some(error.to_string()),
+            ),
+        }
+    }
+    flush(service, collection, &mut batch, &mut summary).await;
+    summary.outcomes.sort_by_key(|outcome| outcome.index);
+
+    tracing::info!(
+        collection,
+        records = summary.records,
+        imported = summary.imported,
+        skipped = summary.skipped,
+        "Foreign import completed"
+    );
+    summary
+}
+
+async fn flush<S: ProcessingApi + ?Sized>(
+    service: &S,
+    collection: &str,
+    batch: &mut Vec<(usize, Option<String>, ImportedVector)>,
+    summary: &mut ForeignImportSummary,
+) {
+    if batch.is_empty() {
+        return;
+    }
+    let (positions, records): (Vec<_>, Vec<_>) = std::mem::take(batch)
+        .into_iter()
+        .map(|(index, id, record)| ((index, id), record))
+        .unzip();
+    let (status, reason) = match service.import_vectors(collection, records).await {
+        Ok(_) => (RecordStatus::Imported, None),
+        Err(error) => (RecordStatus::Skipped, Some(error.to_string())),
+    };
+    for (index, id) in positions {
+        summary.record(index, id, status, reason.clone());
+    }
+}
+
+/// Text and memory metadata of `record` under `mapping`.
+fn map_record(
+    record: &ForeignRecord,
+    mapping: &FieldMapping,
+    now: OffsetDateTime,
+) -> Result<(String, IngestMetadata), String> {
+    let text = record
+        .text
+        .as_deref()
+        .filter(|text| !text.trim().is_empty())
+        .ok_or("record has no text")?;
+    let fields = &record.fields;
+    let timestamp = match fields.get(&mapping.timestamp) {
+        None | Some(Value::Null) => None,
+        Some(Value::String(value)) => Some(
+            validate_ingest_timestamp(value, false, now)
+                .map_err(|error| format!("`{}`: {error}", mapping.timestamp))?,
+        ),
+        Some(Value::Number(seconds)) => {
+            let instant = seconds
+                .as_i64()
+                .and_then(|seconds| OffsetDateTime::from_unix_timestamp(seconds).ok())
+                .ok_or_else(|| format!("`{}` is not a valid Unix timestamp", mapping.timestamp))?;
+            Some(validate_ingest_timestamp(
+                &format_utc_rfc3339(instant),
+                false,
+                now,
+            )?)
+        }
+        Some(_) => {
+            return Err(format!(
+                "`{}` must be a timestamp string or Unix seconds",
+                mapping.timestamp
+            ));
+        }
+    };
+    let metadata = IngestMetadata {
+        project_id: string_field(fields, &mapping.project_id)?,
+        memory_type: string_field(fields, &mapping.memory_type)?,
+        tags: tags_field(fields, &mapping.tags)?,
+        source_uri: string_field(fields, &mapping.source_uri)?,
+        timestamp,
+        auto_classify: None,
+    };
+    Ok((text.to_string(), metadata))
+}
+
+/// String value of `name`; numbers are accepted as their decimal form.
+fn string_field(fields: &Map<String, Value>, name: &str) -> Result<Option<String>, String> {
+    match fields.get(name) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(value)) => Ok(Some(value.clone())),
+        Some(Value::Number(value)) => Ok(Some(value.to_string())),
+        Some(_) => Err(format!("`{name}` must be a string")),
+    }
+}
+
+/// Tags stored under `name` as a string array or a comma-separated string.
+fn tags_field(fields: &Map<String, Value>, name: &str) -> Result<Option<Vec<String>>, String> {
+    let invalid = || format!("`{name}` must be a string array or a comma-separated string");
+    let tags: Vec<String> = match fields.get(name) {
+        None | Some(Value::Null) => return Ok(None),
+        Some(Value::String(value)) => value.split(',').map(|tag| tag.trim().to_string()).collect(),
+        Some(Value::Array(items)) => items
+            .iter()
+            .map(|item| item.as_str().map(str::to_string).ok_or_else(invalid))
+            .collect::<Result<_, _>>()?,
+        Some(_) => return Err(invalid()),
+    };
+    let tags: Vec<String> = tags.into_iter().filter(|tag| !tag.is_empty()).collect();
+    Ok((!tags.is_empty()).then_some(tags))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::processing::import::mem0;
+    use serde_json::json;
+
+    fn record(fields: Value) -> ForeignRecord {
+        ForeignRecord {
+            id: Some("r-1".into()),
+            text: Some("Keep the runbook current".into()),
+            vector: None,
+            fields: fields.as_object().cloned().expect("fields object"),
+        }
+    }
+
+    fn now() -> OffsetDateTime {
+        OffsetDateTime::from_unix_timestamp(1_760_000_000).expect("now")
+    }
+
+    #[test]
+    fn mem0_defaults_map_user_categories_and_creation_time() {
+        let records = mem0::parse(include_bytes!("fixtures/mem0_export.json")).expect("parse");
+        let mapping = FieldMapping::defaults(ForeignFormat::Mem0);
+
+        let (text, metadata) =
+            map_record(records[0].as_ref().expect("record"), &mapping, now()).expect("mapped");
+
+        assert_eq!(text, "Prefers dark mode in every editor");
+        assert_eq!(metadata.project_id.as_deref(), Some("alice"));
+        assert_eq!(metadata.memory_type.as_deref(), Some("semantic"));
+        assert_eq!(
+            metadata.tags,
+            Some(vec!["preferences".to_string(), "tooling".to_string()])
+        );
+        assert_eq!(
+            metadata.timestamp.as_deref(),
+            Some("2024-07-20T08:23:45.123456Z")
+        );
+        assert_eq!(metadata.source_uri.as_deref(), Some("chat/2024-07-20"));
+    }
+
+    #[test]
+    fn custom_mappings_read_comma_tags_and_unix_seconds() {
+        let mapping = FieldMapping {
+            project_id: "team".into(),
+            tags: "labels".into(),
+            timestamp: "written".into(),
+            ..FieldMapping::defaults(ForeignFormat::Chroma)
+        };
+        let fields = json!({ "team": 42, "labels": "ops, , runbooks", "written": 1735689600 });
+
+        let (_, metadata) = map_record(&record(fields), &mapping, now()).expect("mapped");
+
+        assert_eq!(metadata.project_id.as_deref(), Some("42"));
+        assert_eq!(
+            metadata.tags,
+            Some(vec!["ops".to_string(), "runbooks".to_string()])
+        );
+        assert_eq!(metadata.timestamp.as_deref(), Some("2025-01-01T00:00:00Z"));
+    }
+
+    #[test]
+    fn records_with_unusable_fields_are_rejected_with_a_reason() {
+        let mapping = FieldMapping::defaults(ForeignFormat::Chroma);
+        let cases = [
+            (json!({ "tags": [1, 2] }), "`tags` must be a string array"),
+            (
+                json!({ "project_id": { "id": 1 } }),
+                "`project_id` must be a string",
+            ),
+            (
+                json!({ "timestamp": "yesterday" }),
+                "`timestamp`: `timestamp` must be",
+            ),
+            (json!({ "timestamp": "2999-01-01" }), "is in the future"),
+        ];
+        for (fields, expected) in cases {
+            let error = map_record(&record(fields), &mapping, now()).expect_err("rejected");
+            assert!(error.contains(expected), "{error}");
+        }
+
+        let empty = ForeignRecord {
+            text: Some("  ".into()),
+            ..ForeignRecord::default()
+        };
+        assert_eq!(
+            map_record(&empty, &mapping, now()).expect_err("no text"),
+            "record has no text"
+        );
+    }
+}
@@ -0,0 +1,125 @@
+//! Parser for mem0 exports.
+//!
+//! mem0's `get_all()` returns memory objects (`id`, `memory`, `metadata`, and top-level fields
+//! such as `user_id`, `categories`, or `created_at`), either as a bare array or wrapped in
+//! `results` (v1.1 API) or `memories`. Top-level fields and `metadata` entries are merged into the
+//! record's fields; `metadata` wins on a clash.
+
+use serde_json::{Map, Value};
+
+use super::foreign::{ForeignRecord, ParsedRecord};
+
+/// Parse a mem0 export into one entry per memory object.
+///
+/// The document is rejected when it holds no memory array. Entries that are not objects, or whose
+/// `memory` or `metadata` has the wrong type, are reported individually.
+pub fn parse(document: &[u8]) -> Result<Vec<ParsedRecord>, String> {
+    let value: Value = serde_json::from_slice(document)
+        .map_err(|error| format!("invalid mem0 export: {error}"))?;
+    let entries = match value {
+        Value::Array(entries) => entries,
+        Value::Object(mut wrapper) => match ["results", "memories"]
+            .into_iter()
+            .find_map(|key| wrapper.remove(key))
+        {
+            Some(Value::Array(entries)) => entries,
+            _ => {
+                return Err(
+                    "invalid mem0 export: expected an array of memories or a `results` array"
+                        .into(),
+                );
+            }
+        },
+        _ => return Err("invalid mem0 export: expected an array of memories".into()),
+    };
+    Ok(entries
+        .into_iter()
+        .enumerate()
+        .map(|(index, entry)| parse_entry(index, entry))
+        .collect())
+}
+
+fn parse_entry(index: usize, entry: Value) -> ParsedRecord {
+    let Value::Object(mut entry) = entry else {
+        return Err(format!("entry {index} is not a memory object"));
+    };
+    let id = match entry.remove("id") {
+        Some(Value::String(id)) => Some(id),
+        Some(Value::Number(id)) => Some(id.to_string()),
+        _ => None,
+    };
+    let text = match entry.remove("memory") {
+        None | Some(Value::Null) => None,
+        Some(Value::String(text)) => Some(text),
+        Some(_) => return Err("`memory` must be a string".into()),
+    };
+    let metadata = match entry.remove("metadata") {
+        None | Some(Value::Null) => Map::new(),
+        Some(Value::Object(metadata)) => metadata,
+        Some(_) => return Err("`metadata` must be an object".into()),
+    };
+    let mut fields = entry;
+    fields.extend(metadata);
+    Ok(ForeignRecord {
+        id,
+        text,
+        vector: None,
+        fields,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const FIXTURE: &str = include_str!("fixtures/mem0_export.json");
+
+    #[test]
+    fn parses_memories_merging_metadata_over_top_level_fields() {
+        let records = parse(FIXTURE.as_bytes()).expect("parse");
+
+        assert_eq!(records.len(), 4);
+        let first = records[0].as_ref().expect("first record");
+        assert_eq!(
+            first.id.as_deref(),
+            Some("8f0b2c1e-5d3a-4f5e-9b1a-2c7d9e0f1a2b")
+        );
+        assert_eq!(
+            first.text.as_deref(),
+            Some("Prefers dark mode in every editor")
+        );
+        assert_eq!(first.vector, None);
+        assert_eq!(first.fields["user_id"], json!("alice"));
+        assert_eq!(
+            first.fields["categories"],
+            json!(["preferences", "tooling"])
+        );
+        assert_eq!(first.fields["memory_type"], json!("semantic"));
+        assert!(!first.fields.contains_key("memory"));
+
+        assert_eq!(
+            records[2].as_ref().expect("third record").text.as_deref(),
+            Some("")
+        );
+        assert_eq!(
+            records[3].as_ref().expect_err("fourth record"),
+            "entry 3 is not a memory object"
+        );
+    }
+
+    #[test]
+    fn accepts_bare_arrays_and_rejects_other_documents() {
+        let records = parse(br#"[{ "memory": "Uses vim", "metadata": { "user_id": "carol" } }]"#)
+            .expect("parse");
+        let record = records[0].as_ref().expect("record");
+        assert_eq!(record.fields["user_id"], json!("carol"));
+
+        assert!(parse(br#"{ "count": 0 }"#).is_err());
+        assert!(parse(b"\"memories\"").is_err());
+        assert_eq!(
+            parse(br#"[{ "memory": ["a"] }]"#).expect("parse")[0],
+            Err("`memory` must be a string".into())
+        );
+    }
+}
@@ -0,0 +1,269 @@
+//! Bulk import of newline-delimited JSON memory records.
+//!
+//! Each line is one memory. Lines carrying a `vector` are stored as-is in batches of
+//! [`IMPORT_BATCH_SIZE`]; lines without one run through the regular `push` pipeline and are
+//! re-embedded. Input is consumed incrementally and every line is handled before the next chunk
+//! is read, so memory stays bounded by one line ([`MAX_IMPORT_LINE_BYTES`]) plus one batch no
+//! matter how large the import is.
+//!
+//! Exports from other memory servers are whole JSON documents instead: [`chroma`] and [`mem0`]
+//! parse them into [`foreign::ForeignRecord`]s, which [`foreign::import_foreign`] maps onto
+//! memory metadata and stores.
+
+pub mod chroma;
+pub mod foreign;
+pub mod mem0;
+
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::processing::{
+    ProcessingApi, sanitize::validate_ingest_timestamp, types::IngestMetadata,
+};
+
+/// Pre-embedded records written to Qdrant per request.
+pub const IMPORT_BATCH_SIZE: usize = 64;
+/// Longest accepted line; longer lines are skipped and reported.
+pub const MAX_IMPORT_LINE_BYTES: usize = 1024 * 1024;
+/// Per-line errors retained in [`ImportSummary::errors`]; `failed` keeps counting past it.
+pub const MAX_IMPORT_ERRORS: usize = 100;
+
+/// One line of an import stream.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ImportRecord {
+    /// Memory text.
+    pub text: String,
+    /// Existing embedding; when absent the text is chunked and embedded like a `push`.
+    #[serde(default)]
+    pub vector: Option<Vec<f32>>,
+    /// Optional project identifier.
+    #[serde(default)]
+    pub project_id: Option<String>,
+    /// Optional memory classification.
+    #[serde(default)]
+    pub memory_type: Option<String>,
+    /// Optional tags.
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+    /// Optional source URI.
+    #[serde(default)]
+    pub source_uri: Option<String>,
+    /// Optional RFC3339 timestamp recorded instead of the import time.
+    #[serde(default)]
+    pub timestamp: Option<String>,
+    /// Accept a `timestamp` further in the future than the allowed clock skew.
+    #[serde(default)]
+    pub allow_future: bool,
+}
+
+/// Record that already carries its embedding.
+#[derive(Debug, Clone)]
+pub struct ImportedVector {
+    /// Memory text stored in the payload.
+    pub text: String,
+    /// Embedding written unchanged.
+    pub vector: Vec<f32>,
+    /// Payload metadata.
+    pub metadata: IngestMetadata,
+}
+
+/// Failure attributed to one input line.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ImportLineError {
+    /// 1-based line number in the input.
+    pub line: usize,
+    /// Why the line was not imported.
+    pub error: String,
+}
+
+/// Aggregate result of an import.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ImportSummary {
+    /// Non-blank lines read.
+    pub lines: usize,
+    /// Lines stored as memories.
+    pub imported: usize,
+    /// Imported lines that were chunked and embedded.
+    pub reembedded: usize,
+    /// Imported lines whose supplied vector was stored unchanged.
+    pub reused_vectors: usize,
+    /// Lines that were not imported.
+    pub failed: usize,
+    /// First [`MAX_IMPORT_ERRORS`] failures.
+    pub errors: Vec<ImportLineError>,
+}
+
+impl ImportSummary {
+    fn fail(&mut self, line: usize, error: String) {
+        self.failed += 1;
+        if self.errors.len() < MAX_IMPORT_ERRORS {
+            self.errors.push(ImportLineError { line, error });
+        }
+    }
+}
+
+/// Incremental importer fed with raw byte chunks of an NDJSON stream.
+pub struct NdjsonImport<'a, S: ?Sized> {
+    service: &'a S,
+    collection: &'a str,
+    dimension: usize,
+    buffer: Vec<u8>,
+    /// The current line outgrew [`MAX_IMPORT_LINE_BYTES`] and is being discarded.
+    oversized: bool,
+    line: usize,
+    batch: Vec<(usize, ImportedVector)>,
+    summary: ImportSummary,
+}
+
+impl<'a, S: ProcessingApi + ?Sized> NdjsonImport<'a, S> {
+    /// Start an import into `collection`, whose stored vectors have `dimension` components.
+    pub fn new(service: &'a S, collection: &'a str, dimension: usize) -> Self {
+        Self {
+            service,
+            collection,
+            dimension,
+            buffer: Vec::new(),
+            oversized: false,
+            line: 0,
+            batch: Vec::with_capacity(IMPORT_BATCH_SIZE),
+            summary: ImportSummary::default(),
+        }
+    }
+
+    /// Consume the next chunk, importing every line it completes before returning.
+    pub async fn feed(&mut self, mut chunk: &[u8]) {
+        while let Some(newline) = chunk.iter().position(|byte| *byte == b'\n') {
+            self.append(&chunk[..newline]);
+            self.end_line().await;
+            chunk = &chunk[newline + 1..];
+        }
+        self.append(chunk);
+    }
+
+    /// Import any unterminated final line, flush the pending batch, and report the totals.
+    pub async fn finish(mut self) -> ImportSummary {
+        if !self.buffer.is_empty() || self.oversized {
+            self.end_line().await;
+        }
+        self.flush().await;
+        tracing::info!(
+            collection = self.collection,
+            lines = self.summary.lines,
+            imported = self.summary.imported,
+            failed = self.summary.failed,
+            "Import completed"
+        );
+        self.summary
+    }
+
+    fn append(&mut self, bytes: &[u8]) {
+        if self.oversized {
+            return;
+        }
+        if self.buffer.len() + bytes.len() > MAX_IMPORT_LINE_BYTES {
+            self.oversized = true;
+            self.buffer = Vec::new();
+        } else {
+            self.buffer.extend_from_slice(bytes);
+        }
+    }
+
+    async fn end_line(&mut self) {
+        self.line += 1;
+        let line = std::mem::take(&mut self.buffer);
+        if std::mem::take(&mut self.oversized) {
+            self.summary.lines += 1;
+            self.summary.fail(
+                self.line,
+                format!("line exceeds {MAX_IMPORT_LINE_BYTES} bytes"),
+            );
+            return;
+        }
+        if line.trim_ascii().is_empty() {
+            return;
+        }
+        self.summary.lines += 1;
+        match self.parse(&line) {
+            Ok((text, Some(vector), metadata)) => {
+                self.batch.push((
+                    self.line,
+                    ImportedVector {
+                        text,
+                        vector,
+                        metadata,
+                    },
+                ));
+                if self.batch.len() >= IMPORT_BATCH_SIZE {
+                    self.flush().await;
+                }
+            }
+            Ok((text, None, metadata)) => {
+                match self
+                    .service
+                    .process_and_index(self.collection, text, metadata)
+                    .await
+                {
+                    Ok(_) => {
+                        self.summary.imported += 1;
+                        self.summary.reembedded += 1;
+                    }
+                    Err(error) => self.summary.fail(self.line, error.to_string()),
+                }
+            }
+            Err(error) => self.summary.fail(self.line, error),
+        }
+    }
+
+    fn parse(&self, line: &[u8]) -> Result<(String, Option<Vec<f32>>, IngestMetadata), String> {
+        let record: ImportRecord =
+            serde_json::from_slice(line).map_err(|error| format!("invalid record: {error}"))?;
+        if record.text.trim().is_empty() {
+            return Err("`text` must not be empty".into());
+        }
+        if let Some(vector) = record.vector.as_ref()
+            && vector.len() != self.dimension
+        {
+            return Err(format!(
+                "`vector` has {} dimensions; expected {}",
+                vector.len(),
+                self.dimension
+            ));
+        }
+        let timestamp = record
+            .timestamp
+            .map(|value| {
+                validate_ingest_timestamp(&value, record.allow_future, OffsetDateTime::now_utc())
+            })
+            .transpose()?;
+        let metadata = IngestMetadata {
+            project_id: record.project_id,
+            memory_type: record.memory_type,
+            tags: record.tags,
+            source_uri: record.source_uri,
+            timestamp,
+            auto_classify: None,
+        };
+        Ok((record.text, record.vector, metadata))
+    }
+
+    async fn flush(&mut self) {
+        if self.batch.is_empty() {
+            return;
+        }
+        let (lines, records): (Vec<usize>, Vec<ImportedVector>) =
+            std::mem::take(&mut self.batch).into_iter().unzip();
+        match self.service.import_vectors(self.collection, records).await {
+            Ok(stored) => {
+                self.summary.imported += stored;
+                self.summary.reused_vectors += stored;
+            }
+            Err(error) => {
+                let message = error.to_string();
+                for line in lines {
+                    self.summary.fail(line, message.clone());
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,105 @@
+//! Directed links between memories.
+//!
+//! A link is stored on its source point as `links: [{ rel, target_memory_id }]`. Both ends also
+//! carry a derived `linked_to` keyword array listing every memory the point is linked with, in
+//! either direction. Outbound links come from a point's own `links`; inbound links are found by a
+//! filtered scroll on the indexed `linked_to` field and read from the linking points.
+
+use serde_json::{Map, Value};
+
+use crate::qdrant::MemoryLink;
+
+/// Links stored in a `links` payload value; malformed entries are skipped.
+pub(crate) fn stored_links(value: Option<&Value>) -> Vec<MemoryLink> {
+    match value {
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(|item| serde_json::from_value(item.clone()).ok())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Memory identifiers in a payload's `linked_to` array.
+pub(crate) fn linked_ids(payload: &Map<String, Value>) -> Vec<String> {
+    match payload.get("linked_to") {
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Append `link` unless the same relation to the same target is already recorded.
+///
+/// Returns whether the link was added.
+pub(crate) fn add_link(links: &mut Vec<MemoryLink>, link: MemoryLink) -> bool {
+    if links.contains(&link) {
+        return false;
+    }
+    links.push(link);
+    true
+}
+
+/// Append `memory_id` to a `linked_to` list unless already present.
+///
+/// Returns whether the list changed.
+pub(crate) fn add_linked_id(ids: &mut Vec<String>, memory_id: &str) -> bool {
+    if ids.iter().any(|id| id == memory_id) {
+        return false;
+    }
+    ids.push(memory_id.to_string());
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qdrant::LinkRelation;
+    use serde_json::json;
+
+    fn link(rel: LinkRelation, target: &str) -> MemoryLink {
+        MemoryLink {
+            rel,
+            target_memory_id: target.to_string(),
+        }
+    }
+
+    #[test]
+    fn stored_links_skip_malformed_entries() {
+        let value = json!([
+            { "rel": "supersedes", "target_memory_id": "a" },
+            { "rel": "replaces", "target_memory_id": "b" },
+            { "rel": "refines" },
+            "c",
+            { "rel": "relates_to", "target_memory_id": "d" }
+        ]);
+        assert_eq!(
+            stored_links(Some(&value)),
+            [
+                link(LinkRelation::Supersedes, "a"),
+                link(LinkRelation::RelatesTo, "d")
+            ]
+        );
+        assert!(stored_links(Some(&json!("a"))).is_empty());
+        assert!(stored_links(None).is_empty());
+    }
+
+    #[test]
+    fn duplicate_links_and_ids_are_not_added_twice() {
+        let mut links = vec![link(LinkRelation::Refines, "a")];
+        assert!(!add_link(&mut links, link(LinkRelation::Refines, "a")));
+        assert!(add_link(&mut links, link(LinkRelation::Supersedes, "a")));
+        assert!(add_link(&mut links, link(LinkRelation::Refines, "b")));
+        assert_eq!(links.len(), 3);
+
+        let payload = json!({ "linked_to": ["a", 7, "b"] });
+        let mut ids = linked_ids(payload.as_object().expect("object"));
+        assert_eq!(ids, ["a", "b"]);
+        assert!(!add_linked_id(&mut ids, "a"));
+        assert!(add_linked_id(&mut ids, "c"));
+        assert_eq!(ids, ["a", "b", "c"]);
+    }
+}
@@ -0,0 +1,162 @@
+//! Short-lived cache of the distinct project and tag sets per collection.
+//!
+//! Listing either set scrolls the whole collection, and UIs poll them often. Entries expire
+//! after `LISTING_CACHE_TTL_MS` and are dropped whenever this process writes to or deletes from
+//! the collection; writes made by other processes show up once the TTL lapses.
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::{Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+/// Which listing an entry holds.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Listing {
+    Projects,
+    Tags(Option<String>),
+}
+
+/// TTL-bounded map from `(collection, listing)` to the distinct values last scrolled.
+pub(crate) struct ListingCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<(String, Listing), CachedListing>>,
+}
+
+struct CachedListing {
+    stored_at: Instant,
+    values: BTreeSet<String>,
+}
+
+impl ListingCache {
+    /// Create a cache keeping each listing for `ttl`; a zero TTL disables it.
+    pub(crate) fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Cached project identifiers of `collection`, if still fresh.
+    pub(crate) fn projects(&self, collection: &str) -> Option<BTreeSet<String>> {
+        self.get(collection, Listing::Projects)
+    }
+
+    /// Remember the project identifiers of `collection`.
+    pub(crate) fn store_projects(&self, collection: &str, projects: &BTreeSet<String>) {
+        self.insert(collection, Listing::Projects, projects);
+    }
+
+    /// Cached tags of `collection` (within `project_id` when set), if still fresh.
+    pub(crate) fn tags(
+        &self,
+        collection: &str,
+        project_id: Option<&str>,
+    ) -> Option<BTreeSet<String>> {
+        self.get(collection, Listing::Tags(project_id.map(str::to_string)))
+    }
+
+    /// Remember the tags of `collection` (within `project_id` when set).
+    pub(crate) fn store_tags(
+        &self,
+        collection: &str,
+        project_id: Option<&str>,
+        tags: &BTreeSet<String>,
+    ) {
+        self.insert(
+            collection,
+            Listing::Tags(project_id.map(str::to_string)),
+            tags,
+        );
+    }
+
+    /// Drop every listing of `collection`.
+    pub(crate) fn invalidate(&self, collection: &str) {
+        if self.ttl.is_zero() {
+            return;
+        }
+        self.entries()
+            .retain(|(cached, _), _| cached.as_str() != collection);
+    }
+
+    /// Drop every listing, for operations such as renames that also move aliases.
+    pub(crate) fn clear(&self) {
+        self.entries().clear();
+    }
+
+    fn get(&self, collection: &str, listing: Listing) -> Option<BTreeSet<String>> {
+        if self.ttl.is_zero() {
+            return None;
+        }
+        let mut entries = self.entries();
+        let key = (collection.to_string(), listing);
+        match entries.get(&key) {
+            Some(entry) if entry.stored_at.elapsed() < self.ttl => Some(entry.values.clone()),
+            Some(_) => {
+                entries.remove(&key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn insert(&self, collection: &str, listing: Listing, values: &BTreeSet<String>) {
+        if self.ttl.is_zero() {
+            return;
+        }
+        let mut entries = self.entries();
+        entries.retain(|_, entry| entry.stored_at.elapsed() < self.ttl);
+        entries.insert(
+            (collection.to_string(), listing),
+            CachedListing {
+                stored_at: Instant::now(),
+                values: values.clone(),
+            },
+        );
+    }
+
+    fn entries(&self) -> std::sync::MutexGuard<'_, HashMap<(String, Listing), CachedListing>> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(values: &[&str]) -> BTreeSet<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn zero_ttl_disables_the_cache() {
+        let cache = ListingCache::new(Duration::ZERO);
+        cache.store_projects("notes", &set(&["alpha"]));
+        assert!(cache.projects("notes").is_none());
+    }
+
+    #[test]
+    fn tags_are_keyed_by_project_and_expire_after_ttl() {
+        let cache = ListingCache::new(Duration::from_millis(20));
+        cache.store_tags("notes", Some("alpha"), &set(&["api"]));
+        assert_eq!(cache.tags("notes", Some("alpha")), Some(set(&["api"])));
+        assert!(cache.tags("notes", None).is_none());
+        assert!(cache.tags("notes", Some("beta")).is_none());
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(cache.tags("notes", Some("alpha")).is_none());
+    }
+
+    #[test]
+    fn invalidation_only_drops_the_written_collection() {
+        let cache = ListingCache::new(Duration::from_secs(60));
+        cache.store_projects("notes", &set(&["alpha"]));
+        cache.store_tags("notes", None, &set(&["api"]));
+        cache.store_projects("archive", &set(&["beta"]));
+
+        cache.invalidate("notes");
+
+        assert!(cache.projects("notes").is_none());
+        assert!(cache.tags("notes", None).is_none());
+        assert_eq!(cache.projects("archive"), Some(set(&["beta"])));
+    }
+}
@@ -0,0 +1,384 @@
+//! Declarative collection manifests.
+//!
+//! A manifest lists the collections a deployment expects, in TOML:
+//!
+//! ```toml
+//! [[collections]]
+//! name = "team-docs"
+//! vector_size = 768
+//! distance = "Cosine"
+//! shard_number = 2
+//! replication_factor = 2
+//!
+//! [collections.payload_indexes]
+//! source_uri = "keyword"
+//! priority = "integer"
+//! ```
+//!
+//! Applying a manifest (at startup through `COLLECTION_MANIFEST`, or with the `apply-manifest`
+//! tool) is idempotent: missing collections are created, missing payload indexes are added, and
+//! collections that already match are left alone. Settings Qdrant cannot change in place — vector
+//! size, distance, shard and replication counts, or an index of another type — are reported as
+//! conflicts and the collection is not touched.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::qdrant::{
+    CollectionSchema, CollectionTopology, DEFAULT_DISTANCE, PAYLOAD_INDEX_SCHEMAS,
+    VECTOR_DISTANCES, validate_collection_name,
+};
+
+/// Errors raised while loading or validating a manifest.
+#[derive(Debug, Error)]
+pub enum ManifestError {
+    /// The manifest file could not be read.
+    #[error("failed to read collection manifest {path}: {source}")]
+    Read {
+        /// Path that was read.
+        path: PathBuf,
+        /// Underlying I/O failure.
+        source: std::io::Error,
+    },
+    /// The manifest is not valid TOML or does not have the expected shape.
+    #[error("invalid collection manifest: {0}")]
+    Parse(String),
+    /// An entry breaks a rule enforced by the individual collection tools.
+    #[error("invalid collection manifest entry `{collection}`: {message}")]
+    Invalid {
+        /// Name of the offending entry.
+        collection: String,
+        /// What is wrong with it.
+        message: String,
+    },
+}
+
+/// Collections a deployment expects, applied in order.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CollectionManifest {
+    /// Declared collections.
+    #[serde(default)]
+    pub collections: Vec<ManifestCollection>,
+}
+
+/// One `[[collections]]` entry.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestCollection {
+    /// Collection name.
+    pub name: String,
+    /// Vector dimension; defaults to `EMBEDDING_DIMENSION`.
+    #[serde(default)]
+    pub vector_size: Option<u64>,
+    /// Distance metric; defaults to `Cosine`.
+    #[serde(default)]
+    pub distance: Option<String>,
+    /// Shard count; defaults to `QDRANT_SHARD_NUMBER`.
+    #[serde(default)]
+    pub shard_number: Option<u32>,
+    /// Replication factor; defaults to `QDRANT_REPLICATION_FACTOR`.
+    #[serde(default)]
+    pub replication_factor: Option<u32>,
+    /// Payload indexes created in addition to the standard ones, keyed by field.
+    #[serde(default)]
+    pub payload_indexes: BTreeMap<String, String>,
+}
+
+/// What applying a manifest entry did.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ManifestAction {
+    /// The collection was missing and has been created.
+    Created,
+    /// The collection existed; the listed payload indexes were added.
+    Updated {
+        /// Fields that received a new index.
+        indexes_added: Vec<String>,
+    },
+    /// The collection already matched the manifest.
+    Unchanged,
+    /// The collection differs in ways that cannot be changed in place; nothing was modified.
+    Conflict {
+        /// One line per differing setting.
+        reasons: Vec<String>,
+    },
+}
+
+impl ManifestAction {
+    /// Stable lowercase label used in tool responses and logs.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Created => "created",
+            Self::Updated { .. } => "updated",
+            Self::Unchanged => "unchanged",
+            Self::Conflict { .. } => "conflict",
+        }
+    }
+}
+
+/// Result of applying one manifest entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestResult {
+    /// Collection the entry describes.
+    pub collection: String,
+    /// What was done.
+    pub action: ManifestAction,
+}
+
+impl CollectionManifest {
+    /// Parse and validate a TOML manifest.
+    pub fn parse(text: &str) -> Result<Self, ManifestError> {
+        let manifest: Self =
+            toml::from_str(text).map_err(|error| ManifestError::Parse(error.to_string()))?;
+        manifest.validate()?;
+        Ok(manifest)
+    }
+
+    /// Read, parse, and validate the manifest at `path`.
+    pub fn load(path: &Path) -> Result<Self, ManifestError> {
+        let text = std::fs::read_to_string(path).map_err(|source| ManifestError::Read {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Self::parse(&text)
+    }
+
+    fn validate(&self) -> Result<(), ManifestError> {
+        if self.collections.is_empty() {
+            return Err(ManifestError::Parse(
+                "no [[collections]] entries declared".to_string(),
+            ));
+        }
+        let mut seen = BTreeSet::new();
+        for entry in &self.collections {
+            let invalid = |message: String| ManifestError::Invalid {
+                collection: entry.name.clone(),
+                message,
+            };
+            validate_collection_name(&entry.name).map_err(invalid)?;
+            if !seen.insert(entry.name.as_str()) {
+                return Err(invalid("declared more than once".to_string()));
+            }
+            if entry.vector_size == Some(0) {
+                return Err(invalid("`vector_size` must be at least 1".to_string()));
+            }
+            for (key, value) in [
+                ("shard_number", entry.shard_number),
+                ("replication_factor", entry.replication_factor),
+            ] {
+                if value == Some(0) {
+                    return Err(invalid(format!("`{key}` must be at least 1")));
+                }
+            }
+            if let Some(distance) = &entry.distance
+                && !VECTOR_DISTANCES.contains(&distance.as_str())
+            {
+                return Err(invalid(format!(
+                    "unknown distance `{distance}`; expected one of {}",
+                    VECTOR_DISTANCES.join(", ")
+                )));
+            }
+            for (field, schema) in &entry.payload_indexes {
+                if field.trim().is_empty() {
+                    return Err(invalid("payload index field must not be empty".to_string()));
+                }
+                if !PAYLOAD_INDEX_SCHEMAS.contains(&schema.as_str()) {
+                    return Err(invalid(format!(
+                        "unknown index type `{schema}` for `{field}`; expected one of {}",
+                        PAYLOAD_INDEX_SCHEMAS.join(", ")
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ManifestCollection {
+    /// Vector size to create the collection with.
+    pub fn target_vector_size(&self, default_size: u64) -> u64 {
+        self.vector_size.unwrap_or(default_size)
+    }
+
+    /// Distance metric to create the collection with.
+    pub fn target_distance(&self) -> &str {
+        self.distance.as_deref().unwrap_or(DEFAULT_DISTANCE)
+    }
+
+    /// Topology declared by the entry; unset fields fall back to configuration on creation.
+    pub fn topology(&self) -> CollectionTopology {
+        CollectionTopology {
+            shard_number: self.shard_number,
+            replication_factor: self.replication_factor,
+        }
+    }
+
+    /// Compare the entry with an existing collection.
+    ///
+    /// Returns the declared payload indexes that are missing, or the reasons the collection
+    /// cannot be brought in line without being recreated. Topology is only compared when the
+    /// entry declares it and Qdrant reports it.
+    pub fn diff(
+        &self,
+        existing: &CollectionSchema,
+        default_size: u64,
+    ) -> Result<Vec<(String, String)>, Vec<String>> {
+        let mut reasons = Vec::new();
+        let size = self.target_vector_size(default_size);
+        if existing.vector_size != Some(size) {
+            let actual = existing
+                .vector_size
+                .map_or_else(|| "unknown".to_string(), |value| value.to_string());
+            reasons.push(format!("vector size is {actual}, manifest declares {size}"));
+        }
+        if let Some(actual) = &existing.distance
+            && !actual.eq_ignore_ascii_case(self.target_distance())
+        {
+            reasons.push(format!(
+                "distance is {actual}, manifest declares {}",
+                self.target_distance()
+            ));
+        }
+        for (key, declared, actual) in [
+            (
+                "shard_number",
+                self.shard_number,
+                existing.topology.shard_number,
+            ),
+            (
+                "replication_factor",
+                self.replication_factor,
+                existing.topology.replication_factor,
+            ),
+        ] {
+            if let (Some(declared), Some(actual)) = (declared, actual)
+                && declared != actual
+            {
+                reasons.push(format!("{key} is {actual}, manifest declares {declared}"));
+            }
+        }
+
+        let mut missing = Vec::new();
+        for (field, schema) in &self.payload_indexes {
+            match existing.payload_indexes.get(field) {
+                Some(actual) if !actual.eq_ignore_ascii_case(schema) => reasons.push(format!(
+                    "payload index `{field}` is {actual}, manifest declares {schema}"
+                )),
+                Some(_) => {}
+                None => missing.push((field.clone(), schema.clone())),
+            }
+        }
+
+        if reasons.is_empty() {
+            Ok(missing)
+        } else {
+            Err(reasons)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"
+[[collections]]
+name = "team-docs"
+vector_size = 4
+shard_number = 2
+
+[collections.payload_indexes]
+source_uri = "keyword"
+priority = "integer"
+
+[[collections]]
+name = "scratch"
+distance = "Dot"
+"#;
+
+    fn schema(size: u64, distance: &str, indexes: &[(&str, &str)]) -> CollectionSchema {
+        CollectionSchema {
+            vector_size: Some(size),
+            distance: Some(distance.to_string()),
+            payload_indexes: indexes
+                .iter()
+                .map(|(field, kind)| (field.to_string(), kind.to_string()))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn invalid(text: &str) -> String {
+        CollectionManifest::parse(text)
+            .expect_err("manifest should be rejected")
+            .to_string()
+    }
+
+    #[test]
+    fn manifests_parse_with_defaults() {
+        let manifest = CollectionManifest::parse(MANIFEST).expect("manifest");
+        let [docs, scratch] = manifest.collections.as_slice() else {
+            panic!("expected two collections");
+        };
+        assert_eq!(docs.target_vector_size(768), 4);
+        assert_eq!(docs.target_distance(), "Cosine");
+        assert_eq!(docs.topology().shard_number, Some(2));
+        assert_eq!(docs.payload_indexes["priority"], "integer");
+        assert_eq!(scratch.target_vector_size(768), 768);
+        assert_eq!(scratch.target_distance(), "Dot");
+    }
+
+    #[test]
+    fn manifests_follow_the_collection_tool_rules() {
+        assert!(invalid("[[collections]]\nname = \"a/b\"").contains("invalid character"));
+        assert!(invalid("[[collections]]\nname = \"a\"\nvector_size = 0").contains("vector_size"));
+        assert!(
+            invalid("[[collections]]\nname = \"a\"\nshard_number = 0").contains("shard_number")
+        );
+        assert!(invalid("[[collections]]\nname = \"a\"\ndistance = \"cos\"").contains("Cosine"));
+        assert!(
+            invalid("[[collections]]\nname = \"a\"\npayload_indexes = { x = \"blob\" }")
+                .contains("unknown index type `blob`")
+        );
+        assert!(
+            invalid("[[collections]]\nname = \"a\"\n[[collections]]\nname = \"a\"")
+                .contains("more than once")
+        );
+        assert!(invalid("[[collections]]\nname = \"a\"\nquota = 10").contains("quota"));
+        assert!(invalid("").contains("no [[collections]]"));
+    }
+
+    #[test]
+    fn diff_reports_missing_indexes_and_drift() {
+        let manifest = CollectionManifest::parse(MANIFEST).expect("manifest");
+        let docs = &manifest.collections[0];
+
+        let matching = schema(
+            4,
+            "Cosine",
+            &[("source_uri", "keyword"), ("priority", "integer")],
+        );
+        assert_eq!(docs.diff(&matching, 768), Ok(Vec::new()));
+
+        let partial = schema(4, "Cosine", &[("source_uri", "keyword")]);
+        assert_eq!(
+            docs.diff(&partial, 768),
+            Ok(vec![("priority".to_string(), "integer".to_string())])
+        );
+
+        let mut drifted = schema(8, "Euclid", &[("priority", "keyword")]);
+        drifted.topology.shard_number = Some(1);
+        assert_eq!(
+            docs.diff(&drifted, 768),
+            Err(vec![
+                "vector size is 8, manifest declares 4".to_string(),
+                "distance is Euclid, manifest declares Cosine".to_string(),
+                "shard_number is 1, manifest declares 2".to_string(),
+                "payload index `priority` is keyword, manifest declares integer".to_string(),
+            ])
+        );
+    }
+}
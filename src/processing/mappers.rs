@@ -1,10 +1,15 @@
 //! Mapping helpers for Qdrant payloads and chunk preparation.
 
 use crate::{
-    processing::{sanitize, types::SearchHit},
+    processing::{
+        chunking::TextChunk,
+        links::stored_links,
+        sanitize,
+        types::{IngestProfile, SearchHit},
+    },
     qdrant::{self, compute_chunk_hash},
 };
-use serde_json::Value;
+use serde_json::{Map, Value};
 use std::collections::HashSet;
 
 /// Chunk text with associated hash ready for ingestion.
@@ -14,23 +19,47 @@ pub(crate) struct PreparedChunk {
     pub(crate) text: String,
     /// Stable digest used for dedupe.
     pub(crate) chunk_hash: String,
+    /// Composite score from [`sanitize::score_chunk_quality`].
+    pub(crate) quality_score: f32,
+    /// Position of the chunk in the document, counted before dedupe and quality filtering.
+    pub(crate) chunk_index: usize,
+    /// `content_kind` payload value, set for standalone code blocks.
+    pub(crate) content_kind: Option<&'static str>,
+    /// `language` payload value detected by the code splitter.
+    pub(crate) language: Option<&'static str>,
+    /// `symbol` payload value detected by the code splitter.
+    pub(crate) symbol: Option<String>,
 }
 
 /// Remove duplicate chunks within a document, keeping the first occurrence.
-pub(crate) fn dedupe_chunks(chunks: Vec<String>) -> (Vec<PreparedChunk>, usize) {
+pub(crate) fn dedupe_chunks(
+    chunks: impl IntoIterator<Item = impl Into<TextChunk>>,
+) -> (Vec<PreparedChunk>, usize) {
     let mut seen = HashSet::new();
     let mut prepared = Vec::new();
     let mut skipped = 0;
 
-    for text in chunks {
+    for (chunk_index, chunk) in chunks.into_iter().enumerate() {
+        let TextChunk {
+            text,
+            content_kind,
+            language,
+            symbol,
+        } = chunk.into();
         if text.trim().is_empty() {
             continue;
         }
         let hash = compute_chunk_hash(&text);
         if seen.insert(hash.clone()) {
+            let quality_score = sanitize::score_chunk_quality(&text).score;
             prepared.push(PreparedChunk {
                 text,
                 chunk_hash: hash,
+                quality_score,
+                chunk_index,
+                content_kind,
+                language,
+                symbol,
             });
         } else {
             skipped += 1;
@@ -40,6 +69,19 @@ pub(crate) fn dedupe_chunks(chunks: Vec<String>) -> (Vec<PreparedChunk>, usize)
     (prepared, skipped)
 }
 
+/// Split off chunks scoring below `min_quality`, returning `(kept, skipped)` in input order.
+pub(crate) fn split_low_quality(
+    chunks: Vec<PreparedChunk>,
+    min_quality: Option<f32>,
+) -> (Vec<PreparedChunk>, Vec<PreparedChunk>) {
+    match min_quality {
+        Some(min) => chunks
+            .into_iter()
+            .partition(|chunk| chunk.quality_score >= min),
+        None => (chunks, Vec::new()),
+    }
+}
+
 /// Map a Qdrant scored point into a user-friendly search hit structure.
 pub(crate) fn map_scored_point(point: qdrant::ScoredPoint) -> SearchHit {
     let qdrant::ScoredPoint { id, score, payload } = point;
@@ -49,7 +91,13 @@ pub(crate) fn map_scored_point(point: qdrant::ScoredPoint) -> SearchHit {
     let mut memory_type = None;
     let mut timestamp = None;
     let mut source_uri = None;
+    let mut doc_id = None;
+    let mut chunk_index = None;
+    let mut ingested_at = None;
+    let mut embedding_model = None;
     let mut tags = None;
+    let mut citations = None;
+    let mut links = None;
 
     if let Some(mut map) = payload {
         if let Some(Value::String(value)) = map.remove("text") {
@@ -76,7 +124,21 @@ pub(crate) fn map_scored_point(point: qdrant::ScoredPoint) -> SearchHit {
                 source_uri = Some(trimmed.to_string());
             }
         }
+        if let Some(Value::String(value)) = map.remove("doc_id") {
+            doc_id = Some(value);
+        }
+        chunk_index = map.get("chunk_index").and_then(Value::as_u64);
+        if let Some(Value::String(value)) = map.remove("ingested_at") {
+            ingested_at = Some(value);
+        }
+        embedding_model = map
+            .get("ingest_profile")
+            .and_then(Value::as_str)
+            .and_then(IngestProfile::model_from_compact)
+            .map(str::to_string);
         tags = sanitize::extract_tags(&map);
+        citations = extract_citations(&mut map);
+        links = Some(stored_links(map.get("links"))).filter(|links| !links.is_empty());
     }
 
     SearchHit {
@@ -88,13 +150,32 @@ pub(crate) fn map_scored_point(point: qdrant::ScoredPoint) -> SearchHit {
         tags,
         timestamp,
         source_uri,
+        doc_id,
+        chunk_index,
+        ingested_at,
+        embedding_model,
+        citations,
+        links,
+        collection: None,
     }
 }
 
+/// Take the summary citation map out of a payload, ignoring malformed or empty entries.
+pub(crate) fn extract_citations(payload: &mut Map<String, Value>) -> Option<Vec<qdrant::Citation>> {
+    let citations: Vec<qdrant::Citation> = match payload.remove("citations")? {
+        Value::Array(items) => items
+            .into_iter()
+            .filter_map(|item| serde_json::from_value(item).ok())
+            .collect(),
+        _ => return None,
+    };
+    (!citations.is_empty()).then_some(citations)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use serde_json::{Map, Value};
+    use serde_json::json;
 
     #[test]
     fn dedupe_chunks_removes_duplicates_and_counts_skips() {
@@ -113,6 +194,22 @@ mod tests {
         assert_ne!(deduped[0].chunk_hash, deduped[1].chunk_hash);
     }
 
+    #[test]
+    fn split_low_quality_only_filters_when_a_threshold_is_set() {
+        let (chunks, _) = dedupe_chunks(vec![
+            "The deploy runbook covers rollback and key rotation.".to_string(),
+            "aGVsbG8gd29ybGQgaGVsbG8gd29ybGQgaGVsbG8gd29ybGQgaGVsbG8gd29ybGQgaGVsbG8=".to_string(),
+        ]);
+
+        let (kept, skipped) = split_low_quality(chunks.clone(), None);
+        assert_eq!((kept.len(), skipped.len()), (2, 0));
+
+        let (kept, skipped) = split_low_quality(chunks, Some(0.5));
+        assert_eq!(kept.len(), 1);
+        assert!(kept[0].text.starts_with("The deploy runbook"));
+        assert!(skipped[0].text.starts_with("aGVsbG8"));
+    }
+
     #[test]
     fn map_scored_point_extracts_payload_fields() {
         let mut payload = Map::new();
@@ -124,6 +221,14 @@ mod tests {
             Value::String("2025-01-01T00:00:00Z".into()),
         );
         payload.insert("source_uri".into(), Value::String("file://note".into()));
+        payload.insert(
+            "ingested_at".into(),
+            Value::String("2025-02-01T00:00:00Z".into()),
+        );
+        payload.insert(
+            "ingest_profile".into(),
+            Value::String("ollama:nomic-embed-text@768 chunk=512+0 semchunk".into()),
+        );
         payload.insert(
             "tags".into(),
             Value::Array(vec![
@@ -146,7 +251,83 @@ mod tests {
         assert_eq!(hit.memory_type.as_deref(), Some("semantic"));
         assert_eq!(hit.timestamp.as_deref(), Some("2025-01-01T00:00:00Z"));
         assert_eq!(hit.source_uri.as_deref(), Some("file://note"));
+        assert_eq!(hit.ingested_at.as_deref(), Some("2025-02-01T00:00:00Z"));
+        assert_eq!(hit.embedding_model.as_deref(), Some("nomic-embed-text"));
         let tags = hit.tags.expect("tags present");
         assert_eq!(tags, vec!["alpha".to_string(), "beta".to_string()]);
     }
+
+    #[test]
+    fn map_scored_point_exposes_summary_citations() {
+        let payload = json!({
+            "text": "Shipped login [1]",
+            "citations": [
+                { "marker": 1, "memory_id": "ep-1" },
+                { "marker": "two", "memory_id": "ep-2" }
+            ]
+        });
+        let hit = map_scored_point(qdrant::ScoredPoint {
+            id: "summary".into(),
+            score: 0.9,
+            payload: payload.as_object().cloned(),
+        });
+        assert_eq!(
+            hit.citations,
+            Some(vec![qdrant::Citation {
+                marker: 1,
+                memory_id: "ep-1".into()
+            }])
+        );
+    }
+    #[test]
+    fn map_scored_point_exposes_memory_links() {
+        let linked = json!({
+            "text": "Use Postgres",
+            "links": [{ "rel": "supersedes", "target_memory_id": "old-decision" }],
+            "linked_to": ["old-decision"]
+        });
+        let hit = map_scored_point(qdrant::ScoredPoint {
+            id: "decision".into(),
+            score: 0.8,
+            payload: linked.as_object().cloned(),
+        });
+        assert_eq!(
+            hit.links,
+            Some(vec![qdrant::MemoryLink {
+                rel: qdrant::LinkRelation::Supersedes,
+                target_memory_id: "old-decision".into()
+            }])
+        );
+
+        let unlinked = json!({ "text": "No links", "links": [] });
+        let hit = map_scored_point(qdrant::ScoredPoint {
+            id: "plain".into(),
+            score: 0.5,
+            payload: unlinked.as_object().cloned(),
+        });
+        assert_eq!(hit.links, None);
+    }
+
+    #[test]
+    fn map_scored_point_tolerates_projected_payloads() {
+        let sparse = json!({ "tags": ["ops"] });
+        let hit = map_scored_point(qdrant::ScoredPoint {
+            id: "projected".into(),
+            score: 0.7,
+            payload: sparse.as_object().cloned(),
+        });
+        assert_eq!(hit.id, "projected");
+        assert_eq!(hit.tags, Some(vec!["ops".to_string()]));
+        assert_eq!(hit.text, None);
+        assert_eq!(hit.project_id, None);
+        assert_eq!(hit.timestamp, None);
+        assert_eq!(hit.chunk_index, None);
+
+        let bare = map_scored_point(qdrant::ScoredPoint {
+            id: "ids-only".into(),
+            score: 0.6,
+            payload: None,
+        });
+        assert_eq!((bare.id.as_str(), bare.text), ("ids-only", None));
+    }
 }
@@ -0,0 +1,319 @@
+//! Embedding provider migration: candidate collections, sampled queries, and comparison metrics.
+//!
+//! While `EMBEDDING_PROVIDER_NEXT` is set, every ingest also embeds its chunks with the next
+//! provider and writes them to `<collection>__candidate` under the same point ids and payloads.
+//! Qdrant cannot add a named vector to an existing collection, so the candidate vectors live in
+//! this companion collection rather than next to the active ones. Promotion points an alias with
+//! the collection's name at the candidate collection, after which the next provider serves both
+//! queries and writes for that collection only; collections not yet promoted keep the active
+//! provider.
+
+use std::collections::{HashSet, VecDeque};
+use std::sync::{Mutex, MutexGuard};
+
+use crate::config::{EmbeddingProvider, NextEmbedding};
+use crate::embedding::EmbeddingClient;
+use crate::processing::types::{ErrorKind, embedding_error_kind, qdrant_error_kind};
+use crate::qdrant::QdrantError;
+
+/// Suffix of the collection holding the candidate vectors of a provider migration.
+pub const CANDIDATE_COLLECTION_SUFFIX: &str = "__candidate";
+
+/// Search queries remembered during a migration window for `compare-providers`.
+pub const RECENT_QUERY_CAPACITY: usize = 100;
+
+/// Queries compared when the caller supplies none and names no `sample`.
+pub const DEFAULT_COMPARE_SAMPLE: usize = 20;
+
+/// Hits compared per query when the caller names no `k`.
+pub const DEFAULT_COMPARE_K: usize = 10;
+
+/// Name of the collection receiving candidate vectors for `collection`.
+pub fn candidate_collection(collection: &str) -> String {
+    format!("{collection}{CANDIDATE_COLLECTION_SUFFIX}")
+}
+
+/// Share of the top `k` ids of `active` that also appear in the top `k` of `candidate`.
+///
+/// Divides by the longer of the two truncated lists, so two empty lists overlap fully and a
+/// provider that returns fewer hits is penalised. `k` of zero yields `1.0`.
+pub fn overlap_at_k(active: &[String], candidate: &[String], k: usize) -> f32 {
+    let active = &active[..active.len().min(k)];
+    let candidate = &candidate[..candidate.len().min(k)];
+    let denominator = active.len().max(candidate.len());
+    if denominator == 0 {
+        return 1.0;
+    }
+    let candidate: HashSet<&String> = candidate.iter().collect();
+    let shared = active.iter().filter(|id| candidate.contains(id)).count();
+    shared as f32 / denominator as f32
+}
+
+/// Pearson correlation of paired scores, or `None` with fewer than two pairs or no variance.
+pub fn score_correlation(pairs: &[(f32, f32)]) -> Option<f32> {
+    if pairs.len() < 2 {
+        return None;
+    }
+    let count = pairs.len() as f64;
+    let mean_a = pairs.iter().map(|(a, _)| f64::from(*a)).sum::<f64>() / count;
+    let mean_b = pairs.iter().map(|(_, b)| f64::from(*b)).sum::<f64>() / count;
+    let (mut covariance, mut variance_a, mut variance_b) = (0.0, 0.0, 0.0);
+    for (a, b) in pairs {
+        let da = f64::from(*a) - mean_a;
+        let db = f64::from(*b) - mean_b;
+        covariance += da * db;
+        variance_a += da * da;
+        variance_b += db * db;
+    }
+    if variance_a == 0.0 || variance_b == 0.0 {
+        return None;
+    }
+    Some((covariance / (variance_a.sqrt() * variance_b.sqrt())) as f32)
+}
+
+/// Lower-case provider name as accepted by `EMBEDDING_PROVIDER`.
+pub(crate) fn provider_name(provider: EmbeddingProvider) -> &'static str {
+    match provider {
+        EmbeddingProvider::Ollama => "ollama",
+        EmbeddingProvider::OpenAI => "openai",
+    }
+}
+
+/// State of a configured provider migration, owned by the processing service.
+pub(crate) struct ProviderMigration {
+    client: Box<dyn EmbeddingClient + Send + Sync>,
+    next: NextEmbedding,
+    /// Collections whose alias now serves their candidate collection.
+    promoted: Mutex<HashSet<String>>,
+    recent_queries: Mutex<VecDeque<String>>,
+}
+
+impl ProviderMigration {
+    pub(crate) fn new(client: Box<dyn EmbeddingClient + Send + Sync>, next: NextEmbedding) -> Self {
+        Self {
+            client,
+            next,
+            promoted: Mutex::default(),
+            recent_queries: Mutex::default(),
+        }
+    }
+
+    /// Client embedding with the next provider.
+    pub(crate) fn client(&self) -> &(dyn EmbeddingClient + Send + Sync) {
+        self.client.as_ref()
+    }
+
+    /// Provider, model, and dimension being migrated to.
+    pub(crate) fn next(&self) -> &NextEmbedding {
+        &self.next
+    }
+
+    fn promoted(&self) -> MutexGuard<'_, HashSet<String>> {
+        self.promoted
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Whether the candidate vectors of `collection` were promoted to primary.
+    pub(crate) fn is_promoted(&self, collection: &str) -> bool {
+        self.promoted().contains(collection)
+    }
+
+    pub(crate) fn mark_promoted(&self, collection: &str) {
+        self.promoted().insert(collection.to_string());
+    }
+
+    /// Remember a query searched against `collection`, moving a repeated query to the front.
+    pub(crate) fn record_query(&self, collection: &str, query: &str) {
+        let query = query.trim();
+        if query.is_empty() || self.is_promoted(collection) {
+            return;
+        }
+        let mut queries = self
+            .recent_queries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        queries.retain(|existing| existing != query);
+        queries.push_front(query.to_string());
+        queries.truncate(RECENT_QUERY_CAPACITY);
+    }
+
+    /// Up to `count` remembered queries, newest first.
+    pub(crate) fn recent_queries(&self, count: usize) -> Vec<String> {
+        self.recent_queries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .iter()
+            .take(count)
+            .cloned()
+            .collect()
+    }
+}
+
+/// Top hits of one query against the active and the candidate vectors.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryComparison {
+    /// Query text, embedded with each provider's query prefix.
+    pub query: String,
+    /// Point ids returned from the active collection, best first.
+    pub active_ids: Vec<String>,
+    /// Point ids returned from the candidate collection, best first.
+    pub candidate_ids: Vec<String>,
+    /// [`overlap_at_k`] of the two id lists.
+    pub overlap: f32,
+}
+
+/// Result of [`crate::processing::ProcessingService::compare_providers`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProviderComparison {
+    /// Collection holding the active vectors.
+    pub collection: String,
+    /// Collection holding the candidate vectors.
+    pub candidate_collection: String,
+    /// Hits compared per query.
+    pub k: usize,
+    /// Points in the active collection.
+    pub active_points: u64,
+    /// Points in the candidate collection; fewer than `active_points` means ingest predating the
+    /// migration window (or a failed candidate write) is not covered yet.
+    pub candidate_points: u64,
+    /// Per-query results, in the order the queries were compared.
+    pub queries: Vec<QueryComparison>,
+    /// Mean overlap@k across `queries`, `None` without queries.
+    pub mean_overlap: Option<f32>,
+    /// [`score_correlation`] of the raw scores of hits both providers returned.
+    pub score_correlation: Option<f32>,
+    /// Hits returned by both providers, the sample behind `score_correlation`.
+    pub shared_hits: usize,
+}
+
+/// Result of [`crate::processing::ProcessingService::promote_provider`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromotionOutcome {
+    /// Name queries and writes keep using; now an alias of `candidate_collection`.
+    pub collection: String,
+    /// Collection holding the promoted vectors.
+    pub candidate_collection: String,
+    /// Collection the alias pointed at before; kept so the promotion can be rolled back.
+    pub previous_target: Option<String>,
+    /// Whether the active collection was deleted to free its name for the alias.
+    pub dropped_previous: bool,
+    /// Provider now embedding queries and writes.
+    pub provider: String,
+    /// Model now embedding queries and writes.
+    pub model: String,
+    /// Vector size of the promoted vectors.
+    pub dimension: usize,
+}
+
+/// Errors surfaced by `compare-providers` and `promote-provider`.
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum MigrationError {
+    #[error("No provider migration is configured; set EMBEDDING_PROVIDER_NEXT")]
+    NotConfigured,
+    #[error("The candidate provider was already promoted; there is nothing left to compare")]
+    Promoted,
+    #[error("`{0}` already serves its candidate vectors")]
+    AlreadyPromoted(String),
+    #[error(
+        "Candidate collection `{0}` does not exist; push memories during the migration window first"
+    )]
+    MissingCandidate(String),
+    #[error("No queries to compare; pass `queries` or run searches during the migration window")]
+    NoQueries,
+    #[error(
+        "Candidate collection `{candidate}` holds {candidate_points} of {active_points} points; re-push the missing memories or pass `force: true`"
+    )]
+    IncompleteCandidate {
+        candidate: String,
+        active_points: u64,
+        candidate_points: u64,
+    },
+    #[error(
+        "`{0}` is a collection, not an alias; promotion deletes it to free the name, pass `drop_previous: true`"
+    )]
+    ConcreteCollection(String),
+    #[error("Failed to generate embeddings: {0}")]
+    Embedding(#[from] crate::embedding::EmbeddingClientError),
+    #[error(transparent)]
+    Qdrant(#[from] QdrantError),
+}
+
+impl MigrationError {
+    /// Classify this failure for status mapping and retry decisions.
+    pub(crate) fn kind(&self) -> ErrorKind {
+        match self {
+            Self::NotConfigured => ErrorKind::Configuration,
+            Self::Promoted
+            | Self::AlreadyPromoted(_)
+            | Self::MissingCandidate(_)
+            | Self::NoQueries
+            | Self::IncompleteCandidate { .. }
+            | Self::ConcreteCollection(_) => ErrorKind::InvalidRequest,
+            Self::Embedding(source) => embedding_error_kind(source),
+            Self::Qdrant(source) => qdrant_error_kind(source),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embedding::AiLibClient;
+
+    fn ids(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn overlap_counts_shared_ids_within_the_top_k() {
+        let active = ids(&["a", "b", "c", "d"]);
+        let candidate = ids(&["b", "a", "x", "c"]);
+        assert_eq!(overlap_at_k(&active, &candidate, 2), 1.0);
+        assert_eq!(overlap_at_k(&active, &candidate, 3), 2.0 / 3.0);
+        assert_eq!(overlap_at_k(&active, &candidate, 10), 0.75);
+        assert_eq!(overlap_at_k(&active, &ids(&["a"]), 4), 0.25);
+        assert_eq!(overlap_at_k(&[], &[], 5), 1.0);
+    }
+
+    #[test]
+    fn score_correlation_is_pearson_and_undefined_without_variance() {
+        let linear = [(0.9, 0.8), (0.7, 0.6), (0.5, 0.4)];
+        assert!((score_correlation(&linear).unwrap() - 1.0).abs() < 1e-6);
+        let inverse = [(0.9, 0.4), (0.7, 0.6), (0.5, 0.8)];
+        assert!((score_correlation(&inverse).unwrap() + 1.0).abs() < 1e-6);
+        assert_eq!(score_correlation(&[(0.9, 0.8)]), None);
+        assert_eq!(score_correlation(&[(0.5, 0.8), (0.5, 0.4)]), None);
+    }
+
+    #[test]
+    fn recent_queries_are_deduplicated_newest_first_and_bounded() {
+        let migration = ProviderMigration::new(
+            Box::new(AiLibClient::with_dimension(4)),
+            NextEmbedding {
+                provider: EmbeddingProvider::OpenAI,
+                model: "text-embedding-3-small".into(),
+                dimension: 4,
+            },
+        );
+        migration.record_query("notes", "first");
+        migration.record_query("notes", "second");
+        migration.record_query("notes", " first ");
+        migration.record_query("notes", "   ");
+        assert_eq!(migration.recent_queries(5), ids(&["first", "second"]));
+
+        for index in 0..RECENT_QUERY_CAPACITY + 5 {
+            migration.record_query("notes", &format!("query {index}"));
+        }
+        let recent = migration.recent_queries(usize::MAX);
+        assert_eq!(recent.len(), RECENT_QUERY_CAPACITY);
+        assert_eq!(recent[0], format!("query {}", RECENT_QUERY_CAPACITY + 4));
+
+        migration.mark_promoted("notes");
+        migration.record_query("notes", "after promotion");
+        assert_ne!(migration.recent_queries(1), ids(&["after promotion"]));
+        migration.record_query("docs", "still migrating");
+        assert_eq!(migration.recent_queries(1), ids(&["still migrating"]));
+        assert!(migration.is_promoted("notes") && !migration.is_promoted("docs"));
+    }
+}
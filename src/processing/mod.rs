@@ -1,16 +1,40 @@
 //! Document processing pipeline: chunking, embedding, and Qdrant orchestration.
 
 pub mod chunking;
+pub mod classify;
+mod code;
+mod document;
+pub mod import;
+mod links;
+mod listing_cache;
+pub mod manifest;
 mod mappers;
+pub mod migration;
+pub mod partitions;
+mod query;
 pub mod sanitize;
 mod service;
 mod summarize;
 pub mod types;
 
-pub use service::{ProcessingApi, ProcessingService};
+pub use classify::{AutoClassify, ClassificationSummary, MemoryTypeRule};
+pub use import::{ImportSummary, ImportedVector, NdjsonImport};
+pub use manifest::{CollectionManifest, ManifestAction, ManifestError, ManifestResult};
+pub use migration::{PromotionOutcome, ProviderComparison, QueryComparison};
+pub use service::{FALLBACK_EMBEDDING_TAG, ORPHANED_SUMMARY_TAG, ProcessingApi, ProcessingService};
 pub use types::{
-    ChunkingError, IngestMetadata, ProcessingError, ProcessingOutcome, QdrantHealthSnapshot,
-    SearchError, SearchHit, SearchRequest, SearchTimeRange,
+    AnomalyReport, ChunkingError, CollectionProfile, DedupeStats, DocumentChunk, DocumentSelector,
+    DuplicatePurgeOutcome, ErrorKind, InboundLink, IngestMetadata, IngestProfile, LinkOutcome,
+    LookupRequest, MAX_INCLUDE_IDS, MemoryLinks, OrphanedSummary, ProcessingError,
+    ProcessingOutcome, PruneOutcome, QdrantHealthSnapshot, QueryCompression, ReadReplicaHealth,
+    ReconstructedDocument, ReembedOutcome, RenameOutcome, SEARCH_HIT_FIELDS, SearchError,
+    SearchHit, SearchLimits, SearchOutcome, SearchRequest, SearchRequestBuilder, SearchTimeRange,
+    SearchValidationError, StorageEstimate, StorageStats, StorageStatsReport, SummaryCheckOutcome,
+    SummaryQuery, SummaryRecord, TranscriptHandling, ValidatedSearchRequest, VerifyOutcome,
+    WarmupOutcome,
+};
+// Summarization API surface shared by the MCP and HTTP handlers
+pub use service::{
+    ConsolidateMode, RefineOutcome, RefineSummaryRequest, SummarizeError, SummarizeOutcome,
+    SummarizeRequest, SummarizeStrategy,
 };
-// Summarization API surface re-exported for MCP (types only)
-pub(crate) use service::{SummarizeError, SummarizeOutcome, SummarizeRequest, SummarizeStrategy};
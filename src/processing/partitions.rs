@@ -0,0 +1,317 @@
+//! Monthly collections for episodic memories (`EPISODIC_PARTITIONING=monthly`).
+//!
+//! Episodic pushes land in `<collection>-YYYY-MM`, named after the UTC month of the memory
+//! timestamp. Searches and summaries map their `time_range` onto the partitions it overlaps;
+//! without a range they read the current and previous month. Only partitions that already exist
+//! are read. Maintenance that is not bounded by a range (prune, duplicate purge, the recent
+//! listing, and summary source lookups) covers every existing partition.
+
+use time::{Month, OffsetDateTime};
+
+use crate::processing::sanitize::{TimeBoundary, parse_time_boundary};
+use crate::processing::types::SearchTimeRange;
+
+/// Memory type whose pushes and searches are partitioned.
+pub const PARTITIONED_MEMORY_TYPE: &str = "episodic";
+
+/// Partitions searched when a request carries no `time_range`: this month and the previous one.
+const DEFAULT_MONTHS: usize = 2;
+
+/// Calendar month identifying one partition.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct PartitionMonth {
+    year: i32,
+    month: u8,
+}
+
+impl PartitionMonth {
+    fn of(instant: OffsetDateTime) -> Self {
+        Self {
+            year: instant.year(),
+            month: u8::from(instant.month()),
+        }
+    }
+
+    fn previous(self) -> Self {
+        match self.month {
+            1 => Self {
+                year: self.year - 1,
+                month: 12,
+            },
+            month => Self {
+                year: self.year,
+                month: month - 1,
+            },
+        }
+    }
+
+    fn next(self) -> Self {
+        match self.month {
+            12 => Self {
+                year: self.year + 1,
+                month: 1,
+            },
+            month => Self {
+                year: self.year,
+                month: month + 1,
+            },
+        }
+    }
+
+    fn collection(self, base: &str) -> String {
+        format!("{base}-{:04}-{:02}", self.year, self.month)
+    }
+
+    /// Month encoded by a `<base>-YYYY-MM` collection name.
+    fn parse(base: &str, name: &str) -> Option<Self> {
+        let suffix = name.strip_prefix(base)?.strip_prefix('-')?;
+        let (year, month) = suffix.split_once('-')?;
+        if year.len() != 4 || month.len() != 2 {
+            return None;
+        }
+        let year: i32 = year.parse().ok()?;
+        let month: u8 = month.parse().ok()?;
+        Month::try_from(month).ok()?;
+        Some(Self { year, month })
+    }
+}
+
+/// Partition receiving a memory stamped `timestamp`, or stamped `now` when it has none.
+pub fn push_partition(base: &str, timestamp: Option<&str>, now: OffsetDateTime) -> String {
+    let instant = timestamp
+        .and_then(|value| parse_time_boundary(value, TimeBoundary::Start))
+        .unwrap_or(now);
+    PartitionMonth::of(instant).collection(base)
+}
+
+/// Every partition between the months of `start` and `end`, inclusive, oldest first.
+///
+/// An inverted range overlaps nothing.
+pub fn overlapping_partitions(
+    base: &str,
+    start: OffsetDateTime,
+    end: OffsetDateTime,
+) -> Vec<String> {
+    months_between(PartitionMonth::of(start), PartitionMonth::of(end))
+        .into_iter()
+        .map(|month| month.collection(base))
+        .collect()
+}
+
+/// Existing partitions a search should read, newest first.
+///
+/// A missing `end` is `now`. A missing `start` reaches back to the oldest partition in
+/// `existing`. Without a range, the current and previous month are read.
+pub fn search_partitions(
+    base: &str,
+    time_range: Option<&SearchTimeRange>,
+    now: OffsetDateTime,
+    existing: &[String],
+) -> Vec<String> {
+    let mut present: Vec<PartitionMonth> = existing
+        .iter()
+        .filter_map(|name| PartitionMonth::parse(base, name))
+        .collect();
+    present.sort_unstable();
+
+    let current = PartitionMonth::of(now);
+    let (first, last) = match time_range {
+        None => {
+            let first = (1..DEFAULT_MONTHS).fold(current, |month, _| month.previous());
+            (first, current)
+        }
+        Some(range) => {
+            let last = range
+                .end
+                .as_deref()
+                .and_then(|value| parse_time_boundary(value, TimeBoundary::End))
+                .map_or(current, PartitionMonth::of);
+            let first = match range
+                .start
+                .as_deref()
+                .and_then(|value| parse_time_boundary(value, TimeBoundary::Start))
+            {
+                Some(start) => PartitionMonth::of(start),
+                None => match present.first() {
+                    Some(oldest) => *oldest,
+                    None => return Vec::new(),
+                },
+            };
+            (first, last)
+        }
+    };
+
+    months_between(first, last)
+        .into_iter()
+        .rev()
+        .filter(|month| present.binary_search(month).is_ok())
+        .map(|month| month.collection(base))
+        .collect()
+}
+
+/// Every existing partition of `base`, newest first.
+pub fn existing_partitions(base: &str, existing: &[String]) -> Vec<String> {
+    let mut present: Vec<PartitionMonth> = existing
+        .iter()
+        .filter_map(|name| PartitionMonth::parse(base, name))
+        .collect();
+    present.sort_unstable_by(|a, b| b.cmp(a));
+    present.dedup();
+    present
+        .into_iter()
+        .map(|month| month.collection(base))
+        .collect()
+}
+
+fn months_between(first: PartitionMonth, last: PartitionMonth) -> Vec<PartitionMonth> {
+    let mut months = Vec::new();
+    let mut month = first;
+    while month <= last {
+        months.push(month);
+        month = month.next();
+    }
+    months
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(start: Option<&str>, end: Option<&str>) -> SearchTimeRange {
+        SearchTimeRange {
+            start: start.map(str::to_string),
+            end: end.map(str::to_string),
+        }
+    }
+
+    fn at(value: &str) -> OffsetDateTime {
+        parse_time_boundary(value, TimeBoundary::Start).expect("valid instant")
+    }
+
+    fn names(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn pushes_route_by_utc_month_of_the_memory_timestamp() {
+        let now = at("2026-03-10T12:00:00Z");
+        assert_eq!(
+            push_partition("mem", Some("2025-12-31T23:30:00-02:00"), now),
+            "mem-2026-01"
+        );
+        assert_eq!(
+            push_partition("mem", Some("2025-07-04T08:00:00Z"), now),
+            "mem-2025-07"
+        );
+        assert_eq!(push_partition("mem", None, now), "mem-2026-03");
+    }
+
+    #[test]
+    fn ranges_map_to_every_month_they_overlap_across_year_boundaries() {
+        assert_eq!(
+            overlapping_partitions(
+                "mem",
+                at("2024-11-15T00:00:00Z"),
+                at("2025-02-01T00:00:00Z")
+            ),
+            names(&["mem-2024-11", "mem-2024-12", "mem-2025-01", "mem-2025-02"])
+        );
+        assert_eq!(
+            overlapping_partitions(
+                "mem",
+                at("2025-12-31T23:59:00Z"),
+                at("2026-01-01T00:00:00Z")
+            ),
+            names(&["mem-2025-12", "mem-2026-01"])
+        );
+        assert!(
+            overlapping_partitions(
+                "mem",
+                at("2026-01-01T00:00:00Z"),
+                at("2025-12-01T00:00:00Z")
+            )
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn searches_read_only_existing_overlapping_partitions_newest_first() {
+        let now = at("2026-01-20T09:00:00Z");
+        let existing = names(&[
+            "mem",
+            "mem-2025-10",
+            "mem-2025-11",
+            "mem-2025-12",
+            "mem-2026-01",
+            "mem-project-2025-12",
+            "other-2025-12",
+        ]);
+
+        assert_eq!(
+            search_partitions("mem", None, now, &existing),
+            names(&["mem-2026-01", "mem-2025-12"])
+        );
+        assert_eq!(
+            search_partitions(
+                "mem",
+                Some(&range(Some("2025-11-30"), Some("2025-12-01"))),
+                now,
+                &existing
+            ),
+            names(&["mem-2025-12", "mem-2025-11"])
+        );
+        assert_eq!(
+            search_partitions(
+                "mem",
+                Some(&range(Some("2025-12-15"), None)),
+                now,
+                &existing
+            ),
+            names(&["mem-2026-01", "mem-2025-12"])
+        );
+        assert_eq!(
+            search_partitions(
+                "mem",
+                Some(&range(None, Some("2025-11-02"))),
+                now,
+                &existing
+            ),
+            names(&["mem-2025-11", "mem-2025-10"])
+        );
+        assert!(
+            search_partitions(
+                "mem",
+                Some(&range(Some("2024-01-01"), Some("2024-12-31"))),
+                now,
+                &existing
+            )
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn existing_partitions_lists_every_month_of_the_base_newest_first() {
+        let existing = names(&[
+            "mem-2025-11",
+            "mem",
+            "mem-2026-01",
+            "mem-project-2025-12",
+            "other-2025-12",
+            "mem-2025-13",
+        ]);
+        assert_eq!(
+            existing_partitions("mem", &existing),
+            names(&["mem-2026-01", "mem-2025-11"])
+        );
+        assert!(existing_partitions("other-2025", &existing).is_empty());
+    }
+
+    #[test]
+    fn default_window_spans_the_year_boundary_in_january() {
+        let existing = names(&["mem-2025-11", "mem-2025-12", "mem-2026-01"]);
+        assert_eq!(
+            search_partitions("mem", None, at("2026-01-01T00:00:00Z"), &existing),
+            names(&["mem-2026-01", "mem-2025-12"])
+        );
+    }
+}
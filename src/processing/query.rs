@@ -0,0 +1,179 @@
+//! Length guard for search queries.
+//!
+//! Agents sometimes paste an entire file as `query_text`. Embedding it wastes tokens and blurs
+//! the vector, so queries above `MAX_QUERY_TOKENS` are cut at a sentence boundary or, when the
+//! caller asks for it, compressed to one sentence by the summarization model.
+
+use crate::summarization::{SummarizationClient, SummarizationRequest as LlmSummarizationRequest};
+
+/// Word budget requested from the model when compressing a query.
+const COMPRESSED_QUERY_WORDS: usize = 40;
+
+/// Longest prefix of `text` within `max_tokens`, cut at a sentence boundary.
+///
+/// Falls back to a word boundary when even the first sentence is over budget, and keeps at
+/// least one word. Returns `None` when `text` already fits.
+pub(crate) fn truncate_query(
+    text: &str,
+    max_tokens: usize,
+    count_tokens: &dyn Fn(&str) -> usize,
+) -> Option<String> {
+    let text = text.trim();
+    if count_tokens(text) <= max_tokens {
+        return None;
+    }
+    let sentences = sentence_ends(text);
+    let mut end = 0;
+    for &candidate in &sentences {
+        if count_tokens(&text[..candidate]) > max_tokens {
+            break;
+        }
+        end = candidate;
+    }
+    if end > 0 {
+        return Some(text[..end].trim_end().to_string());
+    }
+
+    let mut words = text.split_whitespace();
+    let mut kept = words.next().unwrap_or_default().to_string();
+    for word in words {
+        let candidate = format!("{kept} {word}");
+        if count_tokens(&candidate) > max_tokens {
+            break;
+        }
+        kept = candidate;
+    }
+    Some(kept)
+}
+
+/// Byte offsets just past each line break and each `.`, `!`, or `?` that is followed by
+/// whitespace or the end of the text.
+fn sentence_ends(text: &str) -> Vec<usize> {
+    let mut ends = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some((index, ch)) = chars.next() {
+        let boundary = match ch {
+            '\n' => true,
+            '.' | '!' | '?' => chars.peek().is_none_or(|(_, next)| next.is_whitespace()),
+            _ => false,
+        };
+        if boundary {
+            ends.push(index + ch.len_utf8());
+        }
+    }
+    ends
+}
+
+/// Prompt asking the model to restate a long query as one search sentence.
+fn compression_prompt(query: &str) -> String {
+    format!(
+        "System: You rewrite long search inputs into a single short sentence describing what the user is looking for. Keep key names, identifiers, and error messages. Reply with the sentence only.\n\nInput:\n{query}\n"
+    )
+}
+
+/// Ask `client` to compress `query` into one sentence.
+///
+/// Returns `None` when the request fails or the reply is empty, so callers can fall back to
+/// truncation.
+pub(crate) async fn compress_query(
+    client: &(dyn SummarizationClient + Send + Sync),
+    model: &str,
+    query: &str,
+) -> Option<String> {
+    let request = LlmSummarizationRequest {
+        model: model.to_string(),
+        prompt: compression_prompt(query),
+        max_words: COMPRESSED_QUERY_WORDS,
+    };
+    match client.generate_summary(request).await {
+        Ok(reply) => {
+            let sentence = reply.lines().find(|line| !line.trim().is_empty())?.trim();
+            Some(sentence.trim_matches('"').to_string()).filter(|sentence| !sentence.is_empty())
+        }
+        Err(error) => {
+            tracing::warn!(error = %error, "Query compression failed; truncating instead");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use httpmock::prelude::*;
+    use serde_json::json;
+
+    fn words(text: &str) -> usize {
+        text.split_whitespace().count()
+    }
+
+    #[test]
+    fn short_queries_are_left_alone() {
+        assert_eq!(truncate_query("  rotate the keys  ", 8, &words), None);
+    }
+
+    #[test]
+    fn long_queries_are_cut_at_the_last_sentence_that_fits() {
+        let query = "Rotate keys quarterly. Store them in the vault!\nNever commit secrets to git.";
+        assert_eq!(
+            truncate_query(query, 8, &words).as_deref(),
+            Some("Rotate keys quarterly. Store them in the vault!")
+        );
+        assert_eq!(
+            truncate_query(query, 3, &words).as_deref(),
+            Some("Rotate keys quarterly.")
+        );
+    }
+
+    #[test]
+    fn oversized_first_sentences_fall_back_to_word_boundaries() {
+        let query = "one two three four five six. seven";
+        assert_eq!(
+            truncate_query(query, 4, &words).as_deref(),
+            Some("one two three four")
+        );
+        assert_eq!(
+            truncate_query("v1.2.3 is the release tag", 1, &words).as_deref(),
+            Some("v1.2.3")
+        );
+    }
+
+    #[tokio::test]
+    async fn compression_uses_the_first_line_of_the_model_reply() {
+        let server = MockServer::start_async().await;
+        let generate = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/api/generate")
+                    .body_contains("fn rotate_keys");
+                then.status(200).json_body(json!({
+                    "response": "\n\"How are API keys rotated?\"\nExtra commentary.",
+                    "done": true
+                }));
+            })
+            .await;
+        let client = crate::summarization::ollama_summarization_client(server.base_url(), 0, None);
+
+        let compressed = compress_query(client.as_ref(), "llama", "fn rotate_keys() { ... }").await;
+
+        generate.assert_async().await;
+        assert_eq!(compressed.as_deref(), Some("How are API keys rotated?"));
+    }
+
+    #[tokio::test]
+    async fn failed_compression_returns_none() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(POST).path("/api/generate");
+                then.status(500).body("model not found");
+            })
+            .await;
+        let client = crate::summarization::ollama_summarization_client(server.base_url(), 0, None);
+
+        assert_eq!(
+            compress_query(client.as_ref(), "llama", "query").await,
+            None
+        );
+    }
+}
@@ -1,10 +1,13 @@
-//! Helpers for normalizing metadata values.
+//! Helpers for normalizing metadata values, rating chunk quality, and screening pasted transcripts.
 
-use crate::qdrant::PayloadOverrides;
+use crate::{config::TranscriptPolicy, qdrant::PayloadOverrides};
 use serde_json::{Map, Value};
 use std::collections::HashSet;
+use time::{
+    Date, Duration, Month, OffsetDateTime, Time, UtcOffset, format_description::well_known::Rfc3339,
+};
 
-use super::types::IngestMetadata;
+use super::types::{IngestMetadata, ProcessingError, TranscriptHandling};
 
 /// Sanitize arbitrary string input by trimming whitespace and dropping empties.
 pub(crate) fn sanitize_string(value: Option<String>) -> Option<String> {
@@ -81,6 +84,113 @@ pub fn extract_tags(payload: &Map<String, Value>) -> Option<Vec<String>> {
     }
 }
 
+/// Side of a time range a boundary belongs to; decides how date-only values expand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeBoundary {
+    /// Inclusive lower bound; date-only values expand to the start of the day.
+    Start,
+    /// Inclusive upper bound; date-only values expand to the last instant of the day.
+    End,
+}
+
+/// Parse an RFC3339 timestamp or a date-only string (`YYYY-MM-DD`) into a UTC instant.
+///
+/// Offsets such as `+02:00` are converted to UTC so range filters compare against payload
+/// timestamps (always written with `Z`) on equal footing.
+pub fn parse_time_boundary(value: &str, boundary: TimeBoundary) -> Option<OffsetDateTime> {
+    let trimmed = value.trim();
+    if let Ok(parsed) = OffsetDateTime::parse(trimmed, &Rfc3339) {
+        return Some(parsed.to_offset(UtcOffset::UTC));
+    }
+
+    let date = parse_date_only(trimmed)?;
+    let time = match boundary {
+        TimeBoundary::Start => Time::MIDNIGHT,
+        TimeBoundary::End => Time::from_hms_nano(23, 59, 59, 999_999_999).ok()?,
+    };
+    Some(date.with_time(time).assume_utc())
+}
+
+/// Render a UTC instant as an RFC3339 string with a `Z` suffix.
+pub fn format_utc_rfc3339(value: OffsetDateTime) -> String {
+    value
+        .to_offset(UtcOffset::UTC)
+        .format(&Rfc3339)
+        .unwrap_or_else(|_| "1970-01-01T00:00:00Z".to_string())
+}
+
+/// Normalize a time-range boundary to UTC RFC3339, returning `None` when it cannot be parsed.
+pub fn normalize_time_boundary(value: &str, boundary: TimeBoundary) -> Option<String> {
+    parse_time_boundary(value, boundary).map(format_utc_rfc3339)
+}
+
+/// Parse a relative duration shorthand: a positive integer followed by `h`, `d`, or `w`.
+///
+/// `90d` is ninety days and `2w` two weeks; units are case-insensitive. Anything else,
+/// including zero, fractions, or combined units like `1d12h`, yields `None`.
+pub fn parse_duration_shorthand(value: &str) -> Option<Duration> {
+    let trimmed = value.trim();
+    let unit = trimmed.chars().last()?;
+    let amount: i64 = trimmed[..trimmed.len() - unit.len_utf8()].parse().ok()?;
+    if amount <= 0 || !trimmed.as_bytes()[0].is_ascii_digit() {
+        return None;
+    }
+    match unit.to_ascii_lowercase() {
+        'h' => Some(Duration::seconds(amount.checked_mul(3_600)?)),
+        'd' => Some(Duration::seconds(amount.checked_mul(86_400)?)),
+        'w' => Some(Duration::seconds(amount.checked_mul(604_800)?)),
+        _ => None,
+    }
+}
+
+/// Resolve an `older_than` bound to a UTC instant.
+///
+/// Accepts an RFC3339 timestamp, a date (midnight UTC), or a [`parse_duration_shorthand`]
+/// value counted back from `now`.
+pub fn resolve_older_than(value: &str, now: OffsetDateTime) -> Option<OffsetDateTime> {
+    match parse_duration_shorthand(value) {
+        Some(age) => now.checked_sub(age),
+        None => parse_time_boundary(value, TimeBoundary::Start),
+    }
+}
+
+/// How far ahead of the server clock an ingest `timestamp` may be without `allow_future`.
+pub(crate) const INGEST_TIMESTAMP_SKEW: Duration = Duration::minutes(5);
+
+/// Validate a caller-supplied ingest `timestamp` and normalize it to UTC RFC3339.
+///
+/// Date-only values expand to midnight UTC. Values more than [`INGEST_TIMESTAMP_SKEW`] ahead
+/// of `now` are rejected unless `allow_future` is set.
+pub(crate) fn validate_ingest_timestamp(
+    value: &str,
+    allow_future: bool,
+    now: OffsetDateTime,
+) -> Result<String, String> {
+    let parsed = parse_time_boundary(value, TimeBoundary::Start).ok_or_else(|| {
+        format!("`timestamp` must be a valid RFC3339 timestamp or YYYY-MM-DD date (got '{value}')")
+    })?;
+    if !allow_future && parsed > now + INGEST_TIMESTAMP_SKEW {
+        return Err(format!(
+            "`timestamp` '{value}' is in the future; set `allow_future: true` to store it anyway"
+        ));
+    }
+    Ok(format_utc_rfc3339(parsed))
+}
+
+fn parse_date_only(value: &str) -> Option<Date> {
+    let mut parts = value.splitn(3, '-');
+    let year_part = parts.next()?;
+    let month_part = parts.next()?;
+    let day_part = parts.next()?;
+    if year_part.len() != 4 || month_part.len() != 2 || day_part.len() != 2 {
+        return None;
+    }
+    let year: i32 = year_part.parse().ok()?;
+    let month: u8 = month_part.parse().ok()?;
+    let day: u8 = day_part.parse().ok()?;
+    Date::from_calendar_date(year, Month::try_from(month).ok()?, day).ok()
+}
+
 /// Convert ingest metadata into Qdrant payload overrides.
 pub(crate) fn to_payload_overrides(metadata: IngestMetadata) -> PayloadOverrides {
     let IngestMetadata {
@@ -88,6 +198,8 @@ pub(crate) fn to_payload_overrides(metadata: IngestMetadata) -> PayloadOverrides
         memory_type,
         tags,
         source_uri,
+        timestamp,
+        auto_classify: _,
     } = metadata;
 
     PayloadOverrides {
@@ -97,12 +209,362 @@ pub(crate) fn to_payload_overrides(metadata: IngestMetadata) -> PayloadOverrides
         source_uri: sanitize_string(source_uri),
         source_memory_ids: None,
         summary_key: None,
+        label: None,
+        topic: None,
+        citations: None,
+        timestamp: sanitize_string(timestamp),
+        profile_hash: None,
+        ingest_profile: None,
+        doc_id: None,
+    }
+}
+
+/// Per-chunk features used to rate how much information a chunk carries.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChunkQuality {
+    /// Alphanumeric characters as a share of non-whitespace characters.
+    pub alphanumeric_ratio: f32,
+    /// Distinct whitespace-separated tokens as a share of all tokens.
+    pub unique_token_ratio: f32,
+    /// Mean token length in characters.
+    pub average_token_length: f32,
+    /// Non-blank lines repeating an earlier line, as a share of non-blank lines.
+    pub repeated_line_ratio: f32,
+    /// Composite score in `0.0..=1.0`; higher means more informative.
+    pub score: f32,
+}
+
+/// Rate a chunk by how much prose-like information it carries.
+///
+/// Each feature maps onto a sub-score in `0.0..=1.0` and the composite is their product, so a
+/// single bad signal is enough to sink a chunk:
+///
+/// - alphanumeric ratio: full marks from 0.9, zero at 0.5 (minified code, punctuation soup);
+/// - unique-token ratio: full marks from 0.4, linear below (lockfiles, generated tables);
+/// - average token length: full marks between 3 and 10 characters, decaying outside
+///   (base64 blobs and minified bundles have very long tokens);
+/// - repeated-line ratio: subtracted from 1 (boilerplate and repeated stanzas).
+///
+/// Ordinary prose scores close to 1.0; lockfiles, minified JavaScript, and encoded blobs fall
+/// well below 0.5. Blank input scores 0.0.
+pub fn score_chunk_quality(text: &str) -> ChunkQuality {
+    let (mut visible, mut alphanumeric) = (0usize, 0usize);
+    for ch in text.chars().filter(|ch| !ch.is_whitespace()) {
+        visible += 1;
+        if ch.is_alphanumeric() {
+            alphanumeric += 1;
+        }
+    }
+    let tokens: Vec<&str> = text.split_whitespace().collect();
+    if tokens.is_empty() {
+        return ChunkQuality {
+            alphanumeric_ratio: 0.0,
+            unique_token_ratio: 0.0,
+            average_token_length: 0.0,
+            repeated_line_ratio: 0.0,
+            score: 0.0,
+        };
+    }
+    let unique_tokens: HashSet<&str> = tokens.iter().copied().collect();
+    let mut seen_lines = HashSet::new();
+    let (mut lines, mut repeated_lines) = (0usize, 0usize);
+    for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        lines += 1;
+        if !seen_lines.insert(line) {
+            repeated_lines += 1;
+        }
+    }
+
+    let alphanumeric_ratio = alphanumeric as f32 / visible as f32;
+    let unique_token_ratio = unique_tokens.len() as f32 / tokens.len() as f32;
+    let average_token_length = visible as f32 / tokens.len() as f32;
+    let repeated_line_ratio = repeated_lines as f32 / lines.max(1) as f32;
+
+    let alphanumeric_score = ((alphanumeric_ratio - 0.5) / 0.4).clamp(0.0, 1.0);
+    let unique_score = (unique_token_ratio / 0.4).min(1.0);
+    let length_score = if average_token_length < 3.0 {
+        average_token_length / 3.0
+    } else if average_token_length > 10.0 {
+        10.0 / average_token_length
+    } else {
+        1.0
+    };
+    let score = alphanumeric_score * unique_score * length_score * (1.0 - repeated_line_ratio);
+
+    ChunkQuality {
+        alphanumeric_ratio,
+        unique_token_ratio,
+        average_token_length,
+        repeated_line_ratio,
+        score,
     }
 }
 
+/// Speaker labels that open a user turn in a pasted chat transcript.
+const USER_ROLE_MARKERS: &[&str] = &["user", "human"];
+
+/// Speaker labels that open an assistant turn in a pasted chat transcript.
+const ASSISTANT_ROLE_MARKERS: &[&str] = &["assistant", "ai", "chatgpt", "bot"];
+
+/// Longest label, in bytes, still read as a speaker marker before a colon.
+const MAX_ROLE_MARKER_LEN: usize = 20;
+
+/// Lowercased filler phrases typical of chat-assistant replies.
+const ASSISTANT_BOILERPLATE: &[&str] = &[
+    "as an ai language model",
+    "as an ai assistant",
+    "i'm just an ai",
+    "i hope this helps",
+    "let me know if you have any",
+    "let me know if you need",
+    "feel free to ask",
+    "great question",
+    "happy to help",
+    "i'd be happy to help",
+    "i apologize for the confusion",
+    "i apologize for any confusion",
+];
+
+/// Speaker of a transcript turn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Role {
+    User,
+    Assistant,
+}
+
+/// Line-level evidence that a text is a pasted chat transcript.
+///
+/// Lines inside fenced code blocks are ignored so that documentation quoting a session in a code
+/// sample is not counted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TranscriptSignals {
+    /// Non-blank lines outside code fences.
+    pub lines: usize,
+    /// Lines opening a user turn (`User:`, `Human:`).
+    pub user_turns: usize,
+    /// Lines opening an assistant turn (`Assistant:`, `AI:`, `ChatGPT:`, `Bot:`).
+    pub assistant_turns: usize,
+    /// Lines containing assistant filler such as "I hope this helps".
+    pub boilerplate_lines: usize,
+}
+
+impl TranscriptSignals {
+    /// Whether the signals are strong enough to treat the text as a transcript.
+    ///
+    /// Deliberately conservative: both speakers need at least two turns, turn markers must open
+    /// at least a quarter of the lines, and at least one line must carry assistant filler. A
+    /// single quoted exchange in prose or an interview write-up does not qualify.
+    pub fn is_transcript(&self) -> bool {
+        self.user_turns >= 2
+            && self.assistant_turns >= 2
+            && (self.user_turns + self.assistant_turns) * 4 >= self.lines
+            && self.boilerplate_lines >= 1
+    }
+}
+
+/// Collect transcript signals from `text`.
+pub fn transcript_signals(text: &str) -> TranscriptSignals {
+    let mut signals = TranscriptSignals::default();
+    let mut in_fence = false;
+    for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        if is_fence(line) {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        signals.lines += 1;
+        match role_marker(line) {
+            Some((Role::User, _)) => signals.user_turns += 1,
+            Some((Role::Assistant, _)) => signals.assistant_turns += 1,
+            None => {}
+        }
+        if is_boilerplate(line) {
+            signals.boilerplate_lines += 1;
+        }
+    }
+    signals
+}
+
+/// Remove assistant filler sentences from the assistant turns of a transcript.
+///
+/// User turns and fenced code are kept verbatim. A turn marker stays in place even when its
+/// whole line was filler, so the following lines keep their speaker. Returns the rewritten text
+/// and the number of sentences removed.
+pub fn strip_assistant_boilerplate(text: &str) -> (String, usize) {
+    let mut kept = Vec::new();
+    let mut removed = 0;
+    let mut in_fence = false;
+    let mut speaker = None;
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if is_fence(trimmed) {
+            in_fence = !in_fence;
+        }
+        if in_fence || is_fence(trimmed) {
+            kept.push(line.to_string());
+            continue;
+        }
+        let (prefix, body) = match role_marker(trimmed) {
+            Some((role, body)) => {
+                speaker = Some(role);
+                (&trimmed[..trimmed.len() - body.len()], body)
+            }
+            None => ("", trimmed),
+        };
+        if speaker != Some(Role::Assistant) || !is_boilerplate(body) {
+            kept.push(line.to_string());
+            continue;
+        }
+        let sentences: Vec<&str> = split_sentences(body)
+            .into_iter()
+            .filter(|sentence| {
+                let filler = is_boilerplate(sentence);
+                removed += usize::from(filler);
+                !filler
+            })
+            .collect();
+        let rewritten = format!("{} {}", prefix.trim_end(), sentences.join(" "));
+        let rewritten = rewritten.trim();
+        if !rewritten.is_empty() {
+            kept.push(rewritten.to_string());
+        }
+    }
+    (kept.join("\n"), removed)
+}
+
+/// Screen pushed text for pasted chat transcripts according to `TRANSCRIPT_POLICY`.
+///
+/// Text that is not detected as a transcript passes through untouched. Detected transcripts are
+/// indexed unchanged under `warn`, refused under `reject`, and cleaned of assistant filler under
+/// `strip`; stripping that leaves nothing to index is refused as well.
+pub(crate) fn apply_transcript_policy(
+    policy: TranscriptPolicy,
+    text: String,
+) -> Result<(String, Option<TranscriptHandling>), ProcessingError> {
+    let signals = transcript_signals(&text);
+    if !signals.is_transcript() {
+        return Ok((text, None));
+    }
+    tracing::warn!(
+        ?policy,
+        user_turns = signals.user_turns,
+        assistant_turns = signals.assistant_turns,
+        boilerplate_lines = signals.boilerplate_lines,
+        "Pushed text looks like a chat transcript"
+    );
+    match policy {
+        TranscriptPolicy::Warn => Ok((text, Some(TranscriptHandling::Warned))),
+        TranscriptPolicy::Reject => Err(ProcessingError::TranscriptRejected(format!(
+            "Text looks like a pasted chat transcript ({} user and {} assistant turns); \
+             push a summary of the conversation instead",
+            signals.user_turns, signals.assistant_turns
+        ))),
+        TranscriptPolicy::Strip => {
+            let (stripped, sentences) = strip_assistant_boilerplate(&text);
+            if stripped.trim().is_empty() {
+                return Err(ProcessingError::TranscriptRejected(
+                    "Text looks like a pasted chat transcript and nothing remains once assistant \
+                     filler is stripped"
+                        .into(),
+                ));
+            }
+            Ok((stripped, Some(TranscriptHandling::Stripped { sentences })))
+        }
+    }
+}
+
+fn is_fence(line: &str) -> bool {
+    line.starts_with("```") || line.starts_with("~~~")
+}
+
+fn is_boilerplate(text: &str) -> bool {
+    let lower = text.to_lowercase().replace('\u{2019}', "'");
+    ASSISTANT_BOILERPLATE
+        .iter()
+        .any(|phrase| lower.contains(phrase))
+}
+
+/// Speaker of a `Label: body` line and the body after the colon.
+///
+/// Leading Markdown decoration (`>`, `#`, `*`, `-`) and emphasis around the label are ignored, so
+/// `**User:** hi` and `> Assistant: hello` are both recognised.
+fn role_marker(line: &str) -> Option<(Role, &str)> {
+    let line = line.trim_start_matches(['>', '#', '*', '-', ' ']);
+    let (label, body) = line.split_once(':')?;
+    if label.len() > MAX_ROLE_MARKER_LEN {
+        return None;
+    }
+    let label = label.trim_matches(|ch: char| ch == '*' || ch.is_whitespace());
+    let body = body.trim_start_matches('*').trim_start();
+    if USER_ROLE_MARKERS
+        .iter()
+        .any(|marker| label.eq_ignore_ascii_case(marker))
+    {
+        Some((Role::User, body))
+    } else if ASSISTANT_ROLE_MARKERS
+        .iter()
+        .any(|marker| label.eq_ignore_ascii_case(marker))
+    {
+        Some((Role::Assistant, body))
+    } else {
+        None
+    }
+}
+
+/// Split a line into sentences ending in `.`, `!`, or `?` followed by whitespace.
+fn split_sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((index, ch)) = chars.next() {
+        if matches!(ch, '.' | '!' | '?')
+            && chars.peek().is_some_and(|(_, next)| next.is_whitespace())
+        {
+            let end = index + ch.len_utf8();
+            sentences.push(text[start..end].trim());
+            start = end;
+        }
+    }
+    sentences.push(text[start..].trim());
+    sentences.retain(|sentence| !sentence.is_empty());
+    sentences
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::processing::ErrorKind;
+
+    #[test]
+    fn duration_shorthand_accepts_hours_days_and_weeks() {
+        assert_eq!(parse_duration_shorthand("36h"), Some(Duration::hours(36)));
+        assert_eq!(parse_duration_shorthand("90d"), Some(Duration::days(90)));
+        assert_eq!(parse_duration_shorthand(" 2W "), Some(Duration::weeks(2)));
+        for invalid in ["", "d", "0d", "-3d", "+3d", "1.5d", "3m", "1d12h", "90"] {
+            assert_eq!(parse_duration_shorthand(invalid), None, "{invalid}");
+        }
+        assert_eq!(parse_duration_shorthand("99999999999999999w"), None);
+    }
+
+    #[test]
+    fn older_than_resolves_shorthand_against_now_and_passes_timestamps_through() {
+        let now = OffsetDateTime::parse("2025-06-01T12:00:00Z", &Rfc3339).unwrap();
+        assert_eq!(
+            resolve_older_than("90d", now).map(format_utc_rfc3339),
+            Some("2025-03-03T12:00:00Z".into())
+        );
+        assert_eq!(
+            resolve_older_than("2025-01-01T02:00:00+02:00", now).map(format_utc_rfc3339),
+            Some("2025-01-01T00:00:00Z".into())
+        );
+        assert_eq!(
+            resolve_older_than("2025-01-15", now).map(format_utc_rfc3339),
+            Some("2025-01-15T00:00:00Z".into())
+        );
+        assert_eq!(resolve_older_than("last week", now), None);
+    }
 
     #[test]
     fn sanitize_project_id_trims_and_defaults() {
@@ -147,4 +609,201 @@ mod tests {
         let tags = extract_tags(&payload).expect("array tags");
         assert_eq!(tags, vec!["alpha".to_string(), "beta".to_string()]);
     }
+
+    #[test]
+    fn normalize_time_boundary_converts_offsets_to_utc() {
+        assert_eq!(
+            normalize_time_boundary("2024-06-01T02:30:00+02:00", TimeBoundary::Start).as_deref(),
+            Some("2024-06-01T00:30:00Z")
+        );
+        assert_eq!(
+            normalize_time_boundary("2024-05-31T23:00:00-07:00", TimeBoundary::End).as_deref(),
+            Some("2024-06-01T06:00:00Z")
+        );
+        assert_eq!(
+            normalize_time_boundary(" 2024-06-01T00:00:00Z ", TimeBoundary::Start).as_deref(),
+            Some("2024-06-01T00:00:00Z")
+        );
+    }
+
+    #[test]
+    fn normalize_time_boundary_expands_date_only_values() {
+        assert_eq!(
+            normalize_time_boundary("2024-06-01", TimeBoundary::Start).as_deref(),
+            Some("2024-06-01T00:00:00Z")
+        );
+        assert_eq!(
+            normalize_time_boundary("2024-06-01", TimeBoundary::End).as_deref(),
+            Some("2024-06-01T23:59:59.999999999Z")
+        );
+        assert!(normalize_time_boundary("2024-02-30", TimeBoundary::Start).is_none());
+        assert!(normalize_time_boundary("2024-6-1", TimeBoundary::Start).is_none());
+    }
+
+    #[test]
+    fn normalize_time_boundary_handles_edge_strings() {
+        // Leap seconds are clamped to the last representable instant of the minute.
+        let leap = normalize_time_boundary("2016-12-31T23:59:60Z", TimeBoundary::End)
+            .expect("leap second accepted");
+        assert!(leap.starts_with("2016-12-31T23:59:59"));
+        assert!(normalize_time_boundary("2024-06-01T24:00:00Z", TimeBoundary::Start).is_none());
+        assert!(normalize_time_boundary("yesterday", TimeBoundary::Start).is_none());
+        assert!(normalize_time_boundary("", TimeBoundary::Start).is_none());
+    }
+
+    #[test]
+    fn ingest_timestamps_are_normalized_and_bounded() {
+        let now = OffsetDateTime::parse("2025-06-01T12:00:00Z", &Rfc3339).unwrap();
+        assert_eq!(
+            validate_ingest_timestamp("2019-03-04T10:00:00+02:00", false, now).unwrap(),
+            "2019-03-04T08:00:00Z"
+        );
+        assert_eq!(
+            validate_ingest_timestamp("2019-03-04", false, now).unwrap(),
+            "2019-03-04T00:00:00Z"
+        );
+        assert!(validate_ingest_timestamp("2025-06-01T12:04:00Z", false, now).is_ok());
+
+        let error = validate_ingest_timestamp("2025-06-02T00:00:00Z", false, now).unwrap_err();
+        assert!(error.contains("allow_future"), "{error}");
+        assert!(validate_ingest_timestamp("2025-06-02T00:00:00Z", true, now).is_ok());
+        assert!(validate_ingest_timestamp("last tuesday", true, now).is_err());
+    }
+
+    #[test]
+    fn score_chunk_quality_keeps_prose_and_rejects_machine_text() {
+        let prose = "Rotate the staging API keys every quarter. The on-call engineer \
+            opens a ticket, generates the new key in the vault, updates the deployment \
+            secrets, and confirms that the health checks pass before revoking the old key.";
+        let minified = "!function(e,t){\"object\"==typeof exports&&\"undefined\"!=typeof \
+            module?module.exports=t():\"function\"==typeof define&&define.amd?define(t):\
+            (e=e||self).Vue=t()}(this,function(){\"use strict\";var e=Object.freeze({});\
+            function t(e){return null==e}function n(e){return null!=e}";
+        let lockfile = r#"    "node_modules/ms": {
+      "version": "2.1.2",
+      "resolved": "https://registry.npmjs.org/ms/-/ms-2.1.2.tgz",
+      "integrity": "sha512-sGkPx+VjMtmA6MX27oA4FBFELFCZZ4S4XqeGOXCv68tT+jb3vk/RyaKWP0PTKyWtmLSM0b+adUTEvbs1PEaH2w==",
+      "dev": true
+    },
+    "node_modules/mz": {
+      "version": "2.7.0",
+      "resolved": "https://registry.npmjs.org/mz/-/mz-2.7.0.tgz",
+      "integrity": "sha512-z81GNO7nnYMEhrGh9LeymoE4+Yr0Wn5McHIZMK5cfQCl+NDX08sCZgUc9/6MHni9IWuFLm1Z3HTCXu2z9fN62Q==",
+      "dev": true
+    },"#;
+        let base64 = "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mNkYPhfDwAChwGA\
+            60e6kgAAAABJRU5ErkJggg0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mNk";
+
+        let prose_quality = score_chunk_quality(prose);
+        assert!(prose_quality.score > 0.9, "{prose_quality:?}");
+        for (label, text) in [
+            ("minified", minified),
+            ("lockfile", lockfile),
+            ("base64", base64),
+        ] {
+            let quality = score_chunk_quality(text);
+            assert!(quality.score < 0.5, "{label}: {quality:?}");
+        }
+        assert!(score_chunk_quality(lockfile).repeated_line_ratio > 0.1);
+        assert!(score_chunk_quality(base64).average_token_length > 100.0);
+        assert_eq!(score_chunk_quality("  \n ").score, 0.0);
+    }
+
+    const TRANSCRIPT: &str = "User: How do I rotate the staging API keys?
+Assistant: Great question! Generate a new key in the vault and update the deployment secrets. I hope this helps!
+User: Do I need to revoke the old key right away?
+Assistant: Revoke it once the health checks pass. Let me know if you have any other questions.
+User: Thanks
+Assistant: Happy to help!";
+
+    /// Documentation that quotes dialogue and assistant filler without being a transcript.
+    const DIALOGUE_DOCS: [&str; 3] = [
+        "# Support macros\n\nWhen a customer writes in, the bot replies with a short greeting.\n\
+         User: My invoice is wrong.\nAssistant: I'm sorry to hear that, let me check.\n\
+         After the greeting, the agent takes over and never says \"I hope this helps\" twice. \
+         Escalations go to the billing queue within one business day.",
+        "Example session for the CLI walkthrough:\n\n```text\nUser: list my keys\n\
+         Assistant: You have two keys. I hope this helps!\nUser: revoke the first\n\
+         Assistant: Done. Let me know if you need anything else.\nUser: thanks\n\
+         Assistant: Happy to help!\n```\n\nThe session above is recorded with `--transcript`.",
+        "Style guide: never begin replies with \"As an AI language model\".\n\
+         User: the person asking.\nAssistant: the reply we generate.\n\
+         Interviewer: How long have you run the team?\nInterviewee: Four years.\n\
+         Keep answers short, concrete, and free of filler such as \"great question\".",
+    ];
+
+    #[test]
+    fn transcripts_are_detected_but_dialogue_in_docs_is_not() {
+        let signals = transcript_signals(TRANSCRIPT);
+        assert_eq!(signals.user_turns, 3);
+        assert_eq!(signals.assistant_turns, 3);
+        assert!(signals.is_transcript(), "{signals:?}");
+
+        let emphasized = TRANSCRIPT
+            .replace("User:", "**User:**")
+            .replace("Assistant:", "> **ChatGPT:**");
+        assert!(transcript_signals(&emphasized).is_transcript());
+
+        for doc in DIALOGUE_DOCS {
+            let signals = transcript_signals(doc);
+            assert!(!signals.is_transcript(), "{signals:?}\n{doc}");
+        }
+    }
+
+    #[test]
+    fn warn_policy_keeps_text_and_flags_transcripts() {
+        let (text, handling) =
+            apply_transcript_policy(TranscriptPolicy::Warn, TRANSCRIPT.into()).expect("warn");
+        assert_eq!(text, TRANSCRIPT);
+        assert_eq!(handling, Some(TranscriptHandling::Warned));
+
+        for doc in DIALOGUE_DOCS {
+            let (text, handling) =
+                apply_transcript_policy(TranscriptPolicy::Warn, doc.into()).expect("warn");
+            assert_eq!(text, doc);
+            assert_eq!(handling, None);
+        }
+    }
+
+    #[test]
+    fn reject_policy_refuses_transcripts_only() {
+        let error = apply_transcript_policy(TranscriptPolicy::Reject, TRANSCRIPT.into())
+            .expect_err("transcript rejected");
+        assert!(matches!(error, ProcessingError::TranscriptRejected(_)));
+        assert_eq!(error.kind(), ErrorKind::InvalidRequest);
+        assert!(error.to_string().contains("3 user and 3 assistant turns"));
+
+        for doc in DIALOGUE_DOCS {
+            let (text, handling) = apply_transcript_policy(TranscriptPolicy::Reject, doc.into())
+                .expect("documentation accepted");
+            assert_eq!(text, doc);
+            assert_eq!(handling, None);
+        }
+    }
+
+    #[test]
+    fn strip_policy_removes_assistant_filler_and_leaves_docs_alone() {
+        let (text, handling) =
+            apply_transcript_policy(TranscriptPolicy::Strip, TRANSCRIPT.into()).expect("strip");
+        assert_eq!(
+            text,
+            "User: How do I rotate the staging API keys?
+Assistant: Generate a new key in the vault and update the deployment secrets.
+User: Do I need to revoke the old key right away?
+Assistant: Revoke it once the health checks pass.
+User: Thanks
+Assistant:"
+        );
+        assert_eq!(
+            handling,
+            Some(TranscriptHandling::Stripped { sentences: 4 })
+        );
+
+        for doc in DIALOGUE_DOCS {
+            let (text, handling) =
+                apply_transcript_policy(TranscriptPolicy::Strip, doc.into()).expect("strip");
+            assert_eq!(text, doc);
+            assert_eq!(handling, None);
+        }
+    }
 }
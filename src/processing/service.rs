@@ -1,31 +1,90 @@
 //! Processing service coordinating chunking, embedding, and Qdrant operations.
 
 use crate::{
-    config::get_config,
-    embedding::{EmbeddingClient, get_embedding_client},
+    audit::{AuditCounts, AuditLogger, AuditRecord},
+    config::{
+        ChunkStrategy, Config, EmbeddingProvider, EpisodicPartitioning, IngestFallback,
+        TranscriptPolicy, get_config,
+    },
+    embedding::{
+        AiLibClient, EmbeddingClient, EmbeddingClientError, get_embedding_client,
+        get_next_embedding_client, validate_embeddings,
+    },
     metrics::{CodeMetrics, MetricsSnapshot},
     processing::{
-        chunking::{chunk_text, determine_chunk_size},
-        mappers::{dedupe_chunks, map_scored_point},
-        sanitize::{sanitize_memory_type, sanitize_project_id, sanitize_tags},
+        chunking::{
+            TokenCounter, build_token_counter, chunk_text, determine_chunk_size,
+            override_below_floor, tokenizer_name,
+        },
+        classify::{
+            AutoClassify, ClassificationSummary, MemoryTypeRule, classify_chunk, infer_memory_type,
+            llm_prompt, parse_llm_label,
+        },
+        code::chunk_code,
+        document::{profile_overlap, stitch_chunks},
+        import::ImportedVector,
+        links::{add_link, add_linked_id, linked_ids, stored_links},
+        listing_cache::ListingCache,
+        manifest::{CollectionManifest, ManifestAction, ManifestResult},
+        mappers::{dedupe_chunks, extract_citations, map_scored_point, split_low_quality},
+        migration::{
+            self, MigrationError, PromotionOutcome, ProviderComparison, ProviderMigration,
+            QueryComparison, candidate_collection, overlap_at_k, score_correlation,
+        },
+        partitions::{
+            PARTITIONED_MEMORY_TYPE, existing_partitions, push_partition, search_partitions,
+        },
+        query::{compress_query, truncate_query},
+        sanitize::{
+            TimeBoundary, apply_transcript_policy, extract_tags, format_utc_rfc3339,
+            normalize_time_boundary, parse_time_boundary, sanitize_memory_type,
+            sanitize_project_id, sanitize_string, sanitize_tags,
+        },
         types::{
-            IngestMetadata, ProcessingError, ProcessingOutcome, QdrantHealthSnapshot, SearchError,
-            SearchHit, SearchRequest,
+            CollectionProfile, DedupeStats, DocumentChunk, DocumentSelector, DuplicatePurgeOutcome,
+            ErrorKind, InboundLink, IngestMetadata, IngestProfile, LinkOutcome, LookupRequest,
+            MemoryLinks, OrphanedSummary, ProcessingError, ProcessingOutcome, PruneOutcome,
+            QdrantHealthSnapshot, QueryCompression, ReadReplicaHealth, ReconstructedDocument,
+            ReembedOutcome, RenameOutcome, SearchError, SearchHit, SearchOutcome, SearchRequest,
+            StorageEstimate, StorageStats, StorageStatsReport, SummaryCheckOutcome, SummaryQuery,
+            SummaryRecord, ValidatedSearchRequest, VerifyOutcome, WarmupOutcome,
+            embedding_error_kind, qdrant_error_kind,
         },
     },
-    qdrant::{self, IndexSummary, PointInsert, QdrantService},
-    summarization::{SummarizationRequest as LlmSummarizationRequest, get_summarization_client},
+    qdrant::{
+        self, CollectionInfo, CollectionTopology, IndexSummary, LinkRelation, MemoryLink,
+        PayloadIndexSummary, PointInsert, PointQuery, QdrantService, StoredPoint, TagCounts,
+        client::{DEFAULT_DISTANCE, PAYLOAD_INDEX_FIELDS},
+        payload::{build_payload, current_timestamp_rfc3339, generate_memory_id},
+    },
+    state::{FileSyncState, SyncEntry, SyncStateStore, content_hash},
+    summarization::{
+        SummarizationClient, SummarizationClientError,
+        SummarizationRequest as LlmSummarizationRequest, get_summarization_client,
+    },
 };
 use async_trait::async_trait;
-use std::collections::BTreeSet;
-use std::sync::Arc;
+use serde_json::{Map, Value, json};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
 
 use super::summarize::{
-    EpisodicMemory, build_abstractive_prompt, build_extractive_summary, compute_summary_key,
-    sort_memories,
+    EpisodicMemory, Refinement, build_abstractive_prompt, build_extractive_summary,
+    compute_summary_key, halve_evenly, resolve_citations, sort_memories, trim_to_prompt_budget,
 };
 use super::types::SearchTimeRange as ProcSearchTimeRange;
 
+/// Extra abstractive attempts, each with half the items, after a context-length error.
+const MAX_CONTEXT_RETRIES: usize = 2;
+
+/// How long a collection's indexing status is reused for the push backlog hint.
+const INDEXING_STATUS_TTL: Duration = Duration::from_secs(30);
+
+/// Delay before the first indexing-status re-poll while warming a collection.
+const WARM_INITIAL_BACKOFF: Duration = Duration::from_millis(250);
+
 /// Coordinates the full ingestion pipeline: semantic chunking, embedding, and Qdrant writes.
 ///
 /// The service owns long-lived handles to the embedding client, Qdrant transport, and metrics
@@ -35,8 +94,100 @@ pub struct ProcessingService {
     embedding_client: Box<dyn EmbeddingClient + Send + Sync>,
     qdrant_service: QdrantService,
     metrics: Arc<CodeMetrics>,
+    ingest_fallback: IngestFallback,
+    transcript_policy: TranscriptPolicy,
+    memory_type_rules: Vec<MemoryTypeRule>,
+    embedding_prefixes: EmbeddingPrefixes,
+    audit: AuditLogger,
+    sync_state: Option<Box<dyn SyncStateStore>>,
+    strict_payload_indexes: bool,
+    /// `EPISODIC_PARTITIONING`; reads and maintenance also cover `<collection>-YYYY-MM` when monthly.
+    episodic_partitioning: EpisodicPartitioning,
+    /// Collections whose payload indexes were all ensured by this process.
+    indexed_collections: Mutex<HashSet<String>>,
+    /// Distance metric per collection, read once for `SEARCH_NORMALIZE_SCORES`.
+    collection_distances: Mutex<HashMap<String, String>>,
+    /// Indexing status per collection with the time it was fetched, for the push backlog hint.
+    indexing_statuses: Mutex<HashMap<String, (Instant, CollectionInfo)>>,
+    /// Distinct project and tag sets, kept for `LISTING_CACHE_TTL_MS`.
+    listing_cache: ListingCache,
+    /// Next provider and its window state while `EMBEDDING_PROVIDER_NEXT` is set.
+    migration: Option<ProviderMigration>,
+}
+
+/// Instructions prepended before embedding, for asymmetric models such as e5 or bge.
+#[derive(Debug, Clone, Default)]
+struct EmbeddingPrefixes {
+    /// `EMBEDDING_QUERY_PREFIX`, applied to search and warm-up queries.
+    query: String,
+    /// `EMBEDDING_DOC_PREFIX`, applied to chunks and summaries being stored.
+    document: String,
+}
+
+impl EmbeddingPrefixes {
+    fn queries(&self, texts: Vec<String>) -> Vec<String> {
+        prepend(&self.query, texts)
+    }
+
+    fn documents(&self, texts: Vec<String>) -> Vec<String> {
+        prepend(&self.document, texts)
+    }
+}
+
+/// Prefix every text; the stored payload text and `chunk_hash` stay unprefixed.
+fn prepend(prefix: &str, texts: Vec<String>) -> Vec<String> {
+    if prefix.is_empty() {
+        return texts;
+    }
+    texts
+        .into_iter()
+        .map(|text| format!("{prefix}{text}"))
+        .collect()
 }
 
+/// Tag applied to points embedded with the deterministic fallback encoder.
+pub const FALLBACK_EMBEDDING_TAG: &str = "embedding:fallback";
+
+/// Tag applied by `check_summaries` to summaries whose sources were deleted.
+pub const ORPHANED_SUMMARY_TAG: &str = "summary:orphaned";
+
+/// Source memory ids looked up per retrieve request during `check_summaries`.
+const SUMMARY_SOURCE_BATCH_SIZE: usize = 256;
+
+/// Points sampled by `describe_collection` to discover payload keys.
+const DESCRIBE_SAMPLE_SIZE: usize = 20;
+
+/// Points whose `chunk_hash` is scanned by `describe_collection` to estimate duplication.
+const DEDUPE_SCAN_SAMPLE_SIZE: usize = 1000;
+
+/// Skipped chunk previews logged per document when `CHUNK_QUALITY_MIN` filters chunks.
+const LOW_QUALITY_LOG_SAMPLES: usize = 3;
+
+/// Points re-embedded per provider request during `reembed_fallback`.
+const REEMBED_BATCH_SIZE: usize = 64;
+
+/// Points copied and then deleted per round trip during `move_to_collection`.
+const MOVE_BATCH_SIZE: usize = 256;
+
+/// Points read and copied per round trip during `rename_collection`.
+const RENAME_BATCH_SIZE: usize = 256;
+
+/// Redundant copies deleted per request during `purge_duplicates`.
+const PURGE_BATCH_SIZE: usize = 256;
+
+/// Points read per scroll page during `verify_collection`.
+const VERIFY_PAGE_SIZE: usize = 256;
+
+/// Payload fields written by every ingest; `verify_collection` flags points missing any of them.
+const REQUIRED_PAYLOAD_FIELDS: &[&str] = &[
+    "memory_id",
+    "project_id",
+    "memory_type",
+    "timestamp",
+    "chunk_hash",
+    "text",
+];
+
 /// Abstraction over the processing pipeline used by external surfaces (HTTP, MCP).
 #[async_trait]
 pub trait ProcessingApi: Send + Sync {
@@ -48,16 +199,95 @@ pub trait ProcessingApi: Send + Sync {
         metadata: IngestMetadata,
     ) -> Result<ProcessingOutcome, ProcessingError>;
 
-    /// Create or update a collection with the desired vector size.
+    /// Store records that already carry embeddings without re-embedding them.
+    ///
+    /// Returns the number of points written.
+    async fn import_vectors(
+        &self,
+        collection_name: &str,
+        records: Vec<ImportedVector>,
+    ) -> Result<usize, ProcessingError>;
+
+    /// Create or update a collection with the desired vector size and cluster placement.
     async fn create_collection(
         &self,
         collection_name: &str,
         vector_size: Option<u64>,
+        topology: CollectionTopology,
     ) -> Result<(), ProcessingError>;
 
+    /// Create the collection with the configured dimension if it does not exist yet.
+    async fn ensure_collection(&self, collection_name: &str) -> Result<(), ProcessingError>;
+
+    /// Vector size stored points of `collection_name` must have, creating the collection first
+    /// when it does not exist yet.
+    async fn vector_dimension(&self, collection_name: &str) -> Result<usize, ProcessingError>;
+
     /// Enumerate collections managed by the storage backend.
     async fn list_collections(&self) -> Result<Vec<String>, ProcessingError>;
 
+    /// Enumerate distinct project identifiers stored in the collection.
+    async fn list_projects(
+        &self,
+        collection_name: &str,
+    ) -> Result<BTreeSet<String>, ProcessingError>;
+
+    /// Enumerate distinct tags stored in the collection, optionally scoped by project.
+    async fn list_tags(
+        &self,
+        collection_name: &str,
+        project_id: Option<&str>,
+    ) -> Result<BTreeSet<String>, ProcessingError>;
+
+    /// Execute a semantic search against stored memories.
+    async fn search_memories(
+        &self,
+        request: ValidatedSearchRequest,
+    ) -> Result<SearchOutcome, SearchError>;
+
+    /// Summarize memories within a time range and store the summary as a semantic memory.
+    async fn summarize_memories(
+        &self,
+        request: SummarizeRequest,
+    ) -> Result<SummarizeOutcome, SummarizeError>;
+
+    /// Collection a push of `metadata` into `collection_name` lands in under
+    /// `EPISODIC_PARTITIONING`.
+    fn ingest_collection(&self, collection_name: &str, metadata: &IngestMetadata) -> String;
+
+    /// Collections a read of `collection_name` covers under `EPISODIC_PARTITIONING`, with the
+    /// partitions among them.
+    async fn read_targets(
+        &self,
+        collection_name: &str,
+        memory_type: Option<&str>,
+        time_range: Option<&ProcSearchTimeRange>,
+    ) -> Result<(Vec<String>, Option<Vec<String>>), ProcessingError>;
+
+    /// Fetch status and indexing progress for the target collection.
+    async fn collection_info(
+        &self,
+        collection_name: &str,
+    ) -> Result<CollectionInfo, ProcessingError>;
+
+    /// Approximate disk usage per collection, optionally restricted to one project.
+    async fn storage_stats(
+        &self,
+        collection: Option<&str>,
+        project_id: Option<&str>,
+    ) -> Result<StorageStatsReport, ProcessingError>;
+
+    /// Wait for indexing to settle, then optionally issue warm-up searches.
+    async fn warm_collection(
+        &self,
+        collection_name: &str,
+        timeout: Duration,
+        warmup_queries: usize,
+    ) -> Result<WarmupOutcome, ProcessingError>;
+
+    /// Probe the storage backend for reachability and default collection presence.
+    async fn qdrant_health(&self) -> QdrantHealthSnapshot;
+
     /// Retrieve the current metrics snapshot for diagnostics.
     fn metrics_snapshot(&self) -> MetricsSnapshot;
 }
@@ -66,10 +296,22 @@ impl ProcessingService {
     /// Build a new processing service, initializing backing services as needed.
     pub async fn new() -> Self {
         let config = get_config();
+        if let Some(size) = override_below_floor(
+            config.text_splitter_chunk_size,
+            config.text_splitter_min_chunk_size,
+        ) {
+            tracing::warn!(
+                override_size = size,
+                floor = config.text_splitter_min_chunk_size,
+                "TEXT_SPLITTER_CHUNK_SIZE is below TEXT_SPLITTER_MIN_CHUNK_SIZE; using the floor"
+            );
+        }
         tracing::info!("Initializing embedding client");
         let embedding_client = get_embedding_client();
         tracing::info!("Embedding client initialized");
-        let qdrant_service = QdrantService::new().expect("Failed to connect to Qdrant");
+        let qdrant_service = QdrantService::connect()
+            .await
+            .expect("Failed to connect to Qdrant");
         let vector_size = config.embedding_dimension as u64;
         tracing::debug!(
             collection = %config.qdrant_collection_name,
@@ -80,38 +322,190 @@ impl ProcessingService {
             .create_collection_if_not_exists(&config.qdrant_collection_name, vector_size)
             .await
             .expect("Failed to ensure Qdrant collection exists");
-        qdrant_service
+        let index_summary = qdrant_service
             .ensure_payload_indexes(&config.qdrant_collection_name)
             .await
             .expect("Failed to ensure Qdrant payload indexes");
+        let primary_indexed = check_payload_indexes(
+            &config.qdrant_collection_name,
+            index_summary,
+            config.strict_payload_indexes,
+        )
+        .expect("Failed to ensure Qdrant payload indexes");
         tracing::debug!(collection = %config.qdrant_collection_name, "Primary collection ready");
+        let audit = AuditLogger::open(&config.audit_log).expect("Failed to open AUDIT_LOG");
+        let sync_state = config.state_dir.as_deref().and_then(|dir| {
+            FileSyncState::open(dir)
+                .map(|store| Box::new(store) as Box<dyn SyncStateStore>)
+                .inspect_err(|error| {
+                    tracing::warn!(
+                        dir = %dir.display(),
+                        error = %error,
+                        "Failed to open STATE_DIR; unchanged sources will be re-indexed"
+                    );
+                })
+                .ok()
+        });
+
+        let service = Self {
+            embedding_client,
+            qdrant_service,
+            metrics: Arc::new(CodeMetrics::new()),
+            ingest_fallback: config.ingest_fallback,
+            transcript_policy: config.transcript_policy,
+            memory_type_rules: config.memory_type_rules.clone(),
+            embedding_prefixes: EmbeddingPrefixes {
+                query: config.embedding_query_prefix.clone(),
+                document: config.embedding_doc_prefix.clone(),
+            },
+            audit,
+            sync_state,
+            strict_payload_indexes: config.strict_payload_indexes,
+            episodic_partitioning: config.episodic_partitioning,
+            indexed_collections: Mutex::new(
+                primary_indexed
+                    .then(|| config.qdrant_collection_name.clone())
+                    .into_iter()
+                    .collect(),
+            ),
+            collection_distances: Mutex::default(),
+            indexing_statuses: Mutex::default(),
+            listing_cache: ListingCache::new(Duration::from_millis(config.listing_cache_ttl_ms)),
+            migration: get_next_embedding_client()
+                .zip(config.embedding_next.clone())
+                .map(|(client, next)| ProviderMigration::new(client, next)),
+        };
+        service.detect_promotion().await;
+        if let Some(path) = &config.collection_manifest {
+            let manifest = CollectionManifest::load(path)
+                .unwrap_or_else(|error| panic!("Invalid COLLECTION_MANIFEST: {error}"));
+            service
+                .apply_manifest(&manifest)
+                .await
+                .unwrap_or_else(|error| panic!("Failed to apply COLLECTION_MANIFEST: {error}"));
+        }
+        service
+    }
 
+    /// Assemble a service around pre-built clients so handler tests can target mock servers.
+    #[cfg(test)]
+    pub(crate) fn from_parts(
+        embedding_client: Box<dyn EmbeddingClient + Send + Sync>,
+        qdrant_service: QdrantService,
+    ) -> Self {
         Self {
             embedding_client,
             qdrant_service,
             metrics: Arc::new(CodeMetrics::new()),
+            ingest_fallback: IngestFallback::None,
+            transcript_policy: TranscriptPolicy::Warn,
+            memory_type_rules: Vec::new(),
+            embedding_prefixes: EmbeddingPrefixes::default(),
+            audit: AuditLogger::disabled(),
+            sync_state: None,
+            strict_payload_indexes: false,
+            episodic_partitioning: EpisodicPartitioning::Off,
+            indexed_collections: Mutex::default(),
+            collection_distances: Mutex::default(),
+            indexing_statuses: Mutex::default(),
+            listing_cache: ListingCache::new(Duration::ZERO),
+            migration: None,
         }
     }
 
+    /// Store episodic pushes in monthly partitions, as `EPISODIC_PARTITIONING=monthly` does.
+    #[cfg(test)]
+    pub(crate) fn with_monthly_partitions(mut self) -> Self {
+        self.episodic_partitioning = EpisodicPartitioning::Monthly;
+        self
+    }
+
     /// Chunk, embed, and index a document.
+    #[tracing::instrument(
+        name = "processing.ingest",
+        skip_all,
+        fields(collection = collection_name, chunks = tracing::field::Empty)
+    )]
     pub async fn process_and_index(
         &self,
         collection_name: &str,
         text: String,
-        metadata: IngestMetadata,
+        mut metadata: IngestMetadata,
     ) -> Result<ProcessingOutcome, ProcessingError> {
         tracing::info!(collection = collection_name, "Processing document");
+        let (text, transcript) = apply_transcript_policy(self.transcript_policy, text)?;
         let config = get_config();
         self.ensure_collection(collection_name).await?;
-        let chunk_size = determine_chunk_size(
+        let sizing = determine_chunk_size(
             config.text_splitter_chunk_size,
+            config.text_splitter_min_chunk_size,
             config.embedding_provider,
             &config.embedding_model,
             config.text_splitter_use_safe_defaults,
         );
+        let chunk_size = sizing.chunk_size;
         let overlap = config.text_splitter_chunk_overlap.unwrap_or(0);
+        if sizing.auto_sized
+            && let Some(derived) = sizing.clamped_from
+        {
+            tracing::warn!(
+                derived,
+                chunk_size,
+                context_window = sizing.context_window,
+                "Derived chunk size was clamped into the automatic bounds"
+            );
+        }
+        let sync_key = self.sync_state.as_ref().and_then(|_| {
+            sanitize_string(metadata.source_uri.clone()).map(|source_uri| {
+                (
+                    sanitize_project_id(metadata.project_id.clone()).unwrap_or_default(),
+                    source_uri,
+                    content_hash(&text),
+                )
+            })
+        });
+        if let (Some(store), Some((project_id, source_uri, hash))) = (&self.sync_state, &sync_key)
+            && let Some(entry) = store
+                .get(project_id, source_uri)
+                .filter(|entry| entry.is_unchanged(collection_name, hash))
+        {
+            tracing::info!(
+                collection = collection_name,
+                project_id = %project_id,
+                source_uri = %source_uri,
+                "Source unchanged since last sync; skipping"
+            );
+            let entry_chunks = entry.chunk_count;
+            self.metrics.record_dedupe(
+                collection_name,
+                entry_chunks as u64,
+                0,
+                entry_chunks as u64,
+            );
+            return Ok(ProcessingOutcome {
+                chunk_count: 0,
+                chunk_size,
+                overlap,
+                auto_sized: sizing.auto_sized,
+                embedding_context_window: sizing.context_window,
+                chunk_size_clamped_from: sizing.clamped_from,
+                inserted: 0,
+                updated: 0,
+                skipped_duplicates: 0,
+                skipped_low_quality: 0,
+                skipped_unchanged: true,
+                skipped_unchanged_chunks: entry_chunks,
+                degraded: false,
+                classification: None,
+                ingest_profile: ingest_profile(config, chunk_size, overlap),
+                doc_id: None,
+                transcript,
+            });
+        }
         tracing::debug!(
             chunk_size,
+            auto_sized = sizing.auto_sized,
+            context_window = sizing.context_window,
             override = config.text_splitter_chunk_size,
             provider = ?config.embedding_provider,
             model = %config.embedding_model,
@@ -119,46 +513,168 @@ impl ProcessingService {
             use_safe_defaults = config.text_splitter_use_safe_defaults,
             "Derived chunk size"
         );
-        let chunks = chunk_text(
-            &text,
-            chunk_size,
-            overlap,
-            config.embedding_provider,
-            &config.embedding_model,
-        )?;
+        let chunks = match config.text_splitter_strategy {
+            ChunkStrategy::Token => chunk_text(
+                &text,
+                chunk_size,
+                overlap,
+                config.embedding_provider,
+                &config.embedding_model,
+                config.tokenizer_fallback,
+            )?,
+            ChunkStrategy::Code => chunk_code(
+                &text,
+                chunk_size,
+                overlap,
+                config.embedding_provider,
+                &config.embedding_model,
+                config.tokenizer_fallback,
+                metadata.source_uri.as_deref(),
+            )?,
+        };
         let (prepared_chunks, skipped_duplicates) = dedupe_chunks(chunks);
+        let (prepared_chunks, low_quality) =
+            split_low_quality(prepared_chunks, config.chunk_quality_min);
+        let skipped_low_quality = low_quality.len();
+        if skipped_low_quality > 0 {
+            let samples: Vec<String> = low_quality
+                .iter()
+                .take(LOW_QUALITY_LOG_SAMPLES)
+                .map(|chunk| {
+                    format!(
+                        "{:.3}: {}",
+                        chunk.quality_score,
+                        chunk.text.chars().take(80).collect::<String>()
+                    )
+                })
+                .collect();
+            tracing::debug!(
+                collection = collection_name,
+                skipped_low_quality,
+                min_quality = ?config.chunk_quality_min,
+                ?samples,
+                "Skipped low-quality chunks"
+            );
+        }
         let texts: Vec<String> = prepared_chunks
             .iter()
             .map(|chunk| chunk.text.clone())
             .collect();
-        let embeddings = if texts.is_empty() {
-            Vec::new()
+        if sanitize_memory_type(metadata.memory_type.clone()).is_none()
+            && let Some(rule) = infer_memory_type(
+                &self.memory_type_rules,
+                metadata.tags.as_deref(),
+                metadata.source_uri.as_deref(),
+            )
+        {
+            tracing::info!(
+                collection = collection_name,
+                pattern = %rule.pattern,
+                memory_type = %rule.memory_type,
+                "Inferred memory_type from MEMORY_TYPE_RULES"
+            );
+            metadata.memory_type = Some(rule.memory_type.clone());
+        }
+        let chunk_types = match metadata.auto_classify {
+            Some(mode) => Some(self.classify_chunks(&texts, mode).await),
+            None => None,
+        };
+        let candidate = self.candidate_vectors(collection_name, &texts).await;
+        let (embeddings, degraded) = if texts.is_empty() {
+            (Vec::new(), false)
         } else {
-            self.embedding_client.generate_embeddings(texts).await?
+            self.embed_for_ingest(collection_name, texts).await?
         };
 
         debug_assert_eq!(prepared_chunks.len(), embeddings.len());
 
+        if degraded {
+            metadata
+                .tags
+                .get_or_insert_with(Vec::new)
+                .push(FALLBACK_EMBEDDING_TAG.to_string());
+        }
+
+        let classification = chunk_types.as_ref().map(|types| {
+            let mut summary = ClassificationSummary::default();
+            types
+                .iter()
+                .for_each(|memory_type| summary.record(memory_type));
+            summary
+        });
+        let mut chunk_types = chunk_types.map(Vec::into_iter);
         let points: Vec<PointInsert> = prepared_chunks
             .into_iter()
-            .zip(embeddings.into_iter())
+            .zip(embeddings)
             .map(|(chunk, vector)| PointInsert {
                 text: chunk.text,
                 chunk_hash: chunk.chunk_hash,
                 vector,
+                memory_type: chunk_types
+                    .as_mut()
+                    .and_then(Iterator::next)
+                    .map(str::to_string),
+                quality_score: Some(chunk.quality_score),
+                chunk_index: Some(chunk.chunk_index),
+                content_kind: chunk.content_kind.map(str::to_string),
+                language: chunk.language.map(str::to_string),
+                symbol: chunk.symbol,
             })
             .collect();
 
-        let overrides = metadata.into_overrides();
+        let ingest_profile = ingest_profile(config, chunk_size, overlap);
+        let mut overrides = metadata.into_overrides();
+        overrides.profile_hash = Some(ingest_profile.hash());
+        overrides.ingest_profile = Some(ingest_profile.compact());
+        let doc_id = (!points.is_empty()).then(|| Uuid::new_v4().to_string());
+        overrides.doc_id = doc_id.clone();
+        let (candidate_name, candidate_vectors) = candidate.unzip();
         let IndexSummary { inserted, updated } = self
             .qdrant_service
-            .index_points(collection_name, points, &overrides)
+            .index_points_with_candidate(
+                collection_name,
+                points,
+                &overrides,
+                candidate_name.as_deref().zip(candidate_vectors),
+            )
             .await?;
+        self.listing_cache.invalidate(collection_name);
 
         let chunk_count = inserted + updated;
+        self.audit.record(AuditRecord::new(
+            "push",
+            collection_name,
+            Some(
+                overrides
+                    .project_id
+                    .clone()
+                    .unwrap_or_else(|| "default".into()),
+            ),
+            AuditCounts {
+                inserted,
+                updated,
+                deleted: 0,
+            },
+        ));
 
+        if let (Some(store), Some((project_id, source_uri, hash))) = (&self.sync_state, sync_key) {
+            store.record(SyncEntry::new(
+                &project_id,
+                &source_uri,
+                collection_name,
+                hash,
+                chunk_count,
+            ));
+        }
         self.metrics
-            .record_document(chunk_count as u64, chunk_size as u64);
+            .record_document(collection_name, chunk_count as u64, chunk_size as u64);
+        self.metrics.record_dedupe(
+            collection_name,
+            (chunk_count + skipped_duplicates + skipped_low_quality) as u64,
+            skipped_duplicates as u64,
+            0,
+        );
+        tracing::Span::current().record("chunks", chunk_count);
         tracing::info!(
             collection = collection_name,
             chunks = chunk_count,
@@ -166,23 +682,180 @@ impl ProcessingService {
             inserted,
             updated,
             skipped_duplicates,
+            skipped_low_quality,
+            degraded,
             "Document indexed"
         );
 
         Ok(ProcessingOutcome {
             chunk_count,
             chunk_size,
+            overlap,
+            auto_sized: sizing.auto_sized,
+            embedding_context_window: sizing.context_window,
+            chunk_size_clamped_from: sizing.clamped_from,
             inserted,
             updated,
             skipped_duplicates,
+            skipped_low_quality,
+            skipped_unchanged: false,
+            skipped_unchanged_chunks: 0,
+            degraded,
+            classification,
+            ingest_profile,
+            doc_id,
+            transcript,
         })
     }
 
+    /// Write pre-embedded records as new points, one per record, in a single upsert.
+    pub async fn import_vectors(
+        &self,
+        collection_name: &str,
+        records: Vec<ImportedVector>,
+    ) -> Result<usize, ProcessingError> {
+        if records.is_empty() {
+            return Ok(0);
+        }
+        self.ensure_collection(collection_name).await?;
+        let texts: Vec<String> = records.iter().map(|record| record.text.clone()).collect();
+        let candidate = self.candidate_vectors(collection_name, &texts).await;
+        let ingested_at = current_timestamp_rfc3339();
+        let points: Vec<StoredPoint> = records
+            .into_iter()
+            .map(|record| {
+                let id = generate_memory_id();
+                let payload = match build_payload(
+                    &id,
+                    &record.text,
+                    &ingested_at,
+                    &qdrant::compute_chunk_hash(&record.text),
+                    &record.metadata.into_overrides(),
+                ) {
+                    Value::Object(payload) => payload,
+                    _ => Map::new(),
+                };
+                StoredPoint {
+                    id,
+                    vector: Value::from(record.vector),
+                    payload,
+                }
+            })
+            .collect();
+        self.qdrant_service
+            .upsert_points(collection_name, &points)
+            .await?;
+        if let Some((_, vectors)) = candidate {
+            // Same ids and payloads, so later deletes and edits mirror onto these points.
+            let mirrored: Vec<StoredPoint> = points
+                .iter()
+                .zip(vectors)
+                .map(|(point, vector)| StoredPoint {
+                    vector: Value::from(vector),
+                    ..point.clone()
+                })
+                .collect();
+            self.mirror_to_candidate(collection_name, |candidate| async move {
+                self.qdrant_service
+                    .upsert_points(&candidate, &mirrored)
+                    .await
+            })
+            .await;
+        }
+        self.listing_cache.invalidate(collection_name);
+
+        self.audit.record(AuditRecord::new(
+            "import",
+            collection_name,
+            None,
+            AuditCounts {
+                inserted: points.len(),
+                ..AuditCounts::default()
+            },
+        ));
+        tracing::info!(
+            collection = collection_name,
+            points = points.len(),
+            "Imported pre-embedded records"
+        );
+        Ok(points.len())
+    }
+
+    /// Label each chunk with a memory type using the requested strategy.
+    ///
+    /// LLM mode needs a summarization provider and model; without them, and for any chunk whose
+    /// reply names no single type, the heuristic classifier decides.
+    async fn classify_chunks(&self, texts: &[String], mode: AutoClassify) -> Vec<&'static str> {
+        let llm = match mode {
+            AutoClassify::Heuristic => None,
+            AutoClassify::Llm => {
+                match (
+                    get_config().summarization_model.clone(),
+                    get_summarization_client(),
+                ) {
+                    (Some(model), Some(client)) => Some((model, client)),
+                    _ => {
+                        tracing::warn!(
+                            "auto_classify=llm requires a summarization provider and model; using heuristic"
+                        );
+                        None
+                    }
+                }
+            }
+        };
+
+        let mut labels = Vec::with_capacity(texts.len());
+        for text in texts {
+            let mut label = None;
+            if let Some((model, client)) = &llm {
+                let request = LlmSummarizationRequest {
+                    model: model.clone(),
+                    prompt: llm_prompt(text),
+                    max_words: 1,
+                };
+                match client.generate_summary(request).await {
+                    Ok(reply) => label = parse_llm_label(&reply),
+                    Err(error) => {
+                        tracing::warn!(error = %error, "LLM chunk classification failed; using heuristic");
+                    }
+                }
+            }
+            labels.push(label.unwrap_or_else(|| classify_chunk(text)));
+        }
+        labels
+    }
+
     /// Execute a semantic search query against Qdrant using the configured embedding provider.
+    ///
+    /// A `query_text` longer than `MAX_QUERY_TOKENS` is shortened first (see
+    /// [`shorten_query`]); the outcome reports the query that was embedded.
     pub async fn search_memories(
+        &self,
+        request: ValidatedSearchRequest,
+    ) -> Result<SearchOutcome, SearchError> {
+        self.execute_search(request.into_inner()).await
+    }
+
+    /// Execute a search without validating it first.
+    ///
+    /// Out-of-range limits are clamped silently and other invalid input reaches Qdrant as-is.
+    #[deprecated(
+        since = "0.2.0",
+        note = "build the request with `SearchRequest::builder()` and call `search_memories`; this will be removed in the next release"
+    )]
+    pub async fn search_memories_unchecked(
         &self,
         request: SearchRequest,
-    ) -> Result<Vec<SearchHit>, SearchError> {
+    ) -> Result<SearchOutcome, SearchError> {
+        self.execute_search(request).await
+    }
+
+    #[tracing::instrument(
+        name = "processing.search",
+        skip_all,
+        fields(collection = tracing::field::Empty, hits = tracing::field::Empty)
+    )]
+    async fn execute_search(&self, request: SearchRequest) -> Result<SearchOutcome, SearchError> {
         let config = get_config();
         let SearchRequest {
             query_text,
@@ -190,19 +863,33 @@ impl ProcessingService {
             project_id,
             memory_type,
             tags,
+            include_ids,
             time_range,
             limit,
             score_threshold,
+            search_params,
+            order_by_timestamp,
+            require_text,
+            query_compression,
+            fields,
         } = request;
 
         let collection_name = collection.unwrap_or_else(|| config.qdrant_collection_name.clone());
+        let span = tracing::Span::current();
+        span.record("collection", collection_name.as_str());
+        let query = shorten_query(config, query_text, query_compression).await;
+        if let Some(migration) = &self.migration {
+            migration.record_query(&collection_name, &query.text);
+        }
         let mut vectors = self
-            .embedding_client
-            .generate_embeddings(vec![query_text])
+            .embed(
+                &collection_name,
+                self.embedding_prefixes.queries(vec![query.text.clone()]),
+            )
             .await?;
         let vector = vectors.pop().ok_or(SearchError::EmptyEmbedding)?;
 
-        let expected = config.embedding_dimension;
+        let expected = self.active_dimension(&collection_name);
         let actual = vector.len();
         if actual != expected {
             return Err(SearchError::DimensionMismatch { expected, actual });
@@ -214,468 +901,3692 @@ impl ProcessingService {
 
         let limit = limit.unwrap_or(default_limit).clamp(1, max_limit);
         let threshold = score_threshold.unwrap_or(default_threshold).clamp(0.0, 1.0);
+        let distance = if config.search_normalize_scores {
+            self.collection_distance(&collection_name).await?
+        } else {
+            None
+        };
+        let raw_threshold = match distance.as_deref() {
+            Some(distance) => qdrant::raw_score_threshold(distance, threshold),
+            None => Some(threshold),
+        };
 
         let filter_args = qdrant::SearchFilterArgs {
             project_id: sanitize_project_id(project_id),
             memory_type: sanitize_memory_type(memory_type),
             tags: sanitize_tags(tags),
-            time_range: time_range.map(|range| qdrant::SearchTimeRange {
-                start: range.start,
-                end: range.end,
-            }),
+            include_ids,
+            time_range: time_range.map(normalize_time_range),
+            unix_time_range: self.unix_time_range(),
+            require_text,
         };
 
         let filter = qdrant::build_search_filter(&filter_args);
+        let local_time_range = local_time_range(&filter_args);
+        // Ordering and the local time check read `timestamp`, so it is fetched even when the
+        // projection leaves it out and cleared again below.
+        let payload_fields = fields.as_ref().map(|fields| {
+            let mut keys: Vec<String> = fields
+                .iter()
+                .filter(|field| !matches!(field.as_str(), "id" | "score"))
+                .cloned()
+                .collect();
+            if (order_by_timestamp.is_some() || local_time_range.is_some())
+                && !keys.iter().any(|key| key == "timestamp")
+            {
+                keys.push("timestamp".into());
+            }
+            keys
+        });
 
         let hits = self
             .qdrant_service
             .search_points(
                 &collection_name,
-                vector,
-                filter,
-                limit,
-                Some(threshold),
-                None,
+                PointQuery {
+                    vector,
+                    filter,
+                    limit,
+                    score_threshold: raw_threshold,
+                    params: search_params.as_ref(),
+                    order: order_by_timestamp,
+                    payload_fields: payload_fields.as_deref(),
+                    ..PointQuery::default()
+                },
             )
             .await?;
 
-        Ok(hits.into_iter().map(map_scored_point).collect())
+        let mut hits: Vec<SearchHit> = hits
+            .into_iter()
+            .map(map_scored_point)
+            .filter(|hit| within_time_range(hit.timestamp.as_deref(), local_time_range))
+            .collect();
+        if let Some(distance) = distance.as_deref() {
+            for hit in &mut hits {
+                hit.score = qdrant::normalize_score(distance, hit.score);
+            }
+        }
+        if let Some(fields) = fields.as_deref() {
+            hits.iter_mut().for_each(|hit| hit.retain_fields(fields));
+        }
+        if !hits.is_empty() {
+            let stored_in = self.resolve_collection(&collection_name).await;
+            for hit in &mut hits {
+                hit.collection = Some(stored_in.clone());
+            }
+        }
+        span.record("hits", hits.len());
+        Ok(SearchOutcome {
+            hits,
+            effective_query: query.text,
+            query_truncated: query.truncated,
+            query_compressed: query.compressed,
+        })
     }
 
-    /// Ensure that the target collection exists within Qdrant.
-    pub async fn ensure_collection(&self, collection_name: &str) -> Result<(), ProcessingError> {
+    /// Fetch the records carrying an exact `memory_id` and/or `chunk_hash`, or whose `text`
+    /// contains `text_contains`.
+    ///
+    /// Runs a filtered scroll instead of a vector query, so no embedding is generated. Every
+    /// returned hit has a score of `1.0`; the remaining filters narrow the match like a search.
+    pub async fn lookup_memories(
+        &self,
+        request: LookupRequest,
+    ) -> Result<Vec<SearchHit>, SearchError> {
         let config = get_config();
-        let vector_size = config.embedding_dimension as u64;
-        self.qdrant_service
-            .create_collection_if_not_exists(collection_name, vector_size)
-            .await
-            .map_err(ProcessingError::from)?;
-        self.qdrant_service
-            .ensure_payload_indexes(collection_name)
-            .await
-            .map_err(ProcessingError::from)?;
-        tracing::debug!(collection = collection_name, "Collection ensured");
-        Ok(())
+        let LookupRequest {
+            memory_id,
+            chunk_hash,
+            collection,
+            project_id,
+            memory_type,
+            tags,
+            include_ids,
+            time_range,
+            limit,
+            require_text,
+            text_contains,
+        } = request;
+
+        let collection_name = collection.unwrap_or_else(|| config.qdrant_collection_name.clone());
+        let limit = limit
+            .unwrap_or(config.search_default_limit)
+            .clamp(1, config.search_max_limit);
+
+        let filter_args = qdrant::SearchFilterArgs {
+            project_id: sanitize_project_id(project_id),
+            memory_type: sanitize_memory_type(memory_type),
+            tags: sanitize_tags(tags),
+            include_ids,
+            time_range: time_range.map(normalize_time_range),
+            unix_time_range: self.unix_time_range(),
+            require_text,
+        };
+
+        let local_time_range = local_time_range(&filter_args);
+        let mut filter = match qdrant::build_search_filter(&filter_args) {
+            Some(Value::Object(filter)) => filter,
+            _ => Map::new(),
+        };
+        let must = filter.entry("must").or_insert_with(|| json!([]));
+        for (key, value) in [("memory_id", memory_id), ("chunk_hash", chunk_hash)] {
+            if let (Some(value), Some(must)) = (value, must.as_array_mut()) {
+                must.push(json!({ "key": key, "match": { "value": value } }));
+            }
+        }
+        if let (Some(text), Some(must)) = (text_contains.as_ref(), must.as_array_mut()) {
+            must.push(json!({ "key": "text", "match": { "text": text } }));
+        }
+
+        let records = self
+            .qdrant_service
+            .scroll_payloads_with_ids(
+                &collection_name,
+                Value::Bool(true),
+                Some(Value::Object(filter)),
+            )
+            .await?;
+
+        let stored_in = if records.is_empty() {
+            collection_name
+        } else {
+            self.resolve_collection(&collection_name).await
+        };
+        Ok(records
+            .into_iter()
+            .map(|(id, payload)| SearchHit {
+                collection: Some(stored_in.clone()),
+                ..map_scored_point(qdrant::ScoredPoint {
+                    id,
+                    score: 1.0,
+                    payload: Some(payload),
+                })
+            })
+            .filter(|hit| within_time_range(hit.timestamp.as_deref(), local_time_range))
+            // A full-text index makes Qdrant match tokens, not substrings; keep exact matches only.
+            .filter(|hit| match text_contains.as_deref() {
+                Some(needle) => hit
+                    .text
+                    .as_deref()
+                    .is_some_and(|text| text.contains(needle)),
+                None => true,
+            })
+            .take(limit)
+            .collect())
     }
 
-    /// Create or resize a collection with the desired vector size.
-    pub async fn create_collection(
+    /// Latest `limit` memories stored for `project_id`, newest `timestamp` first.
+    ///
+    /// Monthly partitions are read newest first until they alone fill `limit`, since every older
+    /// partition only holds older memories.
+    pub async fn recent_memories(
         &self,
         collection_name: &str,
-        vector_size: Option<u64>,
-    ) -> Result<(), ProcessingError> {
-        let size = vector_size.unwrap_or_else(|| {
-            let config = get_config();
-            config.embedding_dimension as u64
+        project_id: &str,
+        limit: usize,
+    ) -> Result<Vec<SearchHit>, ProcessingError> {
+        let filter = qdrant::build_search_filter(&qdrant::SearchFilterArgs {
+            project_id: Some(project_id.to_string()),
+            ..Default::default()
         });
+        let mut records = self
+            .qdrant_service
+            .scroll_recent_payloads(collection_name, filter.clone(), limit)
+            .await?;
+        let partitions = self.partitions_of(collection_name).await?;
+        if !partitions.is_empty() {
+            let mut from_partitions = 0;
+            for partition in &partitions {
+                let found = self
+                    .qdrant_service
+                    .scroll_recent_payloads(partition, filter.clone(), limit)
+                    .await?;
+                from_partitions += found.len();
+                records.extend(found);
+                if from_partitions >= limit {
+                    break;
+                }
+            }
+            let timestamp = |payload: &Map<String, Value>| {
+                payload
+                    .get("timestamp")
+                    .and_then(Value::as_str)
+                    .and_then(qdrant::unix_seconds)
+            };
+            records.sort_by_key(|(_, payload)| std::cmp::Reverse(timestamp(payload)));
+            records.truncate(limit);
+        }
 
-        self.qdrant_service
-            .create_collection(collection_name, size)
-            .await
-            .map_err(ProcessingError::from)?;
-        self.qdrant_service
-            .ensure_payload_indexes(collection_name)
-            .await
-            .map_err(ProcessingError::from)?;
-        tracing::info!(
-            collection = collection_name,
-            vector_size = size,
-            "Collection created"
-        );
-        Ok(())
+        Ok(records
+            .into_iter()
+            .map(|(id, payload)| {
+                map_scored_point(qdrant::ScoredPoint {
+                    id,
+                    score: 0.0,
+                    payload: Some(payload),
+                })
+            })
+            .collect())
     }
 
-    /// Enumerate all collections currently known to Qdrant.
-    pub async fn list_collections(&self) -> Result<Vec<String>, ProcessingError> {
-        self.qdrant_service
-            .list_collections()
-            .await
-            .map_err(ProcessingError::from)
+    /// Vector size of the embeddings queries and writes of `collection_name` use, following a
+    /// promoted migration.
+    fn active_dimension(&self, collection_name: &str) -> usize {
+        match &self.migration {
+            Some(migration) if migration.is_promoted(collection_name) => migration.next().dimension,
+            _ => get_config().embedding_dimension,
+        }
     }
 
-    /// Enumerate distinct project identifiers observed in the target collection.
-    pub async fn list_projects(
+    /// Embed `texts` with the next provider and ensure its candidate collection while a provider
+    /// migration window is open.
+    ///
+    /// Failures are logged and skip the candidate write, so ingest never depends on the next
+    /// provider; `compare_providers` reports the resulting coverage gap.
+    async fn candidate_vectors(
         &self,
         collection_name: &str,
-    ) -> Result<BTreeSet<String>, ProcessingError> {
-        self.qdrant_service
-            .list_projects(collection_name)
+        texts: &[String],
+    ) -> Option<(String, Vec<Vec<f32>>)> {
+        let migration = self
+            .migration
+            .as_ref()
+            .filter(|migration| !migration.is_promoted(collection_name))?;
+        if texts.is_empty() {
+            return None;
+        }
+        let candidate = candidate_collection(collection_name);
+        let vectors = migration
+            .client()
+            .generate_embeddings(self.embedding_prefixes.documents(texts.to_vec()))
             .await
-            .map_err(ProcessingError::from)
-    }
-
-    /// Enumerate distinct tags observed in the target collection, optionally scoped by project.
-    pub async fn list_tags(
-        &self,
-        collection_name: &str,
-        project_id: Option<&str>,
-    ) -> Result<BTreeSet<String>, ProcessingError> {
-        self.qdrant_service
-            .list_tags(collection_name, project_id)
+            .and_then(|vectors| validate_embeddings(&vectors).map(|()| vectors));
+        let vectors = match vectors {
+            Ok(vectors) => vectors,
+            Err(error) => {
+                tracing::warn!(
+                    collection = %candidate,
+                    error = %error,
+                    "Next embedding provider failed; skipping the candidate write"
+                );
+                return None;
+            }
+        };
+        if let Err(error) = self
+            .ensure_collection_sized(&candidate, migration.next().dimension as u64)
             .await
-            .map_err(ProcessingError::from)
+        {
+            tracing::warn!(
+                collection = %candidate,
+                error = %error,
+                "Failed to ensure the candidate collection; skipping the candidate write"
+            );
+            return None;
+        }
+        Some((candidate, vectors))
     }
 
-    /// Return the current ingestion metrics snapshot.
-    pub fn metrics_snapshot(&self) -> MetricsSnapshot {
-        self.metrics.snapshot()
+    /// Candidate collection of `collection_name` while its provider migration window is open.
+    fn open_candidate(&self, collection_name: &str) -> Option<String> {
+        self.migration
+            .as_ref()
+            .filter(|migration| !migration.is_promoted(collection_name))
+            .map(|_| candidate_collection(collection_name))
     }
 
-    /// Probe Qdrant to surface a lightweight health snapshot for MCP resources.
-    pub async fn qdrant_health(&self) -> QdrantHealthSnapshot {
-        let config = get_config();
-        match self.qdrant_service.list_collections().await {
-            Ok(collections) => {
-                let default_present = collections
-                    .iter()
-                    .any(|name| name == &config.qdrant_collection_name);
-                QdrantHealthSnapshot {
-                    reachable: true,
-                    default_collection_present: default_present,
-                    error: None,
+    /// Repeat a write to `collection_name` on its candidate collection while the migration
+    /// window is open, so a promotion neither restores deleted memories nor loses payload edits.
+    ///
+    /// Best effort like the candidate write on push: failures are logged and never fail the
+    /// operation, and a candidate that does not exist yet has nothing to mirror.
+    async fn mirror_to_candidate<F, Fut>(&self, collection_name: &str, write: F)
+    where
+        F: FnOnce(String) -> Fut,
+        Fut: Future<Output = Result<(), qdrant::QdrantError>>,
+    {
+        let Some(candidate) = self.open_candidate(collection_name) else {
+            return;
+        };
+        match write(candidate.clone()).await {
+            Ok(()) => {}
+            Err(qdrant::QdrantError::UnexpectedStatus { status, .. })
+                if status == reqwest::StatusCode::NOT_FOUND => {}
+            Err(error) => tracing::warn!(
+                collection = %candidate,
+                error = %error,
+                "Failed to mirror a write to the candidate collection"
+            ),
+        }
+    }
+
+    /// Repeat a `move_to_collection` on the candidate collections of `source` and `target`.
+    ///
+    /// The moved points leave the source's candidate, so a promotion cannot bring them back, and
+    /// land in the target's candidate when it exists; otherwise `compare-providers` reports them
+    /// as a coverage gap.
+    async fn move_candidate_points(&self, source: &str, target: &str, filter: Option<Value>) {
+        self.mirror_to_candidate(source, |source_candidate| async move {
+            let points = self
+                .qdrant_service
+                .scroll_points_with_vectors(&source_candidate, filter)
+                .await?;
+            let target_candidate = match self.open_candidate(target) {
+                Some(candidate) if self.qdrant_service.collection_exists(&candidate).await? => {
+                    Some(candidate)
                 }
-            }
-            Err(error) => {
-                tracing::warn!(error = %error, "Qdrant health probe failed");
-                QdrantHealthSnapshot {
-                    reachable: false,
-                    default_collection_present: false,
-                    error: Some(error.to_string()),
+                _ => None,
+            };
+            for batch in points.chunks(MOVE_BATCH_SIZE) {
+                if let Some(target_candidate) = &target_candidate {
+                    self.qdrant_service
+                        .upsert_points(target_candidate, batch)
+                        .await?;
                 }
+                let ids: Vec<String> = batch.iter().map(|point| point.id.clone()).collect();
+                self.qdrant_service
+                    .delete_points(&source_candidate, &ids)
+                    .await?;
             }
-        }
+            Ok(())
+        })
+        .await;
     }
 
-    /// Summarize episodic (or chosen type) memories within a time range, persist a semantic summary, and return provenance.
-    pub(crate) async fn summarize_memories(
+    /// Delete `ids` from `collection_name` and its open candidate collection.
+    async fn delete_mirrored(
         &self,
-        request: SummarizeRequest,
-    ) -> Result<SummarizeOutcome, SummarizeError> {
-        let config = get_config();
-        let collection = request
-            .collection
-            .clone()
-            .unwrap_or_else(|| config.qdrant_collection_name.clone());
+        collection_name: &str,
+        ids: &[String],
+    ) -> Result<(), qdrant::QdrantError> {
+        self.qdrant_service
+            .delete_points(collection_name, ids)
+            .await?;
+        self.mirror_to_candidate(collection_name, |candidate| async move {
+            self.qdrant_service.delete_points(&candidate, ids).await
+        })
+        .await;
+        Ok(())
+    }
 
-        // Validate time range
-        if request.time_range.start.is_none() || request.time_range.end.is_none() {
-            return Err(SummarizeError::InvalidTimeRange);
+    /// Delete the points matching `filter` from `collection_name` and its open candidate
+    /// collection.
+    async fn delete_by_filter_mirrored(
+        &self,
+        collection_name: &str,
+        filter: Value,
+    ) -> Result<(), qdrant::QdrantError> {
+        self.qdrant_service
+            .delete_points_by_filter(collection_name, filter.clone())
+            .await?;
+        self.mirror_to_candidate(collection_name, |candidate| async move {
+            self.qdrant_service
+                .delete_points_by_filter(&candidate, filter)
+                .await
+        })
+        .await;
+        Ok(())
+    }
+
+    /// Merge payload `updates` into `collection_name` and its open candidate collection.
+    async fn set_payloads_mirrored(
+        &self,
+        collection_name: &str,
+        updates: Vec<(String, Map<String, Value>)>,
+    ) -> Result<(), qdrant::QdrantError> {
+        let mirrored = self
+            .open_candidate(collection_name)
+            .map(|_| updates.clone());
+        self.qdrant_service
+            .set_payloads(collection_name, updates)
+            .await?;
+        if let Some(updates) = mirrored {
+            self.mirror_to_candidate(collection_name, |candidate| async move {
+                self.qdrant_service.set_payloads(&candidate, updates).await
+            })
+            .await;
         }
+        Ok(())
+    }
 
-        // Build episodic filter
-        let filter_args = qdrant::SearchFilterArgs {
-            project_id: request.project_id.clone(),
-            memory_type: request
-                .memory_type
-                .clone()
-                .or_else(|| Some("episodic".into())),
-            tags: request.tags.clone(),
-            time_range: Some(qdrant::SearchTimeRange {
-                start: request.time_range.start.clone(),
-                end: request.time_range.end.clone(),
-            }),
+    /// Whether time ranges must target the integer `timestamp_unix` mirror because Qdrant
+    /// cannot evaluate datetime range filters.
+    fn unix_time_range(&self) -> bool {
+        !self.qdrant_service.capabilities().supports_datetime_index
+    }
+
+    /// Generate embeddings for `texts`, rejecting NaN, infinite, or all-zero vectors.
+    ///
+    /// A `collection_name` whose migration was promoted embeds with the next provider.
+    /// Rejections are counted against `collection_name` in the metrics snapshot.
+    async fn embed(
+        &self,
+        collection_name: &str,
+        texts: Vec<String>,
+    ) -> Result<Vec<Vec<f32>>, EmbeddingClientError> {
+        let client = match &self.migration {
+            Some(migration) if migration.is_promoted(collection_name) => migration.client(),
+            _ => self.embedding_client.as_ref(),
         };
-        let filter = qdrant::build_search_filter(&filter_args);
+        let result = client
+            .generate_embeddings(texts)
+            .await
+            .and_then(|vectors| validate_embeddings(&vectors).map(|()| vectors));
+        // The disk cache validates before storing, so its rejections are counted here too.
+        if let Err(error @ EmbeddingClientError::InvalidVector { .. }) = &result {
+            self.metrics.record_invalid_embedding(collection_name);
+            tracing::warn!(
+                collection = collection_name,
+                error = %error,
+                "Embedding provider returned an invalid vector"
+            );
+        }
+        result
+    }
 
-        // Scroll payloads (id + payload) and map into episodic items
-        let fields = serde_json::json!(["text", "timestamp"]);
-        let mut items = self
+    /// Embed chunk texts for ingestion, honouring the `INGEST_FALLBACK` policy.
+    ///
+    /// Only transient failures (provider unreachable, timed out, or overloaded) fall back; returns
+    /// `true` alongside the vectors when the deterministic encoder stood in for the provider.
+    async fn embed_for_ingest(
+        &self,
+        collection_name: &str,
+        texts: Vec<String>,
+    ) -> Result<(Vec<Vec<f32>>, bool), ProcessingError> {
+        let texts = self.embedding_prefixes.documents(texts);
+        if self.ingest_fallback == IngestFallback::None {
+            let embeddings = self.embed(collection_name, texts).await?;
+            return Ok((embeddings, false));
+        }
+        match self.embed(collection_name, texts.clone()).await {
+            Ok(embeddings) => Ok((embeddings, false)),
+            // Configuration mistakes and provider bugs must reach the caller instead of being
+            // papered over with vectors nobody will notice are degraded.
+            Err(error) if embedding_error_kind(&error) != ErrorKind::Transient => Err(error.into()),
+            Err(error) => {
+                tracing::warn!(
+                    error = %error,
+                    chunks = texts.len(),
+                    "Embedding provider failed; ingesting with deterministic fallback vectors"
+                );
+                let embeddings =
+                    AiLibClient::with_dimension(self.active_dimension(collection_name))
+                        .generate_embeddings(texts)
+                        .await?;
+                Ok((embeddings, true))
+            }
+        }
+    }
+
+    /// Re-embed points written by a degraded ingest and clear their fallback tag.
+    ///
+    /// Vectors are replaced before the tag is removed, so an interrupted run leaves the remaining
+    /// points tagged and a later call picks them up again.
+    pub async fn reembed_fallback(
+        &self,
+        collection_name: &str,
+    ) -> Result<ReembedOutcome, ProcessingError> {
+        let filter = qdrant::build_search_filter(&qdrant::SearchFilterArgs {
+            tags: Some(vec![FALLBACK_EMBEDDING_TAG.to_string()]),
+            ..Default::default()
+        });
+        let points = self
             .qdrant_service
-            .scroll_payloads_with_ids(&collection, fields, filter)
-            .await
-            .map_err(SummarizeError::Qdrant)?
-            .into_iter()
-            .filter_map(|(id, payload)| {
-                let text = payload
-                    .get("text")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("")
-                    .to_string();
-                let timestamp = payload
-                    .get("timestamp")
-                    .and_then(|v| v.as_str())
-                    .map(|s| s.to_string());
-                if text.trim().is_empty() {
-                    None
-                } else {
-                    Some(EpisodicMemory::new(id, text, timestamp))
-                }
-            })
-            .collect::<Vec<_>>();
+            .scroll_payloads_with_ids(collection_name, serde_json::json!(["text", "tags"]), filter)
+            .await?;
 
-        // Sort chronologically and cap by limit
-        sort_memories(&mut items);
-        let limit = request.limit.unwrap_or(50);
-        if items.len() > limit {
-            items.truncate(limit);
+        let mut outcome = ReembedOutcome::default();
+        let mut pending = Vec::new();
+        for (id, payload) in points {
+            let text = payload
+                .get("text")
+                .and_then(Value::as_str)
+                .filter(|text| !text.trim().is_empty());
+            match text {
+                Some(text) => {
+                    let tags: Vec<String> = extract_tags(&payload)
+                        .unwrap_or_default()
+                        .into_iter()
+                        .filter(|tag| tag != FALLBACK_EMBEDDING_TAG)
+                        .collect();
+                    pending.push((id, text.to_string(), tags));
+                }
+                None => outcome.skipped += 1,
+            }
         }
 
-        if items.is_empty() {
-            return Err(SummarizeError::EmptyResult);
+        for batch in pending.chunks(REEMBED_BATCH_SIZE) {
+            let texts = batch.iter().map(|(_, text, _)| text.clone()).collect();
+            let vectors = self
+                .embed(collection_name, self.embedding_prefixes.documents(texts))
+                .await?;
+            let updates = batch
+                .iter()
+                .map(|(id, _, _)| id.clone())
+                .zip(vectors)
+                .collect();
+            self.qdrant_service
+                .update_vectors(collection_name, updates)
+                .await?;
+            let payloads = batch
+                .iter()
+                .map(|(id, _, tags)| {
+                    let mut payload = Map::new();
+                    payload.insert("tags".into(), Value::from(tags.clone()));
+                    (id.clone(), payload)
+                })
+                .collect();
+            self.set_payloads_mirrored(collection_name, payloads)
+                .await?;
+            outcome.reembedded += batch.len();
+        }
+        self.listing_cache.invalidate(collection_name);
+        if outcome.reembedded > 0 {
+            self.audit.record(AuditRecord::new(
+                "reembed-fallback",
+                collection_name,
+                None,
+                AuditCounts {
+                    updated: outcome.reembedded,
+                    ..AuditCounts::default()
+                },
+            ));
         }
 
-        let source_memory_ids: Vec<String> = items.iter().map(|m| m.memory_id.clone()).collect();
-        let summary_key = compute_summary_key(
-            request.project_id.as_deref().unwrap_or("default"),
-            &ProcSearchTimeRange {
-                start: request.time_range.start.clone(),
-                end: request.time_range.end.clone(),
-            },
-            &source_memory_ids,
+        tracing::info!(
+            collection = collection_name,
+            reembedded = outcome.reembedded,
+            skipped = outcome.skipped,
+            "Fallback embeddings repaired"
         );
+        Ok(outcome)
+    }
 
-        // Idempotency: check for existing summary via tag summary:<hash>
-        let idempotency_tag = format!("summary:{summary_key}");
-        let existing_filter = qdrant::build_search_filter(&qdrant::SearchFilterArgs {
-            project_id: request.project_id.clone(),
-            memory_type: Some("semantic".into()),
-            tags: Some(vec![idempotency_tag.clone()]),
-            time_range: None,
-        });
-        let existing = self
+    /// Move the points matching `filter` from `source` into `target`, keeping ids and payloads.
+    ///
+    /// Both collections must already exist with the same vector size. Points are read from the
+    /// primary, never the read replica, so a lagging replica cannot hide recent writes. Each batch
+    /// is deleted from the source only after the target acknowledged the upsert, so an
+    /// interrupted move leaves the remaining points in place and can simply be re-run. Returns
+    /// the number moved.
+    pub(crate) async fn move_to_collection(
+        &self,
+        source: &str,
+        target: &str,
+        filter: qdrant::SearchFilterArgs,
+    ) -> Result<usize, MoveError> {
+        if source == target {
+            return Err(MoveError::SameCollection);
+        }
+        if filter.project_id.is_none() && filter.memory_type.is_none() && filter.tags.is_none() {
+            return Err(MoveError::MissingFilter);
+        }
+        let source_size = self
             .qdrant_service
-            .scroll_payloads_with_ids(&collection, serde_json::json!(["text"]), existing_filter)
-            .await
-            .map_err(SummarizeError::Qdrant)?;
-        if let Some((existing_id, payload)) = existing.into_iter().next() {
-            let summary_text = payload
-                .get("text")
-                .and_then(|v| v.as_str())
-                .unwrap_or("")
-                .to_string();
-            return Ok(SummarizeOutcome {
-                summary: summary_text,
-                source_memory_ids,
-                upserted_memory_id: existing_id,
-                strategy_used: strategy_to_label(&request.strategy),
-                provider: request.provider,
-                model: request.model,
+            .collection_schema(source)
+            .await?
+            .vector_size;
+        let target_size = self
+            .qdrant_service
+            .collection_schema(target)
+            .await?
+            .vector_size;
+        if source_size != target_size {
+            return Err(MoveError::DimensionMismatch {
+                source_size,
+                target_size,
             });
         }
 
-        // Choose summarization strategy
-        let mut chosen_strategy = request.strategy.clone().unwrap_or(SummarizeStrategy::Auto);
-        let mut provider_str = request.provider.clone();
-        let mut model_str = request.model.clone();
+        let points = self
+            .qdrant_service
+            .scroll_points_with_vectors(source, qdrant::build_search_filter(&filter))
+            .await?;
+        let mut moved = 0;
+        for batch in points.chunks(MOVE_BATCH_SIZE) {
+            self.qdrant_service.upsert_points(target, batch).await?;
+            let ids: Vec<String> = batch.iter().map(|point| point.id.clone()).collect();
+            self.qdrant_service.delete_points(source, &ids).await?;
+            moved += batch.len();
+        }
+        if moved > 0 {
+            self.move_candidate_points(source, target, qdrant::build_search_filter(&filter))
+                .await;
+        }
+        self.listing_cache.invalidate(source);
+        self.listing_cache.invalidate(target);
 
-        let mut summary_text = String::new();
-        if matches!(
-            chosen_strategy,
-            SummarizeStrategy::Auto | SummarizeStrategy::Abstractive
-        ) {
-            // Try abstractive path if provider active
-            if matches!(
-                config.summarization_provider,
-                crate::config::SummarizationProvider::Ollama
-            ) {
-                if model_str.is_none() {
-                    model_str = config.summarization_model.clone();
-                }
-                if provider_str.is_none() {
-                    provider_str = Some("ollama".into());
-                }
-                if let (Some(model), Some(client)) = (model_str.clone(), get_summarization_client())
-                {
-                    let prompt = build_abstractive_prompt(
-                        request.project_id.as_deref().unwrap_or("default"),
-                        &ProcSearchTimeRange {
-                            start: request.time_range.start.clone(),
-                            end: request.time_range.end.clone(),
-                        },
-                        request.max_words.unwrap_or(config.summarization_max_words),
-                        &items,
-                    );
-                    match client
-                        .generate_summary(LlmSummarizationRequest {
-                            model,
-                            prompt,
-                            max_words: request.max_words.unwrap_or(config.summarization_max_words),
-                        })
-                        .await
-                    {
-                        Ok(text) => {
-                            summary_text = text;
-                            chosen_strategy = SummarizeStrategy::Abstractive;
-                        }
-                        Err(error) => {
-                            tracing::warn!(error = %error, "Abstractive summarization failed; falling back to extractive");
-                        }
-                    }
-                }
-            }
+        if moved > 0 {
+            self.audit.record(AuditRecord::new(
+                "move-to-collection",
+                target,
+                filter.project_id.clone(),
+                AuditCounts {
+                    inserted: moved,
+                    ..AuditCounts::default()
+                },
+            ));
+            self.audit.record(AuditRecord::new(
+                "move-to-collection",
+                source,
+                filter.project_id,
+                AuditCounts {
+                    deleted: moved,
+                    ..AuditCounts::default()
+                },
+            ));
         }
+        tracing::info!(source, target, moved, "Memories moved between collections");
+        Ok(moved)
+    }
 
-        // Extractive fallback or selection
-        if summary_text.is_empty() {
-            summary_text = build_extractive_summary(
-                &items,
-                request.max_words.unwrap_or(config.summarization_max_words),
-            );
-            if matches!(chosen_strategy, SummarizeStrategy::Auto) {
-                chosen_strategy = SummarizeStrategy::Extractive;
+    /// Rename `source` to `target`: copy it into a new collection, then delete the original.
+    ///
+    /// Qdrant cannot rename in place. `target` must not exist; it is created with the source's
+    /// vector size, distance, topology, and payload indexes, and receives every point with its
+    /// id, vector, and payload one page of `RENAME_BATCH_SIZE` at a time, so memory stays
+    /// bounded. Points are read from the primary, and `source` is deleted only once both
+    /// collections report the same point count there; aliases of `source` are repointed at
+    /// `target` first. A failure or count mismatch before the delete leaves `source` untouched
+    /// next to a partial `target`, which must be deleted before retrying.
+    pub(crate) async fn rename_collection(
+        &self,
+        source: &str,
+        target: &str,
+    ) -> Result<RenameOutcome, MoveError> {
+        if source == target {
+            return Err(MoveError::SameCollection);
+        }
+        if self.qdrant_service.collection_exists(target).await? {
+            return Err(MoveError::TargetExists(target.to_string()));
+        }
+        let schema = self.qdrant_service.collection_schema(source).await?;
+        let Some(vector_size) = schema.vector_size else {
+            return Err(MoveError::UnknownVectorSize(source.to_string()));
+        };
+        self.qdrant_service
+            .create_collection_with_distance(
+                target,
+                vector_size,
+                schema.distance.as_deref().unwrap_or(DEFAULT_DISTANCE),
+                schema.topology.or(CollectionTopology::from_config()),
+            )
+            .await?;
+        for (field, kind) in &schema.payload_indexes {
+            self.qdrant_service
+                .create_payload_index(target, field, kind)
+                .await?;
+        }
+
+        let mut outcome = RenameOutcome::default();
+        let mut offset = None;
+        loop {
+            let (points, next) = self
+                .qdrant_service
+                .scroll_points_with_vectors_page(source, None, offset, RENAME_BATCH_SIZE)
+                .await?;
+            if !points.is_empty() {
+                self.qdrant_service.upsert_points(target, &points).await?;
+                outcome.copied += points.len();
+                outcome.batches += 1;
+                tracing::info!(
+                    source,
+                    target,
+                    copied = outcome.copied,
+                    batches = outcome.batches,
+                    "Copying collection"
+                );
+            }
+            match next {
+                Some(next) => offset = Some(next),
+                None => break,
             }
         }
 
-        // Embed and upsert the summary as semantic
-        let vectors = self
-            .embedding_client
-            .generate_embeddings(vec![summary_text.clone()])
-            .await
-            .map_err(SummarizeError::Embedding)?;
-        let vector = vectors.into_iter().next().ok_or_else(|| {
-            SummarizeError::Embedding(crate::embedding::EmbeddingClientError::Configuration(
-                "no embedding generated".into(),
-            ))
-        })?;
+        let source_points = self
+            .qdrant_service
+            .count_primary_points(source, None)
+            .await?;
+        let target_points = self
+            .qdrant_service
+            .count_primary_points(target, None)
+            .await?;
+        if source_points != target_points {
+            return Err(MoveError::CopyIncomplete {
+                source_points,
+                target_points,
+            });
+        }
 
-        let chunk_hash = qdrant::compute_chunk_hash(&summary_text);
-        let mut tags = request.tags.clone().unwrap_or_default();
-        tags.push("summary".into());
-        tags.push(format!("summary:{summary_key}"));
+        outcome.aliases = self
+            .qdrant_service
+            .list_aliases()
+            .await?
+            .into_iter()
+            .filter(|(_, collection)| collection == source)
+            .map(|(alias, _)| alias)
+            .collect();
+        if !outcome.aliases.is_empty() {
+            self.qdrant_service
+                .repoint_aliases(&outcome.aliases, target)
+                .await?;
+        }
+        self.qdrant_service.delete_collection(source).await?;
+        self.indexed_collections().remove(source);
+        self.indexing_statuses().remove(source);
+        self.listing_cache.clear();
 
-        let overrides = qdrant::types::PayloadOverrides {
-            project_id: request.project_id.clone(),
-            memory_type: Some("semantic".into()),
-            tags: Some(tags),
-            source_uri: None,
-            source_memory_ids: Some(source_memory_ids.clone()),
-            summary_key: Some(summary_key.clone()),
-        };
+        self.audit.record(AuditRecord::new(
+            "rename-collection",
+            target,
+            None,
+            AuditCounts {
+                inserted: outcome.copied,
+                ..AuditCounts::default()
+            },
+        ));
+        self.audit.record(AuditRecord::new(
+            "rename-collection",
+            source,
+            None,
+            AuditCounts {
+                deleted: outcome.copied,
+                ..AuditCounts::default()
+            },
+        ));
+        tracing::info!(
+            source,
+            target,
+            copied = outcome.copied,
+            aliases = ?outcome.aliases,
+            "Collection renamed"
+        );
+        Ok(outcome)
+    }
 
-        self.ensure_collection(&collection)
-            .await
-            .map_err(|e| match e {
-                ProcessingError::Qdrant(err) => SummarizeError::Qdrant(err),
-                ProcessingError::Embedding(err) => SummarizeError::Embedding(err),
-                ProcessingError::Chunking(err) => {
-                    SummarizeError::GenerationFailed(format!("chunking failed: {err}"))
+    /// Run queries against the active and the candidate vectors of a provider migration.
+    ///
+    /// `queries` default to the `sample` most recent searches of the window. Each query is
+    /// embedded with both providers and the top `k` hits of each collection are compared by
+    /// overlap@k; the raw scores of hits both returned feed one Pearson correlation.
+    pub(crate) async fn compare_providers(
+        &self,
+        collection_name: &str,
+        queries: Vec<String>,
+        sample: usize,
+        k: usize,
+    ) -> Result<ProviderComparison, MigrationError> {
+        let migration = self
+            .migration
+            .as_ref()
+            .ok_or(MigrationError::NotConfigured)?;
+        if migration.is_promoted(collection_name) {
+            return Err(MigrationError::Promoted);
+        }
+        let candidate = candidate_collection(collection_name);
+        if !self.qdrant_service.collection_exists(&candidate).await? {
+            return Err(MigrationError::MissingCandidate(candidate));
+        }
+        let queries = if queries.is_empty() {
+            migration.recent_queries(sample)
+        } else {
+            queries
+        };
+        if queries.is_empty() {
+            return Err(MigrationError::NoQueries);
+        }
+        let active_points = self
+            .qdrant_service
+            .count_points(collection_name, None)
+            .await?;
+        let candidate_points = self.qdrant_service.count_points(&candidate, None).await?;
+
+        let no_payload: &[String] = &[];
+        let mut comparisons = Vec::with_capacity(queries.len());
+        let mut score_pairs = Vec::new();
+        for query in queries {
+            let prefixed = self.embedding_prefixes.queries(vec![query.clone()]);
+            let active_vector = self
+                .embedding_client
+                .generate_embeddings(prefixed.clone())
+                .await
+                .and_then(|vectors| validate_embeddings(&vectors).map(|()| vectors))?;
+            let candidate_vector = migration
+                .client()
+                .generate_embeddings(prefixed)
+                .await
+                .and_then(|vectors| validate_embeddings(&vectors).map(|()| vectors))?;
+            let (Some(active_vector), Some(candidate_vector)) = (
+                active_vector.into_iter().next(),
+                candidate_vector.into_iter().next(),
+            ) else {
+                return Err(EmbeddingClientError::GenerationFailed(
+                    "embedding provider returned no vector for the query".into(),
+                )
+                .into());
+            };
+            let active_hits = self
+                .qdrant_service
+                .search_points(
+                    collection_name,
+                    PointQuery {
+                        vector: active_vector,
+                        limit: k,
+                        payload_fields: Some(no_payload),
+                        ..PointQuery::default()
+                    },
+                )
+                .await?;
+            let candidate_hits = self
+                .qdrant_service
+                .search_points(
+                    &candidate,
+                    PointQuery {
+                        vector: candidate_vector,
+                        limit: k,
+                        payload_fields: Some(no_payload),
+                        ..PointQuery::default()
+                    },
+                )
+                .await?;
+            let candidate_scores: HashMap<&str, f32> = candidate_hits
+                .iter()
+                .map(|hit| (hit.id.as_str(), hit.score))
+                .collect();
+            score_pairs.extend(active_hits.iter().filter_map(|hit| {
+                candidate_scores
+                    .get(hit.id.as_str())
+                    .map(|candidate_score| (hit.score, *candidate_score))
+            }));
+            let active_ids: Vec<String> = active_hits.into_iter().map(|hit| hit.id).collect();
+            let candidate_ids: Vec<String> = candidate_hits.into_iter().map(|hit| hit.id).collect();
+            comparisons.push(QueryComparison {
+                overlap: overlap_at_k(&active_ids, &candidate_ids, k),
+                query,
+                active_ids,
+                candidate_ids,
+            });
+        }
+
+        let mean_overlap = Some(
+            comparisons
+                .iter()
+                .map(|comparison| comparison.overlap)
+                .sum::<f32>()
+                / comparisons.len() as f32,
+        );
+        let comparison = ProviderComparison {
+            collection: collection_name.to_string(),
+            candidate_collection: candidate,
+            k,
+            active_points,
+            candidate_points,
+            mean_overlap,
+            score_correlation: score_correlation(&score_pairs),
+            shared_hits: score_pairs.len(),
+            queries: comparisons,
+        };
+        tracing::info!(
+            collection = collection_name,
+            queries = comparison.queries.len(),
+            k,
+            mean_overlap = ?comparison.mean_overlap,
+            score_correlation = ?comparison.score_correlation,
+            "Embedding providers compared"
+        );
+        Ok(comparison)
+    }
+
+    /// Serve `collection_name` from its candidate collection and embed with the next provider.
+    ///
+    /// An alias named `collection_name` is repointed atomically and its previous target is kept
+    /// for rollback. A concrete collection is deleted with `drop_previous` and an alias of the
+    /// same name created in its place; requests in between fail. Unless `force` is set, the
+    /// candidate must hold at least as many points as the active collection. Only
+    /// `collection_name` switches providers; other collections keep the active one until they
+    /// are promoted too. Promotion lasts for this process only: the `EMBEDDING_*` variables must
+    /// be updated before a restart.
+    pub(crate) async fn promote_provider(
+        &self,
+        collection_name: &str,
+        force: bool,
+        drop_previous: bool,
+    ) -> Result<PromotionOutcome, MigrationError> {
+        let migration = self
+            .migration
+            .as_ref()
+            .ok_or(MigrationError::NotConfigured)?;
+        let candidate = candidate_collection(collection_name);
+        let aliases = self.qdrant_service.list_aliases().await?;
+        let previous_target = aliases.get(collection_name).cloned();
+        if previous_target.as_deref() == Some(candidate.as_str()) {
+            return Err(MigrationError::AlreadyPromoted(collection_name.to_string()));
+        }
+        if !self.qdrant_service.collection_exists(&candidate).await? {
+            return Err(MigrationError::MissingCandidate(candidate));
+        }
+        let active_points = self
+            .qdrant_service
+            .count_points(collection_name, None)
+            .await?;
+        let candidate_points = self.qdrant_service.count_points(&candidate, None).await?;
+        if candidate_points < active_points && !force {
+            return Err(MigrationError::IncompleteCandidate {
+                candidate,
+                active_points,
+                candidate_points,
+            });
+        }
+
+        let dropped_previous = match &previous_target {
+            Some(_) => {
+                self.qdrant_service
+                    .repoint_aliases(&[collection_name.to_string()], &candidate)
+                    .await?;
+                false
+            }
+            None if drop_previous => {
+                self.qdrant_service
+                    .delete_collection(collection_name)
+                    .await?;
+                self.qdrant_service
+                    .create_alias(collection_name, &candidate)
+                    .await?;
+                true
+            }
+            None => {
+                return Err(MigrationError::ConcreteCollection(
+                    collection_name.to_string(),
+                ));
+            }
+        };
+        migration.mark_promoted(collection_name);
+        self.indexed_collections().remove(collection_name);
+        self.collection_distances().remove(collection_name);
+        self.indexing_statuses().remove(collection_name);
+        self.listing_cache.clear();
+
+        let next = migration.next();
+        let outcome = PromotionOutcome {
+            collection: collection_name.to_string(),
+            candidate_collection: candidate,
+            previous_target,
+            dropped_previous,
+            provider: migration::provider_name(next.provider).to_string(),
+            model: next.model.clone(),
+            dimension: next.dimension,
+        };
+        self.audit.record(AuditRecord::new(
+            "promote-provider",
+            collection_name,
+            None,
+            AuditCounts {
+                deleted: if dropped_previous {
+                    active_points as usize
+                } else {
+                    0
+                },
+                ..AuditCounts::default()
+            },
+        ));
+        tracing::info!(
+            collection = collection_name,
+            candidate = %outcome.candidate_collection,
+            previous = ?outcome.previous_target,
+            provider = %outcome.provider,
+            model = %outcome.model,
+            dimension = outcome.dimension,
+            "Candidate embedding provider promoted; update EMBEDDING_PROVIDER, EMBEDDING_MODEL and EMBEDDING_DIMENSION before restarting"
+        );
+        Ok(outcome)
+    }
+
+    /// Treat every collection whose alias already serves its candidate as promoted, so a restart
+    /// before the `EMBEDDING_*` variables were updated keeps embedding it with the provider its
+    /// stored vectors came from.
+    async fn detect_promotion(&self) {
+        let Some(migration) = &self.migration else {
+            return;
+        };
+        match self.qdrant_service.list_aliases().await {
+            Ok(aliases) => {
+                for (collection, target) in &aliases {
+                    if *target != candidate_collection(collection) {
+                        continue;
+                    }
+                    migration.mark_promoted(collection);
+                    tracing::warn!(
+                        collection = %collection,
+                        candidate = %target,
+                        "Collection already serves its candidate vectors; embedding it with EMBEDDING_PROVIDER_NEXT. Update EMBEDDING_PROVIDER, EMBEDDING_MODEL and EMBEDDING_DIMENSION"
+                    );
                 }
-            })?;
+            }
+            Err(error) => {
+                tracing::debug!(error = %error, "Could not read aliases to detect a promoted migration");
+            }
+        }
+    }
 
-        self.qdrant_service
-            .index_points(
-                &collection,
-                vec![PointInsert {
-                    text: summary_text.clone(),
-                    chunk_hash,
-                    vector,
-                }],
-                &overrides,
-            )
-            .await
-            .map_err(SummarizeError::Qdrant)?;
+    /// Check stored payloads for corruption, page by page.
+    ///
+    /// `sample_rate` in `(0, 1]` selects an evenly spaced share of the points to check. With
+    /// `fix_hashes`, points whose `chunk_hash` is wrong or missing get the hash of their `text`.
+    /// With `backfill_timestamps`, points written before `timestamp_unix` existed (or whose copy
+    /// disagrees with `timestamp`) get it parsed from their RFC3339 `timestamp`.
+    pub async fn verify_collection(
+        &self,
+        collection_name: &str,
+        sample_rate: f64,
+        fix_hashes: bool,
+        backfill_timestamps: bool,
+    ) -> Result<VerifyOutcome, ProcessingError> {
+        let sample_rate = sample_rate.clamp(f64::MIN_POSITIVE, 1.0);
+        let mut outcome = VerifyOutcome::default();
+        let mut sample_credit = 0.0;
+        let mut offset = None;
+        loop {
+            let (points, next) = self
+                .qdrant_service
+                .scroll_payload_page(collection_name, offset, VERIFY_PAGE_SIZE)
+                .await?;
+            let mut repairs = Vec::new();
+            for (id, payload) in points {
+                outcome.scanned += 1;
+                sample_credit += sample_rate;
+                if sample_credit < 1.0 {
+                    continue;
+                }
+                sample_credit -= 1.0;
+                outcome.checked += 1;
 
-        // Resolve ID of the inserted summary by scanning for the idempotency tag
-        let resolve = self
+                let check = inspect_payload(&payload);
+                if check.missing_field {
+                    outcome.missing_fields.record(&id);
+                }
+                if check.hash_mismatch {
+                    outcome.hash_mismatch.record(&id);
+                }
+                if check.invalid_timestamp {
+                    outcome.invalid_timestamp.record(&id);
+                }
+                let mut update = Map::new();
+                if let Some(hash) = check.repaired_hash.filter(|_| fix_hashes) {
+                    update.insert("chunk_hash".into(), Value::String(hash));
+                    outcome.hashes_fixed += 1;
+                }
+                if let Some(seconds) = check.unix_timestamp.filter(|_| backfill_timestamps) {
+                    update.insert(qdrant::TIMESTAMP_UNIX_FIELD.into(), Value::from(seconds));
+                    outcome.timestamps_backfilled += 1;
+                }
+                if !update.is_empty() {
+                    repairs.push((id, update));
+                }
+            }
+            self.set_payloads_mirrored(collection_name, repairs).await?;
+            match next {
+                Some(next) => offset = Some(next),
+                None => break,
+            }
+        }
+
+        let updated = outcome.hashes_fixed.max(outcome.timestamps_backfilled);
+        if updated > 0 {
+            self.audit.record(AuditRecord::new(
+                "verify-collection",
+                collection_name,
+                None,
+                AuditCounts {
+                    updated,
+                    ..AuditCounts::default()
+                },
+            ));
+        }
+        tracing::info!(
+            collection = collection_name,
+            scanned = outcome.scanned,
+            checked = outcome.checked,
+            hash_mismatch = outcome.hash_mismatch.count,
+            missing_fields = outcome.missing_fields.count,
+            invalid_timestamp = outcome.invalid_timestamp.count,
+            hashes_fixed = outcome.hashes_fixed,
+            timestamps_backfilled = outcome.timestamps_backfilled,
+            "Collection verified"
+        );
+        Ok(outcome)
+    }
+
+    /// Ensure that the target collection exists within Qdrant.
+    ///
+    /// Payload indexes are only re-ensured for collections this process has not fully indexed
+    /// yet, or that had to be created.
+    pub async fn ensure_collection(&self, collection_name: &str) -> Result<(), ProcessingError> {
+        self.ensure_collection_sized(
+            collection_name,
+            self.active_dimension(collection_name) as u64,
+        )
+        .await
+    }
+
+    /// Vector size Qdrant reports for `collection_name`, ensuring the collection first.
+    ///
+    /// Collections created with another size, or promoted by a provider migration, differ from
+    /// `EMBEDDING_DIMENSION`; named-vector layouts fall back to the active dimension.
+    pub async fn vector_dimension(&self, collection_name: &str) -> Result<usize, ProcessingError> {
+        self.ensure_collection(collection_name).await?;
+        let schema = self
             .qdrant_service
-            .scroll_payloads_with_ids(
-                &collection,
-                serde_json::json!(["text"]),
-                qdrant::build_search_filter(&qdrant::SearchFilterArgs {
-                    project_id: request.project_id.clone(),
-                    memory_type: Some("semantic".into()),
-                    tags: Some(vec![idempotency_tag.clone()]),
-                    time_range: None,
-                }),
-            )
+            .collection_schema(collection_name)
+            .await?;
+        Ok(schema.vector_size.map_or_else(
+            || self.active_dimension(collection_name),
+            |size| size as usize,
+        ))
+    }
+
+    /// [`Self::ensure_collection`] for vectors of `vector_size`.
+    async fn ensure_collection_sized(
+        &self,
+        collection_name: &str,
+        vector_size: u64,
+    ) -> Result<(), ProcessingError> {
+        let created = self
+            .qdrant_service
+            .create_collection_if_not_exists(collection_name, vector_size)
             .await
-            .map_err(SummarizeError::Qdrant)?;
-        let upserted_memory_id = resolve
-            .into_iter()
-            .map(|(id, _)| id)
-            .next()
-            .unwrap_or_default();
+            .map_err(ProcessingError::from)?;
+        if created || !self.indexed_collections().contains(collection_name) {
+            self.ensure_payload_indexes(collection_name).await?;
+        }
+        tracing::debug!(collection = collection_name, "Collection ensured");
+        Ok(())
+    }
 
-        Ok(SummarizeOutcome {
-            summary: summary_text,
-            source_memory_ids,
-            upserted_memory_id,
-            strategy_used: strategy_to_label(&Some(chosen_strategy)),
-            provider: provider_str,
-            model: model_str,
-        })
+    /// Ensure the standard payload indexes and remember collections where all of them exist.
+    ///
+    /// Indexes Qdrant refuses are only logged unless `STRICT_PAYLOAD_INDEXES` is enabled.
+    async fn ensure_payload_indexes(&self, collection_name: &str) -> Result<(), ProcessingError> {
+        let summary = self
+            .qdrant_service
+            .ensure_payload_indexes(collection_name)
+            .await?;
+        if check_payload_indexes(collection_name, summary, self.strict_payload_indexes)? {
+            self.indexed_collections()
+                .insert(collection_name.to_string());
+        }
+        Ok(())
     }
-}
 
-#[async_trait]
-impl ProcessingApi for ProcessingService {
-    async fn process_and_index(
+    /// Distance metric of `collection_name`, cached after the first lookup.
+    async fn collection_distance(
         &self,
         collection_name: &str,
-        text: String,
-        metadata: IngestMetadata,
-    ) -> Result<ProcessingOutcome, ProcessingError> {
-        ProcessingService::process_and_index(self, collection_name, text, metadata).await
+    ) -> Result<Option<String>, qdrant::QdrantError> {
+        if let Some(distance) = self.collection_distances().get(collection_name) {
+            return Ok(Some(distance.clone()));
+        }
+        let distance = self
+            .qdrant_service
+            .collection_schema(collection_name)
+            .await?
+            .distance;
+        if let Some(distance) = &distance {
+            self.collection_distances()
+                .insert(collection_name.to_string(), distance.clone());
+        }
+        Ok(distance)
     }
 
-    async fn create_collection(
+    fn collection_distances(&self) -> MutexGuard<'_, HashMap<String, String>> {
+        self.collection_distances
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn indexing_statuses(&self) -> MutexGuard<'_, HashMap<String, (Instant, CollectionInfo)>> {
+        self.indexing_statuses
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn indexed_collections(&self) -> MutexGuard<'_, HashSet<String>> {
+        self.indexed_collections
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Create or resize a collection with the desired vector size.
+    ///
+    /// Topology fields left unset fall back to `QDRANT_SHARD_NUMBER` / `QDRANT_REPLICATION_FACTOR`.
+    pub async fn create_collection(
         &self,
         collection_name: &str,
         vector_size: Option<u64>,
+        topology: CollectionTopology,
     ) -> Result<(), ProcessingError> {
-        ProcessingService::create_collection(self, collection_name, vector_size).await
+        let size = vector_size.unwrap_or_else(|| self.active_dimension(collection_name) as u64);
+
+        self.qdrant_service
+            .create_collection(
+                collection_name,
+                size,
+                topology.or(CollectionTopology::from_config()),
+            )
+            .await
+            .map_err(ProcessingError::from)?;
+        self.ensure_payload_indexes(collection_name).await?;
+        self.audit.record(AuditRecord::new(
+            "new-collection",
+            collection_name,
+            None,
+            AuditCounts::default(),
+        ));
+        tracing::info!(
+            collection = collection_name,
+            vector_size = size,
+            "Collection created"
+        );
+        Ok(())
     }
 
-    async fn list_collections(&self) -> Result<Vec<String>, ProcessingError> {
-        ProcessingService::list_collections(self).await
+    /// Apply a collection manifest, creating missing collections and payload indexes.
+    ///
+    /// Entries are applied in order. An existing collection whose vector size, distance,
+    /// topology, or index types differ from its entry is reported as a conflict and left as is.
+    pub async fn apply_manifest(
+        &self,
+        manifest: &CollectionManifest,
+    ) -> Result<Vec<ManifestResult>, ProcessingError> {
+        let default_size = get_config().embedding_dimension as u64;
+        let mut results = Vec::with_capacity(manifest.collections.len());
+        for entry in &manifest.collections {
+            let name = entry.name.as_str();
+            let action = if self.qdrant_service.collection_exists(name).await? {
+                let schema = self.qdrant_service.collection_schema(name).await?;
+                match entry.diff(&schema, default_size) {
+                    Err(reasons) => {
+                        tracing::warn!(
+                            collection = name,
+                            conflicts = ?reasons,
+                            "Collection differs from its manifest entry; leaving it unchanged"
+                        );
+                        ManifestAction::Conflict { reasons }
+                    }
+                    Ok(missing) if missing.is_empty() => ManifestAction::Unchanged,
+                    Ok(missing) => {
+                        for (field, schema) in &missing {
+                            self.qdrant_service
+                                .create_payload_index(name, field, schema)
+                                .await?;
+                        }
+                        ManifestAction::Updated {
+                            indexes_added: missing.into_iter().map(|(field, _)| field).collect(),
+                        }
+                    }
+                }
+            } else {
+                self.qdrant_service
+                    .create_collection_with_distance(
+                        name,
+                        entry.target_vector_size(default_size),
+                        entry.target_distance(),
+                        entry.topology().or(CollectionTopology::from_config()),
+                    )
+                    .await?;
+                self.ensure_payload_indexes(name).await?;
+                for (field, schema) in &entry.payload_indexes {
+                    self.qdrant_service
+                        .create_payload_index(name, field, schema)
+                        .await?;
+                }
+                ManifestAction::Created
+            };
+
+            if matches!(
+                action,
+                ManifestAction::Created | ManifestAction::Updated { .. }
+            ) {
+                self.audit.record(AuditRecord::new(
+                    "apply-manifest",
+                    name,
+                    None,
+                    AuditCounts::default(),
+                ));
+            }
+            tracing::info!(
+                collection = name,
+                action = action.label(),
+                "Manifest entry applied"
+            );
+            results.push(ManifestResult {
+                collection: entry.name.clone(),
+                action,
+            });
+        }
+        Ok(results)
     }
 
-    fn metrics_snapshot(&self) -> MetricsSnapshot {
-        ProcessingService::metrics_snapshot(self)
+    /// Enumerate all collections currently known to Qdrant.
+    pub async fn list_collections(&self) -> Result<Vec<String>, ProcessingError> {
+        self.qdrant_service
+            .list_collections()
+            .await
+            .map_err(ProcessingError::from)
     }
-}
 
-/// Strategy selection for summarization.
-#[derive(Clone, Debug)]
-pub(crate) enum SummarizeStrategy {
-    /// Choose abstractive when provider available, else extractive.
-    Auto,
-    /// Use local LLM via provider.
-    Abstractive,
-    /// Deterministic bullet extraction.
-    Extractive,
-}
+    /// Existing monthly partitions of `collection_name`, newest first.
+    ///
+    /// Empty unless `EPISODIC_PARTITIONING=monthly`, in which case the collection list is read.
+    pub async fn partitions_of(
+        &self,
+        collection_name: &str,
+    ) -> Result<Vec<String>, qdrant::QdrantError> {
+        if self.episodic_partitioning != EpisodicPartitioning::Monthly {
+            return Ok(Vec::new());
+        }
+        let existing = self.qdrant_service.list_collections().await?;
+        Ok(existing_partitions(collection_name, &existing))
+    }
 
-/// Input parameters for summarization.
-#[derive(Clone, Debug)]
-pub(crate) struct SummarizeRequest {
-    pub project_id: Option<String>,
-    pub memory_type: Option<String>,
-    pub tags: Option<Vec<String>>,
-    pub time_range: ProcSearchTimeRange,
-    pub limit: Option<usize>,
-    pub strategy: Option<SummarizeStrategy>,
-    pub provider: Option<String>,
-    pub model: Option<String>,
-    pub max_words: Option<usize>,
-    pub collection: Option<String>,
-}
+    /// `collection_name` followed by every existing partition of it.
+    async fn with_partitions(
+        &self,
+        collection_name: &str,
+    ) -> Result<Vec<String>, qdrant::QdrantError> {
+        let mut collections = vec![collection_name.to_string()];
+        collections.extend(self.partitions_of(collection_name).await?);
+        Ok(collections)
+    }
 
-/// Errors surfaced from the summarization pipeline.
-#[derive(Debug, thiserror::Error)]
-pub(crate) enum SummarizeError {
-    #[error("Failed to generate summary: {0}")]
-    GenerationFailed(String),
-    #[error("No episodic memories found for the requested scope")]
-    EmptyResult,
-    #[error("`time_range` must include both `start` and `end`")]
-    InvalidTimeRange,
-    #[error(transparent)]
-    Embedding(#[from] crate::embedding::EmbeddingClientError),
-    #[error(transparent)]
-    Qdrant(#[from] crate::qdrant::types::QdrantError),
-}
+    /// Scroll the points matching `filter` from `collection_name` and each of its partitions,
+    /// pairing every point with the collection that stores it.
+    async fn scroll_with_partitions(
+        &self,
+        collection_name: &str,
+        with_payload: Value,
+        filter: Value,
+    ) -> Result<Vec<(String, String, Map<String, Value>)>, qdrant::QdrantError> {
+        let mut points = Vec::new();
+        for collection in self.with_partitions(collection_name).await? {
+            let found = self
+                .qdrant_service
+                .scroll_payloads_with_ids(&collection, with_payload.clone(), Some(filter.clone()))
+                .await?;
+            points.extend(
+                found
+                    .into_iter()
+                    .map(|(id, payload)| (collection.clone(), id, payload)),
+            );
+        }
+        Ok(points)
+    }
 
-/// Result of a summarization request.
-#[derive(Clone, Debug)]
-pub(crate) struct SummarizeOutcome {
-    pub summary: String,
-    pub source_memory_ids: Vec<String>,
-    pub upserted_memory_id: String,
-    pub strategy_used: String,
-    pub provider: Option<String>,
-    pub model: Option<String>,
-}
+    /// Collection a push of `metadata` into `collection_name` lands in.
+    ///
+    /// Under `EPISODIC_PARTITIONING=monthly`, a memory whose `memory_type` is (or is inferred by
+    /// `MEMORY_TYPE_RULES` as) episodic goes to the `<collection>-YYYY-MM` partition of its
+    /// `timestamp`, else of the current month. Everything else stays in `collection_name`.
+    pub fn ingest_collection(&self, collection_name: &str, metadata: &IngestMetadata) -> String {
+        if self.episodic_partitioning != EpisodicPartitioning::Monthly {
+            return collection_name.to_string();
+        }
+        let memory_type = sanitize_memory_type(metadata.memory_type.clone()).or_else(|| {
+            infer_memory_type(
+                &self.memory_type_rules,
+                metadata.tags.as_deref(),
+                metadata.source_uri.as_deref(),
+            )
+            .map(|rule| rule.memory_type.clone())
+        });
+        if memory_type.as_deref() == Some(PARTITIONED_MEMORY_TYPE) {
+            push_partition(
+                collection_name,
+                metadata.timestamp.as_deref(),
+                time::OffsetDateTime::now_utc(),
+            )
+        } else {
+            collection_name.to_string()
+        }
+    }
+
+    /// Collections a read of `collection_name` covers, with the partitions among them when
+    /// `EPISODIC_PARTITIONING` applies.
+    ///
+    /// Reads filtered on episodic memories cover `collection_name` and the partitions overlapping
+    /// `time_range`: imports, pinned collections, and per-chunk `auto_classify` types still store
+    /// episodic memories in the base collection. Reads of any other type are not partitioned.
+    pub async fn read_targets(
+        &self,
+        collection_name: &str,
+        memory_type: Option<&str>,
+        time_range: Option<&ProcSearchTimeRange>,
+    ) -> Result<(Vec<String>, Option<Vec<String>>), qdrant::QdrantError> {
+        let partitioned = self.episodic_partitioning == EpisodicPartitioning::Monthly
+            && matches!(memory_type, None | Some(PARTITIONED_MEMORY_TYPE));
+        if !partitioned {
+            return Ok((vec![collection_name.to_string()], None));
+        }
+        let existing = self.qdrant_service.list_collections().await?;
+        let partitions = search_partitions(
+            collection_name,
+            time_range,
+            time::OffsetDateTime::now_utc(),
+            &existing,
+        );
+        let mut targets = Vec::with_capacity(partitions.len() + 1);
+        targets.push(collection_name.to_string());
+        targets.extend(partitions.iter().cloned());
+        Ok((targets, Some(partitions)))
+    }
+
+    /// Resolve `name` to the concrete collection Qdrant serves it from.
+    ///
+    /// Alias names map to their current target; plain collection names, and any failure to
+    /// read the alias table, resolve to `name` itself.
+    pub async fn resolve_collection(&self, name: &str) -> String {
+        match self.qdrant_service.list_aliases().await {
+            Ok(aliases) => aliases
+                .get(name)
+                .cloned()
+                .unwrap_or_else(|| name.to_string()),
+            Err(error) => {
+                tracing::debug!(collection = name, error = %error, "Could not resolve collection aliases");
+                name.to_string()
+            }
+        }
+    }
+
+    /// Enumerate distinct project identifiers observed in the target collection.
+    ///
+    /// Served from the listing cache while fresh; otherwise the collection is scrolled.
+    pub async fn list_projects(
+        &self,
+        collection_name: &str,
+    ) -> Result<BTreeSet<String>, ProcessingError> {
+        if let Some(projects) = self.listing_cache.projects(collection_name) {
+            return Ok(projects);
+        }
+        let projects = self.qdrant_service.list_projects(collection_name).await?;
+        self.listing_cache
+            .store_projects(collection_name, &projects);
+        Ok(projects)
+    }
+
+    /// Enumerate distinct tags observed in the target collection, optionally scoped by project.
+    ///
+    /// Served from the listing cache while fresh; otherwise the collection is scrolled.
+    pub async fn list_tags(
+        &self,
+        collection_name: &str,
+        project_id: Option<&str>,
+    ) -> Result<BTreeSet<String>, ProcessingError> {
+        if let Some(tags) = self.listing_cache.tags(collection_name, project_id) {
+            return Ok(tags);
+        }
+        let tags = self
+            .qdrant_service
+            .list_tags(collection_name, project_id)
+            .await?;
+        self.listing_cache
+            .store_tags(collection_name, project_id, &tags);
+        Ok(tags)
+    }
+
+    /// Count tag occurrences per memory type, optionally scoped by project and memory type.
+    pub async fn list_tag_counts(
+        &self,
+        collection_name: &str,
+        project_id: Option<&str>,
+        memory_type: Option<&str>,
+    ) -> Result<TagCounts, ProcessingError> {
+        self.qdrant_service
+            .list_tag_counts(collection_name, project_id, memory_type)
+            .await
+            .map_err(ProcessingError::from)
+    }
+
+    /// Fetch status and indexing progress for the target collection.
+    pub async fn collection_info(
+        &self,
+        collection_name: &str,
+    ) -> Result<CollectionInfo, ProcessingError> {
+        self.qdrant_service
+            .collection_info(collection_name)
+            .await
+            .map_err(ProcessingError::from)
+    }
+
+    /// [`Self::collection_info`], reused for `INDEXING_STATUS_TTL` so pushes do not each issue
+    /// a status request.
+    pub async fn indexing_status(
+        &self,
+        collection_name: &str,
+    ) -> Result<CollectionInfo, ProcessingError> {
+        if let Some((fetched_at, info)) = self.indexing_statuses().get(collection_name)
+            && fetched_at.elapsed() < INDEXING_STATUS_TTL
+        {
+            return Ok(info.clone());
+        }
+        let info = self.collection_info(collection_name).await?;
+        self.indexing_statuses()
+            .insert(collection_name.to_string(), (Instant::now(), info.clone()));
+        Ok(info)
+    }
+
+    /// Describe a collection's vectors, payload indexes, and the payload keys found in a sample.
+    ///
+    /// When Qdrant reports no payload schema, the indexes this server creates on startup are
+    /// listed instead. A second, larger sample of `chunk_hash` values estimates how many stored
+    /// chunks are duplicates.
+    pub async fn describe_collection(
+        &self,
+        collection_name: &str,
+    ) -> Result<CollectionProfile, ProcessingError> {
+        let schema = self
+            .qdrant_service
+            .collection_schema(collection_name)
+            .await?;
+        let payloads = self
+            .qdrant_service
+            .sample_payloads(collection_name, DESCRIBE_SAMPLE_SIZE)
+            .await?;
+        let hashes = self
+            .qdrant_service
+            .sample_payload_fields(
+                collection_name,
+                DEDUPE_SCAN_SAMPLE_SIZE,
+                json!(["chunk_hash"]),
+            )
+            .await?;
+        let dedupe_stats = DedupeStats::from_hashes(
+            hashes
+                .iter()
+                .filter_map(|payload| payload.get("chunk_hash").and_then(Value::as_str)),
+        );
+
+        let mut payload_keys = BTreeMap::new();
+        for payload in &payloads {
+            for key in payload.keys() {
+                *payload_keys.entry(key.clone()).or_insert(0) += 1;
+            }
+        }
+        let indexes = if schema.payload_indexes.is_empty() {
+            PAYLOAD_INDEX_FIELDS
+                .iter()
+                .map(|(field, kind)| (field.to_string(), kind.to_string()))
+                .collect()
+        } else {
+            schema.payload_indexes
+        };
+
+        Ok(CollectionProfile {
+            info: schema.info,
+            vector_size: schema.vector_size,
+            distance: schema.distance,
+            indexes,
+            sampled_points: payloads.len(),
+            payload_keys,
+            dedupe_stats,
+        })
+    }
+
+    /// Reassemble a pushed document from its chunks, in `chunk_index` order.
+    ///
+    /// Returns `None` when no chunk carries a matching `doc_id` (documents pushed before chunk
+    /// ordering was recorded have none). Overlap is stripped only when the document's ingest
+    /// profile shows a non-zero chunk overlap. Monthly partitions are read alongside
+    /// `collection_name`, since episodic documents are stored there.
+    pub async fn get_document(
+        &self,
+        collection_name: &str,
+        selector: DocumentSelector,
+    ) -> Result<Option<ReconstructedDocument>, ProcessingError> {
+        let match_key = |key: &str, value: &str| json!({ "key": key, "match": { "value": value } });
+        let filter = match &selector {
+            DocumentSelector::DocId(doc_id) => json!({ "must": [match_key("doc_id", doc_id)] }),
+            DocumentSelector::LatestForSource {
+                source_uri,
+                project_id,
+            } => {
+                let mut must = vec![match_key("source_uri", source_uri)];
+                if let Some(project_id) = project_id {
+                    must.push(match_key("project_id", project_id));
+                }
+                json!({ "must": must })
+            }
+        };
+        let mut points: Vec<(String, Map<String, Value>)> = self
+            .scroll_with_partitions(collection_name, json!(true), filter)
+            .await?
+            .into_iter()
+            .map(|(_, id, payload)| (id, payload))
+            .collect();
+        points.retain(|(_, payload)| payload.get("doc_id").is_some_and(Value::is_string));
+
+        if matches!(selector, DocumentSelector::LatestForSource { .. }) {
+            let latest = points
+                .iter()
+                .max_by_key(|(_, payload)| {
+                    payload
+                        .get("ingested_at")
+                        .and_then(Value::as_str)
+                        .and_then(|raw| parse_time_boundary(raw, TimeBoundary::Start))
+                })
+                .and_then(|(_, payload)| payload.get("doc_id").cloned());
+            points.retain(|(_, payload)| payload.get("doc_id") == latest.as_ref());
+        }
+        let Some((_, first)) = points.first() else {
+            return Ok(None);
+        };
+        let field = |key: &str| first.get(key).and_then(Value::as_str).map(str::to_string);
+        let doc_id = field("doc_id").unwrap_or_default();
+        let source_uri = field("source_uri");
+        let project_id = field("project_id");
+        let ingested_at = field("ingested_at");
+        let overlapped = field("ingest_profile")
+            .as_deref()
+            .and_then(profile_overlap)
+            .is_some_and(|overlap| overlap > 0);
+
+        let mut ordered: Vec<(u64, String, String)> = points
+            .into_iter()
+            .map(|(id, mut payload)| {
+                let index = payload
+                    .get("chunk_index")
+                    .and_then(Value::as_u64)
+                    .unwrap_or(u64::MAX);
+                let text = match payload.remove("text") {
+                    Some(Value::String(text)) => text,
+                    _ => String::new(),
+                };
+                (index, id, text)
+            })
+            .collect();
+        ordered.sort_by(|left, right| (left.0, &left.1).cmp(&(right.0, &right.1)));
+
+        let texts: Vec<&str> = ordered.iter().map(|(_, _, text)| text.as_str()).collect();
+        let (text, stripped) = stitch_chunks(&texts, overlapped);
+        let present: BTreeSet<u64> = ordered.iter().map(|(index, _, _)| *index).collect();
+        let last = present
+            .iter()
+            .copied()
+            .filter(|index| *index != u64::MAX)
+            .max();
+        let missing_chunk_indexes = last
+            .map(|last| (0..last).filter(|index| !present.contains(index)).collect())
+            .unwrap_or_default();
+        let chunks = ordered
+            .into_iter()
+            .zip(stripped)
+            .map(|((chunk_index, id, _), overlap_stripped)| DocumentChunk {
+                id,
+                chunk_index,
+                overlap_stripped,
+            })
+            .collect();
+
+        Ok(Some(ReconstructedDocument {
+            doc_id,
+            source_uri,
+            project_id,
+            ingested_at,
+            text,
+            chunks,
+            missing_chunk_indexes,
+        }))
+    }
+
+    /// Record `rel` from `source_memory_id` to `target_memory_id`.
+    ///
+    /// Both memories must exist in the collection or its monthly partitions. The link is appended
+    /// to the source's `links` and each end's `linked_to` gains the other's id, each written in the
+    /// collection that stores that memory. Repeating a link writes only `linked_to` entries that
+    /// are missing, so an interrupted earlier attempt is completed.
+    pub async fn link_memories(
+        &self,
+        collection_name: &str,
+        source_memory_id: &str,
+        target_memory_id: &str,
+        rel: LinkRelation,
+    ) -> Result<LinkOutcome, ProcessingError> {
+        let filter = json!({ "must": [{
+            "key": "memory_id",
+            "match": { "any": [source_memory_id, target_memory_id] }
+        }] });
+        let points = self
+            .scroll_with_partitions(
+                collection_name,
+                json!(["memory_id", "links", "linked_to"]),
+                filter,
+            )
+            .await?;
+        let find = |memory_id: &str| {
+            points.iter().find(|(_, _, payload)| {
+                payload.get("memory_id").and_then(Value::as_str) == Some(memory_id)
+            })
+        };
+        let (
+            Some((source_collection, source_id, source)),
+            Some((target_collection, target_id, target)),
+        ) = (find(source_memory_id), find(target_memory_id))
+        else {
+            let missing = [source_memory_id, target_memory_id]
+                .into_iter()
+                .filter(|memory_id| find(memory_id).is_none())
+                .map(str::to_string)
+                .collect();
+            return Ok(LinkOutcome::MissingMemories(missing));
+        };
+
+        let mut links = stored_links(source.get("links"));
+        let created = add_link(
+            &mut links,
+            MemoryLink {
+                rel,
+                target_memory_id: target_memory_id.to_string(),
+            },
+        );
+        let mut source_linked = linked_ids(source);
+        let mut target_linked = linked_ids(target);
+        let mut updates: BTreeMap<&str, Vec<_>> = BTreeMap::new();
+        if add_linked_id(&mut source_linked, target_memory_id) || created {
+            let mut payload = Map::new();
+            payload.insert("links".into(), json!(links));
+            payload.insert("linked_to".into(), json!(source_linked));
+            updates
+                .entry(source_collection)
+                .or_default()
+                .push((source_id.clone(), payload));
+        }
+        if add_linked_id(&mut target_linked, source_memory_id) {
+            let mut payload = Map::new();
+            payload.insert("linked_to".into(), json!(target_linked));
+            updates
+                .entry(target_collection)
+                .or_default()
+                .push((target_id.clone(), payload));
+        }
+        for (collection, updates) in updates {
+            self.set_payloads_mirrored(collection, updates).await?;
+        }
+
+        Ok(if created {
+            LinkOutcome::Created
+        } else {
+            LinkOutcome::AlreadyLinked
+        })
+    }
+
+    /// Outbound and inbound links of `memory_id`, or `None` when no point carries it.
+    ///
+    /// One scroll per collection (monthly partitions included) fetches the memory itself and every
+    /// point whose `linked_to` names it; inbound links are the latter's `links` entries that
+    /// target `memory_id`.
+    pub async fn memory_links(
+        &self,
+        collection_name: &str,
+        memory_id: &str,
+    ) -> Result<Option<MemoryLinks>, ProcessingError> {
+        let filter = json!({ "should": [
+            { "key": "memory_id", "match": { "value": memory_id } },
+            { "key": "linked_to", "match": { "value": memory_id } }
+        ] });
+        let points = self
+            .scroll_with_partitions(collection_name, json!(["memory_id", "links"]), filter)
+            .await?;
+
+        let mut outbound = None;
+        let mut inbound = Vec::new();
+        for (_, _, payload) in &points {
+            let links = stored_links(payload.get("links"));
+            match payload.get("memory_id").and_then(Value::as_str) {
+                Some(id) if id == memory_id => outbound = Some(links),
+                Some(id) => inbound.extend(
+                    links
+                        .into_iter()
+                        .filter(|link| link.target_memory_id == memory_id)
+                        .map(|link| InboundLink {
+                            rel: link.rel,
+                            source_memory_id: id.to_string(),
+                        }),
+                ),
+                None => {}
+            }
+        }
+        Ok(outbound.map(|outbound| MemoryLinks { outbound, inbound }))
+    }
+
+    /// Estimate a collection's storage from its point count, vector width, and a payload sample.
+    pub async fn estimate_storage(
+        &self,
+        collection_name: &str,
+    ) -> Result<StorageEstimate, ProcessingError> {
+        let schema = self
+            .qdrant_service
+            .collection_schema(collection_name)
+            .await?;
+        let payloads = self
+            .qdrant_service
+            .sample_payloads(collection_name, DESCRIBE_SAMPLE_SIZE)
+            .await?;
+        let payload_sizes: Vec<usize> = payloads
+            .iter()
+            .map(|payload| serde_json::to_vec(payload).map_or(0, |bytes| bytes.len()))
+            .collect();
+
+        Ok(StorageEstimate::compute(
+            schema.info.points_count.unwrap_or(0),
+            schema.vector_size.unwrap_or(0),
+            schema.vector_datatype.as_deref(),
+            &payload_sizes,
+        ))
+    }
+
+    /// Approximate disk usage of `collection` (every collection when `None`), optionally
+    /// restricted to the points of one project.
+    ///
+    /// Each collection costs a schema read, one small scroll page of payloads, and, with a
+    /// project, an exact count of its points.
+    pub async fn storage_stats(
+        &self,
+        collection: Option<&str>,
+        project_id: Option<&str>,
+    ) -> Result<StorageStatsReport, ProcessingError> {
+        let collections = match collection {
+            Some(name) => vec![name.to_string()],
+            None => self.list_collections().await?,
+        };
+        let filter = project_id.and_then(|project_id| {
+            qdrant::build_search_filter(&qdrant::SearchFilterArgs {
+                project_id: Some(project_id.to_string()),
+                ..Default::default()
+            })
+        });
+
+        let mut stats = Vec::with_capacity(collections.len());
+        for name in collections {
+            let schema = self.qdrant_service.collection_schema(&name).await?;
+            let points_count = match &filter {
+                Some(filter) => {
+                    self.qdrant_service
+                        .count_points(&name, Some(filter.clone()))
+                        .await?
+                }
+                None => schema.info.points_count.unwrap_or(0),
+            };
+            let payload_sizes: Vec<usize> = self
+                .qdrant_service
+                .sample_filtered_payloads(&name, DESCRIBE_SAMPLE_SIZE, filter.clone())
+                .await?
+                .iter()
+                .map(|payload| serde_json::to_vec(payload).map_or(0, |bytes| bytes.len()))
+                .collect();
+            let estimate = StorageEstimate::compute(
+                points_count,
+                schema.vector_size.unwrap_or(0),
+                schema.vector_datatype.as_deref(),
+                &payload_sizes,
+            );
+            stats.push(StorageStats::from_estimate(
+                name,
+                project_id.map(str::to_string),
+                &estimate,
+                &schema.info,
+            ));
+        }
+
+        Ok(StorageStatsReport::new(
+            project_id.map(str::to_string),
+            stats,
+        ))
+    }
+
+    /// Delete `memory_type` memories (optionally of one project) stamped before `cutoff`.
+    ///
+    /// The matching points are counted first; a `dry_run` stops there. Otherwise a single
+    /// delete-by-filter removes them, and the count taken just before is reported as deleted.
+    /// Episodic pruning also covers every monthly partition of the collection. Without a
+    /// datetime index the cutoff bounds `timestamp_unix`; points missing that mirror are scrolled
+    /// and their `timestamp` compared here, then deleted by id.
+    pub async fn prune_memories(
+        &self,
+        collection_name: &str,
+        project_id: Option<String>,
+        memory_type: &str,
+        cutoff: time::OffsetDateTime,
+        dry_run: bool,
+    ) -> Result<PruneOutcome, ProcessingError> {
+        let cutoff_instant = cutoff;
+        let cutoff = format_utc_rfc3339(cutoff);
+        let unix_time_range = self.unix_time_range();
+        let filter = qdrant::build_prune_filter(
+            project_id.as_deref(),
+            memory_type,
+            &cutoff,
+            unix_time_range,
+        );
+        let unmirrored = unix_time_range
+            .then(|| qdrant::build_unmirrored_prune_filter(project_id.as_deref(), memory_type));
+        let collections = if memory_type == PARTITIONED_MEMORY_TYPE {
+            self.with_partitions(collection_name).await?
+        } else {
+            vec![collection_name.to_string()]
+        };
+        let mut matches = Vec::with_capacity(collections.len());
+        for collection in collections {
+            let matched = self
+                .qdrant_service
+                .count_points(&collection, Some(filter.clone()))
+                .await?;
+            let mut stale = Vec::new();
+            if let Some(unmirrored) = &unmirrored {
+                stale = self
+                    .qdrant_service
+                    .scroll_payloads_with_ids(
+                        &collection,
+                        json!(["timestamp"]),
+                        Some(unmirrored.clone()),
+                    )
+                    .await?
+                    .into_iter()
+                    .filter(|(_, payload)| {
+                        payload
+                            .get("timestamp")
+                            .and_then(Value::as_str)
+                            .and_then(|raw| parse_time_boundary(raw, TimeBoundary::Start))
+                            .is_some_and(|stamped| stamped < cutoff_instant)
+                    })
+                    .map(|(id, _)| id)
+                    .collect();
+            }
+            matches.push((collection, matched, stale));
+        }
+        let mut outcome = PruneOutcome {
+            cutoff,
+            matched: matches
+                .iter()
+                .map(|(_, matched, stale)| matched + stale.len() as u64)
+                .sum(),
+            deleted: 0,
+        };
+        if dry_run {
+            return Ok(outcome);
+        }
+
+        for (collection, matched, stale) in matches {
+            if matched == 0 && stale.is_empty() {
+                continue;
+            }
+            if matched > 0 {
+                self.delete_by_filter_mirrored(&collection, filter.clone())
+                    .await?;
+            }
+            self.delete_mirrored(&collection, &stale).await?;
+            let deleted = matched + stale.len() as u64;
+            self.listing_cache.invalidate(&collection);
+            outcome.deleted += deleted;
+            self.audit.record(AuditRecord::new(
+                "prune",
+                &collection,
+                project_id.clone(),
+                AuditCounts {
+                    deleted: deleted as usize,
+                    ..AuditCounts::default()
+                },
+            ));
+            tracing::info!(
+                collection = %collection,
+                memory_type,
+                cutoff = %outcome.cutoff,
+                deleted,
+                "Memories pruned"
+            );
+        }
+        Ok(outcome)
+    }
+
+    /// Delete redundant copies of chunks stored more than once in `collection_name`.
+    ///
+    /// Points are grouped by `(project_id, chunk_hash)`; the copy with the earliest `timestamp`
+    /// (then the lowest id) is kept and the rest are deleted in batches of `PURGE_BATCH_SIZE`.
+    /// Points without a `chunk_hash` are never touched. A `dry_run` only counts.
+    pub async fn purge_duplicates(
+        &self,
+        collection_name: &str,
+        dry_run: bool,
+    ) -> Result<DuplicatePurgeOutcome, ProcessingError> {
+        let points = self
+            .qdrant_service
+            .scroll_payloads_with_ids(
+                collection_name,
+                json!(["project_id", "chunk_hash", "timestamp"]),
+                None,
+            )
+            .await?;
+
+        /// A stored copy: its `timestamp` and point id.
+        type StoredCopy = (Option<String>, String);
+        let mut groups: HashMap<(String, String), Vec<StoredCopy>> = HashMap::new();
+        let mut outcome = DuplicatePurgeOutcome::default();
+        for (id, payload) in points {
+            let field = |key: &str| payload.get(key).and_then(Value::as_str).map(str::to_string);
+            let Some(chunk_hash) = field("chunk_hash") else {
+                continue;
+            };
+            outcome.scanned += 1;
+            let project_id = field("project_id").unwrap_or_default();
+            groups
+                .entry((project_id, chunk_hash))
+                .or_default()
+                .push((field("timestamp"), id));
+        }
+
+        let mut redundant = Vec::new();
+        for mut copies in groups.into_values().filter(|copies| copies.len() > 1) {
+            outcome.duplicate_groups += 1;
+            // Undated copies sort after dated ones so a stamped original is the one kept.
+            copies.sort_by(|(a_time, a_id), (b_time, b_id)| {
+                (a_time.is_none(), a_time, a_id).cmp(&(b_time.is_none(), b_time, b_id))
+            });
+            redundant.extend(copies.into_iter().skip(1).map(|(_, id)| id));
+        }
+        if dry_run || redundant.is_empty() {
+            return Ok(outcome);
+        }
+
+        for batch in redundant.chunks(PURGE_BATCH_SIZE) {
+            self.delete_mirrored(collection_name, batch).await?;
+            outcome.deleted += batch.len();
+        }
+        self.listing_cache.invalidate(collection_name);
+        self.audit.record(AuditRecord::new(
+            "purge_duplicates",
+            collection_name,
+            None,
+            AuditCounts {
+                deleted: outcome.deleted,
+                ..AuditCounts::default()
+            },
+        ));
+        tracing::info!(
+            collection = collection_name,
+            groups = outcome.duplicate_groups,
+            deleted = outcome.deleted,
+            "Duplicate chunks purged"
+        );
+        Ok(outcome)
+    }
+
+    /// Regenerate a stored summary from its source memories, steered by `feedback`.
+    ///
+    /// The summary point is updated in place: its text, vector, `chunk_hash`, and citations are
+    /// replaced and `revision` is bumped. Requires the Ollama summarization provider; when it
+    /// fails, the stored summary is left untouched.
+    pub async fn refine_summary(
+        &self,
+        request: RefineSummaryRequest,
+    ) -> Result<RefineOutcome, SummarizeError> {
+        let config = get_config();
+        if !matches!(
+            config.summarization_provider,
+            crate::config::SummarizationProvider::Ollama
+        ) {
+            return Err(SummarizeError::ProviderNotConfigured);
+        }
+        let model = request
+            .model
+            .clone()
+            .or_else(|| config.summarization_model.clone())
+            .ok_or(SummarizeError::ProviderNotConfigured)?;
+        let client = get_summarization_client().ok_or(SummarizeError::ProviderNotConfigured)?;
+        self.refine_summary_with(client.as_ref(), &model, request)
+            .await
+    }
+
+    /// [`Self::refine_summary`] against an explicit client and model.
+    pub(crate) async fn refine_summary_with(
+        &self,
+        client: &(dyn SummarizationClient + Send + Sync),
+        model: &str,
+        request: RefineSummaryRequest,
+    ) -> Result<RefineOutcome, SummarizeError> {
+        let config = get_config();
+        let collection = request
+            .collection
+            .clone()
+            .unwrap_or_else(|| config.qdrant_collection_name.clone());
+        let memory_id = request.memory_id;
+
+        let (_, summary) = self
+            .qdrant_service
+            .retrieve_payloads(
+                &collection,
+                std::slice::from_ref(&memory_id),
+                json!([
+                    "text",
+                    "tags",
+                    "project_id",
+                    "source_memory_ids",
+                    "revision"
+                ]),
+            )
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| SummarizeError::SummaryNotFound(memory_id.clone()))?;
+        let is_summary = extract_tags(&summary)
+            .unwrap_or_default()
+            .iter()
+            .any(|tag| tag == "summary");
+        let source_ids: Vec<String> = summary
+            .get("source_memory_ids")
+            .and_then(Value::as_array)
+            .map(|ids| {
+                ids.iter()
+                    .filter_map(Value::as_str)
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+        if !is_summary || source_ids.is_empty() {
+            return Err(SummarizeError::NotASummary(memory_id));
+        }
+        let previous_summary = summary.get("text").and_then(Value::as_str).unwrap_or("");
+        let project_id = summary
+            .get("project_id")
+            .and_then(Value::as_str)
+            .unwrap_or("default");
+
+        let mut sources = Vec::new();
+        for target in self.with_partitions(&collection).await? {
+            let found: HashSet<&String> = sources.iter().map(|(id, _)| id).collect();
+            let pending: Vec<String> = source_ids
+                .iter()
+                .filter(|id| !found.contains(id))
+                .cloned()
+                .collect();
+            if pending.is_empty() {
+                break;
+            }
+            let retrieved = self
+                .qdrant_service
+                .retrieve_payloads(&target, &pending, json!(["text", "timestamp"]))
+                .await?;
+            sources.extend(retrieved);
+        }
+        let mut items: Vec<EpisodicMemory> = sources
+            .into_iter()
+            .filter_map(|(id, payload)| {
+                let text = payload.get("text").and_then(Value::as_str)?;
+                let timestamp = payload
+                    .get("timestamp")
+                    .and_then(Value::as_str)
+                    .map(str::to_string);
+                (!text.trim().is_empty())
+                    .then(|| EpisodicMemory::new(id, text.to_string(), timestamp))
+            })
+            .collect();
+        if items.is_empty() {
+            return Err(SummarizeError::EmptyResult);
+        }
+        sort_memories(&mut items);
+        let found: HashSet<&str> = items.iter().map(|item| item.memory_id.as_str()).collect();
+        let missing_source_memory_ids: Vec<String> = source_ids
+            .iter()
+            .filter(|id| !found.contains(id.as_str()))
+            .cloned()
+            .collect();
+        let time_range = ProcSearchTimeRange {
+            start: items.first().and_then(|item| item.timestamp.clone()),
+            end: items.last().and_then(|item| item.timestamp.clone()),
+        };
+
+        let count_tokens = summary_token_counter(config, model);
+        let budget = config
+            .summarization_max_prompt_tokens
+            .zip(count_tokens.as_deref())
+            .map(|(max_tokens, count_tokens)| PromptBudget {
+                max_tokens,
+                count_tokens,
+            });
+        let (generated, abstractive_attempts) = generate_abstractive_summary(
+            client,
+            AbstractiveRequest {
+                model,
+                project_id,
+                time_range: &time_range,
+                max_words: request.max_words.unwrap_or(config.summarization_max_words),
+                items: &items,
+                budget,
+                refinement: Some(&Refinement {
+                    previous_summary,
+                    feedback: &request.feedback,
+                }),
+            },
+        )
+        .await;
+        let generated = generated.ok_or(SummarizeError::ProviderUnavailable)?;
+
+        let vector = self
+            .embed(
+                &collection,
+                self.embedding_prefixes
+                    .documents(vec![generated.text.clone()]),
+            )
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                SummarizeError::Embedding(crate::embedding::EmbeddingClientError::Configuration(
+                    "no embedding generated".into(),
+                ))
+            })?;
+        let revision = summary.get("revision").and_then(Value::as_u64).unwrap_or(1) + 1;
+
+        self.qdrant_service
+            .update_vectors(&collection, vec![(memory_id.clone(), vector)])
+            .await?;
+        if let Some((_, vectors)) = self
+            .candidate_vectors(&collection, std::slice::from_ref(&generated.text))
+            .await
+        {
+            let updates = vec![memory_id.clone()].into_iter().zip(vectors).collect();
+            self.mirror_to_candidate(&collection, |candidate| async move {
+                self.qdrant_service
+                    .update_vectors(&candidate, updates)
+                    .await
+            })
+            .await;
+        }
+        let mut payload = Map::new();
+        payload.insert("text".into(), json!(generated.text));
+        payload.insert(
+            "chunk_hash".into(),
+            json!(qdrant::compute_chunk_hash(&generated.text)),
+        );
+        payload.insert("citations".into(), json!(generated.citations));
+        payload.insert("revision".into(), json!(revision));
+        self.set_payloads_mirrored(&collection, vec![(memory_id.clone(), payload)])
+            .await?;
+        self.audit.record(AuditRecord::new(
+            "refine_summary",
+            &collection,
+            Some(project_id.to_string()),
+            AuditCounts {
+                updated: 1,
+                ..AuditCounts::default()
+            },
+        ));
+
+        Ok(RefineOutcome {
+            memory_id,
+            summary: generated.text,
+            citations: generated.citations,
+            source_memory_ids: items.into_iter().map(|item| item.memory_id).collect(),
+            missing_source_memory_ids,
+            revision,
+            model: model.to_string(),
+            abstractive_attempts,
+            items_summarized: generated.items_summarized,
+            items_trimmed: generated.items_trimmed,
+        })
+    }
+
+    /// Report stored summaries whose `source_memory_ids` no longer exist.
+    ///
+    /// Distinct source ids are looked up in batches of `SUMMARY_SOURCE_BATCH_SIZE`. With
+    /// `tag_orphans`, each orphaned summary not yet tagged gains `ORPHANED_SUMMARY_TAG`.
+    pub async fn check_summaries(
+        &self,
+        collection_name: &str,
+        project_id: Option<String>,
+        tag_orphans: bool,
+    ) -> Result<SummaryCheckOutcome, ProcessingError> {
+        let filter = qdrant::build_search_filter(&qdrant::SearchFilterArgs {
+            project_id: sanitize_project_id(project_id),
+            memory_type: Some("semantic".into()),
+            tags: Some(vec!["summary".into()]),
+            ..Default::default()
+        });
+        let summaries = self
+            .qdrant_service
+            .scroll_payloads_with_ids(
+                collection_name,
+                json!(["project_id", "tags", "source_memory_ids"]),
+                filter,
+            )
+            .await?;
+        let sources_of = |payload: &Map<String, Value>| -> Vec<String> {
+            payload
+                .get("source_memory_ids")
+                .and_then(Value::as_array)
+                .map(|ids| {
+                    ids.iter()
+                        .filter_map(Value::as_str)
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+
+        let referenced: Vec<String> = summaries
+            .iter()
+            .flat_map(|(_, payload)| sources_of(payload))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        // Episodic sources may live in monthly partitions; each is asked only for ids not yet found.
+        let mut existing = BTreeSet::new();
+        for collection in self.with_partitions(collection_name).await? {
+            let pending: Vec<String> = referenced
+                .iter()
+                .filter(|id| !existing.contains(*id))
+                .cloned()
+                .collect();
+            for batch in pending.chunks(SUMMARY_SOURCE_BATCH_SIZE) {
+                existing.extend(
+                    self.qdrant_service
+                        .existing_point_ids(&collection, batch)
+                        .await?,
+                );
+            }
+        }
+
+        let mut outcome = SummaryCheckOutcome {
+            checked: summaries.len(),
+            ..SummaryCheckOutcome::default()
+        };
+        let mut retags = Vec::new();
+        for (memory_id, payload) in summaries {
+            let sources = sources_of(&payload);
+            let missing: Vec<String> = sources
+                .iter()
+                .filter(|id| !existing.contains(*id))
+                .cloned()
+                .collect();
+            if missing.is_empty() {
+                continue;
+            }
+            outcome.dangling_references += missing.len();
+            let mut tags = extract_tags(&payload).unwrap_or_default();
+            if tag_orphans && !tags.iter().any(|tag| tag == ORPHANED_SUMMARY_TAG) {
+                tags.push(ORPHANED_SUMMARY_TAG.to_string());
+                let mut update = Map::new();
+                update.insert("tags".into(), json!(tags));
+                retags.push((memory_id.clone(), update));
+            }
+            outcome.orphaned.push(OrphanedSummary {
+                memory_id,
+                project_id: payload
+                    .get("project_id")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+                source_count: sources.len(),
+                missing_source_memory_ids: missing,
+            });
+        }
+        outcome.tagged = retags.len();
+        self.set_payloads_mirrored(collection_name, retags).await?;
+        self.listing_cache.invalidate(collection_name);
+
+        tracing::info!(
+            collection = collection_name,
+            checked = outcome.checked,
+            orphaned = outcome.orphaned.len(),
+            dangling_references = outcome.dangling_references,
+            tagged = outcome.tagged,
+            "Summaries checked"
+        );
+        Ok(outcome)
+    }
+
+    /// List stored summaries matching `query`, newest first.
+    ///
+    /// Summaries are the semantic points tagged `summary`; `label` and `topic` must match exactly.
+    pub async fn find_summaries(
+        &self,
+        collection_name: &str,
+        query: SummaryQuery,
+    ) -> Result<Vec<SummaryRecord>, ProcessingError> {
+        let SummaryQuery {
+            project_id,
+            label,
+            topic,
+            time_range,
+            limit,
+        } = query;
+        let filter_args = qdrant::SearchFilterArgs {
+            project_id: sanitize_project_id(project_id),
+            memory_type: Some("semantic".into()),
+            tags: Some(vec!["summary".into()]),
+            time_range: time_range.map(normalize_time_range),
+            unix_time_range: self.unix_time_range(),
+            ..Default::default()
+        };
+
+        let local_time_range = local_time_range(&filter_args);
+        let mut filter = match qdrant::build_search_filter(&filter_args) {
+            Some(Value::Object(filter)) => filter,
+            _ => Map::new(),
+        };
+        let must = filter.entry("must").or_insert_with(|| json!([]));
+        for (key, value) in [("label", label), ("topic", topic)] {
+            if let (Some(value), Some(must)) = (sanitize_string(value), must.as_array_mut()) {
+                must.push(json!({ "key": key, "match": { "value": value } }));
+            }
+        }
+
+        let records = self
+            .qdrant_service
+            .scroll_recent_payloads(collection_name, Some(Value::Object(filter)), limit)
+            .await?;
+
+        let field = |payload: &Map<String, Value>, key: &str| {
+            payload.get(key).and_then(Value::as_str).map(str::to_string)
+        };
+        Ok(records
+            .into_iter()
+            .map(|(memory_id, payload)| SummaryRecord {
+                memory_id,
+                summary: field(&payload, "text").unwrap_or_default(),
+                project_id: field(&payload, "project_id"),
+                label: field(&payload, "label"),
+                topic: field(&payload, "topic"),
+                timestamp: field(&payload, "timestamp"),
+                summary_key: field(&payload, "summary_key"),
+                source_memory_ids: payload
+                    .get("source_memory_ids")
+                    .and_then(Value::as_array)
+                    .map(|ids| {
+                        ids.iter()
+                            .filter_map(|id| id.as_str().map(str::to_string))
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            })
+            .filter(|record| within_time_range(record.timestamp.as_deref(), local_time_range))
+            .collect())
+    }
+
+    /// Wait for Qdrant to finish indexing the collection, then optionally issue warm-up searches.
+    ///
+    /// Warm-up searches only run once indexing has settled (or the wait timed out) so they touch
+    /// the final index segments rather than ones about to be rebuilt.
+    pub async fn warm_collection(
+        &self,
+        collection_name: &str,
+        timeout: Duration,
+        warmup_queries: usize,
+    ) -> Result<WarmupOutcome, ProcessingError> {
+        let started = Instant::now();
+        let before = self.qdrant_service.collection_info(collection_name).await?;
+        let after = if before.is_ready() {
+            before.clone()
+        } else {
+            self.qdrant_service
+                .wait_for_indexing(collection_name, timeout, WARM_INITIAL_BACKOFF)
+                .await?
+        };
+
+        if warmup_queries > 0 {
+            let queries = (0..warmup_queries)
+                .map(|index| format!("warm-up query {index}"))
+                .collect();
+            let vectors = self
+                .embed(collection_name, self.embedding_prefixes.queries(queries))
+                .await?;
+            for vector in vectors {
+                self.qdrant_service
+                    .search_points(
+                        collection_name,
+                        PointQuery {
+                            vector,
+                            limit: 1,
+                            ..PointQuery::default()
+                        },
+                    )
+                    .await?;
+            }
+        }
+
+        let elapsed = started.elapsed();
+        tracing::info!(
+            collection = collection_name,
+            ready = after.is_ready(),
+            warmup_queries,
+            elapsed_ms = elapsed.as_millis() as u64,
+            "Collection warm-up finished"
+        );
+        Ok(WarmupOutcome {
+            before,
+            after,
+            warmup_queries,
+            elapsed,
+        })
+    }
+
+    /// Return the current ingestion metrics snapshot.
+    pub fn metrics_snapshot(&self) -> MetricsSnapshot {
+        self.metrics.snapshot()
+    }
+
+    /// Ingest cursors recorded under `STATE_DIR`, optionally limited to one project.
+    ///
+    /// Returns `None` when `STATE_DIR` is not configured.
+    pub fn sync_entries(&self, project_id: Option<&str>) -> Option<Vec<SyncEntry>> {
+        self.sync_state.as_ref().map(|store| store.list(project_id))
+    }
+
+    /// Forget matching ingest cursors so their sources are re-indexed on the next push.
+    ///
+    /// Returns the number of cursors removed, or `None` when `STATE_DIR` is not configured.
+    pub fn reset_sync_entries(
+        &self,
+        project_id: Option<&str>,
+        source_uri: Option<&str>,
+    ) -> Option<usize> {
+        self.sync_state
+            .as_ref()
+            .map(|store| store.reset(project_id, source_uri))
+    }
+
+    /// Return the ingestion counters recorded for a single collection.
+    pub fn collection_metrics_snapshot(&self, collection: &str) -> MetricsSnapshot {
+        self.metrics.collection_snapshot(collection)
+    }
+
+    /// Probe Qdrant to surface a lightweight health snapshot for MCP resources.
+    ///
+    /// The primary and, when configured, the read replica are probed independently.
+    pub async fn qdrant_health(&self) -> QdrantHealthSnapshot {
+        let config = get_config();
+        let version = self
+            .qdrant_service
+            .capabilities()
+            .version
+            .map(|version| version.to_string());
+        let read_replica = match self.qdrant_service.probe_read_replica().await {
+            None => None,
+            Some(outcome) => {
+                let url = self
+                    .qdrant_service
+                    .read_url()
+                    .unwrap_or_default()
+                    .to_string();
+                if let Err(error) = &outcome {
+                    tracing::warn!(url, error = %error, "Qdrant read replica health probe failed");
+                }
+                Some(ReadReplicaHealth {
+                    url,
+                    reachable: outcome.is_ok(),
+                    error: outcome.err().map(|error| error.to_string()),
+                })
+            }
+        };
+        let mut snapshot = QdrantHealthSnapshot {
+            reachable: false,
+            default_collection_present: false,
+            error: None,
+            version,
+            read_replica,
+            probe: "collections",
+        };
+        if let Some(endpoint) = config.qdrant_health_probe.endpoint() {
+            match self.qdrant_service.probe_health_endpoint(endpoint).await {
+                Ok(true) => {
+                    snapshot.reachable = true;
+                    snapshot.probe = endpoint;
+                    // A single-collection lookup also works for keys scoped to that collection.
+                    snapshot.default_collection_present = match self
+                        .qdrant_service
+                        .collection_exists(&config.qdrant_collection_name)
+                        .await
+                    {
+                        Ok(present) => present,
+                        Err(error) => {
+                            tracing::warn!(error = %error, "Qdrant default collection check failed");
+                            false
+                        }
+                    };
+                    return snapshot;
+                }
+                Ok(false) => {
+                    tracing::debug!(
+                        endpoint,
+                        "Qdrant has no health endpoint; probing by listing collections"
+                    );
+                }
+                Err(error) => {
+                    tracing::warn!(endpoint, error = %error, "Qdrant health probe failed");
+                    snapshot.probe = endpoint;
+                    snapshot.error = Some(error.to_string());
+                    return snapshot;
+                }
+            }
+        }
+        match self.qdrant_service.list_collections().await {
+            Ok(collections) => {
+                snapshot.reachable = true;
+                snapshot.default_collection_present = collections
+                    .iter()
+                    .any(|name| name == &config.qdrant_collection_name);
+            }
+            Err(error) => {
+                tracing::warn!(error = %error, "Qdrant health probe failed");
+                snapshot.error = Some(error.to_string());
+            }
+        }
+        snapshot
+    }
+
+    /// Summarize episodic (or chosen type) memories within a time range, persist a semantic summary, and return provenance.
+    pub async fn summarize_memories(
+        &self,
+        request: SummarizeRequest,
+    ) -> Result<SummarizeOutcome, SummarizeError> {
+        let config = get_config();
+        let collection = request
+            .collection
+            .clone()
+            .unwrap_or_else(|| config.qdrant_collection_name.clone());
+
+        // Validate time range
+        if request.time_range.start.is_none() || request.time_range.end.is_none() {
+            return Err(SummarizeError::InvalidTimeRange);
+        }
+
+        // Build episodic filter
+        let filter_args = qdrant::SearchFilterArgs {
+            project_id: request.project_id.clone(),
+            memory_type: request
+                .memory_type
+                .clone()
+                .or_else(|| Some("episodic".into())),
+            tags: request.tags.clone(),
+            time_range: Some(normalize_time_range(request.time_range.clone())),
+            unix_time_range: self.unix_time_range(),
+            ..Default::default()
+        };
+        let filter = qdrant::build_search_filter(&filter_args);
+        let local_time_range = local_time_range(&filter_args);
+        // Without an explicit collection, episodic sources are read from the monthly partitions.
+        let targets = match request.collection {
+            Some(_) => vec![collection.clone()],
+            None => {
+                self.read_targets(
+                    &collection,
+                    filter_args.memory_type.as_deref(),
+                    Some(&request.time_range),
+                )
+                .await?
+                .0
+            }
+        };
+
+        // Scroll payloads (id + payload) and map into episodic items
+        let fields = serde_json::json!(["text", "timestamp", "tags"]);
+        let mut source_tags: HashMap<String, Vec<String>> = HashMap::new();
+        let mut source_collections: HashMap<String, String> = HashMap::new();
+        let mut items = Vec::new();
+        for target in &targets {
+            let records = self
+                .qdrant_service
+                .scroll_payloads_with_ids(target, fields.clone(), filter.clone())
+                .await
+                .map_err(SummarizeError::Qdrant)?;
+            items.extend(records.into_iter().filter_map(|(id, payload)| {
+                let text = payload
+                    .get("text")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("")
+                    .to_string();
+                let timestamp = payload
+                    .get("timestamp")
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_string());
+                if text.trim().is_empty()
+                    || !within_time_range(timestamp.as_deref(), local_time_range)
+                {
+                    None
+                } else {
+                    let mut tags = BTreeSet::new();
+                    qdrant::accumulate_tags(&payload, &mut tags);
+                    source_tags.insert(id.clone(), tags.into_iter().collect());
+                    source_collections.insert(id.clone(), target.clone());
+                    Some(EpisodicMemory::new(id, text, timestamp))
+                }
+            }));
+        }
+
+        // Sort chronologically and cap by limit
+        sort_memories(&mut items);
+        let limit = request.limit.unwrap_or(50);
+        if items.len() > limit {
+            items.truncate(limit);
+        }
+
+        if items.is_empty() {
+            return Err(SummarizeError::EmptyResult);
+        }
+
+        let source_memory_ids: Vec<String> = items.iter().map(|m| m.memory_id.clone()).collect();
+        let summary_key = compute_summary_key(
+            request.project_id.as_deref().unwrap_or("default"),
+            &ProcSearchTimeRange {
+                start: request.time_range.start.clone(),
+                end: request.time_range.end.clone(),
+            },
+            &source_memory_ids,
+        );
+
+        // Idempotency: check for existing summary via tag summary:<hash>
+        let idempotency_tag = format!("summary:{summary_key}");
+        let existing_filter = qdrant::build_search_filter(&qdrant::SearchFilterArgs {
+            project_id: request.project_id.clone(),
+            memory_type: Some("semantic".into()),
+            tags: Some(vec![idempotency_tag.clone()]),
+            ..Default::default()
+        });
+        let existing = self
+            .qdrant_service
+            .scroll_payloads_with_ids(
+                &collection,
+                serde_json::json!(["text", "citations", "label", "topic"]),
+                existing_filter,
+            )
+            .await
+            .map_err(SummarizeError::Qdrant)?;
+        if let Some((existing_id, mut payload)) = existing.into_iter().next() {
+            let summary_text = payload
+                .get("text")
+                .and_then(|v| v.as_str())
+                .unwrap_or("")
+                .to_string();
+            let citations = extract_citations(&mut payload).unwrap_or_default();
+            let mut retag = Map::new();
+            for (key, value) in [("label", &request.label), ("topic", &request.topic)] {
+                if let Some(value) = value
+                    && payload.get(key).and_then(Value::as_str) != Some(value.as_str())
+                {
+                    retag.insert(key.into(), Value::String(value.clone()));
+                }
+            }
+            if !retag.is_empty() {
+                self.set_payloads_mirrored(&collection, vec![(existing_id.clone(), retag)])
+                    .await
+                    .map_err(SummarizeError::Qdrant)?;
+                self.listing_cache.invalidate(&collection);
+            }
+            let stored = |key: &str| payload.get(key).and_then(Value::as_str).map(str::to_string);
+            let label = request.label.clone().or_else(|| stored("label"));
+            let topic = request.topic.clone().or_else(|| stored("topic"));
+            // The summary is already persisted, so a retried consolidation is safe to apply.
+            if let Some(mode) = request.consolidate {
+                let counts = self
+                    .consolidate_sources(
+                        mode,
+                        &source_memory_ids,
+                        &source_collections,
+                        &source_tags,
+                    )
+                    .await?;
+                self.audit.record(AuditRecord::new(
+                    "summarize",
+                    &collection,
+                    request.project_id.clone(),
+                    counts,
+                ));
+            }
+            return Ok(SummarizeOutcome {
+                summary: summary_text,
+                source_memory_ids,
+                citations,
+                upserted_memory_id: existing_id,
+                strategy_used: strategy_to_label(&request.strategy),
+                provider: request.provider,
+                model: request.model,
+                consolidated: request.consolidate,
+                abstractive_attempts: 0,
+                items_summarized: items.len(),
+                items_trimmed: 0,
+                label,
+                topic,
+            });
+        }
+
+        // Choose summarization strategy
+        let mut chosen_strategy = request.strategy.clone().unwrap_or(SummarizeStrategy::Auto);
+        let mut provider_str = request.provider.clone();
+        let mut model_str = request.model.clone();
+
+        let mut summary_text = String::new();
+        let mut citations = Vec::new();
+        let mut abstractive_attempts = 0;
+        let mut items_summarized = items.len();
+        let mut items_trimmed = 0;
+        if matches!(
+            chosen_strategy,
+            SummarizeStrategy::Auto | SummarizeStrategy::Abstractive
+        ) {
+            // Try abstractive path if provider active
+            if matches!(
+                config.summarization_provider,
+                crate::config::SummarizationProvider::Ollama
+            ) {
+                if model_str.is_none() {
+                    model_str = config.summarization_model.clone();
+                }
+                if provider_str.is_none() {
+                    provider_str = Some("ollama".into());
+                }
+                if let (Some(model), Some(client)) = (model_str.clone(), get_summarization_client())
+                {
+                    let count_tokens = summary_token_counter(config, &model);
+                    let budget = config
+                        .summarization_max_prompt_tokens
+                        .zip(count_tokens.as_deref())
+                        .map(|(max_tokens, count_tokens)| PromptBudget {
+                            max_tokens,
+                            count_tokens,
+                        });
+                    let (summary, attempts) = generate_abstractive_summary(
+                        client.as_ref(),
+                        AbstractiveRequest {
+                            model: &model,
+                            project_id: request.project_id.as_deref().unwrap_or("default"),
+                            time_range: &ProcSearchTimeRange {
+                                start: request.time_range.start.clone(),
+                                end: request.time_range.end.clone(),
+                            },
+                            max_words: request.max_words.unwrap_or(config.summarization_max_words),
+                            items: &items,
+                            budget,
+                            refinement: None,
+                        },
+                    )
+                    .await;
+                    abstractive_attempts = attempts;
+                    if let Some(summary) = summary {
+                        summary_text = summary.text;
+                        citations = summary.citations;
+                        items_summarized = summary.items_summarized;
+                        items_trimmed = summary.items_trimmed;
+                        chosen_strategy = SummarizeStrategy::Abstractive;
+                    }
+                }
+            }
+        }
+
+        // Extractive fallback or selection
+        if summary_text.is_empty() {
+            (summary_text, citations) = build_extractive_summary(
+                &items,
+                request.max_words.unwrap_or(config.summarization_max_words),
+            );
+            if matches!(chosen_strategy, SummarizeStrategy::Auto) {
+                chosen_strategy = SummarizeStrategy::Extractive;
+            }
+        }
+
+        // Embed and upsert the summary as semantic
+        let vectors = self
+            .embed(
+                &collection,
+                self.embedding_prefixes
+                    .documents(vec![summary_text.clone()]),
+            )
+            .await
+            .map_err(SummarizeError::Embedding)?;
+        let vector = vectors.into_iter().next().ok_or_else(|| {
+            SummarizeError::Embedding(crate::embedding::EmbeddingClientError::Configuration(
+                "no embedding generated".into(),
+            ))
+        })?;
+
+        let chunk_hash = qdrant::compute_chunk_hash(&summary_text);
+        let mut tags = request.tags.clone().unwrap_or_default();
+        tags.push("summary".into());
+        tags.push(format!("summary:{summary_key}"));
+
+        let overrides = qdrant::types::PayloadOverrides {
+            project_id: request.project_id.clone(),
+            memory_type: Some("semantic".into()),
+            tags: Some(tags),
+            source_uri: None,
+            source_memory_ids: Some(source_memory_ids.clone()),
+            summary_key: Some(summary_key.clone()),
+            label: request.label.clone(),
+            topic: request.topic.clone(),
+            citations: Some(citations.clone()),
+            timestamp: None,
+            profile_hash: None,
+            ingest_profile: None,
+            doc_id: None,
+        };
+
+        self.ensure_collection(&collection)
+            .await
+            .map_err(|e| match e {
+                ProcessingError::Qdrant(err) => SummarizeError::Qdrant(err),
+                ProcessingError::Embedding(err) => SummarizeError::Embedding(err),
+                ProcessingError::Chunking(err) => {
+                    SummarizeError::GenerationFailed(format!("chunking failed: {err}"))
+                }
+                error @ (ProcessingError::PayloadIndexes { .. }
+                | ProcessingError::TranscriptRejected(_)) => {
+                    SummarizeError::GenerationFailed(error.to_string())
+                }
+            })?;
+
+        let candidate = self
+            .candidate_vectors(&collection, std::slice::from_ref(&summary_text))
+            .await;
+        let (candidate_name, candidate_vectors) = candidate.unzip();
+        let IndexSummary { inserted, updated } = self
+            .qdrant_service
+            .index_points_with_candidate(
+                &collection,
+                vec![PointInsert {
+                    text: summary_text.clone(),
+                    chunk_hash,
+                    vector,
+                    memory_type: None,
+                    quality_score: None,
+                    chunk_index: None,
+                    content_kind: None,
+                    language: None,
+                    symbol: None,
+                }],
+                &overrides,
+                candidate_name.as_deref().zip(candidate_vectors),
+            )
+            .await
+            .map_err(SummarizeError::Qdrant)?;
+        self.listing_cache.invalidate(&collection);
+
+        // Resolve ID of the inserted summary by scanning for the idempotency tag
+        let resolve = self
+            .qdrant_service
+            .scroll_payloads_with_ids(
+                &collection,
+                serde_json::json!(["text"]),
+                qdrant::build_search_filter(&qdrant::SearchFilterArgs {
+                    project_id: request.project_id.clone(),
+                    memory_type: Some("semantic".into()),
+                    tags: Some(vec![idempotency_tag.clone()]),
+                    ..Default::default()
+                }),
+            )
+            .await
+            .map_err(SummarizeError::Qdrant)?;
+        let upserted_memory_id = resolve
+            .into_iter()
+            .map(|(id, _)| id)
+            .next()
+            .unwrap_or_default();
+
+        // Only touch the sources once the summary write above has been acknowledged (`wait=true`).
+        let mut counts = AuditCounts {
+            inserted,
+            updated,
+            deleted: 0,
+        };
+        if let Some(mode) = request.consolidate {
+            let consolidated = self
+                .consolidate_sources(mode, &source_memory_ids, &source_collections, &source_tags)
+                .await?;
+            counts.updated += consolidated.updated;
+            counts.deleted += consolidated.deleted;
+        }
+        self.audit.record(AuditRecord::new(
+            "summarize",
+            &collection,
+            request.project_id.clone(),
+            counts,
+        ));
+
+        Ok(SummarizeOutcome {
+            summary: summary_text,
+            source_memory_ids,
+            citations,
+            upserted_memory_id,
+            strategy_used: strategy_to_label(&Some(chosen_strategy)),
+            provider: provider_str,
+            model: model_str,
+            consolidated: request.consolidate,
+            abstractive_attempts,
+            items_summarized,
+            items_trimmed,
+            label: request.label,
+            topic: request.topic,
+        })
+    }
+
+    /// Delete or archive the episodic sources folded into a persisted summary, in the collection
+    /// (or monthly partition) each was read from.
+    async fn consolidate_sources(
+        &self,
+        mode: ConsolidateMode,
+        source_memory_ids: &[String],
+        source_collections: &HashMap<String, String>,
+        source_tags: &HashMap<String, Vec<String>>,
+    ) -> Result<AuditCounts, SummarizeError> {
+        let mut by_collection: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+        for id in source_memory_ids {
+            if let Some(collection) = source_collections.get(id) {
+                by_collection
+                    .entry(collection.as_str())
+                    .or_default()
+                    .push(id.clone());
+            }
+        }
+        let mut counts = AuditCounts::default();
+        for (collection, ids) in by_collection {
+            let touched = self
+                .consolidate_in(collection, mode, &ids, source_tags)
+                .await?;
+            counts.updated += touched.updated;
+            counts.deleted += touched.deleted;
+        }
+        Ok(counts)
+    }
+
+    /// Delete or archive summarized sources stored in `collection`.
+    ///
+    /// Archiving appends the `archived` tag and moves the memory out of its original type so
+    /// it no longer feeds future summaries or type-filtered searches. Returns the rows touched.
+    async fn consolidate_in(
+        &self,
+        collection: &str,
+        mode: ConsolidateMode,
+        source_memory_ids: &[String],
+        source_tags: &HashMap<String, Vec<String>>,
+    ) -> Result<AuditCounts, SummarizeError> {
+        let affected = source_memory_ids.len();
+        let counts = match mode {
+            ConsolidateMode::Delete => {
+                self.delete_mirrored(collection, source_memory_ids).await?;
+                AuditCounts {
+                    deleted: affected,
+                    ..AuditCounts::default()
+                }
+            }
+            ConsolidateMode::Archive => {
+                let updates = source_memory_ids
+                    .iter()
+                    .map(|id| {
+                        let mut tags = source_tags.get(id).cloned().unwrap_or_default();
+                        if !tags.iter().any(|tag| tag == ARCHIVED_LABEL) {
+                            tags.push(ARCHIVED_LABEL.into());
+                        }
+                        let mut payload = Map::new();
+                        payload.insert("tags".into(), Value::from(tags));
+                        payload.insert("memory_type".into(), Value::from(ARCHIVED_LABEL));
+                        (id.clone(), payload)
+                    })
+                    .collect();
+                self.set_payloads_mirrored(collection, updates).await?;
+                AuditCounts {
+                    updated: affected,
+                    ..AuditCounts::default()
+                }
+            }
+        };
+        self.listing_cache.invalidate(collection);
+        tracing::info!(
+            collection,
+            mode = ?mode,
+            sources = affected,
+            "Consolidated summarized sources"
+        );
+        Ok(counts)
+    }
+}
+
+#[async_trait]
+impl ProcessingApi for ProcessingService {
+    async fn process_and_index(
+        &self,
+        collection_name: &str,
+        text: String,
+        metadata: IngestMetadata,
+    ) -> Result<ProcessingOutcome, ProcessingError> {
+        ProcessingService::process_and_index(self, collection_name, text, metadata).await
+    }
+
+    async fn import_vectors(
+        &self,
+        collection_name: &str,
+        records: Vec<ImportedVector>,
+    ) -> Result<usize, ProcessingError> {
+        ProcessingService::import_vectors(self, collection_name, records).await
+    }
+
+    async fn create_collection(
+        &self,
+        collection_name: &str,
+        vector_size: Option<u64>,
+        topology: CollectionTopology,
+    ) -> Result<(), ProcessingError> {
+        ProcessingService::create_collection(self, collection_name, vector_size, topology).await
+    }
+
+    async fn ensure_collection(&self, collection_name: &str) -> Result<(), ProcessingError> {
+        ProcessingService::ensure_collection(self, collection_name).await
+    }
+
+    async fn vector_dimension(&self, collection_name: &str) -> Result<usize, ProcessingError> {
+        ProcessingService::vector_dimension(self, collection_name).await
+    }
+
+    async fn list_collections(&self) -> Result<Vec<String>, ProcessingError> {
+        ProcessingService::list_collections(self).await
+    }
+
+    async fn list_projects(
+        &self,
+        collection_name: &str,
+    ) -> Result<BTreeSet<String>, ProcessingError> {
+        ProcessingService::list_projects(self, collection_name).await
+    }
+
+    async fn list_tags(
+        &self,
+        collection_name: &str,
+        project_id: Option<&str>,
+    ) -> Result<BTreeSet<String>, ProcessingError> {
+        ProcessingService::list_tags(self, collection_name, project_id).await
+    }
+
+    async fn search_memories(
+        &self,
+        request: ValidatedSearchRequest,
+    ) -> Result<SearchOutcome, SearchError> {
+        ProcessingService::search_memories(self, request).await
+    }
+
+    async fn summarize_memories(
+        &self,
+        request: SummarizeRequest,
+    ) -> Result<SummarizeOutcome, SummarizeError> {
+        ProcessingService::summarize_memories(self, request).await
+    }
+
+    fn ingest_collection(&self, collection_name: &str, metadata: &IngestMetadata) -> String {
+        ProcessingService::ingest_collection(self, collection_name, metadata)
+    }
+
+    async fn read_targets(
+        &self,
+        collection_name: &str,
+        memory_type: Option<&str>,
+        time_range: Option<&ProcSearchTimeRange>,
+    ) -> Result<(Vec<String>, Option<Vec<String>>), ProcessingError> {
+        ProcessingService::read_targets(self, collection_name, memory_type, time_range)
+            .await
+            .map_err(ProcessingError::from)
+    }
+
+    async fn collection_info(
+        &self,
+        collection_name: &str,
+    ) -> Result<CollectionInfo, ProcessingError> {
+        ProcessingService::collection_info(self, collection_name).await
+    }
+
+    async fn storage_stats(
+        &self,
+        collection: Option<&str>,
+        project_id: Option<&str>,
+    ) -> Result<StorageStatsReport, ProcessingError> {
+        ProcessingService::storage_stats(self, collection, project_id).await
+    }
+
+    async fn warm_collection(
+        &self,
+        collection_name: &str,
+        timeout: Duration,
+        warmup_queries: usize,
+    ) -> Result<WarmupOutcome, ProcessingError> {
+        ProcessingService::warm_collection(self, collection_name, timeout, warmup_queries).await
+    }
+
+    async fn qdrant_health(&self) -> QdrantHealthSnapshot {
+        ProcessingService::qdrant_health(self).await
+    }
+
+    fn metrics_snapshot(&self) -> MetricsSnapshot {
+        ProcessingService::metrics_snapshot(self)
+    }
+}
+
+/// Strategy selection for summarization.
+#[derive(Clone, Debug)]
+pub enum SummarizeStrategy {
+    /// Choose abstractive when provider available, else extractive.
+    Auto,
+    /// Use local LLM via provider.
+    Abstractive,
+    /// Deterministic bullet extraction.
+    Extractive,
+}
+
+/// Tag and memory type applied to archived summary sources.
+const ARCHIVED_LABEL: &str = "archived";
+
+/// How source memories are handled once their summary has been written.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConsolidateMode {
+    /// Re-tag sources as `archived` and move them out of the episodic pool.
+    Archive,
+    /// Delete sources outright.
+    Delete,
+}
+
+impl ConsolidateMode {
+    /// Wire label used in tool arguments and responses.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Archive => "archive",
+            Self::Delete => "delete",
+        }
+    }
+}
+
+/// Input parameters for summarization.
+#[derive(Clone, Debug)]
+pub struct SummarizeRequest {
+    /// Project whose memories are summarized.
+    pub project_id: Option<String>,
+    /// Memory type to summarize (defaults to `episodic`).
+    pub memory_type: Option<String>,
+    /// Optional contains-any tag filter.
+    pub tags: Option<Vec<String>>,
+    /// Window to summarize; both boundaries are required.
+    pub time_range: ProcSearchTimeRange,
+    /// Maximum number of source memories.
+    pub limit: Option<usize>,
+    /// Extractive, abstractive, or automatic selection.
+    pub strategy: Option<SummarizeStrategy>,
+    /// Summarization provider override.
+    pub provider: Option<String>,
+    /// Summarization model override.
+    pub model: Option<String>,
+    /// Word budget for the summary.
+    pub max_words: Option<usize>,
+    /// Optional Qdrant collection override.
+    pub collection: Option<String>,
+    /// What to do with the sources once the summary is stored.
+    pub consolidate: Option<ConsolidateMode>,
+    /// Label stored on the summary; re-running over the same sources re-tags it.
+    pub label: Option<String>,
+    /// Topic stored on the summary; re-running over the same sources re-tags it.
+    pub topic: Option<String>,
+}
+
+/// Input parameters for `refine_summary`.
+#[derive(Clone, Debug)]
+pub struct RefineSummaryRequest {
+    /// Point id of the stored summary (`upserted_memory_id` from `summarize`).
+    pub memory_id: String,
+    /// What the revised summary should change or expand on.
+    pub feedback: String,
+    /// Word budget for the revision.
+    pub max_words: Option<usize>,
+    /// Summarization model override.
+    pub model: Option<String>,
+    /// Optional Qdrant collection override.
+    pub collection: Option<String>,
+}
+
+/// Result of refining a stored summary in place.
+#[derive(Clone, Debug)]
+pub struct RefineOutcome {
+    /// Point id of the updated summary.
+    pub memory_id: String,
+    /// Regenerated summary text.
+    pub summary: String,
+    /// Resolved `[n]` markers of the new text.
+    pub citations: Vec<qdrant::Citation>,
+    /// Source memories still present and fed to the prompt.
+    pub source_memory_ids: Vec<String>,
+    /// Source memories referenced by the summary that no longer exist.
+    pub missing_source_memory_ids: Vec<String>,
+    /// Revision stored on the summary; the original counts as revision 1.
+    pub revision: u64,
+    /// Model that produced the revision.
+    pub model: String,
+    /// Abstractive requests made, including context-overflow retries.
+    pub abstractive_attempts: usize,
+    /// Source memories the revision actually covers.
+    pub items_summarized: usize,
+    /// Oldest memories dropped to fit `SUMMARIZATION_MAX_PROMPT_TOKENS`.
+    pub items_trimmed: usize,
+}
+
+/// Errors surfaced while moving memories between collections.
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum MoveError {
+    #[error("Source and target collections must differ")]
+    SameCollection,
+    #[error("Provide at least one of `project_id`, `memory_type`, or `tags` to select memories")]
+    MissingFilter,
+    #[error(
+        "Vector size mismatch: source collection has {source_size:?}, target collection has {target_size:?}"
+    )]
+    DimensionMismatch {
+        source_size: Option<u64>,
+        target_size: Option<u64>,
+    },
+    #[error("Target collection `{0}` already exists")]
+    TargetExists(String),
+    #[error("Collection `{0}` reports no vector size to copy")]
+    UnknownVectorSize(String),
+    #[error(
+        "Copy incomplete: source collection has {source_points} points, target collection has {target_points}; the source was kept"
+    )]
+    CopyIncomplete {
+        source_points: u64,
+        target_points: u64,
+    },
+    #[error(transparent)]
+    Qdrant(#[from] crate::qdrant::types::QdrantError),
+}
+
+impl MoveError {
+    /// Classify this failure for status mapping and retry decisions.
+    pub(crate) fn kind(&self) -> ErrorKind {
+        match self {
+            Self::SameCollection
+            | Self::MissingFilter
+            | Self::DimensionMismatch { .. }
+            | Self::TargetExists(_)
+            | Self::UnknownVectorSize(_) => ErrorKind::InvalidRequest,
+            Self::CopyIncomplete { .. } => ErrorKind::Internal,
+            Self::Qdrant(source) => qdrant_error_kind(source),
+        }
+    }
+}
+
+/// Errors surfaced from the summarization pipeline.
+#[derive(Debug, thiserror::Error)]
+pub enum SummarizeError {
+    /// The summary could not be produced.
+    #[error("Failed to generate summary: {0}")]
+    GenerationFailed(String),
+    /// No memories matched the requested scope.
+    #[error("No episodic memories found for the requested scope")]
+    EmptyResult,
+    /// The time range is missing a boundary.
+    #[error("`time_range` must include both `start` and `end`")]
+    InvalidTimeRange,
+    /// Embedding the summary failed.
+    #[error(transparent)]
+    Embedding(#[from] crate::embedding::EmbeddingClientError),
+    /// Reading sources or storing the summary failed.
+    #[error(transparent)]
+    Qdrant(#[from] crate::qdrant::types::QdrantError),
+    /// No point with the given id exists in the collection.
+    #[error("Summary `{0}` was not found")]
+    SummaryNotFound(String),
+    /// The point exists but is not a stored summary with source memories.
+    #[error("Memory `{0}` is not a summary with source memories")]
+    NotASummary(String),
+    /// No abstractive provider and model are configured.
+    #[error("Refining requires SUMMARIZATION_PROVIDER=ollama and a summarization model")]
+    ProviderNotConfigured,
+    /// The abstractive provider failed; the stored summary was left unchanged.
+    #[error("Summarization provider unavailable; the summary was left unchanged")]
+    ProviderUnavailable,
+}
+
+impl SummarizeError {
+    /// Classify this failure for status mapping and retry decisions.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::GenerationFailed(_) => ErrorKind::Internal,
+            Self::EmptyResult
+            | Self::InvalidTimeRange
+            | Self::SummaryNotFound(_)
+            | Self::NotASummary(_) => ErrorKind::InvalidRequest,
+            Self::ProviderNotConfigured => ErrorKind::Configuration,
+            Self::ProviderUnavailable => ErrorKind::Transient,
+            Self::Embedding(source) => embedding_error_kind(source),
+            Self::Qdrant(source) => qdrant_error_kind(source),
+        }
+    }
+
+    /// Whether the same request may succeed if retried unchanged.
+    pub fn is_retryable(&self) -> bool {
+        self.kind().is_retryable()
+    }
+}
+
+/// Result of a summarization request.
+#[derive(Clone, Debug)]
+pub struct SummarizeOutcome {
+    /// Summary text, with `[n]` markers when abstractive.
+    pub summary: String,
+    /// Memories the summary was built from.
+    pub source_memory_ids: Vec<String>,
+    /// Resolved `[n]` markers.
+    pub citations: Vec<qdrant::Citation>,
+    /// Identifier of the stored summary memory.
+    pub upserted_memory_id: String,
+    /// Strategy that produced the summary (`extractive` or `abstractive`).
+    pub strategy_used: String,
+    /// Provider used for an abstractive summary.
+    pub provider: Option<String>,
+    /// Model used for an abstractive summary.
+    pub model: Option<String>,
+    /// How the sources were consolidated, if at all.
+    pub consolidated: Option<ConsolidateMode>,
+    /// Abstractive requests made, including context-overflow retries.
+    pub abstractive_attempts: usize,
+    /// Source memories the summary actually covers.
+    pub items_summarized: usize,
+    /// Oldest memories dropped to fit `SUMMARIZATION_MAX_PROMPT_TOKENS`.
+    pub items_trimmed: usize,
+    /// Label stored on the summary, if any.
+    pub label: Option<String>,
+    /// Topic stored on the summary, if any.
+    pub topic: Option<String>,
+}
+
+/// Tokenizer for the `SUMMARIZATION_MAX_PROMPT_TOKENS` check, when that cap is set.
+fn summary_token_counter(config: &Config, model: &str) -> Option<TokenCounter> {
+    config.summarization_max_prompt_tokens.and_then(|_| {
+        build_token_counter(EmbeddingProvider::Ollama, model, config.tokenizer_fallback).ok()
+    })
+}
+
+/// Abstractive summary along with the memories its prompt included.
+struct AbstractiveSummary {
+    text: String,
+    citations: Vec<qdrant::Citation>,
+    items_summarized: usize,
+    items_trimmed: usize,
+}
+
+/// Token ceiling checked before an abstractive prompt is sent.
+#[derive(Clone, Copy)]
+struct PromptBudget<'a> {
+    max_tokens: usize,
+    count_tokens: &'a (dyn Fn(&str) -> usize + Send + Sync),
+}
+
+/// Items and prompt settings for one abstractive summary.
+struct AbstractiveRequest<'a> {
+    model: &'a str,
+    project_id: &'a str,
+    time_range: &'a ProcSearchTimeRange,
+    max_words: usize,
+    items: &'a [EpisodicMemory],
+    budget: Option<PromptBudget<'a>>,
+    refinement: Option<&'a Refinement<'a>>,
+}
+
+/// Ask `client` for an abstractive summary of `request.items`.
+///
+/// With a `budget`, the oldest items are dropped up front until the prompt fits. When the prompt overflows the model context the items are halved (keeping even temporal
+/// coverage) and the request retried up to [`MAX_CONTEXT_RETRIES`] times. Any other failure,
+/// or running out of retries, yields `None` so the caller falls back to an extractive summary.
+/// The number of requests made is returned alongside.
+async fn generate_abstractive_summary(
+    client: &(dyn SummarizationClient + Send + Sync),
+    request: AbstractiveRequest<'_>,
+) -> (Option<AbstractiveSummary>, usize) {
+    let AbstractiveRequest {
+        model,
+        project_id,
+        time_range,
+        max_words,
+        items,
+        budget,
+        refinement,
+    } = request;
+    let mut included = items.to_vec();
+    let items_trimmed = budget.map_or(0, |budget| {
+        trim_to_prompt_budget(
+            project_id,
+            time_range,
+            max_words,
+            &mut included,
+            budget.max_tokens,
+            budget.count_tokens,
+            refinement,
+        )
+    });
+    if items_trimmed > 0 {
+        tracing::warn!(
+            trimmed = items_trimmed,
+            kept = included.len(),
+            "Summary prompt exceeded SUMMARIZATION_MAX_PROMPT_TOKENS; dropped the oldest memories"
+        );
+    }
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        let prompt =
+            build_abstractive_prompt(project_id, time_range, max_words, &included, refinement);
+        let result = client
+            .generate_summary(LlmSummarizationRequest {
+                model: model.to_string(),
+                prompt,
+                max_words,
+            })
+            .await;
+        match result {
+            Ok(text) => {
+                let summary = AbstractiveSummary {
+                    citations: resolve_citations(&text, &included),
+                    text,
+                    items_summarized: included.len(),
+                    items_trimmed,
+                };
+                return (Some(summary), attempts);
+            }
+            Err(SummarizationClientError::ContextExceeded(error))
+                if attempts <= MAX_CONTEXT_RETRIES && included.len() > 1 =>
+            {
+                included = halve_evenly(&included);
+                tracing::warn!(
+                    error = %error,
+                    attempt = attempts,
+                    items = included.len(),
+                    "Summary prompt exceeded the model context; retrying with fewer items"
+                );
+            }
+            Err(error) => {
+                tracing::warn!(
+                    error = %error,
+                    attempts,
+                    "Abstractive summarization failed; falling back to extractive"
+                );
+                return (None, attempts);
+            }
+        }
+    }
+}
+
+/// Query text after the `MAX_QUERY_TOKENS` guard.
+struct ShortenedQuery {
+    text: String,
+    truncated: bool,
+    compressed: bool,
+}
+
+/// Bring `query` within `MAX_QUERY_TOKENS`, measured with the embedding model's tokenizer.
+///
+/// `Llm` compression asks the summarization model for a one-sentence restatement; when no model
+/// is configured or the request fails, the query is truncated at a sentence boundary instead.
+/// A compressed query that is still over budget is truncated as well.
+async fn shorten_query(
+    config: &Config,
+    query: String,
+    compression: QueryCompression,
+) -> ShortenedQuery {
+    let unchanged = |text| ShortenedQuery {
+        text,
+        truncated: false,
+        compressed: false,
+    };
+    let count_tokens = match build_token_counter(
+        config.embedding_provider,
+        &config.embedding_model,
+        config.tokenizer_fallback,
+    ) {
+        Ok(counter) => counter,
+        Err(error) => {
+            tracing::warn!(error = %error, "Tokenizer unavailable; skipping the query length guard");
+            return unchanged(query);
+        }
+    };
+    let max_tokens = config.max_query_tokens;
+    let tokens = count_tokens(&query);
+    if tokens <= max_tokens {
+        return unchanged(query);
+    }
+
+    let mut shortened = unchanged(query);
+    if compression == QueryCompression::Llm
+        && let (Some(model), Some(client)) = (
+            config.summarization_model.as_deref(),
+            get_summarization_client(),
+        )
+        && let Some(compressed) = compress_query(client.as_ref(), model, &shortened.text).await
+    {
+        shortened.text = compressed;
+        shortened.compressed = true;
+    }
+    if let Some(truncated) = truncate_query(&shortened.text, max_tokens, count_tokens.as_ref()) {
+        shortened.text = truncated;
+        shortened.truncated = true;
+    }
+    tracing::info!(
+        tokens,
+        max_tokens,
+        truncated = shortened.truncated,
+        compressed = shortened.compressed,
+        "Shortened query_text over MAX_QUERY_TOKENS"
+    );
+    shortened
+}
+
+/// The range results must be re-checked against `timestamp` client-side.
+///
+/// A `timestamp_unix` filter lets points written before that mirror existed through unfiltered.
+fn local_time_range(args: &qdrant::SearchFilterArgs) -> Option<&qdrant::SearchTimeRange> {
+    args.time_range.as_ref().filter(|_| args.unix_time_range)
+}
+
+/// Whether `timestamp` falls inside `range`; no range admits everything, and a range rejects
+/// points without a parseable timestamp.
+fn within_time_range(timestamp: Option<&str>, range: Option<&qdrant::SearchTimeRange>) -> bool {
+    let Some(range) = range else {
+        return true;
+    };
+    let Some(instant) = timestamp.and_then(|value| parse_time_boundary(value, TimeBoundary::Start))
+    else {
+        return false;
+    };
+    let bound = |value: &Option<String>, boundary| {
+        value
+            .as_deref()
+            .and_then(|raw| parse_time_boundary(raw, boundary))
+    };
+    bound(&range.start, TimeBoundary::Start).is_none_or(|start| instant >= start)
+        && bound(&range.end, TimeBoundary::End).is_none_or(|end| instant <= end)
+}
+
+/// Normalize time-range boundaries to UTC RFC3339 so range filters compare consistently with
+/// stored payload timestamps. Unparseable values are forwarded untouched for Qdrant to reject.
+fn normalize_time_range(range: ProcSearchTimeRange) -> qdrant::SearchTimeRange {
+    let normalize = |value: Option<String>, boundary: TimeBoundary| {
+        value.map(|raw| normalize_time_boundary(&raw, boundary).unwrap_or(raw))
+    };
+    qdrant::SearchTimeRange {
+        start: normalize(range.start, TimeBoundary::Start),
+        end: normalize(range.end, TimeBoundary::End),
+    }
+}
+
+/// Whether every standard index in `summary` exists; refused indexes are an error when `strict`.
+fn check_payload_indexes(
+    collection_name: &str,
+    summary: PayloadIndexSummary,
+    strict: bool,
+) -> Result<bool, ProcessingError> {
+    if summary.failed.is_empty() {
+        return Ok(true);
+    }
+    if strict {
+        return Err(ProcessingError::PayloadIndexes {
+            collection: collection_name.to_string(),
+            failures: summary.failed,
+        });
+    }
+    Ok(false)
+}
 
 fn strategy_to_label(strategy: &Option<SummarizeStrategy>) -> String {
     match strategy {
@@ -684,3 +4595,4254 @@ fn strategy_to_label(strategy: &Option<SummarizeStrategy>) -> String {
         _ => "auto".into(),
     }
 }
+
+/// Describe the settings `process_and_index` applies with the derived chunk size and overlap.
+fn ingest_profile(config: &Config, chunk_size: usize, overlap: usize) -> IngestProfile {
+    IngestProfile {
+        embedding_provider: match config.embedding_provider {
+            EmbeddingProvider::Ollama => "ollama",
+            EmbeddingProvider::OpenAI => "openai",
+        }
+        .to_string(),
+        embedding_model: config.embedding_model.clone(),
+        embedding_dimension: config.embedding_dimension,
+        chunk_size,
+        chunk_overlap: overlap,
+        strategy: match config.text_splitter_strategy {
+            ChunkStrategy::Token => "semchunk",
+            ChunkStrategy::Code => "code",
+        },
+        tokenizer: tokenizer_name(&config.embedding_model),
+        dedupe: "chunk_hash",
+        version: env!("CARGO_PKG_VERSION"),
+    }
+}
+
+/// Anomalies found in a single stored payload.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct PayloadCheck {
+    missing_field: bool,
+    hash_mismatch: bool,
+    invalid_timestamp: bool,
+    /// Correct hash for a point whose stored `chunk_hash` is wrong or absent.
+    repaired_hash: Option<String>,
+    /// Seconds for a point whose `timestamp_unix` is absent or disagrees with `timestamp`.
+    unix_timestamp: Option<i64>,
+}
+
+fn inspect_payload(payload: &Map<String, Value>) -> PayloadCheck {
+    let missing_field = REQUIRED_PAYLOAD_FIELDS
+        .iter()
+        .any(|field| payload.get(*field).is_none_or(Value::is_null));
+    let invalid_timestamp = ["timestamp", "ingested_at"].iter().any(|field| {
+        payload.get(*field).is_some_and(|value| {
+            value.as_str().is_none_or(|raw| {
+                time::OffsetDateTime::parse(raw, &time::format_description::well_known::Rfc3339)
+                    .is_err()
+            })
+        })
+    });
+    let stored_hash = payload.get("chunk_hash").and_then(Value::as_str);
+    let expected_hash = payload
+        .get("text")
+        .and_then(Value::as_str)
+        .map(qdrant::compute_chunk_hash);
+    let hash_mismatch = matches!(
+        (stored_hash, expected_hash.as_deref()),
+        (Some(stored), Some(expected)) if stored != expected
+    );
+    let repaired_hash = expected_hash.filter(|expected| stored_hash != Some(expected.as_str()));
+    let unix_timestamp = payload
+        .get("timestamp")
+        .and_then(Value::as_str)
+        .and_then(qdrant::unix_seconds)
+        .filter(|seconds| {
+            payload
+                .get(qdrant::TIMESTAMP_UNIX_FIELD)
+                .and_then(Value::as_i64)
+                != Some(*seconds)
+        });
+    PayloadCheck {
+        missing_field,
+        hash_mismatch,
+        invalid_timestamp,
+        repaired_hash,
+        unix_timestamp,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{
+        EmbeddingProvider, EpisodicPartitioning, IngestFallback, NextEmbedding, ensure_test_config,
+    };
+    use crate::embedding::EmbeddingClientError;
+    use httpmock::{
+        Method::{DELETE, GET, POST, PUT},
+        MockServer,
+    };
+    use reqwest::Client;
+    use serde_json::json;
+    use std::collections::BTreeMap;
+
+    struct FixedEmbedding;
+
+    #[async_trait]
+    impl EmbeddingClient for FixedEmbedding {
+        async fn generate_embeddings(
+            &self,
+            texts: Vec<String>,
+        ) -> Result<Vec<Vec<f32>>, EmbeddingClientError> {
+            Ok(texts.iter().map(|_| vec![0.5, 0.5]).collect())
+        }
+    }
+
+    /// Mimics a broken provider build that emits NaN components.
+    struct NanEmbedding;
+
+    #[async_trait]
+    impl EmbeddingClient for NanEmbedding {
+        async fn generate_embeddings(
+            &self,
+            texts: Vec<String>,
+        ) -> Result<Vec<Vec<f32>>, EmbeddingClientError> {
+            Ok(texts.iter().map(|_| vec![f32::NAN, 0.5]).collect())
+        }
+    }
+
+    struct UnavailableEmbedding;
+
+    #[async_trait]
+    impl EmbeddingClient for UnavailableEmbedding {
+        async fn generate_embeddings(
+            &self,
+            _texts: Vec<String>,
+        ) -> Result<Vec<Vec<f32>>, EmbeddingClientError> {
+            Err(EmbeddingClientError::ProviderUnavailable(
+                "connection refused".into(),
+            ))
+        }
+    }
+
+    struct MisconfiguredEmbedding;
+
+    #[async_trait]
+    impl EmbeddingClient for MisconfiguredEmbedding {
+        async fn generate_embeddings(
+            &self,
+            _texts: Vec<String>,
+        ) -> Result<Vec<Vec<f32>>, EmbeddingClientError> {
+            Err(EmbeddingClientError::Configuration(
+                "OPENAI_API_KEY is not set".into(),
+            ))
+        }
+    }
+
+    /// Records every text it is asked to embed.
+    #[derive(Clone, Default)]
+    struct RecordingEmbedding(Arc<std::sync::Mutex<Vec<String>>>);
+
+    #[async_trait]
+    impl EmbeddingClient for RecordingEmbedding {
+        async fn generate_embeddings(
+            &self,
+            texts: Vec<String>,
+        ) -> Result<Vec<Vec<f32>>, EmbeddingClientError> {
+            let dimension = get_config().embedding_dimension;
+            let vectors = texts.iter().map(|_| vec![0.1; dimension]).collect();
+            self.0.lock().expect("recorded texts").extend(texts);
+            Ok(vectors)
+        }
+    }
+
+    fn service_for(server: &MockServer) -> ProcessingService {
+        service_with(server, Box::new(FixedEmbedding), IngestFallback::None)
+    }
+
+    fn service_with(
+        server: &MockServer,
+        embedding_client: Box<dyn EmbeddingClient + Send + Sync>,
+        ingest_fallback: IngestFallback,
+    ) -> ProcessingService {
+        ProcessingService {
+            embedding_client,
+            qdrant_service: QdrantService {
+                client: Client::builder()
+                    .user_agent("rusty-mem-test")
+                    .build()
+                    .expect("client"),
+                base_url: server.base_url(),
+                read_base_url: None,
+                api_key: None,
+                api_keys: BTreeMap::new(),
+                capabilities: Default::default(),
+                #[cfg(feature = "fault-injection")]
+                faults: None,
+            },
+            metrics: Arc::new(CodeMetrics::new()),
+            ingest_fallback,
+            transcript_policy: TranscriptPolicy::Warn,
+            memory_type_rules: Vec::new(),
+            embedding_prefixes: EmbeddingPrefixes::default(),
+            audit: AuditLogger::disabled(),
+            sync_state: None,
+            strict_payload_indexes: false,
+            episodic_partitioning: EpisodicPartitioning::Off,
+            indexed_collections: Mutex::default(),
+            collection_distances: Mutex::default(),
+            indexing_statuses: Mutex::default(),
+            listing_cache: ListingCache::new(Duration::ZERO),
+            migration: None,
+        }
+    }
+
+    fn summarize_request(mode: ConsolidateMode) -> SummarizeRequest {
+        SummarizeRequest {
+            project_id: Some("default".into()),
+            memory_type: None,
+            tags: None,
+            time_range: ProcSearchTimeRange {
+                start: Some("2025-01-01T00:00:00Z".into()),
+                end: Some("2025-01-02T00:00:00Z".into()),
+            },
+            limit: None,
+            strategy: Some(SummarizeStrategy::Extractive),
+            provider: None,
+            model: None,
+            max_words: Some(50),
+            collection: Some("demo".into()),
+            consolidate: Some(mode),
+            label: None,
+            topic: None,
+        }
+    }
+
+    /// Mock the reads and collection bookkeeping shared by every summarize run.
+    async fn mock_summarize_reads(server: &MockServer) {
+        server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/scroll")
+                    .body_contains("episodic");
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": {
+                        "points": [
+                            { "id": "ep-1", "payload": {
+                                "text": "Fixed the login bug",
+                                "timestamp": "2025-01-01T09:00:00Z",
+                                "tags": ["auth"]
+                            } },
+                            { "id": "ep-2", "payload": {
+                                "text": "Shipped the release",
+                                "timestamp": "2025-01-01T17:00:00Z"
+                            } }
+                        ],
+                        "next_page_offset": null
+                    }
+                }));
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/scroll")
+                    .body_contains("summary:");
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": { "points": [], "next_page_offset": null }
+                }));
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path("/collections/demo");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method(PUT).path("/collections/demo/index");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+    }
+
+    #[tokio::test]
+    async fn extractive_summary_stores_citation_map() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        mock_summarize_reads(&server).await;
+        let upsert = server
+            .mock_async(|when, then| {
+                when.method(PUT)
+                    .path("/collections/demo/points")
+                    .body_contains(r#""citations":[{"marker":1,"memory_id":"ep-1"},{"marker":2,"memory_id":"ep-2"}]"#);
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+
+        let request = SummarizeRequest {
+            consolidate: None,
+            ..summarize_request(ConsolidateMode::Delete)
+        };
+        let outcome = service_for(&server)
+            .summarize_memories(request)
+            .await
+            .expect("summary");
+
+        upsert.assert();
+        assert!(outcome.summary.contains("Fixed the login bug [1]"));
+        assert_eq!(
+            outcome
+                .citations
+                .iter()
+                .map(|citation| citation.memory_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["ep-1", "ep-2"]
+        );
+    }
+
+    #[tokio::test]
+    async fn health_probe_uses_healthz_and_looks_up_only_the_default_collection() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        let healthz = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/healthz");
+                then.status(200).body("healthz check passed");
+            })
+            .await;
+        let default_path = format!("/collections/{}", get_config().qdrant_collection_name);
+        let default_collection = server
+            .mock_async(|when, then| {
+                when.method(GET).path(default_path);
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+        let listing = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/collections");
+                then.status(403).body("forbidden for this key");
+            })
+            .await;
+
+        let snapshot = service_for(&server).qdrant_health().await;
+
+        healthz.assert_hits_async(1).await;
+        default_collection.assert_hits_async(1).await;
+        listing.assert_hits_async(0).await;
+        assert!(snapshot.reachable);
+        assert!(snapshot.default_collection_present);
+        assert_eq!(snapshot.probe, "healthz");
+        assert!(snapshot.error.is_none());
+    }
+
+    #[tokio::test]
+    async fn health_probe_falls_back_to_listing_collections_without_healthz() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path("/healthz");
+                then.status(404);
+            })
+            .await;
+        let listing = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/collections");
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": { "collections": [{ "name": "other" }] }
+                }));
+            })
+            .await;
+
+        let snapshot = service_for(&server).qdrant_health().await;
+
+        listing.assert_hits_async(1).await;
+        assert!(snapshot.reachable);
+        assert!(!snapshot.default_collection_present);
+        assert_eq!(snapshot.probe, "collections");
+    }
+
+    #[tokio::test]
+    async fn failing_healthz_reports_qdrant_unreachable() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path("/healthz");
+                then.status(503).body("starting");
+            })
+            .await;
+
+        let snapshot = service_for(&server).qdrant_health().await;
+
+        assert!(!snapshot.reachable);
+        assert_eq!(snapshot.probe, "healthz");
+        assert!(snapshot.error.expect("error").contains("starting"));
+    }
+
+    #[tokio::test]
+    async fn summary_label_and_topic_are_stored_and_findable() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        mock_summarize_reads(&server).await;
+        let upsert = server
+            .mock_async(|when, then| {
+                when.method(PUT)
+                    .path("/collections/demo/points")
+                    .body_contains(r#""label":"weekly-review""#)
+                    .body_contains(r#""topic":"auth""#);
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+
+        let request = SummarizeRequest {
+            consolidate: None,
+            label: Some("weekly-review".into()),
+            topic: Some("auth".into()),
+            ..summarize_request(ConsolidateMode::Delete)
+        };
+        let service = service_for(&server);
+        let outcome = service.summarize_memories(request).await.expect("summary");
+        upsert.assert();
+        assert_eq!(outcome.label.as_deref(), Some("weekly-review"));
+        assert_eq!(outcome.topic.as_deref(), Some("auth"));
+
+        let find = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/scroll")
+                    .body_contains(r#"{"key":"label","match":{"value":"weekly-review"}}"#)
+                    .body_contains(r#"{"key":"topic","match":{"value":"auth"}}"#)
+                    .body_contains(r#"{"key":"tags","match":{"any":["summary"]}}"#);
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": {
+                        "points": [{ "id": "sum-1", "payload": {
+                            "text": "Fixed the login bug [1]",
+                            "project_id": "default",
+                            "label": "weekly-review",
+                            "topic": "auth",
+                            "timestamp": "2025-01-03T00:00:00Z",
+                            "summary_key": "abc",
+                            "source_memory_ids": ["ep-1", "ep-2"]
+                        } }],
+                        "next_page_offset": null
+                    }
+                }));
+            })
+            .await;
+        let summaries = service
+            .find_summaries(
+                "demo",
+                SummaryQuery {
+                    project_id: Some("default".into()),
+                    label: Some("weekly-review".into()),
+                    topic: Some("auth".into()),
+                    limit: 10,
+                    ..SummaryQuery::default()
+                },
+            )
+            .await
+            .expect("summaries");
+
+        find.assert();
+        assert_eq!(
+            summaries,
+            vec![SummaryRecord {
+                memory_id: "sum-1".into(),
+                summary: "Fixed the login bug [1]".into(),
+                project_id: Some("default".into()),
+                label: Some("weekly-review".into()),
+                topic: Some("auth".into()),
+                timestamp: Some("2025-01-03T00:00:00Z".into()),
+                summary_key: Some("abc".into()),
+                source_memory_ids: vec!["ep-1".into(), "ep-2".into()],
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn check_summaries_reports_partially_orphaned_summaries() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/scroll")
+                    .body_contains(r#"{"key":"tags","match":{"any":["summary"]}}"#);
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": {
+                        "points": [
+                            { "id": "sum-1", "payload": {
+                                "project_id": "default",
+                                "tags": ["summary"],
+                                "source_memory_ids": ["ep-1", "ep-2"]
+                            } },
+                            { "id": "sum-2", "payload": {
+                                "project_id": "default",
+                                "tags": ["summary"],
+                                "source_memory_ids": ["ep-1"]
+                            } }
+                        ],
+                        "next_page_offset": null
+                    }
+                }));
+            })
+            .await;
+        let retrieve = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points")
+                    .json_body(json!({
+                        "ids": ["ep-1", "ep-2"],
+                        "with_payload": false,
+                        "with_vector": false
+                    }));
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": [{ "id": "ep-1" }]
+                }));
+            })
+            .await;
+        let retag = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/batch")
+                    .json_body(json!({ "operations": [{ "set_payload": {
+                        "payload": { "tags": ["summary", "summary:orphaned"] },
+                        "points": ["sum-1"]
+                    } }] }));
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": [] }));
+            })
+            .await;
+
+        let outcome = service_for(&server)
+            .check_summaries("demo", None, true)
+            .await
+            .expect("check");
+
+        retrieve.assert_async().await;
+        retag.assert_async().await;
+        assert_eq!(outcome.checked, 2);
+        assert_eq!(outcome.dangling_references, 1);
+        assert_eq!(outcome.tagged, 1);
+        assert_eq!(
+            outcome.orphaned,
+            [OrphanedSummary {
+                memory_id: "sum-1".into(),
+                project_id: Some("default".into()),
+                source_count: 2,
+                missing_source_memory_ids: vec!["ep-2".into()],
+            }]
+        );
+        assert!(outcome.orphaned[0].is_partial());
+    }
+
+    #[tokio::test]
+    async fn rerunning_a_summary_with_a_new_label_retags_it() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/scroll")
+                    .body_contains("episodic");
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": {
+                        "points": [{ "id": "ep-1", "payload": {
+                            "text": "Fixed the login bug",
+                            "timestamp": "2025-01-01T09:00:00Z"
+                        } }],
+                        "next_page_offset": null
+                    }
+                }));
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/scroll")
+                    .body_contains("summary:");
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": {
+                        "points": [{ "id": "sum-1", "payload": {
+                            "text": "Fixed the login bug [1]",
+                            "label": "draft",
+                            "topic": "auth"
+                        } }],
+                        "next_page_offset": null
+                    }
+                }));
+            })
+            .await;
+        let retag = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/batch")
+                    .json_body(json!({ "operations": [
+                        { "set_payload": { "payload": { "label": "final" }, "points": ["sum-1"] } }
+                    ] }));
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+
+        let request = SummarizeRequest {
+            consolidate: None,
+            label: Some("final".into()),
+            topic: Some("auth".into()),
+            ..summarize_request(ConsolidateMode::Delete)
+        };
+        let outcome = service_for(&server)
+            .summarize_memories(request)
+            .await
+            .expect("summary");
+
+        retag.assert();
+        assert_eq!(outcome.upserted_memory_id, "sum-1");
+        assert_eq!(outcome.label.as_deref(), Some("final"));
+        assert_eq!(outcome.topic.as_deref(), Some("auth"));
+    }
+
+    fn episodic_items(count: usize) -> Vec<EpisodicMemory> {
+        (1..=count)
+            .map(|index| {
+                EpisodicMemory::new(
+                    format!("ep-{index}"),
+                    format!("Note number {index}"),
+                    Some(format!("2025-01-{index:02}T00:00:00Z")),
+                )
+            })
+            .collect()
+    }
+
+    /// Mock retrieval of a stored summary `summary-1` and its two source memories.
+    async fn mock_refine_reads(server: &MockServer, tags: Value) {
+        server
+            .mock_async(move |when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points")
+                    .body_contains("summary-1");
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": [{ "id": "summary-1", "payload": {
+                        "text": "Login work happened [1].",
+                        "tags": tags,
+                        "project_id": "default",
+                        "source_memory_ids": ["ep-1", "ep-2", "ep-gone"]
+                    } }]
+                }));
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points")
+                    .body_contains("ep-1");
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": [
+                        { "id": "ep-2", "payload": {
+                            "text": "Shipped the release",
+                            "timestamp": "2025-01-01T17:00:00Z"
+                        } },
+                        { "id": "ep-1", "payload": {
+                            "text": "Fixed the login bug",
+                            "timestamp": "2025-01-01T09:00:00Z"
+                        } }
+                    ]
+                }));
+            })
+            .await;
+    }
+
+    fn refine_request() -> RefineSummaryRequest {
+        RefineSummaryRequest {
+            memory_id: "summary-1".into(),
+            feedback: "Mention the release".into(),
+            max_words: Some(50),
+            model: None,
+            collection: Some("demo".into()),
+        }
+    }
+
+    #[tokio::test]
+    async fn refine_summary_regenerates_text_and_bumps_revision_in_place() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        mock_refine_reads(&server, json!(["summary", "summary:default"])).await;
+        let generate = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/api/generate")
+                    .body_contains("Login work happened [1].")
+                    .body_contains("Mention the release");
+                then.status(200).json_body(json!({
+                    "response": "Fixed login [1] and shipped the release [2].",
+                    "done": true
+                }));
+            })
+            .await;
+        let vectors = server
+            .mock_async(|when, then| {
+                when.method(PUT)
+                    .path("/collections/demo/points/vectors")
+                    .json_body(json!({ "points": [{ "id": "summary-1", "vector": [0.5, 0.5] }] }));
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+        let payload = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/batch")
+                    .body_contains(r#""revision":2"#)
+                    .body_contains("shipped the release [2]");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": [] }));
+            })
+            .await;
+
+        let client = crate::summarization::ollama_summarization_client(server.base_url(), 0, None);
+        let outcome = service_for(&server)
+            .refine_summary_with(client.as_ref(), "llama", refine_request())
+            .await
+            .expect("refined summary");
+
+        generate.assert_hits_async(1).await;
+        vectors.assert_hits_async(1).await;
+        payload.assert_hits_async(1).await;
+        assert_eq!(outcome.memory_id, "summary-1");
+        assert_eq!(outcome.revision, 2);
+        assert_eq!(outcome.source_memory_ids, vec!["ep-1", "ep-2"]);
+        assert_eq!(outcome.missing_source_memory_ids, vec!["ep-gone"]);
+        assert_eq!(
+            outcome
+                .citations
+                .iter()
+                .map(|citation| citation.memory_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["ep-1", "ep-2"]
+        );
+    }
+
+    #[tokio::test]
+    async fn refine_summary_rejects_points_that_are_not_summaries() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        mock_refine_reads(&server, json!(["notes"])).await;
+
+        let client = crate::summarization::ollama_summarization_client(server.base_url(), 0, None);
+        let error = service_for(&server)
+            .refine_summary_with(client.as_ref(), "llama", refine_request())
+            .await
+            .expect_err("not a summary");
+
+        assert!(matches!(error, SummarizeError::NotASummary(ref id) if id == "summary-1"));
+        assert_eq!(error.kind(), ErrorKind::InvalidRequest);
+    }
+
+    #[tokio::test]
+    async fn refine_summary_leaves_the_summary_untouched_when_the_provider_fails() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        mock_refine_reads(&server, json!(["summary"])).await;
+        server
+            .mock_async(|when, then| {
+                when.method(POST).path("/api/generate");
+                then.status(500).body("model not loaded");
+            })
+            .await;
+        let vectors = server
+            .mock_async(|when, then| {
+                when.method(PUT).path("/collections/demo/points/vectors");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+
+        let client = crate::summarization::ollama_summarization_client(server.base_url(), 0, None);
+        let error = service_for(&server)
+            .refine_summary_with(client.as_ref(), "llama", refine_request())
+            .await
+            .expect_err("provider failure");
+
+        assert!(matches!(error, SummarizeError::ProviderUnavailable));
+        assert_eq!(error.kind(), ErrorKind::Transient);
+        vectors.assert_hits_async(0).await;
+    }
+
+    async fn summarize_with_ollama(
+        server: &MockServer,
+        items: &[EpisodicMemory],
+    ) -> (Option<AbstractiveSummary>, usize) {
+        let client = crate::summarization::ollama_summarization_client(server.base_url(), 0, None);
+        generate_abstractive_summary(
+            client.as_ref(),
+            AbstractiveRequest {
+                model: "llama",
+                project_id: "default",
+                time_range: &ProcSearchTimeRange {
+                    start: Some("2025-01-01T00:00:00Z".into()),
+                    end: Some("2025-01-31T00:00:00Z".into()),
+                },
+                max_words: 100,
+                items,
+                budget: None,
+                refinement: None,
+            },
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn oversized_prompts_drop_the_oldest_memories_before_sending() {
+        let server = MockServer::start_async().await;
+        let generate = server
+            .mock_async(|when, then| {
+                when.method(POST).path("/api/generate").matches(|request| {
+                    let body = String::from_utf8_lossy(request.body.as_deref().unwrap_or_default());
+                    !body.contains("Note number 1") && !body.contains("Note number 2")
+                });
+                then.status(200).json_body(json!({
+                    "response": "Work continued [1] and wrapped up [2].",
+                    "done": true
+                }));
+            })
+            .await;
+        let items = episodic_items(4);
+        let time_range = ProcSearchTimeRange {
+            start: Some("2025-01-01T00:00:00Z".into()),
+            end: Some("2025-01-31T00:00:00Z".into()),
+        };
+        let count_words = |prompt: &str| prompt.split_whitespace().count();
+        let max_tokens = count_words(&build_abstractive_prompt(
+            "default",
+            &time_range,
+            100,
+            &items[2..],
+            None,
+        ));
+
+        let client = crate::summarization::ollama_summarization_client(server.base_url(), 0, None);
+        let (summary, attempts) = generate_abstractive_summary(
+            client.as_ref(),
+            AbstractiveRequest {
+                model: "llama",
+                project_id: "default",
+                time_range: &time_range,
+                max_words: 100,
+                items: &items,
+                budget: Some(PromptBudget {
+                    max_tokens,
+                    count_tokens: &count_words,
+                }),
+                refinement: None,
+            },
+        )
+        .await;
+
+        generate.assert_hits_async(1).await;
+        assert_eq!(attempts, 1);
+        let summary = summary.expect("abstractive summary");
+        assert_eq!(summary.items_trimmed, 2);
+        assert_eq!(summary.items_summarized, 2);
+        assert_eq!(
+            summary
+                .citations
+                .iter()
+                .map(|citation| citation.memory_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["ep-3", "ep-4"]
+        );
+    }
+
+    #[tokio::test]
+    async fn context_errors_retry_abstractive_summary_with_half_the_items() {
+        let server = MockServer::start_async().await;
+        let overflow = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/api/generate")
+                    .body_contains("Note number 3");
+                then.status(500).json_body(json!({
+                    "error": "prompt too long; exceeded max context length by 512 tokens"
+                }));
+            })
+            .await;
+        let success = server
+            .mock_async(|when, then| {
+                when.method(POST).path("/api/generate").matches(|request| {
+                    !String::from_utf8_lossy(request.body.as_deref().unwrap_or_default())
+                        .contains("Note number 3")
+                });
+                then.status(200).json_body(json!({
+                    "response": "Work started [1] and wrapped up [2].",
+                    "done": true
+                }));
+            })
+            .await;
+
+        let (summary, attempts) = summarize_with_ollama(&server, &episodic_items(4)).await;
+
+        overflow.assert_hits_async(1).await;
+        success.assert_hits_async(1).await;
+        assert_eq!(attempts, 2);
+        let summary = summary.expect("abstractive summary");
+        assert_eq!(summary.items_summarized, 2);
+        assert_eq!(
+            summary
+                .citations
+                .iter()
+                .map(|citation| citation.memory_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["ep-1", "ep-4"]
+        );
+    }
+
+    #[tokio::test]
+    async fn abstractive_summary_gives_up_after_context_retries() {
+        let server = MockServer::start_async().await;
+        let overflow = server
+            .mock_async(|when, then| {
+                when.method(POST).path("/api/generate");
+                then.status(400)
+                    .body("input length exceeds the model context");
+            })
+            .await;
+
+        let (summary, attempts) = summarize_with_ollama(&server, &episodic_items(8)).await;
+
+        assert!(summary.is_none());
+        assert_eq!(attempts, 1 + MAX_CONTEXT_RETRIES);
+        overflow.assert_hits_async(3).await;
+    }
+
+    #[tokio::test]
+    async fn other_abstractive_failures_do_not_retry() {
+        let server = MockServer::start_async().await;
+        let failure = server
+            .mock_async(|when, then| {
+                when.method(POST).path("/api/generate");
+                then.status(500).body("model not loaded");
+            })
+            .await;
+
+        let (summary, attempts) = summarize_with_ollama(&server, &episodic_items(4)).await;
+
+        assert!(summary.is_none());
+        assert_eq!(attempts, 1);
+        failure.assert_hits_async(1).await;
+    }
+
+    #[tokio::test]
+    async fn consolidate_delete_removes_sources_after_summary_write() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        mock_summarize_reads(&server).await;
+        let upsert = server
+            .mock_async(|when, then| {
+                when.method(PUT).path("/collections/demo/points");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+        let delete = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/delete")
+                    .json_body(json!({ "points": ["ep-1", "ep-2"] }));
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+
+        let outcome = service_for(&server)
+            .summarize_memories(summarize_request(ConsolidateMode::Delete))
+            .await
+            .expect("summary");
+
+        upsert.assert();
+        delete.assert();
+        assert_eq!(outcome.consolidated, Some(ConsolidateMode::Delete));
+        assert_eq!(outcome.source_memory_ids, vec!["ep-1", "ep-2"]);
+    }
+
+    #[tokio::test]
+    async fn consolidate_archive_retags_sources_after_summary_write() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        mock_summarize_reads(&server).await;
+        let upsert = server
+            .mock_async(|when, then| {
+                when.method(PUT).path("/collections/demo/points");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+        let archive = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/batch")
+                    .json_body(json!({ "operations": [
+                        { "set_payload": {
+                            "payload": { "tags": ["auth", "archived"], "memory_type": "archived" },
+                            "points": ["ep-1"]
+                        } },
+                        { "set_payload": {
+                            "payload": { "tags": ["archived"], "memory_type": "archived" },
+                            "points": ["ep-2"]
+                        } }
+                    ] }));
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": [] }));
+            })
+            .await;
+
+        let outcome = service_for(&server)
+            .summarize_memories(summarize_request(ConsolidateMode::Archive))
+            .await
+            .expect("summary");
+
+        upsert.assert();
+        archive.assert();
+        assert_eq!(outcome.consolidated, Some(ConsolidateMode::Archive));
+    }
+
+    #[tokio::test]
+    async fn consolidation_is_skipped_when_summary_write_fails() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        mock_summarize_reads(&server).await;
+        let upsert = server
+            .mock_async(|when, then| {
+                when.method(PUT).path("/collections/demo/points");
+                then.status(500).body("disk full");
+            })
+            .await;
+        let delete = server
+            .mock_async(|when, then| {
+                when.method(POST).path("/collections/demo/points/delete");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+        let archive = server
+            .mock_async(|when, then| {
+                when.method(POST).path("/collections/demo/points/batch");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": [] }));
+            })
+            .await;
+
+        let service = service_for(&server);
+        for mode in [ConsolidateMode::Delete, ConsolidateMode::Archive] {
+            let error = service
+                .summarize_memories(summarize_request(mode))
+                .await
+                .unwrap_err();
+            assert!(matches!(error, SummarizeError::Qdrant(_)));
+        }
+
+        upsert.assert_hits(2);
+        delete.assert_hits(0);
+        archive.assert_hits(0);
+    }
+
+    #[tokio::test]
+    async fn warm_collection_skips_polling_when_ready_and_runs_warmup_queries() {
+        let server = MockServer::start_async().await;
+        let info = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/collections/demo");
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": {
+                        "status": "green",
+                        "points_count": 12,
+                        "indexed_vectors_count": 12
+                    }
+                }));
+            })
+            .await;
+        let search = server
+            .mock_async(|when, then| {
+                when.method(POST).path("/collections/demo/points/query");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": [] }));
+            })
+            .await;
+
+        let outcome = service_for(&server)
+            .warm_collection("demo", Duration::from_secs(1), 3)
+            .await
+            .expect("warm-up");
+
+        info.assert_hits(1);
+        search.assert_hits(3);
+        assert!(outcome.before.is_ready());
+        assert_eq!(outcome.after, outcome.before);
+        assert_eq!(outcome.warmup_queries, 3);
+    }
+
+    #[tokio::test]
+    async fn vector_dimension_follows_the_collection_not_the_global_setting() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        let schema = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/collections/legacy");
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": {
+                        "status": "green",
+                        "config": { "params": { "vectors": { "size": 384, "distance": "Cosine" } } }
+                    }
+                }));
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method(PUT).path("/collections/legacy/index");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+
+        let dimension = service_for(&server)
+            .vector_dimension("legacy")
+            .await
+            .expect("dimension");
+
+        assert_eq!(dimension, 384);
+        assert_ne!(dimension, get_config().embedding_dimension);
+        schema.assert_hits_async(2).await;
+    }
+
+    #[tokio::test]
+    async fn indexing_status_reuses_the_last_fetch_per_collection() {
+        let server = MockServer::start_async().await;
+        let info = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/collections/demo");
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": {
+                        "status": "yellow",
+                        "points_count": 40_000,
+                        "indexed_vectors_count": 10_000
+                    }
+                }));
+            })
+            .await;
+
+        let service = service_for(&server);
+        let first = service.indexing_status("demo").await.expect("status");
+        let second = service.indexing_status("demo").await.expect("status");
+
+        info.assert_hits(1);
+        assert_eq!(first, second);
+        assert_eq!(first.unindexed_backlog(), 30_000);
+    }
+
+    #[tokio::test]
+    async fn apply_manifest_creates_skips_and_reports_drift() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        let existing = |size: u64| {
+            json!({
+                "status": "ok",
+                "result": {
+                    "status": "green",
+                    "config": { "params": { "vectors": { "size": size, "distance": "Cosine" } } },
+                    "payload_schema": { "source_uri": { "data_type": "keyword" } }
+                }
+            })
+        };
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path("/collections/fresh");
+                then.status(404)
+                    .json_body(json!({ "status": { "error": "Not found" } }));
+            })
+            .await;
+        let same_body = existing(4);
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path("/collections/same");
+                then.status(200).json_body(same_body);
+            })
+            .await;
+        let drifted_body = existing(8);
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path("/collections/drifted");
+                then.status(200).json_body(drifted_body);
+            })
+            .await;
+        let create = server
+            .mock_async(|when, then| {
+                when.method(PUT)
+                    .path("/collections/fresh")
+                    .json_body_partial(r#"{ "vectors": { "size": 4, "distance": "Dot" } }"#);
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": true }));
+            })
+            .await;
+        let custom_index = server
+            .mock_async(|when, then| {
+                when.method(PUT)
+                    .path("/collections/fresh/index")
+                    .json_body_partial(
+                        r#"{ "field_name": "source_uri", "field_schema": "keyword" }"#,
+                    );
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method(PUT).path("/collections/fresh/index");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+        let untouched = server
+            .mock_async(|when, then| {
+                when.method(PUT).path_contains("/collections/same");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+        let manifest = CollectionManifest::parse(
+            r#"
+[[collections]]
+name = "fresh"
+vector_size = 4
+distance = "Dot"
+payload_indexes = { source_uri = "keyword" }
+
+[[collections]]
+name = "same"
+vector_size = 4
+payload_indexes = { source_uri = "keyword" }
+
+[[collections]]
+name = "drifted"
+vector_size = 4
+"#,
+        )
+        .expect("manifest");
+
+        let results = service_for(&server)
+            .apply_manifest(&manifest)
+            .await
+            .expect("apply");
+
+        create.assert_async().await;
+        custom_index.assert_async().await;
+        assert_eq!(untouched.hits_async().await, 0);
+        let actions: Vec<(&str, &ManifestAction)> = results
+            .iter()
+            .map(|result| (result.collection.as_str(), &result.action))
+            .collect();
+        assert_eq!(
+            actions,
+            [
+                ("fresh", &ManifestAction::Created),
+                ("same", &ManifestAction::Unchanged),
+                (
+                    "drifted",
+                    &ManifestAction::Conflict {
+                        reasons: vec!["vector size is 8, manifest declares 4".to_string()],
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn storage_estimate_multiplies_points_by_vector_and_sampled_payload_bytes() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path("/collections/demo");
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": {
+                        "status": "green",
+                        "points_count": 1000,
+                        "config": { "params": { "vectors": {
+                            "size": 4, "distance": "Cosine", "datatype": "float16"
+                        } } }
+                    }
+                }));
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method(POST).path("/collections/demo/points/scroll");
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": {
+                        "points": [
+                            { "id": 1, "payload": { "text": "abcd" } },
+                            { "id": 2, "payload": { "text": "abcdefgh" } }
+                        ],
+                        "next_page_offset": 3
+                    }
+                }));
+            })
+            .await;
+
+        let estimate = service_for(&server)
+            .estimate_storage("demo")
+            .await
+            .expect("estimate");
+
+        // 4 × float16 = 8 vector bytes; payloads serialize to 15 and 19 bytes (mean 17).
+        assert_eq!(
+            estimate,
+            StorageEstimate {
+                points_count: 1000,
+                vector_size: 4,
+                vector_datatype: "float16".to_string(),
+                vector_bytes: 8,
+                sampled_points: 2,
+                average_payload_bytes: 17,
+                estimated_bytes: 25_000,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn storage_stats_count_and_sample_only_the_requested_project() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path("/collections/demo");
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": {
+                        "status": "yellow",
+                        "points_count": 1000,
+                        "segments_count": 5,
+                        "config": { "params": { "vectors": { "size": 4, "distance": "Cosine" } } }
+                    }
+                }));
+            })
+            .await;
+        let project_filter = json!({
+            "must": [{ "key": "project_id", "match": { "value": "alpha" } }]
+        });
+        let count = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/count")
+                    .json_body_partial(json!({ "filter": project_filter }).to_string());
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": { "count": 30 } }));
+            })
+            .await;
+        let sample = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/scroll")
+                    .json_body_partial(
+                        json!({ "limit": DESCRIBE_SAMPLE_SIZE, "filter": project_filter })
+                            .to_string(),
+                    );
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": {
+                        "points": [{ "id": 1, "payload": { "text": "abcd" } }],
+                        "next_page_offset": null
+                    }
+                }));
+            })
+            .await;
+
+        let report = service_for(&server)
+            .storage_stats(Some("demo"), Some("alpha"))
+            .await
+            .expect("stats");
+
+        count.assert_async().await;
+        sample.assert_async().await;
+        // 30 points × (4 × float32 = 16 vector bytes + 15 payload bytes).
+        let stats = &report.collections[0];
+        assert_eq!(stats.project_id.as_deref(), Some("alpha"));
+        assert_eq!(stats.points_count, 30);
+        assert_eq!(stats.payload_bytes, 450);
+        assert_eq!(stats.vector_bytes, 480);
+        assert_eq!(stats.segments_count, Some(5));
+        assert_eq!(report.total_estimated_bytes, 930);
+        assert!(report.approximate);
+    }
+
+    #[tokio::test]
+    async fn get_document_orders_latest_chunks_and_strips_overlap() {
+        let server = MockServer::start_async().await;
+        let scroll = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/scroll")
+                    .json_body_partial(
+                        r#"{"filter":{"must":[{"key":"source_uri","match":{"value":"file:///notes.md"}}]}}"#,
+                    );
+                let chunk = |id: &str, doc: &str, index: u64, at: &str, text: &str| {
+                    json!({ "id": id, "payload": {
+                        "doc_id": doc,
+                        "chunk_index": index,
+                        "ingested_at": at,
+                        "source_uri": "file:///notes.md",
+                        "ingest_profile": "ollama:nomic@768 chunk=4+1 semchunk",
+                        "text": text
+                    } })
+                };
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": {
+                        "points": [
+                            chunk("c", "new", 2, "2025-02-01T00:00:00Z", "beta gamma delta"),
+                            chunk("x", "old", 0, "2025-01-01T00:00:00Z", "stale text"),
+                            chunk("a", "new", 0, "2025-02-01T00:00:00Z", "alpha beta"),
+                            { "id": "legacy", "payload": { "text": "no doc id" } }
+                        ],
+                        "next_page_offset": null
+                    }
+                }));
+            })
+            .await;
+
+        let document = service_for(&server)
+            .get_document(
+                "demo",
+                DocumentSelector::LatestForSource {
+                    source_uri: "file:///notes.md".to_string(),
+                    project_id: None,
+                },
+            )
+            .await
+            .expect("get document")
+            .expect("document found");
+
+        scroll.assert_async().await;
+        assert_eq!(document.doc_id, "new");
+        assert_eq!(document.text, "alpha beta gamma delta");
+        let ids: Vec<&str> = document
+            .chunks
+            .iter()
+            .map(|chunk| chunk.id.as_str())
+            .collect();
+        assert_eq!(ids, ["a", "c"]);
+        assert_eq!(document.chunks[1].overlap_stripped, "beta".len());
+        assert_eq!(document.missing_chunk_indexes, [1]);
+    }
+
+    #[tokio::test]
+    async fn get_document_without_tracked_chunks_is_none() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(POST).path("/collections/demo/points/scroll");
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": { "points": [], "next_page_offset": null }
+                }));
+            })
+            .await;
+
+        let document = service_for(&server)
+            .get_document("demo", DocumentSelector::DocId("missing".to_string()))
+            .await
+            .expect("get document");
+        assert!(document.is_none());
+    }
+
+    async fn mock_demo_partitions(server: &MockServer) {
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path("/collections");
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": { "collections": [
+                        { "name": "demo" },
+                        { "name": "demo-2025-03" }
+                    ] }
+                }));
+            })
+            .await;
+    }
+
+    async fn mock_scroll_page(server: &MockServer, collection: &str, points: Value) {
+        server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path(format!("/collections/{collection}/points/scroll"));
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": { "points": points, "next_page_offset": null }
+                }));
+            })
+            .await;
+    }
+
+    #[tokio::test]
+    async fn get_document_finds_episodic_documents_in_their_partition() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        mock_demo_partitions(&server).await;
+        mock_scroll_page(&server, "demo", json!([])).await;
+        mock_scroll_page(
+            &server,
+            "demo-2025-03",
+            json!([
+                { "id": "b", "payload": { "doc_id": "standup", "chunk_index": 1, "text": "then lunch" } },
+                { "id": "a", "payload": { "doc_id": "standup", "chunk_index": 0, "text": "coffee first" } }
+            ]),
+        )
+        .await;
+
+        let document = service_for(&server)
+            .with_monthly_partitions()
+            .get_document("demo", DocumentSelector::DocId("standup".to_string()))
+            .await
+            .expect("get document")
+            .expect("document found in the partition");
+
+        assert_eq!(document.doc_id, "standup");
+        assert_eq!(document.text, "coffee first then lunch");
+    }
+
+    #[tokio::test]
+    async fn link_memories_writes_each_end_in_the_collection_that_stores_it() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        mock_demo_partitions(&server).await;
+        mock_scroll_page(
+            &server,
+            "demo",
+            json!([{ "id": "p-new", "payload": { "memory_id": "new" } }]),
+        )
+        .await;
+        mock_scroll_page(
+            &server,
+            "demo-2025-03",
+            json!([{ "id": "p-old", "payload": { "memory_id": "old" } }]),
+        )
+        .await;
+        let mut batches = Vec::new();
+        for (collection, operation) in [
+            (
+                "demo",
+                json!({ "set_payload": {
+                    "payload": {
+                        "links": [{ "rel": "supersedes", "target_memory_id": "old" }],
+                        "linked_to": ["old"]
+                    },
+                    "points": ["p-new"]
+                } }),
+            ),
+            (
+                "demo-2025-03",
+                json!({ "set_payload": {
+                    "payload": { "linked_to": ["new"] },
+                    "points": ["p-old"]
+                } }),
+            ),
+        ] {
+            batches.push(
+                server
+                    .mock_async(|when, then| {
+                        when.method(POST)
+                            .path(format!("/collections/{collection}/points/batch"))
+                            .json_body(json!({ "operations": [operation] }));
+                        then.status(200)
+                            .json_body(json!({ "status": "ok", "result": [] }));
+                    })
+                    .await,
+            );
+        }
+        let service = service_for(&server).with_monthly_partitions();
+
+        let outcome = service
+            .link_memories("demo", "new", "old", LinkRelation::Supersedes)
+            .await
+            .expect("link");
+
+        assert_eq!(outcome, LinkOutcome::Created);
+        for batch in &batches {
+            batch.assert_async().await;
+        }
+        assert!(
+            service
+                .memory_links("demo", "old")
+                .await
+                .expect("links")
+                .is_some()
+        );
+    }
+
+    async fn mock_link_scroll(server: &MockServer, points: Value) {
+        server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/scroll")
+                    .json_body_partial(
+                        r#"{"filter":{"must":[{"key":"memory_id","match":{"any":["new","old"]}}]}}"#,
+                    );
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": { "points": points, "next_page_offset": null }
+                }));
+            })
+            .await;
+    }
+
+    #[tokio::test]
+    async fn link_memories_writes_both_ends_once() {
+        let server = MockServer::start_async().await;
+        mock_link_scroll(
+            &server,
+            json!([
+                { "id": "p-new", "payload": { "memory_id": "new" } },
+                { "id": "p-old", "payload": {
+                    "memory_id": "old",
+                    "links": [{ "rel": "relates_to", "target_memory_id": "other" }],
+                    "linked_to": ["other"]
+                } }
+            ]),
+        )
+        .await;
+        let batch = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/batch")
+                    .json_body(json!({ "operations": [
+                        { "set_payload": {
+                            "payload": {
+                                "links": [{ "rel": "supersedes", "target_memory_id": "old" }],
+                                "linked_to": ["old"]
+                            },
+                            "points": ["p-new"]
+                        } },
+                        { "set_payload": {
+                            "payload": { "linked_to": ["other", "new"] },
+                            "points": ["p-old"]
+                        } }
+                    ] }));
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": [] }));
+            })
+            .await;
+
+        let outcome = service_for(&server)
+            .link_memories("demo", "new", "old", LinkRelation::Supersedes)
+            .await
+            .expect("link");
+        assert_eq!(outcome, LinkOutcome::Created);
+        batch.assert_async().await;
+
+        let linked = MockServer::start_async().await;
+        mock_link_scroll(
+            &linked,
+            json!([
+                { "id": "p-new", "payload": {
+                    "memory_id": "new",
+                    "links": [{ "rel": "supersedes", "target_memory_id": "old" }],
+                    "linked_to": ["old"]
+                } },
+                { "id": "p-old", "payload": { "memory_id": "old", "linked_to": ["new"] } }
+            ]),
+        )
+        .await;
+        let untouched = linked
+            .mock_async(|when, then| {
+                when.method(POST).path("/collections/demo/points/batch");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": [] }));
+            })
+            .await;
+        let service = service_for(&linked);
+        let outcome = service
+            .link_memories("demo", "new", "old", LinkRelation::Supersedes)
+            .await
+            .expect("duplicate link");
+        assert_eq!(outcome, LinkOutcome::AlreadyLinked);
+        untouched.assert_hits_async(0).await;
+
+        // A different relation to the same target is a new link.
+        let outcome = service
+            .link_memories("demo", "new", "old", LinkRelation::Refines)
+            .await
+            .expect("second relation");
+        assert_eq!(outcome, LinkOutcome::Created);
+        untouched.assert_hits_async(1).await;
+    }
+
+    #[tokio::test]
+    async fn link_memories_requires_both_memories_to_exist() {
+        let server = MockServer::start_async().await;
+        mock_link_scroll(
+            &server,
+            json!([{ "id": "p-new", "payload": { "memory_id": "new" } }]),
+        )
+        .await;
+        let batch = server
+            .mock_async(|when, then| {
+                when.method(POST).path("/collections/demo/points/batch");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": [] }));
+            })
+            .await;
+
+        let outcome = service_for(&server)
+            .link_memories("demo", "new", "old", LinkRelation::Refines)
+            .await
+            .expect("lookup");
+        assert_eq!(outcome, LinkOutcome::MissingMemories(vec!["old".into()]));
+        batch.assert_hits_async(0).await;
+    }
+
+    #[tokio::test]
+    async fn memory_links_resolve_outbound_and_inbound() {
+        let server = MockServer::start_async().await;
+        let scroll = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/scroll")
+                    .json_body_partial(
+                        r#"{"filter":{"should":[{"key":"memory_id","match":{"value":"b"}},{"key":"linked_to","match":{"value":"b"}}]}}"#,
+                    );
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": {
+                        "points": [
+                            { "id": "p-a", "payload": {
+                                "memory_id": "a",
+                                "links": [
+                                    { "rel": "relates_to", "target_memory_id": "c" },
+                                    { "rel": "supersedes", "target_memory_id": "b" }
+                                ]
+                            } },
+                            { "id": "p-b", "payload": {
+                                "memory_id": "b",
+                                "links": [{ "rel": "refines", "target_memory_id": "c" }]
+                            } },
+                            { "id": "p-c", "payload": { "memory_id": "c" } }
+                        ],
+                        "next_page_offset": null
+                    }
+                }));
+            })
+            .await;
+
+        let links = service_for(&server)
+            .memory_links("demo", "b")
+            .await
+            .expect("links")
+            .expect("memory exists");
+        scroll.assert_async().await;
+        assert_eq!(
+            links.outbound,
+            [MemoryLink {
+                rel: LinkRelation::Refines,
+                target_memory_id: "c".into(),
+            }]
+        );
+        assert_eq!(
+            links.inbound,
+            [InboundLink {
+                rel: LinkRelation::Supersedes,
+                source_memory_id: "a".into(),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn describe_collection_reports_vectors_indexes_and_sampled_keys() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path("/collections/demo");
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": {
+                        "status": "green",
+                        "points_count": 2,
+                        "indexed_vectors_count": 2,
+                        "config": { "params": { "vectors": { "size": 768, "distance": "Cosine" } } },
+                        "payload_schema": {
+                            "project_id": { "data_type": "keyword", "points": 2 },
+                            "timestamp": { "data_type": "datetime", "points": 2 }
+                        }
+                    }
+                }));
+            })
+            .await;
+        let sample = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/scroll")
+                    .json_body_partial(r#"{ "limit": 20, "with_vector": false }"#);
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": {
+                        "points": [
+                            { "id": 1, "payload": { "project_id": "a", "text": "x", "ticket": "T-1" } },
+                            { "id": 2, "payload": { "project_id": "b", "text": "y" } }
+                        ],
+                        "next_page_offset": 3
+                    }
+                }));
+            })
+            .await;
+
+        let hashes = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/scroll")
+                    .json_body_partial(r#"{ "limit": 1000, "with_payload": ["chunk_hash"] }"#);
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": {
+                        "points": [
+                            { "id": 1, "payload": { "chunk_hash": "h1" } },
+                            { "id": 2, "payload": { "chunk_hash": "h2" } },
+                            { "id": 3, "payload": { "chunk_hash": "h1" } },
+                            { "id": 4, "payload": {} }
+                        ],
+                        "next_page_offset": null
+                    }
+                }));
+            })
+            .await;
+
+        let profile = service_for(&server)
+            .describe_collection("demo")
+            .await
+            .expect("description");
+
+        sample.assert_hits(1);
+        hashes.assert_hits(1);
+        assert_eq!(
+            profile.dedupe_stats,
+            DedupeStats {
+                sampled_points: 3,
+                distinct_hashes: 2,
+                duplicate_points: 1,
+                duplicate_ratio: 1.0 / 3.0,
+            }
+        );
+        assert_eq!(profile.vector_size, Some(768));
+        assert_eq!(profile.distance.as_deref(), Some("Cosine"));
+        assert_eq!(profile.sampled_points, 2);
+        assert_eq!(
+            profile.payload_keys,
+            BTreeMap::from([
+                ("project_id".to_string(), 2),
+                ("text".to_string(), 2),
+                ("ticket".to_string(), 1),
+            ])
+        );
+        assert_eq!(
+            profile.indexes.get("timestamp").map(String::as_str),
+            Some("datetime")
+        );
+        assert!(!profile.indexes.contains_key("ticket"));
+    }
+
+    fn stored_payload(text: &str, timestamp: &str) -> Value {
+        json!({
+            "memory_id": "m",
+            "project_id": "default",
+            "memory_type": "semantic",
+            "timestamp": timestamp,
+            "chunk_hash": qdrant::compute_chunk_hash(text),
+            "text": text,
+        })
+    }
+
+    async fn mock_verify_pages(server: &MockServer) {
+        let mut tampered = stored_payload("edited later", "2025-01-01T00:00:00Z");
+        tampered["chunk_hash"] = json!("stale");
+        let mut incomplete = stored_payload("no project", "2025-01-01T00:00:00Z");
+        incomplete.as_object_mut().unwrap().remove("project_id");
+        server
+            .mock_async(move |when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/scroll")
+                    .matches(|request| {
+                        !String::from_utf8_lossy(request.body.as_deref().unwrap_or_default())
+                            .contains("offset")
+                    });
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": {
+                        "points": [
+                            { "id": "ok-1", "payload": stored_payload("fine", "2025-01-01T00:00:00Z") },
+                            { "id": "tampered", "payload": tampered },
+                            { "id": "incomplete", "payload": incomplete }
+                        ],
+                        "next_page_offset": "page-2"
+                    }
+                }));
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/scroll")
+                    .json_body_partial(r#"{ "offset": "page-2" }"#);
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": {
+                        "points": [
+                            { "id": "bad-time", "payload": stored_payload("late", "yesterday") },
+                            { "id": "ok-2", "payload": stored_payload("also fine", "2025-01-02T00:00:00Z") }
+                        ],
+                        "next_page_offset": null
+                    }
+                }));
+            })
+            .await;
+    }
+
+    #[tokio::test]
+    async fn verify_collection_reports_each_anomaly_class_across_pages() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        mock_verify_pages(&server).await;
+        let repairs = server
+            .mock_async(|when, then| {
+                when.method(POST).path("/collections/demo/points/batch");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": [] }));
+            })
+            .await;
+
+        let outcome = service_for(&server)
+            .verify_collection("demo", 1.0, false, false)
+            .await
+            .expect("verify");
+
+        repairs.assert_hits(0);
+        assert_eq!((outcome.scanned, outcome.checked), (5, 5));
+        assert_eq!(outcome.hash_mismatch.examples, ["tampered"]);
+        assert_eq!(outcome.missing_fields.examples, ["incomplete"]);
+        assert_eq!(outcome.invalid_timestamp.examples, ["bad-time"]);
+        assert_eq!(outcome.hashes_fixed, 0);
+        assert_eq!(outcome.timestamps_backfilled, 0);
+    }
+
+    #[tokio::test]
+    async fn verify_collection_backfills_timestamp_unix_from_rfc3339_timestamps() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        mock_verify_pages(&server).await;
+        let first_page = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/batch")
+                    .body_contains(r#""timestamp_unix":1735689600"#)
+                    .body_contains(r#""points":["incomplete"]"#);
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": [] }));
+            })
+            .await;
+        let second_page = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/batch")
+                    .body_contains(r#""timestamp_unix":1735776000"#)
+                    .body_contains(r#""points":["ok-2"]"#);
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": [] }));
+            })
+            .await;
+
+        let outcome = service_for(&server)
+            .verify_collection("demo", 1.0, false, true)
+            .await
+            .expect("backfill");
+
+        first_page.assert_hits(1);
+        second_page.assert_hits(1);
+        // `bad-time` has no parseable timestamp to mirror.
+        assert_eq!(outcome.timestamps_backfilled, 4);
+        assert_eq!(outcome.hashes_fixed, 0);
+    }
+
+    #[tokio::test]
+    async fn verify_collection_rewrites_stale_hashes_and_samples_evenly() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        mock_verify_pages(&server).await;
+        let expected = qdrant::compute_chunk_hash("edited later");
+        let repairs = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/batch")
+                    .body_contains(expected.as_str())
+                    .body_contains(r#""points":["tampered"]"#);
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": [] }));
+            })
+            .await;
+
+        let service = service_for(&server);
+        let outcome = service
+            .verify_collection("demo", 1.0, true, false)
+            .await
+            .expect("verify and fix");
+        repairs.assert_hits(1);
+        assert_eq!(outcome.hashes_fixed, 1);
+
+        let sampled = service
+            .verify_collection("demo", 0.5, false, false)
+            .await
+            .expect("sampled verify");
+        assert_eq!((sampled.scanned, sampled.checked), (5, 2));
+    }
+
+    async fn mock_ingest_collection(server: &MockServer) {
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path("/collections/demo");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method(PUT).path("/collections/demo/index");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+    }
+
+    #[tokio::test]
+    async fn verified_collections_skip_re_ensuring_payload_indexes() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        let lookup = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/collections/demo");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+        let indexes = server
+            .mock_async(|when, then| {
+                when.method(PUT).path("/collections/demo/index");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": true }));
+            })
+            .await;
+        let service = service_for(&server);
+
+        service.ensure_collection("demo").await.expect("first");
+        service.ensure_collection("demo").await.expect("second");
+
+        lookup.assert_hits_async(2).await;
+        indexes.assert_hits_async(PAYLOAD_INDEX_FIELDS.len()).await;
+    }
+
+    #[tokio::test]
+    async fn refused_payload_indexes_fail_only_in_strict_mode() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path("/collections/demo");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method(PUT)
+                    .path("/collections/demo/index")
+                    .json_body_partial(r#"{"field_name":"doc_id"}"#);
+                then.status(400).body("index quota exceeded");
+            })
+            .await;
+        let indexes = server
+            .mock_async(|when, then| {
+                when.method(PUT).path("/collections/demo/index");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": true }));
+            })
+            .await;
+
+        let lenient = service_for(&server);
+        lenient.ensure_collection("demo").await.expect("warns only");
+        lenient.ensure_collection("demo").await.expect("warns only");
+        // An incomplete set is not cached, so every call retries the indexes.
+        indexes
+            .assert_hits_async(2 * (PAYLOAD_INDEX_FIELDS.len() - 1))
+            .await;
+
+        let strict = ProcessingService {
+            strict_payload_indexes: true,
+            ..service_for(&server)
+        };
+        let error = strict.ensure_collection("demo").await.unwrap_err();
+        let ProcessingError::PayloadIndexes {
+            collection,
+            failures,
+        } = &error
+        else {
+            panic!("expected a payload index error, got {error:?}");
+        };
+        assert_eq!(collection, "demo");
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].field, "doc_id");
+        assert_eq!(error.kind(), ErrorKind::InvalidRequest);
+        assert!(
+            error.to_string().contains("index quota exceeded"),
+            "{error}"
+        );
+    }
+
+    #[tokio::test]
+    async fn require_text_adds_a_text_present_condition_to_the_query_filter() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        let query = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/query")
+                    .json_body_partial(
+                        json!({
+                            "filter": {
+                                "must": [{ "key": "project_id", "match": { "value": "default" } }],
+                                "must_not": [{ "is_empty": { "key": "text" } }]
+                            }
+                        })
+                        .to_string(),
+                    );
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": { "points": [
+                        { "id": "with-text", "score": 0.9, "payload": { "text": "Usable" } }
+                    ] }
+                }));
+            })
+            .await;
+
+        let service = service_with(
+            &server,
+            Box::new(RecordingEmbedding::default()),
+            IngestFallback::None,
+        );
+        let hits = service
+            .search_memories(
+                SearchRequest::builder()
+                    .query_text("usable records")
+                    .collection("demo")
+                    .project_id("default")
+                    .require_text(true)
+                    .build()
+                    .expect("valid request"),
+            )
+            .await
+            .expect("search")
+            .hits;
+
+        query.assert_hits_async(1).await;
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].id, "with-text");
+    }
+
+    #[tokio::test]
+    async fn long_queries_are_truncated_before_embedding() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(POST).path("/collections/demo/points/query");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": { "points": [] } }));
+            })
+            .await;
+        let recorder = RecordingEmbedding::default();
+        let service = service_with(&server, Box::new(recorder.clone()), IngestFallback::None);
+        let pasted = "Rotate the signing keys every quarter. ".repeat(200);
+
+        let outcome = service
+            .search_memories(
+                SearchRequest::builder()
+                    .query_text(pasted.clone())
+                    .collection("demo")
+                    .build()
+                    .expect("valid request"),
+            )
+            .await
+            .expect("search");
+
+        assert!(outcome.query_truncated);
+        assert!(!outcome.query_compressed);
+        assert!(outcome.effective_query.ends_with("every quarter."));
+        assert!(outcome.effective_query.len() < pasted.len());
+        assert_eq!(
+            *recorder.0.lock().expect("recorded texts"),
+            vec![outcome.effective_query.clone()]
+        );
+    }
+
+    fn ingest_metadata() -> IngestMetadata {
+        IngestMetadata {
+            project_id: Some("default".into()),
+            memory_type: None,
+            tags: Some(vec!["notes".into()]),
+            source_uri: None,
+            timestamp: None,
+            auto_classify: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn embedding_prefixes_split_queries_from_documents() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        mock_ingest_collection(&server).await;
+        let upsert = server
+            .mock_async(|when, then| {
+                when.method(PUT)
+                    .path("/collections/demo/points")
+                    .body_contains(r#""text":"Rotate keys quarterly.""#);
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+        let query = server
+            .mock_async(|when, then| {
+                when.method(POST).path("/collections/demo/points/query");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": { "points": [] } }));
+            })
+            .await;
+
+        let recorder = RecordingEmbedding::default();
+        let service = ProcessingService {
+            embedding_prefixes: EmbeddingPrefixes {
+                query: "query: ".into(),
+                document: "passage: ".into(),
+            },
+            ..service_with(&server, Box::new(recorder.clone()), IngestFallback::None)
+        };
+        service
+            .process_and_index("demo", "Rotate keys quarterly.".into(), ingest_metadata())
+            .await
+            .expect("ingest");
+        service
+            .search_memories(
+                SearchRequest::builder()
+                    .query_text("key rotation")
+                    .collection("demo")
+                    .build()
+                    .expect("valid request"),
+            )
+            .await
+            .expect("search");
+
+        upsert.assert_hits(1);
+        query.assert_hits(1);
+        assert_eq!(
+            *recorder.0.lock().expect("recorded texts"),
+            ["passage: Rotate keys quarterly.", "query: key rotation"]
+        );
+    }
+
+    #[tokio::test]
+    async fn unchanged_sources_are_skipped_until_their_content_changes() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        mock_ingest_collection(&server).await;
+        let upsert = server
+            .mock_async(|when, then| {
+                when.method(PUT).path("/collections/demo/points");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+
+        let recorder = RecordingEmbedding::default();
+        let service = ProcessingService {
+            sync_state: Some(Box::new(crate::state::InMemorySyncState::new())),
+            ..service_with(&server, Box::new(recorder.clone()), IngestFallback::None)
+        };
+        let push = |text: &str| {
+            service.process_and_index(
+                "demo",
+                text.to_string(),
+                IngestMetadata {
+                    source_uri: Some("docs/guide.md".into()),
+                    ..ingest_metadata()
+                },
+            )
+        };
+
+        let first = push("Rotate keys quarterly.").await.expect("first sync");
+        assert!(!first.skipped_unchanged);
+        let repeat = push("Rotate keys quarterly.").await.expect("repeat sync");
+        assert!(repeat.skipped_unchanged);
+        assert_eq!(repeat.chunk_count, 0);
+        assert_eq!(repeat.skipped_unchanged_chunks, first.chunk_count);
+        assert_eq!(repeat.duplicate_ratio(), 1.0);
+        assert_eq!(first.duplicate_ratio(), 0.0);
+        let edited = push("Rotate keys monthly.").await.expect("edited sync");
+        assert!(!edited.skipped_unchanged);
+
+        upsert.assert_hits(2);
+        assert_eq!(recorder.0.lock().expect("recorded texts").len(), 2);
+        let entries = service
+            .sync_entries(Some("default"))
+            .expect("state enabled");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(
+            entries[0].content_hash,
+            crate::state::content_hash("Rotate keys monthly.")
+        );
+
+        assert_eq!(service.reset_sync_entries(None, None), Some(1));
+        let after_reset = push("Rotate keys monthly.").await.expect("resynced");
+        assert!(!after_reset.skipped_unchanged);
+
+        let dedupe = service.collection_metrics_snapshot("demo").dedupe;
+        assert_eq!(dedupe.chunks_seen, 4);
+        assert_eq!(dedupe.skipped_unchanged_source, 1);
+        assert_eq!(dedupe.embeddings_saved, 1);
+        assert_eq!(dedupe.duplicate_ratio, 0.25);
+    }
+
+    #[tokio::test]
+    async fn cached_listings_skip_the_scroll_until_a_push_invalidates_them() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        mock_ingest_collection(&server).await;
+        let scroll = |field: &'static str| {
+            let server = &server;
+            async move {
+                server
+                    .mock_async(move |when, then| {
+                        when.method(POST)
+                            .path("/collections/demo/points/scroll")
+                            .body_contains(format!(r#""with_payload":["{field}"]"#));
+                        then.status(200).json_body(json!({
+                            "status": "ok",
+                            "result": {
+                                "points": [{
+                                    "id": 1,
+                                    "payload": { "project_id": "alpha", "tags": ["api"] }
+                                }],
+                                "next_page_offset": null
+                            }
+                        }));
+                    })
+                    .await
+            }
+        };
+        let projects_scroll = scroll("project_id").await;
+        let tags_scroll = scroll("tags").await;
+        server
+            .mock_async(|when, then| {
+                when.method(PUT).path("/collections/demo/points");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+        let service = ProcessingService {
+            listing_cache: ListingCache::new(Duration::from_secs(60)),
+            ..service_for(&server)
+        };
+
+        for _ in 0..2 {
+            let projects = service.list_projects("demo").await.expect("projects");
+            assert_eq!(projects, BTreeSet::from(["alpha".to_string()]));
+            let tags = service.list_tags("demo", None).await.expect("tags");
+            assert_eq!(tags, BTreeSet::from(["api".to_string()]));
+        }
+        assert_eq!(projects_scroll.hits_async().await, 1);
+        assert_eq!(tags_scroll.hits_async().await, 1);
+
+        service
+            .process_and_index("demo", "Rotate keys quarterly.".into(), ingest_metadata())
+            .await
+            .expect("ingest");
+        service.list_projects("demo").await.expect("projects");
+        service.list_tags("demo", None).await.expect("tags");
+        assert_eq!(projects_scroll.hits_async().await, 2);
+        assert_eq!(tags_scroll.hits_async().await, 2);
+    }
+
+    #[tokio::test]
+    async fn degraded_ingest_writes_tagged_fallback_vectors() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        mock_ingest_collection(&server).await;
+        let upsert = server
+            .mock_async(|when, then| {
+                when.method(PUT)
+                    .path("/collections/demo/points")
+                    .body_contains(r#""tags":["notes","embedding:fallback"]"#);
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+
+        let outcome = service_with(
+            &server,
+            Box::new(UnavailableEmbedding),
+            IngestFallback::Deterministic,
+        )
+        .process_and_index("demo", "Capture this note.".into(), ingest_metadata())
+        .await
+        .expect("degraded ingest");
+
+        upsert.assert_hits(1);
+        assert!(outcome.degraded);
+        assert_eq!(outcome.inserted, 1);
+    }
+
+    #[tokio::test]
+    async fn ingest_fallback_sizes_vectors_for_a_promoted_collection() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        mock_ingest_collection(&server).await;
+        let upsert = server
+            .mock_async(|when, then| {
+                when.method(PUT)
+                    .path("/collections/demo/points")
+                    .body_contains("embedding:fallback")
+                    .matches(|request| {
+                        serde_json::from_slice::<Value>(request.body.as_deref().unwrap_or_default())
+                            .ok()
+                            .and_then(|body| body["points"][0]["vector"].as_array().map(Vec::len))
+                            == Some(4)
+                    });
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+        let migration = ProviderMigration::new(
+            Box::new(UnavailableEmbedding),
+            NextEmbedding {
+                provider: EmbeddingProvider::OpenAI,
+                model: "text-embedding-3-small".into(),
+                dimension: 4,
+            },
+        );
+        migration.mark_promoted("demo");
+        let service = ProcessingService {
+            migration: Some(migration),
+            ..service_with(
+                &server,
+                Box::new(AiLibClient::new()),
+                IngestFallback::Deterministic,
+            )
+        };
+        assert_ne!(get_config().embedding_dimension, 4);
+
+        let outcome = service
+            .process_and_index("demo", "Capture this note.".into(), ingest_metadata())
+            .await
+            .expect("degraded ingest");
+
+        upsert.assert_hits_async(1).await;
+        assert!(outcome.degraded);
+    }
+
+    #[tokio::test]
+    async fn ingest_fallback_returns_configuration_errors_to_the_caller() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        mock_ingest_collection(&server).await;
+        let upsert = server
+            .mock_async(|when, then| {
+                when.method(PUT).path("/collections/demo/points");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+
+        let error = service_with(
+            &server,
+            Box::new(MisconfiguredEmbedding),
+            IngestFallback::Deterministic,
+        )
+        .process_and_index("demo", "Capture this note.".into(), ingest_metadata())
+        .await
+        .expect_err("configuration errors must not fall back");
+
+        upsert.assert_hits(0);
+        assert!(matches!(
+            error,
+            ProcessingError::Embedding(EmbeddingClientError::Configuration(_))
+        ));
+    }
+
+    #[cfg(feature = "fault-injection")]
+    #[tokio::test]
+    async fn only_the_faulted_ingest_falls_back_to_deterministic_vectors() {
+        use crate::fault::{FaultConfig, FaultyEmbeddingClient};
+
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        mock_ingest_collection(&server).await;
+        let fallback = server
+            .mock_async(|when, then| {
+                when.method(PUT)
+                    .path("/collections/demo/points")
+                    .body_contains("embedding:fallback");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+        let primary = server
+            .mock_async(|when, then| {
+                when.method(PUT)
+                    .path("/collections/demo/points")
+                    .matches(|request| {
+                        let body =
+                            String::from_utf8_lossy(request.body.as_deref().unwrap_or_default());
+                        !body.contains("embedding:fallback")
+                    });
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+
+        let embedding = FaultyEmbeddingClient::new(
+            Box::new(FixedEmbedding),
+            FaultConfig {
+                fail_on_call: Some(2),
+                ..FaultConfig::default()
+            },
+        );
+        let service = service_with(&server, Box::new(embedding), IngestFallback::Deterministic);
+        let mut degraded = Vec::new();
+        for note in ["First note.", "Second note.", "Third note."] {
+            let outcome = service
+                .process_and_index("demo", note.into(), ingest_metadata())
+                .await
+                .expect("ingest");
+            degraded.push(outcome.degraded);
+        }
+
+        assert_eq!(degraded, [false, true, false]);
+        fallback.assert_hits_async(1).await;
+        primary.assert_hits_async(2).await;
+    }
+
+    #[tokio::test]
+    async fn backdated_ingest_is_found_by_historical_time_range() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        mock_ingest_collection(&server).await;
+        let upsert = server
+            .mock_async(|when, then| {
+                when.method(PUT)
+                    .path("/collections/demo/points")
+                    .body_contains(r#""timestamp":"2019-03-04T08:00:00Z""#)
+                    .body_contains(r#""ingested_at":""#);
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+
+        let metadata = IngestMetadata {
+            timestamp: Some("2019-03-04T08:00:00Z".into()),
+            ..ingest_metadata()
+        };
+        service_for(&server)
+            .process_and_index("demo", "Migrated the wiki.".into(), metadata)
+            .await
+            .expect("backdated ingest");
+        upsert.assert_hits(1);
+
+        let historical = normalize_time_range(ProcSearchTimeRange {
+            start: Some("2019-03-01".into()),
+            end: Some("2019-03-31".into()),
+        });
+        let filter = qdrant::build_search_filter(&qdrant::SearchFilterArgs {
+            time_range: Some(historical.clone()),
+            ..Default::default()
+        })
+        .expect("filter");
+        assert!(filter.to_string().contains(r#""key":"timestamp""#));
+        let numeric = qdrant::build_search_filter(&qdrant::SearchFilterArgs {
+            time_range: Some(historical),
+            unix_time_range: true,
+            ..Default::default()
+        })
+        .expect("filter");
+        let condition = &numeric["must"][0]["should"][0];
+        let range = &condition["range"];
+        let backdated = qdrant::unix_seconds("2019-03-04T08:00:00Z").expect("seconds");
+        assert_eq!(condition["key"], "timestamp_unix");
+        assert!(range["gte"].as_i64() <= Some(backdated));
+        assert!(range["lte"].as_i64() >= Some(backdated));
+    }
+
+    #[tokio::test]
+    async fn time_ranges_match_the_same_points_with_datetime_or_unix_filters() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        let stored = json!({
+            "points": [
+                {
+                    "id": "feb",
+                    "payload": {
+                        "text": "February retro",
+                        "timestamp": "2025-02-10T12:00:00Z",
+                        "timestamp_unix": 1_739_188_800
+                    }
+                }
+            ],
+            "next_page_offset": null
+        });
+        let datetime = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/scroll")
+                    .body_contains(r#""key":"timestamp","range":{"gte":"2025-02-01T00:00:00Z""#);
+                then.status(200)
+                    .json_body(json!({ "result": stored.clone() }));
+            })
+            .await;
+        let unix = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/scroll")
+                    .body_contains(
+                        r#""key":"timestamp_unix","range":{"gte":1738368000,"lte":1740787199}"#,
+                    );
+                then.status(200)
+                    .json_body(json!({ "result": stored.clone() }));
+            })
+            .await;
+        let request = || LookupRequest {
+            collection: Some("demo".into()),
+            time_range: Some(ProcSearchTimeRange {
+                start: Some("2025-02-01".into()),
+                end: Some("2025-02-28".into()),
+            }),
+            ..Default::default()
+        };
+
+        let current = service_for(&server)
+            .lookup_memories(request())
+            .await
+            .expect("datetime range");
+        let mut legacy = service_for(&server);
+        legacy.qdrant_service.capabilities =
+            qdrant::QdrantCapabilities::from_version(qdrant::QdrantVersion::parse("1.7.0"));
+        let fallback = legacy.lookup_memories(request()).await.expect("unix range");
+
+        datetime.assert_hits(1);
+        unix.assert_hits(1);
+        let ids = |hits: &[SearchHit]| hits.iter().map(|hit| hit.id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(&current), ["feb"]);
+        assert_eq!(ids(&current), ids(&fallback));
+    }
+
+    #[tokio::test]
+    async fn unix_time_ranges_check_points_missing_the_mirror_client_side() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        let scroll = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/scroll")
+                    .body_contains(r#"{"is_empty":{"key":"timestamp_unix"}}"#);
+                then.status(200).json_body(json!({
+                    "result": {
+                        "points": [
+                            {
+                                "id": "mirrored",
+                                "payload": {
+                                    "text": "February retro",
+                                    "timestamp": "2025-02-10T12:00:00Z",
+                                    "timestamp_unix": 1_739_188_800
+                                }
+                            },
+                            {
+                                "id": "legacy-feb",
+                                "payload": { "text": "Old retro", "timestamp": "2025-02-20T08:00:00Z" }
+                            },
+                            {
+                                "id": "legacy-jan",
+                                "payload": { "text": "Older retro", "timestamp": "2025-01-20T08:00:00Z" }
+                            }
+                        ],
+                        "next_page_offset": null
+                    }
+                }));
+            })
+            .await;
+        let mut service = service_for(&server);
+        service.qdrant_service.capabilities =
+            qdrant::QdrantCapabilities::from_version(qdrant::QdrantVersion::parse("1.7.0"));
+
+        let hits = service
+            .lookup_memories(LookupRequest {
+                collection: Some("demo".into()),
+                time_range: Some(ProcSearchTimeRange {
+                    start: Some("2025-02-01".into()),
+                    end: Some("2025-02-28".into()),
+                }),
+                ..Default::default()
+            })
+            .await
+            .expect("unix range");
+
+        scroll.assert_hits(1);
+        let ids: Vec<_> = hits.iter().map(|hit| hit.id.as_str()).collect();
+        assert_eq!(ids, ["mirrored", "legacy-feb"]);
+    }
+
+    #[tokio::test]
+    async fn auto_classify_overrides_memory_type_per_chunk() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        mock_ingest_collection(&server).await;
+        let upsert = server
+            .mock_async(|when, then| {
+                when.method(PUT)
+                    .path("/collections/demo/points")
+                    .body_contains(r#""memory_type":"procedural""#);
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+
+        let metadata = IngestMetadata {
+            memory_type: Some("semantic".into()),
+            auto_classify: Some(AutoClassify::Heuristic),
+            ..ingest_metadata()
+        };
+        let outcome = service_for(&server)
+            .process_and_index("demo", "Run cargo test before pushing.".into(), metadata)
+            .await
+            .expect("classified ingest");
+
+        upsert.assert_hits(1);
+        assert_eq!(
+            outcome.classification,
+            Some(ClassificationSummary {
+                episodic: 0,
+                semantic: 0,
+                procedural: 1,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn oversized_code_blocks_are_stored_as_tagged_standalone_chunks() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        mock_ingest_collection(&server).await;
+        let body: String = (0..600).map(|line| format!("step_{line}();\n")).collect();
+        let block = format!("```python\n{body}```");
+        let upsert = server
+            .mock_async(|when, then| {
+                when.method(PUT)
+                    .path("/collections/demo/points")
+                    .body_contains(r#""content_kind":"code_block""#)
+                    .matches(|request| {
+                        let body = String::from_utf8_lossy(request.body.as_deref().unwrap_or(&[]));
+                        !body.contains(['\u{E000}', '\u{E001}'])
+                    });
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+
+        let text = format!("Setup notes for the migration.\n\n{block}\n\nRun it twice.");
+        let outcome = service_for(&server)
+            .process_and_index("demo", text, ingest_metadata())
+            .await
+            .expect("ingest with code block");
+
+        upsert.assert_hits(1);
+        assert_eq!(outcome.chunk_count, 3);
+    }
+
+    #[tokio::test]
+    async fn push_stores_a_stable_profile_hash_on_each_point() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        mock_ingest_collection(&server).await;
+        let first = server
+            .mock_async(|when, then| {
+                when.method(PUT)
+                    .path("/collections/demo/points")
+                    .body_contains(r#""ingest_profile":""#);
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+        let service = service_for(&server);
+        let outcome = service
+            .process_and_index("demo", "Profiled note.".into(), ingest_metadata())
+            .await
+            .expect("first ingest");
+        first.assert_hits(1);
+        first.delete_async().await;
+        let config = get_config();
+        assert_eq!(
+            outcome.ingest_profile.embedding_model,
+            config.embedding_model
+        );
+
+        let expected = format!(r#""profile_hash":"{}""#, outcome.ingest_profile.hash());
+        let second = server
+            .mock_async(|when, then| {
+                when.method(PUT)
+                    .path("/collections/demo/points")
+                    .body_contains(expected.as_str());
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+        let repeat = service
+            .process_and_index("demo", "Another note.".into(), ingest_metadata())
+            .await
+            .expect("second ingest");
+        second.assert_hits(1);
+        assert_eq!(repeat.ingest_profile, outcome.ingest_profile);
+    }
+
+    async fn ingest_with_rules(source_uri: &str, expected_type: &str) {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        mock_ingest_collection(&server).await;
+        let expected = format!(r#""memory_type":"{expected_type}""#);
+        let upsert = server
+            .mock_async(|when, then| {
+                when.method(PUT)
+                    .path("/collections/demo/points")
+                    .body_contains(expected.as_str());
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+
+        let service = ProcessingService {
+            memory_type_rules: MemoryTypeRule::parse_list("*.log=episodic,tag:runbook=procedural")
+                .expect("rules"),
+            ..service_for(&server)
+        };
+        let metadata = IngestMetadata {
+            source_uri: Some(source_uri.into()),
+            ..ingest_metadata()
+        };
+        service
+            .process_and_index("demo", "Deploy finished without errors.".into(), metadata)
+            .await
+            .expect("ingest");
+        upsert.assert_hits(1);
+    }
+
+    #[tokio::test]
+    async fn memory_type_rule_infers_episodic_for_log_sources() {
+        ingest_with_rules("file:///var/log/deploy.log", "episodic").await;
+    }
+
+    #[tokio::test]
+    async fn unmatched_memory_type_rules_default_to_semantic() {
+        ingest_with_rules("file:///docs/architecture.md", "semantic").await;
+    }
+
+    #[tokio::test]
+    async fn ingest_fails_without_fallback_policy() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        mock_ingest_collection(&server).await;
+        let upsert = server
+            .mock_async(|when, then| {
+                when.method(PUT).path("/collections/demo/points");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+
+        let error = service_with(
+            &server,
+            Box::new(UnavailableEmbedding),
+            IngestFallback::None,
+        )
+        .process_and_index("demo", "Capture this note.".into(), ingest_metadata())
+        .await
+        .unwrap_err();
+
+        assert!(matches!(error, ProcessingError::Embedding(_)));
+        upsert.assert_hits(0);
+    }
+
+    #[tokio::test]
+    async fn ingest_rejects_invalid_vectors_and_counts_them() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        mock_ingest_collection(&server).await;
+        let upsert = server
+            .mock_async(|when, then| {
+                when.method(PUT).path("/collections/demo/points");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+
+        let service = service_with(&server, Box::new(NanEmbedding), IngestFallback::None);
+        let error = service
+            .process_and_index("demo", "Capture this note.".into(), ingest_metadata())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            error,
+            ProcessingError::Embedding(EmbeddingClientError::InvalidVector { index: 0, .. })
+        ));
+        assert_eq!(error.kind(), ErrorKind::Transient);
+        upsert.assert_hits(0);
+        assert_eq!(service.metrics_snapshot().invalid_embeddings, 1);
+        assert_eq!(
+            service
+                .collection_metrics_snapshot("demo")
+                .invalid_embeddings,
+            1
+        );
+    }
+
+    /// Audit sink whose contents the test can read back.
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for SharedBuffer {
+        fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().expect("buffer").extend_from_slice(bytes);
+            Ok(bytes.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn push_appends_one_audit_record() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        mock_ingest_collection(&server).await;
+        server
+            .mock_async(|when, then| {
+                when.method(PUT).path("/collections/demo/points");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+        let buffer = SharedBuffer::default();
+        let mut service = service_for(&server);
+        service.audit = AuditLogger::to_writer(buffer.clone());
+
+        let metadata = IngestMetadata {
+            project_id: Some("team-a".into()),
+            ..ingest_metadata()
+        };
+        service
+            .process_and_index("demo", "Capture this note.".into(), metadata)
+            .await
+            .expect("ingest");
+
+        let written = String::from_utf8(buffer.0.lock().expect("buffer").clone()).expect("utf8");
+        let lines: Vec<&str> = written.lines().collect();
+        assert_eq!(lines.len(), 1);
+        let mut record: Value = serde_json::from_str(lines[0]).expect("json record");
+        let timestamp = record["timestamp"].take();
+        assert!(
+            timestamp
+                .as_str()
+                .and_then(|value| parse_time_boundary(value, TimeBoundary::Start))
+                .is_some()
+        );
+        assert_eq!(
+            record,
+            json!({
+                "timestamp": null,
+                "tool": "push",
+                "collection": "demo",
+                "project_id": "team-a",
+                "inserted": 1,
+                "updated": 0,
+                "deleted": 0
+            })
+        );
+    }
+
+    async fn mock_collection_size(server: &MockServer, name: &str, size: u64) {
+        let path = format!("/collections/{name}");
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path(path);
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": {
+                        "status": "green",
+                        "config": { "params": { "vectors": { "size": size, "distance": "Cosine" } } },
+                        "payload_schema": {}
+                    }
+                }));
+            })
+            .await;
+    }
+
+    fn project_filter(project_id: &str) -> qdrant::SearchFilterArgs {
+        qdrant::SearchFilterArgs {
+            project_id: Some(project_id.into()),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn move_to_collection_copies_points_then_deletes_them_from_source() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        mock_collection_size(&server, "inbox", 2).await;
+        mock_collection_size(&server, "archive", 2).await;
+        let scroll = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/inbox/points/scroll")
+                    .json_body_partial(r#"{ "with_vector": true }"#)
+                    .body_contains(r#""value":"alpha""#);
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": {
+                        "points": [
+                            {
+                                "id": "m-1",
+                                "vector": [0.1, 0.2],
+                                "payload": { "text": "first", "project_id": "alpha", "tags": ["a"] }
+                            },
+                            {
+                                "id": "m-2",
+                                "vector": [0.3, 0.4],
+                                "payload": { "text": "second", "project_id": "alpha" }
+                            }
+                        ],
+                        "next_page_offset": null
+                    }
+                }));
+            })
+            .await;
+        let upsert = server
+            .mock_async(|when, then| {
+                when.method(PUT)
+                    .path("/collections/archive/points")
+                    .json_body(json!({
+                        "points": [
+                            {
+                                "id": "m-1",
+                                "vector": [0.1, 0.2],
+                                "payload": { "text": "first", "project_id": "alpha", "tags": ["a"] }
+                            },
+                            {
+                                "id": "m-2",
+                                "vector": [0.3, 0.4],
+                                "payload": { "text": "second", "project_id": "alpha" }
+                            }
+                        ]
+                    }));
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+        let delete = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/inbox/points/delete")
+                    .json_body(json!({ "points": ["m-1", "m-2"] }));
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+
+        let moved = service_for(&server)
+            .move_to_collection("inbox", "archive", project_filter("alpha"))
+            .await
+            .expect("move");
+
+        assert_eq!(moved, 2);
+        scroll.assert_hits_async(1).await;
+        upsert.assert_hits_async(1).await;
+        delete.assert_hits_async(1).await;
+    }
+
+    #[tokio::test]
+    async fn rename_collection_creates_copies_in_batches_repoints_aliases_then_deletes() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        let target_lookup = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/collections/archive");
+                then.status(404)
+                    .json_body(json!({ "status": { "error": "Not found" } }));
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path("/collections/inbox");
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": {
+                        "status": "green",
+                        "config": { "params": { "vectors": { "size": 2, "distance": "Dot" } } },
+                        "payload_schema": { "project_id": { "data_type": "keyword", "points": 2 } }
+                    }
+                }));
+            })
+            .await;
+        let create = server
+            .mock_async(|when, then| {
+                when.method(PUT)
+                    .path("/collections/archive")
+                    .json_body(json!({ "vectors": { "size": 2, "distance": "Dot" } }));
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": true }));
+            })
+            .await;
+        let index = server
+            .mock_async(|when, then| {
+                when.method(PUT)
+                    .path("/collections/archive/index")
+                    .json_body(json!({ "field_name": "project_id", "field_schema": "keyword" }));
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+        let point = |id: &str| json!({ "id": id, "vector": [0.1, 0.2], "payload": { "text": id } });
+        let (first, second) = (point("m-1"), point("m-2"));
+        let first_page = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/inbox/points/scroll")
+                    .json_body_partial(r#"{ "with_vector": true, "offset": null }"#);
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": { "points": [first], "next_page_offset": "m-2" }
+                }));
+            })
+            .await;
+        let second_page = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/inbox/points/scroll")
+                    .json_body_partial(r#"{ "with_vector": true, "offset": "m-2" }"#);
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": { "points": [second], "next_page_offset": null }
+                }));
+            })
+            .await;
+        let upsert = server
+            .mock_async(|when, then| {
+                when.method(PUT).path("/collections/archive/points");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+        let mut counts = Vec::new();
+        for collection in ["inbox", "archive"] {
+            counts.push(
+                server
+                    .mock_async(|when, then| {
+                        when.method(POST)
+                            .path(format!("/collections/{collection}/points/count"));
+                        then.status(200)
+                            .json_body(json!({ "status": "ok", "result": { "count": 2 } }));
+                    })
+                    .await,
+            );
+        }
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path("/aliases");
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": { "aliases": [
+                        { "alias_name": "mail", "collection_name": "inbox" },
+                        { "alias_name": "live", "collection_name": "docs" }
+                    ] }
+                }));
+            })
+            .await;
+        let repoint = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/aliases")
+                    .json_body(json!({ "actions": [
+                        { "delete_alias": { "alias_name": "mail" } },
+                        { "create_alias": { "collection_name": "archive", "alias_name": "mail" } }
+                    ] }));
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": true }));
+            })
+            .await;
+        let delete = server
+            .mock_async(|when, then| {
+                when.method(DELETE).path("/collections/inbox");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": true }));
+            })
+            .await;
+
+        let outcome = service_for(&server)
+            .rename_collection("inbox", "archive")
+            .await
+            .expect("rename");
+
+        assert_eq!(
+            outcome,
+            RenameOutcome {
+                copied: 2,
+                batches: 2,
+                aliases: vec!["mail".to_string()],
+            }
+        );
+        target_lookup.assert_hits_async(1).await;
+        create.assert_hits_async(1).await;
+        index.assert_hits_async(1).await;
+        first_page.assert_hits_async(1).await;
+        second_page.assert_hits_async(1).await;
+        upsert.assert_hits_async(2).await;
+        for count in &counts {
+            count.assert_hits_async(1).await;
+        }
+        repoint.assert_hits_async(1).await;
+        delete.assert_hits_async(1).await;
+    }
+
+    #[tokio::test]
+    async fn rename_collection_keeps_the_source_when_point_counts_differ() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path("/collections/archive");
+                then.status(404)
+                    .json_body(json!({ "status": { "error": "Not found" } }));
+            })
+            .await;
+        mock_collection_size(&server, "inbox", 2).await;
+        server
+            .mock_async(|when, then| {
+                when.method(PUT).path("/collections/archive");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": true }));
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method(POST).path("/collections/inbox/points/scroll");
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": { "points": [], "next_page_offset": null }
+                }));
+            })
+            .await;
+        for (collection, count) in [("inbox", 1), ("archive", 0)] {
+            server
+                .mock_async(|when, then| {
+                    when.method(POST)
+                        .path(format!("/collections/{collection}/points/count"));
+                    then.status(200)
+                        .json_body(json!({ "status": "ok", "result": { "count": count } }));
+                })
+                .await;
+        }
+        let delete = server
+            .mock_async(|when, then| {
+                when.method(DELETE).path("/collections/inbox");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": true }));
+            })
+            .await;
+
+        let error = service_for(&server)
+            .rename_collection("inbox", "archive")
+            .await
+            .expect_err("counts differ");
+
+        assert!(matches!(
+            error,
+            MoveError::CopyIncomplete {
+                source_points: 1,
+                target_points: 0,
+            }
+        ));
+        delete.assert_hits_async(0).await;
+    }
+
+    #[tokio::test]
+    async fn rename_collection_refuses_an_existing_target() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        mock_collection_size(&server, "archive", 2).await;
+        let delete = server
+            .mock_async(|when, then| {
+                when.method(DELETE).path("/collections/inbox");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": true }));
+            })
+            .await;
+
+        let error = service_for(&server)
+            .rename_collection("inbox", "archive")
+            .await
+            .expect_err("target exists");
+
+        assert!(matches!(error, MoveError::TargetExists(ref name) if name == "archive"));
+        delete.assert_hits_async(0).await;
+    }
+
+    fn prune_filter_body() -> Value {
+        json!({
+            "must": [
+                { "key": "project_id", "match": { "value": "alpha" } },
+                { "key": "memory_type", "match": { "value": "episodic" } },
+                { "key": "timestamp", "range": { "lt": "2025-03-01T00:00:00Z" } }
+            ]
+        })
+    }
+
+    fn prune_cutoff() -> time::OffsetDateTime {
+        parse_time_boundary("2025-03-01T00:00:00Z", TimeBoundary::Start).expect("cutoff")
+    }
+
+    #[tokio::test]
+    async fn prune_deletes_by_filter_and_reports_the_counted_points() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        let count = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/notes/points/count")
+                    .json_body(json!({ "exact": true, "filter": prune_filter_body() }));
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": { "count": 7 } }));
+            })
+            .await;
+        let delete = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/notes/points/delete")
+                    .json_body(json!({ "filter": prune_filter_body() }));
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+
+        let outcome = service_for(&server)
+            .prune_memories(
+                "notes",
+                Some("alpha".into()),
+                "episodic",
+                prune_cutoff(),
+                false,
+            )
+            .await
+            .expect("prune");
+
+        assert_eq!(
+            outcome,
+            PruneOutcome {
+                cutoff: "2025-03-01T00:00:00Z".into(),
+                matched: 7,
+                deleted: 7,
+            }
+        );
+        count.assert_hits_async(1).await;
+        delete.assert_hits_async(1).await;
+    }
+
+    #[tokio::test]
+    async fn prune_without_datetime_index_bounds_the_unix_mirror_and_checks_unmirrored_points() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        let mirrored_filter = json!({
+            "must": [
+                { "key": "project_id", "match": { "value": "alpha" } },
+                { "key": "memory_type", "match": { "value": "episodic" } },
+                { "key": "timestamp_unix", "range": { "lt": 1_740_787_200 } }
+            ]
+        });
+        let count = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/notes/points/count")
+                    .json_body(json!({ "exact": true, "filter": mirrored_filter.clone() }));
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": { "count": 5 } }));
+            })
+            .await;
+        let scroll = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/notes/points/scroll")
+                    .body_contains(r#"{"is_empty":{"key":"timestamp_unix"}}"#);
+                then.status(200).json_body(json!({
+                    "result": {
+                        "points": [
+                            { "id": "legacy-jan", "payload": { "timestamp": "2025-01-20T08:00:00Z" } },
+                            { "id": "legacy-apr", "payload": { "timestamp": "2025-04-02T08:00:00Z" } },
+                            { "id": "unstamped", "payload": {} }
+                        ],
+                        "next_page_offset": null
+                    }
+                }));
+            })
+            .await;
+        let delete_filtered = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/notes/points/delete")
+                    .json_body(json!({ "filter": mirrored_filter.clone() }));
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+        let delete_stale = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/notes/points/delete")
+                    .json_body(json!({ "points": ["legacy-jan"] }));
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+        let mut service = service_for(&server);
+        service.qdrant_service.capabilities =
+            qdrant::QdrantCapabilities::from_version(qdrant::QdrantVersion::parse("1.7.0"));
+
+        let outcome = service
+            .prune_memories(
+                "notes",
+                Some("alpha".into()),
+                "episodic",
+                prune_cutoff(),
+                false,
+            )
+            .await
+            .expect("prune");
+
+        assert_eq!(outcome.matched, 6);
+        assert_eq!(outcome.deleted, 6);
+        count.assert_hits_async(1).await;
+        scroll.assert_hits_async(1).await;
+        delete_filtered.assert_hits_async(1).await;
+        delete_stale.assert_hits_async(1).await;
+    }
+
+    #[tokio::test]
+    async fn prune_dry_run_only_counts() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        let count = server
+            .mock_async(|when, then| {
+                when.method(POST).path("/collections/notes/points/count");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": { "count": 3 } }));
+            })
+            .await;
+        let delete = server
+            .mock_async(|when, then| {
+                when.method(POST).path("/collections/notes/points/delete");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+
+        let outcome = service_for(&server)
+            .prune_memories(
+                "notes",
+                Some("alpha".into()),
+                "episodic",
+                prune_cutoff(),
+                true,
+            )
+            .await
+            .expect("dry run");
+
+        assert_eq!(outcome.matched, 3);
+        assert_eq!(outcome.deleted, 0);
+        count.assert_hits_async(1).await;
+        delete.assert_hits_async(0).await;
+    }
+
+    #[test]
+    fn episodic_pushes_route_to_the_partition_of_their_timestamp() {
+        let server = MockServer::start();
+        let mut service = service_for(&server).with_monthly_partitions();
+        service.memory_type_rules = MemoryTypeRule::parse_list("*.log=episodic").expect("rules");
+        let metadata = |memory_type: Option<&str>, source_uri: Option<&str>| IngestMetadata {
+            memory_type: memory_type.map(str::to_string),
+            source_uri: source_uri.map(str::to_string),
+            timestamp: Some("2025-02-10T12:00:00Z".into()),
+            ..IngestMetadata::default()
+        };
+
+        assert_eq!(
+            service.ingest_collection("notes", &metadata(Some("episodic"), None)),
+            "notes-2025-02"
+        );
+        assert_eq!(
+            service.ingest_collection("notes", &metadata(None, Some("build/ci.log"))),
+            "notes-2025-02"
+        );
+        assert_eq!(
+            service.ingest_collection("notes", &metadata(Some("semantic"), Some("build/ci.log"))),
+            "notes"
+        );
+        service.episodic_partitioning = EpisodicPartitioning::Off;
+        assert_eq!(
+            service.ingest_collection("notes", &metadata(Some("episodic"), None)),
+            "notes"
+        );
+    }
+
+    #[tokio::test]
+    async fn episodic_reads_keep_the_base_collection() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path("/collections");
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": { "collections": [
+                        { "name": "notes" },
+                        { "name": "notes-2025-01" },
+                        { "name": "notes-2025-02" }
+                    ] }
+                }));
+            })
+            .await;
+        let service = service_for(&server).with_monthly_partitions();
+        let range = ProcSearchTimeRange {
+            start: Some("2025-01-01".into()),
+            end: Some("2025-02-28".into()),
+        };
+
+        let (targets, partitions) = service
+            .read_targets("notes", Some("episodic"), Some(&range))
+            .await
+            .expect("targets");
+
+        assert_eq!(targets, ["notes", "notes-2025-02", "notes-2025-01"]);
+        assert_eq!(
+            partitions.as_deref(),
+            Some(&["notes-2025-02".to_string(), "notes-2025-01".to_string()][..])
+        );
+    }
+
+    #[tokio::test]
+    async fn prune_covers_every_monthly_partition() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path("/collections");
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": { "collections": [
+                        { "name": "notes" },
+                        { "name": "notes-2025-01" },
+                        { "name": "notes-2025-02" },
+                        { "name": "other-2025-01" }
+                    ] }
+                }));
+            })
+            .await;
+        let mut counts = Vec::new();
+        let mut deletes = Vec::new();
+        for (collection, matched) in [("notes", 0), ("notes-2025-01", 4), ("notes-2025-02", 2)] {
+            counts.push(
+                server
+                    .mock_async(|when, then| {
+                        when.method(POST)
+                            .path(format!("/collections/{collection}/points/count"));
+                        then.status(200)
+                            .json_body(json!({ "status": "ok", "result": { "count": matched } }));
+                    })
+                    .await,
+            );
+            deletes.push(
+                server
+                    .mock_async(|when, then| {
+                        when.method(POST)
+                            .path(format!("/collections/{collection}/points/delete"));
+                        then.status(200)
+                            .json_body(json!({ "status": "ok", "result": {} }));
+                    })
+                    .await,
+            );
+        }
+        let mut service = service_for(&server);
+        service.episodic_partitioning = EpisodicPartitioning::Monthly;
+
+        let outcome = service
+            .prune_memories(
+                "notes",
+                Some("alpha".into()),
+                "episodic",
+                prune_cutoff(),
+                false,
+            )
+            .await
+            .expect("prune");
+
+        assert_eq!(outcome.matched, 6);
+        assert_eq!(outcome.deleted, 6);
+        for count in &counts {
+            count.assert_hits_async(1).await;
+        }
+        deletes[0].assert_hits_async(0).await;
+        deletes[1].assert_hits_async(1).await;
+        deletes[2].assert_hits_async(1).await;
+    }
+
+    #[tokio::test]
+    async fn purge_duplicates_keeps_the_oldest_copy_per_project_and_hash() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        let point = |id: &str, project: &str, hash: Option<&str>, timestamp: Option<&str>| {
+            let mut payload = json!({ "project_id": project });
+            if let Some(hash) = hash {
+                payload["chunk_hash"] = json!(hash);
+            }
+            if let Some(timestamp) = timestamp {
+                payload["timestamp"] = json!(timestamp);
+            }
+            json!({ "id": id, "payload": payload })
+        };
+        server
+            .mock_async(|when, then| {
+                when.method(POST).path("/collections/notes/points/scroll");
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": {
+                        "points": [
+                            point("b-newer", "alpha", Some("h1"), Some("2025-02-01T00:00:00Z")),
+                            point("a-older", "alpha", Some("h1"), Some("2025-01-01T00:00:00Z")),
+                            point("undated", "alpha", Some("h1"), None),
+                            point("other-project", "beta", Some("h1"), Some("2025-03-01T00:00:00Z")),
+                            point("unique", "alpha", Some("h2"), None),
+                            point("no-hash-1", "alpha", None, None),
+                            point("no-hash-2", "alpha", None, None),
+                        ],
+                        "next_page_offset": null
+                    }
+                }));
+            })
+            .await;
+        let delete = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/notes/points/delete")
+                    .json_body(json!({ "points": ["b-newer", "undated"] }));
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+        let service = service_for(&server);
+
+        let preview = service
+            .purge_duplicates("notes", true)
+            .await
+            .expect("dry run");
+        assert_eq!(
+            preview,
+            DuplicatePurgeOutcome {
+                scanned: 5,
+                duplicate_groups: 1,
+                deleted: 0,
+            }
+        );
+        delete.assert_hits_async(0).await;
+
+        let outcome = service
+            .purge_duplicates("notes", false)
+            .await
+            .expect("purge");
+        assert_eq!(outcome.deleted, 2);
+        delete.assert_hits_async(1).await;
+    }
+
+    #[tokio::test]
+    async fn move_to_collection_rejects_mismatched_vector_sizes() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        mock_collection_size(&server, "inbox", 2).await;
+        mock_collection_size(&server, "wide", 768).await;
+        let scroll = server
+            .mock_async(|when, then| {
+                when.method(POST).path("/collections/inbox/points/scroll");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+
+        let service = service_for(&server);
+        let error = service
+            .move_to_collection("inbox", "wide", project_filter("alpha"))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            MoveError::DimensionMismatch {
+                source_size: Some(2),
+                target_size: Some(768)
+            }
+        ));
+        assert_eq!(error.kind(), ErrorKind::InvalidRequest);
+        scroll.assert_hits_async(0).await;
+
+        let error = service
+            .move_to_collection("inbox", "wide", qdrant::SearchFilterArgs::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(error, MoveError::MissingFilter));
+    }
+
+    #[tokio::test]
+    async fn reembed_fallback_replaces_vectors_and_clears_tag() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        let scroll = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/scroll")
+                    .body_contains(FALLBACK_EMBEDDING_TAG);
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": {
+                        "points": [
+                            {
+                                "id": "m-1",
+                                "payload": {
+                                    "text": "Capture this note.",
+                                    "tags": ["notes", "embedding:fallback"]
+                                }
+                            },
+                            { "id": "m-2", "payload": { "tags": ["embedding:fallback"] } }
+                        ],
+                        "next_page_offset": null
+                    }
+                }));
+            })
+            .await;
+        let vectors = server
+            .mock_async(|when, then| {
+                when.method(PUT)
+                    .path("/collections/demo/points/vectors")
+                    .json_body(json!({ "points": [{ "id": "m-1", "vector": [0.5, 0.5] }] }));
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+        let retag = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/batch")
+                    .json_body(json!({
+                        "operations": [
+                            { "set_payload": { "payload": { "tags": ["notes"] }, "points": ["m-1"] } }
+                        ]
+                    }));
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": [] }));
+            })
+            .await;
+
+        let outcome = service_for(&server)
+            .reembed_fallback("demo")
+            .await
+            .expect("repair pass");
+
+        scroll.assert_hits(1);
+        vectors.assert_hits(1);
+        retag.assert_hits(1);
+        assert_eq!(
+            outcome,
+            ReembedOutcome {
+                reembedded: 1,
+                skipped: 1
+            }
+        );
+    }
+
+    #[test]
+    fn summarize_errors_are_classified() {
+        let cases = [
+            (
+                SummarizeError::GenerationFailed("x".into()),
+                ErrorKind::Internal,
+            ),
+            (SummarizeError::EmptyResult, ErrorKind::InvalidRequest),
+            (SummarizeError::InvalidTimeRange, ErrorKind::InvalidRequest),
+            (
+                SummarizeError::Embedding(EmbeddingClientError::ProviderUnavailable("down".into())),
+                ErrorKind::Transient,
+            ),
+            (
+                SummarizeError::Qdrant(qdrant::QdrantError::InvalidUrl("nope".into())),
+                ErrorKind::Configuration,
+            ),
+        ];
+        for (error, expected) in cases {
+            assert_eq!(error.kind(), expected, "{error}");
+            assert_eq!(error.is_retryable(), expected.is_retryable(), "{error}");
+        }
+    }
+
+    fn deterministic_migration(dimension: usize) -> ProviderMigration {
+        ProviderMigration::new(
+            Box::new(AiLibClient::with_dimension(dimension)),
+            NextEmbedding {
+                provider: EmbeddingProvider::OpenAI,
+                model: "text-embedding-3-small".into(),
+                dimension,
+            },
+        )
+    }
+
+    fn migrating_service(server: &MockServer) -> ProcessingService {
+        ProcessingService {
+            migration: Some(deterministic_migration(4)),
+            ..service_with(server, Box::new(AiLibClient::new()), IngestFallback::None)
+        }
+    }
+
+    /// Point ids written per collection by the dual-write test, keyed by vector length.
+    static DUAL_WRITE_IDS: Mutex<BTreeMap<usize, BTreeSet<String>>> = Mutex::new(BTreeMap::new());
+
+    fn record_dual_write(request: &httpmock::prelude::HttpMockRequest) -> bool {
+        let Ok(body) = serde_json::from_slice::<Value>(request.body.as_deref().unwrap_or_default())
+        else {
+            return false;
+        };
+        let mut recorded = DUAL_WRITE_IDS.lock().expect("recorded ids");
+        for point in body["points"].as_array().into_iter().flatten() {
+            if let (Some(vector), Some(id)) = (point["vector"].as_array(), point["id"].as_str()) {
+                recorded
+                    .entry(vector.len())
+                    .or_default()
+                    .insert(id.to_string());
+            }
+        }
+        true
+    }
+
+    #[tokio::test]
+    async fn migration_window_dual_writes_candidate_vectors_under_the_same_ids() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        mock_ingest_collection(&server).await;
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path("/collections/demo__candidate");
+                then.status(404);
+            })
+            .await;
+        let create = server
+            .mock_async(|when, then| {
+                when.method(PUT)
+                    .path("/collections/demo__candidate")
+                    .body_contains(r#""size":4"#);
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": true }));
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method(PUT).path("/collections/demo__candidate/index");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": true }));
+            })
+            .await;
+        let primary = server
+            .mock_async(|when, then| {
+                when.method(PUT)
+                    .path("/collections/demo/points")
+                    .matches(record_dual_write);
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+        let candidate = server
+            .mock_async(|when, then| {
+                when.method(PUT)
+                    .path("/collections/demo__candidate/points")
+                    .body_contains(r#""text":"Rotate keys quarterly.""#)
+                    .matches(record_dual_write);
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+
+        let outcome = migrating_service(&server)
+            .process_and_index("demo", "Rotate keys quarterly.".into(), ingest_metadata())
+            .await
+            .expect("ingest");
+
+        assert_eq!(outcome.inserted, 1);
+        create.assert_hits_async(1).await;
+        primary.assert_hits_async(1).await;
+        candidate.assert_hits_async(1).await;
+        let recorded = DUAL_WRITE_IDS.lock().expect("recorded ids");
+        assert_eq!(recorded.len(), 2, "{recorded:?}");
+        let active_dimension = get_config().embedding_dimension;
+        assert_eq!(recorded.get(&active_dimension).map(BTreeSet::len), Some(1));
+        assert_eq!(recorded.get(&active_dimension), recorded.get(&4));
+    }
+
+    #[tokio::test]
+    async fn compare_providers_reports_overlap_and_score_correlation() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path("/collections/demo__candidate");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+        for (collection, count) in [("demo", 3), ("demo__candidate", 2)] {
+            server
+                .mock_async(|when, then| {
+                    when.method(POST)
+                        .path(format!("/collections/{collection}/points/count"));
+                    then.status(200)
+                        .json_body(json!({ "status": "ok", "result": { "count": count } }));
+                })
+                .await;
+        }
+        server
+            .mock_async(|when, then| {
+                when.method(POST).path("/collections/demo/points/query");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": { "points": [
+                    { "id": "a", "score": 0.9 },
+                    { "id": "b", "score": 0.7 },
+                    { "id": "c", "score": 0.5 },
+                ] } }));
+            })
+            .await;
+        let candidate_query = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo__candidate/points/query")
+                    .matches(|request| {
+                        let body: Value =
+                            serde_json::from_slice(request.body.as_deref().unwrap_or_default())
+                                .unwrap_or_default();
+                        body["query"].as_array().map(Vec::len) == Some(4)
+                    });
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": { "points": [
+                    { "id": "a", "score": 0.8 },
+                    { "id": "x", "score": 0.6 },
+                    { "id": "c", "score": 0.4 },
+                ] } }));
+            })
+            .await;
+        let service = migrating_service(&server);
+
+        let error = service
+            .compare_providers("demo", Vec::new(), 5, 3)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, MigrationError::NoQueries), "{error}");
+
+        let comparison = service
+            .compare_providers("demo", vec!["key rotation".into()], 5, 3)
+            .await
+            .expect("comparison");
+
+        candidate_query.assert_hits_async(1).await;
+        assert_eq!(comparison.candidate_collection, "demo__candidate");
+        assert_eq!(
+            (comparison.active_points, comparison.candidate_points),
+            (3, 2)
+        );
+        assert_eq!(comparison.queries.len(), 1);
+        assert_eq!(comparison.queries[0].candidate_ids, ["a", "x", "c"]);
+        assert!((comparison.mean_overlap.unwrap() - 2.0 / 3.0).abs() < 1e-6);
+        assert_eq!(comparison.shared_hits, 2);
+        assert!((comparison.score_correlation.unwrap() - 1.0).abs() < 1e-6);
+    }
+
+    async fn mock_promotion_reads(server: &MockServer, aliases: Value, candidate_points: u64) {
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path("/aliases");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": { "aliases": aliases } }));
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path("/collections/demo__candidate");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+        for (collection, count) in [("demo", 2), ("demo__candidate", candidate_points)] {
+            server
+                .mock_async(|when, then| {
+                    when.method(POST)
+                        .path(format!("/collections/{collection}/points/count"));
+                    then.status(200)
+                        .json_body(json!({ "status": "ok", "result": { "count": count } }));
+                })
+                .await;
+        }
+    }
+
+    #[tokio::test]
+    async fn promote_provider_repoints_the_alias_and_embeds_with_the_candidate() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        mock_promotion_reads(
+            &server,
+            json!([{ "alias_name": "demo", "collection_name": "demo_v1" }]),
+            2,
+        )
+        .await;
+        let repoint = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/aliases")
+                    .json_body(json!({ "actions": [
+                        { "delete_alias": { "alias_name": "demo" } },
+                        { "create_alias": { "collection_name": "demo__candidate", "alias_name": "demo" } }
+                    ] }));
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": true }));
+            })
+            .await;
+        let service = migrating_service(&server);
+
+        let outcome = service
+            .promote_provider("demo", false, false)
+            .await
+            .expect("promotion");
+
+        repoint.assert_hits_async(1).await;
+        assert_eq!(outcome.previous_target.as_deref(), Some("demo_v1"));
+        assert!(!outcome.dropped_previous);
+        assert_eq!(
+            (outcome.provider.as_str(), outcome.dimension),
+            ("openai", 4)
+        );
+        assert_eq!(service.active_dimension("demo"), 4);
+        let vectors = service
+            .embed("demo", vec!["after promotion".into()])
+            .await
+            .expect("embedding");
+        assert_eq!(vectors[0].len(), 4);
+        // Other collections were not promoted and keep the active provider.
+        let dimension = get_config().embedding_dimension;
+        assert_eq!(service.active_dimension("notes"), dimension);
+        let vectors = service
+            .embed("notes", vec!["still active".into()])
+            .await
+            .expect("embedding");
+        assert_eq!(vectors[0].len(), dimension);
+        assert!(matches!(
+            service
+                .compare_providers("demo", vec!["q".into()], 1, 1)
+                .await,
+            Err(MigrationError::Promoted)
+        ));
+    }
+
+    #[tokio::test]
+    async fn prunes_during_the_migration_window_stay_deleted_after_promotion() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/count")
+                    .json_body(json!({ "exact": true, "filter": prune_filter_body() }));
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": { "count": 1 } }));
+            })
+            .await;
+        let mut deletes = Vec::new();
+        for collection in ["demo", "demo__candidate"] {
+            deletes.push(
+                server
+                    .mock_async(|when, then| {
+                        when.method(POST)
+                            .path(format!("/collections/{collection}/points/delete"))
+                            .json_body(json!({ "filter": prune_filter_body() }));
+                        then.status(200)
+                            .json_body(json!({ "status": "ok", "result": {} }));
+                    })
+                    .await,
+            );
+        }
+        mock_promotion_reads(
+            &server,
+            json!([{ "alias_name": "demo", "collection_name": "demo_v1" }]),
+            2,
+        )
+        .await;
+        server
+            .mock_async(|when, then| {
+                when.method(POST).path("/collections/aliases");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": true }));
+            })
+            .await;
+        let service = migrating_service(&server);
+        let prune = || {
+            service.prune_memories(
+                "demo",
+                Some("alpha".into()),
+                "episodic",
+                prune_cutoff(),
+                false,
+            )
+        };
+
+        prune().await.expect("prune during the window");
+        deletes[0].assert_hits_async(1).await;
+        deletes[1].assert_hits_async(1).await;
+
+        service
+            .promote_provider("demo", false, false)
+            .await
+            .expect("promotion");
+        // Once promoted, `demo` serves the candidate and nothing is mirrored any more.
+        prune().await.expect("prune after promotion");
+        deletes[0].assert_hits_async(2).await;
+        deletes[1].assert_hits_async(1).await;
+    }
+
+    #[tokio::test]
+    async fn detect_promotion_marks_each_alias_serving_its_candidate() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path("/aliases");
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": { "aliases": [
+                        { "alias_name": "demo", "collection_name": "demo__candidate" },
+                        { "alias_name": "notes", "collection_name": "notes_v2" },
+                        { "alias_name": "docs", "collection_name": "demo__candidate" }
+                    ] }
+                }));
+            })
+            .await;
+        let service = migrating_service(&server);
+
+        service.detect_promotion().await;
+
+        let migration = service.migration.as_ref().expect("migration");
+        assert!(migration.is_promoted("demo"));
+        assert!(!migration.is_promoted("notes"));
+        assert!(!migration.is_promoted("docs"));
+    }
+
+    #[tokio::test]
+    async fn promote_provider_guards_incomplete_candidates_and_concrete_collections() {
+        ensure_test_config();
+        let server = MockServer::start_async().await;
+        mock_promotion_reads(&server, json!([]), 1).await;
+        let delete = server
+            .mock_async(|when, then| {
+                when.method(DELETE).path("/collections/demo");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": true }));
+            })
+            .await;
+        let alias = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/aliases")
+                    .json_body(json!({ "actions": [
+                        { "create_alias": { "collection_name": "demo__candidate", "alias_name": "demo" } }
+                    ] }));
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": true }));
+            })
+            .await;
+        let service = migrating_service(&server);
+
+        let error = service
+            .promote_provider("demo", false, true)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(
+                error,
+                MigrationError::IncompleteCandidate {
+                    active_points: 2,
+                    candidate_points: 1,
+                    ..
+                }
+            ),
+            "{error}"
+        );
+        let error = service
+            .promote_provider("demo", true, false)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(error, MigrationError::ConcreteCollection(_)),
+            "{error}"
+        );
+        assert_eq!(error.kind(), ErrorKind::InvalidRequest);
+        delete.assert_hits_async(0).await;
+
+        let outcome = service
+            .promote_provider("demo", true, true)
+            .await
+            .expect("promotion");
+
+        assert!(outcome.dropped_previous);
+        assert_eq!(outcome.previous_target, None);
+        delete.assert_hits_async(1).await;
+        alias.assert_hits_async(1).await;
+        assert!(matches!(
+            ProcessingService::from_parts(
+                Box::new(AiLibClient::new()),
+                service_for(&server).qdrant_service
+            )
+            .promote_provider("demo", true, true)
+            .await,
+            Err(MigrationError::NotConfigured)
+        ));
+    }
+}
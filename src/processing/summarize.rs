@@ -1,8 +1,10 @@
 //! Helper routines for the summarization pipeline.
 
 use crate::processing::types::SearchTimeRange;
+use crate::qdrant::Citation;
 use sha2::{Digest, Sha256};
 use std::cmp::Ordering;
+use std::collections::BTreeMap;
 use time::{OffsetDateTime, format_description::well_known::Rfc3339};
 
 /// Episodic memory loaded for summarization.
@@ -58,45 +60,126 @@ pub(crate) fn compute_summary_key(
     hex::encode(hasher.finalize())
 }
 
+/// Longest marker body (`[12, 13-15]`) scanned before a `[` is treated as plain text.
+const MAX_MARKER_LEN: usize = 32;
+
+/// Previous summary and reader feedback steering a `refine_summary` regeneration.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Refinement<'a> {
+    pub(crate) previous_summary: &'a str,
+    pub(crate) feedback: &'a str,
+}
+
 /// Build the abstractive summarization prompt.
+///
+/// Memories are numbered from `[1]` in slice order so the markers the model cites can be
+/// resolved with [`resolve_citations`]. With a `refinement`, the previous summary and the
+/// feedback follow the notes and the model is asked to revise rather than start over.
 pub(crate) fn build_abstractive_prompt(
     project_id: &str,
     time_range: &SearchTimeRange,
     max_words: usize,
     memories: &[EpisodicMemory],
+    refinement: Option<&Refinement<'_>>,
 ) -> String {
     let start = time_range.start.as_deref().unwrap_or("(unspecified)");
     let end = time_range.end.as_deref().unwrap_or("(unspecified)");
     let mut prompt = String::new();
     prompt.push_str(&format!(
-        "System: You summarize developer activity into concise, factual bullet points. Prefer neutral tone. Avoid speculation. Include dates if present. Return at most {max_words} words. Output a single paragraph.\n\n"
+        "System: You summarize developer activity into concise, factual bullet points. Prefer neutral tone. Avoid speculation. Include dates if present. Return at most {max_words} words. Output a single paragraph. Cite the notes each statement relies on with their bracketed numbers, e.g. [3] or [1][4]; cite only numbers listed below.\n\n"
     ));
     prompt.push_str(&format!(
         "Summarize the following episodic notes for project '{project_id}' between {start} and {end}.\n"
     ));
 
-    for memory in memories {
+    for (index, memory) in memories.iter().enumerate() {
         let text = memory.text.trim();
         if text.is_empty() {
             continue;
         }
+        let marker = index + 1;
         let snippet = truncate_sentence(text, 180);
         if let Some(timestamp) = memory.timestamp.as_deref() {
-            prompt.push_str(&format!("- {timestamp}: {snippet}\n"));
+            prompt.push_str(&format!("[{marker}] {timestamp}: {snippet}\n"));
         } else {
-            prompt.push_str(&format!("- {snippet}\n"));
+            prompt.push_str(&format!("[{marker}] {snippet}\n"));
         }
     }
 
+    if let Some(refinement) = refinement {
+        prompt.push_str(&format!(
+            "\nPrevious summary:\n{}\n\nRewrite the previous summary to address this feedback, keeping the word limit and citing the numbered notes as above: {}\n",
+            refinement.previous_summary.trim(),
+            refinement.feedback.trim()
+        ));
+    }
+
     prompt
 }
 
+/// Drop the oldest `memories` until the abstractive prompt fits within `max_tokens`.
+///
+/// `memories` must be in chronological order. The newest memory is always kept, even when the
+/// prompt still exceeds the budget, so the provider's own context error can take over. Returns
+/// how many memories were dropped.
+pub(crate) fn trim_to_prompt_budget(
+    project_id: &str,
+    time_range: &SearchTimeRange,
+    max_words: usize,
+    memories: &mut Vec<EpisodicMemory>,
+    max_tokens: usize,
+    count_tokens: &dyn Fn(&str) -> usize,
+    refinement: Option<&Refinement<'_>>,
+) -> usize {
+    let mut dropped = 0;
+    while memories.len() - dropped > 1 {
+        let prompt = build_abstractive_prompt(
+            project_id,
+            time_range,
+            max_words,
+            &memories[dropped..],
+            refinement,
+        );
+        if count_tokens(&prompt) <= max_tokens {
+            break;
+        }
+        dropped += 1;
+    }
+    memories.drain(..dropped);
+    dropped
+}
+
+/// Keep half of `memories` (at least one), spread evenly across the chronological order.
+///
+/// The first and last memories always survive so a shrunken prompt still spans the whole
+/// window; a single survivor is taken from the middle.
+pub(crate) fn halve_evenly(memories: &[EpisodicMemory]) -> Vec<EpisodicMemory> {
+    let total = memories.len();
+    let keep = (total / 2).max(1);
+    if keep >= total {
+        return memories.to_vec();
+    }
+    if keep == 1 {
+        return vec![memories[total / 2].clone()];
+    }
+    (0..keep)
+        .map(|slot| memories[slot * (total - 1) / (keep - 1)].clone())
+        .collect()
+}
+
 /// Build a deterministic extractive summary bounded by a word budget.
-pub(crate) fn build_extractive_summary(memories: &[EpisodicMemory], max_words: usize) -> String {
+///
+/// Each bullet ends with the `[n]` marker of the memory it was taken from, numbered as in
+/// [`build_abstractive_prompt`], and the returned citations map those markers to memory ids.
+pub(crate) fn build_extractive_summary(
+    memories: &[EpisodicMemory],
+    max_words: usize,
+) -> (String, Vec<Citation>) {
     let mut bullets = Vec::new();
+    let mut citations = Vec::new();
     let mut used_words = 0usize;
 
-    for memory in memories {
+    for (index, memory) in memories.iter().enumerate() {
         let text = memory.text.trim();
         if text.is_empty() {
             continue;
@@ -107,10 +190,11 @@ pub(crate) fn build_extractive_summary(memories: &[EpisodicMemory], max_words: u
             continue;
         }
 
+        let marker = index + 1;
         let bullet = if let Some(timestamp) = memory.timestamp.as_deref() {
-            format!("- {}: {}", timestamp, sentence)
+            format!("- {}: {} [{}]", timestamp, sentence, marker)
         } else {
-            format!("- {}", sentence)
+            format!("- {} [{}]", sentence, marker)
         };
 
         let bullet_words = count_words(&bullet);
@@ -122,6 +206,10 @@ pub(crate) fn build_extractive_summary(memories: &[EpisodicMemory], max_words: u
         }
         used_words += bullet_words;
         bullets.push(bullet);
+        citations.push(Citation {
+            marker,
+            memory_id: memory.memory_id.clone(),
+        });
         if used_words >= max_words {
             break;
         }
@@ -130,18 +218,75 @@ pub(crate) fn build_extractive_summary(memories: &[EpisodicMemory], max_words: u
     if bullets.is_empty() {
         return memories
             .iter()
-            .find_map(|memory| {
+            .enumerate()
+            .find_map(|(index, memory)| {
                 let trimmed = memory.text.trim();
                 if trimmed.is_empty() {
                     None
                 } else {
-                    Some(truncate_sentence(trimmed, 200))
+                    let marker = index + 1;
+                    let text = format!("{} [{}]", truncate_sentence(trimmed, 200), marker);
+                    let citation = Citation {
+                        marker,
+                        memory_id: memory.memory_id.clone(),
+                    };
+                    Some((text, vec![citation]))
                 }
             })
-            .unwrap_or_else(|| "No episodic memories available.".into());
+            .unwrap_or_else(|| ("No episodic memories available.".into(), Vec::new()));
     }
 
-    bullets.join("\n")
+    (bullets.join("\n"), citations)
+}
+
+/// Resolve `[n]` markers in a model-written summary against the numbered prompt memories.
+///
+/// Accepts `[3]`, `[1][4]`, `[1, 4]`, `[2-4]`, `[^3]`, and stray whitespace. Markers that are
+/// out of range, non-numeric, or unterminated are ignored rather than failing the summary.
+/// The result is ordered by marker with one entry per cited memory.
+pub(crate) fn resolve_citations(summary: &str, memories: &[EpisodicMemory]) -> Vec<Citation> {
+    let mut cited = BTreeMap::new();
+    let mut rest = summary;
+    while let Some(open) = rest.find('[') {
+        rest = &rest[open + 1..];
+        let Some(close) = rest.find(']').filter(|close| *close <= MAX_MARKER_LEN) else {
+            continue;
+        };
+        for marker in parse_marker_body(&rest[..close]) {
+            if let Some(memory) = marker.checked_sub(1).and_then(|index| memories.get(index)) {
+                cited
+                    .entry(marker)
+                    .or_insert_with(|| memory.memory_id.clone());
+            }
+        }
+        rest = &rest[close + 1..];
+    }
+    cited
+        .into_iter()
+        .map(|(marker, memory_id)| Citation { marker, memory_id })
+        .collect()
+}
+
+/// Parse the inside of a `[...]` marker; any malformed part discards the whole marker.
+fn parse_marker_body(body: &str) -> Vec<usize> {
+    let mut markers = Vec::new();
+    for part in body.split([',', ';']) {
+        let part = part.trim().trim_start_matches('^').trim();
+        let parsed: Vec<usize> = match part.split_once(['-', '–']) {
+            Some((start, end)) => match (start.trim().parse(), end.trim().parse()) {
+                (Ok(start), Ok(end)) if start <= end && end - start < MAX_MARKER_LEN => {
+                    (start..=end).collect()
+                }
+                _ => return Vec::new(),
+            },
+            None => match part.parse() {
+                Ok(marker) => vec![marker],
+                Err(_) => return Vec::new(),
+            },
+        };
+        markers.extend(parsed);
+    }
+    markers
 }
 
 fn first_sentence(text: &str) -> &str {
@@ -213,9 +358,128 @@ mod tests {
                 Some("2025-01-02".into()),
             ),
         ];
-        let summary = build_extractive_summary(&memories, 6);
+        let (summary, citations) = build_extractive_summary(&memories, 6);
         let word_count = count_words(&summary);
         assert!(word_count <= 6);
         assert!(summary.contains("2025-01-01"));
+        assert_eq!(
+            citations,
+            vec![Citation {
+                marker: 1,
+                memory_id: "1".into()
+            }]
+        );
+    }
+
+    fn numbered_memories() -> Vec<EpisodicMemory> {
+        ["ep-a", "ep-b", "ep-c", "ep-d"]
+            .into_iter()
+            .map(|id| EpisodicMemory::new(id.into(), format!("note {id}"), None))
+            .collect()
+    }
+
+    fn markers(citations: &[Citation]) -> Vec<(usize, &str)> {
+        citations
+            .iter()
+            .map(|citation| (citation.marker, citation.memory_id.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn extractive_bullets_cite_their_source_memory() {
+        let memories = numbered_memories();
+        let (summary, citations) = build_extractive_summary(&memories, 200);
+        assert!(summary.lines().next().unwrap().ends_with("[1]"));
+        assert_eq!(
+            resolve_citations(&summary, &memories),
+            citations,
+            "extractive markers must resolve to the same map"
+        );
+        assert_eq!(citations.len(), 4);
+    }
+
+    #[test]
+    fn prompt_numbers_memories_and_requests_citations() {
+        let memories = numbered_memories();
+        let prompt =
+            build_abstractive_prompt("default", &SearchTimeRange::default(), 100, &memories, None);
+        assert!(prompt.contains("[1] note ep-a\n"));
+        assert!(prompt.contains("[4] note ep-d\n"));
+        assert!(prompt.contains("bracketed numbers"));
+    }
+
+    #[test]
+    fn refinement_prompt_appends_previous_summary_and_feedback() {
+        let memories = numbered_memories();
+        let refinement = Refinement {
+            previous_summary: "  Deploys went out [1].  ",
+            feedback: "More detail about the deployment incident",
+        };
+        let prompt = build_abstractive_prompt(
+            "default",
+            &SearchTimeRange::default(),
+            100,
+            &memories,
+            Some(&refinement),
+        );
+        let notes_end = prompt.find("[4] note ep-d\n").expect("notes listed");
+        let previous = prompt
+            .find("Previous summary:\nDeploys went out [1].\n")
+            .expect("previous summary included");
+        assert!(
+            previous > notes_end,
+            "refinement follows the numbered notes"
+        );
+        assert!(prompt.ends_with("as above: More detail about the deployment incident\n"));
+    }
+
+    #[test]
+    fn resolves_well_formed_model_citations() {
+        let memories = numbered_memories();
+        let reply = "Login shipped [1]. Search landed [2][4] and was tuned [2].";
+        assert_eq!(
+            markers(&resolve_citations(reply, &memories)),
+            vec![(1, "ep-a"), (2, "ep-b"), (4, "ep-d")]
+        );
+    }
+
+    #[test]
+    fn resolves_lists_ranges_and_footnote_markers() {
+        let memories = numbered_memories();
+        let reply = "Refactors [1, 3] preceded the outage [ 2-3 ] noted in [^4].";
+        assert_eq!(
+            markers(&resolve_citations(reply, &memories)),
+            vec![(1, "ep-a"), (2, "ep-b"), (3, "ep-c"), (4, "ep-d")]
+        );
+    }
+
+    #[test]
+    fn ignores_malformed_and_out_of_range_markers() {
+        let memories = numbered_memories();
+        let reply = "Done [0], [7], [two], [3-1], [link](http://x) [1a] arr[i] and [2";
+        assert!(resolve_citations(reply, &memories).is_empty());
+        let reply = "Mixed [9, 2] then [3] and an unterminated [4";
+        assert_eq!(
+            markers(&resolve_citations(reply, &memories)),
+            vec![(2, "ep-b"), (3, "ep-c")]
+        );
+    }
+
+    #[test]
+    fn halving_keeps_even_temporal_coverage() {
+        let memories: Vec<EpisodicMemory> = (0..10)
+            .map(|index| EpisodicMemory::new(format!("m{index}"), "note".into(), None))
+            .collect();
+        let ids = |kept: Vec<EpisodicMemory>| {
+            kept.into_iter()
+                .map(|memory| memory.memory_id)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(ids(halve_evenly(&memories)), ["m0", "m2", "m4", "m6", "m9"]);
+        assert_eq!(ids(halve_evenly(&memories[..4])), ["m0", "m3"]);
+        assert_eq!(ids(halve_evenly(&memories[..3])), ["m1"]);
+        assert_eq!(ids(halve_evenly(&memories[..1])), ["m0"]);
+        assert!(halve_evenly(&[]).is_empty());
     }
 }
@@ -1,11 +1,25 @@
 //! Core data types and error definitions for the processing pipeline.
 
 use crate::{
-    config::EmbeddingProvider,
-    qdrant::{PayloadOverrides, QdrantError},
+    config::{Config, EmbeddingProvider, get_config},
+    embedding::EmbeddingClientError,
+    processing::{
+        classify::{AutoClassify, ClassificationSummary},
+        sanitize::{TimeBoundary, format_utc_rfc3339, parse_time_boundary, sanitize_memory_type},
+    },
+    qdrant::{
+        Citation, CollectionInfo, LinkRelation, MemoryLink, PayloadIndexFailure, PayloadOverrides,
+        QdrantError, SearchParams, TimestampOrder, validate_collection_name,
+    },
 };
 use anyhow::Error as TokenizerError;
+use reqwest::StatusCode;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::time::Duration;
 use thiserror::Error;
+use uuid::Uuid;
 
 /// Errors produced while turning raw text into semantic chunks.
 #[derive(Debug, Error)]
@@ -32,10 +46,32 @@ pub enum ProcessingError {
     Chunking(#[from] ChunkingError),
     /// Embedding provider failed to produce vectors for the input text.
     #[error("Failed to generate embeddings: {0}")]
-    Embedding(#[from] crate::embedding::EmbeddingClientError),
+    Embedding(#[from] EmbeddingClientError),
     /// Qdrant interaction failed during ingestion or metadata queries.
     #[error("Qdrant request failed: {0}")]
     Qdrant(#[from] QdrantError),
+    /// Qdrant refused payload indexes while `STRICT_PAYLOAD_INDEXES` is enabled.
+    #[error(
+        "Failed to ensure payload indexes on '{collection}': {}",
+        describe_index_failures(failures)
+    )]
+    PayloadIndexes {
+        /// Collection whose indexes were being ensured.
+        collection: String,
+        /// Fields Qdrant refused to index.
+        failures: Vec<PayloadIndexFailure>,
+    },
+    /// The pushed text looks like a chat transcript and `TRANSCRIPT_POLICY` refuses it.
+    #[error("{0}")]
+    TranscriptRejected(String),
+}
+
+fn describe_index_failures(failures: &[PayloadIndexFailure]) -> String {
+    failures
+        .iter()
+        .map(|failure| format!("{} ({}): {}", failure.field, failure.schema, failure.error))
+        .collect::<Vec<_>>()
+        .join("; ")
 }
 
 /// Errors emitted while orchestrating similarity searches.
@@ -43,7 +79,7 @@ pub enum ProcessingError {
 pub enum SearchError {
     /// Embedding provider failed to return vectors for the query text.
     #[error("Failed to generate embeddings: {0}")]
-    Embedding(#[from] crate::embedding::EmbeddingClientError),
+    Embedding(#[from] EmbeddingClientError),
     /// Qdrant search request returned an error response.
     #[error("Qdrant request failed: {0}")]
     Qdrant(#[from] QdrantError),
@@ -60,19 +96,696 @@ pub enum SearchError {
     EmptyEmbedding,
 }
 
+/// Coarse failure classification shared by the HTTP and MCP error mappers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// Upstream was unreachable, timed out, or returned a 5xx/429; retrying may succeed.
+    Transient,
+    /// The caller supplied input the upstream rejected.
+    InvalidRequest,
+    /// Server-side configuration (URL, credentials, model, dimension) is wrong.
+    Configuration,
+    /// Any other failure that retrying will not fix.
+    Internal,
+    /// The server is at its concurrency limit and rejected the call without starting it.
+    ServerBusy,
+}
+
+impl ErrorKind {
+    /// Whether the same request may succeed if retried unchanged.
+    pub fn is_retryable(self) -> bool {
+        matches!(self, Self::Transient | Self::ServerBusy)
+    }
+
+    /// Stable snake_case label exposed to clients.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Transient => "transient",
+            Self::InvalidRequest => "invalid_request",
+            Self::Configuration => "configuration",
+            Self::Internal => "internal",
+            Self::ServerBusy => "server_busy",
+        }
+    }
+}
+
+/// Classify a Qdrant failure by transport outcome and response status.
+pub(crate) fn qdrant_error_kind(error: &QdrantError) -> ErrorKind {
+    match error {
+        QdrantError::InvalidUrl(_) => ErrorKind::Configuration,
+        QdrantError::Http(source) => {
+            if let Some(status) = source.status() {
+                status_kind(status)
+            } else if source.is_builder() {
+                ErrorKind::Configuration
+            } else if source.is_decode() {
+                ErrorKind::Internal
+            } else {
+                // Connect failures, timeouts, and dropped bodies.
+                ErrorKind::Transient
+            }
+        }
+        QdrantError::UnexpectedStatus { status, .. } => status_kind(*status),
+    }
+}
+
+/// Classify an embedding provider failure.
+pub(crate) fn embedding_error_kind(error: &EmbeddingClientError) -> ErrorKind {
+    match error {
+        EmbeddingClientError::ProviderUnavailable(_) => ErrorKind::Transient,
+        EmbeddingClientError::Configuration(_) => ErrorKind::Configuration,
+        EmbeddingClientError::GenerationFailed(_) => ErrorKind::Internal,
+        // A misbehaving provider build or a transient glitch; a retry may produce a clean vector.
+        EmbeddingClientError::InvalidVector { .. } => ErrorKind::Transient,
+    }
+}
+
+fn status_kind(status: StatusCode) -> ErrorKind {
+    match status {
+        StatusCode::REQUEST_TIMEOUT | StatusCode::TOO_MANY_REQUESTS => ErrorKind::Transient,
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => ErrorKind::Configuration,
+        status if status.is_server_error() => ErrorKind::Transient,
+        status if status.is_client_error() => ErrorKind::InvalidRequest,
+        _ => ErrorKind::Internal,
+    }
+}
+
+impl ProcessingError {
+    /// Classify this failure for status mapping and retry decisions.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Chunking(_) => ErrorKind::Configuration,
+            Self::Embedding(source) => embedding_error_kind(source),
+            Self::Qdrant(source) => qdrant_error_kind(source),
+            Self::PayloadIndexes { failures, .. } => {
+                failures.first().map_or(ErrorKind::Internal, |failure| {
+                    qdrant_error_kind(&failure.error)
+                })
+            }
+            Self::TranscriptRejected(_) => ErrorKind::InvalidRequest,
+        }
+    }
+
+    /// Whether the same request may succeed if retried unchanged.
+    pub fn is_retryable(&self) -> bool {
+        self.kind().is_retryable()
+    }
+}
+
+impl SearchError {
+    /// Classify this failure for status mapping and retry decisions.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Embedding(source) => embedding_error_kind(source),
+            Self::Qdrant(source) => qdrant_error_kind(source),
+            Self::DimensionMismatch { .. } => ErrorKind::Configuration,
+            Self::EmptyEmbedding => ErrorKind::Internal,
+        }
+    }
+
+    /// Whether the same request may succeed if retried unchanged.
+    pub fn is_retryable(&self) -> bool {
+        self.kind().is_retryable()
+    }
+}
+
+/// Settings that determined how a pushed document was chunked and embedded.
+///
+/// Returned with every push and stored on each point (as `profile_hash` plus a compact
+/// `ingest_profile` string) so points written under older settings can be found later.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct IngestProfile {
+    /// Embedding backend (`ollama` or `openai`).
+    pub embedding_provider: String,
+    /// Embedding model identifier.
+    pub embedding_model: String,
+    /// Vector dimension.
+    pub embedding_dimension: usize,
+    /// Token budget per chunk.
+    pub chunk_size: usize,
+    /// Tokens shared between adjacent chunks.
+    pub chunk_overlap: usize,
+    /// Splitting strategy.
+    pub strategy: &'static str,
+    /// Encoding used to count tokens.
+    pub tokenizer: &'static str,
+    /// How duplicate chunks are detected.
+    pub dedupe: &'static str,
+    /// Version of this crate that performed the ingestion.
+    pub version: &'static str,
+}
+
+impl IngestProfile {
+    /// Stable 16-hex-digit fingerprint of the profile; identical settings yield identical hashes.
+    pub fn hash(&self) -> String {
+        let encoded = serde_json::to_vec(self).unwrap_or_default();
+        hex::encode(&Sha256::digest(&encoded)[..8])
+    }
+
+    /// One-line rendering stored in point payloads.
+    pub fn compact(&self) -> String {
+        format!(
+            "{}:{}@{} chunk={}+{} {} {} dedupe={} v{}",
+            self.embedding_provider,
+            self.embedding_model,
+            self.embedding_dimension,
+            self.chunk_size,
+            self.chunk_overlap,
+            self.strategy,
+            self.tokenizer,
+            self.dedupe,
+            self.version,
+        )
+    }
+
+    /// Embedding model named by a [`compact`](Self::compact) rendering.
+    pub fn model_from_compact(compact: &str) -> Option<&str> {
+        let (_, rest) = compact.split_whitespace().next()?.split_once(':')?;
+        rest.rsplit_once('@')
+            .map(|(model, _)| model)
+            .filter(|model| !model.is_empty())
+    }
+}
+
+/// Result of [`crate::processing::ProcessingService::rename_collection`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RenameOutcome {
+    /// Points copied into the new collection.
+    pub copied: usize,
+    /// Scroll pages the copy took, each upserted in one request.
+    pub batches: usize,
+    /// Aliases that pointed at the old collection and now point at the new one.
+    pub aliases: Vec<String>,
+}
+
 /// Summary of a completed ingestion produced by [`crate::processing::ProcessingService::process_and_index`].
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct ProcessingOutcome {
     /// Number of chunks produced for the document.
     pub chunk_count: usize,
     /// Chunk size used during processing.
     pub chunk_size: usize,
+    /// Tokens shared between adjacent chunks.
+    pub overlap: usize,
+    /// Chunk size was derived from the model's context window rather than configured.
+    pub auto_sized: bool,
+    /// Embedding context window of the configured model.
+    pub embedding_context_window: usize,
+    /// Chunk size before it was clamped into bounds, when clamping changed it.
+    pub chunk_size_clamped_from: Option<usize>,
     /// Number of new vectors inserted into Qdrant.
     pub inserted: usize,
     /// Number of existing vectors that were updated in place.
     pub updated: usize,
     /// Chunks skipped within the request due to duplicate `chunk_hash`.
     pub skipped_duplicates: usize,
+    /// Chunks skipped for scoring below `CHUNK_QUALITY_MIN`.
+    pub skipped_low_quality: usize,
+    /// The source matched its `STATE_DIR` cursor, so nothing was chunked, embedded, or written.
+    pub skipped_unchanged: bool,
+    /// Chunks the unchanged source was stored as at its last sync; 0 unless `skipped_unchanged`.
+    pub skipped_unchanged_chunks: usize,
+    /// Chunks were embedded with the deterministic fallback encoder; retrieval quality is reduced.
+    pub degraded: bool,
+    /// Per-type chunk counts when `auto_classify` was requested.
+    pub classification: Option<ClassificationSummary>,
+    /// Chunking and embedding settings applied to the document.
+    pub ingest_profile: IngestProfile,
+    /// Identifier shared by the document's chunks; `None` when nothing was written.
+    pub doc_id: Option<String>,
+    /// How the text was handled after it was detected as a chat transcript.
+    pub transcript: Option<TranscriptHandling>,
+}
+
+impl ProcessingOutcome {
+    /// Chunks the push produced, counting the stored chunks of an unchanged source.
+    pub fn total_chunks(&self) -> usize {
+        self.chunk_count
+            + self.skipped_duplicates
+            + self.skipped_low_quality
+            + self.skipped_unchanged_chunks
+    }
+
+    /// Embedding calls avoided by skipping exact duplicates and unchanged sources.
+    pub fn embeddings_saved(&self) -> usize {
+        self.skipped_duplicates + self.skipped_unchanged_chunks
+    }
+
+    /// Share of [`Self::total_chunks`] skipped as duplicates; 0 when nothing was chunked.
+    pub fn duplicate_ratio(&self) -> f64 {
+        crate::metrics::duplicate_ratio(self.embeddings_saved() as u64, self.total_chunks() as u64)
+    }
+}
+
+/// Action taken on pushed text that was detected as a chat transcript.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranscriptHandling {
+    /// Indexed unchanged; the caller is warned.
+    Warned,
+    /// Assistant boilerplate was removed before chunking.
+    Stripped {
+        /// Boilerplate sentences removed.
+        sentences: usize,
+    },
+}
+
+/// Result of re-embedding points written while the embedding provider was unavailable.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReembedOutcome {
+    /// Points re-embedded with the configured provider and cleared of the fallback tag.
+    pub reembedded: usize,
+    /// Tagged points skipped because they carry no text to embed.
+    pub skipped: usize,
+}
+
+/// Example point ids kept per anomaly class by `verify_collection`.
+pub const MAX_ANOMALY_EXAMPLES: usize = 20;
+
+/// Occurrences of one class of payload anomaly found by `verify_collection`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AnomalyReport {
+    /// Points exhibiting the anomaly.
+    pub count: usize,
+    /// Up to `MAX_ANOMALY_EXAMPLES` affected point ids, in scroll order.
+    pub examples: Vec<String>,
+}
+
+impl AnomalyReport {
+    pub(crate) fn record(&mut self, id: &str) {
+        self.count += 1;
+        if self.examples.len() < MAX_ANOMALY_EXAMPLES {
+            self.examples.push(id.to_string());
+        }
+    }
+}
+
+/// Memories selected by a `prune` and what happened to them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PruneOutcome {
+    /// UTC RFC3339 cutoff; memories stamped strictly before it matched.
+    pub cutoff: String,
+    /// Points matching the prune filter when it ran.
+    pub matched: u64,
+    /// Points deleted; `0` for a dry run.
+    pub deleted: u64,
+}
+
+/// Result of a `purge_duplicates` pass over one collection.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DuplicatePurgeOutcome {
+    /// Points carrying a `chunk_hash` that were inspected.
+    pub scanned: usize,
+    /// `(project_id, chunk_hash)` pairs stored more than once.
+    pub duplicate_groups: usize,
+    /// Redundant copies deleted; `0` for a dry run.
+    pub deleted: usize,
+}
+
+/// Filters for listing stored summaries with `find_summaries`.
+#[derive(Debug, Clone, Default)]
+pub struct SummaryQuery {
+    /// Only summaries of this project.
+    pub project_id: Option<String>,
+    /// Exact `label` match.
+    pub label: Option<String>,
+    /// Exact `topic` match.
+    pub topic: Option<String>,
+    /// Bounds on the summary's `timestamp` (when it was written).
+    pub time_range: Option<SearchTimeRange>,
+    /// Maximum summaries returned, newest first.
+    pub limit: usize,
+}
+
+/// Stored summary returned by `find_summaries`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SummaryRecord {
+    /// Identifier of the summary point.
+    pub memory_id: String,
+    /// Summary text.
+    pub summary: String,
+    /// Project the summary belongs to.
+    pub project_id: Option<String>,
+    /// Label attached when the summary was stored or re-tagged.
+    pub label: Option<String>,
+    /// Topic attached when the summary was stored or re-tagged.
+    pub topic: Option<String>,
+    /// When the summary was written.
+    pub timestamp: Option<String>,
+    /// Idempotency key shared by re-runs over the same sources.
+    pub summary_key: Option<String>,
+    /// Memories the summary was built from.
+    pub source_memory_ids: Vec<String>,
+}
+
+/// Stored summary whose `source_memory_ids` reference memories that no longer exist.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OrphanedSummary {
+    /// Identifier of the summary point.
+    pub memory_id: String,
+    /// Project the summary belongs to.
+    pub project_id: Option<String>,
+    /// Sources the summary was built from.
+    pub source_count: usize,
+    /// Sources no longer stored in the collection.
+    pub missing_source_memory_ids: Vec<String>,
+}
+
+impl OrphanedSummary {
+    /// Whether some of the summary's sources still exist.
+    pub fn is_partial(&self) -> bool {
+        self.missing_source_memory_ids.len() < self.source_count
+    }
+}
+
+/// Result of checking stored summaries for deleted sources.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SummaryCheckOutcome {
+    /// Summaries examined.
+    pub checked: usize,
+    /// Summaries with at least one missing source.
+    pub orphaned: Vec<OrphanedSummary>,
+    /// Missing source references across all summaries.
+    pub dangling_references: usize,
+    /// Orphaned summaries newly tagged `summary:orphaned`.
+    pub tagged: usize,
+}
+
+/// Result of checking a collection's payloads for corruption.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VerifyOutcome {
+    /// Points read from the collection.
+    pub scanned: usize,
+    /// Points selected by the sample rate and checked.
+    pub checked: usize,
+    /// Points whose stored `chunk_hash` differs from the hash of their `text`.
+    pub hash_mismatch: AnomalyReport,
+    /// Points lacking one of the payload fields every ingest writes.
+    pub missing_fields: AnomalyReport,
+    /// Points whose `timestamp` or `ingested_at` is not RFC3339.
+    pub invalid_timestamp: AnomalyReport,
+    /// Points whose `chunk_hash` was rewritten because `fix_hashes` was set.
+    pub hashes_fixed: usize,
+    /// Points given a `timestamp_unix` because `backfill_timestamps` was set.
+    pub timestamps_backfilled: usize,
+}
+
+/// Result of warming a collection after bulk ingestion.
+#[derive(Debug, Clone)]
+pub struct WarmupOutcome {
+    /// Collection status observed before waiting.
+    pub before: CollectionInfo,
+    /// Collection status observed after indexing completed or the wait timed out.
+    pub after: CollectionInfo,
+    /// Number of warm-up searches issued once indexing settled.
+    pub warmup_queries: usize,
+    /// Wall-clock time spent waiting and warming.
+    pub elapsed: Duration,
+}
+
+/// Layout of a collection plus the payload keys observed in a small sample of its points.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CollectionProfile {
+    /// Status and indexing counters.
+    pub info: CollectionInfo,
+    /// Vector dimension configured for the collection.
+    pub vector_size: Option<u64>,
+    /// Distance metric configured for the collection.
+    pub distance: Option<String>,
+    /// Indexed payload fields mapped to their index type.
+    pub indexes: BTreeMap<String, String>,
+    /// Number of points whose payloads were sampled.
+    pub sampled_points: usize,
+    /// Payload keys seen in the sample, with how many sampled points carry each.
+    pub payload_keys: BTreeMap<String, usize>,
+    /// Repeated `chunk_hash` values in a larger sample of the collection.
+    pub dedupe_stats: DedupeStats,
+}
+
+/// Duplicate `chunk_hash` values found in a sample of a collection's points.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DedupeStats {
+    /// Sampled points that carry a `chunk_hash`.
+    pub sampled_points: usize,
+    /// Distinct hashes among them.
+    pub distinct_hashes: usize,
+    /// Sampled points whose hash was already seen in the sample.
+    pub duplicate_points: usize,
+    /// `duplicate_points / sampled_points`, or 0 for an empty sample.
+    pub duplicate_ratio: f64,
+}
+
+impl DedupeStats {
+    /// Count repeated hashes in `hashes`.
+    pub fn from_hashes<'a>(hashes: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut seen = std::collections::HashSet::new();
+        let mut sampled_points = 0;
+        for hash in hashes {
+            sampled_points += 1;
+            seen.insert(hash);
+        }
+        let duplicate_points = sampled_points - seen.len();
+        Self {
+            sampled_points,
+            distinct_hashes: seen.len(),
+            duplicate_points,
+            duplicate_ratio: crate::metrics::duplicate_ratio(
+                duplicate_points as u64,
+                sampled_points as u64,
+            ),
+        }
+    }
+}
+
+/// Which stored document `get_document` should reassemble.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DocumentSelector {
+    /// The document pushed with this `doc_id`.
+    DocId(String),
+    /// The most recently ingested document recorded for a source.
+    LatestForSource {
+        /// `source_uri` the document was pushed with.
+        source_uri: String,
+        /// Restrict the lookup to one project.
+        project_id: Option<String>,
+    },
+}
+
+/// One stored chunk of a reassembled document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentChunk {
+    /// Point identifier.
+    pub id: String,
+    /// Position of the chunk within the document.
+    pub chunk_index: u64,
+    /// Leading bytes dropped because they repeated the previous chunk's overlap.
+    pub overlap_stripped: usize,
+}
+
+/// Document text rebuilt from its chunks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReconstructedDocument {
+    /// Identifier shared by the document's chunks.
+    pub doc_id: String,
+    /// Source URI recorded at ingest.
+    pub source_uri: Option<String>,
+    /// Project the document belongs to.
+    pub project_id: Option<String>,
+    /// RFC3339 time the document was ingested.
+    pub ingested_at: Option<String>,
+    /// Stitched document text.
+    pub text: String,
+    /// Chunks in document order.
+    pub chunks: Vec<DocumentChunk>,
+    /// Indexes below the last chunk with no stored point (deduplicated or filtered at ingest).
+    pub missing_chunk_indexes: Vec<u64>,
+}
+
+/// Result of linking one memory to another.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkOutcome {
+    /// The link was stored on the source and mirrored in both `linked_to` arrays.
+    Created,
+    /// The source already carried the same link; only missing `linked_to` entries were written.
+    AlreadyLinked,
+    /// No point carries these memory ids; nothing was written.
+    MissingMemories(Vec<String>),
+}
+
+/// Link pointing at a memory from another one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InboundLink {
+    /// Relation recorded by the linking memory.
+    pub rel: LinkRelation,
+    /// Identifier of the linking memory.
+    pub source_memory_id: String,
+}
+
+/// Links resolved in both directions for one memory.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemoryLinks {
+    /// Links the memory records toward others.
+    pub outbound: Vec<MemoryLink>,
+    /// Links other memories record toward this one.
+    pub inbound: Vec<InboundLink>,
+}
+
+/// Approximate storage footprint of a collection.
+///
+/// Raw vectors plus serialized payloads, extrapolated from a payload sample. HNSW graphs,
+/// payload indexes, and segment overhead are not included, so treat the total as a lower bound.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StorageEstimate {
+    /// Points reported by Qdrant.
+    pub points_count: u64,
+    /// Vector dimension.
+    pub vector_size: u64,
+    /// Vector element type used for the byte width.
+    pub vector_datatype: String,
+    /// Bytes of one stored vector.
+    pub vector_bytes: u64,
+    /// Points whose payloads were sampled.
+    pub sampled_points: usize,
+    /// Mean serialized payload size across the sample.
+    pub average_payload_bytes: u64,
+    /// `points_count × (vector_bytes + average_payload_bytes)`.
+    pub estimated_bytes: u64,
+}
+
+impl StorageEstimate {
+    /// Extrapolate the footprint of `points_count` points from `payload_sizes`.
+    ///
+    /// `datatype` defaults to `float32`; unknown types are counted as four bytes per element.
+    pub fn compute(
+        points_count: u64,
+        vector_size: u64,
+        datatype: Option<&str>,
+        payload_sizes: &[usize],
+    ) -> Self {
+        let datatype = datatype.unwrap_or("float32");
+        let width = match datatype.to_ascii_lowercase().as_str() {
+            "uint8" => 1,
+            "float16" => 2,
+            _ => 4,
+        };
+        let vector_bytes = vector_size * width;
+        let sampled = payload_sizes.len() as u64;
+        let total: u64 = payload_sizes.iter().map(|size| *size as u64).sum();
+        let average_payload_bytes = (total + sampled / 2).checked_div(sampled).unwrap_or(0);
+        Self {
+            points_count,
+            vector_size,
+            vector_datatype: datatype.to_string(),
+            vector_bytes,
+            sampled_points: payload_sizes.len(),
+            average_payload_bytes,
+            estimated_bytes: points_count.saturating_mul(vector_bytes + average_payload_bytes),
+        }
+    }
+}
+
+/// Caveat attached to every [`StorageStatsReport`].
+pub const STORAGE_STATS_NOTE: &str = "Approximate: payload bytes extrapolate a sampled mean over the point count and vector bytes assume raw storage. HNSW graphs, payload indexes, and segment overhead are not included; segment counts drop once the optimizer merges segments.";
+
+/// Approximate disk usage of a collection, or of one project's points within it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct StorageStats {
+    /// Collection the figures describe.
+    pub collection: String,
+    /// Project the points were counted and sampled for; `None` covers the whole collection.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    /// Points counted (within the project when one is set).
+    pub points_count: u64,
+    /// Vector dimension.
+    pub vector_size: u64,
+    /// Vector element type used for the byte width.
+    pub vector_datatype: String,
+    /// Points whose payloads were sampled.
+    pub sampled_points: usize,
+    /// Mean serialized payload size across the sample.
+    pub average_payload_bytes: u64,
+    /// `points_count × average_payload_bytes`.
+    pub payload_bytes: u64,
+    /// `points_count × vector_size × element width`.
+    pub vector_bytes: u64,
+    /// `payload_bytes + vector_bytes`.
+    pub estimated_bytes: u64,
+    /// Segments Qdrant reports for the whole collection.
+    pub segments_count: Option<u64>,
+    /// Optimizer status; `yellow` while segments are still being merged.
+    pub status: Option<String>,
+}
+
+impl StorageStats {
+    /// Scale a per-point [`StorageEstimate`] to totals, attaching the collection's segment info.
+    pub fn from_estimate(
+        collection: String,
+        project_id: Option<String>,
+        estimate: &StorageEstimate,
+        info: &CollectionInfo,
+    ) -> Self {
+        let points = estimate.points_count;
+        let payload_bytes = points.saturating_mul(estimate.average_payload_bytes);
+        let vector_bytes = points.saturating_mul(estimate.vector_bytes);
+        Self {
+            collection,
+            project_id,
+            points_count: points,
+            vector_size: estimate.vector_size,
+            vector_datatype: estimate.vector_datatype.clone(),
+            sampled_points: estimate.sampled_points,
+            average_payload_bytes: estimate.average_payload_bytes,
+            payload_bytes,
+            vector_bytes,
+            estimated_bytes: payload_bytes.saturating_add(vector_bytes),
+            segments_count: info.segments_count,
+            status: info.status.clone(),
+        }
+    }
+}
+
+/// Storage figures for one or more collections, shared by the `storage-stats` tool and
+/// `GET /storage-stats`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StorageStatsReport {
+    /// Always `true`; every byte count is an estimate.
+    pub approximate: bool,
+    /// What the estimates leave out.
+    pub note: &'static str,
+    /// Project filter applied to every collection.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    /// Per-collection figures, in collection order.
+    pub collections: Vec<StorageStats>,
+    /// Sum of `points_count`.
+    pub total_points: u64,
+    /// Sum of `payload_bytes`.
+    pub total_payload_bytes: u64,
+    /// Sum of `vector_bytes`.
+    pub total_vector_bytes: u64,
+    /// Sum of `estimated_bytes`.
+    pub total_estimated_bytes: u64,
+}
+
+impl StorageStatsReport {
+    /// Total the per-collection figures.
+    pub fn new(project_id: Option<String>, collections: Vec<StorageStats>) -> Self {
+        let sum = |field: fn(&StorageStats) -> u64| {
+            collections
+                .iter()
+                .fold(0u64, |total, stats| total.saturating_add(field(stats)))
+        };
+        Self {
+            approximate: true,
+            note: STORAGE_STATS_NOTE,
+            project_id,
+            total_points: sum(|stats| stats.points_count),
+            total_payload_bytes: sum(|stats| stats.payload_bytes),
+            total_vector_bytes: sum(|stats| stats.vector_bytes),
+            total_estimated_bytes: sum(|stats| stats.estimated_bytes),
+            collections,
+        }
+    }
 }
 
 /// Reachability and readiness snapshot for Qdrant.
@@ -84,10 +797,31 @@ pub struct QdrantHealthSnapshot {
     pub default_collection_present: bool,
     /// Optional diagnostic string captured when Qdrant is unreachable.
     pub error: Option<String>,
+    /// Server version detected at startup, if any.
+    pub version: Option<String>,
+    /// Read replica status, when `QDRANT_READ_URL` is set.
+    pub read_replica: Option<ReadReplicaHealth>,
+    /// Check that decided reachability: `healthz`, `readyz`, or `collections`.
+    pub probe: &'static str,
 }
 
-/// Parameters supplied to the search pipeline.
+/// Reachability of the Qdrant read replica.
 #[derive(Debug, Clone)]
+pub struct ReadReplicaHealth {
+    /// Normalized replica URL.
+    pub url: String,
+    /// Whether the replica answered the probe.
+    pub reachable: bool,
+    /// Diagnostic string captured when the replica is unreachable.
+    pub error: Option<String>,
+}
+
+/// Parameters supplied to the search pipeline.
+///
+/// Build one with [`SearchRequest::builder`] to get the same validation the MCP `search` tool
+/// applies; a hand-written literal is only accepted by the deprecated
+/// `ProcessingService::search_memories_unchecked`.
+#[derive(Debug, Clone, Default)]
 pub struct SearchRequest {
     /// Natural language query text to embed.
     pub query_text: String,
@@ -99,12 +833,570 @@ pub struct SearchRequest {
     pub memory_type: Option<String>,
     /// Optional contains-any filter for `tags`.
     pub tags: Option<Vec<String>>,
+    /// Only match points whose id (`memory_id`) is listed, at most [`MAX_INCLUDE_IDS`].
+    pub include_ids: Option<Vec<String>>,
     /// Optional timestamp boundaries for `timestamp` payload field.
     pub time_range: Option<SearchTimeRange>,
     /// Maximum number of results to return (defaults applied downstream).
     pub limit: Option<usize>,
     /// Minimum score accepted from Qdrant (defaults applied downstream).
     pub score_threshold: Option<f32>,
+    /// Optional HNSW/quantization tuning forwarded to Qdrant as query `params`.
+    pub search_params: Option<SearchParams>,
+    /// Return the newest (or oldest) of the nearest candidates instead of the most similar.
+    pub order_by_timestamp: Option<TimestampOrder>,
+    /// Skip records stored without `text`.
+    pub require_text: bool,
+    /// How `query_text` over `MAX_QUERY_TOKENS` is shortened before embedding.
+    pub query_compression: QueryCompression,
+    /// Hit fields to return, from [`SEARCH_HIT_FIELDS`]; every field when `None`.
+    ///
+    /// Only these payload keys are fetched from Qdrant, so leaving out `text` keeps large chunks
+    /// off the wire. `id` and `score` are always returned.
+    pub fields: Option<Vec<String>>,
+}
+
+/// Most ids a search may be restricted to with [`SearchRequest::include_ids`].
+pub const MAX_INCLUDE_IDS: usize = 256;
+
+/// Hit fields a search can be narrowed to with [`SearchRequest::fields`].
+///
+/// Apart from `id` and `score`, each name is also the payload key it is read from.
+pub const SEARCH_HIT_FIELDS: &[&str] = &[
+    "id",
+    "score",
+    "text",
+    "project_id",
+    "memory_type",
+    "tags",
+    "timestamp",
+    "source_uri",
+    "doc_id",
+    "chunk_index",
+    "citations",
+    "links",
+];
+
+impl SearchRequest {
+    /// Start a fluent, validating builder.
+    ///
+    /// ```no_run
+    /// use rustymcp::processing::{ProcessingService, SearchRequest};
+    ///
+    /// # async fn run(service: &ProcessingService) -> Result<(), Box<dyn std::error::Error>> {
+    /// let request = SearchRequest::builder()
+    ///     .query_text("how do we rotate API keys?")
+    ///     .project_id("ops")
+    ///     .memory_type("procedural")
+    ///     .tags(["security"])
+    ///     .limit(5)
+    ///     .score_threshold(0.3)
+    ///     .build()?;
+    /// let outcome = service.search_memories(request).await?;
+    /// println!("{} hits", outcome.hits.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Invalid input is reported before anything is embedded:
+    ///
+    /// ```no_run
+    /// use rustymcp::processing::{SearchRequest, SearchValidationError};
+    ///
+    /// let error = SearchRequest::builder()
+    ///     .query_text("deploys")
+    ///     .memory_type("diary")
+    ///     .build()
+    ///     .unwrap_err();
+    /// assert_eq!(error, SearchValidationError::InvalidMemoryType);
+    /// ```
+    pub fn builder() -> SearchRequestBuilder {
+        SearchRequestBuilder::default()
+    }
+}
+
+/// Fluent constructor for [`SearchRequest`], validated by [`SearchRequestBuilder::build`].
+#[derive(Debug, Clone, Default)]
+pub struct SearchRequestBuilder {
+    request: SearchRequest,
+}
+
+impl From<SearchRequest> for SearchRequestBuilder {
+    fn from(request: SearchRequest) -> Self {
+        Self { request }
+    }
+}
+
+impl SearchRequestBuilder {
+    /// Set the natural language query to embed.
+    pub fn query_text(mut self, query_text: impl Into<String>) -> Self {
+        self.request.query_text = query_text.into();
+        self
+    }
+
+    /// Search this collection instead of `QDRANT_COLLECTION_NAME`.
+    pub fn collection(mut self, collection: impl Into<String>) -> Self {
+        self.request.collection = Some(collection.into());
+        self
+    }
+
+    /// Only match memories of this project.
+    pub fn project_id(mut self, project_id: impl Into<String>) -> Self {
+        self.request.project_id = Some(project_id.into());
+        self
+    }
+
+    /// Only match `episodic`, `semantic`, or `procedural` memories (case-insensitive).
+    pub fn memory_type(mut self, memory_type: impl Into<String>) -> Self {
+        self.request.memory_type = Some(memory_type.into());
+        self
+    }
+
+    /// Only match memories carrying at least one of these tags.
+    pub fn tags<I, T>(mut self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.request.tags = Some(tags.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Only match memories whose `memory_id` is one of these.
+    pub fn include_ids<I, T>(mut self, ids: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.request.include_ids = Some(ids.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Only match memories whose `timestamp` falls within these inclusive bounds.
+    pub fn time_range(mut self, time_range: SearchTimeRange) -> Self {
+        self.request.time_range = Some(time_range);
+        self
+    }
+
+    /// Return at most this many hits.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.request.limit = Some(limit);
+        self
+    }
+
+    /// Drop hits scoring below this similarity.
+    pub fn score_threshold(mut self, score_threshold: f32) -> Self {
+        self.request.score_threshold = Some(score_threshold);
+        self
+    }
+
+    /// Forward HNSW/quantization tuning to Qdrant.
+    pub fn search_params(mut self, search_params: SearchParams) -> Self {
+        self.request.search_params = Some(search_params);
+        self
+    }
+
+    /// Return the newest (or oldest) of the nearest candidates instead of the most similar.
+    pub fn order_by_timestamp(mut self, order: TimestampOrder) -> Self {
+        self.request.order_by_timestamp = Some(order);
+        self
+    }
+
+    /// Skip records stored without `text`.
+    pub fn require_text(mut self, require_text: bool) -> Self {
+        self.request.require_text = require_text;
+        self
+    }
+
+    /// Choose how a query over `MAX_QUERY_TOKENS` is shortened.
+    pub fn query_compression(mut self, query_compression: QueryCompression) -> Self {
+        self.request.query_compression = query_compression;
+        self
+    }
+
+    /// Return only these hit fields (see [`SEARCH_HIT_FIELDS`]).
+    pub fn fields<I, T>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.request.fields = Some(fields.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Validate against the limits of the loaded configuration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the global configuration has not been initialized.
+    pub fn build(self) -> Result<ValidatedSearchRequest, SearchValidationError> {
+        self.build_with_limits(&SearchLimits::from_config(get_config()))
+    }
+
+    /// Validate against explicit limits, normalizing the request on success.
+    ///
+    /// Memory types are lower-cased, tags and ids trimmed and deduplicated, time bounds rewritten as UTC
+    /// RFC3339, and the limit and score threshold resolved to their effective values.
+    pub fn build_with_limits(
+        self,
+        limits: &SearchLimits,
+    ) -> Result<ValidatedSearchRequest, SearchValidationError> {
+        let mut request = self.request;
+        if request.query_text.trim().is_empty() {
+            return Err(SearchValidationError::EmptyQuery);
+        }
+        if let Some(name) = request.collection.as_deref() {
+            validate_collection_name(name).map_err(SearchValidationError::InvalidCollection)?;
+        }
+        request.memory_type = validate_memory_type(request.memory_type)?;
+        let tags_provided = request.tags.is_some();
+        request.tags = validate_tags(request.tags, tags_provided)?;
+        request.include_ids = request.include_ids.map(validate_include_ids).transpose()?;
+        let range_provided = request.time_range.is_some();
+        request.time_range = validate_time_range(request.time_range, range_provided)?;
+        let (limit, clamped_limit) = resolve_limit(
+            request.limit,
+            limits.default_limit,
+            limits.max_limit,
+            limits.clamp_limit,
+        )?;
+        request.limit = Some(limit);
+        validate_score_threshold(request.score_threshold)?;
+        request.score_threshold = Some(
+            request
+                .score_threshold
+                .unwrap_or(limits.default_score_threshold),
+        );
+        if let Some(params) = request.search_params.as_ref() {
+            validate_search_params(params)?;
+        }
+        request.fields = request.fields.map(validate_search_fields).transpose()?;
+        Ok(ValidatedSearchRequest {
+            request,
+            clamped_limit,
+        })
+    }
+}
+
+/// Result-count and score defaults a search request is validated against.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SearchLimits {
+    /// Limit used when the request sets none.
+    pub default_limit: usize,
+    /// Largest accepted limit.
+    pub max_limit: usize,
+    /// Clamp limits above `max_limit` instead of rejecting them.
+    pub clamp_limit: bool,
+    /// Score threshold used when the request sets none.
+    pub default_score_threshold: f32,
+}
+
+impl SearchLimits {
+    /// Read the `SEARCH_*` limits from `config`.
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            default_limit: config.search_default_limit,
+            max_limit: config.search_max_limit,
+            clamp_limit: config.search_clamp_limit,
+            default_score_threshold: config.search_default_score_threshold,
+        }
+    }
+}
+
+/// A [`SearchRequest`] that passed validation, with its limit and score threshold resolved.
+#[derive(Debug, Clone)]
+pub struct ValidatedSearchRequest {
+    request: SearchRequest,
+    clamped_limit: bool,
+}
+
+impl ValidatedSearchRequest {
+    /// The normalized request.
+    pub fn request(&self) -> &SearchRequest {
+        &self.request
+    }
+
+    /// Whether a limit above the maximum was clamped rather than rejected.
+    pub fn clamped_limit(&self) -> bool {
+        self.clamped_limit
+    }
+
+    /// Unwrap the normalized request.
+    pub fn into_inner(self) -> SearchRequest {
+        self.request
+    }
+}
+
+/// Reasons a search request is rejected before it reaches the embedding provider or Qdrant.
+///
+/// Messages name the offending field as the MCP `search` tool and HTTP API report it.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SearchValidationError {
+    /// `query_text` was empty or whitespace.
+    #[error("`query_text` must not be empty")]
+    EmptyQuery,
+    /// The collection name is not a safe Qdrant identifier.
+    #[error("Invalid `collection`: {0}")]
+    InvalidCollection(String),
+    /// `memory_type` is not one of the known variants.
+    #[error("`memory_type` must be one of episodic|semantic|procedural")]
+    InvalidMemoryType,
+    /// `tags` was empty or contained a blank tag.
+    #[error("`tags` must be an array of non-empty strings")]
+    InvalidTags,
+    /// `include_ids` was empty or listed more than [`MAX_INCLUDE_IDS`] distinct ids.
+    #[error("`include_ids` must list between 1 and {} ids", MAX_INCLUDE_IDS)]
+    IncludeIdsCount,
+    /// An `include_ids` entry is neither a UUID nor an unsigned integer.
+    #[error("`include_ids` entries must be UUIDs or unsigned integers; got '{0}'")]
+    InvalidIncludeId(String),
+    /// A time bound could not be parsed.
+    #[error("`{field}` must be a valid RFC3339 timestamp or YYYY-MM-DD date (got '{value}')")]
+    InvalidTimestamp {
+        /// Offending field, such as `time_range.start`.
+        field: &'static str,
+        /// Value as supplied.
+        value: String,
+    },
+    /// `time_range` was given with neither bound.
+    #[error("`time_range` must include `start`, `end`, or both")]
+    EmptyTimeRange,
+    /// `time_range.start` is after `time_range.end`.
+    #[error("`time_range.start` must be earlier than or equal to `time_range.end`")]
+    InvertedTimeRange,
+    /// `limit` was zero, or above the maximum without clamping.
+    #[error("`limit` must be between 1 and {max}")]
+    LimitOutOfRange {
+        /// Largest accepted limit.
+        max: usize,
+    },
+    /// `score_threshold` was outside `0.0..=1.0`.
+    #[error("`score_threshold` must be between 0.0 and 1.0")]
+    ScoreThresholdOutOfRange,
+    /// `search_params.hnsw_ef` was zero.
+    #[error("`search_params.hnsw_ef` must be at least 1")]
+    InvalidHnswEf,
+    /// `search_params.quantization.oversampling` was below 1.0 or not finite.
+    #[error("`search_params.quantization.oversampling` must be at least 1.0")]
+    InvalidOversampling,
+    /// `fields` named something other than a [`SEARCH_HIT_FIELDS`] entry.
+    #[error("`fields` entries must be one of {}; got '{0}'", SEARCH_HIT_FIELDS.join("|"))]
+    UnknownField(String),
+}
+
+/// Lower-case a `memory_type` filter, rejecting unknown or blank values.
+pub(crate) fn validate_memory_type(
+    memory_type: Option<String>,
+) -> Result<Option<String>, SearchValidationError> {
+    memory_type
+        .map(|value| {
+            sanitize_memory_type(Some(value)).ok_or(SearchValidationError::InvalidMemoryType)
+        })
+        .transpose()
+}
+
+/// Trim and dedupe a tag filter; `provided` marks a `tags` key that was present but null.
+pub(crate) fn validate_tags(
+    tags: Option<Vec<String>>,
+    provided: bool,
+) -> Result<Option<Vec<String>>, SearchValidationError> {
+    let Some(tags) = tags else {
+        return if provided {
+            Err(SearchValidationError::InvalidTags)
+        } else {
+            Ok(None)
+        };
+    };
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags {
+        let trimmed = tag.trim();
+        if trimmed.is_empty() {
+            return Err(SearchValidationError::InvalidTags);
+        }
+        if !normalized.iter().any(|seen| seen == trimmed) {
+            normalized.push(trimmed.to_string());
+        }
+    }
+    if normalized.is_empty() {
+        return Err(SearchValidationError::InvalidTags);
+    }
+    Ok(Some(normalized))
+}
+
+/// Trim and dedupe `include_ids`, rejecting ids Qdrant cannot address and oversized sets.
+///
+/// UUIDs are rewritten in their hyphenated lower-case form, as Qdrant reports point ids.
+pub(crate) fn validate_include_ids(ids: Vec<String>) -> Result<Vec<String>, SearchValidationError> {
+    let mut normalized: Vec<String> = Vec::with_capacity(ids.len());
+    for id in ids {
+        let trimmed = id.trim();
+        let canonical = match Uuid::parse_str(trimmed) {
+            Ok(uuid) => uuid.hyphenated().to_string(),
+            Err(_) if trimmed.parse::<u64>().is_ok() => trimmed.to_string(),
+            Err(_) => return Err(SearchValidationError::InvalidIncludeId(id)),
+        };
+        if !normalized.contains(&canonical) {
+            normalized.push(canonical);
+        }
+    }
+    if normalized.is_empty() || normalized.len() > MAX_INCLUDE_IDS {
+        return Err(SearchValidationError::IncludeIdsCount);
+    }
+    Ok(normalized)
+}
+
+/// Normalize time bounds to UTC RFC3339 and check their order.
+///
+/// Date-only bounds expand to the start (or end) of the day. A range with neither bound is
+/// dropped, or rejected when `provided` says the caller spelled it out.
+pub(crate) fn validate_time_range(
+    time_range: Option<SearchTimeRange>,
+    provided: bool,
+) -> Result<Option<SearchTimeRange>, SearchValidationError> {
+    let Some(mut range) = time_range else {
+        return Ok(None);
+    };
+    let normalize = |field: &'static str, value: &mut String, boundary: TimeBoundary| {
+        let parsed = parse_time_boundary(value.trim(), boundary).ok_or_else(|| {
+            SearchValidationError::InvalidTimestamp {
+                field,
+                value: value.clone(),
+            }
+        })?;
+        *value = format_utc_rfc3339(parsed);
+        Ok::<_, SearchValidationError>(parsed)
+    };
+    let start = range
+        .start
+        .as_mut()
+        .map(|start| normalize("time_range.start", start, TimeBoundary::Start))
+        .transpose()?;
+    let end = range
+        .end
+        .as_mut()
+        .map(|end| normalize("time_range.end", end, TimeBoundary::End))
+        .transpose()?;
+
+    if range.start.is_none() && range.end.is_none() {
+        return if provided {
+            Err(SearchValidationError::EmptyTimeRange)
+        } else {
+            Ok(None)
+        };
+    }
+    if start.zip(end).is_some_and(|(start, end)| start > end) {
+        return Err(SearchValidationError::InvertedTimeRange);
+    }
+    Ok(Some(range))
+}
+
+/// Resolve the effective limit, returning whether an oversized value was clamped.
+///
+/// With `clamp` disabled, values above `max` are rejected; zero is always rejected.
+pub(crate) fn resolve_limit(
+    limit: Option<usize>,
+    default: usize,
+    max: usize,
+    clamp: bool,
+) -> Result<(usize, bool), SearchValidationError> {
+    match limit {
+        None => Ok((default, false)),
+        Some(value) if (1..=max).contains(&value) => Ok((value, false)),
+        Some(value) if value > max && clamp => Ok((max, true)),
+        Some(_) => Err(SearchValidationError::LimitOutOfRange { max }),
+    }
+}
+
+/// Reject a score threshold outside `0.0..=1.0`.
+pub(crate) fn validate_score_threshold(
+    score_threshold: Option<f32>,
+) -> Result<(), SearchValidationError> {
+    if score_threshold.is_some_and(|threshold| !(0.0..=1.0).contains(&threshold)) {
+        return Err(SearchValidationError::ScoreThresholdOutOfRange);
+    }
+    Ok(())
+}
+
+/// Trim and dedupe a `fields` projection, rejecting names outside [`SEARCH_HIT_FIELDS`].
+pub(crate) fn validate_search_fields(
+    fields: Vec<String>,
+) -> Result<Vec<String>, SearchValidationError> {
+    let mut normalized: Vec<String> = Vec::with_capacity(fields.len());
+    for field in fields {
+        let trimmed = field.trim();
+        if !SEARCH_HIT_FIELDS.contains(&trimmed) {
+            return Err(SearchValidationError::UnknownField(field));
+        }
+        if !normalized.iter().any(|seen| seen == trimmed) {
+            normalized.push(trimmed.to_string());
+        }
+    }
+    Ok(normalized)
+}
+
+/// Reject HNSW/quantization knobs Qdrant would refuse.
+pub(crate) fn validate_search_params(params: &SearchParams) -> Result<(), SearchValidationError> {
+    if params.hnsw_ef == Some(0) {
+        return Err(SearchValidationError::InvalidHnswEf);
+    }
+    let oversampling = params
+        .quantization
+        .as_ref()
+        .and_then(|quantization| quantization.oversampling);
+    if oversampling.is_some_and(|value| !(value.is_finite() && value >= 1.0)) {
+        return Err(SearchValidationError::InvalidOversampling);
+    }
+    Ok(())
+}
+
+/// Strategy for shortening a query longer than `MAX_QUERY_TOKENS`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QueryCompression {
+    /// Keep the leading sentences that fit the budget.
+    #[default]
+    Truncate,
+    /// Ask the summarization model for a one-sentence restatement, truncating if it fails.
+    Llm,
+}
+
+/// Hits of a semantic search along with the query that was actually embedded.
+#[derive(Debug, Clone)]
+pub struct SearchOutcome {
+    /// Matching records, ordered as requested.
+    pub hits: Vec<SearchHit>,
+    /// Query text sent to the embedding provider.
+    pub effective_query: String,
+    /// Whether the query was cut to fit `MAX_QUERY_TOKENS`.
+    pub query_truncated: bool,
+    /// Whether the summarization model rewrote the query.
+    pub query_compressed: bool,
+}
+
+/// Parameters for an exact lookup by `memory_id` and/or `chunk_hash`, bypassing embeddings.
+#[derive(Debug, Clone, Default)]
+pub struct LookupRequest {
+    /// Memory identifier the record must carry.
+    pub memory_id: Option<String>,
+    /// Content hash the record must carry.
+    pub chunk_hash: Option<String>,
+    /// Optional Qdrant collection override.
+    pub collection: Option<String>,
+    /// Optional payload filter for `project_id`.
+    pub project_id: Option<String>,
+    /// Optional payload filter for `memory_type`.
+    pub memory_type: Option<String>,
+    /// Optional contains-any filter for `tags`.
+    pub tags: Option<Vec<String>>,
+    /// Only match points whose id (`memory_id`) is listed.
+    pub include_ids: Option<Vec<String>>,
+    /// Optional timestamp boundaries for `timestamp` payload field.
+    pub time_range: Option<SearchTimeRange>,
+    /// Maximum number of records to return (defaults applied downstream).
+    pub limit: Option<usize>,
+    /// Skip records stored without `text`.
+    pub require_text: bool,
+    /// Substring the stored `text` must contain, matched case-sensitively.
+    pub text_contains: Option<String>,
 }
 
 /// Inclusive timestamp boundaries expressed as RFC3339 strings.
@@ -135,6 +1427,61 @@ pub struct SearchHit {
     pub timestamp: Option<String>,
     /// Stored source URI, if available.
     pub source_uri: Option<String>,
+    /// Document the chunk was pushed with, if recorded.
+    pub doc_id: Option<String>,
+    /// Position of the chunk within its document, if recorded.
+    pub chunk_index: Option<u64>,
+    /// Time the point was written (`ingested_at`), if available.
+    pub ingested_at: Option<String>,
+    /// Embedding model recorded in the point's ingest profile, if available.
+    pub embedding_model: Option<String>,
+    /// Citation map stored on summaries, if available.
+    pub citations: Option<Vec<Citation>>,
+    /// Outbound links recorded with `link-memories`, if any.
+    pub links: Option<Vec<MemoryLink>>,
+    /// Collection the point is stored in, with aliases resolved; set by searches and lookups.
+    pub collection: Option<String>,
+}
+
+impl SearchHit {
+    /// Clear the fields not named in `fields`; `id` and `score` are always kept.
+    ///
+    /// `ingested_at` and `embedding_model` are not selectable and are always cleared.
+    pub fn retain_fields(&mut self, fields: &[String]) {
+        let keep = |name: &str| fields.iter().any(|field| field == name);
+        if !keep("text") {
+            self.text = None;
+        }
+        if !keep("project_id") {
+            self.project_id = None;
+        }
+        if !keep("memory_type") {
+            self.memory_type = None;
+        }
+        if !keep("tags") {
+            self.tags = None;
+        }
+        if !keep("timestamp") {
+            self.timestamp = None;
+        }
+        if !keep("source_uri") {
+            self.source_uri = None;
+        }
+        if !keep("doc_id") {
+            self.doc_id = None;
+        }
+        if !keep("chunk_index") {
+            self.chunk_index = None;
+        }
+        if !keep("citations") {
+            self.citations = None;
+        }
+        if !keep("links") {
+            self.links = None;
+        }
+        self.ingested_at = None;
+        self.embedding_model = None;
+    }
 }
 
 /// Optional metadata passed along with a `push` request.
@@ -148,6 +1495,10 @@ pub struct IngestMetadata {
     pub tags: Option<Vec<String>>,
     /// Optional URI describing the source document for traceability.
     pub source_uri: Option<String>,
+    /// Validated UTC RFC3339 timestamp stored instead of the ingest time (for backdated notes).
+    pub timestamp: Option<String>,
+    /// Classify each chunk individually, overriding `memory_type` per chunk.
+    pub auto_classify: Option<AutoClassify>,
 }
 
 impl IngestMetadata {
@@ -161,3 +1512,494 @@ impl IngestMetadata {
 pub fn embedding_context_window(provider: EmbeddingProvider, model: &str) -> usize {
     super::chunking::embedding_context_window(provider, model)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use httpmock::prelude::*;
+
+    fn status_error(code: u16) -> QdrantError {
+        QdrantError::UnexpectedStatus {
+            status: StatusCode::from_u16(code).expect("valid status"),
+            body: String::new(),
+        }
+    }
+
+    async fn http_error(server: &MockServer, status: u16, body: &str) -> reqwest::Error {
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path("/probe");
+                then.status(status).body(body);
+            })
+            .await;
+        let response = reqwest::get(server.url("/probe")).await.expect("response");
+        match response.error_for_status() {
+            Err(error) => error,
+            Ok(response) => response
+                .json::<serde_json::Value>()
+                .await
+                .expect_err("body is not JSON"),
+        }
+    }
+
+    #[test]
+    fn error_kind_only_retries_transient_failures() {
+        assert!(ErrorKind::Transient.is_retryable());
+        assert!(!ErrorKind::InvalidRequest.is_retryable());
+        assert!(!ErrorKind::Configuration.is_retryable());
+        assert!(!ErrorKind::Internal.is_retryable());
+        assert_eq!(ErrorKind::InvalidRequest.as_str(), "invalid_request");
+    }
+
+    #[test]
+    fn qdrant_statuses_are_classified() {
+        for code in [408, 429, 500, 502, 503, 504] {
+            assert_eq!(
+                qdrant_error_kind(&status_error(code)),
+                ErrorKind::Transient,
+                "{code}"
+            );
+        }
+        for code in [401, 403] {
+            assert_eq!(
+                qdrant_error_kind(&status_error(code)),
+                ErrorKind::Configuration,
+                "{code}"
+            );
+        }
+        for code in [400, 404, 409, 422] {
+            assert_eq!(
+                qdrant_error_kind(&status_error(code)),
+                ErrorKind::InvalidRequest,
+                "{code}"
+            );
+        }
+        assert_eq!(qdrant_error_kind(&status_error(302)), ErrorKind::Internal);
+        assert_eq!(
+            qdrant_error_kind(&QdrantError::InvalidUrl("nope".into())),
+            ErrorKind::Configuration
+        );
+    }
+
+    #[tokio::test]
+    async fn qdrant_transport_errors_are_classified() {
+        let builder = reqwest::Client::new()
+            .get("not a url")
+            .send()
+            .await
+            .expect_err("invalid url");
+        assert_eq!(
+            qdrant_error_kind(&QdrantError::Http(builder)),
+            ErrorKind::Configuration
+        );
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind");
+        let port = listener.local_addr().expect("addr").port();
+        drop(listener);
+        let connect = reqwest::get(format!("http://127.0.0.1:{port}/"))
+            .await
+            .expect_err("connection refused");
+        assert_eq!(
+            qdrant_error_kind(&QdrantError::Http(connect)),
+            ErrorKind::Transient
+        );
+
+        let server = MockServer::start_async().await;
+        let unavailable = http_error(&server, 503, "").await;
+        assert_eq!(
+            qdrant_error_kind(&QdrantError::Http(unavailable)),
+            ErrorKind::Transient
+        );
+
+        let server = MockServer::start_async().await;
+        let decode = http_error(&server, 200, "not json").await;
+        assert_eq!(
+            qdrant_error_kind(&QdrantError::Http(decode)),
+            ErrorKind::Internal
+        );
+    }
+
+    #[test]
+    fn embedding_errors_are_classified() {
+        let cases = [
+            (
+                EmbeddingClientError::ProviderUnavailable("down".into()),
+                ErrorKind::Transient,
+            ),
+            (
+                EmbeddingClientError::Configuration("no key".into()),
+                ErrorKind::Configuration,
+            ),
+            (
+                EmbeddingClientError::GenerationFailed("bad".into()),
+                ErrorKind::Internal,
+            ),
+            (
+                EmbeddingClientError::InvalidVector {
+                    index: 0,
+                    reason: "contains NaN components",
+                },
+                ErrorKind::Transient,
+            ),
+        ];
+        for (error, expected) in cases {
+            assert_eq!(embedding_error_kind(&error), expected, "{error}");
+        }
+    }
+
+    #[test]
+    fn processing_errors_are_classified() {
+        let cases = [
+            (
+                ProcessingError::Chunking(ChunkingError::InvalidChunkSize),
+                ErrorKind::Configuration,
+            ),
+            (
+                ProcessingError::Embedding(EmbeddingClientError::ProviderUnavailable(
+                    "down".into(),
+                )),
+                ErrorKind::Transient,
+            ),
+            (
+                ProcessingError::Qdrant(status_error(503)),
+                ErrorKind::Transient,
+            ),
+            (
+                ProcessingError::Qdrant(status_error(400)),
+                ErrorKind::InvalidRequest,
+            ),
+        ];
+        for (error, expected) in cases {
+            assert_eq!(error.kind(), expected, "{error}");
+            assert_eq!(error.is_retryable(), expected.is_retryable(), "{error}");
+        }
+    }
+
+    #[test]
+    fn search_errors_are_classified() {
+        let cases = [
+            (
+                SearchError::Embedding(EmbeddingClientError::Configuration("no key".into())),
+                ErrorKind::Configuration,
+            ),
+            (SearchError::Qdrant(status_error(429)), ErrorKind::Transient),
+            (
+                SearchError::DimensionMismatch {
+                    expected: 768,
+                    actual: 384,
+                },
+                ErrorKind::Configuration,
+            ),
+            (SearchError::EmptyEmbedding, ErrorKind::Internal),
+        ];
+        for (error, expected) in cases {
+            assert_eq!(error.kind(), expected, "{error}");
+            assert_eq!(error.is_retryable(), expected.is_retryable(), "{error}");
+        }
+    }
+
+    #[test]
+    fn anomaly_examples_are_capped() {
+        let mut report = AnomalyReport::default();
+        for index in 0..MAX_ANOMALY_EXAMPLES + 5 {
+            report.record(&index.to_string());
+        }
+        assert_eq!(report.count, MAX_ANOMALY_EXAMPLES + 5);
+        assert_eq!(report.examples.len(), MAX_ANOMALY_EXAMPLES);
+        assert_eq!(report.examples[0], "0");
+    }
+
+    fn sample_profile() -> IngestProfile {
+        IngestProfile {
+            embedding_provider: "ollama".into(),
+            embedding_model: "nomic-embed-text".into(),
+            embedding_dimension: 768,
+            chunk_size: 512,
+            chunk_overlap: 0,
+            strategy: "semchunk",
+            tokenizer: "cl100k_base",
+            dedupe: "chunk_hash",
+            version: "0.0.0",
+        }
+    }
+
+    #[test]
+    fn profile_hash_is_stable_for_identical_settings() {
+        let hash = sample_profile().hash();
+        assert_eq!(hash.len(), 16);
+        assert_eq!(sample_profile().hash(), hash);
+
+        let resized = IngestProfile {
+            chunk_size: 256,
+            ..sample_profile()
+        };
+        assert_ne!(resized.hash(), hash);
+        assert_eq!(
+            sample_profile().compact(),
+            "ollama:nomic-embed-text@768 chunk=512+0 semchunk cl100k_base dedupe=chunk_hash v0.0.0"
+        );
+        assert_eq!(
+            IngestProfile::model_from_compact(&sample_profile().compact()),
+            Some("nomic-embed-text")
+        );
+        assert_eq!(
+            IngestProfile::model_from_compact("ollama:llama3.1:8b@4096 chunk=512+0"),
+            Some("llama3.1:8b")
+        );
+        assert_eq!(IngestProfile::model_from_compact("garbage"), None);
+    }
+
+    const LIMITS: SearchLimits = SearchLimits {
+        default_limit: 5,
+        max_limit: 50,
+        clamp_limit: false,
+        default_score_threshold: 0.25,
+    };
+
+    fn query() -> SearchRequestBuilder {
+        SearchRequest::builder().query_text("key rotation")
+    }
+
+    #[test]
+    fn builder_resolves_defaults_and_normalizes_filters() {
+        let validated = query()
+            .collection("notes")
+            .memory_type(" Procedural ")
+            .tags([" ops ", "security", "ops"])
+            .include_ids([
+                "6F9619FF-8B86-D011-B42D-00CF4FC964FF",
+                " 42 ",
+                "6f9619ff-8b86-d011-b42d-00cf4fc964ff",
+            ])
+            .time_range(SearchTimeRange {
+                start: Some("2024-06-01T02:00:00+02:00".into()),
+                end: Some("2024-06-02".into()),
+            })
+            .require_text(true)
+            .build_with_limits(&LIMITS)
+            .expect("valid request");
+
+        assert!(!validated.clamped_limit());
+        let request = validated.into_inner();
+        assert_eq!(request.query_text, "key rotation");
+        assert_eq!(request.collection.as_deref(), Some("notes"));
+        assert_eq!(request.memory_type.as_deref(), Some("procedural"));
+        assert_eq!(request.tags, Some(vec!["ops".into(), "security".into()]));
+        assert_eq!(
+            request.include_ids,
+            Some(vec![
+                "6f9619ff-8b86-d011-b42d-00cf4fc964ff".into(),
+                "42".into()
+            ])
+        );
+        let range = request.time_range.expect("range kept");
+        assert_eq!(range.start.as_deref(), Some("2024-06-01T00:00:00Z"));
+        assert_eq!(range.end.as_deref(), Some("2024-06-02T23:59:59.999999999Z"));
+        assert_eq!(request.limit, Some(5));
+        assert_eq!(request.score_threshold, Some(0.25));
+        assert!(request.require_text);
+    }
+
+    #[test]
+    fn builder_clamps_oversized_limits_only_when_allowed() {
+        let clamping = SearchLimits {
+            clamp_limit: true,
+            ..LIMITS
+        };
+        let validated = query()
+            .limit(500)
+            .build_with_limits(&clamping)
+            .expect("clamped");
+        assert!(validated.clamped_limit());
+        assert_eq!(validated.request().limit, Some(50));
+
+        let validated = query().limit(50).build_with_limits(&LIMITS).expect("max");
+        assert_eq!(validated.request().limit, Some(50));
+        assert!(!validated.clamped_limit());
+    }
+
+    #[test]
+    fn builder_rejects_invalid_requests() {
+        let oversampled = SearchParams {
+            quantization: Some(crate::qdrant::QuantizationSearchParams {
+                rescore: Some(true),
+                oversampling: Some(0.5),
+            }),
+            ..SearchParams::default()
+        };
+        let cases = [
+            (SearchRequest::builder(), SearchValidationError::EmptyQuery),
+            (
+                SearchRequest::builder().query_text("   "),
+                SearchValidationError::EmptyQuery,
+            ),
+            (
+                query().memory_type("diary"),
+                SearchValidationError::InvalidMemoryType,
+            ),
+            (
+                query().memory_type(" "),
+                SearchValidationError::InvalidMemoryType,
+            ),
+            (
+                query().tags(Vec::<String>::new()),
+                SearchValidationError::InvalidTags,
+            ),
+            (
+                query().tags(["ok", " "]),
+                SearchValidationError::InvalidTags,
+            ),
+            (
+                query().include_ids(Vec::<String>::new()),
+                SearchValidationError::IncludeIdsCount,
+            ),
+            (
+                query().include_ids((0..=MAX_INCLUDE_IDS).map(|id| id.to_string())),
+                SearchValidationError::IncludeIdsCount,
+            ),
+            (
+                query().include_ids(["memory-1"]),
+                SearchValidationError::InvalidIncludeId("memory-1".into()),
+            ),
+            (
+                query().time_range(SearchTimeRange::default()),
+                SearchValidationError::EmptyTimeRange,
+            ),
+            (
+                query().time_range(SearchTimeRange {
+                    start: Some("yesterday".into()),
+                    end: None,
+                }),
+                SearchValidationError::InvalidTimestamp {
+                    field: "time_range.start",
+                    value: "yesterday".into(),
+                },
+            ),
+            (
+                query().time_range(SearchTimeRange {
+                    start: None,
+                    end: Some(String::new()),
+                }),
+                SearchValidationError::InvalidTimestamp {
+                    field: "time_range.end",
+                    value: String::new(),
+                },
+            ),
+            (
+                // 01:00+02:00 is 23:00Z the previous day, before the start of 2024-06-01.
+                query().time_range(SearchTimeRange {
+                    start: Some("2024-06-01".into()),
+                    end: Some("2024-06-01T01:00:00+02:00".into()),
+                }),
+                SearchValidationError::InvertedTimeRange,
+            ),
+            (
+                query().limit(0),
+                SearchValidationError::LimitOutOfRange { max: 50 },
+            ),
+            (
+                query().limit(51),
+                SearchValidationError::LimitOutOfRange { max: 50 },
+            ),
+            (
+                query().score_threshold(1.5),
+                SearchValidationError::ScoreThresholdOutOfRange,
+            ),
+            (
+                query().score_threshold(-0.1),
+                SearchValidationError::ScoreThresholdOutOfRange,
+            ),
+            (
+                query().search_params(SearchParams {
+                    hnsw_ef: Some(0),
+                    ..SearchParams::default()
+                }),
+                SearchValidationError::InvalidHnswEf,
+            ),
+            (
+                query().search_params(oversampled),
+                SearchValidationError::InvalidOversampling,
+            ),
+        ];
+        for (builder, expected) in cases {
+            let debug = format!("{builder:?}");
+            let error = builder.build_with_limits(&LIMITS).unwrap_err();
+            assert_eq!(error, expected, "{debug}");
+        }
+
+        let error = query()
+            .collection("../points")
+            .build_with_limits(&LIMITS)
+            .unwrap_err();
+        assert!(matches!(error, SearchValidationError::InvalidCollection(_)));
+        assert!(error.to_string().starts_with("Invalid `collection`:"));
+    }
+
+    #[test]
+    fn zero_limit_is_rejected_even_when_clamping() {
+        let clamping = SearchLimits {
+            clamp_limit: true,
+            ..LIMITS
+        };
+        assert_eq!(
+            query().limit(0).build_with_limits(&clamping).unwrap_err(),
+            SearchValidationError::LimitOutOfRange { max: 50 }
+        );
+    }
+
+    #[test]
+    fn storage_stats_scale_the_per_point_estimate() {
+        // 768 × float32 = 3072 bytes per vector; payloads of 100 and 201 bytes round to 151.
+        let estimate = StorageEstimate::compute(40, 768, None, &[100, 201]);
+        let info = CollectionInfo {
+            status: Some("yellow".into()),
+            points_count: Some(1_000),
+            indexed_vectors_count: Some(900),
+            segments_count: Some(6),
+        };
+        let stats =
+            StorageStats::from_estimate("memories".into(), Some("alpha".into()), &estimate, &info);
+
+        assert_eq!(stats.points_count, 40);
+        assert_eq!(stats.average_payload_bytes, 151);
+        assert_eq!(stats.payload_bytes, 6_040);
+        assert_eq!(stats.vector_bytes, 122_880);
+        assert_eq!(stats.estimated_bytes, 128_920);
+        assert_eq!(stats.estimated_bytes, estimate.estimated_bytes);
+        assert_eq!(stats.segments_count, Some(6));
+        assert_eq!(stats.status.as_deref(), Some("yellow"));
+
+        let empty = StorageStats::from_estimate(
+            "empty".into(),
+            None,
+            &StorageEstimate::compute(0, 768, None, &[]),
+            &CollectionInfo::default(),
+        );
+        assert_eq!(empty.estimated_bytes, 0);
+        assert_eq!(empty.segments_count, None);
+    }
+
+    #[test]
+    fn storage_stats_report_totals_collections_and_is_labeled_approximate() {
+        let stats = |collection: &str, points: u64| {
+            StorageStats::from_estimate(
+                collection.into(),
+                None,
+                &StorageEstimate::compute(points, 4, Some("uint8"), &[10]),
+                &CollectionInfo::default(),
+            )
+        };
+        let report = StorageStatsReport::new(None, vec![stats("a", 3), stats("b", 7)]);
+
+        assert!(report.approximate);
+        assert_eq!(report.total_points, 10);
+        assert_eq!(report.total_payload_bytes, 100);
+        assert_eq!(report.total_vector_bytes, 40);
+        assert_eq!(report.total_estimated_bytes, 140);
+
+        let json = serde_json::to_value(&report).expect("serialize");
+        assert_eq!(json["approximate"], true);
+        assert_eq!(json["collections"][1]["collection"], "b");
+        assert!(json.get("project_id").is_none());
+    }
+}
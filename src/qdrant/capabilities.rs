@@ -0,0 +1,164 @@
+//! Qdrant server version detection and the request features it unlocks.
+
+use std::fmt;
+
+/// First release accepting `order_by` in scroll requests.
+const ORDER_BY_SINCE: QdrantVersion = QdrantVersion::new(1, 8, 0);
+/// First release accepting `order_by` as a `points/query` query.
+const QUERY_ORDER_BY_SINCE: QdrantVersion = QdrantVersion::new(1, 10, 0);
+/// First release serving `points/query/groups`.
+const QUERY_GROUPS_SINCE: QdrantVersion = QdrantVersion::new(1, 10, 0);
+/// First release supporting `datetime` payload indexes and RFC3339 range filters.
+const DATETIME_INDEX_SINCE: QdrantVersion = QdrantVersion::new(1, 8, 0);
+
+/// `major.minor.patch` version reported by the Qdrant root endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct QdrantVersion {
+    /// Major version.
+    pub major: u64,
+    /// Minor version.
+    pub minor: u64,
+    /// Patch version.
+    pub patch: u64,
+}
+
+impl QdrantVersion {
+    /// Build a version from its components.
+    pub const fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Parse strings such as `1.9.2`, `v1.10.0`, or `1.12.0-dev`; a missing patch reads as `0`.
+    pub fn parse(raw: &str) -> Option<Self> {
+        let core = raw.trim().trim_start_matches('v');
+        let core = core.split(['-', '+']).next().unwrap_or(core);
+        let mut parts = core.split('.').map(str::parse::<u64>);
+        let major = parts.next()?.ok()?;
+        let minor = parts.next()?.ok()?;
+        let patch = match parts.next() {
+            Some(patch) => patch.ok()?,
+            None => 0,
+        };
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(Self::new(major, minor, patch))
+    }
+}
+
+impl fmt::Display for QdrantVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Request shapes the connected Qdrant server accepts.
+///
+/// When the version cannot be detected every feature is assumed available, matching the
+/// behaviour before detection existed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QdrantCapabilities {
+    /// Detected server version, if any.
+    pub version: Option<QdrantVersion>,
+    /// Scroll requests may carry `order_by`.
+    pub supports_order_by: bool,
+    /// Queries may re-order prefetched candidates with an `order_by` query.
+    pub supports_query_order_by: bool,
+    /// Grouped queries (`points/query/groups`) are available.
+    pub supports_query_groups: bool,
+    /// `datetime` payload indexes and RFC3339 range filters are available.
+    pub supports_datetime_index: bool,
+}
+
+impl QdrantCapabilities {
+    /// Derive capabilities from a detected version; `None` assumes a current server.
+    pub fn from_version(version: Option<QdrantVersion>) -> Self {
+        let since = |minimum: QdrantVersion| version.is_none_or(|detected| detected >= minimum);
+        Self {
+            version,
+            supports_order_by: since(ORDER_BY_SINCE),
+            supports_query_order_by: since(QUERY_ORDER_BY_SINCE),
+            supports_query_groups: since(QUERY_GROUPS_SINCE),
+            supports_datetime_index: since(DATETIME_INDEX_SINCE),
+        }
+    }
+}
+
+impl Default for QdrantCapabilities {
+    fn default() -> Self {
+        Self::from_version(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn capabilities(raw: &str) -> QdrantCapabilities {
+        QdrantCapabilities::from_version(QdrantVersion::parse(raw))
+    }
+
+    #[test]
+    fn parses_common_version_strings() {
+        assert_eq!(
+            QdrantVersion::parse("1.9.2"),
+            Some(QdrantVersion::new(1, 9, 2))
+        );
+        assert_eq!(
+            QdrantVersion::parse("v1.10.0"),
+            Some(QdrantVersion::new(1, 10, 0))
+        );
+        assert_eq!(
+            QdrantVersion::parse("1.12.0-dev"),
+            Some(QdrantVersion::new(1, 12, 0))
+        );
+        assert_eq!(
+            QdrantVersion::parse("1.7"),
+            Some(QdrantVersion::new(1, 7, 0))
+        );
+        assert_eq!(QdrantVersion::parse("latest"), None);
+        assert_eq!(QdrantVersion::parse("1.2.3.4"), None);
+        assert_eq!(QdrantVersion::new(1, 10, 0).to_string(), "1.10.0");
+    }
+
+    #[test]
+    fn old_servers_disable_every_gated_feature() {
+        let caps = capabilities("1.7.4");
+        assert!(!caps.supports_order_by);
+        assert!(!caps.supports_query_order_by);
+        assert!(!caps.supports_query_groups);
+        assert!(!caps.supports_datetime_index);
+    }
+
+    #[test]
+    fn mid_servers_support_order_by_and_datetime_but_not_query_groups() {
+        for raw in ["1.8.0", "1.9.7"] {
+            let caps = capabilities(raw);
+            assert!(caps.supports_order_by, "{raw}");
+            assert!(caps.supports_datetime_index, "{raw}");
+            assert!(!caps.supports_query_groups, "{raw}");
+            assert!(!caps.supports_query_order_by, "{raw}");
+        }
+    }
+
+    #[test]
+    fn current_servers_support_everything() {
+        let caps = capabilities("v1.12.1");
+        assert!(caps.supports_order_by);
+        assert!(caps.supports_query_order_by);
+        assert!(caps.supports_query_groups);
+        assert!(caps.supports_datetime_index);
+    }
+
+    #[test]
+    fn unknown_versions_assume_a_current_server() {
+        let caps = capabilities("unknown");
+        assert_eq!(caps.version, None);
+        assert_eq!(caps, QdrantCapabilities::default());
+        assert!(caps.supports_order_by && caps.supports_query_groups);
+    }
+}
@@ -1,24 +1,90 @@
 //! HTTP client wrapper for interacting with Qdrant.
 
 use crate::config::get_config;
+use crate::logging::TracedSend;
 use crate::qdrant::types::PayloadOverrides;
 use crate::qdrant::{
-    filters::{accumulate_project_id, accumulate_tags},
-    payload::{build_payload, current_timestamp_rfc3339, generate_memory_id},
+    capabilities::{QdrantCapabilities, QdrantVersion},
+    filters::{
+        MAX_COUNTED_TAGS, TagCounter, accumulate_project_id, accumulate_tags, build_search_filter,
+    },
+    payload::{TIMESTAMP_UNIX_FIELD, build_payload, current_timestamp_rfc3339, generate_memory_id},
     types::{
-        IndexSummary, ListCollectionsResponse, QdrantError, QueryResponse, QueryResponseResult,
-        ScoredPoint, ScrollResponse,
+        CollectionInfo, CollectionInfoResponse, CollectionSchema, CollectionSchemaResponse,
+        CollectionTopology, CountResponse, IndexSummary, ListAliasesResponse,
+        ListCollectionsResponse, PayloadIndexFailure, PayloadIndexSummary, PointQuery, QdrantError,
+        QueryResponse, RetrieveResponse, ScoredPoint, ScrollResponse, SearchFilterArgs,
+        StoredPoint, TagCounts, TimestampOrder,
     },
 };
+use futures_util::future::join_all;
 use reqwest::{Client, Method, StatusCode};
 use serde_json::{Map, Value, json};
-use std::collections::BTreeSet;
+use sha2::{Digest, Sha256};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::{Duration, Instant};
+use time::{OffsetDateTime, format_description::well_known::Rfc3339};
+
+/// Payload indexes created by [`QdrantService::ensure_payload_indexes`], with their schema type.
+pub(crate) const PAYLOAD_INDEX_FIELDS: [(&str, &str); 11] = [
+    ("project_id", "keyword"),
+    ("memory_type", "keyword"),
+    ("tags", "keyword"),
+    ("timestamp", "datetime"),
+    (TIMESTAMP_UNIX_FIELD, "integer"),
+    ("chunk_hash", "keyword"),
+    ("profile_hash", "keyword"),
+    ("doc_id", "keyword"),
+    ("label", "keyword"),
+    ("topic", "keyword"),
+    ("linked_to", "keyword"),
+];
+
+/// Vector distance used when a collection is created without an explicit metric.
+pub const DEFAULT_DISTANCE: &str = "Cosine";
+/// Distance metrics accepted by Qdrant for dense vectors.
+pub const VECTOR_DISTANCES: [&str; 4] = ["Cosine", "Dot", "Euclid", "Manhattan"];
+/// Payload index schema types accepted by Qdrant.
+pub const PAYLOAD_INDEX_SCHEMAS: [&str; 8] = [
+    "keyword", "integer", "float", "bool", "geo", "datetime", "text", "uuid",
+];
+
+/// Upper bound for the delay between indexing-status polls.
+const MAX_POLL_BACKOFF: Duration = Duration::from_secs(2);
+/// Nearest candidates considered per returned hit when ordering a search by timestamp.
+pub(crate) const ORDERED_SEARCH_CANDIDATE_FACTOR: usize = 4;
 
 /// Lightweight HTTP client for Qdrant operations.
 pub struct QdrantService {
     pub(crate) client: Client,
     pub(crate) base_url: String,
+    /// Replica serving searches, scrolls, and counts; `None` sends them to `base_url`.
+    pub(crate) read_base_url: Option<String>,
     pub(crate) api_key: Option<String>,
+    pub(crate) api_keys: BTreeMap<String, String>,
+    pub(crate) capabilities: QdrantCapabilities,
+    /// Faults injected before every request (`FAULT_QDRANT_*`).
+    #[cfg(feature = "fault-injection")]
+    pub(crate) faults: Option<std::sync::Arc<crate::fault::FaultInjector>>,
+}
+
+/// Operation class used to select a labelled API key.
+#[derive(Clone, Copy, Debug)]
+enum KeyScope {
+    /// Queries, scrolls, and collection lookups.
+    Read,
+    /// Upserts, collection creation, and index management.
+    Write,
+}
+
+impl KeyScope {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Read => "read",
+            Self::Write => "write",
+        }
+    }
 }
 
 impl QdrantService {
@@ -28,23 +94,96 @@ impl QdrantService {
         let client = Client::builder().user_agent("rusty-mem/0.1").build()?;
 
         let base_url = normalize_base_url(&config.qdrant_url).map_err(QdrantError::InvalidUrl)?;
+        let read_base_url = config
+            .qdrant_read_url
+            .as_deref()
+            .map(normalize_base_url)
+            .transpose()
+            .map_err(QdrantError::InvalidUrl)?;
         tracing::debug!(
             url = %base_url,
+            read_url = ?read_base_url,
             has_api_key = %config
                 .qdrant_api_key
                 .as_deref()
                 .map(|value| !value.is_empty())
                 .unwrap_or(false),
+            api_key_labels = ?config.qdrant_api_keys.keys().collect::<Vec<_>>(),
             "Initialized Qdrant HTTP client"
         );
 
         Ok(Self {
             client,
             base_url,
+            read_base_url,
             api_key: config.qdrant_api_key.clone(),
+            api_keys: config.qdrant_api_keys.clone(),
+            capabilities: QdrantCapabilities::default(),
+            #[cfg(feature = "fault-injection")]
+            faults: crate::fault::qdrant_injector_from_env(),
         })
     }
 
+    /// Build the client and detect which request shapes the server supports.
+    pub async fn connect() -> Result<Self, QdrantError> {
+        let mut service = Self::new()?;
+        service.detect_capabilities().await;
+        Ok(service)
+    }
+
+    /// Read the server version and gate features on it, logging the result once.
+    ///
+    /// Detection failures leave every feature enabled so an unreachable or unusual server
+    /// behaves as it did before detection existed.
+    pub async fn detect_capabilities(&mut self) {
+        let version = match self.server_version().await {
+            Ok(Some(raw)) => {
+                let parsed = QdrantVersion::parse(&raw);
+                if parsed.is_none() {
+                    tracing::warn!(version = %raw, "Unrecognized Qdrant version; assuming all features");
+                }
+                parsed
+            }
+            Ok(None) => None,
+            Err(error) => {
+                tracing::warn!(error = %error, "Qdrant version detection failed; assuming all features");
+                None
+            }
+        };
+        self.capabilities = QdrantCapabilities::from_version(version);
+        tracing::info!(
+            version = ?version.map(|version| version.to_string()),
+            supports_order_by = self.capabilities.supports_order_by,
+            supports_query_order_by = self.capabilities.supports_query_order_by,
+            supports_query_groups = self.capabilities.supports_query_groups,
+            supports_datetime_index = self.capabilities.supports_datetime_index,
+            "Detected Qdrant capabilities"
+        );
+    }
+
+    /// Request shapes the connected server accepts.
+    pub fn capabilities(&self) -> &QdrantCapabilities {
+        &self.capabilities
+    }
+
+    /// Fetch the raw `version` string from the root endpoint.
+    pub async fn server_version(&self) -> Result<Option<String>, QdrantError> {
+        let response = self
+            .request(KeyScope::Read, Method::GET, "")?
+            .send_qdrant(self)
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(QdrantError::UnexpectedStatus { status, body });
+        }
+        let body: Value = response.json().await?;
+        Ok(body
+            .get("version")
+            .and_then(Value::as_str)
+            .map(str::to_string))
+    }
+
     /// Enumerate distinct project identifiers stored in the collection payloads.
     pub async fn list_projects(&self, collection: &str) -> Result<BTreeSet<String>, QdrantError> {
         let payloads = self
@@ -63,19 +202,8 @@ impl QdrantService {
         collection: &str,
         project_id: Option<&str>,
     ) -> Result<BTreeSet<String>, QdrantError> {
-        let filter = project_id.map(|project| {
-            json!({
-                "must": [
-                    {
-                        "key": "project_id",
-                        "match": { "value": project }
-                    }
-                ]
-            })
-        });
-
         let payloads = self
-            .scroll_payloads(collection, json!(["tags"]), filter)
+            .scroll_payloads(collection, json!(["tags"]), project_filter(project_id))
             .await?;
         let mut tags = BTreeSet::new();
         for payload in payloads {
@@ -84,14 +212,40 @@ impl QdrantService {
         Ok(tags)
     }
 
-    /// Create a collection only when it is missing from Qdrant.
+    /// Tally how many payloads carry each tag, split by `memory_type`, while scrolling.
+    ///
+    /// Pages are counted as they arrive rather than collected, and at most
+    /// [`MAX_COUNTED_TAGS`] distinct tags are tracked before new ones fold into the overflow.
+    pub async fn list_tag_counts(
+        &self,
+        collection: &str,
+        project_id: Option<&str>,
+        memory_type: Option<&str>,
+    ) -> Result<TagCounts, QdrantError> {
+        let filter = build_search_filter(&SearchFilterArgs {
+            project_id: project_id.map(str::to_string),
+            memory_type: memory_type.map(str::to_string),
+            ..SearchFilterArgs::default()
+        });
+        let mut counter = TagCounter::new(MAX_COUNTED_TAGS);
+        self.scroll_payloads_each(
+            collection,
+            json!(["tags", "memory_type"]),
+            filter,
+            |payload| counter.record(&payload),
+        )
+        .await?;
+        Ok(counter.finish())
+    }
+
+    /// Create a collection only when it is missing from Qdrant, returning whether it was created.
     pub async fn create_collection_if_not_exists(
         &self,
         collection_name: &str,
         vector_size: u64,
-    ) -> Result<(), QdrantError> {
+    ) -> Result<bool, QdrantError> {
         if self.collection_exists(collection_name).await? {
-            return Ok(());
+            return Ok(false);
         }
 
         tracing::debug!(
@@ -99,26 +253,62 @@ impl QdrantService {
             vector_size,
             "Creating collection"
         );
-        self.create_collection(collection_name, vector_size).await
+        self.create_collection(
+            collection_name,
+            vector_size,
+            CollectionTopology::from_config(),
+        )
+        .await?;
+        Ok(true)
     }
 
     /// Create or update a collection with the specified vector size.
+    ///
+    /// `shard_number` and `replication_factor` are only sent when set in `topology`.
     pub async fn create_collection(
         &self,
         collection_name: &str,
         vector_size: u64,
+        topology: CollectionTopology,
     ) -> Result<(), QdrantError> {
-        let body = json!({
+        self.create_collection_with_distance(
+            collection_name,
+            vector_size,
+            DEFAULT_DISTANCE,
+            topology,
+        )
+        .await
+    }
+
+    /// Create or update a collection using `distance` (one of [`VECTOR_DISTANCES`]).
+    pub async fn create_collection_with_distance(
+        &self,
+        collection_name: &str,
+        vector_size: u64,
+        distance: &str,
+        topology: CollectionTopology,
+    ) -> Result<(), QdrantError> {
+        let mut body = json!({
             "vectors": {
                 "size": vector_size,
-                "distance": "Cosine"
+                "distance": distance
             }
         });
+        if let Some(shard_number) = topology.shard_number {
+            body["shard_number"] = json!(shard_number);
+        }
+        if let Some(replication_factor) = topology.replication_factor {
+            body["replication_factor"] = json!(replication_factor);
+        }
 
         let response = self
-            .request(Method::PUT, &format!("collections/{collection_name}"))?
+            .request(
+                KeyScope::Write,
+                Method::PUT,
+                &format!("collections/{collection_name}"),
+            )?
             .json(&body)
-            .send()
+            .send_qdrant(self)
             .await?;
 
         self.ensure_success(response, || {
@@ -127,9 +317,49 @@ impl QdrantService {
         .await
     }
 
+    /// Normalized read replica URL, when `QDRANT_READ_URL` is set.
+    pub fn read_url(&self) -> Option<&str> {
+        self.read_base_url.as_deref()
+    }
+
+    /// Probe a dedicated health endpoint such as `healthz` or `readyz`.
+    ///
+    /// Qdrant serves these without checking the API key, so the probe works with keys scoped to
+    /// a single collection. Returns `Ok(false)` when the server has no such endpoint (`404`).
+    pub async fn probe_health_endpoint(&self, path: &str) -> Result<bool, QdrantError> {
+        let response = self
+            .request(KeyScope::Read, Method::GET, path)?
+            .send_qdrant(self)
+            .await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(false);
+        }
+        self.ensure_success(response, || {}).await?;
+        Ok(true)
+    }
+
+    /// Check that the read replica answers a collection listing.
+    ///
+    /// Returns `None` when no replica is configured.
+    pub async fn probe_read_replica(&self) -> Option<Result<(), QdrantError>> {
+        self.read_base_url.as_ref()?;
+        let outcome = async {
+            let response = self
+                .read_request(Method::GET, "collections")?
+                .send_qdrant(self)
+                .await?;
+            self.ensure_success(response, || {}).await
+        }
+        .await;
+        Some(outcome)
+    }
+
     /// Retrieve the names of all collections present in Qdrant.
     pub async fn list_collections(&self) -> Result<Vec<String>, QdrantError> {
-        let response = self.request(Method::GET, "collections")?.send().await?;
+        let response = self
+            .request(KeyScope::Read, Method::GET, "collections")?
+            .send_qdrant(self)
+            .await?;
 
         if response.status().is_success() {
             let payload: ListCollectionsResponse = response.json().await?;
@@ -149,435 +379,2627 @@ impl QdrantService {
         }
     }
 
-    /// Upload new vectors to the given collection.
-    pub async fn index_points(
-        &self,
-        collection_name: &str,
-        points: Vec<crate::qdrant::types::PointInsert>,
-        overrides: &PayloadOverrides,
-    ) -> Result<IndexSummary, QdrantError> {
-        if points.is_empty() {
-            return Ok(IndexSummary::default());
+    /// Map every collection alias to the collection it currently points at.
+    pub async fn list_aliases(&self) -> Result<BTreeMap<String, String>, QdrantError> {
+        let response = self
+            .request(KeyScope::Read, Method::GET, "aliases")?
+            .send_qdrant(self)
+            .await?;
+
+        if response.status().is_success() {
+            let payload: ListAliasesResponse = response.json().await?;
+            Ok(payload
+                .result
+                .aliases
+                .into_iter()
+                .map(|alias| (alias.alias_name, alias.collection_name))
+                .collect())
+        } else {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            Err(QdrantError::UnexpectedStatus { status, body })
         }
+    }
 
-        let now = current_timestamp_rfc3339();
-        let serialized: Vec<_> = points
-            .into_iter()
-            .map(|point| {
-                let memory_id = generate_memory_id();
-                let payload =
-                    build_payload(&memory_id, &point.text, &now, &point.chunk_hash, overrides);
-                json!({
-                    "id": memory_id,
-                    "vector": point.vector,
-                    "payload": payload,
-                })
+    /// Point each of `aliases` at `collection` in one atomic alias update.
+    pub async fn repoint_aliases(
+        &self,
+        aliases: &[String],
+        collection: &str,
+    ) -> Result<(), QdrantError> {
+        let actions: Vec<Value> = aliases
+            .iter()
+            .flat_map(|alias| {
+                [
+                    json!({ "delete_alias": { "alias_name": alias } }),
+                    json!({ "create_alias": { "collection_name": collection, "alias_name": alias } }),
+                ]
             })
             .collect();
+        let response = self
+            .request(KeyScope::Write, Method::POST, "collections/aliases")?
+            .json(&json!({ "actions": actions }))
+            .send_qdrant(self)
+            .await?;
 
-        let point_count = serialized.len();
+        self.ensure_success(response, || {
+            tracing::debug!(collection, ?aliases, "Aliases repointed");
+        })
+        .await
+    }
+
+    /// Create `alias` pointing at `collection`.
+    pub async fn create_alias(&self, alias: &str, collection: &str) -> Result<(), QdrantError> {
         let response = self
-            .request(
-                Method::PUT,
-                &format!("collections/{}/points", collection_name),
-            )?
-            .query(&[("wait", true)])
-            .json(&json!({ "points": serialized }))
-            .send()
+            .request(KeyScope::Write, Method::POST, "collections/aliases")?
+            .json(&json!({
+                "actions": [
+                    { "create_alias": { "collection_name": collection, "alias_name": alias } }
+                ]
+            }))
+            .send_qdrant(self)
             .await?;
 
         self.ensure_success(response, || {
-            tracing::debug!(
-                collection = collection_name,
-                points = point_count,
-                "Points indexed"
-            );
+            tracing::debug!(collection, alias, "Alias created");
         })
-        .await?;
+        .await
+    }
 
-        Ok(IndexSummary {
-            inserted: point_count,
-            updated: 0,
+    /// Delete a collection together with its points.
+    pub async fn delete_collection(&self, collection_name: &str) -> Result<(), QdrantError> {
+        let response = self
+            .request(
+                KeyScope::Write,
+                Method::DELETE,
+                &format!("collections/{collection_name}"),
+            )?
+            .send_qdrant(self)
+            .await?;
+
+        self.ensure_success(response, || {
+            tracing::debug!(collection = collection_name, "Collection deleted");
         })
+        .await
     }
 
-    /// Perform a similarity search against a collection, returning scored payloads.
-    pub async fn search_points(
+    /// Fetch status and indexing counters for a collection.
+    pub async fn collection_info(
         &self,
         collection_name: &str,
-        vector: Vec<f32>,
-        filter: Option<Value>,
-        limit: usize,
-        score_threshold: Option<f32>,
-        using: Option<String>,
-    ) -> Result<Vec<ScoredPoint>, QdrantError> {
-        let mut body = json!({
-            "query": vector,
-            "limit": limit,
-            "with_payload": true,
-        });
-        let obj = body
-            .as_object_mut()
-            .expect("query body should remain an object");
-
-        if let Some(name) = using.and_then(|value| {
-            let trimmed = value.trim();
-            if trimmed.is_empty() {
-                None
-            } else {
-                Some(trimmed.to_string())
-            }
-        }) {
-            obj.insert("using".into(), Value::String(name));
-        }
+    ) -> Result<CollectionInfo, QdrantError> {
+        let response = self
+            .request(
+                KeyScope::Read,
+                Method::GET,
+                &format!("collections/{collection_name}"),
+            )?
+            .send_qdrant(self)
+            .await?;
 
-        if let Some(threshold) = score_threshold {
-            obj.insert("score_threshold".into(), Value::from(threshold));
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            let error = QdrantError::UnexpectedStatus { status, body };
+            tracing::error!(collection = collection_name, error = %error, "Failed to fetch collection info");
+            return Err(error);
         }
 
-        if let Some(filter_value) = filter {
-            obj.insert("filter".into(), filter_value);
-        }
+        let CollectionInfoResponse { result } = response.json().await?;
+        Ok(result)
+    }
 
+    /// Fetch vector parameters and payload indexes alongside the collection status.
+    pub async fn collection_schema(
+        &self,
+        collection_name: &str,
+    ) -> Result<CollectionSchema, QdrantError> {
         let response = self
             .request(
-                Method::POST,
-                &format!("collections/{collection_name}/points/query"),
+                KeyScope::Read,
+                Method::GET,
+                &format!("collections/{collection_name}"),
             )?
-            .json(&body)
-            .send()
+            .send_qdrant(self)
             .await?;
 
         if !response.status().is_success() {
             let status = response.status();
             let body = response.text().await.unwrap_or_default();
             let error = QdrantError::UnexpectedStatus { status, body };
-            tracing::error!(collection = collection_name, error = %error, "Qdrant search failed");
+            tracing::error!(collection = collection_name, error = %error, "Failed to fetch collection schema");
             return Err(error);
         }
 
-        let payload: QueryResponse = response.json().await?;
-        let points = match payload.result {
-            QueryResponseResult::Points(points) => points,
-            QueryResponseResult::Object { points, .. } => points,
-        };
-        let results = points
-            .into_iter()
-            .map(|point| ScoredPoint {
-                id: stringify_point_id(point.id),
-                score: point.score,
-                payload: point.payload,
-            })
-            .collect();
+        let CollectionSchemaResponse { result } = response.json().await?;
+        Ok(result.into())
+    }
 
-        Ok(results)
+    /// Read the payloads of up to `limit` points from the first scroll page.
+    pub async fn sample_payloads(
+        &self,
+        collection_name: &str,
+        limit: usize,
+    ) -> Result<Vec<Map<String, Value>>, QdrantError> {
+        self.sample_payload_fields(collection_name, limit, json!(true))
+            .await
     }
 
-    /// Ensure standard payload indexes exist for common filters.
-    pub async fn ensure_payload_indexes(&self, collection_name: &str) -> Result<(), QdrantError> {
-        let fields: [(&str, &str); 5] = [
-            ("project_id", "keyword"),
-            ("memory_type", "keyword"),
-            ("tags", "keyword"),
-            ("timestamp", "datetime"),
-            ("chunk_hash", "keyword"),
-        ];
+    /// Like [`Self::sample_payloads`], but only the payload `fields` are returned.
+    pub async fn sample_payload_fields(
+        &self,
+        collection_name: &str,
+        limit: usize,
+        fields: Value,
+    ) -> Result<Vec<Map<String, Value>>, QdrantError> {
+        self.scroll_sample(collection_name, limit, fields, None)
+            .await
+    }
 
-        for (field, schema) in fields {
-            let body = json!({
-                "field_name": field,
-                "field_schema": schema,
-            });
+    /// Like [`Self::sample_payloads`], but only points matching `filter` are sampled.
+    pub async fn sample_filtered_payloads(
+        &self,
+        collection_name: &str,
+        limit: usize,
+        filter: Option<Value>,
+    ) -> Result<Vec<Map<String, Value>>, QdrantError> {
+        self.scroll_sample(collection_name, limit, json!(true), filter)
+            .await
+    }
 
-            let response = self
-                .request(Method::PUT, &format!("collections/{collection_name}/index"))?
-                .json(&body)
-                .send()
-                .await?;
+    async fn scroll_sample(
+        &self,
+        collection_name: &str,
+        limit: usize,
+        fields: Value,
+        filter: Option<Value>,
+    ) -> Result<Vec<Map<String, Value>>, QdrantError> {
+        let mut body = json!({
+            "limit": limit,
+            "with_payload": fields,
+            "with_vector": false,
+        });
+        if let Some(filter) = filter {
+            body["filter"] = filter;
+        }
+        let response = self
+            .read_request(
+                Method::POST,
+                &format!("collections/{collection_name}/points/scroll"),
+            )?
+            .json(&body)
+            .send_qdrant(self)
+            .await?;
 
-            if response.status().is_success() {
-                tracing::debug!(
-                    collection = collection_name,
-                    field,
-                    schema,
-                    "Payload index ensured"
-                );
-            } else if response.status() == StatusCode::CONFLICT {
-                tracing::debug!(
-                    collection = collection_name,
-                    field,
-                    schema,
-                    "Payload index already exists"
-                );
-            } else {
-                let status = response.status();
-                let body = response.text().await.unwrap_or_default();
-                let error = QdrantError::UnexpectedStatus { status, body };
-                tracing::warn!(collection = collection_name, field, schema, error = %error, "Failed to ensure payload index");
-            }
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            let error = QdrantError::UnexpectedStatus { status, body };
+            tracing::error!(collection = collection_name, error = %error, "Failed to sample payloads");
+            return Err(error);
         }
 
-        Ok(())
+        let ScrollResponse { result } = response.json().await?;
+        Ok(result
+            .points
+            .into_iter()
+            .filter_map(|point| point.payload)
+            .collect())
     }
 
-    async fn collection_exists(&self, collection_name: &str) -> Result<bool, QdrantError> {
-        let response = self
-            .request(Method::GET, &format!("collections/{collection_name}"))?
-            .send()
-            .await?;
-
-        match response.status() {
-            StatusCode::OK => Ok(true),
-            StatusCode::NOT_FOUND => Ok(false),
-            status => {
-                let body = response.text().await.unwrap_or_default();
-                let error = QdrantError::UnexpectedStatus { status, body };
-                tracing::error!(collection = collection_name, error = %error, "Collection existence check failed");
-                Err(error)
+    /// Poll collection info until Qdrant reports indexing complete or `timeout` elapses.
+    ///
+    /// The delay between polls starts at `initial_backoff` and doubles up to two seconds.
+    /// Returns the last observed info; callers check [`CollectionInfo::is_ready`] for timeouts.
+    pub async fn wait_for_indexing(
+        &self,
+        collection_name: &str,
+        timeout: Duration,
+        initial_backoff: Duration,
+    ) -> Result<CollectionInfo, QdrantError> {
+        let deadline = Instant::now() + timeout;
+        let mut backoff = initial_backoff;
+        loop {
+            let info = self.collection_info(collection_name).await?;
+            let now = Instant::now();
+            if info.is_ready() || now >= deadline {
+                return Ok(info);
             }
+            tracing::debug!(
+                collection = collection_name,
+                status = ?info.status,
+                backlog = info.unindexed_backlog(),
+                "Waiting for collection indexing"
+            );
+            tokio::time::sleep(backoff.min(deadline - now)).await;
+            backoff = (backoff * 2).min(MAX_POLL_BACKOFF);
         }
     }
 
-    fn request(&self, method: Method, path: &str) -> Result<reqwest::RequestBuilder, QdrantError> {
-        let url = format_endpoint(&self.base_url, path);
-        let mut req = self.client.request(method, url);
-        if let Some(api_key) = &self.api_key
-            && !api_key.is_empty()
-        {
-            req = req.header("api-key", api_key);
+    /// Upload new vectors to the given collection.
+    pub async fn index_points(
+        &self,
+        collection_name: &str,
+        points: Vec<crate::qdrant::types::PointInsert>,
+        overrides: &PayloadOverrides,
+    ) -> Result<IndexSummary, QdrantError> {
+        self.index_points_with_candidate(collection_name, points, overrides, None)
+            .await
+    }
+
+    /// Upload new vectors and, with `candidate`, the same points into a second collection.
+    ///
+    /// `candidate` names the collection and holds one vector per point, in order. Candidate
+    /// points reuse the primary ids and payloads and are written after the primary upsert
+    /// succeeded; a failed candidate write is only logged, so it never fails the ingest.
+    pub async fn index_points_with_candidate(
+        &self,
+        collection_name: &str,
+        points: Vec<crate::qdrant::types::PointInsert>,
+        overrides: &PayloadOverrides,
+        candidate: Option<(&str, Vec<Vec<f32>>)>,
+    ) -> Result<IndexSummary, QdrantError> {
+        if points.is_empty() {
+            return Ok(IndexSummary::default());
         }
-        Ok(req)
+
+        let ingested_at = current_timestamp_rfc3339();
+        let serialized: Vec<_> = points
+            .into_iter()
+            .map(|point| {
+                let memory_id = generate_memory_id();
+                let mut payload = build_payload(
+                    &memory_id,
+                    &point.text,
+                    &ingested_at,
+                    &point.chunk_hash,
+                    overrides,
+                );
+                if let Some(memory_type) = point.memory_type {
+                    payload["memory_type"] = Value::String(memory_type);
+                }
+                if let Some(quality_score) = point.quality_score {
+                    payload["quality_score"] = json!((quality_score * 1000.0).round() / 1000.0);
+                }
+                if let Some(chunk_index) = point.chunk_index {
+                    payload["chunk_index"] = json!(chunk_index);
+                }
+                if let Some(content_kind) = point.content_kind {
+                    payload["content_kind"] = Value::String(content_kind);
+                }
+                if let Some(language) = point.language {
+                    payload["language"] = Value::String(language);
+                }
+                if let Some(symbol) = point.symbol {
+                    payload["symbol"] = Value::String(symbol);
+                }
+                json!({
+                    "id": memory_id,
+                    "vector": point.vector,
+                    "payload": payload,
+                })
+            })
+            .collect();
+
+        let point_count = serialized.len();
+        let candidate_points = candidate.map(|(name, vectors)| {
+            let points: Vec<Value> = serialized
+                .iter()
+                .zip(vectors)
+                .map(|(point, vector)| {
+                    json!({
+                        "id": point["id"],
+                        "vector": vector,
+                        "payload": point["payload"],
+                    })
+                })
+                .collect();
+            (name, points)
+        });
+        self.put_points(collection_name, serialized).await?;
+        tracing::debug!(
+            collection = collection_name,
+            points = point_count,
+            "Points indexed"
+        );
+        if let Some((name, points)) = candidate_points {
+            match self.put_points(name, points).await {
+                Ok(()) => tracing::debug!(
+                    collection = name,
+                    points = point_count,
+                    "Candidate points indexed"
+                ),
+                Err(error) => tracing::warn!(
+                    collection = name,
+                    points = point_count,
+                    error = %error,
+                    "Failed to write candidate vectors; the candidate collection is incomplete"
+                ),
+            }
+        }
+
+        Ok(IndexSummary {
+            inserted: point_count,
+            updated: 0,
+        })
     }
 
-    async fn ensure_success<F>(
+    /// Upsert serialized points, waiting for the write to be applied.
+    async fn put_points(
         &self,
-        response: reqwest::Response,
-        on_success: F,
-    ) -> Result<(), QdrantError>
-    where
-        F: FnOnce(),
-    {
-        if response.status().is_success() {
-            on_success();
-            Ok(())
-        } else {
+        collection_name: &str,
+        points: Vec<Value>,
+    ) -> Result<(), QdrantError> {
+        let response = self
+            .request(
+                KeyScope::Write,
+                Method::PUT,
+                &format!("collections/{}/points", collection_name),
+            )?
+            .query(&[("wait", true)])
+            .json(&json!({ "points": points }))
+            .send_qdrant(self)
+            .await?;
+
+        self.ensure_success(response, || {}).await
+    }
+
+    /// Write points with their existing ids, vectors, and payloads, waiting for completion.
+    pub async fn upsert_points(
+        &self,
+        collection_name: &str,
+        points: &[StoredPoint],
+    ) -> Result<(), QdrantError> {
+        if points.is_empty() {
+            return Ok(());
+        }
+        let serialized: Vec<Value> = points
+            .iter()
+            .map(|point| {
+                json!({
+                    "id": point.id,
+                    "vector": point.vector,
+                    "payload": point.payload,
+                })
+            })
+            .collect();
+        let response = self
+            .request(
+                KeyScope::Write,
+                Method::PUT,
+                &format!("collections/{collection_name}/points"),
+            )?
+            .query(&[("wait", true)])
+            .json(&json!({ "points": serialized }))
+            .send_qdrant(self)
+            .await?;
+
+        self.ensure_success(response, || {
+            tracing::debug!(
+                collection = collection_name,
+                points = points.len(),
+                "Points upserted"
+            );
+        })
+        .await
+    }
+
+    /// Delete points by identifier, waiting for the operation to be applied.
+    pub async fn delete_points(
+        &self,
+        collection_name: &str,
+        ids: &[String],
+    ) -> Result<(), QdrantError> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+        let response = self
+            .request(
+                KeyScope::Write,
+                Method::POST,
+                &format!("collections/{collection_name}/points/delete"),
+            )?
+            .query(&[("wait", true)])
+            .json(&json!({ "points": ids }))
+            .send_qdrant(self)
+            .await?;
+
+        self.ensure_success(response, || {
+            tracing::debug!(
+                collection = collection_name,
+                points = ids.len(),
+                "Points deleted"
+            );
+        })
+        .await
+    }
+
+    /// Delete every point matching `filter`, waiting for the operation to be applied.
+    pub async fn delete_points_by_filter(
+        &self,
+        collection_name: &str,
+        filter: Value,
+    ) -> Result<(), QdrantError> {
+        let response = self
+            .request(
+                KeyScope::Write,
+                Method::POST,
+                &format!("collections/{collection_name}/points/delete"),
+            )?
+            .query(&[("wait", true)])
+            .json(&json!({ "filter": filter }))
+            .send_qdrant(self)
+            .await?;
+
+        self.ensure_success(response, || {
+            tracing::debug!(collection = collection_name, "Points deleted by filter");
+        })
+        .await
+    }
+
+    /// Subset of `ids` that exist in the collection, fetched in one retrieve request.
+    pub async fn existing_point_ids(
+        &self,
+        collection_name: &str,
+        ids: &[String],
+    ) -> Result<BTreeSet<String>, QdrantError> {
+        if ids.is_empty() {
+            return Ok(BTreeSet::new());
+        }
+        let response = self
+            .read_request(
+                Method::POST,
+                &format!("collections/{collection_name}/points"),
+            )?
+            .json(&json!({ "ids": ids, "with_payload": false, "with_vector": false }))
+            .send_qdrant(self)
+            .await?;
+
+        if !response.status().is_success() {
             let status = response.status();
             let body = response.text().await.unwrap_or_default();
             let error = QdrantError::UnexpectedStatus { status, body };
-            tracing::error!(error = %error, "Qdrant request failed");
-            Err(error)
+            tracing::error!(collection = collection_name, error = %error, "Failed to retrieve points");
+            return Err(error);
         }
+
+        let RetrieveResponse { result } = response.json().await?;
+        Ok(result
+            .into_iter()
+            .filter_map(|point| point.id.map(stringify_point_id))
+            .collect())
     }
 
-    async fn scroll_payloads(
+    /// Fetch the payload fields in `with_payload` for the points in `ids` that exist.
+    ///
+    /// Points are returned in Qdrant's order; missing ids are simply absent.
+    pub async fn retrieve_payloads(
         &self,
-        collection: &str,
+        collection_name: &str,
+        ids: &[String],
         with_payload: Value,
+    ) -> Result<Vec<(String, Map<String, Value>)>, QdrantError> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let response = self
+            .read_request(
+                Method::POST,
+                &format!("collections/{collection_name}/points"),
+            )?
+            .json(&json!({ "ids": ids, "with_payload": with_payload, "with_vector": false }))
+            .send_qdrant(self)
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            let error = QdrantError::UnexpectedStatus { status, body };
+            tracing::error!(collection = collection_name, error = %error, "Failed to retrieve points");
+            return Err(error);
+        }
+
+        let RetrieveResponse { result } = response.json().await?;
+        Ok(result
+            .into_iter()
+            .filter_map(|point| {
+                let id = point.id.map(stringify_point_id)?;
+                Some((id, point.payload.unwrap_or_default()))
+            })
+            .collect())
+    }
+
+    /// Count the points matching `filter` exactly (all points when `None`).
+    pub async fn count_points(
+        &self,
+        collection_name: &str,
         filter: Option<Value>,
-    ) -> Result<Vec<Map<String, Value>>, QdrantError> {
-        let mut offset: Option<Value> = None;
-        let mut payloads = Vec::new();
-        let filter_body = filter.unwrap_or_else(|| json!({ "must": [] }));
+    ) -> Result<u64, QdrantError> {
+        let base_url = self.read_base_url.as_deref().unwrap_or(&self.base_url);
+        self.count_points_at(base_url, collection_name, filter)
+            .await
+    }
 
-        loop {
-            let mut body = json!({
-                "with_payload": with_payload.clone(),
-                "with_vector": false,
-                "limit": 512,
-                "offset": offset.clone().unwrap_or(Value::Null),
-                "filter": filter_body.clone(),
-            });
+    /// Count the points matching `filter` on the primary, never the read replica.
+    ///
+    /// Used to confirm a copy landed before its source is deleted.
+    pub async fn count_primary_points(
+        &self,
+        collection_name: &str,
+        filter: Option<Value>,
+    ) -> Result<u64, QdrantError> {
+        self.count_points_at(&self.base_url, collection_name, filter)
+            .await
+    }
 
-            if offset.is_none() {
-                body.as_object_mut().unwrap().remove("offset");
-            }
+    async fn count_points_at(
+        &self,
+        base_url: &str,
+        collection_name: &str,
+        filter: Option<Value>,
+    ) -> Result<u64, QdrantError> {
+        let mut body = json!({ "exact": true });
+        if let Some(filter) = filter {
+            body["filter"] = filter;
+        }
+        let response = self
+            .request_to(
+                base_url,
+                KeyScope::Read,
+                Method::POST,
+                &format!("collections/{collection_name}/points/count"),
+            )?
+            .json(&body)
+            .send_qdrant(self)
+            .await?;
 
-            let response = self
-                .request(
-                    Method::POST,
-                    &format!("collections/{collection}/points/scroll"),
-                )?
-                .json(&body)
-                .send()
-                .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            let error = QdrantError::UnexpectedStatus { status, body };
+            tracing::error!(collection = collection_name, error = %error, "Failed to count points");
+            return Err(error);
+        }
 
-            if !response.status().is_success() {
-                let status = response.status();
-                let body = response.text().await.unwrap_or_default();
-                let error = QdrantError::UnexpectedStatus { status, body };
-                tracing::error!(collection, error = %error, "Failed to scroll payloads");
-                return Err(error);
-            }
+        let CountResponse { result } = response.json().await?;
+        Ok(result.count)
+    }
 
-            let ScrollResponse { result } = response.json().await?;
-            for point in result.points {
-                if let Some(payload) = point.payload {
-                    payloads.push(payload);
-                }
-            }
+    /// Replace the vectors of existing points without touching their payloads.
+    pub async fn update_vectors(
+        &self,
+        collection_name: &str,
+        vectors: Vec<(String, Vec<f32>)>,
+    ) -> Result<(), QdrantError> {
+        if vectors.is_empty() {
+            return Ok(());
+        }
+        let point_count = vectors.len();
+        let points: Vec<Value> = vectors
+            .into_iter()
+            .map(|(id, vector)| json!({ "id": id, "vector": vector }))
+            .collect();
+        let response = self
+            .request(
+                KeyScope::Write,
+                Method::PUT,
+                &format!("collections/{collection_name}/points/vectors"),
+            )?
+            .query(&[("wait", true)])
+            .json(&json!({ "points": points }))
+            .send_qdrant(self)
+            .await?;
+
+        self.ensure_success(response, || {
+            tracing::debug!(
+                collection = collection_name,
+                points = point_count,
+                "Vectors updated"
+            );
+        })
+        .await
+    }
+
+    /// Merge per-point payload updates in a single batch request, waiting for completion.
+    pub async fn set_payloads(
+        &self,
+        collection_name: &str,
+        updates: Vec<(String, Map<String, Value>)>,
+    ) -> Result<(), QdrantError> {
+        if updates.is_empty() {
+            return Ok(());
+        }
+        let point_count = updates.len();
+        let operations: Vec<Value> = updates
+            .into_iter()
+            .map(|(id, payload)| json!({ "set_payload": { "payload": payload, "points": [id] } }))
+            .collect();
+        let response = self
+            .request(
+                KeyScope::Write,
+                Method::POST,
+                &format!("collections/{collection_name}/points/batch"),
+            )?
+            .query(&[("wait", true)])
+            .json(&json!({ "operations": operations }))
+            .send_qdrant(self)
+            .await?;
+
+        self.ensure_success(response, || {
+            tracing::debug!(
+                collection = collection_name,
+                points = point_count,
+                "Payloads updated"
+            );
+        })
+        .await
+    }
+
+    /// Perform a similarity search against a collection, returning scored payloads.
+    ///
+    /// `query.params` is forwarded as the query `params` object and omitted when no knob is set.
+    /// With `query.payload_fields`, only those payload keys are returned (`with_payload.include`).
+    ///
+    /// With `query.order`, the `limit` hits are the newest (or oldest) of the
+    /// `limit * ORDERED_SEARCH_CANDIDATE_FACTOR` nearest candidates. Qdrant re-orders the
+    /// prefetched candidates itself; servers without query `order_by` return the candidates and
+    /// they are sorted here, with unparsable timestamps last.
+    pub async fn search_points(
+        &self,
+        collection_name: &str,
+        query: PointQuery<'_>,
+    ) -> Result<Vec<ScoredPoint>, QdrantError> {
+        let PointQuery {
+            vector,
+            filter,
+            limit,
+            score_threshold,
+            using,
+            params,
+            order,
+            payload_fields,
+        } = query;
+        let with_payload = match payload_fields {
+            Some(fields) => json!({ "include": fields }),
+            None => Value::Bool(true),
+        };
+        let candidates = match order {
+            Some(_) => limit.saturating_mul(ORDERED_SEARCH_CANDIDATE_FACTOR),
+            None => limit,
+        };
+        let mut nearest = json!({
+            "query": vector,
+            "limit": candidates,
+        });
+        let obj = nearest
+            .as_object_mut()
+            .expect("query body should remain an object");
+
+        if let Some(name) = using.and_then(|value| {
+            let trimmed = value.trim();
+            if trimmed.is_empty() {
+                None
+            } else {
+                Some(trimmed.to_string())
+            }
+        }) {
+            obj.insert("using".into(), Value::String(name));
+        }
+
+        if let Some(threshold) = score_threshold {
+            obj.insert("score_threshold".into(), Value::from(threshold));
+        }
+
+        if let Some(filter_value) = filter {
+            obj.insert("filter".into(), filter_value);
+        }
+
+        if let Some(params) = params.filter(|params| !params.is_empty()) {
+            obj.insert(
+                "params".into(),
+                serde_json::to_value(params).expect("search params serialize to JSON"),
+            );
+        }
+
+        let ordered_by_qdrant = order.is_some() && self.capabilities.supports_query_order_by;
+        let body = match order {
+            Some(order) if ordered_by_qdrant => json!({
+                "prefetch": nearest,
+                "query": {
+                    "order_by": { "key": "timestamp", "direction": order.direction() }
+                },
+                "limit": limit,
+                "with_payload": with_payload,
+            }),
+            _ => {
+                nearest["with_payload"] = with_payload;
+                nearest
+            }
+        };
+
+        let response = self
+            .read_request(
+                Method::POST,
+                &format!("collections/{collection_name}/points/query"),
+            )?
+            .json(&body)
+            .send_qdrant(self)
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            let error = QdrantError::UnexpectedStatus { status, body };
+            tracing::error!(collection = collection_name, error = %error, "Qdrant search failed");
+            return Err(error);
+        }
+
+        let payload: QueryResponse = response.json().await?;
+        let mut results: Vec<ScoredPoint> = payload
+            .result
+            .into_points()
+            .into_iter()
+            .map(|point| ScoredPoint {
+                id: stringify_point_id(point.id),
+                score: point.score,
+                payload: point.payload,
+            })
+            .collect();
+
+        if let Some(order) = order.filter(|_| !ordered_by_qdrant) {
+            let instant = |point: &ScoredPoint| {
+                point
+                    .payload
+                    .as_ref()
+                    .and_then(|payload| payload.get("timestamp"))
+                    .and_then(Value::as_str)
+                    .and_then(|raw| OffsetDateTime::parse(raw, &Rfc3339).ok())
+            };
+            match order {
+                TimestampOrder::Descending => results.sort_by_cached_key(|point| {
+                    (instant(point).is_none(), Reverse(instant(point)))
+                }),
+                TimestampOrder::Ascending => {
+                    results.sort_by_cached_key(|point| (instant(point).is_none(), instant(point)))
+                }
+            }
+            results.truncate(limit);
+        }
+
+        Ok(results)
+    }
+
+    /// Ensure standard payload indexes exist for common filters.
+    ///
+    /// The index requests are sent concurrently. Fields Qdrant refuses to index are collected in
+    /// [`PayloadIndexSummary::failed`] rather than failing the call; transport errors are
+    /// returned as-is.
+    pub async fn ensure_payload_indexes(
+        &self,
+        collection_name: &str,
+    ) -> Result<PayloadIndexSummary, QdrantError> {
+        let mut summary = PayloadIndexSummary::default();
+        let mut pending = Vec::with_capacity(PAYLOAD_INDEX_FIELDS.len());
+        for (field, schema) in PAYLOAD_INDEX_FIELDS {
+            if schema == "datetime" && !self.capabilities.supports_datetime_index {
+                tracing::debug!(
+                    collection = collection_name,
+                    field,
+                    "Skipping datetime index unsupported by this Qdrant version"
+                );
+                summary.skipped.push(field);
+            } else {
+                pending.push((field, schema));
+            }
+        }
+
+        let results = join_all(
+            pending
+                .iter()
+                .map(|(field, schema)| self.create_payload_index(collection_name, field, schema)),
+        )
+        .await;
+        for ((field, schema), result) in pending.into_iter().zip(results) {
+            match result {
+                Ok(()) => summary.ensured.push(field),
+                Err(error @ QdrantError::UnexpectedStatus { .. }) => {
+                    tracing::warn!(collection = collection_name, field, schema, error = %error, "Failed to ensure payload index");
+                    summary.failed.push(PayloadIndexFailure {
+                        field,
+                        schema,
+                        error,
+                    });
+                }
+                Err(error) => return Err(error),
+            }
+        }
+
+        Ok(summary)
+    }
+
+    /// Create a payload index on `field`; an index that already exists counts as success.
+    pub async fn create_payload_index(
+        &self,
+        collection_name: &str,
+        field: &str,
+        schema: &str,
+    ) -> Result<(), QdrantError> {
+        let body = json!({
+            "field_name": field,
+            "field_schema": schema,
+        });
+
+        let response = self
+            .request(
+                KeyScope::Write,
+                Method::PUT,
+                &format!("collections/{collection_name}/index"),
+            )?
+            .json(&body)
+            .send_qdrant(self)
+            .await?;
+
+        if response.status().is_success() {
+            tracing::debug!(
+                collection = collection_name,
+                field,
+                schema,
+                "Payload index ensured"
+            );
+            Ok(())
+        } else if response.status() == StatusCode::CONFLICT {
+            tracing::debug!(
+                collection = collection_name,
+                field,
+                schema,
+                "Payload index already exists"
+            );
+            Ok(())
+        } else {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            Err(QdrantError::UnexpectedStatus { status, body })
+        }
+    }
+
+    /// Whether `collection_name` exists in Qdrant.
+    pub async fn collection_exists(&self, collection_name: &str) -> Result<bool, QdrantError> {
+        let response = self
+            .request(
+                KeyScope::Read,
+                Method::GET,
+                &format!("collections/{collection_name}"),
+            )?
+            .send_qdrant(self)
+            .await?;
+
+        match response.status() {
+            StatusCode::OK => Ok(true),
+            StatusCode::NOT_FOUND => Ok(false),
+            status => {
+                let body = response.text().await.unwrap_or_default();
+                let error = QdrantError::UnexpectedStatus { status, body };
+                tracing::error!(collection = collection_name, error = %error, "Collection existence check failed");
+                Err(error)
+            }
+        }
+    }
+
+    fn request(
+        &self,
+        scope: KeyScope,
+        method: Method,
+        path: &str,
+    ) -> Result<reqwest::RequestBuilder, QdrantError> {
+        self.request_to(&self.base_url, scope, method, path)
+    }
+
+    /// Build a search, scroll, or count request, sent to the read replica when one is set.
+    fn read_request(
+        &self,
+        method: Method,
+        path: &str,
+    ) -> Result<reqwest::RequestBuilder, QdrantError> {
+        let base_url = self.read_base_url.as_deref().unwrap_or(&self.base_url);
+        self.request_to(base_url, KeyScope::Read, method, path)
+    }
+
+    fn request_to(
+        &self,
+        base_url: &str,
+        scope: KeyScope,
+        method: Method,
+        path: &str,
+    ) -> Result<reqwest::RequestBuilder, QdrantError> {
+        let url = format_endpoint(base_url, path);
+        let mut req = self.client.request(method, url);
+        if let Some(api_key) = self.api_key_for(scope) {
+            req = req.header("api-key", api_key);
+        }
+        Ok(req)
+    }
+
+    /// Pick the labelled key for the operation scope, falling back to the single `api_key`.
+    fn api_key_for(&self, scope: KeyScope) -> Option<&str> {
+        self.api_keys
+            .get(scope.label())
+            .or(self.api_key.as_ref())
+            .map(String::as_str)
+            .filter(|value| !value.is_empty())
+    }
+
+    async fn ensure_success<F>(
+        &self,
+        response: reqwest::Response,
+        on_success: F,
+    ) -> Result<(), QdrantError>
+    where
+        F: FnOnce(),
+    {
+        if response.status().is_success() {
+            on_success();
+            Ok(())
+        } else {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            let error = QdrantError::UnexpectedStatus { status, body };
+            tracing::error!(error = %error, "Qdrant request failed");
+            Err(error)
+        }
+    }
+
+    async fn scroll_payloads(
+        &self,
+        collection: &str,
+        with_payload: Value,
+        filter: Option<Value>,
+    ) -> Result<Vec<Map<String, Value>>, QdrantError> {
+        let mut payloads = Vec::new();
+        self.scroll_payloads_each(collection, with_payload, filter, |payload| {
+            payloads.push(payload)
+        })
+        .await?;
+        Ok(payloads)
+    }
+
+    /// Scroll every matching payload, handing each to `visit` as its page arrives.
+    async fn scroll_payloads_each(
+        &self,
+        collection: &str,
+        with_payload: Value,
+        filter: Option<Value>,
+        mut visit: impl FnMut(Map<String, Value>),
+    ) -> Result<(), QdrantError> {
+        let mut offset: Option<Value> = None;
+        let filter_body = filter.unwrap_or_else(|| json!({ "must": [] }));
+
+        loop {
+            let mut body = json!({
+                "with_payload": with_payload.clone(),
+                "with_vector": false,
+                "limit": 512,
+                "offset": offset.clone().unwrap_or(Value::Null),
+                "filter": filter_body.clone(),
+            });
+
+            if offset.is_none() {
+                body.as_object_mut().unwrap().remove("offset");
+            }
+
+            let response = self
+                .read_request(
+                    Method::POST,
+                    &format!("collections/{collection}/points/scroll"),
+                )?
+                .json(&body)
+                .send_qdrant(self)
+                .await?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().await.unwrap_or_default();
+                let error = QdrantError::UnexpectedStatus { status, body };
+                tracing::error!(collection, error = %error, "Failed to scroll payloads");
+                return Err(error);
+            }
+
+            let ScrollResponse { result } = response.json().await?;
+            for point in result.points {
+                if let Some(payload) = point.payload {
+                    visit(payload);
+                }
+            }
+
+            match result.next_page_offset {
+                Some(next) => offset = Some(next),
+                None => break,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Scroll payloads and return their associated point identifiers.
+    pub async fn scroll_payloads_with_ids(
+        &self,
+        collection: &str,
+        with_payload: Value,
+        filter: Option<Value>,
+    ) -> Result<Vec<(String, Map<String, Value>)>, QdrantError> {
+        let mut offset: Option<Value> = None;
+        let mut results = Vec::new();
+        let filter_body = filter.unwrap_or_else(|| json!({ "must": [] }));
+
+        loop {
+            let mut body = json!({
+                "with_payload": with_payload.clone(),
+                "with_vector": false,
+                "limit": 512,
+                "offset": offset.clone().unwrap_or(Value::Null),
+                "filter": filter_body,
+            });
+            // Older servers reject `order_by`; callers sort the results themselves.
+            if self.capabilities.supports_order_by {
+                body["order_by"] = json!([{ "key": "timestamp", "direction": "asc" }]);
+            }
+
+            let response = self
+                .read_request(
+                    Method::POST,
+                    &format!("collections/{collection}/points/scroll"),
+                )?
+                .json(&body)
+                .send_qdrant(self)
+                .await?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().await.unwrap_or_default();
+                let error = QdrantError::UnexpectedStatus { status, body };
+                tracing::error!(collection, error = %error, "Failed to scroll payloads with ids");
+                return Err(error);
+            }
+
+            let ScrollResponse { result } = response.json().await?;
+            for point in result.points {
+                if let (Some(id), Some(payload)) = (point.id, point.payload) {
+                    results.push((stringify_point_id(id), payload));
+                }
+            }
+
+            match result.next_page_offset {
+                Some(next) => offset = Some(next),
+                None => break,
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Latest `limit` points matching `filter`, newest `timestamp` first.
+    ///
+    /// Uses a descending `order_by` scroll on the `timestamp` datetime index, or on the integer
+    /// `timestamp_unix` mirror when the server has no datetime index. Since `order_by` skips
+    /// points without the key, the mirror scroll is merged with the points written before
+    /// `timestamp_unix` existed. Servers without `order_by` are scrolled in full and sorted here,
+    /// with unparsable timestamps last.
+    pub async fn scroll_recent_payloads(
+        &self,
+        collection: &str,
+        filter: Option<Value>,
+        limit: usize,
+    ) -> Result<Vec<(String, Map<String, Value>)>, QdrantError> {
+        let newest_first = |points: &mut Vec<(String, Map<String, Value>)>| {
+            points.sort_by_cached_key(|(_, payload)| {
+                Reverse(
+                    payload
+                        .get("timestamp")
+                        .and_then(Value::as_str)
+                        .and_then(|raw| OffsetDateTime::parse(raw, &Rfc3339).ok()),
+                )
+            });
+            points.truncate(limit);
+        };
+        if !self.capabilities.supports_order_by {
+            let mut points = self
+                .scroll_payloads_with_ids(collection, json!(true), filter)
+                .await?;
+            newest_first(&mut points);
+            return Ok(points);
+        }
+
+        let key = if self.capabilities.supports_datetime_index {
+            "timestamp"
+        } else {
+            TIMESTAMP_UNIX_FIELD
+        };
+        let mut points = self
+            .scroll_ordered_payloads(collection, filter.clone(), key, limit)
+            .await?;
+        if key == TIMESTAMP_UNIX_FIELD {
+            let mut unmirrored = filter.unwrap_or_else(|| json!({ "must": [] }));
+            let condition = json!({ "is_empty": { "key": TIMESTAMP_UNIX_FIELD } });
+            match unmirrored.get_mut("must").and_then(Value::as_array_mut) {
+                Some(must) => must.push(condition),
+                None => unmirrored["must"] = json!([condition]),
+            }
+            points.extend(
+                self.scroll_payloads_with_ids(collection, json!(true), Some(unmirrored))
+                    .await?,
+            );
+            newest_first(&mut points);
+        }
+        Ok(points)
+    }
+
+    /// First `limit` points matching `filter` in descending order of the indexed `key`.
+    async fn scroll_ordered_payloads(
+        &self,
+        collection: &str,
+        filter: Option<Value>,
+        key: &str,
+        limit: usize,
+    ) -> Result<Vec<(String, Map<String, Value>)>, QdrantError> {
+        let body = json!({
+            "with_payload": true,
+            "with_vector": false,
+            "limit": limit,
+            "filter": filter.unwrap_or_else(|| json!({ "must": [] })),
+            "order_by": { "key": key, "direction": "desc" },
+        });
+        let response = self
+            .read_request(
+                Method::POST,
+                &format!("collections/{collection}/points/scroll"),
+            )?
+            .json(&body)
+            .send_qdrant(self)
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            let error = QdrantError::UnexpectedStatus { status, body };
+            tracing::error!(collection, error = %error, "Failed to scroll recent payloads");
+            return Err(error);
+        }
+
+        let ScrollResponse { result } = response.json().await?;
+        Ok(result
+            .points
+            .into_iter()
+            .filter_map(|point| Some((stringify_point_id(point.id?), point.payload?)))
+            .collect())
+    }
+
+    /// Scroll every point matching `filter`, including its vector(s) and full payload.
+    ///
+    /// Reads from the primary like [`Self::scroll_points_with_vectors_page`].
+    pub async fn scroll_points_with_vectors(
+        &self,
+        collection: &str,
+        filter: Option<Value>,
+    ) -> Result<Vec<StoredPoint>, QdrantError> {
+        let mut offset: Option<Value> = None;
+        let mut results = Vec::new();
+
+        loop {
+            let (points, next) = self
+                .scroll_points_with_vectors_page(collection, filter.clone(), offset, 256)
+                .await?;
+            results.extend(points);
+            match next {
+                Some(next) => offset = Some(next),
+                None => break,
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Fetch one page of points matching `filter` with vectors and full payloads.
+    ///
+    /// Returns the page and the offset of the next one, or `None` once the scroll is exhausted.
+    /// Always reads from the primary, never the read replica: callers copy these points elsewhere
+    /// and then delete them, so a lagging replica would lose the writes it has not seen yet.
+    pub async fn scroll_points_with_vectors_page(
+        &self,
+        collection: &str,
+        filter: Option<Value>,
+        offset: Option<Value>,
+        limit: usize,
+    ) -> Result<(Vec<StoredPoint>, Option<Value>), QdrantError> {
+        let body = json!({
+            "with_payload": true,
+            "with_vector": true,
+            "limit": limit,
+            "offset": offset.unwrap_or(Value::Null),
+            "filter": filter.unwrap_or_else(|| json!({ "must": [] })),
+        });
+
+        let response = self
+            .request(
+                KeyScope::Read,
+                Method::POST,
+                &format!("collections/{collection}/points/scroll"),
+            )?
+            .json(&body)
+            .send_qdrant(self)
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            let error = QdrantError::UnexpectedStatus { status, body };
+            tracing::error!(collection, error = %error, "Failed to scroll points with vectors");
+            return Err(error);
+        }
+
+        let ScrollResponse { result } = response.json().await?;
+        let points = result
+            .points
+            .into_iter()
+            .filter_map(|point| {
+                Some(StoredPoint {
+                    id: stringify_point_id(point.id?),
+                    vector: point.vector?,
+                    payload: point.payload.unwrap_or_default(),
+                })
+            })
+            .collect();
+        Ok((points, result.next_page_offset))
+    }
+
+    /// Fetch one page of points with their full payloads, starting at `offset`.
+    ///
+    /// Returns the page and the offset of the next one, or `None` once the scroll is exhausted.
+    pub async fn scroll_payload_page(
+        &self,
+        collection: &str,
+        offset: Option<Value>,
+        limit: usize,
+    ) -> Result<(Vec<(String, Map<String, Value>)>, Option<Value>), QdrantError> {
+        let mut body = json!({
+            "with_payload": true,
+            "with_vector": false,
+            "limit": limit,
+        });
+        if let Some(offset) = offset {
+            body["offset"] = offset;
+        }
+
+        let response = self
+            .read_request(
+                Method::POST,
+                &format!("collections/{collection}/points/scroll"),
+            )?
+            .json(&body)
+            .send_qdrant(self)
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            let error = QdrantError::UnexpectedStatus { status, body };
+            tracing::error!(collection, error = %error, "Failed to scroll payload page");
+            return Err(error);
+        }
+
+        let ScrollResponse { result } = response.json().await?;
+        let points = result
+            .points
+            .into_iter()
+            .filter_map(|point| {
+                Some((
+                    stringify_point_id(point.id?),
+                    point.payload.unwrap_or_default(),
+                ))
+            })
+            .collect();
+        Ok((points, result.next_page_offset))
+    }
+}
+
+fn project_filter(project_id: Option<&str>) -> Option<Value> {
+    project_id.map(|project| {
+        json!({
+            "must": [
+                {
+                    "key": "project_id",
+                    "match": { "value": project }
+                }
+            ]
+        })
+    })
+}
+
+fn normalize_base_url(url: &str) -> Result<String, String> {
+    let mut parsed = reqwest::Url::parse(url).map_err(|err| err.to_string())?;
+    let path = parsed.path().trim_end_matches('/').to_string();
+    parsed.set_path(&path);
+    Ok(parsed.to_string())
+}
+
+/// Longest collection name accepted by [`validate_collection_name`].
+const MAX_COLLECTION_NAME_LEN: usize = 255;
+
+/// Check that a collection name is safe to splice into Qdrant URL paths.
+///
+/// Names may use ASCII letters, digits, `-`, `_` and `.` (1–255 characters) and must not consist
+/// only of dots, which URL normalization would resolve as `.`/`..` path segments.
+pub fn validate_collection_name(name: &str) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("collection name must not be empty".into());
+    }
+    if let Some(invalid) = name
+        .chars()
+        .find(|ch| !(ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.')))
+    {
+        return Err(format!(
+            "collection name {name:?} contains invalid character {invalid:?}; use ASCII letters, digits, '-', '_' or '.'"
+        ));
+    }
+    if name.chars().all(|ch| ch == '.') {
+        return Err(format!(
+            "collection name {name:?} must not consist only of dots"
+        ));
+    }
+    if name.len() > MAX_COLLECTION_NAME_LEN {
+        return Err(format!(
+            "collection name must be at most {MAX_COLLECTION_NAME_LEN} characters (got {})",
+            name.len()
+        ));
+    }
+    Ok(())
+}
+
+/// Collection holding one project's memories under `COLLECTION_PER_PROJECT`: `<base>-<project_id>`.
+///
+/// Characters [`validate_collection_name`] rejects become `_`. When the id had to be altered or
+/// shortened to fit, the first 8 hex digits of its SHA-256 are appended, so ids that sanitize to
+/// the same text (`a/b`, `a:b`) still get distinct collections.
+pub fn project_collection_name(base: &str, project_id: &str) -> String {
+    let sanitized: String = project_id
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.') {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let room = MAX_COLLECTION_NAME_LEN.saturating_sub(base.len() + 1);
+    if sanitized == project_id && sanitized.len() <= room {
+        return format!("{base}-{sanitized}");
+    }
+    let digest = hex::encode(&Sha256::digest(project_id.as_bytes())[..4]);
+    let keep = room.saturating_sub(digest.len() + 1).min(sanitized.len());
+    format!("{base}-{}-{digest}", &sanitized[..keep])
+}
+
+/// Sends a built request on behalf of a [`QdrantService`].
+trait QdrantSend {
+    /// Apply any injected faults, then send the request inside an `outbound.request` span.
+    fn send_qdrant(
+        self,
+        service: &QdrantService,
+    ) -> impl Future<Output = Result<reqwest::Response, QdrantError>> + Send;
+}
+
+impl QdrantSend for reqwest::RequestBuilder {
+    #[cfg_attr(not(feature = "fault-injection"), allow(unused_variables))]
+    async fn send_qdrant(self, service: &QdrantService) -> Result<reqwest::Response, QdrantError> {
+        #[cfg(feature = "fault-injection")]
+        if let Some(faults) = &service.faults {
+            faults
+                .inject()
+                .await
+                .map_err(|fault| QdrantError::UnexpectedStatus {
+                    status: StatusCode::SERVICE_UNAVAILABLE,
+                    body: fault.to_string(),
+                })?;
+        }
+        Ok(self.send_traced("qdrant").await?)
+    }
+}
+
+fn format_endpoint(base: &str, path: &str) -> String {
+    let base = base.trim_end_matches('/');
+    let path = path
+        .trim_start_matches('/')
+        .split('/')
+        .map(encode_path_segment)
+        .collect::<Vec<_>>()
+        .join("/");
+    format!("{base}/{path}")
+}
+
+/// Percent-encode every byte outside the RFC 3986 unreserved set.
+///
+/// Collection names are validated before they reach the client; this keeps a name that slipped
+/// through from injecting query strings or fragments into the request URL.
+fn encode_path_segment(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            encoded.push(char::from(byte));
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+fn stringify_point_id(id: Value) -> String {
+    match id {
+        Value::String(text) => text,
+        Value::Number(number) => number.to_string(),
+        Value::Object(map) => map
+            .get("uuid")
+            .map(|value| match value {
+                Value::String(uuid) => uuid.clone(),
+                other => other.to_string(),
+            })
+            .unwrap_or_else(|| Value::Object(map).to_string()),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qdrant::types::SearchParams;
+    use httpmock::{
+        Method::{GET, POST, PUT},
+        MockServer,
+    };
+    use proptest::prelude::*;
+    use reqwest::Client;
+
+    fn test_service(
+        base_url: String,
+        api_key: Option<&str>,
+        api_keys: &[(&str, &str)],
+    ) -> QdrantService {
+        QdrantService {
+            client: Client::builder()
+                .user_agent("rusty-mem-test")
+                .build()
+                .expect("client"),
+            base_url,
+            read_base_url: None,
+            api_key: api_key.map(str::to_string),
+            api_keys: api_keys
+                .iter()
+                .map(|(label, key)| (label.to_string(), key.to_string()))
+                .collect(),
+            capabilities: QdrantCapabilities::default(),
+            #[cfg(feature = "fault-injection")]
+            faults: None,
+        }
+    }
+
+    #[cfg(feature = "fault-injection")]
+    #[tokio::test]
+    async fn injected_faults_fail_the_scheduled_requests_before_sending() {
+        use crate::fault::{FaultConfig, FaultInjector};
+        use std::sync::Arc;
+
+        let server = MockServer::start_async().await;
+        let list = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/collections");
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": { "collections": [{ "name": "demo" }] }
+                }));
+            })
+            .await;
+        let faults = Arc::new(FaultInjector::new(FaultConfig {
+            error_rate: 0.5,
+            ..FaultConfig::default()
+        }));
+        let service = QdrantService {
+            faults: Some(faults.clone()),
+            ..test_service(server.base_url(), None, &[])
+        };
+
+        let mut outcomes = Vec::new();
+        for _ in 0..4 {
+            outcomes.push(match service.list_collections().await {
+                Ok(_) => "ok".to_string(),
+                Err(QdrantError::UnexpectedStatus { status, .. }) => status.as_u16().to_string(),
+                Err(other) => panic!("unexpected error: {other}"),
+            });
+        }
+
+        assert_eq!(outcomes, ["ok", "503", "ok", "503"]);
+        assert_eq!(faults.calls(), 4);
+        list.assert_hits_async(2).await;
+    }
+
+    #[tokio::test]
+    async fn reads_and_writes_use_labelled_keys() {
+        let server = MockServer::start_async().await;
+        let read = server
+            .mock_async(|when, then| {
+                when.method(GET)
+                    .path("/collections")
+                    .header("api-key", "read-key");
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "time": 0.0,
+                    "result": { "collections": [{ "name": "demo" }] }
+                }));
+            })
+            .await;
+        let write = server
+            .mock_async(|when, then| {
+                when.method(PUT)
+                    .path("/collections/demo")
+                    .header("api-key", "write-key");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": true }));
+            })
+            .await;
+
+        let service = test_service(
+            server.base_url(),
+            Some("fallback-key"),
+            &[("read", "read-key"), ("write", "write-key")],
+        );
+
+        let names = service.list_collections().await.expect("list collections");
+        service
+            .create_collection("demo", 4, CollectionTopology::default())
+            .await
+            .expect("create collection");
+
+        read.assert();
+        write.assert();
+        assert_eq!(names, vec!["demo".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn missing_labelled_key_falls_back_to_single_key() {
+        let server = MockServer::start_async().await;
+        let write = server
+            .mock_async(|when, then| {
+                when.method(PUT)
+                    .path("/collections/demo")
+                    .header("api-key", "fallback-key");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": true }));
+            })
+            .await;
+
+        let service = test_service(
+            server.base_url(),
+            Some("fallback-key"),
+            &[("read", "read-key")],
+        );
+
+        service
+            .create_collection("demo", 4, CollectionTopology::default())
+            .await
+            .expect("create collection");
+
+        write.assert();
+    }
+
+    #[tokio::test]
+    async fn create_collection_sends_topology_only_when_configured() {
+        let server = MockServer::start_async().await;
+        let single_node = server
+            .mock_async(|when, then| {
+                when.method(PUT)
+                    .path("/collections/single")
+                    .json_body(json!({ "vectors": { "size": 4, "distance": "Cosine" } }));
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": true }));
+            })
+            .await;
+        let clustered = server
+            .mock_async(|when, then| {
+                when.method(PUT)
+                    .path("/collections/clustered")
+                    .json_body(json!({
+                        "vectors": { "size": 4, "distance": "Cosine" },
+                        "shard_number": 6,
+                        "replication_factor": 2
+                    }));
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": true }));
+            })
+            .await;
+
+        let service = test_service(server.base_url(), None, &[]);
+        service
+            .create_collection("single", 4, CollectionTopology::default())
+            .await
+            .expect("single-node collection");
+        let topology = CollectionTopology {
+            replication_factor: Some(2),
+            ..Default::default()
+        }
+        .or(CollectionTopology {
+            shard_number: Some(6),
+            replication_factor: Some(3),
+        });
+        service
+            .create_collection("clustered", 4, topology)
+            .await
+            .expect("clustered collection");
+
+        single_node.assert();
+        clustered.assert();
+    }
+
+    #[tokio::test]
+    async fn search_points_emits_expected_request() {
+        let server = MockServer::start_async().await;
+
+        let filter = crate::qdrant::build_search_filter(&crate::qdrant::SearchFilterArgs {
+            project_id: Some("repo-a".into()),
+            tags: Some(vec!["alpha".into(), "beta".into()]),
+            ..Default::default()
+        })
+        .expect("filter value");
+
+        let mock = server
+            .mock_async(|when, then| {
+                when.method(POST).path("/collections/demo/points/query");
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "time": 0.0,
+                    "result": [
+                        {
+                            "id": "memory-1",
+                            "score": 0.42,
+                            "payload": {
+                                "text": "Example",
+                                "project_id": "repo-a"
+                            }
+                        }
+                    ]
+                }));
+            })
+            .await;
+
+        let service = QdrantService {
+            client: Client::builder()
+                .user_agent("rusty-mem-test")
+                .build()
+                .expect("client"),
+            base_url: server.base_url(),
+            read_base_url: None,
+            api_key: None,
+            api_keys: BTreeMap::new(),
+            capabilities: Default::default(),
+            #[cfg(feature = "fault-injection")]
+            faults: None,
+        };
+
+        let results = service
+            .search_points(
+                "demo",
+                PointQuery {
+                    vector: vec![0.1, 0.2],
+                    filter: Some(filter.clone()),
+                    limit: 3,
+                    score_threshold: Some(0.25),
+                    ..PointQuery::default()
+                },
+            )
+            .await
+            .expect("search request");
+
+        mock.assert();
+
+        assert_eq!(results.len(), 1);
+        let hit = &results[0];
+        assert_eq!(hit.id, "memory-1");
+        assert!((hit.score - 0.42).abs() < f32::EPSILON);
+        let payload = hit.payload.as_ref().expect("payload");
+        assert_eq!(payload["project_id"], Value::String("repo-a".into()));
+        assert_eq!(payload["text"], Value::String("Example".into()));
+    }
+
+    #[tokio::test]
+    async fn list_tag_counts_orders_by_popularity_and_scopes_by_memory_type() {
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/scroll")
+                    .json_body_partial(
+                        json!({
+                            "with_payload": ["tags", "memory_type"],
+                            "filter": { "must": [
+                                { "key": "project_id", "match": { "value": "repo-a" } },
+                                { "key": "memory_type", "match": { "value": "episodic" } }
+                            ] }
+                        })
+                        .to_string(),
+                    );
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "time": 0.0,
+                    "result": {
+                        "points": [
+                            { "id": "a", "payload": { "tags": ["beta", "alpha"], "memory_type": "episodic" } },
+                            { "id": "b", "payload": { "tags": ["alpha"], "memory_type": "episodic" } },
+                            { "id": "c", "payload": { "tags": "gamma" } },
+                            { "id": "d", "payload": { "tags": ["alpha", "gamma"], "memory_type": "episodic" } }
+                        ],
+                        "next_page_offset": null
+                    }
+                }));
+            })
+            .await;
+
+        let service = QdrantService {
+            client: Client::builder()
+                .user_agent("rusty-mem-test")
+                .build()
+                .expect("client"),
+            base_url: server.base_url(),
+            read_base_url: None,
+            api_key: None,
+            api_keys: BTreeMap::new(),
+            capabilities: Default::default(),
+            #[cfg(feature = "fault-injection")]
+            faults: None,
+        };
+
+        let counts = service
+            .list_tag_counts("demo", Some("repo-a"), Some("episodic"))
+            .await
+            .expect("tag counts");
+
+        mock.assert();
+        assert_eq!(counts.overflow, None);
+        let tags: Vec<(&str, usize, Option<&usize>)> = counts
+            .tags
+            .iter()
+            .map(|entry| {
+                (
+                    entry.tag.as_str(),
+                    entry.count,
+                    entry.memory_types.get("episodic"),
+                )
+            })
+            .collect();
+        assert_eq!(
+            tags,
+            [
+                ("alpha", 3, Some(&3)),
+                ("gamma", 2, Some(&1)),
+                ("beta", 1, Some(&1)),
+            ]
+        );
+    }
+
+    fn collection_info_body(status: &str, points: u64, indexed: u64) -> Value {
+        json!({
+            "status": "ok",
+            "time": 0.0,
+            "result": {
+                "status": status,
+                "points_count": points,
+                "indexed_vectors_count": indexed
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn wait_for_indexing_polls_until_collection_is_ready() {
+        let server = MockServer::start_async().await;
+        let indexing = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/collections/demo");
+                then.status(200)
+                    .json_body(collection_info_body("yellow", 40_000, 12_000));
+            })
+            .await;
+        let service = test_service(server.base_url(), None, &[]);
+
+        let swap = async {
+            while indexing.hits_async().await < 2 {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+            indexing.delete_async().await;
+            server
+                .mock_async(|when, then| {
+                    when.method(GET).path("/collections/demo");
+                    then.status(200)
+                        .json_body(collection_info_body("green", 40_000, 40_000));
+                })
+                .await
+        };
+        let (info, ready) = tokio::join!(
+            service.wait_for_indexing("demo", Duration::from_secs(5), Duration::from_millis(10)),
+            swap
+        );
+
+        let info = info.expect("collection info");
+        assert!(info.is_ready());
+        assert_eq!(info.unindexed_backlog(), 0);
+        assert!(ready.hits_async().await >= 1);
+    }
+
+    #[tokio::test]
+    async fn wait_for_indexing_returns_last_status_on_timeout() {
+        let server = MockServer::start_async().await;
+        let indexing = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/collections/demo");
+                then.status(200)
+                    .json_body(collection_info_body("yellow", 40_000, 12_000));
+            })
+            .await;
+        let service = test_service(server.base_url(), None, &[]);
+
+        let info = service
+            .wait_for_indexing("demo", Duration::from_millis(50), Duration::from_millis(10))
+            .await
+            .expect("collection info");
+
+        assert!(!info.is_ready());
+        assert_eq!(info.unindexed_backlog(), 28_000);
+        assert!(indexing.hits_async().await >= 2);
+    }
+
+    async fn search_with_params(params: Option<SearchParams>, expected_body: Value) {
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/query")
+                    .json_body(expected_body);
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": { "points": [] } }));
+            })
+            .await;
+
+        test_service(server.base_url(), None, &[])
+            .search_points(
+                "demo",
+                PointQuery {
+                    vector: vec![0.5],
+                    limit: 4,
+                    params: params.as_ref(),
+                    ..PointQuery::default()
+                },
+            )
+            .await
+            .expect("search request");
+
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn search_points_forwards_oversampling_params() {
+        let params = SearchParams {
+            hnsw_ef: Some(256),
+            quantization: Some(crate::qdrant::QuantizationSearchParams {
+                rescore: Some(true),
+                oversampling: Some(2.5),
+            }),
+        };
+        search_with_params(
+            Some(params),
+            json!({
+                "query": [0.5],
+                "limit": 4,
+                "with_payload": true,
+                "params": {
+                    "hnsw_ef": 256,
+                    "quantization": { "rescore": true, "oversampling": 2.5 }
+                }
+            }),
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn search_points_omits_unset_params() {
+        let expected = json!({ "query": [0.5], "limit": 4, "with_payload": true });
+        search_with_params(None, expected.clone()).await;
+        search_with_params(
+            Some(SearchParams {
+                hnsw_ef: None,
+                quantization: Some(Default::default()),
+            }),
+            expected,
+        )
+        .await;
+    }
+
+    #[test]
+    fn validate_collection_name_rejects_unsafe_names() {
+        for name in ["notes", "repo-a_v2", "team.notes", "A1"] {
+            assert!(validate_collection_name(name).is_ok(), "{name}");
+        }
+        let rejected = [
+            ("", "must not be empty"),
+            ("../collections", "invalid character '/'"),
+            ("a/b", "invalid character '/'"),
+            ("notes?wait=false", "invalid character '?'"),
+            ("notes#frag", "invalid character '#'"),
+            ("my notes", "invalid character ' '"),
+            ("notes\n", "invalid character '\\n'"),
+            ("café", "invalid character 'é'"),
+            ("記憶", "invalid character '記'"),
+            ("..", "only of dots"),
+        ];
+        for (name, expected) in rejected {
+            let message = validate_collection_name(name).unwrap_err();
+            assert!(message.contains(expected), "{name:?}: {message}");
+        }
+        assert!(validate_collection_name(&"a".repeat(255)).is_ok());
+        assert!(
+            validate_collection_name(&"a".repeat(256))
+                .unwrap_err()
+                .contains("at most 255")
+        );
+    }
+
+    #[test]
+    fn project_collection_names_are_sanitized_and_stay_distinct() {
+        assert_eq!(
+            project_collection_name("rusty-mem", "repo-a_v2.1"),
+            "rusty-mem-repo-a_v2.1"
+        );
+
+        let slash = project_collection_name("rusty-mem", "Team A/Notes: café");
+        let colon = project_collection_name("rusty-mem", "Team A:Notes/ café");
+        assert!(
+            slash.starts_with("rusty-mem-Team_A_Notes__caf_-"),
+            "{slash}"
+        );
+        assert_eq!(slash.len(), "rusty-mem-Team_A_Notes__caf_-".len() + 8);
+        assert_ne!(slash, colon);
+        assert_eq!(
+            slash,
+            project_collection_name("rusty-mem", "Team A/Notes: café")
+        );
+
+        let long = project_collection_name("rusty-mem", &"p".repeat(400));
+        assert_eq!(long.len(), MAX_COLLECTION_NAME_LEN);
+        for name in [slash, colon, long] {
+            assert!(validate_collection_name(&name).is_ok(), "{name}");
+        }
+    }
+
+    #[test]
+    fn format_endpoint_percent_encodes_path_segments() {
+        assert_eq!(
+            format_endpoint("http://qdrant:6333/", "/collections/notes/points"),
+            "http://qdrant:6333/collections/notes/points"
+        );
+        assert_eq!(
+            format_endpoint("http://qdrant:6333", "collections/a?b#c/points"),
+            "http://qdrant:6333/collections/a%3Fb%23c/points"
+        );
+        assert_eq!(
+            format_endpoint("http://qdrant:6333", "collections/café"),
+            "http://qdrant:6333/collections/caf%C3%A9"
+        );
+    }
+
+    #[tokio::test]
+    async fn detect_capabilities_gates_features_on_reported_version() {
+        for (raw, order_by, query_groups) in [
+            ("1.7.4", false, false),
+            ("1.9.2", true, false),
+            ("v1.12.0-dev", true, true),
+            ("nightly", true, true),
+        ] {
+            let server = MockServer::start_async().await;
+            server
+                .mock_async(|when, then| {
+                    when.method(GET).path("/");
+                    then.status(200)
+                        .json_body(json!({ "title": "qdrant", "version": raw }));
+                })
+                .await;
+
+            let mut service = test_service(server.base_url(), None, &[]);
+            service.detect_capabilities().await;
+
+            let caps = service.capabilities();
+            assert_eq!(caps.supports_order_by, order_by, "{raw}");
+            assert_eq!(caps.supports_query_groups, query_groups, "{raw}");
+        }
+    }
+
+    #[tokio::test]
+    async fn failed_version_detection_assumes_all_features() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path("/");
+                then.status(500);
+            })
+            .await;
+
+        let mut service = test_service(server.base_url(), None, &[]);
+        service.detect_capabilities().await;
+
+        assert_eq!(*service.capabilities(), QdrantCapabilities::default());
+    }
+
+    #[tokio::test]
+    async fn old_servers_skip_order_by_and_datetime_index() {
+        let server = MockServer::start_async().await;
+        let scroll_without_order = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/scroll")
+                    .matches(|request| {
+                        let body = request.body.as_deref().unwrap_or_default();
+                        !String::from_utf8_lossy(body).contains("order_by")
+                    });
+                then.status(200).json_body(json!({
+                    "result": { "points": [], "next_page_offset": null }
+                }));
+            })
+            .await;
+        let keyword_index = server
+            .mock_async(|when, then| {
+                when.method(PUT)
+                    .path("/collections/demo/index")
+                    .json_body_partial(r#"{"field_schema":"keyword"}"#);
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": true }));
+            })
+            .await;
+        let datetime_index = server
+            .mock_async(|when, then| {
+                when.method(PUT)
+                    .path("/collections/demo/index")
+                    .json_body_partial(r#"{"field_schema":"datetime"}"#);
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": true }));
+            })
+            .await;
+
+        let mut service = test_service(server.base_url(), None, &[]);
+        service.capabilities = QdrantCapabilities::from_version(QdrantVersion::parse("1.7.0"));
+
+        service
+            .scroll_payloads_with_ids("demo", json!(true), None)
+            .await
+            .expect("scroll");
+        service
+            .ensure_payload_indexes("demo")
+            .await
+            .expect("indexes");
+
+        scroll_without_order.assert_async().await;
+        assert!(keyword_index.hits_async().await > 0);
+        datetime_index.assert_hits_async(0).await;
+    }
+
+    #[tokio::test]
+    async fn payload_indexes_are_ensured_concurrently_and_summarized() {
+        let server = MockServer::start_async().await;
+        let refused = server
+            .mock_async(|when, then| {
+                when.method(PUT)
+                    .path("/collections/demo/index")
+                    .json_body_partial(r#"{"field_name":"tags"}"#);
+                then.status(400)
+                    .delay(Duration::from_millis(300))
+                    .body("bad field schema");
+            })
+            .await;
+        let accepted = server
+            .mock_async(|when, then| {
+                when.method(PUT).path("/collections/demo/index");
+                then.status(200)
+                    .delay(Duration::from_millis(300))
+                    .json_body(json!({ "status": "ok", "result": true }));
+            })
+            .await;
+        let service = test_service(server.base_url(), None, &[]);
+
+        let started = Instant::now();
+        let summary = service
+            .ensure_payload_indexes("demo")
+            .await
+            .expect("refusals are summarized, not returned");
+        let elapsed = started.elapsed();
+
+        refused.assert_hits_async(1).await;
+        accepted
+            .assert_hits_async(PAYLOAD_INDEX_FIELDS.len() - 1)
+            .await;
+        assert!(
+            elapsed < Duration::from_millis(300) * 3,
+            "index requests ran one after another: {elapsed:?}"
+        );
+        assert_eq!(summary.ensured.len(), PAYLOAD_INDEX_FIELDS.len() - 1);
+        assert!(!summary.ensured.contains(&"tags"));
+        assert!(summary.skipped.is_empty());
+        assert_eq!(summary.failed.len(), 1);
+        assert_eq!(
+            (summary.failed[0].field, summary.failed[0].schema),
+            ("tags", "keyword")
+        );
+        assert!(matches!(
+            summary.failed[0].error,
+            QdrantError::UnexpectedStatus { status, .. } if status == StatusCode::BAD_REQUEST
+        ));
+    }
+
+    #[tokio::test]
+    async fn recent_payloads_order_by_timestamp_descending() {
+        let server = MockServer::start_async().await;
+        let scroll = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/scroll")
+                    .json_body_partial(
+                        r#"{"limit":2,"order_by":{"key":"timestamp","direction":"desc"}}"#,
+                    );
+                then.status(200).json_body(json!({
+                    "result": {
+                        "points": [
+                            { "id": "new", "payload": { "timestamp": "2025-03-01T00:00:00Z" } },
+                            { "id": "old", "payload": { "timestamp": "2025-01-01T00:00:00Z" } }
+                        ],
+                        "next_page_offset": "more"
+                    }
+                }));
+            })
+            .await;
 
-            match result.next_page_offset {
-                Some(next) => offset = Some(next),
-                None => break,
-            }
-        }
+        let service = test_service(server.base_url(), None, &[]);
+        let points = service
+            .scroll_recent_payloads("demo", None, 2)
+            .await
+            .expect("recent");
 
-        Ok(payloads)
+        scroll.assert_async().await;
+        let ids: Vec<_> = points.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(ids, ["new", "old"]);
     }
 
-    /// Scroll payloads and return their associated point identifiers.
-    pub async fn scroll_payloads_with_ids(
-        &self,
-        collection: &str,
-        with_payload: Value,
-        filter: Option<Value>,
-    ) -> Result<Vec<(String, Map<String, Value>)>, QdrantError> {
-        let mut offset: Option<Value> = None;
-        let mut results = Vec::new();
-        let filter_body = filter.unwrap_or_else(|| json!({ "must": [] }));
-
-        loop {
-            let body = json!({
-                "with_payload": with_payload.clone(),
-                "with_vector": false,
-                "limit": 512,
-                "offset": offset.clone().unwrap_or(Value::Null),
-                "filter": filter_body,
-                "order_by": [
-                    { "key": "timestamp", "direction": "asc" }
-                ]
-            });
+    #[tokio::test]
+    async fn recent_payloads_without_datetime_index_merge_points_missing_the_unix_mirror() {
+        let server = MockServer::start_async().await;
+        let ordered = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/scroll")
+                    .json_body_partial(
+                        r#"{"limit":2,"order_by":{"key":"timestamp_unix","direction":"desc"}}"#,
+                    );
+                then.status(200).json_body(json!({
+                    "result": {
+                        "points": [
+                            { "id": "feb", "payload": { "timestamp": "2025-02-01T00:00:00Z", "timestamp_unix": 1_738_368_000 } },
+                            { "id": "jan", "payload": { "timestamp": "2025-01-01T00:00:00Z", "timestamp_unix": 1_735_689_600 } }
+                        ],
+                        "next_page_offset": "more"
+                    }
+                }));
+            })
+            .await;
+        let unmirrored = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/scroll")
+                    .body_contains(r#"{"is_empty":{"key":"timestamp_unix"}}"#);
+                then.status(200).json_body(json!({
+                    "result": {
+                        "points": [
+                            { "id": "legacy", "payload": { "timestamp": "2025-03-01T00:00:00Z" } }
+                        ],
+                        "next_page_offset": null
+                    }
+                }));
+            })
+            .await;
 
-            // Qdrant does not yet support `order_by` in scroll for all versions; keep it in body but tolerate errors.
-            let response = self
-                .request(
-                    Method::POST,
-                    &format!("collections/{collection}/points/scroll"),
-                )?
-                .json(&body)
-                .send()
-                .await?;
+        let mut service = test_service(server.base_url(), None, &[]);
+        service.capabilities.supports_datetime_index = false;
+        let points = service
+            .scroll_recent_payloads("demo", None, 2)
+            .await
+            .expect("recent");
 
-            if !response.status().is_success() {
-                let status = response.status();
-                let body = response.text().await.unwrap_or_default();
-                let error = QdrantError::UnexpectedStatus { status, body };
-                tracing::error!(collection, error = %error, "Failed to scroll payloads with ids");
-                return Err(error);
-            }
+        ordered.assert_async().await;
+        unmirrored.assert_async().await;
+        let ids: Vec<_> = points.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(ids, ["legacy", "feb"]);
+    }
 
-            let ScrollResponse { result } = response.json().await?;
-            for point in result.points {
-                if let (Some(id), Some(payload)) = (point.id, point.payload) {
-                    results.push((stringify_point_id(id), payload));
-                }
-            }
+    #[tokio::test]
+    async fn recent_payloads_sort_all_pages_when_order_by_is_unsupported() {
+        let server = MockServer::start_async().await;
+        let first_page = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/scroll")
+                    .json_body_partial(r#"{"offset":null}"#);
+                then.status(200).json_body(json!({
+                    "result": {
+                        "points": [
+                            { "id": "jan", "payload": { "timestamp": "2025-01-01T00:00:00Z" } },
+                            { "id": "undated", "payload": { "text": "no timestamp" } },
+                            { "id": "mar", "payload": { "timestamp": "2025-03-01T00:00:00.5Z" } }
+                        ],
+                        "next_page_offset": "page-2"
+                    }
+                }));
+            })
+            .await;
+        let second_page = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/scroll")
+                    .json_body_partial(r#"{"offset":"page-2"}"#);
+                then.status(200).json_body(json!({
+                    "result": {
+                        "points": [
+                            { "id": "feb", "payload": { "timestamp": "2025-02-01T00:00:00Z" } },
+                            { "id": "mar-early", "payload": { "timestamp": "2025-03-01T00:00:00Z" } }
+                        ],
+                        "next_page_offset": null
+                    }
+                }));
+            })
+            .await;
 
-            match result.next_page_offset {
-                Some(next) => offset = Some(next),
-                None => break,
-            }
-        }
+        let mut service = test_service(server.base_url(), None, &[]);
+        service.capabilities = QdrantCapabilities::from_version(QdrantVersion::parse("1.7.0"));
+        let points = service
+            .scroll_recent_payloads("demo", None, 4)
+            .await
+            .expect("recent");
 
-        Ok(results)
+        first_page.assert_async().await;
+        second_page.assert_async().await;
+        let ids: Vec<_> = points.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(ids, ["mar", "mar-early", "feb", "jan"]);
     }
-}
 
-fn normalize_base_url(url: &str) -> Result<String, String> {
-    let mut parsed = reqwest::Url::parse(url).map_err(|err| err.to_string())?;
-    let path = parsed.path().trim_end_matches('/').to_string();
-    parsed.set_path(&path);
-    Ok(parsed.to_string())
-}
+    #[tokio::test]
+    async fn ordered_search_lets_qdrant_reorder_prefetched_candidates() {
+        let server = MockServer::start_async().await;
+        let ordered = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/query")
+                    .json_body(json!({
+                        "prefetch": {
+                            "query": [0.5],
+                            "limit": 8,
+                            "score_threshold": 0.25,
+                        },
+                        "query": {
+                            "order_by": { "key": "timestamp", "direction": "desc" }
+                        },
+                        "limit": 2,
+                        "with_payload": true,
+                    }));
+                then.status(200).json_body(json!({
+                    "result": {
+                        "points": [
+                            { "id": "mar", "payload": { "timestamp": "2025-03-01T00:00:00Z" } },
+                            { "id": "feb", "score": 0.3, "payload": { "timestamp": "2025-02-01T00:00:00Z" } }
+                        ]
+                    }
+                }));
+            })
+            .await;
 
-fn format_endpoint(base: &str, path: &str) -> String {
-    let base = base.trim_end_matches('/');
-    let path = path.trim_start_matches('/');
-    format!("{base}/{path}")
-}
+        let points = test_service(server.base_url(), None, &[])
+            .search_points(
+                "demo",
+                PointQuery {
+                    vector: vec![0.5],
+                    limit: 2,
+                    score_threshold: Some(0.25),
+                    order: Some(TimestampOrder::Descending),
+                    ..PointQuery::default()
+                },
+            )
+            .await
+            .expect("ordered search");
 
-fn stringify_point_id(id: Value) -> String {
-    match id {
-        Value::String(text) => text,
-        Value::Number(number) => number.to_string(),
-        Value::Object(map) => map
-            .get("uuid")
-            .map(|value| match value {
-                Value::String(uuid) => uuid.clone(),
-                other => other.to_string(),
-            })
-            .unwrap_or_else(|| Value::Object(map).to_string()),
-        Value::Null => String::new(),
-        other => other.to_string(),
+        ordered.assert_async().await;
+        let ids: Vec<_> = points.iter().map(|point| point.id.as_str()).collect();
+        assert_eq!(ids, ["mar", "feb"]);
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use httpmock::{Method::POST, MockServer};
-    use reqwest::Client;
 
     #[tokio::test]
-    async fn search_points_emits_expected_request() {
+    async fn ordered_search_sorts_candidates_locally_when_query_order_by_is_unsupported() {
         let server = MockServer::start_async().await;
+        let nearest = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/query")
+                    .json_body(json!({
+                        "query": [0.5],
+                        "limit": 8,
+                        "with_payload": true,
+                    }));
+                then.status(200).json_body(json!({
+                    "result": [
+                        { "id": "feb", "score": 0.9, "payload": { "timestamp": "2025-02-01T00:00:00Z" } },
+                        { "id": "undated", "score": 0.8, "payload": { "text": "no timestamp" } },
+                        { "id": "jan", "score": 0.7, "payload": { "timestamp": "2025-01-01T00:00:00Z" } },
+                        { "id": "mar", "score": 0.6, "payload": { "timestamp": "2025-03-01T00:00:00Z" } }
+                    ]
+                }));
+            })
+            .await;
 
-        let filter = crate::qdrant::build_search_filter(&crate::qdrant::SearchFilterArgs {
-            project_id: Some("repo-a".into()),
-            tags: Some(vec!["alpha".into(), "beta".into()]),
-            ..Default::default()
-        })
-        .expect("filter value");
+        let mut service = test_service(server.base_url(), None, &[]);
+        service.capabilities = QdrantCapabilities::from_version(QdrantVersion::parse("1.9.2"));
+        let newest = service
+            .search_points(
+                "demo",
+                PointQuery {
+                    vector: vec![0.5],
+                    limit: 2,
+                    order: Some(TimestampOrder::Descending),
+                    ..PointQuery::default()
+                },
+            )
+            .await
+            .expect("newest first");
+        let oldest = service
+            .search_points(
+                "demo",
+                PointQuery {
+                    vector: vec![0.5],
+                    limit: 2,
+                    order: Some(TimestampOrder::Ascending),
+                    ..PointQuery::default()
+                },
+            )
+            .await
+            .expect("oldest first");
 
-        let mock = server
+        nearest.assert_hits_async(2).await;
+        let ids = |points: &[ScoredPoint]| -> Vec<String> {
+            points.iter().map(|point| point.id.clone()).collect()
+        };
+        assert_eq!(ids(&newest), ["mar", "feb"]);
+        assert_eq!(ids(&oldest), ["jan", "feb"]);
+    }
+
+    #[tokio::test]
+    async fn reads_go_to_the_replica_and_writes_to_the_primary() {
+        let primary = MockServer::start_async().await;
+        let replica = MockServer::start_async().await;
+        let ok = json!({ "status": "ok", "result": {} });
+        let primary_delete = primary
+            .mock_async(|when, then| {
+                when.method(POST).path("/collections/demo/points/delete");
+                then.status(200).json_body(ok.clone());
+            })
+            .await;
+        let primary_info = primary
+            .mock_async(|when, then| {
+                when.method(GET).path("/collections/demo");
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": { "status": "green", "points_count": 1 }
+                }));
+            })
+            .await;
+        let replica_query = replica
             .mock_async(|when, then| {
                 when.method(POST).path("/collections/demo/points/query");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": { "points": [] } }));
+            })
+            .await;
+        let replica_scroll = replica
+            .mock_async(|when, then| {
+                when.method(POST).path("/collections/demo/points/scroll");
                 then.status(200).json_body(json!({
                     "status": "ok",
-                    "time": 0.0,
-                    "result": [
-                        {
-                            "id": "memory-1",
-                            "score": 0.42,
-                            "payload": {
-                                "text": "Example",
-                                "project_id": "repo-a"
-                            }
-                        }
-                    ]
+                    "result": { "points": [], "next_page_offset": null }
                 }));
             })
             .await;
+        let replica_count = replica
+            .mock_async(|when, then| {
+                when.method(POST).path("/collections/demo/points/count");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": { "count": 0 } }));
+            })
+            .await;
+        // Each server only mocks its own class of requests, so a misrouted call fails with 404.
+        let mut service = test_service(primary.base_url(), None, &[]);
+        service.read_base_url = Some(normalize_base_url(&replica.base_url()).expect("url"));
 
-        let service = QdrantService {
-            client: Client::builder()
-                .user_agent("rusty-mem-test")
-                .build()
-                .expect("client"),
-            base_url: server.base_url(),
-            api_key: None,
-        };
+        service
+            .search_points(
+                "demo",
+                PointQuery {
+                    vector: vec![0.5],
+                    limit: 3,
+                    ..PointQuery::default()
+                },
+            )
+            .await
+            .expect("search");
+        service.list_tags("demo", None).await.expect("scroll");
+        service.count_points("demo", None).await.expect("count");
+        service
+            .delete_points("demo", &["m-1".to_string()])
+            .await
+            .expect("delete");
+        service.collection_info("demo").await.expect("info");
 
-        let results = service
+        replica_query.assert_hits_async(1).await;
+        replica_scroll.assert_hits_async(1).await;
+        replica_count.assert_hits_async(1).await;
+        primary_delete.assert_hits_async(1).await;
+        primary_info.assert_hits_async(1).await;
+    }
+
+    #[tokio::test]
+    async fn copy_reads_and_their_counts_stay_on_the_primary() {
+        let primary = MockServer::start_async().await;
+        let replica = MockServer::start_async().await;
+        let primary_scroll = primary
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/scroll")
+                    .json_body_partial(r#"{ "with_vector": true }"#);
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": { "points": [], "next_page_offset": null }
+                }));
+            })
+            .await;
+        let primary_count = primary
+            .mock_async(|when, then| {
+                when.method(POST).path("/collections/demo/points/count");
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": { "count": 0 } }));
+            })
+            .await;
+        let replica_reads = replica
+            .mock_async(|when, then| {
+                when.method(POST);
+                then.status(200)
+                    .json_body(json!({ "status": "ok", "result": {} }));
+            })
+            .await;
+        let mut service = test_service(primary.base_url(), None, &[]);
+        service.read_base_url = Some(normalize_base_url(&replica.base_url()).expect("url"));
+
+        service
+            .scroll_points_with_vectors_page("demo", None, None, 10)
+            .await
+            .expect("page");
+        service
+            .scroll_points_with_vectors("demo", None)
+            .await
+            .expect("scroll");
+        service
+            .count_primary_points("demo", None)
+            .await
+            .expect("count");
+
+        primary_scroll.assert_hits_async(2).await;
+        primary_count.assert_hits_async(1).await;
+        replica_reads.assert_hits_async(0).await;
+    }
+
+    #[tokio::test]
+    async fn search_points_requests_only_the_projected_payload_fields() {
+        let server = MockServer::start_async().await;
+        let include = json!({ "include": ["tags", "timestamp"] });
+        let plain = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/collections/demo/points/query")
+                    .json_body_partial(json!({ "with_payload": include }).to_string());
+                then.status(200).json_body(json!({
+                    "status": "ok",
+                    "result": { "points": [{ "id": 1, "score": 0.9, "payload": { "tags": ["a"] } }] }
+                }));
+            })
+            .await;
+        let service = test_service(server.base_url(), None, &[]);
+        let fields = ["tags".to_string(), "timestamp".to_string()];
+
+        let hits = service
             .search_points(
                 "demo",
-                vec![0.1, 0.2],
-                Some(filter.clone()),
-                3,
-                Some(0.25),
-                None,
+                PointQuery {
+                    vector: vec![0.5],
+                    limit: 3,
+                    payload_fields: Some(&fields),
+                    ..PointQuery::default()
+                },
             )
             .await
-            .expect("search request");
+            .expect("projected search");
 
-        mock.assert();
+        plain.assert_hits_async(1).await;
+        assert_eq!(
+            hits[0].payload.as_ref().expect("payload").get("tags"),
+            Some(&json!(["a"]))
+        );
+    }
 
-        assert_eq!(results.len(), 1);
-        let hit = &results[0];
-        assert_eq!(hit.id, "memory-1");
-        assert!((hit.score - 0.42).abs() < f32::EPSILON);
-        let payload = hit.payload.as_ref().expect("payload");
-        assert_eq!(payload["project_id"], Value::String("repo-a".into()));
-        assert_eq!(payload["text"], Value::String("Example".into()));
+    #[tokio::test]
+    async fn read_replica_probe_reports_its_own_reachability() {
+        let primary = MockServer::start_async().await;
+        let replica = MockServer::start_async().await;
+        let service = test_service(primary.base_url(), None, &[]);
+        assert!(service.probe_read_replica().await.is_none());
+
+        let listing = replica
+            .mock_async(|when, then| {
+                when.method(GET).path("/collections");
+                then.status(503).body("replica syncing");
+            })
+            .await;
+        let mut service = test_service(primary.base_url(), None, &[]);
+        service.read_base_url = Some(replica.base_url());
+
+        let outcome = service
+            .probe_read_replica()
+            .await
+            .expect("replica configured");
+        assert!(matches!(
+            outcome,
+            Err(QdrantError::UnexpectedStatus { status, .. }) if status.as_u16() == 503
+        ));
+        listing.assert_hits_async(1).await;
+    }
+
+    /// Arbitrary JSON, nested a few levels, including `{ "uuid": ... }` objects.
+    fn arbitrary_json() -> impl Strategy<Value = Value> {
+        let leaf = prop_oneof![
+            Just(Value::Null),
+            any::<bool>().prop_map(Value::Bool),
+            any::<i64>().prop_map(Value::from),
+            any::<u64>().prop_map(Value::from),
+            any::<f64>().prop_map(Value::from),
+            ".*".prop_map(Value::String),
+        ];
+        leaf.prop_recursive(4, 64, 8, |inner| {
+            prop_oneof![
+                prop::collection::vec(inner.clone(), 0..8).prop_map(Value::Array),
+                prop::collection::btree_map(".*", inner.clone(), 0..8)
+                    .prop_map(|map| Value::Object(map.into_iter().collect())),
+                inner.prop_map(|uuid| json!({ "uuid": uuid })),
+            ]
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn stringify_point_id_never_panics_and_is_deterministic(id in arbitrary_json()) {
+            let first = stringify_point_id(id.clone());
+            prop_assert_eq!(first, stringify_point_id(id));
+        }
+
+        #[test]
+        fn stringify_point_id_keeps_string_numeric_and_uuid_ids(
+            text in ".*",
+            number in any::<u64>(),
+            uuid in ".*",
+        ) {
+            prop_assert_eq!(stringify_point_id(Value::String(text.clone())), text);
+            prop_assert_eq!(stringify_point_id(json!(number)), number.to_string());
+            prop_assert_eq!(stringify_point_id(json!({ "uuid": uuid.clone() })), uuid);
+        }
     }
 }
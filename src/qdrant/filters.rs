@@ -1,10 +1,16 @@
 //! Filter helpers for Qdrant search queries and payload accumulation.
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashMap};
 
 use serde_json::{Map, Value, json};
 
-use super::types::SearchFilterArgs;
+use super::{
+    payload::{TIMESTAMP_UNIX_FIELD, unix_seconds},
+    types::{SearchFilterArgs, TagCount, TagCounts, TagOverflow},
+};
+
+/// Distinct tags tracked by [`TagCounter`] before new tags are folded into the overflow bucket.
+pub const MAX_COUNTED_TAGS: usize = 10_000;
 
 /// Compose the standard Qdrant filter payload from optional search arguments.
 pub fn build_search_filter(args: &SearchFilterArgs) -> Option<Value> {
@@ -37,27 +43,108 @@ pub fn build_search_filter(args: &SearchFilterArgs) -> Option<Value> {
         }
     }
 
+    if let Some(ids) = args.include_ids.as_ref() {
+        // Integer point ids must be sent as numbers; every other id is a UUID string.
+        let ids: Vec<Value> = ids
+            .iter()
+            .filter_map(|id| non_empty(id))
+            .map(|id| match id.parse::<u64>() {
+                Ok(number) => Value::from(number),
+                Err(_) => Value::String(id.to_string()),
+            })
+            .collect();
+        if !ids.is_empty() {
+            must.push(json!({ "has_id": ids }));
+        }
+    }
+
     if let Some(range) = args.time_range.as_ref() {
         let mut boundaries = Map::new();
-        if let Some(start) = range.start.as_ref().and_then(|value| non_empty(value)) {
-            boundaries.insert("gte".into(), Value::String(start.to_string()));
-        }
-        if let Some(end) = range.end.as_ref().and_then(|value| non_empty(value)) {
-            boundaries.insert("lte".into(), Value::String(end.to_string()));
+        for (bound, value) in [("gte", &range.start), ("lte", &range.end)] {
+            if let Some(value) = value.as_ref().and_then(|value| non_empty(value)) {
+                boundaries.insert(bound.into(), Value::String(value.to_string()));
+            }
         }
+        let seconds: Map<String, Value> = boundaries
+            .iter()
+            .filter_map(|(bound, value)| {
+                let seconds = value.as_str().and_then(unix_seconds)?;
+                Some((bound.clone(), Value::from(seconds)))
+            })
+            .collect();
+        // A boundary that is not RFC3339 keeps the string form so Qdrant reports it.
+        let (key, boundaries) = if args.unix_time_range && seconds.len() == boundaries.len() {
+            (TIMESTAMP_UNIX_FIELD, seconds)
+        } else {
+            ("timestamp", boundaries)
+        };
         if !boundaries.is_empty() {
-            must.push(json!({
-                "key": "timestamp",
+            let range = json!({
+                "key": key,
                 "range": Value::Object(boundaries)
-            }));
+            });
+            if key == TIMESTAMP_UNIX_FIELD {
+                // Points written before the mirror existed pass here; callers check their
+                // `timestamp` client-side.
+                must.push(json!({ "should": [range, { "is_empty": { "key": key } }] }));
+            } else {
+                must.push(range);
+            }
         }
     }
 
-    if must.is_empty() {
-        None
-    } else {
-        Some(json!({ "must": must }))
+    let mut filter = Map::new();
+    if !must.is_empty() {
+        filter.insert("must".into(), Value::Array(must));
     }
+    if args.require_text {
+        // `is_empty` matches missing, `null`, and `[]` values, so negating it keeps stored text.
+        filter.insert(
+            "must_not".into(),
+            json!([{ "is_empty": { "key": "text" } }]),
+        );
+    }
+    (!filter.is_empty()).then_some(Value::Object(filter))
+}
+
+/// Filter selecting `memory_type` points (optionally of one project) stamped before `cutoff`.
+///
+/// The `lt` bound is strict, so a memory written exactly at the cutoff is kept. Points without a
+/// `timestamp` never match a range condition and are therefore never pruned. With
+/// `unix_time_range` (servers without a datetime index) the bound is placed on the integer
+/// `timestamp_unix` mirror instead; points written before the mirror are selected by
+/// [`build_unmirrored_prune_filter`].
+pub fn build_prune_filter(
+    project_id: Option<&str>,
+    memory_type: &str,
+    cutoff: &str,
+    unix_time_range: bool,
+) -> Value {
+    let mut must = prune_scope(project_id, memory_type);
+    match unix_seconds(cutoff).filter(|_| unix_time_range) {
+        Some(seconds) => {
+            must.push(json!({ "key": TIMESTAMP_UNIX_FIELD, "range": { "lt": seconds } }));
+        }
+        None => must.push(json!({ "key": "timestamp", "range": { "lt": cutoff } })),
+    }
+    json!({ "must": must })
+}
+
+/// Filter selecting the `memory_type` points a unix-time prune cannot bound: those missing the
+/// `timestamp_unix` mirror. Callers compare their `timestamp` to the cutoff client-side.
+pub fn build_unmirrored_prune_filter(project_id: Option<&str>, memory_type: &str) -> Value {
+    let mut must = prune_scope(project_id, memory_type);
+    must.push(json!({ "is_empty": { "key": TIMESTAMP_UNIX_FIELD } }));
+    json!({ "must": must })
+}
+
+fn prune_scope(project_id: Option<&str>, memory_type: &str) -> Vec<Value> {
+    let mut must = Vec::new();
+    if let Some(project_id) = project_id.and_then(non_empty) {
+        must.push(json!({ "key": "project_id", "match": { "value": project_id } }));
+    }
+    must.push(json!({ "key": "memory_type", "match": { "value": memory_type } }));
+    must
 }
 
 fn non_empty(input: &str) -> Option<&str> {
@@ -81,13 +168,88 @@ pub fn accumulate_project_id(payload: &Map<String, Value>, projects: &mut BTreeS
 
 /// Accumulate tag values from Qdrant payloads.
 pub fn accumulate_tags(payload: &Map<String, Value>, tags: &mut BTreeSet<String>) {
+    visit_tags(payload, |tag| {
+        tags.insert(tag.to_string());
+    });
+}
+
+/// Per-tag occurrence counter with bounded memory.
+///
+/// Each payload increments a tag at most once, split by its `memory_type`. At most `cap` distinct
+/// tags are tracked; occurrences of tags first seen after that land in a single overflow bucket,
+/// so a collection with runaway tag cardinality cannot exhaust memory.
+#[derive(Debug)]
+pub struct TagCounter {
+    cap: usize,
+    tallies: HashMap<String, TagOverflow>,
+    overflow: TagOverflow,
+}
+
+impl TagCounter {
+    /// Counter tracking at most `cap` distinct tags.
+    pub fn new(cap: usize) -> Self {
+        Self {
+            cap,
+            tallies: HashMap::new(),
+            overflow: TagOverflow::default(),
+        }
+    }
+
+    /// Count the tags of one payload.
+    pub fn record(&mut self, payload: &Map<String, Value>) {
+        // Dedupe within a payload so a repeated tag on one point does not inflate its popularity.
+        let mut seen = BTreeSet::new();
+        accumulate_tags(payload, &mut seen);
+        let memory_type = payload
+            .get("memory_type")
+            .and_then(Value::as_str)
+            .and_then(non_empty);
+        for tag in seen {
+            let tally = if self.tallies.contains_key(&tag) || self.tallies.len() < self.cap {
+                self.tallies.entry(tag).or_default()
+            } else {
+                &mut self.overflow
+            };
+            tally.count += 1;
+            if let Some(memory_type) = memory_type {
+                *tally
+                    .memory_types
+                    .entry(memory_type.to_string())
+                    .or_insert(0) += 1;
+            }
+        }
+    }
+
+    /// Tags ordered by descending count then name, plus the overflow bucket when it was used.
+    pub fn finish(self) -> TagCounts {
+        let mut tags: Vec<TagCount> = self
+            .tallies
+            .into_iter()
+            .map(|(tag, tally)| TagCount {
+                tag,
+                count: tally.count,
+                memory_types: tally.memory_types,
+            })
+            .collect();
+        tags.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.tag.cmp(&b.tag)));
+        TagCounts {
+            tags,
+            overflow: (self.overflow.count > 0).then_some(self.overflow),
+        }
+    }
+}
+
+fn visit_tags<F>(payload: &Map<String, Value>, mut visit: F)
+where
+    F: FnMut(&str),
+{
     match payload.get("tags") {
         Some(Value::Array(values)) => {
             for value in values {
                 if let Value::String(tag) = value {
                     let trimmed = tag.trim();
                     if !trimmed.is_empty() {
-                        tags.insert(trimmed.to_string());
+                        visit(trimmed);
                     }
                 }
             }
@@ -95,7 +257,7 @@ pub fn accumulate_tags(payload: &Map<String, Value>, tags: &mut BTreeSet<String>
         Some(Value::String(tag)) => {
             let trimmed = tag.trim();
             if !trimmed.is_empty() {
-                tags.insert(trimmed.to_string());
+                visit(trimmed);
             }
         }
         _ => {}
@@ -128,6 +290,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn build_search_filter_requires_text_with_must_not_is_empty() {
+        let filter = build_search_filter(&SearchFilterArgs {
+            require_text: true,
+            ..Default::default()
+        })
+        .expect("filter");
+
+        assert_eq!(
+            filter,
+            json!({ "must_not": [{ "is_empty": { "key": "text" } }] })
+        );
+    }
+
     #[test]
     fn build_search_filter_handles_tags() {
         let filter = build_search_filter(&SearchFilterArgs {
@@ -149,6 +325,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn build_search_filter_restricts_to_included_ids() {
+        let filter = build_search_filter(&SearchFilterArgs {
+            project_id: Some("repo-a".into()),
+            include_ids: Some(vec![
+                "6f9619ff-8b86-d011-b42d-00cf4fc964ff".into(),
+                "42".into(),
+                " ".into(),
+            ]),
+            ..Default::default()
+        })
+        .expect("filter");
+
+        assert_eq!(
+            filter["must"][1],
+            json!({ "has_id": ["6f9619ff-8b86-d011-b42d-00cf4fc964ff", 42] })
+        );
+        assert!(
+            build_search_filter(&SearchFilterArgs {
+                include_ids: Some(Vec::new()),
+                ..Default::default()
+            })
+            .is_none()
+        );
+    }
+
     #[test]
     fn build_search_filter_handles_time_range() {
         let filter = build_search_filter(&SearchFilterArgs {
@@ -176,11 +378,92 @@ mod tests {
         );
     }
 
+    #[test]
+    fn build_search_filter_expresses_unix_time_range_in_seconds() {
+        let range = SearchTimeRange {
+            start: Some("2025-01-01T00:00:00Z".into()),
+            end: Some("2025-12-31T23:59:59.999Z".into()),
+        };
+        let filter = build_search_filter(&SearchFilterArgs {
+            time_range: Some(range.clone()),
+            unix_time_range: true,
+            ..Default::default()
+        })
+        .expect("filter");
+        assert_eq!(
+            filter,
+            json!({
+                "must": [
+                    {
+                        "should": [
+                            {
+                                "key": "timestamp_unix",
+                                "range": { "gte": 1_735_689_600, "lte": 1_767_225_599 }
+                            },
+                            { "is_empty": { "key": "timestamp_unix" } }
+                        ]
+                    }
+                ]
+            })
+        );
+
+        let filter = build_search_filter(&SearchFilterArgs {
+            time_range: Some(SearchTimeRange {
+                end: Some("last tuesday".into()),
+                ..range
+            }),
+            unix_time_range: true,
+            ..Default::default()
+        })
+        .expect("filter");
+        assert_eq!(filter["must"][0]["key"], "timestamp");
+        assert_eq!(filter["must"][0]["range"]["lte"], "last tuesday");
+    }
+
     #[test]
     fn build_search_filter_returns_none_when_empty() {
         assert!(build_search_filter(&SearchFilterArgs::default()).is_none());
     }
 
+    #[test]
+    fn build_prune_filter_bounds_timestamp_strictly_before_cutoff() {
+        let filter = build_prune_filter(Some("alpha"), "episodic", "2025-03-01T00:00:00Z", false);
+        assert_eq!(
+            filter,
+            json!({
+                "must": [
+                    { "key": "project_id", "match": { "value": "alpha" } },
+                    { "key": "memory_type", "match": { "value": "episodic" } },
+                    { "key": "timestamp", "range": { "lt": "2025-03-01T00:00:00Z" } }
+                ]
+            })
+        );
+
+        let filter = build_prune_filter(Some("  "), "semantic", "2025-03-01T00:00:00Z", false);
+        assert_eq!(filter["must"].as_array().map(Vec::len), Some(2));
+        assert_eq!(filter["must"][0]["key"], "memory_type");
+    }
+
+    #[test]
+    fn unix_prune_filters_bound_the_mirror_and_select_unmirrored_points_separately() {
+        let filter = build_prune_filter(Some("alpha"), "episodic", "2025-03-01T00:00:00Z", true);
+        assert_eq!(
+            filter["must"][2],
+            json!({ "key": "timestamp_unix", "range": { "lt": 1_740_787_200 } })
+        );
+
+        assert_eq!(
+            build_unmirrored_prune_filter(Some("alpha"), "episodic"),
+            json!({
+                "must": [
+                    { "key": "project_id", "match": { "value": "alpha" } },
+                    { "key": "memory_type", "match": { "value": "episodic" } },
+                    { "is_empty": { "key": "timestamp_unix" } }
+                ]
+            })
+        );
+    }
+
     #[test]
     fn accumulate_project_ignores_empty() {
         let mut map = Map::new();
@@ -218,4 +501,56 @@ mod tests {
             vec![&"alpha".to_string(), &"beta".to_string()]
         );
     }
+
+    fn tagged(tags: Value, memory_type: &str) -> Map<String, Value> {
+        let mut payload = Map::new();
+        payload.insert("tags".into(), tags);
+        payload.insert("memory_type".into(), Value::String(memory_type.into()));
+        payload
+    }
+
+    #[test]
+    fn tag_counter_increments_once_per_payload_by_memory_type() {
+        let mut counter = TagCounter::new(MAX_COUNTED_TAGS);
+        counter.record(&tagged(json!(["alpha", "beta", "alpha"]), "episodic"));
+        counter.record(&tagged(Value::String("alpha".into()), "semantic"));
+        counter.record(&tagged(json!(["beta"]), "episodic"));
+
+        let counts = counter.finish();
+        assert_eq!(counts.overflow, None);
+        assert_eq!(
+            counts.tags,
+            vec![
+                TagCount {
+                    tag: "alpha".into(),
+                    count: 2,
+                    memory_types: [("episodic".into(), 1), ("semantic".into(), 1)].into(),
+                },
+                TagCount {
+                    tag: "beta".into(),
+                    count: 2,
+                    memory_types: [("episodic".into(), 2)].into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn tag_counter_folds_tags_past_the_cap_into_overflow() {
+        let mut counter = TagCounter::new(2);
+        counter.record(&tagged(json!(["alpha", "beta"]), "semantic"));
+        counter.record(&tagged(json!(["gamma", "alpha"]), "episodic"));
+        counter.record(&tagged(json!(["delta", "gamma"]), "episodic"));
+
+        let counts = counter.finish();
+        let tags: Vec<(&str, usize)> = counts
+            .tags
+            .iter()
+            .map(|entry| (entry.tag.as_str(), entry.count))
+            .collect();
+        assert_eq!(tags, [("alpha", 2), ("beta", 1)]);
+        let overflow = counts.overflow.expect("overflow bucket");
+        assert_eq!(overflow.count, 3);
+        assert_eq!(overflow.memory_types.get("episodic"), Some(&3));
+    }
 }
@@ -1,14 +1,26 @@
 //! Qdrant vector store integration.
 
+pub mod capabilities;
 pub mod client;
 pub mod filters;
 pub mod payload;
+pub mod scores;
 pub mod types;
 
-pub use client::QdrantService;
-pub use filters::{accumulate_project_id, accumulate_tags, build_search_filter};
-pub use payload::compute_chunk_hash;
+pub use capabilities::{QdrantCapabilities, QdrantVersion};
+pub use client::{
+    DEFAULT_DISTANCE, PAYLOAD_INDEX_SCHEMAS, QdrantService, VECTOR_DISTANCES,
+    project_collection_name, validate_collection_name,
+};
+pub use filters::{
+    MAX_COUNTED_TAGS, TagCounter, accumulate_project_id, accumulate_tags, build_prune_filter,
+    build_search_filter, build_unmirrored_prune_filter,
+};
+pub use payload::{TIMESTAMP_UNIX_FIELD, compute_chunk_hash, unix_seconds};
+pub use scores::{normalize_score, raw_score_threshold};
 pub use types::{
-    IndexSummary, PayloadOverrides, PointInsert, QdrantError, ScoredPoint, SearchFilterArgs,
-    SearchTimeRange,
+    Citation, CollectionInfo, CollectionSchema, CollectionTopology, IndexSummary, LinkRelation,
+    MemoryLink, PayloadIndexFailure, PayloadIndexSummary, PayloadOverrides, PointInsert,
+    PointQuery, QdrantError, QuantizationSearchParams, ScoredPoint, SearchFilterArgs, SearchParams,
+    SearchTimeRange, StoredPoint, TagCount, TagCounts, TagOverflow, TimestampOrder,
 };
@@ -1,16 +1,21 @@
 //! Helpers for constructing and hashing Qdrant payloads.
 
 use crate::qdrant::types::PayloadOverrides;
-use serde_json::{Map, Value};
+use serde_json::{Map, Value, json};
 use sha2::{Digest, Sha256};
 use time::OffsetDateTime;
 use uuid::Uuid;
 
 /// Build the payload object stored alongside each indexed chunk.
+///
+/// `timestamp` takes the override when present and `ingested_at` always records
+/// `ingested_at_rfc3339`, so backdated memories stay distinguishable from fresh writes.
+/// `timestamp_unix` mirrors `timestamp` in whole seconds for integer `order_by` and range
+/// filters; it is omitted when `timestamp` is not RFC3339.
 pub(crate) fn build_payload(
     memory_id: &str,
     text: &str,
-    timestamp_rfc3339: &str,
+    ingested_at_rfc3339: &str,
     chunk_hash: &str,
     overrides: &PayloadOverrides,
 ) -> Value {
@@ -34,9 +39,17 @@ pub(crate) fn build_payload(
                 .unwrap_or_else(default_memory_type),
         ),
     );
+    let timestamp = overrides
+        .timestamp
+        .clone()
+        .unwrap_or_else(|| ingested_at_rfc3339.to_string());
+    if let Some(seconds) = unix_seconds(&timestamp) {
+        payload.insert(TIMESTAMP_UNIX_FIELD.into(), Value::from(seconds));
+    }
+    payload.insert("timestamp".into(), Value::String(timestamp));
     payload.insert(
-        "timestamp".into(),
-        Value::String(timestamp_rfc3339.to_string()),
+        "ingested_at".into(),
+        Value::String(ingested_at_rfc3339.to_string()),
     );
     payload.insert("chunk_hash".into(), Value::String(chunk_hash.to_string()));
     payload.insert("text".into(), Value::String(text.to_string()));
@@ -49,6 +62,10 @@ pub(crate) fn build_payload(
         payload.insert("source_uri".into(), Value::String(source_uri.clone()));
     }
 
+    if let Some(doc_id) = overrides.doc_id.as_ref() {
+        payload.insert("doc_id".into(), Value::String(doc_id.clone()));
+    }
+
     if let Some(tags) = overrides.tags.as_ref().filter(|tags| !tags.is_empty()) {
         payload.insert(
             "tags".into(),
@@ -75,6 +92,27 @@ pub(crate) fn build_payload(
         payload.insert("summary_key".into(), Value::String(key.clone()));
     }
 
+    for (field, value) in [("label", &overrides.label), ("topic", &overrides.topic)] {
+        if let Some(value) = value.as_ref().filter(|value| !value.trim().is_empty()) {
+            payload.insert(field.into(), Value::String(value.clone()));
+        }
+    }
+
+    if let Some(citations) = overrides
+        .citations
+        .as_ref()
+        .filter(|citations| !citations.is_empty())
+    {
+        payload.insert("citations".into(), json!(citations));
+    }
+
+    if let Some(hash) = overrides.profile_hash.as_ref() {
+        payload.insert("profile_hash".into(), Value::String(hash.clone()));
+    }
+    if let Some(profile) = overrides.ingest_profile.as_ref() {
+        payload.insert("ingest_profile".into(), Value::String(profile.clone()));
+    }
+
     Value::Object(payload)
 }
 
@@ -86,6 +124,19 @@ pub fn compute_chunk_hash(text: &str) -> String {
     hex::encode(digest)
 }
 
+/// Payload field holding `timestamp` as whole seconds since the Unix epoch.
+pub const TIMESTAMP_UNIX_FIELD: &str = "timestamp_unix";
+
+/// Whole seconds since the Unix epoch for an RFC3339 timestamp, rounded down.
+pub fn unix_seconds(rfc3339: &str) -> Option<i64> {
+    OffsetDateTime::parse(
+        rfc3339.trim(),
+        &time::format_description::well_known::Rfc3339,
+    )
+    .ok()
+    .map(OffsetDateTime::unix_timestamp)
+}
+
 /// Current timestamp formatted for payload storage.
 pub(crate) fn current_timestamp_rfc3339() -> String {
     OffsetDateTime::now_utc()
@@ -109,6 +160,7 @@ pub(crate) fn generate_memory_id() -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::qdrant::types::Citation;
 
     #[test]
     fn chunk_hash_is_stable() {
@@ -135,6 +187,7 @@ mod tests {
         assert_eq!(payload["project_id"], "default");
         assert_eq!(payload["memory_type"], "semantic");
         assert_eq!(payload["timestamp"], now);
+        assert_eq!(payload["ingested_at"], now);
         assert_eq!(payload["text"], "sample");
         assert_eq!(payload["chunk_hash"], chunk_hash);
     }
@@ -158,5 +211,49 @@ mod tests {
         assert_eq!(tags.len(), 2);
         assert!(tags.iter().any(|tag| tag == "alpha"));
         assert!(tags.iter().any(|tag| tag == "beta"));
+        assert!(payload.get("citations").is_none());
+        assert!(payload.get("profile_hash").is_none());
+    }
+
+    #[test]
+    fn payload_timestamp_override_keeps_ingest_time_separately() {
+        let overrides = PayloadOverrides {
+            timestamp: Some("2019-03-04T08:00:00Z".into()),
+            ..Default::default()
+        };
+        let payload = build_payload("id", "old note", "2025-01-01T00:00:00Z", "hash", &overrides);
+        assert_eq!(payload["timestamp"], "2019-03-04T08:00:00Z");
+        assert_eq!(payload["timestamp_unix"], 1_551_686_400);
+        assert_eq!(payload["ingested_at"], "2025-01-01T00:00:00Z");
+    }
+
+    #[test]
+    fn unix_seconds_round_down_and_reject_non_rfc3339() {
+        assert_eq!(unix_seconds("2025-01-01T00:00:00Z"), Some(1_735_689_600));
+        assert_eq!(
+            unix_seconds("2025-01-01T02:00:00.9+02:00"),
+            Some(1_735_689_600)
+        );
+        assert_eq!(unix_seconds("1969-12-31T23:59:59.5Z"), Some(-1));
+        assert_eq!(unix_seconds("yesterday"), None);
+
+        let payload = build_payload("id", "note", "now", "hash", &PayloadOverrides::default());
+        assert!(payload.get("timestamp_unix").is_none());
+    }
+
+    #[test]
+    fn payload_stores_citation_map() {
+        let overrides = PayloadOverrides {
+            citations: Some(vec![Citation {
+                marker: 2,
+                memory_id: "ep-2".into(),
+            }]),
+            ..Default::default()
+        };
+        let payload = build_payload("id", "summary [2]", "now", "hash", &overrides);
+        assert_eq!(
+            payload["citations"],
+            json!([{ "marker": 2, "memory_id": "ep-2" }])
+        );
     }
 }
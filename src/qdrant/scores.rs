@@ -0,0 +1,82 @@
+//! Mapping between raw Qdrant scores and a metric-independent `0..1` scale.
+//!
+//! Qdrant reports cosine similarity in `-1..1`, unbounded dot products, and Euclid/Manhattan
+//! distances where lower is closer. [`normalize_score`] maps each onto `0..1` with higher meaning
+//! closer, and [`raw_score_threshold`] inverts the mapping so a normalized threshold can still be
+//! enforced by Qdrant.
+
+/// Map a raw score reported for a collection using `distance` into `0..1`.
+///
+/// Unknown metrics leave the score untouched.
+pub fn normalize_score(distance: &str, raw: f32) -> f32 {
+    match distance {
+        "Cosine" => raw.clamp(0.0, 1.0),
+        "Dot" => 1.0 / (1.0 + (-raw).exp()),
+        "Euclid" | "Manhattan" => 1.0 / (1.0 + raw.max(0.0)),
+        _ => raw,
+    }
+}
+
+/// Convert a normalized `0..1` threshold into the raw score Qdrant compares against.
+///
+/// Returns `None` when the threshold admits every point (a normalized `0` for `Dot`, `Euclid`,
+/// and `Manhattan`), since their raw ranges have no finite bound there.
+pub fn raw_score_threshold(distance: &str, normalized: f32) -> Option<f32> {
+    let normalized = normalized.clamp(0.0, 1.0);
+    match distance {
+        "Dot" if normalized <= 0.0 => None,
+        "Dot" if normalized >= 1.0 => Some(f32::MAX),
+        "Dot" => Some((normalized / (1.0 - normalized)).ln()),
+        "Euclid" | "Manhattan" if normalized <= 0.0 => None,
+        "Euclid" | "Manhattan" => Some(1.0 / normalized - 1.0),
+        _ => Some(normalized),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cosine_is_identity_within_unit_range() {
+        assert_eq!(normalize_score("Cosine", 0.75), 0.75);
+        assert_eq!(normalize_score("Cosine", 1.0), 1.0);
+        assert_eq!(normalize_score("Cosine", -0.5), 0.0);
+        assert_eq!(raw_score_threshold("Cosine", 0.25), Some(0.25));
+    }
+
+    #[test]
+    fn dot_uses_sigmoid() {
+        assert_eq!(normalize_score("Dot", 0.0), 0.5);
+        assert!(normalize_score("Dot", 10.0) > 0.99);
+        assert!(normalize_score("Dot", -10.0) < 0.01);
+        assert!(normalize_score("Dot", 2.0) > normalize_score("Dot", 1.0));
+
+        assert_eq!(raw_score_threshold("Dot", 0.5), Some(0.0));
+        assert_eq!(raw_score_threshold("Dot", 0.0), None);
+        assert_eq!(raw_score_threshold("Dot", 1.0), Some(f32::MAX));
+        let raw = raw_score_threshold("Dot", 0.75).expect("finite threshold");
+        assert!((normalize_score("Dot", raw) - 0.75).abs() < 1e-6);
+    }
+
+    #[test]
+    fn euclid_and_manhattan_invert_distance() {
+        for metric in ["Euclid", "Manhattan"] {
+            assert_eq!(normalize_score(metric, 0.0), 1.0);
+            assert_eq!(normalize_score(metric, 1.0), 0.5);
+            assert_eq!(normalize_score(metric, 3.0), 0.25);
+            assert!(normalize_score(metric, 1.0) > normalize_score(metric, 2.0));
+
+            assert_eq!(raw_score_threshold(metric, 0.5), Some(1.0));
+            assert_eq!(raw_score_threshold(metric, 0.25), Some(3.0));
+            assert_eq!(raw_score_threshold(metric, 1.0), Some(0.0));
+            assert_eq!(raw_score_threshold(metric, 0.0), None);
+        }
+    }
+
+    #[test]
+    fn unknown_metric_passes_scores_through() {
+        assert_eq!(normalize_score("Hamming", 7.5), 7.5);
+        assert_eq!(raw_score_threshold("Hamming", 0.25), Some(0.25));
+    }
+}
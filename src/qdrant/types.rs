@@ -1,8 +1,13 @@
 //! Shared types used by the Qdrant client and helpers.
+//!
+//! Response types never use `deny_unknown_fields`: Qdrant adds fields between releases, and a
+//! response must keep parsing when it does. Collections that older or newer servers send as
+//! `null` (or omit) deserialize as empty through [`null_as_default`].
 
 use reqwest::StatusCode;
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::{Map, Value};
+use std::collections::BTreeMap;
 use thiserror::Error;
 
 /// Errors returned while interacting with Qdrant.
@@ -39,6 +44,50 @@ pub struct PayloadOverrides {
     pub source_memory_ids: Option<Vec<String>>,
     /// Optional idempotency key for summaries.
     pub summary_key: Option<String>,
+    /// Human-friendly summary label, stored as the indexed `label` field.
+    pub label: Option<String>,
+    /// Summary topic, stored as the indexed `topic` field.
+    pub topic: Option<String>,
+    /// Optional citation map resolving summary markers to source memories.
+    pub citations: Option<Vec<Citation>>,
+    /// Override for the `timestamp` field; `ingested_at` still records the actual write time.
+    pub timestamp: Option<String>,
+    /// Fingerprint of the ingest settings, stored as the indexed `profile_hash` field.
+    pub profile_hash: Option<String>,
+    /// Compact description of the ingest settings, stored as `ingest_profile`.
+    pub ingest_profile: Option<String>,
+    /// Identifier shared by every chunk of one pushed document, stored as `doc_id`.
+    pub doc_id: Option<String>,
+}
+
+/// Inline `[n]` marker in a summary resolved to the memory it cites.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Citation {
+    /// 1-based marker number as it appears in the summary text.
+    pub marker: usize,
+    /// Identifier of the cited source memory.
+    pub memory_id: String,
+}
+
+/// Kind of directed relation one memory records toward another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkRelation {
+    /// The source replaces the target (for example a revised decision).
+    Supersedes,
+    /// The source adds detail to the target.
+    Refines,
+    /// The memories are related without either replacing the other.
+    RelatesTo,
+}
+
+/// Outbound link stored in a memory's `links` payload.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryLink {
+    /// Relation from the linking memory to the target.
+    pub rel: LinkRelation,
+    /// Identifier of the linked memory.
+    pub target_memory_id: String,
 }
 
 /// Prepared point ready for indexing, including text, hash, and vector.
@@ -50,6 +99,29 @@ pub struct PointInsert {
     pub chunk_hash: String,
     /// Embedding vector produced for the chunk.
     pub vector: Vec<f32>,
+    /// Per-chunk `memory_type` that takes precedence over the request-level override.
+    pub memory_type: Option<String>,
+    /// Composite chunk quality score stored as `quality_score` for later analysis.
+    pub quality_score: Option<f32>,
+    /// Position of the chunk within its document, stored as `chunk_index`.
+    pub chunk_index: Option<usize>,
+    /// Kind of content the chunk holds (e.g. `code_block`), stored as `content_kind`.
+    pub content_kind: Option<String>,
+    /// Programming language of a code chunk, stored as `language`.
+    pub language: Option<String>,
+    /// First definition in a code chunk, stored as `symbol`.
+    pub symbol: Option<String>,
+}
+
+/// Point read back with its identifier, vector(s), and payload, ready to be written elsewhere.
+#[derive(Debug, Clone)]
+pub struct StoredPoint {
+    /// Identifier assigned to the point.
+    pub id: String,
+    /// Vector exactly as Qdrant returned it (a list, or an object of named vectors).
+    pub vector: Value,
+    /// Payload stored with the point.
+    pub payload: Map<String, Value>,
 }
 
 /// Filters that can be applied to Qdrant search queries.
@@ -61,8 +133,16 @@ pub struct SearchFilterArgs {
     pub memory_type: Option<String>,
     /// Contains-any constraint for the `tags` payload field.
     pub tags: Option<Vec<String>>,
+    /// Point ids the match is restricted to, via a `has_id` condition.
+    pub include_ids: Option<Vec<String>>,
     /// Timestamp boundaries applied to the `timestamp` payload field.
     pub time_range: Option<SearchTimeRange>,
+    /// Apply `time_range` to the integer `timestamp_unix` field in whole seconds instead, for
+    /// servers without datetime range filters. Points lacking that field always match and must
+    /// be checked against `timestamp` by the caller.
+    pub unix_time_range: bool,
+    /// Only match points whose `text` payload field is present and non-null.
+    pub require_text: bool,
 }
 
 /// Inclusive timestamp boundaries expressed in RFC3339.
@@ -85,6 +165,195 @@ pub struct ScoredPoint {
     pub payload: Option<Map<String, Value>>,
 }
 
+/// Tag observed in a collection along with the number of payloads carrying it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagCount {
+    /// Tag value as stored in the payload.
+    pub tag: String,
+    /// Number of points whose payload includes the tag.
+    pub count: usize,
+    /// `count` broken down by the payload's `memory_type`.
+    pub memory_types: BTreeMap<String, usize>,
+}
+
+/// Result of counting tags over a scroll.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TagCounts {
+    /// Tracked tags ordered by descending count, then name.
+    pub tags: Vec<TagCount>,
+    /// Occurrences of tags first seen after the tracking cap was reached.
+    pub overflow: Option<TagOverflow>,
+}
+
+/// Occurrences folded together once the number of distinct tags hit the cap.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TagOverflow {
+    /// Tag occurrences not attributed to a tracked tag.
+    pub count: usize,
+    /// `count` broken down by the payload's `memory_type`.
+    pub memory_types: BTreeMap<String, usize>,
+}
+
+/// Collection status and indexing progress reported by `GET /collections/{name}`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct CollectionInfo {
+    /// Optimizer health (`green` when idle, `yellow` while optimizing/indexing).
+    #[serde(default)]
+    pub status: Option<String>,
+    /// Total number of points stored in the collection.
+    #[serde(default)]
+    pub points_count: Option<u64>,
+    /// Number of vectors already covered by the HNSW index.
+    #[serde(default)]
+    pub indexed_vectors_count: Option<u64>,
+    /// Segments the collection is currently stored in; drops as the optimizer merges them.
+    #[serde(default)]
+    pub segments_count: Option<u64>,
+}
+
+impl CollectionInfo {
+    /// Whether Qdrant reports no pending optimizations.
+    ///
+    /// Segments below Qdrant's indexing threshold are never HNSW-indexed, so `green` rather than
+    /// equal counts is the reliable completion signal.
+    pub fn is_ready(&self) -> bool {
+        self.status.as_deref() == Some("green")
+    }
+
+    /// Points not yet covered by the vector index.
+    pub fn unindexed_backlog(&self) -> u64 {
+        self.points_count
+            .unwrap_or(0)
+            .saturating_sub(self.indexed_vectors_count.unwrap_or(0))
+    }
+}
+
+/// Vector parameters and payload indexes reported by `GET /collections/{name}`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CollectionSchema {
+    /// Status and indexing counters.
+    pub info: CollectionInfo,
+    /// Vector dimension (the first named vector when the collection has several).
+    pub vector_size: Option<u64>,
+    /// Distance metric paired with `vector_size`.
+    pub distance: Option<String>,
+    /// Element type of the vector (`float32`, `float16`, `uint8`); Qdrant omits it for `float32`.
+    pub vector_datatype: Option<String>,
+    /// Payload fields with an index, mapped to the index data type.
+    pub payload_indexes: BTreeMap<String, String>,
+    /// Shard and replication settings, when Qdrant reports them.
+    pub topology: CollectionTopology,
+}
+
+/// Cluster placement settings sent when creating a collection.
+///
+/// Unset fields are omitted from the request so single-node deployments keep Qdrant's defaults.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CollectionTopology {
+    /// Number of shards the collection is split into.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_number: Option<u32>,
+    /// Number of copies kept of each shard.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replication_factor: Option<u32>,
+}
+
+impl CollectionTopology {
+    /// Defaults configured through `QDRANT_SHARD_NUMBER` and `QDRANT_REPLICATION_FACTOR`.
+    pub fn from_config() -> Self {
+        let config = crate::config::get_config();
+        Self {
+            shard_number: config.qdrant_shard_number,
+            replication_factor: config.qdrant_replication_factor,
+        }
+    }
+
+    /// Fill fields left unset here from `defaults`.
+    pub fn or(self, defaults: Self) -> Self {
+        Self {
+            shard_number: self.shard_number.or(defaults.shard_number),
+            replication_factor: self.replication_factor.or(defaults.replication_factor),
+        }
+    }
+}
+
+/// Nearest-neighbour query sent by [`crate::qdrant::QdrantService::search_points`].
+#[derive(Debug, Clone, Default)]
+pub struct PointQuery<'a> {
+    /// Query vector.
+    pub vector: Vec<f32>,
+    /// Qdrant filter restricting the candidates.
+    pub filter: Option<Value>,
+    /// Maximum number of hits returned.
+    pub limit: usize,
+    /// Raw similarity floor, in the collection's native score scale.
+    pub score_threshold: Option<f32>,
+    /// Named vector to search; blank names target the default vector.
+    pub using: Option<String>,
+    /// Per-query HNSW and quantization tuning.
+    pub params: Option<&'a SearchParams>,
+    /// Return the newest (or oldest) of the nearest candidates instead of the nearest.
+    pub order: Option<TimestampOrder>,
+    /// Payload keys to return; all keys when unset.
+    pub payload_fields: Option<&'a [String]>,
+}
+
+/// Direction in which search hits are ordered by their `timestamp` payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimestampOrder {
+    /// Newest first.
+    Descending,
+    /// Oldest first.
+    Ascending,
+}
+
+impl TimestampOrder {
+    /// Qdrant `order_by` direction.
+    pub fn direction(self) -> &'static str {
+        match self {
+            Self::Descending => "desc",
+            Self::Ascending => "asc",
+        }
+    }
+}
+
+/// Per-query HNSW and quantization tuning sent as the Qdrant query `params` object.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SearchParams {
+    /// HNSW beam width; larger values trade latency for recall.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hnsw_ef: Option<u64>,
+    /// Rescoring controls for quantized collections.
+    #[serde(default, skip_serializing_if = "QuantizationSearchParams::is_unset")]
+    pub quantization: Option<QuantizationSearchParams>,
+}
+
+impl SearchParams {
+    /// Whether no tuning knob is set, in which case `params` is omitted from the query.
+    pub fn is_empty(&self) -> bool {
+        self.hnsw_ef.is_none() && QuantizationSearchParams::is_unset(&self.quantization)
+    }
+}
+
+/// Quantization knobs compensating for the recall lost to compressed vectors.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QuantizationSearchParams {
+    /// Re-score the candidates with the original vectors.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rescore: Option<bool>,
+    /// Fetch `limit * oversampling` quantized candidates before rescoring (must be >= 1.0).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oversampling: Option<f64>,
+}
+
+impl QuantizationSearchParams {
+    fn is_unset(value: &Option<Self>) -> bool {
+        value
+            .as_ref()
+            .is_none_or(|params| params.rescore.is_none() && params.oversampling.is_none())
+    }
+}
+
 /// Summary describing how Qdrant applied an indexing request.
 #[derive(Debug, Clone, Copy, Default)]
 pub struct IndexSummary {
@@ -94,6 +363,120 @@ pub struct IndexSummary {
     pub updated: usize,
 }
 
+/// Per-field outcome of ensuring the standard payload indexes on a collection.
+#[derive(Debug, Default)]
+pub struct PayloadIndexSummary {
+    /// Fields whose index was created or already existed.
+    pub ensured: Vec<&'static str>,
+    /// Fields skipped because this Qdrant version cannot index their schema.
+    pub skipped: Vec<&'static str>,
+    /// Fields Qdrant refused to index.
+    pub failed: Vec<PayloadIndexFailure>,
+}
+
+/// Payload index Qdrant refused to create.
+#[derive(Debug)]
+pub struct PayloadIndexFailure {
+    /// Payload field that should have been indexed.
+    pub field: &'static str,
+    /// Index schema requested for the field.
+    pub schema: &'static str,
+    /// Response Qdrant returned.
+    pub error: QdrantError,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct CountResponse {
+    pub(crate) result: CountResult,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct CountResult {
+    pub(crate) count: u64,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct CollectionInfoResponse {
+    pub(crate) result: CollectionInfo,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct CollectionSchemaResponse {
+    pub(crate) result: CollectionSchemaResult,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct CollectionSchemaResult {
+    #[serde(flatten)]
+    pub(crate) info: CollectionInfo,
+    #[serde(default)]
+    pub(crate) config: Option<CollectionConfig>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub(crate) payload_schema: BTreeMap<String, PayloadIndexInfo>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct CollectionConfig {
+    #[serde(default)]
+    pub(crate) params: Option<CollectionParams>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct CollectionParams {
+    #[serde(default)]
+    pub(crate) vectors: Option<VectorsConfig>,
+    #[serde(flatten)]
+    pub(crate) topology: CollectionTopology,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub(crate) enum VectorsConfig {
+    Single(VectorParams),
+    Named(BTreeMap<String, VectorParams>),
+}
+
+#[derive(Deserialize)]
+pub(crate) struct VectorParams {
+    pub(crate) size: u64,
+    #[serde(default)]
+    pub(crate) distance: Option<String>,
+    #[serde(default)]
+    pub(crate) datatype: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct PayloadIndexInfo {
+    #[serde(default)]
+    pub(crate) data_type: Option<String>,
+}
+
+impl From<CollectionSchemaResult> for CollectionSchema {
+    fn from(result: CollectionSchemaResult) -> Self {
+        let (vectors, topology) = match result.config.and_then(|config| config.params) {
+            Some(params) => (params.vectors, params.topology),
+            None => (None, CollectionTopology::default()),
+        };
+        let vector = match vectors {
+            Some(VectorsConfig::Single(params)) => Some(params),
+            Some(VectorsConfig::Named(named)) => named.into_values().next(),
+            None => None,
+        };
+        Self {
+            info: result.info,
+            vector_size: vector.as_ref().map(|params| params.size),
+            distance: vector.as_ref().and_then(|params| params.distance.clone()),
+            vector_datatype: vector.and_then(|params| params.datatype),
+            payload_indexes: result
+                .payload_schema
+                .into_iter()
+                .map(|(field, index)| (field, index.data_type.unwrap_or_default()))
+                .collect(),
+            topology,
+        }
+    }
+}
+
 #[derive(Deserialize)]
 pub(crate) struct ListCollectionsResponse {
     pub(crate) result: ListCollectionsResult,
@@ -101,6 +484,7 @@ pub(crate) struct ListCollectionsResponse {
 
 #[derive(Deserialize)]
 pub(crate) struct ListCollectionsResult {
+    #[serde(default, deserialize_with = "null_as_default")]
     pub(crate) collections: Vec<CollectionDescription>,
 }
 
@@ -109,26 +493,67 @@ pub(crate) struct CollectionDescription {
     pub(crate) name: String,
 }
 
+#[derive(Deserialize)]
+pub(crate) struct ListAliasesResponse {
+    pub(crate) result: ListAliasesResult,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ListAliasesResult {
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub(crate) aliases: Vec<AliasDescription>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct AliasDescription {
+    pub(crate) alias_name: String,
+    pub(crate) collection_name: String,
+}
+
 #[derive(Deserialize)]
 pub(crate) struct QueryResponse {
     pub(crate) result: QueryResponseResult,
 }
 
+/// Query results in any envelope Qdrant has used: a bare array (`points/search` and early
+/// `points/query`), `{ points }` (1.10+), or `{ groups: [{ id, hits }] }` (`points/query/groups`).
 #[derive(Deserialize)]
 #[serde(untagged)]
 pub(crate) enum QueryResponseResult {
     Points(Vec<QueryPoint>),
+    // Listed before `Object`, whose fields all default and would match a grouped body too.
+    Groups {
+        groups: Vec<QueryGroup>,
+    },
     Object {
-        #[serde(default)]
+        #[serde(default, deserialize_with = "null_as_default")]
         points: Vec<QueryPoint>,
         #[serde(default)]
         _count: Option<usize>,
     },
 }
 
+impl QueryResponseResult {
+    /// Matched points in response order; grouped hits are flattened group by group.
+    pub(crate) fn into_points(self) -> Vec<QueryPoint> {
+        match self {
+            Self::Points(points) | Self::Object { points, .. } => points,
+            Self::Groups { groups } => groups.into_iter().flat_map(|group| group.hits).collect(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub(crate) struct QueryGroup {
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub(crate) hits: Vec<QueryPoint>,
+}
+
 #[derive(Deserialize)]
 pub(crate) struct QueryPoint {
     pub(crate) id: Value,
+    // Ordered queries may omit the similarity score.
+    #[serde(default)]
     pub(crate) score: f32,
     #[serde(default)]
     pub(crate) payload: Option<Map<String, Value>>,
@@ -136,21 +561,199 @@ pub(crate) struct QueryPoint {
 
 #[derive(Deserialize)]
 pub(crate) struct ScrollResponse {
+    // A body without `result` reads as an empty final page rather than failing the scroll.
+    #[serde(default)]
     pub(crate) result: ScrollResult,
 }
 
-#[derive(Deserialize)]
+#[derive(Default, Deserialize)]
 pub(crate) struct ScrollResult {
-    #[serde(default)]
+    #[serde(default, deserialize_with = "null_as_default")]
     pub(crate) points: Vec<ScrollPoint>,
     #[serde(default)]
     pub(crate) next_page_offset: Option<Value>,
 }
 
+#[derive(Deserialize)]
+pub(crate) struct RetrieveResponse {
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub(crate) result: Vec<ScrollPoint>,
+}
+
 #[derive(Deserialize)]
 pub(crate) struct ScrollPoint {
     #[serde(default)]
     pub(crate) id: Option<Value>,
     #[serde(default)]
     pub(crate) payload: Option<Map<String, Value>>,
+    #[serde(default)]
+    pub(crate) vector: Option<Value>,
+}
+
+/// Deserialize `null` as `T::default()`; pair with `#[serde(default)]` to cover absent fields.
+fn null_as_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::de::DeserializeOwned;
+    use serde_json::json;
+
+    /// Captured Qdrant response bodies, named after the release or shape they came from.
+    macro_rules! fixture {
+        ($name:literal) => {
+            ($name, include_str!(concat!("fixtures/", $name, ".json")))
+        };
+    }
+
+    fn parse<T: DeserializeOwned>((name, body): (&str, &str)) -> T {
+        serde_json::from_str(body).unwrap_or_else(|error| panic!("{name}: {error}"))
+    }
+
+    fn ids(points: &[QueryPoint]) -> Vec<Value> {
+        points.iter().map(|point| point.id.clone()).collect()
+    }
+
+    #[test]
+    fn collection_responses_parse_from_1_7_through_latest() {
+        let CollectionSchemaResponse { result } = parse(fixture!("collection_v1_7"));
+        let schema = CollectionSchema::from(result);
+        assert_eq!(schema.vector_size, Some(768));
+        assert_eq!(schema.distance.as_deref(), Some("Cosine"));
+        assert_eq!(schema.vector_datatype, None);
+        assert_eq!(schema.info.points_count, Some(1200));
+        assert_eq!(schema.info.segments_count, Some(4));
+        assert_eq!(schema.topology.shard_number, Some(1));
+        assert_eq!(
+            schema.payload_indexes.keys().collect::<Vec<_>>(),
+            ["project_id", "timestamp"]
+        );
+
+        // Named vectors, sparse vectors, quantization, strict mode, and top-level `usage`.
+        let CollectionSchemaResponse { result } = parse(fixture!("collection_v1_13"));
+        let schema = CollectionSchema::from(result);
+        assert_eq!(schema.vector_size, Some(384));
+        assert_eq!(schema.distance.as_deref(), Some("Dot"));
+        assert_eq!(schema.vector_datatype.as_deref(), Some("float16"));
+        assert_eq!(schema.info.status.as_deref(), Some("yellow"));
+        assert_eq!(schema.topology.shard_number, Some(2));
+        assert_eq!(schema.topology.replication_factor, Some(3));
+        assert_eq!(
+            schema
+                .payload_indexes
+                .get("chunk_index")
+                .map(String::as_str),
+            Some("integer")
+        );
+
+        let CollectionInfoResponse { result } = parse(fixture!("collection_v1_13"));
+        assert_eq!(result.unindexed_backlog(), 52);
+
+        let CollectionSchemaResponse { result } = parse(fixture!("collection_null_schema"));
+        let schema = CollectionSchema::from(result);
+        assert_eq!(schema.vector_size, None);
+        assert_eq!(schema.info.points_count, None);
+        assert!(schema.payload_indexes.is_empty());
+    }
+
+    #[test]
+    fn scroll_responses_parse_every_id_shape_and_missing_points() {
+        let ScrollResponse { result } = parse(fixture!("scroll_v1_7"));
+        assert_eq!(result.points.len(), 2);
+        assert_eq!(result.points[0].id, Some(json!(1)));
+        assert_eq!(result.next_page_offset, Some(json!(3)));
+
+        let ScrollResponse { result } = parse(fixture!("scroll_uuid_ids"));
+        let ids: Vec<_> = result.points.iter().map(|point| point.id.clone()).collect();
+        assert_eq!(
+            ids,
+            [
+                Some(json!("5c56c793-69f3-4fbf-87e6-c4bf54c28c26")),
+                Some(json!("b0d1e3f2-0c3e-4a6e-9a4f-2c1d7e8f9a0b")),
+                Some(json!({ "uuid": "0f6a6b1c-4b0c-4f0e-9a7e-1f2e3d4c5b6a" })),
+            ]
+        );
+        assert!(result.points[1].payload.is_none());
+        assert!(result.points[2].payload.is_none());
+        assert_eq!(result.points[2].vector, Some(json!([0.1, 0.2])));
+        assert_eq!(
+            result.next_page_offset,
+            Some(json!("d2c1a0b9-0000-4000-8000-000000000000"))
+        );
+
+        for empty in [
+            fixture!("scroll_missing_points"),
+            fixture!("scroll_null_points"),
+            fixture!("scroll_missing_result"),
+        ] {
+            let name = empty.0;
+            let ScrollResponse { result } = parse(empty);
+            assert!(result.points.is_empty(), "{name}");
+            assert_eq!(result.next_page_offset, None, "{name}");
+        }
+    }
+
+    #[test]
+    fn query_responses_parse_array_object_and_grouped_envelopes() {
+        let QueryResponse { result } = parse(fixture!("search_array"));
+        let points = result.into_points();
+        assert_eq!(
+            ids(&points),
+            [json!(10), json!("3b0e5a9e-2f7d-4a43-9c55-0d3f5f1e6d21")]
+        );
+        assert_eq!(points[0].score, 0.91);
+        assert!(points[1].payload.is_none());
+
+        let QueryResponse { result } = parse(fixture!("query_object"));
+        let points = result.into_points();
+        assert_eq!(points.len(), 2);
+        assert_eq!(
+            points[1].id,
+            json!({ "uuid": "3b0e5a9e-2f7d-4a43-9c55-0d3f5f1e6d21" })
+        );
+        // Ordered queries omit the score.
+        assert_eq!(points[1].score, 0.0);
+
+        let QueryResponse { result } = parse(fixture!("query_null_points"));
+        assert!(result.into_points().is_empty());
+
+        let QueryResponse { result } = parse(fixture!("query_groups"));
+        assert!(matches!(result, QueryResponseResult::Groups { .. }));
+        assert_eq!(
+            ids(&result.into_points()),
+            [
+                json!(1),
+                json!(2),
+                json!("9e1f7c1a-7d0a-4c6f-b7a4-3a9d0f1e2b3c")
+            ]
+        );
+    }
+
+    #[test]
+    fn listing_count_and_retrieve_responses_tolerate_extra_and_null_fields() {
+        let RetrieveResponse { result } = parse(fixture!("retrieve"));
+        assert_eq!(result.len(), 3);
+        assert!(result[0].payload.is_some());
+        assert!(result[1].payload.is_none() && result[2].payload.is_none());
+
+        let CountResponse { result } = parse(fixture!("count"));
+        assert_eq!(result.count, 42);
+
+        let ListCollectionsResponse { result } = parse(fixture!("collections_list"));
+        let names: Vec<_> = result.collections.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["memories", "archive"]);
+
+        let ListAliasesResponse { result } = parse(fixture!("aliases_list"));
+        assert_eq!(result.aliases[0].alias_name, "memories");
+        assert_eq!(result.aliases[0].collection_name, "memories_v2");
+
+        let ListAliasesResponse { result } = parse(fixture!("aliases_null"));
+        assert!(result.aliases.is_empty());
+    }
 }
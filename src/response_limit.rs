@@ -0,0 +1,111 @@
+//! Size guard for list-shaped responses (`MAX_RESPONSE_BYTES`).
+//!
+//! A response is rendered from its items and measured as serialized JSON before it is emitted.
+//! When it is over the cap, the longest prefix of the items that fits is kept instead; items are
+//! ranked, so the lowest-ranked ones are dropped first. The renderer is told how many items were
+//! dropped so it can add the `truncated` markers, and those markers count toward the size.
+
+use serde::Serialize;
+use serde_json::{Value, json};
+
+/// Render `items`, keeping only the longest prefix whose serialized rendering fits `max_bytes`.
+///
+/// `render` receives the kept items and the number dropped. Without a cap, or when every item
+/// fits, it runs once. Otherwise the prefix length is binary searched; when not even an empty
+/// list fits, the empty rendering is returned.
+pub fn fit_response<T, R: Serialize>(
+    items: &[T],
+    max_bytes: Option<usize>,
+    mut render: impl FnMut(&[T], usize) -> R,
+) -> R {
+    let full = render(items, 0);
+    let Some(max_bytes) = max_bytes else {
+        return full;
+    };
+    let fits = |response: &R| serialized_len(response) <= max_bytes;
+    if fits(&full) {
+        return full;
+    }
+
+    let total = items.len();
+    // Invariant: every prefix shorter than `low` that was tried fits; `high` items never fit.
+    let (mut low, mut high) = (0, total);
+    let mut best = None;
+    while low < high {
+        let kept = low + (high - low) / 2;
+        let response = render(&items[..kept], total - kept);
+        if fits(&response) {
+            best = Some(response);
+            low = kept + 1;
+        } else {
+            high = kept;
+        }
+    }
+    let kept = low.saturating_sub(1);
+    tracing::debug!(
+        kept,
+        dropped = total - kept,
+        max_bytes,
+        "Truncated oversized response"
+    );
+    best.unwrap_or_else(|| render(&[], total))
+}
+
+/// Mark `payload` as truncated by `dropped` items; untouched when nothing was dropped.
+pub fn mark_truncated(payload: &mut Value, dropped: usize) {
+    if dropped > 0
+        && let Some(map) = payload.as_object_mut()
+    {
+        map.insert("truncated".into(), json!(true));
+        map.insert("dropped_count".into(), json!(dropped));
+    }
+}
+
+fn serialized_len<R: Serialize>(response: &R) -> usize {
+    serde_json::to_vec(response).map_or(usize::MAX, |bytes| bytes.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(items: &[String], dropped: usize) -> Value {
+        let mut payload = json!({ "results": items });
+        mark_truncated(&mut payload, dropped);
+        payload
+    }
+
+    #[test]
+    fn oversized_responses_keep_the_longest_fitting_prefix_and_flag_it() {
+        let items: Vec<String> = (0..50)
+            .map(|index| format!("{index:02}{}", "x".repeat(98)))
+            .collect();
+        let max_bytes = 1_000;
+
+        let payload = fit_response(&items, Some(max_bytes), render);
+
+        let kept = payload["results"].as_array().expect("results").len();
+        assert!(kept > 0 && kept < items.len());
+        assert!(serde_json::to_vec(&payload).expect("json").len() <= max_bytes);
+        assert_eq!(payload["truncated"], json!(true));
+        assert_eq!(payload["dropped_count"], json!(items.len() - kept));
+        assert_eq!(payload["results"][0], json!(items[0]));
+
+        let one_more = render(&items[..kept + 1], items.len() - kept - 1);
+        assert!(serde_json::to_vec(&one_more).expect("json").len() > max_bytes);
+    }
+
+    #[test]
+    fn responses_within_the_cap_or_without_one_are_untouched() {
+        let items = vec!["a".to_string(), "b".to_string()];
+
+        for max_bytes in [None, Some(1_000)] {
+            let payload = fit_response(&items, max_bytes, render);
+            assert_eq!(payload, json!({ "results": ["a", "b"] }));
+        }
+
+        let payload = fit_response(&items, Some(1), render);
+        assert_eq!(payload["results"], json!([]));
+        assert_eq!(payload["dropped_count"], json!(2));
+    }
+}
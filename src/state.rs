@@ -0,0 +1,306 @@
+//! Persistent ingest cursors for incremental source syncing.
+//!
+//! When `STATE_DIR` is set, every successful `push` that carries a `source_uri` records the
+//! content hash and time it was indexed, keyed by project and source. A later push of the same
+//! source with identical content is skipped before chunking or embedding, so periodically
+//! re-syncing a documentation folder only pays for files that changed. The `sync-state` MCP tool
+//! lists and resets these cursors.
+//!
+//! The state is a cache: a missing, unreadable, or corrupt file degrades to an empty store (with
+//! a warning) so the next sync simply re-indexes everything.
+
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use time::{OffsetDateTime, format_description::well_known::Rfc3339};
+
+/// File written inside `STATE_DIR`.
+pub const SYNC_STATE_FILE: &str = "sync-state.json";
+
+/// Last indexed state of one source within a project.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncEntry {
+    /// Project the source was pushed under.
+    pub project_id: String,
+    /// Source identifier supplied with the push.
+    pub source_uri: String,
+    /// Collection the source was indexed into.
+    pub collection: String,
+    /// SHA-256 of the pushed text.
+    pub content_hash: String,
+    /// RFC3339 UTC time the source was last indexed.
+    pub indexed_at: String,
+    /// Chunks written for the source; 0 for cursors recorded before this was tracked.
+    #[serde(default)]
+    pub chunk_count: usize,
+}
+
+impl SyncEntry {
+    /// Describe a source that was just indexed.
+    pub fn new(
+        project_id: &str,
+        source_uri: &str,
+        collection: &str,
+        content_hash: String,
+        chunk_count: usize,
+    ) -> Self {
+        Self {
+            project_id: project_id.to_string(),
+            source_uri: source_uri.to_string(),
+            collection: collection.to_string(),
+            content_hash,
+            indexed_at: OffsetDateTime::now_utc()
+                .format(&Rfc3339)
+                .unwrap_or_default(),
+            chunk_count,
+        }
+    }
+
+    /// Whether pushing `content_hash` into `collection` would repeat this entry.
+    pub fn is_unchanged(&self, collection: &str, content_hash: &str) -> bool {
+        self.collection == collection && self.content_hash == content_hash
+    }
+}
+
+/// Hash identifying the content of a pushed source.
+pub fn content_hash(text: &str) -> String {
+    hex::encode(Sha256::digest(text.as_bytes()))
+}
+
+/// Storage for per-project ingest cursors.
+///
+/// Implementations never fail the caller: persistence problems are reported through `tracing`
+/// and the in-memory view stays authoritative for the rest of the process.
+pub trait SyncStateStore: Send + Sync {
+    /// Cursor recorded for `source_uri` within `project_id`, if any.
+    fn get(&self, project_id: &str, source_uri: &str) -> Option<SyncEntry>;
+
+    /// Insert or replace the cursor for the entry's project and source.
+    fn record(&self, entry: SyncEntry);
+
+    /// Cursors ordered by project then source, optionally limited to one project.
+    fn list(&self, project_id: Option<&str>) -> Vec<SyncEntry>;
+
+    /// Remove matching cursors and return how many were removed.
+    ///
+    /// Each filter left as `None` matches every value, so `reset(None, None)` clears the store.
+    fn reset(&self, project_id: Option<&str>, source_uri: Option<&str>) -> usize;
+}
+
+type EntryKey = (String, String);
+
+/// Volatile store used in tests and as the working copy of [`FileSyncState`].
+#[derive(Default)]
+pub struct InMemorySyncState {
+    entries: Mutex<BTreeMap<EntryKey, SyncEntry>>,
+}
+
+impl InMemorySyncState {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn from_entries(entries: Vec<SyncEntry>) -> Self {
+        let entries = entries
+            .into_iter()
+            .map(|entry| ((entry.project_id.clone(), entry.source_uri.clone()), entry))
+            .collect();
+        Self {
+            entries: Mutex::new(entries),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<EntryKey, SyncEntry>> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl SyncStateStore for InMemorySyncState {
+    fn get(&self, project_id: &str, source_uri: &str) -> Option<SyncEntry> {
+        self.lock()
+            .get(&(project_id.to_string(), source_uri.to_string()))
+            .cloned()
+    }
+
+    fn record(&self, entry: SyncEntry) {
+        self.lock()
+            .insert((entry.project_id.clone(), entry.source_uri.clone()), entry);
+    }
+
+    fn list(&self, project_id: Option<&str>) -> Vec<SyncEntry> {
+        self.lock()
+            .values()
+            .filter(|entry| project_id.is_none_or(|project| entry.project_id == project))
+            .cloned()
+            .collect()
+    }
+
+    fn reset(&self, project_id: Option<&str>, source_uri: Option<&str>) -> usize {
+        let mut entries = self.lock();
+        let before = entries.len();
+        entries.retain(|(project, source), _| {
+            !(project_id.is_none_or(|filter| project == filter)
+                && source_uri.is_none_or(|filter| source == filter))
+        });
+        before - entries.len()
+    }
+}
+
+/// Store persisted as a JSON array in `STATE_DIR/sync-state.json`.
+///
+/// The whole file is rewritten through a temporary sibling after every change, so a crash leaves
+/// either the previous or the new state on disk.
+pub struct FileSyncState {
+    path: PathBuf,
+    inner: InMemorySyncState,
+}
+
+impl FileSyncState {
+    /// Load the store kept in `dir`, creating the directory when missing.
+    ///
+    /// A corrupt state file is logged and ignored; it is overwritten on the next change.
+    pub fn open(dir: &Path) -> io::Result<Self> {
+        std::fs::create_dir_all(dir)?;
+        let path = dir.join(SYNC_STATE_FILE);
+        let entries = match std::fs::read(&path) {
+            Ok(bytes) => match serde_json::from_slice::<Vec<SyncEntry>>(&bytes) {
+                Ok(entries) => entries,
+                Err(error) => {
+                    tracing::warn!(
+                        path = %path.display(),
+                        error = %error,
+                        "Sync state is unreadable; sources will be re-indexed"
+                    );
+                    Vec::new()
+                }
+            },
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(error) => {
+                tracing::warn!(
+                    path = %path.display(),
+                    error = %error,
+                    "Failed to read sync state; sources will be re-indexed"
+                );
+                Vec::new()
+            }
+        };
+        Ok(Self {
+            path,
+            inner: InMemorySyncState::from_entries(entries),
+        })
+    }
+
+    fn persist(&self) {
+        let entries = self.inner.list(None);
+        let temp = self.path.with_extension("json.tmp");
+        let result = serde_json::to_vec_pretty(&entries)
+            .map_err(io::Error::other)
+            .and_then(|bytes| std::fs::write(&temp, bytes))
+            .and_then(|()| std::fs::rename(&temp, &self.path));
+        if let Err(error) = result {
+            tracing::warn!(
+                path = %self.path.display(),
+                error = %error,
+                "Failed to persist sync state"
+            );
+        }
+    }
+}
+
+impl SyncStateStore for FileSyncState {
+    fn get(&self, project_id: &str, source_uri: &str) -> Option<SyncEntry> {
+        self.inner.get(project_id, source_uri)
+    }
+
+    fn record(&self, entry: SyncEntry) {
+        self.inner.record(entry);
+        self.persist();
+    }
+
+    fn list(&self, project_id: Option<&str>) -> Vec<SyncEntry> {
+        self.inner.list(project_id)
+    }
+
+    fn reset(&self, project_id: Option<&str>, source_uri: Option<&str>) -> usize {
+        let removed = self.inner.reset(project_id, source_uri);
+        if removed > 0 {
+            self.persist();
+        }
+        removed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(project: &str, source: &str, text: &str) -> SyncEntry {
+        SyncEntry::new(project, source, "docs", content_hash(text), 1)
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("rusty-mem-state-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn in_memory_store_lists_and_resets_by_filter() {
+        let store = InMemorySyncState::new();
+        store.record(entry("alpha", "docs/a.md", "one"));
+        store.record(entry("alpha", "docs/b.md", "two"));
+        store.record(entry("beta", "docs/a.md", "three"));
+        store.record(entry("alpha", "docs/a.md", "one, edited"));
+
+        let hash = store.get("alpha", "docs/a.md").expect("entry").content_hash;
+        assert_eq!(hash, content_hash("one, edited"));
+        assert_eq!(store.list(None).len(), 3);
+        assert_eq!(store.list(Some("alpha")).len(), 2);
+
+        assert_eq!(store.reset(None, Some("docs/a.md")), 2);
+        assert_eq!(store.list(None).len(), 1);
+        assert_eq!(store.reset(None, None), 1);
+        assert!(store.list(None).is_empty());
+    }
+
+    #[test]
+    fn file_store_survives_reopen() {
+        let dir = temp_dir("reopen");
+        let store = FileSyncState::open(&dir).expect("open");
+        store.record(entry("alpha", "docs/a.md", "one"));
+        store.record(entry("alpha", "docs/b.md", "two"));
+        assert_eq!(store.reset(Some("alpha"), Some("docs/b.md")), 1);
+
+        let reopened = FileSyncState::open(&dir).expect("reopen");
+        let _ = std::fs::remove_dir_all(&dir);
+        let sources: Vec<String> = reopened
+            .list(None)
+            .into_iter()
+            .map(|entry| entry.source_uri)
+            .collect();
+        assert_eq!(sources, ["docs/a.md"]);
+    }
+
+    #[test]
+    fn corrupt_state_file_degrades_to_empty_store() {
+        let dir = temp_dir("corrupt");
+        std::fs::create_dir_all(&dir).expect("dir");
+        std::fs::write(dir.join(SYNC_STATE_FILE), b"{ not json").expect("write");
+
+        let store = FileSyncState::open(&dir).expect("open");
+        assert!(store.list(None).is_empty());
+        store.record(entry("alpha", "docs/a.md", "one"));
+
+        let reopened = FileSyncState::open(&dir).expect("reopen");
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(reopened.list(None).len(), 1);
+    }
+}
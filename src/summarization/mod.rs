@@ -2,16 +2,24 @@
 //!
 //! The summarization pipeline is optional; when no provider is configured the processing layer
 //! falls back to deterministic extractive summaries. The Ollama-backed client mirrors the
-//! embedding adapter by issuing HTTP requests directly to the runtime.
+//! embedding adapter by issuing HTTP requests directly to the runtime. Transient failures
+//! (connection errors, 5xx, 429) are retried with exponential backoff up to
+//! `SUMMARIZATION_MAX_RETRIES` times; generation runs at a low temperature, so a retried request
+//! yields an equivalent summary. Each attempt gives up after `SUMMARIZATION_TIMEOUT_MS`, which
+//! surfaces as [`SummarizationClientError::ProviderUnavailable`] and counts as transient.
 
 use crate::config::{SummarizationProvider, get_config};
+use crate::logging::TracedSend;
 use async_trait::async_trait;
 use reqwest::{Client, StatusCode};
 use serde::Deserialize;
-use serde_json::json;
+use serde_json::{Value, json};
+use std::time::Duration;
 use thiserror::Error;
 
 const DEFAULT_OLLAMA_URL: &str = "http://127.0.0.1:11434";
+/// Delay before the first retry; each further retry doubles it.
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(250);
 
 /// Errors surfaced while attempting abstractive summarization.
 #[derive(Debug, Error)]
@@ -22,6 +30,9 @@ pub enum SummarizationClientError {
     /// Provider returned an error response.
     #[error("Failed to generate summary: {0}")]
     GenerationFailed(String),
+    /// Prompt was longer than the model context window.
+    #[error("Prompt exceeds the model context: {0}")]
+    ContextExceeded(String),
     /// Provider response could not be parsed.
     #[error("Malformed provider response: {0}")]
     InvalidResponse(String),
@@ -58,28 +69,155 @@ pub fn get_summarization_client() -> Option<Box<dyn SummarizationClient + Send +
                 .ollama_url
                 .clone()
                 .unwrap_or_else(|| DEFAULT_OLLAMA_URL.to_string());
-            Some(Box::new(OllamaSummarizationClient::new(base_url)))
+            Some(ollama_summarization_client(
+                base_url,
+                config.summarization_max_retries,
+                (config.summarization_timeout_ms > 0)
+                    .then(|| Duration::from_millis(config.summarization_timeout_ms)),
+            ))
         }
     }
 }
 
+/// Build an Ollama-backed summarization client for `base_url` that retries transient failures
+/// up to `max_retries` times and abandons an attempt after `timeout`.
+pub(crate) fn ollama_summarization_client(
+    base_url: String,
+    max_retries: usize,
+    timeout: Option<Duration>,
+) -> Box<dyn SummarizationClient + Send + Sync> {
+    Box::new(OllamaSummarizationClient::new(
+        base_url,
+        max_retries,
+        timeout,
+    ))
+}
+
+/// Whether an error response reports a prompt longer than the model context.
+///
+/// Ollama words this as `exceeded max context length`; other 400s mentioning context or tokens
+/// are treated the same so callers can retry with a shorter prompt.
+fn is_context_error(status: StatusCode, body: &str) -> bool {
+    let body = body.to_lowercase();
+    body.contains("context length")
+        || body.contains("context window")
+        || (status == StatusCode::BAD_REQUEST
+            && (body.contains("context") || body.contains("token")))
+}
+
+/// Whether a failed response is worth retrying unchanged.
+///
+/// Server errors and rate limiting are usually transient. A 5xx reporting an oversized prompt is
+/// not: the same prompt fails again, and the caller shrinks it instead.
+fn is_retryable_status(status: StatusCode, body: &str) -> bool {
+    (status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS)
+        && !is_context_error(status, body)
+}
+
 struct OllamaSummarizationClient {
     http: Client,
     base_url: String,
+    max_retries: usize,
+    retry_delay: Duration,
+    timeout: Option<Duration>,
 }
 
 impl OllamaSummarizationClient {
-    fn new(base_url: String) -> Self {
-        let http = Client::builder()
-            .user_agent("rusty-mem/summary")
+    fn new(base_url: String, max_retries: usize, timeout: Option<Duration>) -> Self {
+        let mut http = Client::builder().user_agent("rusty-mem/summary");
+        if let Some(timeout) = timeout {
+            http = http.timeout(timeout);
+        }
+        let http = http
             .build()
             .expect("Failed to construct reqwest::Client for summarization");
-        Self { http, base_url }
+        Self {
+            http,
+            base_url,
+            max_retries,
+            retry_delay: RETRY_BASE_DELAY,
+            timeout,
+        }
     }
 
     fn endpoint(&self) -> String {
         format!("{}/api/generate", self.base_url.trim_end_matches('/'))
     }
+
+    /// Issue one generate request; errors carry whether retrying them may succeed.
+    async fn send_once(&self, payload: &Value) -> Result<String, (SummarizationClientError, bool)> {
+        let response = self
+            .http
+            .post(self.endpoint())
+            .json(payload)
+            .send_traced("ollama")
+            .await
+            .map_err(|error| {
+                let transient = error.is_connect() || error.is_timeout();
+                let message = match self.timeout {
+                    Some(timeout) if error.is_timeout() => format!(
+                        "Ollama at {} did not respond within {} ms (SUMMARIZATION_TIMEOUT_MS)",
+                        self.base_url,
+                        timeout.as_millis()
+                    ),
+                    _ => format!("failed to reach Ollama at {}: {error}", self.base_url),
+                };
+                (
+                    SummarizationClientError::ProviderUnavailable(message),
+                    transient,
+                )
+            })?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err((
+                SummarizationClientError::ProviderUnavailable(format!(
+                    "Ollama endpoint {} returned 404",
+                    self.endpoint()
+                )),
+                false,
+            ));
+        }
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            if is_context_error(status, &body) {
+                return Err((
+                    SummarizationClientError::ContextExceeded(format!(
+                        "Ollama returned {status}: {body}"
+                    )),
+                    false,
+                ));
+            }
+            let retryable = is_retryable_status(status, &body);
+            return Err((
+                SummarizationClientError::GenerationFailed(format!(
+                    "Ollama returned {status}: {body}"
+                )),
+                retryable,
+            ));
+        }
+
+        let body: OllamaResponse = response.json().await.map_err(|error| {
+            (
+                SummarizationClientError::InvalidResponse(format!(
+                    "failed to decode Ollama response: {error}"
+                )),
+                false,
+            )
+        })?;
+
+        if !body.done {
+            return Err((
+                SummarizationClientError::InvalidResponse(
+                    "Ollama response incomplete (streaming not supported)".into(),
+                ),
+                false,
+            ));
+        }
+
+        Ok(body.response.trim().to_string())
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -104,47 +242,24 @@ impl SummarizationClient for OllamaSummarizationClient {
             }
         });
 
-        let response = self
-            .http
-            .post(self.endpoint())
-            .json(&payload)
-            .send()
-            .await
-            .map_err(|error| {
-                SummarizationClientError::ProviderUnavailable(format!(
-                    "failed to reach Ollama at {}: {error}",
-                    self.base_url
-                ))
-            })?;
-
-        if response.status() == StatusCode::NOT_FOUND {
-            return Err(SummarizationClientError::ProviderUnavailable(format!(
-                "Ollama endpoint {} returned 404",
-                self.endpoint()
-            )));
-        }
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            return Err(SummarizationClientError::GenerationFailed(format!(
-                "Ollama returned {status}: {body}"
-            )));
-        }
-
-        let body: OllamaResponse = response.json().await.map_err(|error| {
-            SummarizationClientError::InvalidResponse(format!(
-                "failed to decode Ollama response: {error}"
-            ))
-        })?;
-
-        if !body.done {
-            return Err(SummarizationClientError::InvalidResponse(
-                "Ollama response incomplete (streaming not supported)".into(),
-            ));
+        let mut attempt = 0;
+        loop {
+            match self.send_once(&payload).await {
+                Err((error, true)) if attempt < self.max_retries => {
+                    let delay = self.retry_delay * 2u32.saturating_pow(attempt as u32);
+                    attempt += 1;
+                    tracing::warn!(
+                        attempt,
+                        max_retries = self.max_retries,
+                        delay_ms = delay.as_millis() as u64,
+                        %error,
+                        "Retrying summarization after transient failure"
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                result => return result.map_err(|(error, _)| error),
+            }
         }
-
-        Ok(body.response.trim().to_string())
     }
 }
 
@@ -162,6 +277,9 @@ mod tests {
                 .build()
                 .expect("client"),
             base_url: server.base_url(),
+            max_retries: 0,
+            retry_delay: Duration::ZERO,
+            timeout: None,
         };
 
         let mock = server
@@ -196,6 +314,9 @@ mod tests {
                 .build()
                 .expect("client"),
             base_url: server.base_url(),
+            max_retries: 0,
+            retry_delay: Duration::ZERO,
+            timeout: None,
         };
 
         server
@@ -216,4 +337,160 @@ mod tests {
 
         matches!(error, SummarizationClientError::GenerationFailed(message) if message.contains("500"));
     }
+
+    fn retrying_client(server: &MockServer, retry_delay: Duration) -> OllamaSummarizationClient {
+        OllamaSummarizationClient {
+            http: Client::builder()
+                .user_agent("rusty-mem-test")
+                .build()
+                .expect("client"),
+            base_url: server.base_url(),
+            max_retries: 2,
+            retry_delay,
+            timeout: None,
+        }
+    }
+
+    fn summary_request() -> SummarizationRequest {
+        SummarizationRequest {
+            model: "llama".into(),
+            prompt: "Summarize".into(),
+            max_words: 100,
+        }
+    }
+
+    #[tokio::test]
+    async fn ollama_client_retries_transient_server_errors() {
+        let server = MockServer::start_async().await;
+        let client = retrying_client(&server, Duration::from_millis(200));
+        let unavailable = server
+            .mock_async(|when, then| {
+                when.method(POST).path("/api/generate");
+                then.status(503).body("loading model");
+            })
+            .await;
+
+        // Swap the 503 for a success once the first attempt has failed, before the retry fires.
+        let recover = async {
+            while unavailable.hits_async().await == 0 {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+            unavailable.delete_async().await;
+            server
+                .mock_async(|when, then| {
+                    when.method(POST).path("/api/generate");
+                    then.status(200)
+                        .json_body(json!({ "response": "Recovered", "done": true }));
+                })
+                .await
+        };
+        let (summary, success) = tokio::join!(client.generate_summary(summary_request()), recover);
+
+        assert_eq!(summary.expect("summary after retry"), "Recovered");
+        success.assert_hits_async(1).await;
+    }
+
+    #[tokio::test]
+    async fn ollama_client_does_not_retry_missing_endpoint() {
+        let server = MockServer::start_async().await;
+        let client = retrying_client(&server, Duration::ZERO);
+        let missing = server
+            .mock_async(|when, then| {
+                when.method(POST).path("/api/generate");
+                then.status(404).body("not found");
+            })
+            .await;
+
+        let error = client
+            .generate_summary(summary_request())
+            .await
+            .expect_err("404 fails");
+
+        missing.assert_hits_async(1).await;
+        assert!(matches!(
+            error,
+            SummarizationClientError::ProviderUnavailable(message) if message.contains("404")
+        ));
+    }
+
+    #[tokio::test]
+    async fn ollama_client_gives_up_after_max_retries() {
+        let server = MockServer::start_async().await;
+        let client = retrying_client(&server, Duration::ZERO);
+        let failing = server
+            .mock_async(|when, then| {
+                when.method(POST).path("/api/generate");
+                then.status(500).body("boom");
+            })
+            .await;
+
+        let error = client
+            .generate_summary(summary_request())
+            .await
+            .expect_err("persistent 500");
+
+        failing.assert_hits_async(3).await;
+        assert!(matches!(
+            error,
+            SummarizationClientError::GenerationFailed(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn stalled_generation_times_out_as_provider_unavailable() {
+        let server = MockServer::start_async().await;
+        let stalled = server
+            .mock_async(|when, then| {
+                when.method(POST).path("/api/generate");
+                then.status(200)
+                    .delay(Duration::from_millis(500))
+                    .json_body(json!({ "response": "Too late", "done": true }));
+            })
+            .await;
+        let client =
+            OllamaSummarizationClient::new(server.base_url(), 0, Some(Duration::from_millis(50)));
+
+        let error = client
+            .generate_summary(summary_request())
+            .await
+            .expect_err("timed out");
+
+        stalled.assert_hits_async(1).await;
+        assert!(
+            matches!(&error, SummarizationClientError::ProviderUnavailable(message) if message.contains("did not respond within 50 ms")),
+            "unexpected error: {error}"
+        );
+    }
+
+    #[test]
+    fn only_transient_statuses_are_retryable() {
+        assert!(is_retryable_status(StatusCode::SERVICE_UNAVAILABLE, ""));
+        assert!(is_retryable_status(StatusCode::TOO_MANY_REQUESTS, ""));
+        assert!(!is_retryable_status(StatusCode::NOT_FOUND, ""));
+        assert!(!is_retryable_status(
+            StatusCode::BAD_REQUEST,
+            "unknown model"
+        ));
+        assert!(!is_retryable_status(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "exceeded max context length"
+        ));
+    }
+
+    #[test]
+    fn context_errors_are_recognised() {
+        assert!(is_context_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            r#"{"error":"prompt too long; exceeded max context length by 812 tokens"}"#
+        ));
+        assert!(is_context_error(
+            StatusCode::BAD_REQUEST,
+            "input exceeds the token limit"
+        ));
+        assert!(!is_context_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "token bucket empty"
+        ));
+        assert!(!is_context_error(StatusCode::BAD_REQUEST, "unknown model"));
+    }
 }
@@ -0,0 +1,249 @@
+#![cfg(feature = "http-client")]
+
+use std::sync::Arc;
+
+use axum::{
+    extract::Request,
+    http::{StatusCode, header},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+};
+use httpmock::{Method::GET, Method::POST, Method::PUT, Mock, MockServer};
+use regex::Regex;
+use rustymcp::{
+    api::{
+        create_router,
+        dto::{IndexRequest, SearchBody, SummarizeBody, SummarizeTimeRange},
+    },
+    client::{ClientError, RustyMemClient},
+    config,
+    processing::ProcessingService,
+};
+use serde_json::json;
+use tokio::{net::TcpListener, sync::OnceCell};
+
+const TOKEN: &str = "client-secret";
+
+static INIT: OnceCell<()> = OnceCell::const_new();
+static MOCK_SERVER: OnceCell<&'static MockServer> = OnceCell::const_new();
+static MOCK_HANDLES: OnceCell<Vec<Mock<'static>>> = OnceCell::const_new();
+
+fn set_env(key: &str, value: &str) {
+    // SAFETY: Tests run in a single process and establish deterministic configuration upfront.
+    unsafe { std::env::set_var(key, value) }
+}
+
+/// Start a mocked Qdrant and load the configuration pointing at it, once per test binary.
+async fn init_backend() {
+    INIT.get_or_init(|| async {
+        let server: &'static MockServer = Box::leak(Box::new(MockServer::start_async().await));
+        set_env("QDRANT_URL", &server.base_url());
+        set_env("QDRANT_COLLECTION_NAME", "rusty-mem");
+        set_env("EMBEDDING_PROVIDER", "openai");
+        set_env("EMBEDDING_MODEL", "nomic-embed-text:latest");
+        set_env("EMBEDDING_DIMENSION", "768");
+        set_env("TEXT_SPLITTER_CHUNK_SIZE", "64");
+        MOCK_SERVER.set(server).ok();
+
+        let collections = Regex::new(r"^/collections/").unwrap();
+        let mocks = vec![
+            server
+                .mock_async(|when, then| {
+                    when.method(GET).path("/collections");
+                    then.status(200).json_body(json!({
+                        "status": "ok",
+                        "result": { "collections": [{ "name": "rusty-mem" }] }
+                    }));
+                })
+                .await,
+            server
+                .mock_async(|when, then| {
+                    when.method(POST)
+                        .path_matches(collections.clone())
+                        .path_contains("/points/query");
+                    then.status(200).json_body(json!({
+                        "status": "ok",
+                        "result": [{
+                            "id": "memory-1",
+                            "score": 0.9,
+                            "payload": {
+                                "text": "Rotate keys quarterly",
+                                "project_id": "default",
+                                "memory_type": "semantic",
+                                "tags": ["security"]
+                            }
+                        }]
+                    }));
+                })
+                .await,
+            server
+                .mock_async(|when, then| {
+                    when.method(POST)
+                        .path_matches(collections.clone())
+                        .path_contains("/points/scroll")
+                        .body_contains("episodic");
+                    then.status(200).json_body(json!({
+                        "status": "ok",
+                        "result": {
+                            "points": [{
+                                "id": "ep-1",
+                                "payload": {
+                                    "text": "Rotated the staging keys",
+                                    "timestamp": "2025-01-02T09:00:00Z"
+                                }
+                            }],
+                            "next_page_offset": null
+                        }
+                    }));
+                })
+                .await,
+            server
+                .mock_async(|when, then| {
+                    when.method(POST)
+                        .path_matches(collections.clone())
+                        .path_contains("/points/scroll");
+                    then.status(200).json_body(json!({
+                        "status": "ok",
+                        "result": { "points": [], "next_page_offset": null }
+                    }));
+                })
+                .await,
+            server
+                .mock_async(|when, then| {
+                    when.method(GET).path_matches(collections.clone());
+                    then.status(200)
+                        .json_body(json!({ "status": "ok", "result": {} }));
+                })
+                .await,
+            server
+                .mock_async(|when, then| {
+                    when.method(PUT).path_matches(collections.clone());
+                    then.status(200).json_body(json!({
+                        "status": "ok",
+                        "result": { "operation_id": 1, "status": "completed" }
+                    }));
+                })
+                .await,
+        ];
+        MOCK_HANDLES.set(mocks).ok();
+        config::init_config();
+    })
+    .await;
+}
+
+/// Stand-in for an authenticating proxy in front of the server.
+async fn require_token(request: Request, next: Next) -> Response {
+    let expected = format!("Bearer {TOKEN}");
+    match request.headers().get(header::AUTHORIZATION) {
+        Some(value) if value.as_bytes() == expected.as_bytes() => next.run(request).await,
+        _ => (StatusCode::UNAUTHORIZED, "missing token").into_response(),
+    }
+}
+
+/// Serve the router on an ephemeral port and return its base URL.
+async fn spawn_server() -> String {
+    init_backend().await;
+    let processing = Arc::new(ProcessingService::new().await);
+    let app = create_router(processing).layer(middleware::from_fn(require_token));
+    let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+    let address = listener.local_addr().expect("local addr");
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.expect("serve");
+    });
+    format!("http://{address}/")
+}
+
+async fn client() -> RustyMemClient {
+    RustyMemClient::new(spawn_server().await).with_auth_token(TOKEN)
+}
+
+#[tokio::test]
+async fn push_and_metrics_round_trip() {
+    let client = client().await;
+
+    let indexed = client
+        .push(&IndexRequest {
+            text: "Rotate the staging keys every quarter.".into(),
+            collection: Some("client-test".into()),
+            tags: Some(vec!["security".into()]),
+            ..Default::default()
+        })
+        .await
+        .expect("push");
+    assert_eq!(indexed.chunks_indexed, 1);
+    assert_eq!(indexed.inserted, 1);
+    assert_eq!(indexed.ingest_profile.embedding_dimension, 768);
+    assert_eq!(indexed.ingest_profile.profile_hash.len(), 16);
+
+    let metrics = client.metrics().await.expect("metrics");
+    assert_eq!(metrics.documents_indexed, 1);
+    assert_eq!(metrics.chunks_indexed, 1);
+}
+
+#[tokio::test]
+async fn search_and_collections_decode_typed_responses() {
+    let client = client().await;
+
+    let response = client
+        .search(&SearchBody {
+            query_text: "key rotation".into(),
+            collection: Some("client-test".into()),
+            limit: Some(3),
+            ..Default::default()
+        })
+        .await
+        .expect("search");
+    assert_eq!(response.collection, "client-test");
+    assert_eq!(response.results.len(), 1);
+    assert_eq!(response.results[0].id, "memory-1");
+    assert_eq!(
+        response.results[0].tags.as_deref(),
+        Some(&["security".to_string()][..])
+    );
+
+    let collections = client.collections().await.expect("collections");
+    assert_eq!(collections, ["rusty-mem"]);
+}
+
+#[tokio::test]
+async fn summarize_stores_an_extractive_summary() {
+    let client = client().await;
+
+    let summary = client
+        .summarize(&SummarizeBody {
+            time_range: SummarizeTimeRange {
+                start: "2025-01-01".into(),
+                end: "2025-01-07".into(),
+            },
+            strategy: Some("extractive".into()),
+            collection: Some("client-test".into()),
+            ..Default::default()
+        })
+        .await
+        .expect("summarize");
+    assert_eq!(summary.strategy_used, "extractive");
+    assert_eq!(summary.source_memory_ids, ["ep-1"]);
+    assert_eq!(summary.items_summarized, 1);
+    assert!(summary.summary.contains("Rotated the staging keys"));
+}
+
+#[tokio::test]
+async fn errors_map_onto_client_error_variants() {
+    let base_url = spawn_server().await;
+    let client = RustyMemClient::new(base_url.clone()).with_auth_token(TOKEN);
+
+    let error = client
+        .search(&SearchBody {
+            query_text: "   ".into(),
+            ..Default::default()
+        })
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(error, ClientError::InvalidRequest(ref message) if message.contains("query_text"))
+    );
+    assert!(!error.is_retryable());
+
+    let error = RustyMemClient::new(base_url).metrics().await.unwrap_err();
+    assert!(matches!(error, ClientError::Status { status: 401, .. }));
+}
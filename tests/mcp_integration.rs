@@ -22,11 +22,17 @@ fn set_env(key: &str, value: &str) {
 }
 
 #[derive(Clone, Default)]
-struct DummyClientHandler;
+struct DummyClientHandler {
+    name: Option<&'static str>,
+}
 
 impl ClientHandler for DummyClientHandler {
     fn get_info(&self) -> ClientInfo {
-        ClientInfo::default()
+        let mut info = ClientInfo::default();
+        if let Some(name) = self.name {
+            info.client_info.name = name.to_string();
+        }
+        info
     }
 }
 
@@ -37,6 +43,14 @@ struct TestHarness {
 
 impl TestHarness {
     async fn new() -> Self {
+        Self::with_client(DummyClientHandler::default()).await
+    }
+
+    async fn for_client(name: &'static str) -> Self {
+        Self::with_client(DummyClientHandler { name: Some(name) }).await
+    }
+
+    async fn with_client(client_handler: DummyClientHandler) -> Self {
         eprintln!("[harness] init start");
         INIT.get_or_init(|| async {
             eprintln!("[harness:init] starting mock server");
@@ -52,6 +66,7 @@ impl TestHarness {
             set_env("EMBEDDING_DIMENSION", "768");
             set_env("TEXT_SPLITTER_CHUNK_SIZE", "4");
             set_env("OLLAMA_URL", "http://127.0.0.1:11434");
+            set_env("CLIENT_PROJECT_MAP", "cursor=frontend");
 
             MOCK_SERVER.set(mock_server).ok();
 
@@ -112,6 +127,37 @@ impl TestHarness {
                         }
                     })
                     .await,
+                server
+                    .mock_async({
+                        let collections_regex = collections_regex.clone();
+                        move |when, then| {
+                            when.method(POST)
+                                .path_matches(collections_regex.clone())
+                                .path_contains("/points/scroll")
+                                .body_contains(r#""direction":"desc""#);
+                            then.status(200).json_body(json!({
+                                "status": "ok",
+                                "time": 0.0,
+                                "result": {
+                                    "points": [
+                                        {
+                                            "id": "recent-1",
+                                            "payload": {
+                                                "text": "Shipped the   rollout checklist",
+                                                "project_id": "frontend",
+                                                "memory_type": "episodic",
+                                                "timestamp": "2025-02-01T00:00:00Z",
+                                                "tags": ["release"],
+                                                "source_uri": "notes/rollout.md"
+                                            }
+                                        }
+                                    ],
+                                    "next_page_offset": null
+                                }
+                            }));
+                        }
+                    })
+                    .await,
                 server
                     .mock_async({
                         let collections_regex = collections_regex.clone();
@@ -167,7 +213,6 @@ impl TestHarness {
         let server_transport = AsyncRwTransport::new_server(server_read, server_write);
 
         let server_info = server.get_info();
-        let client_handler = DummyClientHandler;
         let client_info = ClientHandler::get_info(&client_handler);
 
         let server =
@@ -268,6 +313,112 @@ async fn index_tool_invokes_processing() {
     harness.shutdown().await;
 }
 
+async fn call_json(
+    harness: &TestHarness,
+    name: &'static str,
+    arguments: serde_json::Value,
+) -> serde_json::Value {
+    let response = harness
+        .service
+        .call_tool(CallToolRequestParam {
+            name: name.into(),
+            arguments: Some(arguments.as_object().unwrap().clone()),
+        })
+        .await
+        .expect("tool call");
+    assert_eq!(response.is_error, Some(false));
+    response.structured_content.expect("structured payload")
+}
+
+#[tokio::test]
+async fn metrics_tool_reports_per_collection_counters() {
+    let harness = TestHarness::new().await;
+
+    for (text, collection) in [
+        ("Alpha memory one", "metrics-alpha"),
+        ("Alpha memory two", "metrics-alpha"),
+        ("Beta memory", "metrics-beta"),
+    ] {
+        let pushed = call_json(
+            &harness,
+            "push",
+            json!({ "text": text, "collection": collection }),
+        )
+        .await;
+        assert_eq!(pushed["collection"], collection);
+    }
+
+    let alpha = call_json(
+        &harness,
+        "metrics",
+        json!({ "collection": "metrics-alpha" }),
+    )
+    .await;
+    assert_eq!(alpha["collection"], "metrics-alpha");
+    assert_eq!(alpha["documentsIndexed"], 2);
+
+    let beta = call_json(&harness, "metrics", json!({ "collection": "metrics-beta" })).await;
+    assert_eq!(beta["documentsIndexed"], 1);
+
+    let unused = call_json(
+        &harness,
+        "metrics",
+        json!({ "collection": "metrics-gamma" }),
+    )
+    .await;
+    assert_eq!(unused["documentsIndexed"], 0);
+    assert!(unused["lastChunkSize"].is_null());
+
+    let global = call_json(&harness, "metrics", json!({})).await;
+    assert!(global.get("collection").is_none());
+    assert_eq!(global["documentsIndexed"], 3);
+    assert_eq!(
+        global["chunksIndexed"].as_u64().unwrap(),
+        alpha["chunksIndexed"].as_u64().unwrap() + beta["chunksIndexed"].as_u64().unwrap()
+    );
+
+    harness.shutdown().await;
+}
+
+async fn push_as(harness: &TestHarness) -> serde_json::Map<String, serde_json::Value> {
+    let response = harness
+        .service
+        .call_tool(CallToolRequestParam {
+            name: "push".into(),
+            arguments: Some(
+                json!({ "text": "Hello world" })
+                    .as_object()
+                    .unwrap()
+                    .clone(),
+            ),
+        })
+        .await
+        .expect("push tool call");
+    assert_eq!(response.is_error, Some(false));
+    response
+        .structured_content
+        .expect("structured payload")
+        .as_object()
+        .expect("object payload")
+        .clone()
+}
+
+#[tokio::test]
+async fn push_infers_tags_and_project_from_client_identity() {
+    let cursor = TestHarness::for_client("cursor").await;
+    let payload = push_as(&cursor).await;
+    assert_eq!(
+        payload["inferred"],
+        json!({ "projectId": "frontend", "tags": ["client:cursor"] })
+    );
+    cursor.shutdown().await;
+
+    let zed = TestHarness::for_client("Zed").await;
+    let payload = push_as(&zed).await;
+    assert_eq!(payload["inferred"], json!({ "tags": ["client:zed"] }));
+    zed.shutdown().await;
+}
+
 #[tokio::test]
 async fn search_tool_returns_results() {
     let harness = TestHarness::new().await;
@@ -326,3 +477,58 @@ async fn invalid_payload_returns_error() {
 
     harness.shutdown().await;
 }
+
+#[tokio::test]
+async fn recent_resource_lists_latest_project_memories() {
+    let harness = TestHarness::new().await;
+    let service = &harness.service;
+
+    let templates = service
+        .list_resource_templates(Some(PaginatedRequestParam { cursor: None }))
+        .await
+        .expect("list_resource_templates");
+    assert!(
+        templates
+            .resource_templates
+            .iter()
+            .any(|template| template.uri_template == "mcp://{project_id}/recent")
+    );
+
+    let result = service
+        .read_resource(model::ReadResourceRequestParam {
+            uri: "mcp://frontend/recent?limit=5".into(),
+        })
+        .await
+        .expect("read recent resource");
+    let model::ResourceContents::TextResourceContents { text, .. } = &result.contents[0] else {
+        panic!("expected text contents");
+    };
+    let payload: serde_json::Value = serde_json::from_str(text).expect("json payload");
+    assert_eq!(payload["project_id"], "frontend");
+    assert_eq!(payload["limit"], 5);
+    assert_eq!(
+        payload["memories"],
+        json!([{
+            "id": "recent-1",
+            "timestamp": "2025-02-01T00:00:00Z",
+            "preview": "Shipped the rollout checklist",
+            "memory_type": "episodic",
+            "tags": ["release"],
+            "source_uri": "notes/rollout.md"
+        }])
+    );
+
+    let error = service
+        .read_resource(model::ReadResourceRequestParam {
+            uri: "mcp://frontend/recent?limit=500".into(),
+        })
+        .await
+        .expect_err("limit above the cap");
+    assert!(matches!(
+        error,
+        rmcp::service::ServiceError::McpError(ref data)
+            if data.code == model::ErrorCode::INVALID_PARAMS
+    ));
+
+    harness.shutdown().await;
+}